@@ -1,8 +1,18 @@
 //! Error types for StratoUI framework
+//!
+//! Errors keep their underlying cause: wrap foreign errors with
+//! [`StratoError::wrap`] and add what was being attempted with
+//! [`ResultExt::with_context`]. [`StratoError::report`] prints the whole
+//! chain, with a recovery hint when one is known.
 
 use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt;
 use thiserror::Error;
 
+/// Boxed error kept as the source of another error
+pub type BoxedError = Box<dyn StdError + Send + Sync + 'static>;
+
 /// Context information for errors to aid in debugging
 #[derive(Debug, Clone)]
 pub struct ErrorContext {
@@ -64,49 +74,101 @@ impl ErrorContext {
     }
 }
 
+/// GPU adapter details attached to device initialization errors
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AdapterDetails {
+    /// Adapter name as reported by the driver
+    pub name: String,
+    /// Graphics backend (Vulkan, Metal, Dx12, Gl, ...)
+    pub backend: String,
+    /// Device type (discrete, integrated, CPU, ...)
+    pub device_type: String,
+    /// Driver name and version, when known
+    pub driver: String,
+}
+
+impl fmt::Display for AdapterDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' ({}, {}", self.name, self.backend, self.device_type)?;
+        if !self.driver.is_empty() {
+            write!(f, ", driver {}", self.driver)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Step of framework initialization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InitStep {
+    Core,
+    Widgets,
+    Platform,
+    TextRenderer,
+}
+
+impl InitStep {
+    /// Convert InitStep to string
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InitStep::Core => "core",
+            InitStep::Widgets => "widgets",
+            InitStep::Platform => "platform",
+            InitStep::TextRenderer => "text renderer",
+        }
+    }
+}
+
+impl fmt::Display for InitStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Main error type for StratoUI operations
+///
+/// Contexts and adapter details are boxed so results stay small.
 #[derive(Debug, Error)]
 pub enum StratoError {
     #[error("Platform error: {message}")]
     Platform {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Renderer error: {message}")]
     Renderer {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Widget error: {message}")]
     Widget {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("State management error: {message}")]
     State {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Layout calculation error: {message}")]
     Layout {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Initialization error: {message}")]
     Initialization {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Configuration error: {message}")]
     Configuration {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("IO error: {0}")]
@@ -115,19 +177,55 @@ pub enum StratoError {
     #[error("Not implemented: {message}")]
     NotImplemented {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Plugin error: {message}")]
     PluginError {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
     },
 
     #[error("Other error: {message}")]
     Other {
         message: String,
-        context: Option<ErrorContext>,
+        context: Option<Box<ErrorContext>>,
+    },
+
+    /// GPU adapter or device could not be set up
+    #[error("GPU device initialization failed: {message}{}", .adapter.as_ref().map(|a| format!(" on adapter {}", a)).or_else(|| .backend.as_ref().map(|b| format!(" (backend {})", b))).unwrap_or_default())]
+    DeviceInit {
+        message: String,
+        /// Requested backend, when no adapter was found
+        backend: Option<String>,
+        /// Adapter the failure happened on
+        adapter: Option<Box<AdapterDetails>>,
+        #[source]
+        source: Option<BoxedError>,
+    },
+
+    /// A step of framework initialization failed
+    #[error("Initialization failed at the {step} step")]
+    InitFailed {
+        step: InitStep,
+        #[source]
+        source: Box<StratoError>,
+    },
+
+    /// Describes what was being attempted when `source` occurred
+    #[error("{context}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<StratoError>,
+    },
+
+    /// An error from outside the framework
+    #[error("{message}")]
+    External {
+        message: String,
+        #[source]
+        source: BoxedError,
     },
 }
 
@@ -136,7 +234,7 @@ impl StratoError {
     pub fn platform_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Platform {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -144,7 +242,7 @@ impl StratoError {
     pub fn renderer_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Renderer {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -152,7 +250,7 @@ impl StratoError {
     pub fn widget_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Widget {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -160,7 +258,7 @@ impl StratoError {
     pub fn state_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::State {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -168,7 +266,7 @@ impl StratoError {
     pub fn layout_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Layout {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -176,7 +274,7 @@ impl StratoError {
     pub fn initialization_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Initialization {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -184,7 +282,7 @@ impl StratoError {
     pub fn configuration_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Configuration {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -192,7 +290,7 @@ impl StratoError {
     pub fn plugin_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::PluginError {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -200,7 +298,7 @@ impl StratoError {
     pub fn other_with_context<S: Into<String>>(msg: S, context: ErrorContext) -> Self {
         Self::Other {
             message: msg.into(),
-            context: Some(context),
+            context: Some(Box::new(context)),
         }
     }
 
@@ -284,6 +382,108 @@ impl StratoError {
         }
     }
 
+    /// Wrap a foreign error, keeping it as the source
+    pub fn wrap<S: Into<String>, E: Into<BoxedError>>(msg: S, source: E) -> Self {
+        Self::External {
+            message: msg.into(),
+            source: source.into(),
+        }
+    }
+
+    /// Create an error for a backend without a usable GPU adapter
+    pub fn adapter_unavailable<S: Into<String>>(backend: S) -> Self {
+        Self::DeviceInit {
+            message: "no compatible GPU adapter found".to_string(),
+            backend: Some(backend.into()),
+            adapter: None,
+            source: None,
+        }
+    }
+
+    /// Create an error for a device that could not be created on an adapter
+    pub fn device_request_failed<E: Into<BoxedError>>(adapter: AdapterDetails, source: E) -> Self {
+        Self::DeviceInit {
+            message: "device request was rejected".to_string(),
+            backend: None,
+            adapter: Some(Box::new(adapter)),
+            source: Some(source.into()),
+        }
+    }
+
+    /// Record the initialization step an error happened in
+    pub fn init_failed(step: InitStep, source: StratoError) -> Self {
+        Self::InitFailed {
+            step,
+            source: Box::new(source),
+        }
+    }
+
+    /// Describe what was being attempted when this error occurred
+    pub fn with_context<S: Into<String>>(self, context: S) -> Self {
+        Self::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// This error followed by its sources, outermost first
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn StdError + 'static)> {
+        std::iter::successors(
+            Some(self as &(dyn StdError + 'static)),
+            |&error: &&(dyn StdError + 'static)| error.source(),
+        )
+    }
+
+    /// The innermost source of this error
+    pub fn root_cause(&self) -> &(dyn StdError + 'static) {
+        self.chain().last().expect("chain starts with self")
+    }
+
+    /// The initialization step that failed, if this is an initialization error
+    pub fn failed_step(&self) -> Option<InitStep> {
+        match self {
+            Self::InitFailed { step, .. } => Some(*step),
+            Self::Context { source, .. } => source.failed_step(),
+            _ => None,
+        }
+    }
+
+    /// Suggestion for recovering from this error, if one is known
+    pub fn hint(&self) -> Option<&'static str> {
+        let own = match self {
+            Self::DeviceInit { adapter: None, .. } => Some(
+                "Make sure a GPU driver is installed, or allow more backends (Vulkan, Metal, \
+                 DX12, GL) when creating the renderer",
+            ),
+            Self::DeviceInit { .. } => Some(
+                "Update the GPU driver, or lower the required features and limits for this adapter",
+            ),
+            Self::InitFailed {
+                step: InitStep::TextRenderer,
+                ..
+            } => Some(
+                "Check the custom font directories in InitConfig, or keep skip_problematic_fonts enabled",
+            ),
+            Self::InitFailed {
+                step: InitStep::Platform,
+                ..
+            } => Some("Check that a display server or browser canvas is available"),
+            _ => None,
+        };
+
+        // Prefer the hint closest to the root cause
+        let inner = match self {
+            Self::InitFailed { source, .. } | Self::Context { source, .. } => source.hint(),
+            _ => None,
+        };
+        inner.or(own)
+    }
+
+    /// Display the full chain of causes and any recovery hint
+    pub fn report(&self) -> ErrorReport<'_> {
+        ErrorReport(self)
+    }
+
     /// Get the error context if available
     pub fn context(&self) -> Option<&ErrorContext> {
         match self {
@@ -296,14 +496,19 @@ impl StratoError {
             | Self::Configuration { context, .. }
             | Self::NotImplemented { context, .. }
             | Self::PluginError { context, .. }
-            | Self::Other { context, .. } => context.as_ref(),
-            Self::Io(_) => None,
+            | Self::Other { context, .. } => context.as_deref(),
+            Self::InitFailed { source, .. } | Self::Context { source, .. } => source.context(),
+            Self::Io(_) | Self::DeviceInit { .. } | Self::External { .. } => None,
         }
     }
 
     /// Format error with context for logging
     pub fn format_for_log(&self) -> String {
-        let base_msg = self.to_string();
+        let base_msg = self
+            .chain()
+            .map(|error| error.to_string())
+            .collect::<Vec<_>>()
+            .join(": ");
         if let Some(context) = self.context() {
             format!("{} [{}]", base_msg, context.format_for_log())
         } else {
@@ -312,12 +517,64 @@ impl StratoError {
     }
 }
 
+/// Multi-line rendering of an error chain, see [`StratoError::report`]
+///
+/// ```text
+/// Initialization failed at the platform step
+///
+/// Caused by:
+///     0: Loading window icon
+///     1: IO error: No such file or directory
+///
+/// Hint: ...
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorReport<'a>(&'a StratoError);
+
+impl fmt::Display for ErrorReport<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+
+        let mut causes = self.0.chain().skip(1).peekable();
+        if causes.peek().is_some() {
+            write!(f, "\n\nCaused by:")?;
+            for (index, cause) in causes.enumerate() {
+                write!(f, "\n    {}: {}", index, cause)?;
+            }
+        }
+
+        if let Some(hint) = self.0.hint() {
+            write!(f, "\n\nHint: {}", hint)?;
+        }
+        Ok(())
+    }
+}
+
 /// Result type alias for StratoUI operations
 pub type Result<T> = std::result::Result<T, StratoError>;
 
 /// Alternative result type alias for backward compatibility
 pub type StratoResult<T> = Result<T>;
 
+/// Context combinators for results
+pub trait ResultExt<T> {
+    /// Describe what was being attempted if the result is an error
+    fn with_context<S, F>(self, context: F) -> Result<T>
+    where
+        S: Into<String>,
+        F: FnOnce() -> S;
+}
+
+impl<T, E: Into<StratoError>> ResultExt<T> for std::result::Result<T, E> {
+    fn with_context<S, F>(self, context: F) -> Result<T>
+    where
+        S: Into<String>,
+        F: FnOnce() -> S,
+    {
+        self.map_err(|error| error.into().with_context(context()))
+    }
+}
+
 // =============================================================================
 // Taffy Layout Engine Error Types
 // =============================================================================
@@ -427,3 +684,58 @@ pub type TaffyRenderResult<T> = std::result::Result<T, TaffyRenderError>;
 
 /// Result type for Taffy validation operations.
 pub type TaffyValidationResult<T> = std::result::Result<T, TaffyValidationError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn load_fonts() -> Result<()> {
+        let missing = std::io::Error::new(std::io::ErrorKind::NotFound, "fonts.conf missing");
+        Err(missing).with_context(|| "Loading font configuration")
+    }
+
+    #[test]
+    fn test_context_chain_keeps_sources() {
+        let error = StratoError::init_failed(InitStep::TextRenderer, load_fonts().unwrap_err());
+
+        let messages: Vec<String> = error.chain().map(|e| e.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Initialization failed at the text renderer step",
+                "Loading font configuration",
+                "IO error: fonts.conf missing",
+                "fonts.conf missing",
+            ]
+        );
+        assert_eq!(error.root_cause().to_string(), "fonts.conf missing");
+        assert_eq!(
+            error.with_context("Starting app").failed_step(),
+            Some(InitStep::TextRenderer)
+        );
+    }
+
+    #[test]
+    fn test_report_prints_chain_and_hint() {
+        let adapter = AdapterDetails {
+            name: "Test GPU".to_string(),
+            backend: "Vulkan".to_string(),
+            device_type: "DiscreteGpu".to_string(),
+            driver: String::new(),
+        };
+        let error = StratoError::init_failed(
+            InitStep::Platform,
+            StratoError::device_request_failed(adapter, "limits exceeded"),
+        );
+
+        let report = error.report().to_string();
+        assert!(report.starts_with("Initialization failed at the platform step\n\nCaused by:"));
+        assert!(report.contains(
+            "0: GPU device initialization failed: device request was rejected on adapter \
+             'Test GPU' (Vulkan, DiscreteGpu)"
+        ));
+        assert!(report.contains("1: limits exceeded"));
+        // The device hint is closer to the cause than the platform one
+        assert!(report.ends_with("Hint: Update the GPU driver, or lower the required features and limits for this adapter"));
+    }
+}
@@ -130,7 +130,7 @@ pub enum KeyEvent {
 }
 
 /// Keyboard modifiers
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct Modifiers {
     pub shift: bool,
     pub control: bool,
@@ -139,8 +139,14 @@ pub struct Modifiers {
 }
 
 /// Mouse event data
+///
+/// Positions are in logical pixels, the same space widgets are laid out and
+/// drawn in. Platforms convert from the window's physical pixels by dividing
+/// by the scale factor before delivering events, so on a 2x display a click
+/// at physical (200, 200) arrives at (100, 100).
 #[derive(Debug, Clone)]
 pub struct MouseEvent {
+    /// Pointer position in logical pixels
     pub position: Vec2,
     pub button: Option<MouseButton>,
     pub modifiers: Modifiers,
@@ -159,22 +165,57 @@ pub struct KeyboardEvent {
 /// Window event data
 #[derive(Debug, Clone)]
 pub enum WindowEvent {
-    Resize { width: u32, height: u32 },
-    Move { x: i32, y: i32 },
+    /// The window surface was resized; the size is in physical pixels
+    Resize {
+        width: u32,
+        height: u32,
+    },
+    Move {
+        x: i32,
+        y: i32,
+    },
     Focus(bool),
     Close,
     Minimize,
     Maximize,
+    RendererRecovered {
+        device_recreated: bool,
+    },
 }
 
 /// Touch event data
 #[derive(Debug, Clone)]
 pub struct TouchEvent {
+    /// Finger identifier, unique among the fingers down at the same time
     pub id: u64,
     pub position: Vec2,
+    /// Pressure from 0 to 1, where the platform reports it
     pub force: Option<f32>,
 }
 
+/// Gesture recognized from several touches or reported by a touchpad
+///
+/// See [`crate::touch`] for how gestures are derived from touches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureEvent {
+    /// Fingers moved apart or together around `center`
+    ///
+    /// `scale` is the change since the last pinch event: above 1 zooms in,
+    /// below 1 zooms out.
+    Pinch { center: Vec2, scale: f32 },
+    /// Two fingers moved together by `delta` logical pixels
+    Pan { center: Vec2, delta: Vec2 },
+}
+
+impl GestureEvent {
+    /// Where the gesture happens, in logical pixels
+    pub fn center(&self) -> Vec2 {
+        match *self {
+            GestureEvent::Pinch { center, .. } | GestureEvent::Pan { center, .. } => center,
+        }
+    }
+}
+
 /// Main event type
 #[derive(Debug, Clone)]
 pub enum Event {
@@ -184,8 +225,15 @@ pub enum Event {
     MouseUp(MouseEvent),
     /// Mouse moved
     MouseMove(MouseEvent),
-    /// Mouse wheel scrolled
-    MouseWheel { delta: Vec2, modifiers: Modifiers },
+    /// Mouse wheel scrolled, with the delta in logical pixels
+    ///
+    /// `precise` is set for exact pixel deltas from devices such as
+    /// trackpads, and unset for wheel notches converted to pixels.
+    MouseWheel {
+        delta: Vec2,
+        modifiers: Modifiers,
+        precise: bool,
+    },
     /// Mouse entered widget
     MouseEnter,
     /// Mouse left widget
@@ -209,6 +257,8 @@ pub enum Event {
     TouchEnd(TouchEvent),
     /// Touch cancelled
     TouchCancel(TouchEvent),
+    /// Pinch or two-finger pan
+    Gesture(GestureEvent),
 
     /// Custom user event
     Custom(Arc<dyn Any + Send + Sync>),
@@ -0,0 +1,294 @@
+//! Hit testing against laid-out widget bounds
+//!
+//! Widgets register the rectangle they were laid out in while the tree is
+//! laid out for drawing. [`HitTest::widgets_at`] then walks the live widget
+//! tree and returns the widgets under a point, deepest first, so event
+//! routing, widgets and the inspector agree on what is under the pointer.
+//!
+//! Among overlapping siblings the one registered last (drawn on top) wins.
+//!
+//! The same bounds, read back with [`HitTest::layout_tree`], tell tests and
+//! tools where every widget ended up in the last frame.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Point, Rect};
+use crate::widget::WidgetId;
+
+#[derive(Debug, Clone, Copy)]
+struct HitEntry {
+    bounds: Rect,
+    /// Registration order; later entries are drawn above earlier ones
+    order: u64,
+}
+
+/// Registry of widget bounds used to find the widgets under a point
+#[derive(Debug, Default)]
+pub struct HitTest {
+    entries: RwLock<HashMap<WidgetId, HitEntry>>,
+    next_order: AtomicU64,
+}
+
+impl HitTest {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget all registered bounds, before laying out a new frame
+    pub fn begin_frame(&self) {
+        self.entries.write().clear();
+    }
+
+    /// Record the bounds a widget was laid out in
+    ///
+    /// Registering a widget again replaces its bounds and moves it above
+    /// everything registered before.
+    pub fn register(&self, id: WidgetId, bounds: Rect) {
+        let order = self.next_order.fetch_add(1, Ordering::Relaxed);
+        self.entries.write().insert(id, HitEntry { bounds, order });
+    }
+
+    /// The bounds last registered for a widget
+    pub fn bounds(&self, id: WidgetId) -> Option<Rect> {
+        self.entries.read().get(&id).map(|entry| entry.bounds)
+    }
+
+    /// Whether a widget's registered bounds contain `point`
+    ///
+    /// Widgets that have not been laid out contain nothing.
+    pub fn contains(&self, id: WidgetId, point: Point) -> bool {
+        self.bounds(id).is_some_and(|bounds| bounds.contains(point))
+    }
+
+    /// The widgets under `point`, deepest first
+    ///
+    /// `id` and `children` describe the live tree starting at `root`.
+    /// Widgets without registered bounds are transparent: they are never
+    /// returned, but their children still are.
+    pub fn widgets_at<N>(
+        &self,
+        root: N,
+        point: Point,
+        id: impl Fn(&N) -> WidgetId + Copy,
+        children: impl Fn(&N) -> Vec<N> + Copy,
+    ) -> Vec<WidgetId> {
+        let entries = self.entries.read();
+        visit(&entries, &root, point, id, children)
+            .map(|(_, path)| path)
+            .unwrap_or_default()
+    }
+
+    /// Where every widget in the tree at `root` was laid out
+    ///
+    /// `id`, `name` and `children` describe the live tree as for
+    /// [`widgets_at`](Self::widgets_at). Widgets without registered bounds
+    /// are kept, without bounds, so the tree mirrors the widget tree.
+    pub fn layout_tree<N>(
+        &self,
+        root: N,
+        id: impl Fn(&N) -> WidgetId + Copy,
+        name: impl Fn(&N) -> String + Copy,
+        children: impl Fn(&N) -> Vec<N> + Copy,
+    ) -> LayoutTree {
+        let entries = self.entries.read();
+        LayoutTree {
+            root: layout_node(&entries, &root, id, name, children),
+        }
+    }
+}
+
+fn layout_node<N>(
+    entries: &HashMap<WidgetId, HitEntry>,
+    node: &N,
+    id: impl Fn(&N) -> WidgetId + Copy,
+    name: impl Fn(&N) -> String + Copy,
+    children: impl Fn(&N) -> Vec<N> + Copy,
+) -> LayoutNode {
+    let node_id = id(node);
+    LayoutNode {
+        id: node_id,
+        name: name(node),
+        bounds: entries.get(&node_id).map(|entry| entry.bounds),
+        children: children(node)
+            .iter()
+            .map(|child| layout_node(entries, child, id, name, children))
+            .collect(),
+    }
+}
+
+/// A widget in a [`LayoutTree`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutNode {
+    pub id: WidgetId,
+    /// The widget's type, e.g. `Button`
+    pub name: String,
+    /// Where the widget was drawn, if it registered its bounds
+    pub bounds: Option<Rect>,
+    pub children: Vec<LayoutNode>,
+}
+
+/// Snapshot of where the widgets of a tree were laid out
+///
+/// Lighter than a full inspector snapshot, and meant for assertions such as
+/// one button being to the right of another.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutTree {
+    pub root: LayoutNode,
+}
+
+impl LayoutTree {
+    /// Every node, parents before their children
+    pub fn nodes(&self) -> Vec<&LayoutNode> {
+        fn collect<'a>(node: &'a LayoutNode, nodes: &mut Vec<&'a LayoutNode>) {
+            nodes.push(node);
+            for child in &node.children {
+                collect(child, nodes);
+            }
+        }
+        let mut nodes = Vec::new();
+        collect(&self.root, &mut nodes);
+        nodes
+    }
+
+    /// The node for a widget
+    pub fn find(&self, id: WidgetId) -> Option<&LayoutNode> {
+        self.nodes().into_iter().find(|node| node.id == id)
+    }
+
+    /// The bounds a widget was laid out in
+    pub fn bounds(&self, id: WidgetId) -> Option<Rect> {
+        self.find(id).and_then(|node| node.bounds)
+    }
+
+    /// The nodes of widgets of the type `name`, in tree order
+    pub fn find_by_name(&self, name: &str) -> Vec<&LayoutNode> {
+        self.nodes()
+            .into_iter()
+            .filter(|node| node.name == name)
+            .collect()
+    }
+}
+
+/// Hits in the subtree at `node` as (topmost order, path deepest first)
+fn visit<N>(
+    entries: &HashMap<WidgetId, HitEntry>,
+    node: &N,
+    point: Point,
+    id: impl Fn(&N) -> WidgetId + Copy,
+    children: impl Fn(&N) -> Vec<N> + Copy,
+) -> Option<(u64, Vec<WidgetId>)> {
+    let topmost_child = children(node)
+        .iter()
+        .filter_map(|child| visit(entries, child, point, id, children))
+        .max_by_key(|(order, _)| *order);
+
+    let node_id = id(node);
+    let own = entries
+        .get(&node_id)
+        .filter(|entry| entry.bounds.contains(point));
+
+    match (topmost_child, own) {
+        (Some((order, mut path)), own) => {
+            if own.is_some() {
+                path.push(node_id);
+            }
+            Some((order.max(own.map_or(0, |entry| entry.order)), path))
+        }
+        (None, Some(entry)) => Some((entry.order, vec![node_id])),
+        (None, None) => None,
+    }
+}
+
+static HIT_TEST: OnceLock<HitTest> = OnceLock::new();
+
+/// Access the global hit-test registry shared by all widgets
+pub fn hit_test() -> &'static HitTest {
+    HIT_TEST.get_or_init(HitTest::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Node {
+        id: u64,
+        children: Vec<Node>,
+    }
+
+    fn node(id: u64, children: Vec<Node>) -> Node {
+        Node { id, children }
+    }
+
+    fn hits(hit_test: &HitTest, root: &Node, x: f32, y: f32) -> Vec<u64> {
+        hit_test
+            .widgets_at(
+                root,
+                Point::new(x, y),
+                |node| WidgetId(node.id),
+                |node| node.children.iter().collect(),
+            )
+            .into_iter()
+            .map(|id| id.0)
+            .collect()
+    }
+
+    #[test]
+    fn test_widgets_at_returns_deepest_first() {
+        let hit_test = HitTest::new();
+        let tree = node(1, vec![node(2, vec![node(3, vec![])]), node(4, vec![])]);
+        hit_test.register(WidgetId(1), Rect::new(0.0, 0.0, 100.0, 100.0));
+        hit_test.register(WidgetId(2), Rect::new(0.0, 0.0, 50.0, 50.0));
+        hit_test.register(WidgetId(3), Rect::new(10.0, 10.0, 10.0, 10.0));
+        hit_test.register(WidgetId(4), Rect::new(40.0, 40.0, 50.0, 50.0));
+
+        assert_eq!(hits(&hit_test, &tree, 15.0, 15.0), vec![3, 2, 1]);
+        assert_eq!(hits(&hit_test, &tree, 30.0, 30.0), vec![2, 1]);
+        assert_eq!(hits(&hit_test, &tree, 90.0, 10.0), vec![1]);
+        assert!(hits(&hit_test, &tree, 150.0, 10.0).is_empty());
+
+        // Where siblings overlap the later (topmost) one wins
+        assert_eq!(hits(&hit_test, &tree, 45.0, 45.0), vec![4, 1]);
+    }
+
+    #[test]
+    fn test_unregistered_widgets_are_transparent() {
+        let hit_test = HitTest::new();
+        let tree = node(1, vec![node(2, vec![])]);
+        hit_test.register(WidgetId(2), Rect::new(0.0, 0.0, 10.0, 10.0));
+
+        assert_eq!(hits(&hit_test, &tree, 5.0, 5.0), vec![2]);
+        assert!(hit_test.contains(WidgetId(2), Point::new(5.0, 5.0)));
+        assert!(!hit_test.contains(WidgetId(1), Point::new(5.0, 5.0)));
+
+        hit_test.begin_frame();
+        assert!(hits(&hit_test, &tree, 5.0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_layout_tree_mirrors_the_widget_tree() {
+        let hit_test = HitTest::new();
+        let tree = node(1, vec![node(2, vec![]), node(3, vec![])]);
+        hit_test.register(WidgetId(2), Rect::new(0.0, 0.0, 40.0, 20.0));
+        hit_test.register(WidgetId(3), Rect::new(50.0, 0.0, 40.0, 20.0));
+
+        let layout = hit_test.layout_tree(
+            &tree,
+            |node| WidgetId(node.id),
+            |node| format!("Node{}", node.id),
+            |node| node.children.iter().collect(),
+        );
+        assert_eq!(layout.nodes().len(), 3);
+        assert_eq!(layout.root.bounds, None);
+        assert_eq!(layout.find_by_name("Node3")[0].id, WidgetId(3));
+
+        let left = layout.bounds(WidgetId(2)).unwrap();
+        let right = layout.bounds(WidgetId(3)).unwrap();
+        assert!(right.x >= left.x + left.width);
+    }
+}
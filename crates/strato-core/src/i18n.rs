@@ -0,0 +1,704 @@
+//! Localized messages, plural rules and number and date formatting
+//!
+//! A [`Localizer`] holds one [`MessageBundle`] per locale and formats
+//! messages for the active locale. A message missing from the active locale
+//! is looked up in its language without the region (`de-AT` → `de`), then in
+//! the fallback locale. Bundles use a small line based format:
+//!
+//! ```text
+//! # Lines starting with '#' are comments
+//! greeting = Hello, {name}!
+//! files = {count} files
+//! files[one] = {count} file
+//! files[=0] = No files
+//! ```
+//!
+//! `{name}` is replaced by the argument `name`, numbers formatted for the
+//! locale; `{{` and `}}` are literal braces. A message with variants picks
+//! one by its `count` argument: an exact `[=N]` match first, then the
+//! [`plural_category`] of the count, then the plain message.
+//!
+//! ```
+//! use strato_core::i18n::{message_args, Localizer};
+//!
+//! let localizer = Localizer::new("en");
+//! localizer
+//!     .load("en", "files = {count} files\nfiles[one] = {count} file")
+//!     .unwrap();
+//! assert_eq!(localizer.format("files", &message_args([("count", 1)])), "1 file");
+//! assert_eq!(localizer.format("files", &message_args([("count", 1200)])), "1,200 files");
+//! ```
+
+use crate::error::{Result, StratoError};
+use crate::state::Signal;
+use chrono::{Datelike, NaiveDate};
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+/// Locale used when none is set
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// Value interpolated into a message
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageArg {
+    Text(String),
+    Number(f64),
+}
+
+impl From<&str> for MessageArg {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for MessageArg {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+macro_rules! number_arg {
+    ($($ty:ty),*) => {
+        $(impl From<$ty> for MessageArg {
+            fn from(value: $ty) -> Self {
+                Self::Number(value as f64)
+            }
+        })*
+    };
+}
+
+number_arg!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64);
+
+/// Named arguments of a message
+pub type MessageArgs = Vec<(String, MessageArg)>;
+
+/// Collect `(name, value)` pairs into [`MessageArgs`]
+pub fn message_args<K, V>(args: impl IntoIterator<Item = (K, V)>) -> MessageArgs
+where
+    K: Into<String>,
+    V: Into<MessageArg>,
+{
+    args.into_iter()
+        .map(|(name, value)| (name.into(), value.into()))
+        .collect()
+}
+
+/// CLDR plural category of a count
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    fn parse(name: &str) -> Option<Self> {
+        Some(match name {
+            "zero" => Self::Zero,
+            "one" => Self::One,
+            "two" => Self::Two,
+            "few" => Self::Few,
+            "many" => Self::Many,
+            "other" => Self::Other,
+            _ => return None,
+        })
+    }
+}
+
+/// The language subtag of `locale`, lowercased: `pt_BR` → `pt`
+fn language(locale: &str) -> String {
+    locale
+        .split(['-', '_'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase()
+}
+
+/// `locale` in the form bundles are keyed by: `pt_BR` → `pt-br`
+fn normalize(locale: &str) -> String {
+    locale.replace('_', "-").to_ascii_lowercase()
+}
+
+/// The plural category of `n` in `locale`
+///
+/// Covers the cardinal rules of common languages; languages without a rule
+/// here use the English one.
+pub fn plural_category(locale: &str, n: f64) -> PluralCategory {
+    use PluralCategory::*;
+
+    let integer = n.fract() == 0.0;
+    let i = n.abs().trunc() as u64;
+    let (mod10, mod100) = (i % 10, i % 100);
+
+    match language(locale).as_str() {
+        "ja" | "zh" | "ko" | "th" | "vi" | "id" | "ms" => Other,
+        "fr" | "pt" | "hi" => {
+            if i <= 1 {
+                One
+            } else {
+                Other
+            }
+        }
+        "ru" | "uk" | "be" | "sr" | "hr" | "bs" => match (integer, mod10, mod100) {
+            (false, _, _) => Other,
+            (true, 1, m) if m != 11 => One,
+            (true, 2..=4, m) if !(12..=14).contains(&m) => Few,
+            _ => Many,
+        },
+        "pl" => match (integer, i, mod10, mod100) {
+            (false, ..) => Other,
+            (true, 1, ..) => One,
+            (true, _, 2..=4, m) if !(12..=14).contains(&m) => Few,
+            _ => Many,
+        },
+        "cs" | "sk" => match (integer, i) {
+            (false, _) => Many,
+            (true, 1) => One,
+            (true, 2..=4) => Few,
+            _ => Other,
+        },
+        "ar" => match (integer, i, mod100) {
+            (false, ..) => Other,
+            (true, 0, _) => Zero,
+            (true, 1, _) => One,
+            (true, 2, _) => Two,
+            (true, _, 3..=10) => Few,
+            (true, _, 11..=99) => Many,
+            _ => Other,
+        },
+        _ => {
+            if integer && i == 1 {
+                One
+            } else {
+                Other
+            }
+        }
+    }
+}
+
+/// Digit group and decimal separators of `locale`
+fn separators(locale: &str) -> (char, char) {
+    match language(locale).as_str() {
+        "de" | "it" | "es" | "nl" | "pt" | "id" | "tr" | "da" | "el" => ('.', ','),
+        "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "fi" | "nb" | "no" => ('\u{a0}', ','),
+        _ => (',', '.'),
+    }
+}
+
+/// `n` with the digit grouping and decimal separator of `locale`
+///
+/// Keeps at most three fraction digits, dropping trailing zeros.
+pub fn format_number(locale: &str, n: f64) -> String {
+    if !n.is_finite() {
+        return n.to_string();
+    }
+    let (group, decimal) = separators(locale);
+    let fixed = format!("{:.3}", n.abs());
+    let (integer, fraction) = fixed.split_once('.').unwrap_or((&fixed, ""));
+    let fraction = fraction.trim_end_matches('0');
+
+    let mut formatted = String::new();
+    if n < 0.0 && (integer != "0" || !fraction.is_empty()) {
+        formatted.push('-');
+    }
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            formatted.push(group);
+        }
+        formatted.push(digit);
+    }
+    if !fraction.is_empty() {
+        formatted.push(decimal);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// `date` in the short numeric form used in `locale`
+pub fn format_date(locale: &str, date: NaiveDate) -> String {
+    let (y, m, d) = (date.year(), date.month(), date.day());
+    let normalized = normalize(locale);
+    match language(locale).as_str() {
+        "en" if normalized == "en" || normalized == "en-us" => format!("{m}/{d}/{y}"),
+        "en" | "fr" | "it" | "es" | "pt" | "el" => format!("{d:02}/{m:02}/{y}"),
+        "de" | "ru" | "uk" | "pl" | "cs" | "sk" | "fi" | "nb" | "no" | "tr" | "da" => {
+            format!("{d:02}.{m:02}.{y}")
+        }
+        "nl" => format!("{d:02}-{m:02}-{y}"),
+        "ja" | "zh" => format!("{y}/{m:02}/{d:02}"),
+        "ko" => format!("{y}. {m:02}. {d:02}."),
+        _ => format!("{y}-{m:02}-{d:02}"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    Arg(String),
+}
+
+/// A parsed message template
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    fn parse(source: &str) -> std::result::Result<Self, String> {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = source.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literal.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literal.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(format!("unclosed '{{' in \"{source}\"")),
+                        }
+                    }
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return Err(format!("empty argument in \"{source}\""));
+                    }
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(Segment::Arg(name.to_string()));
+                }
+                '}' => return Err(format!("unmatched '}}' in \"{source}\"")),
+                c => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        Ok(Self { segments })
+    }
+
+    fn render(&self, locale: &str, args: &[(String, MessageArg)]) -> String {
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => out.push_str(text),
+                Segment::Arg(name) => match args.iter().find(|(arg, _)| arg == name) {
+                    Some((_, MessageArg::Text(text))) => out.push_str(text),
+                    Some((_, MessageArg::Number(n))) => out.push_str(&format_number(locale, *n)),
+                    // Left visible so the missing argument is noticed
+                    None => {
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                },
+            }
+        }
+        out
+    }
+}
+
+/// A message and its plural variants
+#[derive(Debug, Clone, Default)]
+struct Message {
+    plain: Option<Template>,
+    categories: HashMap<PluralCategory, Template>,
+    exact: HashMap<i64, Template>,
+}
+
+impl Message {
+    fn select(&self, locale: &str, args: &[(String, MessageArg)]) -> Option<&Template> {
+        let count = args.iter().find_map(|(name, value)| match value {
+            MessageArg::Number(n) if name == "count" => Some(*n),
+            _ => None,
+        });
+        let variant = count.and_then(|n| {
+            let exact = (n.fract() == 0.0)
+                .then(|| self.exact.get(&(n as i64)))
+                .flatten();
+            exact.or_else(|| self.categories.get(&plural_category(locale, n)))
+        });
+        variant
+            .or(self.plain.as_ref())
+            .or_else(|| self.categories.get(&PluralCategory::Other))
+    }
+}
+
+/// The messages of one locale
+#[derive(Debug, Clone)]
+pub struct MessageBundle {
+    locale: String,
+    messages: HashMap<String, Message>,
+}
+
+impl MessageBundle {
+    /// An empty bundle for `locale`, e.g. `"en"` or `"pt-BR"`
+    pub fn new(locale: impl Into<String>) -> Self {
+        Self {
+            locale: locale.into(),
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Parse a bundle in the format described in the [module docs](self)
+    pub fn parse(locale: impl Into<String>, source: &str) -> Result<Self> {
+        let mut bundle = Self::new(locale);
+        for (index, line) in source.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            // The key ends at the first '=', unless that '=' opens a `[=N]`
+            // variant, in which case it ends at the '=' after the variant
+            let separator = line.find('=').and_then(|at| {
+                let head = &line[..at];
+                if head.contains('[') && !head.contains(']') {
+                    let close = line.find(']')?;
+                    line[close..].find('=').map(|after| close + after)
+                } else {
+                    Some(at)
+                }
+            });
+            let result = match separator {
+                Some(at) => bundle.insert_line(line[..at].trim(), line[at + 1..].trim()),
+                None => Err("expected 'key = message'".to_string()),
+            };
+            result.map_err(|error| {
+                StratoError::configuration(format!(
+                    "{} messages, line {}: {error}",
+                    bundle.locale,
+                    index + 1
+                ))
+            })?;
+        }
+        Ok(bundle)
+    }
+
+    /// Add a message, or a variant of one with `key[one]` or `key[=0]`
+    pub fn insert(&mut self, key: &str, template: &str) -> Result<()> {
+        self.insert_line(key, template).map_err(|error| {
+            StratoError::configuration(format!("{} messages: {error}", self.locale))
+        })
+    }
+
+    fn insert_line(&mut self, key: &str, template: &str) -> std::result::Result<(), String> {
+        let (key, variant) = match key.split_once('[') {
+            Some((key, rest)) => {
+                let variant = rest
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("unclosed '[' in key \"{key}\""))?;
+                (key.trim(), Some(variant.trim()))
+            }
+            None => (key, None),
+        };
+        if key.is_empty() {
+            return Err("empty message key".to_string());
+        }
+        let template = Template::parse(template)?;
+
+        enum Variant {
+            Plain,
+            Exact(i64),
+            Category(PluralCategory),
+        }
+        let variant =
+            match variant {
+                None => Variant::Plain,
+                Some(variant) => match variant.strip_prefix('=') {
+                    Some(n) => Variant::Exact(
+                        n.trim()
+                            .parse()
+                            .map_err(|_| format!("invalid count in \"{key}[{variant}]\""))?,
+                    ),
+                    None => Variant::Category(PluralCategory::parse(variant).ok_or_else(|| {
+                        format!("unknown plural category in \"{key}[{variant}]\"")
+                    })?),
+                },
+            };
+
+        let message = self.messages.entry(key.to_string()).or_default();
+        match variant {
+            Variant::Plain => message.plain = Some(template),
+            Variant::Exact(n) => {
+                message.exact.insert(n, template);
+            }
+            Variant::Category(category) => {
+                message.categories.insert(category, template);
+            }
+        }
+        Ok(())
+    }
+
+    /// The locale these messages are in
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Whether the bundle has a message for `key`
+    pub fn contains(&self, key: &str) -> bool {
+        self.messages.contains_key(key)
+    }
+
+    /// Format the message for `key`, or `None` if there is none
+    pub fn format(&self, key: &str, args: &[(String, MessageArg)]) -> Option<String> {
+        let template = self.messages.get(key)?.select(&self.locale, args)?;
+        Some(template.render(&self.locale, args))
+    }
+}
+
+struct LocalizerInner {
+    bundles: RwLock<HashMap<String, MessageBundle>>,
+    locale: Signal<String>,
+    fallback: RwLock<String>,
+}
+
+/// Message bundles and the active locale
+///
+/// Clones are handles to the same localizer.
+#[derive(Clone)]
+pub struct Localizer {
+    inner: Arc<LocalizerInner>,
+}
+
+impl Localizer {
+    /// A localizer with no bundles, using `fallback` as both the active and
+    /// the fallback locale
+    pub fn new(fallback: impl Into<String>) -> Self {
+        let fallback = fallback.into();
+        Self {
+            inner: Arc::new(LocalizerInner {
+                bundles: RwLock::new(HashMap::new()),
+                locale: Signal::new(fallback.clone()),
+                fallback: RwLock::new(fallback),
+            }),
+        }
+    }
+
+    /// Access the application-wide localizer
+    pub fn global() -> Localizer {
+        static GLOBAL: OnceLock<Localizer> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| Localizer::new(DEFAULT_LOCALE))
+            .clone()
+    }
+
+    /// Add `bundle`, replacing the bundle of the same locale
+    pub fn add_bundle(&self, bundle: MessageBundle) {
+        self.inner
+            .bundles
+            .write()
+            .insert(normalize(bundle.locale()), bundle);
+        // Text showing a message that was just added formats it again
+        self.inner.locale.set(self.inner.locale.get());
+    }
+
+    /// Parse and add the messages of `locale`
+    pub fn load(&self, locale: &str, source: &str) -> Result<()> {
+        self.add_bundle(MessageBundle::parse(locale, source)?);
+        Ok(())
+    }
+
+    /// Switch the active locale
+    pub fn set_locale(&self, locale: impl Into<String>) {
+        self.inner.locale.set(locale.into());
+    }
+
+    /// The active locale
+    pub fn locale(&self) -> String {
+        self.inner.locale.get()
+    }
+
+    /// Signal holding the active locale, set again whenever a bundle is added
+    pub fn locale_signal(&self) -> &Signal<String> {
+        &self.inner.locale
+    }
+
+    /// Use `locale` for messages missing from the active locale
+    pub fn set_fallback_locale(&self, locale: impl Into<String>) {
+        *self.inner.fallback.write() = locale.into();
+    }
+
+    /// The locale used for messages missing from the active locale
+    pub fn fallback_locale(&self) -> String {
+        self.inner.fallback.read().clone()
+    }
+
+    /// Format the message for `key` in the active locale
+    ///
+    /// Returns `key` itself when no bundle in the lookup chain has it.
+    pub fn format(&self, key: &str, args: &[(String, MessageArg)]) -> String {
+        let locale = self.locale();
+        let fallback = self.fallback_locale();
+        let bundles = self.inner.bundles.read();
+
+        let mut chain = vec![
+            normalize(&locale),
+            language(&locale),
+            normalize(&fallback),
+            language(&fallback),
+        ];
+        chain.dedup();
+        for candidate in &chain {
+            if let Some(text) = bundles
+                .get(candidate)
+                .and_then(|bundle| bundle.format(key, args))
+            {
+                return text;
+            }
+        }
+
+        tracing::warn!("No message \"{key}\" for locale {locale} or fallback {fallback}");
+        key.to_string()
+    }
+
+    /// `n` formatted for the active locale
+    pub fn format_number(&self, n: f64) -> String {
+        format_number(&self.locale(), n)
+    }
+
+    /// `date` formatted for the active locale
+    pub fn format_date(&self, date: NaiveDate) -> String {
+        format_date(&self.locale(), date)
+    }
+}
+
+impl fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut locales: Vec<String> = self.inner.bundles.read().keys().cloned().collect();
+        locales.sort();
+        f.debug_struct("Localizer")
+            .field("locale", &self.locale())
+            .field("fallback", &self.fallback_locale())
+            .field("bundles", &locales)
+            .finish()
+    }
+}
+
+/// Switch the active locale of the global [`Localizer`]
+pub fn set_locale(locale: impl Into<String>) {
+    Localizer::global().set_locale(locale);
+}
+
+/// Format the message for `key` with the global [`Localizer`]
+pub fn localize(key: &str, args: &[(String, MessageArg)]) -> String {
+    Localizer::global().format(key, args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EN: &str = "
+        # English
+        greeting = Hello, {name}!
+        files = {count} files
+        files[one] = {count} file
+        files[=0] = No files
+        braces = [beta] {{literal}}
+    ";
+
+    const RU: &str = "
+        files[one] = {count} файл
+        files[few] = {count} файла
+        files[many] = {count} файлов
+        files[other] = {count} файла
+    ";
+
+    fn localizer() -> Localizer {
+        let localizer = Localizer::new("en");
+        localizer.load("en", EN).unwrap();
+        localizer.load("ru", RU).unwrap();
+        localizer
+    }
+
+    #[test]
+    fn test_interpolation_and_english_plurals() {
+        let localizer = localizer();
+        let count = |n: i32| localizer.format("files", &message_args([("count", n)]));
+
+        assert_eq!(
+            localizer.format("greeting", &message_args([("name", "Ada")])),
+            "Hello, Ada!"
+        );
+        assert_eq!(
+            (count(0), count(1), count(2)),
+            ("No files".into(), "1 file".into(), "2 files".into())
+        );
+        assert_eq!(localizer.format("braces", &[]), "[beta] {literal}");
+    }
+
+    #[test]
+    fn test_russian_plurals_and_fallback() {
+        let localizer = localizer();
+        localizer.set_locale("ru-RU");
+        let count = |n: i32| localizer.format("files", &message_args([("count", n)]));
+
+        assert_eq!(count(1), "1 файл");
+        assert_eq!(count(3), "3 файла");
+        assert_eq!(count(11), "11 файлов");
+        assert_eq!(count(21), "21 файл");
+        assert_eq!(count(1005), "1\u{a0}005 файлов");
+        // Missing in Russian, so the English message is used
+        assert_eq!(
+            localizer.format("greeting", &message_args([("name", "Ada")])),
+            "Hello, Ada!"
+        );
+        assert_eq!(localizer.format("missing", &[]), "missing");
+    }
+
+    #[test]
+    fn test_locale_signal_fires_on_switch_and_load() {
+        let localizer = localizer();
+        let changes = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = changes.clone();
+        let _subscription = localizer.locale_signal().subscribe(Box::new(move |_| {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }));
+
+        localizer.set_locale("de");
+        localizer.load("de", "greeting = Hallo, {name}!").unwrap();
+
+        assert_eq!(changes.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(
+            localizer.format("greeting", &message_args([("name", "Ada")])),
+            "Hallo, Ada!"
+        );
+    }
+
+    #[test]
+    fn test_invalid_bundles_are_rejected() {
+        assert!(MessageBundle::parse("en", "greeting Hello").is_err());
+        assert!(MessageBundle::parse("en", "greeting = Hello, {name").is_err());
+        assert!(MessageBundle::parse("en", "files[several] = {count}").is_err());
+    }
+
+    #[test]
+    fn test_number_and_date_formatting() {
+        assert_eq!(format_number("en-US", 1234567.891), "1,234,567.891");
+        assert_eq!(format_number("de", -1234.5), "-1.234,5");
+        assert_eq!(format_number("fr", 1000.0), "1\u{a0}000");
+        assert_eq!(format_number("en", 0.1 + 0.2), "0.3");
+
+        let date = NaiveDate::from_ymd_opt(2026, 3, 7).unwrap();
+        assert_eq!(format_date("en-US", date), "3/7/2026");
+        assert_eq!(format_date("en-GB", date), "07/03/2026");
+        assert_eq!(format_date("de-DE", date), "07.03.2026");
+        assert_eq!(format_date("ja", date), "2026/03/07");
+        assert_eq!(format_date("sv", date), "2026-03-07");
+    }
+}
@@ -0,0 +1,174 @@
+//! Input held at the start of a frame
+//!
+//! Events are the authoritative record of input: they arrive in order and
+//! none is skipped. Some code would rather poll, though, like a drag that
+//! snaps while Shift is held or a game moving a sprite while an arrow key is
+//! down. [`InputState`] serves it: the keys, modifiers and mouse buttons
+//! held and the pointer position, as of the start of the frame, built from
+//! the events delivered before it.
+//!
+//! A press and release between two frames never shows in a snapshot, so
+//! react to events for anything that must not be missed.
+
+use crate::event::{Event, KeyCode, Modifiers, MouseButton, WindowEvent};
+use glam::Vec2;
+
+/// Keys, modifiers and mouse buttons held, and where the pointer is
+///
+/// Buttons beyond the eighth, `MouseButton::Other(5)` and up, aren't
+/// tracked. Everything is released when the window loses focus, since the
+/// matching key and button ups go to another window.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct InputState {
+    /// One bit per [`KeyCode`], by discriminant
+    keys: u128,
+    /// One bit per tracked [`MouseButton`]
+    buttons: u8,
+    modifiers: Modifiers,
+    mouse_position: Option<Vec2>,
+}
+
+impl InputState {
+    /// Nothing held and no pointer position yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the state with an event
+    pub fn apply(&mut self, event: &Event) {
+        match event {
+            Event::MouseDown(mouse) | Event::MouseUp(mouse) | Event::MouseMove(mouse) => {
+                self.mouse_position = Some(mouse.position);
+                self.modifiers = mouse.modifiers;
+                if let Some(bit) = mouse.button.and_then(button_bit) {
+                    match event {
+                        Event::MouseDown(_) => self.buttons |= bit,
+                        Event::MouseUp(_) => self.buttons &= !bit,
+                        _ => {}
+                    }
+                }
+            }
+            Event::MouseWheel { modifiers, .. } => self.modifiers = *modifiers,
+            Event::KeyDown(key) | Event::KeyUp(key) => {
+                let down = matches!(event, Event::KeyDown(_));
+                let bit = 1u128 << key.key_code as u32;
+                if down {
+                    self.keys |= bit;
+                } else {
+                    self.keys &= !bit;
+                }
+                // Platforms disagree on whether a modifier key's own event
+                // carries it, so it counts from its press to its release
+                self.modifiers = key.modifiers;
+                match key.key_code {
+                    KeyCode::Shift => self.modifiers.shift = down,
+                    KeyCode::Control => self.modifiers.control = down,
+                    KeyCode::Alt => self.modifiers.alt = down,
+                    KeyCode::Super => self.modifiers.super_key = down,
+                    _ => {}
+                }
+            }
+            Event::Window(WindowEvent::Focus(false)) => {
+                self.keys = 0;
+                self.buttons = 0;
+                self.modifiers = Modifiers::default();
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether `key` is held
+    pub fn is_key_down(&self, key: KeyCode) -> bool {
+        self.keys & (1u128 << key as u32) != 0
+    }
+
+    /// Whether any key is held
+    pub fn any_key_down(&self) -> bool {
+        self.keys != 0
+    }
+
+    /// The modifier keys held
+    pub fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Whether `button` is held
+    pub fn is_mouse_down(&self, button: MouseButton) -> bool {
+        button_bit(button).is_some_and(|bit| self.buttons & bit != 0)
+    }
+
+    /// Whether any mouse button is held
+    pub fn any_mouse_down(&self) -> bool {
+        self.buttons != 0
+    }
+
+    /// Where the pointer last was, in logical pixels, if it has moved over
+    /// the window yet
+    pub fn mouse_position(&self) -> Option<Vec2> {
+        self.mouse_position
+    }
+}
+
+fn button_bit(button: MouseButton) -> Option<u8> {
+    match button {
+        MouseButton::Left => Some(1),
+        MouseButton::Right => Some(1 << 1),
+        MouseButton::Middle => Some(1 << 2),
+        MouseButton::Other(n) if n < 5 => Some(1 << (3 + n)),
+        MouseButton::Other(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{KeyboardEvent, MouseEvent};
+
+    fn key(key_code: KeyCode, shift: bool) -> KeyboardEvent {
+        KeyboardEvent {
+            key_code,
+            modifiers: Modifiers {
+                shift,
+                ..Default::default()
+            },
+            is_repeat: false,
+            text: None,
+        }
+    }
+
+    fn mouse(x: f32, button: Option<MouseButton>) -> MouseEvent {
+        MouseEvent {
+            position: Vec2::new(x, 10.0),
+            button,
+            modifiers: Modifiers::default(),
+            delta: Vec2::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_tracks_what_is_held_until_released_or_unfocused() {
+        let mut input = InputState::new();
+        // The Shift press itself doesn't carry the modifier
+        input.apply(&Event::KeyDown(key(KeyCode::Shift, false)));
+        input.apply(&Event::KeyDown(key(KeyCode::Right, true)));
+        input.apply(&Event::MouseDown(mouse(5.0, Some(MouseButton::Left))));
+        input.apply(&Event::MouseMove(mouse(40.0, None)));
+
+        assert!(input.is_key_down(KeyCode::Right));
+        assert!(!input.is_key_down(KeyCode::Left));
+        assert!(input.is_mouse_down(MouseButton::Left));
+        assert!(!input.is_mouse_down(MouseButton::Right));
+        assert_eq!(input.mouse_position(), Some(Vec2::new(40.0, 10.0)));
+
+        input.apply(&Event::KeyUp(key(KeyCode::Right, true)));
+        assert!(!input.is_key_down(KeyCode::Right));
+        assert!(input.modifiers().shift);
+        input.apply(&Event::KeyUp(key(KeyCode::Shift, true)));
+        assert!(!input.modifiers().shift);
+
+        input.apply(&Event::Window(WindowEvent::Focus(false)));
+        assert!(!input.any_mouse_down());
+        assert!(!input.any_key_down());
+        assert_eq!(input.mouse_position(), Some(Vec2::new(40.0, 10.0)));
+    }
+}
@@ -3,8 +3,14 @@
 //! This module provides a comprehensive flexbox layout system that supports
 //! all major flexbox properties including direction, wrap, alignment, and gaps.
 
+use crate::state::{Signal, Subscription};
+use crate::types::Point;
 use glam::Vec2;
+use parking_lot::{Mutex, RwLock};
 use std::fmt::Debug;
+use std::ops::{Add, Div, Mul, Sub};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
 
 /// Layout constraints for widgets
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -29,6 +35,11 @@ impl Constraints {
         }
     }
 
+    /// Create constraints with no limits; the same as [`Constraints::none`]
+    pub fn unbounded() -> Self {
+        Self::none()
+    }
+
     /// Create tight constraints (fixed size)
     pub fn tight(width: f32, height: f32) -> Self {
         Self {
@@ -49,12 +60,97 @@ impl Constraints {
         }
     }
 
+    /// Create tight constraints that only allow `size`
+    pub fn tight_size(size: Size) -> Self {
+        Self::tight(size.width, size.height)
+    }
+
+    /// Create loose constraints that allow any size up to `size`
+    pub fn loose_size(size: Size) -> Self {
+        Self::loose(size.width, size.height)
+    }
+
+    /// Create constraints that are tight in the given axes and unbounded in
+    /// the others
+    pub fn tight_for(width: Option<f32>, height: Option<f32>) -> Self {
+        let none = Self::none();
+        Self {
+            min_width: width.unwrap_or(none.min_width),
+            max_width: width.unwrap_or(none.max_width),
+            min_height: height.unwrap_or(none.min_height),
+            max_height: height.unwrap_or(none.max_height),
+        }
+    }
+
+    /// Whether exactly one size satisfies these constraints
+    pub fn is_tight(&self) -> bool {
+        self.min_width >= self.max_width && self.min_height >= self.max_height
+    }
+
+    /// The largest size these constraints allow
+    pub fn biggest(&self) -> Size {
+        Size::new(self.max_width, self.max_height)
+    }
+
+    /// The smallest size these constraints allow
+    pub fn smallest(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+
     /// Constrain a size to these constraints
+    ///
+    /// Each axis is clamped between its minimum and maximum. If the minimum
+    /// exceeds the maximum the minimum wins, and a NaN axis resolves to the
+    /// minimum, so callers never see a panic or a NaN from bad input.
     pub fn constrain(&self, size: Size) -> Size {
         Size {
-            width: size.width.clamp(self.min_width, self.max_width),
-            height: size.height.clamp(self.min_height, self.max_height),
+            width: constrain_axis(size.width, self.min_width, self.max_width),
+            height: constrain_axis(size.height, self.min_height, self.max_height),
+        }
+    }
+
+    /// Constrain a width to these constraints
+    pub fn constrain_width(&self, width: f32) -> f32 {
+        constrain_axis(width, self.min_width, self.max_width)
+    }
+
+    /// Constrain a height to these constraints
+    pub fn constrain_height(&self, height: f32) -> f32 {
+        constrain_axis(height, self.min_height, self.max_height)
+    }
+
+    /// Constrain a size while keeping its aspect ratio where possible
+    ///
+    /// The size is scaled down to fit the maximums, then up to reach the
+    /// minimums; if both cannot hold the result is clamped like
+    /// [`Constraints::constrain`].
+    pub fn constrain_aspect_ratio(&self, size: Size) -> Size {
+        if size.width <= 0.0 || size.height <= 0.0 || self.is_tight() {
+            return self.constrain(size);
+        }
+
+        let aspect_ratio = size.width / size.height;
+        let mut width = size.width;
+        let mut height = size.height;
+
+        if width > self.max_width {
+            width = self.max_width;
+            height = width / aspect_ratio;
+        }
+        if height > self.max_height {
+            height = self.max_height;
+            width = height * aspect_ratio;
         }
+        if width < self.min_width {
+            width = self.min_width;
+            height = width / aspect_ratio;
+        }
+        if height < self.min_height {
+            height = self.min_height;
+            width = height * aspect_ratio;
+        }
+
+        self.constrain(Size::new(width, height))
     }
 
     /// Check if a size satisfies these constraints
@@ -66,6 +162,13 @@ impl Constraints {
     }
 }
 
+fn constrain_axis(value: f32, min: f32, max: f32) -> f32 {
+    if value.is_nan() {
+        return min;
+    }
+    value.min(max).max(min)
+}
+
 /// Size representation
 #[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub struct Size {
@@ -88,6 +191,16 @@ impl Size {
     pub fn to_vec2(self) -> Vec2 {
         Vec2::new(self.width, self.height)
     }
+
+    /// The larger of each dimension
+    pub fn max(self, other: Size) -> Size {
+        Size::new(self.width.max(other.width), self.height.max(other.height))
+    }
+
+    /// The smaller of each dimension
+    pub fn min(self, other: Size) -> Size {
+        Size::new(self.width.min(other.width), self.height.min(other.height))
+    }
 }
 
 impl From<Vec2> for Size {
@@ -96,6 +209,55 @@ impl From<Vec2> for Size {
     }
 }
 
+impl Add for Size {
+    type Output = Size;
+
+    fn add(self, rhs: Size) -> Self::Output {
+        Size::new(self.width + rhs.width, self.height + rhs.height)
+    }
+}
+
+impl Sub for Size {
+    type Output = Size;
+
+    fn sub(self, rhs: Size) -> Self::Output {
+        Size::new(self.width - rhs.width, self.height - rhs.height)
+    }
+}
+
+impl Mul<f32> for Size {
+    type Output = Size;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Size::new(self.width * rhs, self.height * rhs)
+    }
+}
+
+impl Div<f32> for Size {
+    type Output = Size;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Size::new(self.width / rhs, self.height / rhs)
+    }
+}
+
+/// Offset a point by a layout size, e.g. from a widget's origin to its far corner
+impl Add<Size> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Size) -> Self::Output {
+        Point::new(self.x + rhs.width, self.y + rhs.height)
+    }
+}
+
+impl Sub<Size> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Size) -> Self::Output {
+        Point::new(self.x - rhs.width, self.y - rhs.height)
+    }
+}
+
 /// Flex direction
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FlexDirection {
@@ -337,6 +499,16 @@ impl Layout {
         )
     }
 
+    /// The top-left corner
+    pub fn origin(&self) -> Point {
+        Point::new(self.position.x, self.position.y)
+    }
+
+    /// The center point
+    pub fn center(&self) -> Point {
+        self.origin() + self.size / 2.0
+    }
+
     /// Check if a point is within this layout
     pub fn contains(&self, point: Vec2) -> bool {
         point.x >= self.position.x
@@ -354,6 +526,152 @@ struct FlexLine {
     cross_size: f32,
 }
 
+/// Options for the widget layout pass
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutConfig {
+    /// Lay out and draw the children of rows and columns on the rayon
+    /// thread pool
+    pub parallel: bool,
+    /// Fewest children a row or column needs before its children are laid
+    /// out and drawn in parallel; smaller containers stay on the calling
+    /// thread
+    pub parallel_threshold: usize,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            parallel: false,
+            parallel_threshold: 32,
+        }
+    }
+}
+
+static LAYOUT_CONFIG: OnceLock<RwLock<LayoutConfig>> = OnceLock::new();
+
+fn layout_config_slot() -> &'static RwLock<LayoutConfig> {
+    LAYOUT_CONFIG.get_or_init(|| RwLock::new(LayoutConfig::default()))
+}
+
+/// The options used by the layout pass
+pub fn layout_config() -> LayoutConfig {
+    *layout_config_slot().read()
+}
+
+/// Change the options used by the layout pass, e.g. to enable parallel layout
+pub fn set_layout_config(config: LayoutConfig) {
+    *layout_config_slot().write() = config;
+}
+
+/// Memoized layout of a widget
+///
+/// A widget keeps one of these and lays itself out through
+/// [`get_or_layout`](Self::get_or_layout), which returns the size computed
+/// for the last constraints until the constraints change or the widget is
+/// marked dirty. Marking a widget dirty also marks the widgets that laid it
+/// out (see [`adopt`](Self::adopt)), so a change deep in the tree lays out
+/// the path to the root again and leaves the other subtrees alone.
+///
+/// Clones are handles to the same cache.
+#[derive(Clone, Default)]
+pub struct LayoutCache {
+    inner: Arc<LayoutCacheInner>,
+}
+
+#[derive(Default)]
+struct LayoutCacheInner {
+    dirty: AtomicBool,
+    entry: Mutex<Option<(Constraints, Size)>>,
+    parent: Mutex<Weak<LayoutCacheInner>>,
+    // Watched signals, unsubscribed when the widget is dropped
+    subscriptions: Mutex<Vec<Subscription>>,
+}
+
+impl LayoutCache {
+    /// Create an empty cache; the first layout always computes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the cached size for `constraints`, or compute and cache it
+    pub fn get_or_layout(&self, constraints: Constraints, layout: impl FnOnce() -> Size) -> Size {
+        if !self.inner.dirty.load(Ordering::Acquire) {
+            if let Some((cached_constraints, size)) = *self.inner.entry.lock() {
+                if cached_constraints == constraints {
+                    return size;
+                }
+            }
+        }
+
+        // Cleared first so a child marking this cache dirty during the
+        // layout keeps it dirty for the next pass
+        self.inner.dirty.store(false, Ordering::Release);
+        let size = layout();
+        *self.inner.entry.lock() = Some((constraints, size));
+        size
+    }
+
+    /// The size cached for `constraints`, if it is still valid
+    ///
+    /// Unlike [`get_or_layout`](Self::get_or_layout) this never computes, so
+    /// it can answer a measurement without laying the widget out.
+    pub fn cached(&self, constraints: Constraints) -> Option<Size> {
+        if self.inner.dirty.load(Ordering::Acquire) {
+            return None;
+        }
+        match *self.inner.entry.lock() {
+            Some((cached_constraints, size)) if cached_constraints == constraints => Some(size),
+            _ => None,
+        }
+    }
+
+    /// Record that this cache's widget lays out the widget owning `child`
+    ///
+    /// Marking `child` dirty then marks this cache dirty as well.
+    pub fn adopt(&self, child: &LayoutCache) {
+        *child.inner.parent.lock() = Arc::downgrade(&self.inner);
+    }
+
+    /// Force the next layout to compute, here and in every ancestor
+    pub fn mark_dirty(&self) {
+        let mut node = Some(Arc::clone(&self.inner));
+        while let Some(current) = node {
+            // Ancestors of a dirty cache are already dirty
+            if current.dirty.swap(true, Ordering::AcqRel) {
+                break;
+            }
+            node = current.parent.lock().upgrade();
+        }
+    }
+
+    /// Whether the next layout will compute
+    pub fn is_dirty(&self) -> bool {
+        self.inner.dirty.load(Ordering::Acquire) || self.inner.entry.lock().is_none()
+    }
+
+    /// Mark the widget dirty whenever `signal` changes
+    ///
+    /// For signals the widget's size depends on, e.g. a label's text.
+    pub fn watch<T: Clone + Send + Sync + 'static>(&self, signal: &Signal<T>) {
+        let inner = Arc::downgrade(&self.inner);
+        let subscription = signal.subscribe(Box::new(move |_| {
+            if let Some(inner) = inner.upgrade() {
+                LayoutCache { inner }.mark_dirty();
+            }
+        }));
+        self.inner.subscriptions.lock().push(subscription);
+    }
+}
+
+impl Debug for LayoutCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayoutCache")
+            .field("dirty", &self.inner.dirty.load(Ordering::Relaxed))
+            .field("entry", &*self.inner.entry.lock())
+            .finish()
+    }
+}
+
 /// Layout engine for calculating widget positions
 pub struct LayoutEngine {
     // Allow dead code for cache field as it's part of future optimization plans
@@ -713,6 +1031,104 @@ mod tests {
         assert_eq!(constrained.height, 100.0);
     }
 
+    #[test]
+    fn test_size_operators() {
+        let size = Size::new(10.0, 20.0);
+        assert_eq!(size + Size::new(1.0, 2.0), Size::new(11.0, 22.0));
+        assert_eq!(size - Size::new(1.0, 2.0), Size::new(9.0, 18.0));
+        assert_eq!(size * 2.0, Size::new(20.0, 40.0));
+        assert_eq!(size / 2.0, Size::new(5.0, 10.0));
+        assert_eq!(size.max(Size::new(15.0, 5.0)), Size::new(15.0, 20.0));
+        assert_eq!(size.min(Size::new(15.0, 5.0)), Size::new(10.0, 5.0));
+        assert_eq!(Point::new(1.0, 1.0) + size, Point::new(11.0, 21.0));
+        assert_eq!(Point::new(1.0, 1.0) - size, Point::new(-9.0, -19.0));
+
+        let layout = Layout::new(Vec2::new(5.0, 5.0), size);
+        assert_eq!(layout.origin(), Point::new(5.0, 5.0));
+        assert_eq!(layout.center(), Point::new(10.0, 15.0));
+    }
+
+    #[test]
+    fn test_constrain_clamps_each_axis() {
+        let constraints = Constraints {
+            min_width: 10.0,
+            max_width: 100.0,
+            min_height: 20.0,
+            max_height: f32::INFINITY,
+        };
+
+        assert_eq!(
+            constraints.constrain(Size::new(5.0, 5.0)),
+            Size::new(10.0, 20.0)
+        );
+        assert_eq!(
+            constraints.constrain(Size::new(50.0, 500.0)),
+            Size::new(50.0, 500.0)
+        );
+        assert_eq!(
+            constraints.constrain(Size::new(500.0, 50.0)),
+            Size::new(100.0, 50.0)
+        );
+        assert_eq!(
+            constraints.constrain(Size::new(f32::NAN, 30.0)),
+            Size::new(10.0, 30.0)
+        );
+
+        // Conflicting limits resolve to the minimum instead of panicking
+        let conflicting = Constraints {
+            min_width: 80.0,
+            max_width: 40.0,
+            ..Constraints::none()
+        };
+        assert_eq!(conflicting.constrain(Size::new(60.0, 0.0)).width, 80.0);
+    }
+
+    #[test]
+    fn test_constraint_helpers() {
+        let size = Size::new(30.0, 40.0);
+        assert_eq!(
+            Constraints::tight_size(size),
+            Constraints::tight(30.0, 40.0)
+        );
+        assert_eq!(
+            Constraints::loose_size(size),
+            Constraints::loose(30.0, 40.0)
+        );
+        assert!(Constraints::tight_size(size).is_tight());
+        assert!(!Constraints::loose_size(size).is_tight());
+        assert_eq!(Constraints::unbounded(), Constraints::none());
+
+        let width_only = Constraints::tight_for(Some(30.0), None);
+        assert_eq!(
+            width_only.constrain(Size::new(99.0, 99.0)),
+            Size::new(30.0, 99.0)
+        );
+        assert_eq!(Constraints::loose_size(size).biggest(), size);
+        assert_eq!(Constraints::loose_size(size).smallest(), Size::zero());
+    }
+
+    #[test]
+    fn test_constrain_aspect_ratio() {
+        let constraints = Constraints::loose(100.0, 100.0);
+        assert_eq!(
+            constraints.constrain_aspect_ratio(Size::new(400.0, 200.0)),
+            Size::new(100.0, 50.0)
+        );
+        assert_eq!(
+            constraints.constrain_aspect_ratio(Size::new(100.0, 400.0)),
+            Size::new(25.0, 100.0)
+        );
+
+        let with_min = Constraints {
+            min_width: 60.0,
+            ..Constraints::loose(100.0, 100.0)
+        };
+        assert_eq!(
+            with_min.constrain_aspect_ratio(Size::new(30.0, 15.0)),
+            Size::new(60.0, 30.0)
+        );
+    }
+
     #[test]
     fn test_flex_layout() {
         let engine = LayoutEngine::new();
@@ -735,4 +1151,52 @@ mod tests {
         assert_eq!(layouts[0].size.width, 100.0);
         assert_eq!(layouts[1].size.width, 200.0);
     }
+
+    #[test]
+    fn test_layout_cache_reuses_size_until_dirty() {
+        let cache = LayoutCache::new();
+        let constraints = Constraints::loose(100.0, 100.0);
+        let mut computed = 0;
+        let mut layout = |cache: &LayoutCache, constraints| {
+            cache.get_or_layout(constraints, || {
+                computed += 1;
+                Size::new(10.0, 10.0)
+            })
+        };
+
+        layout(&cache, constraints);
+        layout(&cache, constraints);
+        assert!(!cache.is_dirty());
+
+        cache.mark_dirty();
+        layout(&cache, constraints);
+        layout(&cache, Constraints::loose(50.0, 50.0));
+        drop(layout);
+        assert_eq!(computed, 3);
+    }
+
+    #[test]
+    fn test_layout_cache_dirty_child_marks_ancestors() {
+        let (root, parent, child, sibling) = (
+            LayoutCache::new(),
+            LayoutCache::new(),
+            LayoutCache::new(),
+            LayoutCache::new(),
+        );
+        root.adopt(&parent);
+        root.adopt(&sibling);
+        parent.adopt(&child);
+        for cache in [&root, &parent, &child, &sibling] {
+            cache.get_or_layout(Constraints::none(), Size::zero);
+        }
+
+        let text = Signal::new(String::from("a"));
+        child.watch(&text);
+        text.set(String::from("b"));
+
+        assert!(child.is_dirty());
+        assert!(parent.is_dirty());
+        assert!(root.is_dirty());
+        assert!(!sibling.is_dirty());
+    }
 }
@@ -6,16 +6,22 @@
 pub mod config;
 pub mod error;
 pub mod event;
+pub mod hit_test;
 pub mod hot_reload;
+pub mod i18n;
+pub mod input;
 pub mod inspector;
 pub mod layout;
 pub mod logging;
+#[cfg(feature = "serde")]
+pub mod persistence;
 pub mod plugin;
 pub mod reactive;
 pub mod state;
 pub mod taffy_layout;
 pub mod text;
 pub mod theme;
+pub mod touch;
 pub mod types;
 pub mod ui_node;
 pub mod validated_rect;
@@ -24,14 +30,21 @@ pub mod widget;
 pub mod window;
 
 pub use error::{
-    Result, StratoError, StratoResult, TaffyLayoutError, TaffyLayoutResult,
+    AdapterDetails, ErrorReport, InitStep, Result, ResultExt, StratoError, StratoResult,
+    TaffyLayoutError, TaffyLayoutResult,
     TaffyRenderError, TaffyRenderResult, TaffyValidationError, TaffyValidationResult,
 };
 pub use event::{Event, EventHandler, EventResult};
-pub use layout::{Constraints, Layout, LayoutConstraints, LayoutEngine, Size};
-pub use logging::{LogCategory, LogLevel};
-pub use reactive::{Computed, Effect, Reactive};
-pub use state::{Signal, State};
+pub use hit_test::HitTest;
+pub use input::InputState;
+pub use layout::{
+    Constraints, Layout, LayoutCache, LayoutConfig, LayoutConstraints, LayoutEngine, Size,
+};
+pub use logging::{LogBuffer, LogBufferLayer, LogCategory, LogLevel, LogRecord};
+#[cfg(feature = "serde")]
+pub use persistence::{PersistentSignal, StateStore};
+pub use reactive::{create_scope, Computed, Effect, Reactive, Scope};
+pub use state::{Signal, State, Subscription};
 pub use taffy;
 pub use taffy_layout::{ComputedLayout, DrawCommand, TaffyLayoutManager, TaffyWidget};
 pub use types::{Color, Point, Rect, Transform};
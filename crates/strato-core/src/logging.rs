@@ -2,12 +2,23 @@
 //!
 //! This module provides a comprehensive logging system with rate limiting,
 //! contextual error information, and category-based filtering.
+//!
+//! Each [`LogCategory`] has a minimum level that can be changed while the
+//! application runs with [`set_category_level`]. Levels live in atomics, so
+//! filtering a disabled message costs a single load. The same levels can gate
+//! `tracing` output through [`CategoryFilter`].
+//!
+//! Every message that passes filtering is also kept in a bounded
+//! [`LogBuffer`], which in-app consoles read from. Plain `tracing` events
+//! reach it through [`LogBufferLayer`].
 
 use crate::config::LoggingConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::{Arc, OnceLock, RwLock};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 use std::time::{Duration, Instant};
+use tracing_subscriber::layer::{Context, Filter, Layer};
 
 /// Log levels supported by the system
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
@@ -35,6 +46,43 @@ pub enum LogCategory {
 }
 
 impl LogCategory {
+    /// Every category, in declaration order
+    pub const ALL: [LogCategory; 10] = [
+        LogCategory::Core,
+        LogCategory::Renderer,
+        LogCategory::Vulkan,
+        LogCategory::Text,
+        LogCategory::UI,
+        LogCategory::Input,
+        LogCategory::Audio,
+        LogCategory::Network,
+        LogCategory::Plugin,
+        LogCategory::Platform,
+    ];
+
+    /// Parse a category name as produced by [`LogCategory::as_str`]
+    pub fn from_name(name: &str) -> Option<LogCategory> {
+        Self::ALL
+            .into_iter()
+            .find(|category| category.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Category for a `tracing` target such as `strato_renderer::gpu`
+    pub fn from_target(target: &str) -> Option<LogCategory> {
+        let root = target.split("::").next().unwrap_or(target);
+        match root {
+            "strato_core" => Some(LogCategory::Core),
+            "strato_renderer" => Some(LogCategory::Renderer),
+            "strato_widgets" => Some(LogCategory::UI),
+            "strato_platform" => Some(LogCategory::Platform),
+            _ => Self::from_name(root),
+        }
+    }
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
     /// Convert LogCategory to string
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -52,6 +100,18 @@ impl LogCategory {
     }
 }
 
+impl AsRef<str> for LogCategory {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+/// Name of a category given as a [`LogCategory`] or a string, used by the macros
+#[doc(hidden)]
+pub fn category_name<T: AsRef<str> + ?Sized>(category: &T) -> &str {
+    category.as_ref()
+}
+
 impl std::fmt::Display for LogCategory {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -83,6 +143,18 @@ impl LogLevel {
     }
 }
 
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => LogLevel::Trace,
+            tracing::Level::DEBUG => LogLevel::Debug,
+            tracing::Level::INFO => LogLevel::Info,
+            tracing::Level::WARN => LogLevel::Warn,
+            tracing::Level::ERROR => LogLevel::Error,
+        }
+    }
+}
+
 impl std::fmt::Display for LogLevel {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", self.as_str())
@@ -179,9 +251,282 @@ impl LoggerConfig {
 /// Global logger instance
 static LOGGER: OnceLock<Arc<RwLock<LoggerConfig>>> = OnceLock::new();
 
+/// Level used by categories that were never configured
+const DEFAULT_LEVEL: LogLevel = LogLevel::Info;
+
+/// Marker for a category without an explicit level
+const UNSET: u8 = u8::MAX;
+
+/// Minimum level per category, indexed by [`LogCategory::index`]
+static CATEGORY_LEVELS: [AtomicU8; LogCategory::ALL.len()] =
+    [const { AtomicU8::new(UNSET) }; LogCategory::ALL.len()];
+
+/// Set the minimum level logged for a category, effective immediately
+pub fn set_category_level(category: LogCategory, level: LogLevel) {
+    CATEGORY_LEVELS[category.index()].store(level as u8, Ordering::Relaxed);
+}
+
+/// Current minimum level of a category
+pub fn category_level(category: LogCategory) -> LogLevel {
+    match CATEGORY_LEVELS[category.index()].load(Ordering::Relaxed) {
+        0 => LogLevel::Trace,
+        1 => LogLevel::Debug,
+        2 => LogLevel::Info,
+        3 => LogLevel::Warn,
+        4 => LogLevel::Error,
+        _ => DEFAULT_LEVEL,
+    }
+}
+
+/// Current minimum level of every category
+pub fn get_levels() -> Vec<(LogCategory, LogLevel)> {
+    LogCategory::ALL
+        .into_iter()
+        .map(|category| (category, category_level(category)))
+        .collect()
+}
+
+/// Check whether a message would be logged
+///
+/// Known categories are checked against their runtime level; other names fall
+/// back to the configured `category_levels`.
+pub fn is_enabled(category: &str, level: LogLevel) -> bool {
+    match LogCategory::from_name(category) {
+        Some(category) => level >= category_level(category),
+        None => match get_logger() {
+            Some(logger) => logger.read().unwrap().is_level_enabled(category, level),
+            None => level >= DEFAULT_LEVEL,
+        },
+    }
+}
+
+/// Load category levels from configuration, replacing runtime overrides
+fn apply_category_levels(config: &LoggingConfig) {
+    for category in LogCategory::ALL {
+        let level = config
+            .category_levels
+            .get(category.as_str())
+            .and_then(|level| LogLevel::from_str(level));
+        let value = level.map_or(UNSET, |level| level as u8);
+        CATEGORY_LEVELS[category.index()].store(value, Ordering::Relaxed);
+    }
+}
+
+/// `tracing` filter applying the runtime category levels
+///
+/// Events are mapped to a category by target (see
+/// [`LogCategory::from_target`]); events outside any category pass through.
+///
+/// ```ignore
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(tracing_subscriber::fmt::layer().with_filter(CategoryFilter))
+///     .with(LogBufferLayer::new().with_filter(CategoryFilter))
+///     .init();
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CategoryFilter;
+
+impl<S> Filter<S> for CategoryFilter {
+    fn enabled(&self, metadata: &tracing::Metadata<'_>, _cx: &Context<'_, S>) -> bool {
+        match LogCategory::from_target(metadata.target()) {
+            Some(category) => LogLevel::from(*metadata.level()) >= category_level(category),
+            None => true,
+        }
+    }
+
+    fn callsite_enabled(
+        &self,
+        _metadata: &'static tracing::Metadata<'static>,
+    ) -> tracing::subscriber::Interest {
+        // Levels change at runtime, so decisions must not be cached per callsite
+        tracing::subscriber::Interest::sometimes()
+    }
+}
+
+/// `tracing` layer keeping events in a [`LogBuffer`]
+///
+/// The renderer and platform log through plain `tracing` macros; install
+/// this layer next to the fmt layer so their events show up in in-app
+/// consoles too. Events are filed under the category of their target (see
+/// [`LogCategory::from_target`]), or under the target's crate when it has
+/// none.
+#[derive(Debug, Clone)]
+pub struct LogBufferLayer {
+    buffer: LogBuffer,
+}
+
+impl LogBufferLayer {
+    /// Create a layer feeding [`LogBuffer::global`]
+    pub fn new() -> Self {
+        Self::with_buffer(LogBuffer::global())
+    }
+
+    /// Create a layer feeding `buffer`
+    pub fn with_buffer(buffer: LogBuffer) -> Self {
+        Self { buffer }
+    }
+}
+
+impl Default for LogBufferLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for LogBufferLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let target = metadata.target();
+        let category = match LogCategory::from_target(target) {
+            Some(category) => category.as_str(),
+            None => target.split("::").next().unwrap_or(target),
+        };
+
+        let mut message = MessageVisitor::default();
+        event.record(&mut message);
+        self.buffer
+            .push(LogLevel::from(*metadata.level()), category, &message.0);
+    }
+}
+
+/// Collects an event's message followed by its other fields
+#[derive(Default)]
+struct MessageVisitor(String);
+
+impl tracing::field::Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        use std::fmt::Write;
+
+        if field.name() == "message" {
+            let fields = std::mem::take(&mut self.0);
+            let _ = write!(self.0, "{:?}{}", value, fields);
+        } else {
+            let _ = write!(self.0, " {}={:?}", field.name(), value);
+        }
+    }
+
+    fn record_str(&mut self, field: &tracing::field::Field, value: &str) {
+        if field.name() == "message" {
+            self.0.insert_str(0, value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+}
+
+/// Records kept by [`LogBuffer::global`]
+pub const DEFAULT_LOG_BUFFER_CAPACITY: usize = 5000;
+
+/// A log message kept by a [`LogBuffer`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogRecord {
+    /// Position in the stream of all records pushed to the buffer, starting at 1
+    pub sequence: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub level: LogLevel,
+    pub category: String,
+    pub message: String,
+}
+
+#[derive(Debug)]
+struct LogBufferInner {
+    records: VecDeque<LogRecord>,
+    capacity: usize,
+    last_sequence: u64,
+}
+
+/// Ring buffer of the most recent log records
+///
+/// A cheap, cloneable handle; clones share the same records. Once full, the
+/// oldest record is dropped for every new one.
+#[derive(Debug, Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<LogBufferInner>>,
+}
+
+impl LogBuffer {
+    /// Create a buffer keeping at most `capacity` records
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(LogBufferInner {
+                records: VecDeque::with_capacity(capacity.min(1024)),
+                capacity: capacity.max(1),
+                last_sequence: 0,
+            })),
+        }
+    }
+
+    /// Access the buffer fed by the logging macros
+    pub fn global() -> LogBuffer {
+        static GLOBAL: OnceLock<LogBuffer> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| LogBuffer::new(DEFAULT_LOG_BUFFER_CAPACITY))
+            .clone()
+    }
+
+    /// Append a record, dropping the oldest one when full
+    pub fn push(&self, level: LogLevel, category: &str, message: &str) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.last_sequence += 1;
+        let record = LogRecord {
+            sequence: inner.last_sequence,
+            timestamp: chrono::Utc::now(),
+            level,
+            category: category.to_string(),
+            message: message.to_string(),
+        };
+        if inner.records.len() == inner.capacity {
+            inner.records.pop_front();
+        }
+        inner.records.push_back(record);
+    }
+
+    /// All records currently kept, oldest first
+    pub fn records(&self) -> Vec<LogRecord> {
+        self.records_since(0)
+    }
+
+    /// Records pushed after the one numbered `sequence`, oldest first
+    pub fn records_since(&self, sequence: u64) -> Vec<LogRecord> {
+        let inner = self.inner.lock().unwrap();
+        let kept = inner.records.len() as u64;
+        let first = inner.last_sequence + 1 - kept;
+        let skip = sequence.saturating_sub(first - 1).min(kept) as usize;
+        inner.records.iter().skip(skip).cloned().collect()
+    }
+
+    /// Sequence number of the newest record, 0 when nothing was pushed yet
+    pub fn last_sequence(&self) -> u64 {
+        self.inner.lock().unwrap().last_sequence
+    }
+
+    /// Maximum number of records kept
+    pub fn capacity(&self) -> usize {
+        self.inner.lock().unwrap().capacity
+    }
+
+    /// Number of records currently kept
+    pub fn len(&self) -> usize {
+        self.inner.lock().unwrap().records.len()
+    }
+
+    /// Check if no records are kept
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop all records; sequence numbers keep counting
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().records.clear();
+    }
+}
+
 /// Initialize the logging system
 pub fn init(config: &LoggingConfig) -> Result<(), Box<dyn std::error::Error>> {
     let logger_config = LoggerConfig::new(config.clone());
+    apply_category_levels(config);
     LOGGER.set(Arc::new(RwLock::new(logger_config))).ok();
     Ok(())
 }
@@ -193,20 +538,21 @@ fn get_logger() -> Option<Arc<RwLock<LoggerConfig>>> {
 
 /// Internal logging function
 pub fn log_internal(level: LogLevel, category: &str, message: &str, rate_limited: bool) {
+    // Check if level is enabled for this category
+    if !is_enabled(category, level) {
+        return;
+    }
+
     if let Some(logger) = get_logger() {
         let logger_guard = logger.read().unwrap();
 
-        // Check if level is enabled for this category
-        if !logger_guard.is_level_enabled(category, level) {
-            return;
-        }
-
         // Check rate limiting if requested
         if rate_limited && !logger_guard.should_allow_log(category) {
             return;
         }
 
         drop(logger_guard); // Release the lock before printing
+        LogBuffer::global().push(level, category, message);
 
         // Format and print the log message
         let timestamp = chrono::Utc::now().format("%Y-%m-%d %H:%M:%S%.3f");
@@ -219,6 +565,7 @@ pub fn log_internal(level: LogLevel, category: &str, message: &str, rate_limited
         );
     } else {
         // Fallback if logger not initialized
+        LogBuffer::global().push(level, category, message);
         println!(
             "[UNINITIALIZED] [{}] [{}] {}",
             level.as_str().to_uppercase(),
@@ -230,6 +577,7 @@ pub fn log_internal(level: LogLevel, category: &str, message: &str, rate_limited
 
 /// Update logger configuration
 pub fn update_config(config: LoggingConfig) {
+    apply_category_levels(&config);
     if let Some(logger) = get_logger() {
         let mut logger_guard = logger.write().unwrap();
         logger_guard.update_config(config);
@@ -240,35 +588,60 @@ pub fn update_config(config: LoggingConfig) {
 #[macro_export]
 macro_rules! strato_trace {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Trace, &$category.to_string(), &format!($($arg)*), false);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Trace) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Trace, category, &format!($($arg)*), false);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_debug {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Debug, &$category.to_string(), &format!($($arg)*), false);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Debug) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Debug, category, &format!($($arg)*), false);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_info {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Info, &$category.to_string(), &format!($($arg)*), false);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Info) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Info, category, &format!($($arg)*), false);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_warn {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Warn, &$category.to_string(), &format!($($arg)*), false);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Warn) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Warn, category, &format!($($arg)*), false);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_error {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Error, &$category.to_string(), &format!($($arg)*), false);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Error) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Error, category, &format!($($arg)*), false);
+            }
+        }
     };
 }
 
@@ -276,35 +649,60 @@ macro_rules! strato_error {
 #[macro_export]
 macro_rules! strato_trace_rate_limited {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Trace, &$category.to_string(), &format!($($arg)*), true);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Trace) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Trace, category, &format!($($arg)*), true);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_debug_rate_limited {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Debug, &$category.to_string(), &format!($($arg)*), true);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Debug) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Debug, category, &format!($($arg)*), true);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_info_rate_limited {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Info, &$category.to_string(), &format!($($arg)*), true);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Info) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Info, category, &format!($($arg)*), true);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_warn_rate_limited {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Warn, &$category.to_string(), &format!($($arg)*), true);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Warn) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Warn, category, &format!($($arg)*), true);
+            }
+        }
     };
 }
 
 #[macro_export]
 macro_rules! strato_error_rate_limited {
     ($category:expr, $($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Error, &$category.to_string(), &format!($($arg)*), true);
+        {
+            let category = $crate::logging::category_name(&$category);
+            if $crate::logging::is_enabled(category, $crate::logging::LogLevel::Error) {
+                $crate::logging::log_internal($crate::logging::LogLevel::Error, category, &format!($($arg)*), true);
+            }
+        }
     };
 }
 
@@ -312,7 +710,9 @@ macro_rules! strato_error_rate_limited {
 #[macro_export]
 macro_rules! strato_text_debug {
     ($($arg:tt)*) => {
-        $crate::logging::log_internal($crate::logging::LogLevel::Debug, "text", &format!($($arg)*), false);
+        if $crate::logging::is_enabled("text", $crate::logging::LogLevel::Debug) {
+            $crate::logging::log_internal($crate::logging::LogLevel::Debug, "text", &format!($($arg)*), false);
+        }
     };
 }
 
@@ -383,4 +783,51 @@ mod tests {
         // Test rate limiting
         assert!(logger_config.should_allow_log("test"));
     }
+
+    #[test]
+    fn test_runtime_category_levels() {
+        // Only Audio is touched here so other tests see their usual levels
+        assert!(!is_enabled("audio", LogLevel::Debug));
+
+        set_category_level(LogCategory::Audio, LogLevel::Debug);
+        assert_eq!(category_level(LogCategory::Audio), LogLevel::Debug);
+        assert!(is_enabled("audio", LogLevel::Debug));
+        assert!(!is_enabled("audio", LogLevel::Trace));
+        assert!(get_levels().contains(&(LogCategory::Audio, LogLevel::Debug)));
+
+        set_category_level(LogCategory::Audio, LogLevel::Error);
+        assert!(!is_enabled("audio", LogLevel::Warn));
+    }
+
+    #[test]
+    fn test_log_buffer_keeps_newest_records() {
+        let buffer = LogBuffer::new(3);
+        for i in 0..5 {
+            buffer.push(LogLevel::Info, "core", &format!("message {}", i));
+        }
+
+        let messages: Vec<_> = buffer.records().into_iter().map(|r| r.message).collect();
+        assert_eq!(messages, ["message 2", "message 3", "message 4"]);
+        assert_eq!(buffer.last_sequence(), 5);
+
+        let newer = buffer.records_since(4);
+        assert_eq!(newer.len(), 1);
+        assert_eq!(newer[0].sequence, 5);
+        assert!(buffer.records_since(5).is_empty());
+        // Records older than the buffer are simply gone
+        assert_eq!(buffer.records_since(1).len(), 3);
+    }
+
+    #[test]
+    fn test_category_names_and_targets() {
+        for category in LogCategory::ALL {
+            assert_eq!(LogCategory::from_name(category.as_str()), Some(category));
+        }
+        assert_eq!(
+            LogCategory::from_target("strato_renderer::gpu::device"),
+            Some(LogCategory::Renderer)
+        );
+        assert_eq!(LogCategory::from_target("input"), Some(LogCategory::Input));
+        assert_eq!(LogCategory::from_target("wgpu_core::device"), None);
+    }
 }
@@ -0,0 +1,606 @@
+//! Persistent state that survives application restarts
+//!
+//! A [`StateStore`] maps string keys to JSON encoded values and writes them to
+//! a pluggable [`StateBackend`]: a JSON file on desktop, `localStorage` on the
+//! web, or memory in tests. [`PersistentSignal`] is a [`Signal`] that loads its
+//! initial value from a store and writes every change back.
+//!
+//! Writes are debounced: a burst of changes results in a single backend write
+//! once the value has been quiet for the store's debounce interval. Pending
+//! writes are flushed when the last store handle is dropped, explicitly with
+//! [`StateStore::flush`], or for every live store with [`flush_all`], which
+//! the platform layer calls when the application exits.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use parking_lot::{Mutex, RwLock};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::error::{Result, StratoError};
+use crate::logging::LogCategory;
+use crate::state::Signal;
+
+/// Default quiet period before pending values are written
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Key-value storage used by a [`StateStore`]
+pub trait StateBackend: Send + Sync {
+    /// Read the raw JSON stored under `key`
+    fn read(&self, key: &str) -> Option<String>;
+
+    /// Store raw JSON under `key`
+    fn write(&self, key: &str, value: &str) -> Result<()>;
+
+    /// Remove the value stored under `key`
+    fn remove(&self, key: &str) -> Result<()>;
+}
+
+/// Backend that keeps values in memory only
+#[derive(Debug, Default)]
+pub struct MemoryBackend {
+    values: RwLock<HashMap<String, String>>,
+}
+
+impl MemoryBackend {
+    /// Create an empty memory backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl StateBackend for MemoryBackend {
+    fn read(&self, key: &str) -> Option<String> {
+        self.values.read().get(key).cloned()
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<()> {
+        self.values
+            .write()
+            .insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        self.values.write().remove(key);
+        Ok(())
+    }
+}
+
+/// Backend that stores all values in a single JSON object on disk
+///
+/// A missing or corrupt file is treated as empty, so every key falls back to
+/// its default; the file is rewritten on the next change.
+#[derive(Debug)]
+pub struct JsonFileBackend {
+    path: PathBuf,
+    values: RwLock<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl JsonFileBackend {
+    /// Open the store file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let values = match std::fs::read_to_string(&path) {
+            Ok(contents) => match serde_json::from_str(&contents) {
+                Ok(serde_json::Value::Object(values)) => values,
+                _ => {
+                    crate::strato_warn!(
+                        LogCategory::Core,
+                        "Ignoring corrupt state file {}",
+                        path.display()
+                    );
+                    serde_json::Map::new()
+                }
+            },
+            Err(_) => serde_json::Map::new(),
+        };
+
+        Self {
+            path,
+            values: RwLock::new(values),
+        }
+    }
+
+    /// Location of the store file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn save(&self, values: &serde_json::Map<String, serde_json::Value>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(values)
+            .map_err(|e| StratoError::state(format!("Failed to encode state: {}", e)))?;
+
+        // Write through a temporary file so a crash never leaves a torn file
+        let temp = self.path.with_extension("json.tmp");
+        std::fs::write(&temp, contents)?;
+        std::fs::rename(&temp, &self.path)?;
+        Ok(())
+    }
+}
+
+impl StateBackend for JsonFileBackend {
+    fn read(&self, key: &str) -> Option<String> {
+        self.values.read().get(key).map(|value| value.to_string())
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<()> {
+        let value = serde_json::from_str(value)
+            .map_err(|e| StratoError::state(format!("Invalid JSON for '{}': {}", key, e)))?;
+        let mut values = self.values.write();
+        values.insert(key.to_string(), value);
+        self.save(&values)
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        let mut values = self.values.write();
+        if values.remove(key).is_some() {
+            self.save(&values)?;
+        }
+        Ok(())
+    }
+}
+
+/// Backend that stores values in the browser's `localStorage`
+#[cfg(target_arch = "wasm32")]
+#[derive(Debug)]
+pub struct LocalStorageBackend {
+    prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorageBackend {
+    /// Store values under keys starting with `prefix`
+    pub fn new(prefix: impl Into<String>) -> Self {
+        Self {
+            prefix: prefix.into(),
+        }
+    }
+
+    fn storage() -> Option<web_sys::Storage> {
+        web_sys::window()?.local_storage().ok().flatten()
+    }
+
+    fn item_key(&self, key: &str) -> String {
+        format!("{}.{}", self.prefix, key)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl StateBackend for LocalStorageBackend {
+    fn read(&self, key: &str) -> Option<String> {
+        Self::storage()?
+            .get_item(&self.item_key(key))
+            .ok()
+            .flatten()
+    }
+
+    fn write(&self, key: &str, value: &str) -> Result<()> {
+        let storage =
+            Self::storage().ok_or_else(|| StratoError::state("localStorage is unavailable"))?;
+        storage
+            .set_item(&self.item_key(key), value)
+            .map_err(|_| StratoError::state(format!("Failed to store '{}'", key)))
+    }
+
+    fn remove(&self, key: &str) -> Result<()> {
+        if let Some(storage) = Self::storage() {
+            let _ = storage.remove_item(&self.item_key(key));
+        }
+        Ok(())
+    }
+}
+
+struct StoreInner {
+    backend: Box<dyn StateBackend>,
+    pending: Mutex<HashMap<String, String>>,
+    debounce: Duration,
+    #[cfg(not(target_arch = "wasm32"))]
+    wake: Mutex<Option<std::sync::mpsc::Sender<()>>>,
+}
+
+impl StoreInner {
+    fn flush(&self) -> Result<()> {
+        let pending = std::mem::take(&mut *self.pending.lock());
+        let mut result = Ok(());
+        for (key, value) in pending {
+            if let Err(e) = self.backend.write(&key, &value) {
+                crate::strato_warn!(LogCategory::Core, "Failed to persist '{}': {}", key, e);
+                result = Err(e);
+            }
+        }
+        result
+    }
+}
+
+/// Every store created, so [`flush_all`] can reach those still alive
+static STORES: Mutex<Vec<Weak<StoreInner>>> = Mutex::new(Vec::new());
+
+/// Write the pending values of every live store now
+///
+/// Exiting the process skips the flush stores do when dropped, so call this
+/// on the way out; the platform's event loop does.
+pub fn flush_all() -> Result<()> {
+    let stores: Vec<_> = {
+        let mut stores = STORES.lock();
+        stores.retain(|store| store.strong_count() > 0);
+        stores.iter().filter_map(Weak::upgrade).collect()
+    };
+    let mut result = Ok(());
+    for store in stores {
+        if let Err(e) = store.flush() {
+            result = Err(e);
+        }
+    }
+    result
+}
+
+impl Drop for StoreInner {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+/// Shared handle to a keyed, debounced persistent store
+///
+/// Clones share the same backend and pending writes.
+#[derive(Clone)]
+pub struct StateStore {
+    inner: Arc<StoreInner>,
+}
+
+impl fmt::Debug for StateStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateStore")
+            .field("pending", &self.inner.pending.lock().len())
+            .field("debounce", &self.inner.debounce)
+            .finish()
+    }
+}
+
+impl StateStore {
+    /// Create a store on top of a backend
+    pub fn new(backend: impl StateBackend + 'static) -> Self {
+        Self::with_debounce(backend, DEFAULT_DEBOUNCE)
+    }
+
+    /// Create a store with a custom debounce interval
+    ///
+    /// A zero interval writes every change immediately.
+    pub fn with_debounce(backend: impl StateBackend + 'static, debounce: Duration) -> Self {
+        let inner = Arc::new(StoreInner {
+            backend: Box::new(backend),
+            pending: Mutex::new(HashMap::new()),
+            debounce,
+            #[cfg(not(target_arch = "wasm32"))]
+            wake: Mutex::new(None),
+        });
+        let mut stores = STORES.lock();
+        stores.retain(|store| store.strong_count() > 0);
+        stores.push(Arc::downgrade(&inner));
+        Self { inner }
+    }
+
+    /// Create a store that is never written to disk
+    pub fn memory() -> Self {
+        Self::new(MemoryBackend::new())
+    }
+
+    /// Create a store backed by a JSON file
+    pub fn json_file(path: impl Into<PathBuf>) -> Self {
+        Self::new(JsonFileBackend::new(path))
+    }
+
+    /// Create the platform's default store for an application
+    ///
+    /// On desktop this is `<config dir>/<app_name>/state.json`; on the web
+    /// values live in `localStorage` under keys prefixed with `app_name`.
+    pub fn for_app(app_name: &str) -> Self {
+        #[cfg(target_arch = "wasm32")]
+        {
+            Self::new(LocalStorageBackend::new(app_name))
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            Self::json_file(config_dir().join(app_name).join("state.json"))
+        }
+    }
+
+    /// Load the value stored under `key`
+    ///
+    /// Returns `None` if the key is missing or its value cannot be decoded as `T`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let raw = match self.inner.pending.lock().get(key) {
+            Some(raw) => raw.clone(),
+            None => self.inner.backend.read(key)?,
+        };
+        match serde_json::from_str(&raw) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                crate::strato_warn!(
+                    LogCategory::Core,
+                    "Ignoring corrupt persisted value '{}': {}",
+                    key,
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// Schedule `value` to be written under `key`
+    pub fn set<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        let raw = serde_json::to_string(value)
+            .map_err(|e| StratoError::state(format!("Failed to encode '{}': {}", key, e)))?;
+        self.inner.pending.lock().insert(key.to_string(), raw);
+        self.schedule_flush()
+    }
+
+    /// Remove the value stored under `key`
+    pub fn remove(&self, key: &str) -> Result<()> {
+        self.inner.pending.lock().remove(key);
+        self.inner.backend.remove(key)
+    }
+
+    /// Write all pending values to the backend now
+    pub fn flush(&self) -> Result<()> {
+        self.inner.flush()
+    }
+
+    /// Number of values waiting to be written
+    pub fn pending_count(&self) -> usize {
+        self.inner.pending.lock().len()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn schedule_flush(&self) -> Result<()> {
+        // No background threads on the web; localStorage writes are cheap
+        self.flush()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn schedule_flush(&self) -> Result<()> {
+        if self.inner.debounce.is_zero() {
+            return self.flush();
+        }
+
+        let mut wake = self.inner.wake.lock();
+        if let Some(sender) = wake.as_ref() {
+            if sender.send(()).is_ok() {
+                return Ok(());
+            }
+        }
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let inner = Arc::downgrade(&self.inner);
+        let debounce = self.inner.debounce;
+        std::thread::Builder::new()
+            .name("strato-state-store".into())
+            .spawn(move || debounce_writes(inner, receiver, debounce))?;
+        let _ = sender.send(());
+        *wake = Some(sender);
+        Ok(())
+    }
+}
+
+/// Flush the store once no change has arrived for `debounce`
+#[cfg(not(target_arch = "wasm32"))]
+fn debounce_writes(
+    inner: Weak<StoreInner>,
+    receiver: std::sync::mpsc::Receiver<()>,
+    debounce: Duration,
+) {
+    use std::sync::mpsc::RecvTimeoutError;
+
+    while receiver.recv().is_ok() {
+        loop {
+            match receiver.recv_timeout(debounce) {
+                Ok(()) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                // The store was dropped and flushed itself
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        match inner.upgrade() {
+            Some(inner) => {
+                let _ = inner.flush();
+            }
+            None => return,
+        }
+    }
+}
+
+/// Per-user configuration directory on desktop platforms
+#[cfg(not(target_arch = "wasm32"))]
+fn config_dir() -> PathBuf {
+    let env_path = |name: &str| std::env::var_os(name).map(PathBuf::from);
+
+    #[cfg(target_os = "windows")]
+    let dir = env_path("APPDATA");
+
+    #[cfg(target_os = "macos")]
+    let dir = env_path("HOME").map(|home| home.join("Library").join("Application Support"));
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dir =
+        env_path("XDG_CONFIG_HOME").or_else(|| env_path("HOME").map(|home| home.join(".config")));
+
+    dir.unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// A [`Signal`] whose value is persisted in a [`StateStore`]
+///
+/// The initial value is loaded from the store, falling back to the provided
+/// default when the key is missing or corrupt. Every change is written back.
+pub struct PersistentSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    key: String,
+    signal: Signal<T>,
+    store: StateStore,
+}
+
+impl<T> PersistentSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    /// Create a signal persisted under `key`
+    pub fn new(store: &StateStore, key: impl Into<String>, default: T) -> Self {
+        let key = key.into();
+        let signal = Signal::new(store.get(&key).unwrap_or(default));
+
+        let writer = store.clone();
+        let writer_key = key.clone();
+        // The subscription lives as long as the signal itself
+        signal.retain(signal.subscribe(Box::new(move |value| {
+            if let Some(value) = value.downcast_ref::<T>() {
+                if let Err(e) = writer.set(&writer_key, value) {
+                    crate::strato_warn!(
+                        LogCategory::Core,
+                        "Failed to persist '{}': {}",
+                        writer_key,
+                        e
+                    );
+                }
+            }
+        })));
+
+        Self {
+            key,
+            signal,
+            store: store.clone(),
+        }
+    }
+
+    /// Key the value is stored under
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The underlying signal
+    pub fn signal(&self) -> &Signal<T> {
+        &self.signal
+    }
+
+    /// The store the value is persisted in
+    pub fn store(&self) -> &StateStore {
+        &self.store
+    }
+}
+
+impl<T> Deref for PersistentSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    type Target = Signal<T>;
+
+    fn deref(&self) -> &Signal<T> {
+        &self.signal
+    }
+}
+
+impl<T> Clone for PersistentSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            signal: self.signal.clone(),
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for PersistentSignal<T>
+where
+    T: Clone + Send + Sync + Serialize + DeserializeOwned + fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PersistentSignal")
+            .field("key", &self.key)
+            .field("value", &self.signal.peek())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!(
+                "strato-persistence-{}-{}",
+                std::process::id(),
+                name
+            ))
+            .join("state.json")
+    }
+
+    #[test]
+    fn test_persistent_signal_round_trip() {
+        let path = temp_path("round-trip");
+        {
+            let store = StateStore::json_file(&path);
+            let counter = PersistentSignal::new(&store, "counter", 0);
+            assert_eq!(counter.get(), 0);
+            counter.set(7);
+        }
+
+        let store = StateStore::json_file(&path);
+        let counter = PersistentSignal::new(&store, "counter", 0);
+        assert_eq!(counter.get(), 7);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_writes_are_debounced() {
+        let store = StateStore::with_debounce(MemoryBackend::new(), Duration::from_millis(20));
+        let value = PersistentSignal::new(&store, "value", String::new());
+        value.set("a".into());
+        value.set("b".into());
+        assert_eq!(store.pending_count(), 1);
+        assert_eq!(store.get::<String>("value").as_deref(), Some("b"));
+
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(store.pending_count(), 0);
+        assert_eq!(store.get::<String>("value").as_deref(), Some("b"));
+    }
+
+    #[test]
+    fn test_corrupt_data_falls_back_to_default() {
+        let path = temp_path("corrupt");
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "{ not json").unwrap();
+        let store = StateStore::json_file(&path);
+        assert_eq!(PersistentSignal::new(&store, "volume", 3).get(), 3);
+
+        let backend = MemoryBackend::new();
+        backend.write("volume", "\"loud\"").unwrap();
+        let store = StateStore::new(backend);
+        assert_eq!(PersistentSignal::new(&store, "volume", 3).get(), 3);
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_flush_all_writes_pending_values() {
+        let path = temp_path("flush-all");
+        let store = StateStore::with_debounce(JsonFileBackend::new(&path), Duration::from_secs(60));
+        store.set("theme", &"dark").unwrap();
+
+        flush_all().unwrap();
+        assert_eq!(store.pending_count(), 0);
+        let reopened = StateStore::json_file(&path);
+        assert_eq!(reopened.get::<String>("theme").as_deref(), Some("dark"));
+        let _ = std::fs::remove_dir_all(path.parent().unwrap());
+    }
+}
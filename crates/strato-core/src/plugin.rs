@@ -65,27 +65,52 @@ pub enum PluginState {
 }
 
 /// Plugin trait that all plugins must implement
+///
+/// Only the metadata and the downcasting methods are required; the other
+/// hooks do nothing by default. Applications run by `strato-platform` call
+/// [`initialize`](Self::initialize) and [`activate`](Self::activate) before
+/// the event loop starts, the frame hooks around every frame and
+/// [`handle_event`](Self::handle_event) for every event.
 pub trait Plugin: Send + Sync {
     /// Get plugin metadata
     fn metadata(&self) -> &PluginMetadata;
 
     /// Initialize the plugin
-    fn initialize(&mut self, context: &mut PluginContext) -> Result<()>;
+    fn initialize(&mut self, _context: &mut PluginContext) -> Result<()> {
+        Ok(())
+    }
 
     /// Activate the plugin
-    fn activate(&mut self, context: &mut PluginContext) -> Result<()>;
+    fn activate(&mut self, _context: &mut PluginContext) -> Result<()> {
+        Ok(())
+    }
 
     /// Deactivate the plugin
-    fn deactivate(&mut self, context: &mut PluginContext) -> Result<()>;
+    fn deactivate(&mut self, _context: &mut PluginContext) -> Result<()> {
+        Ok(())
+    }
 
     /// Cleanup plugin resources
-    fn cleanup(&mut self, context: &mut PluginContext) -> Result<()>;
+    fn cleanup(&mut self, _context: &mut PluginContext) -> Result<()> {
+        Ok(())
+    }
 
     /// Handle plugin events
     fn handle_event(&mut self, _event: &Event, _context: &mut PluginContext) -> EventResult {
         EventResult::Ignored
     }
 
+    /// Called at the start of every frame, before layout
+    ///
+    /// `app` is the application drawing the frame, a
+    /// `strato_platform::Application` to downcast to when run by the
+    /// platform layer.
+    fn before_frame(&mut self, _app: &mut dyn Any) {}
+
+    /// Called after every frame has been submitted, with the same `app` as
+    /// [`before_frame`](Self::before_frame)
+    fn after_frame(&mut self, _app: &mut dyn Any) {}
+
     /// Get plugin as Any for downcasting
     fn as_any(&self) -> &dyn Any;
 
@@ -94,6 +119,9 @@ pub trait Plugin: Send + Sync {
 }
 
 /// Plugin context provides access to framework services
+///
+/// Clones share the same registries and storage.
+#[derive(Clone)]
 pub struct PluginContext {
     /// Widget registry
     pub widget_registry: Arc<RwLock<WidgetRegistry>>,
@@ -548,7 +576,7 @@ macro_rules! plugin_metadata {
             description: $description.to_string(),
             author: $author.to_string(),
             dependencies: vec![$($($dep.to_string()),*)?],
-            min_strato_version: plugin_metadata!(@min_version $($min_version)?).to_string(),
+            min_strato_version: $crate::plugin_metadata!(@min_version $($min_version)?).to_string(),
             capabilities: vec![$($($cap),*)?],
         }
     };
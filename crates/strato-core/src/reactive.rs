@@ -1,8 +1,45 @@
 //! Reactive programming primitives for StratoUI
-
-use parking_lot::RwLock;
+//!
+//! # Ownership
+//!
+//! Derived signals and effects live as long as something owns them. Outside
+//! a [`Scope`], a signal's [`computed`](crate::state::Signal::computed)
+//! values are kept alive by the signal itself, so a component removed from
+//! the UI leaves them behind. Code run in a scope, with [`create_scope`] or
+//! [`Scope::run`], hands them to the scope instead, and disposing the scope
+//! drops them all:
+//!
+//! ```
+//! use strato_core::reactive::create_scope;
+//! use strato_core::state::Signal;
+//!
+//! let count = Signal::new(1);
+//! let (doubled, scope) = create_scope(|_| count.map(|n| n * 2));
+//! count.set(2);
+//! assert_eq!(doubled.get(), 4);
+//!
+//! scope.dispose();
+//! count.set(3);
+//! assert_eq!(doubled.get(), 4);
+//! assert_eq!(count.subscriber_count(), 0);
+//! ```
+//!
+//! # Redrawing on demand
+//!
+//! Setting or updating a [`Signal`](crate::state::Signal) marks the UI
+//! dirty, moving the global [`change_count`] on. A render loop that only
+//! draws when something changed calls [`take_dirty`] once per frame and
+//! skips the frame when it returns `false`, or remembers the count its last
+//! frame drew and skips frames until the count moves past it. Changes made
+//! while drawing, e.g. widgets remembering their bounds, are run in
+//! [`without_marking_dirty`] so they don't ask for another frame.
+
+use parking_lot::{Mutex, RwLock};
 use smallvec::SmallVec;
-use std::sync::Arc;
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 // Removed unused std::fmt::Debug import
 use std::marker::PhantomData;
 
@@ -87,6 +124,12 @@ impl Effect {
             active: Arc::new(RwLock::new(true)),
         };
 
+        // An effect created in a scope stops with it
+        if let Some(scope) = current_scope() {
+            let active = Arc::clone(&effect.active);
+            scope.on_cleanup(move || *active.write() = false);
+        }
+
         // Run the effect immediately
         effect.run();
 
@@ -175,6 +218,7 @@ impl<T: Clone + Send + Sync + 'static> Watch<T> {
     /// Set a new value and trigger callbacks
     pub fn set(&self, value: T) {
         *self.value.write() = value.clone();
+        mark_dirty();
         let callbacks = self.callbacks.read();
         for callback in callbacks.iter() {
             callback(&value);
@@ -226,6 +270,245 @@ impl Default for Batch {
     }
 }
 
+/// Changes marked so far, starting at one so the first frame is drawn
+static CHANGES: AtomicU64 = AtomicU64::new(1);
+
+/// [`change_count`] as of the last [`take_dirty`]
+static TAKEN: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// Depth of [`without_marking_dirty`] calls running on this thread
+    static QUIET: Cell<u32> = const { Cell::new(0) };
+}
+
+/// Mark the UI as needing a redraw
+///
+/// Signals do this whenever they are set. Call it after changing state the
+/// UI reads that isn't held in a signal.
+pub fn mark_dirty() {
+    if QUIET.with(Cell::get) == 0 {
+        CHANGES.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+/// Whether anything was marked dirty since the last call, dirty at start so
+/// the first frame is drawn
+///
+/// Only one loop should call this, since each call consumes the changes it
+/// reports; others compare [`change_count`] against their own last count.
+pub fn take_dirty() -> bool {
+    let now = change_count();
+    TAKEN.fetch_max(now, Ordering::AcqRel) < now
+}
+
+/// Run `f` without marking the UI dirty for changes it makes on this thread
+///
+/// The render pass runs in here, so signals widgets set while laying out
+/// and drawing a frame don't ask for another one. Changes other threads
+/// make meanwhile still count.
+pub fn without_marking_dirty<R>(f: impl FnOnce() -> R) -> R {
+    struct Exit;
+    impl Drop for Exit {
+        fn drop(&mut self) {
+            QUIET.with(|quiet| quiet.set(quiet.get() - 1));
+        }
+    }
+
+    QUIET.with(|quiet| quiet.set(quiet.get() + 1));
+    let _exit = Exit;
+    f()
+}
+
+/// How many times the UI was marked dirty, only ever growing
+///
+/// A loop that remembers the count it drew needs a new frame once the count
+/// moves past it. Reading it clears nothing, so any number of loops can
+/// watch it.
+pub fn change_count() -> u64 {
+    CHANGES.load(Ordering::Acquire)
+}
+
+/// Owner of the reactive resources created while it runs code
+///
+/// A scope keeps derived signals' subscriptions, effects, values handed to
+/// [`own`](Self::own) and callbacks registered with
+/// [`on_cleanup`](Self::on_cleanup) until it is disposed, either explicitly
+/// with [`dispose`](Self::dispose) or when its last handle is dropped.
+/// Scopes created while another one runs are its children and are disposed
+/// with it.
+///
+/// Handles are cheap to clone and all refer to the same scope.
+#[derive(Clone, Default)]
+pub struct Scope {
+    inner: Arc<ScopeInner>,
+}
+
+#[derive(Default)]
+struct ScopeInner {
+    parent: Weak<ScopeInner>,
+    state: Mutex<ScopeState>,
+}
+
+#[derive(Default)]
+struct ScopeState {
+    disposed: bool,
+    resources: Vec<Box<dyn Any + Send>>,
+    cleanups: Vec<Box<dyn FnOnce() + Send>>,
+    children: Vec<Scope>,
+}
+
+impl ScopeState {
+    /// Run the cleanups and drop the resources, newest first
+    fn release(&mut self) {
+        for child in std::mem::take(&mut self.children) {
+            child.dispose();
+        }
+        while let Some(cleanup) = self.cleanups.pop() {
+            cleanup();
+        }
+        while let Some(resource) = self.resources.pop() {
+            drop(resource);
+        }
+    }
+}
+
+impl Drop for ScopeInner {
+    fn drop(&mut self) {
+        self.state.get_mut().release();
+    }
+}
+
+impl std::fmt::Debug for Scope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.inner.state.lock();
+        f.debug_struct("Scope")
+            .field("disposed", &state.disposed)
+            .field("resources", &state.resources.len())
+            .field("cleanups", &state.cleanups.len())
+            .field("children", &state.children.len())
+            .finish()
+    }
+}
+
+thread_local! {
+    /// Scopes running code on this thread, innermost last
+    static SCOPES: RefCell<Vec<Scope>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The innermost scope running code on this thread, if any
+pub fn current_scope() -> Option<Scope> {
+    SCOPES.with(|scopes| scopes.borrow().last().cloned())
+}
+
+/// Run `f` in a new scope and return its result with the scope
+///
+/// The scope is a child of the scope running the call, if any. Keep the
+/// returned handle for as long as what `f` created should stay alive.
+pub fn create_scope<R>(f: impl FnOnce(&Scope) -> R) -> (R, Scope) {
+    let scope = match current_scope() {
+        Some(parent) => parent.child(),
+        None => Scope::new(),
+    };
+    let result = scope.run(|| f(&scope));
+    (result, scope)
+}
+
+impl Scope {
+    /// Create a scope without a parent
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a scope disposed together with this one
+    pub fn child(&self) -> Scope {
+        let child = Scope {
+            inner: Arc::new(ScopeInner {
+                parent: Arc::downgrade(&self.inner),
+                state: Mutex::default(),
+            }),
+        };
+        let mut state = self.inner.state.lock();
+        if state.disposed {
+            drop(state);
+            child.dispose();
+        } else {
+            state.children.push(child.clone());
+        }
+        child
+    }
+
+    /// Run `f` with this scope as the [current scope](current_scope)
+    pub fn run<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct Exit;
+        impl Drop for Exit {
+            fn drop(&mut self) {
+                SCOPES.with(|scopes| scopes.borrow_mut().pop());
+            }
+        }
+
+        SCOPES.with(|scopes| scopes.borrow_mut().push(self.clone()));
+        let _exit = Exit;
+        f()
+    }
+
+    /// Keep `resource`, such as a [`Subscription`](crate::state::Subscription),
+    /// alive until the scope is disposed
+    ///
+    /// A disposed scope drops it at once.
+    pub fn own(&self, resource: impl Send + 'static) {
+        let mut state = self.inner.state.lock();
+        if !state.disposed {
+            state.resources.push(Box::new(resource));
+        }
+    }
+
+    /// Run `cleanup` when the scope is disposed, or now if it already is
+    pub fn on_cleanup(&self, cleanup: impl FnOnce() + Send + 'static) {
+        let mut state = self.inner.state.lock();
+        if state.disposed {
+            drop(state);
+            cleanup();
+        } else {
+            state.cleanups.push(Box::new(cleanup));
+        }
+    }
+
+    /// Dispose the scope and its children
+    ///
+    /// Cleanups run and owned resources are dropped, the most recent ones
+    /// first. Disposing twice does nothing.
+    pub fn dispose(&self) {
+        let mut released = {
+            let mut state = self.inner.state.lock();
+            if state.disposed {
+                return;
+            }
+            state.disposed = true;
+            ScopeState {
+                disposed: true,
+                resources: std::mem::take(&mut state.resources),
+                cleanups: std::mem::take(&mut state.cleanups),
+                children: std::mem::take(&mut state.children),
+            }
+        };
+        // Released outside the lock, so cleanups may use the scope
+        released.release();
+
+        if let Some(parent) = self.inner.parent.upgrade() {
+            parent
+                .state
+                .lock()
+                .children
+                .retain(|child| !Arc::ptr_eq(&child.inner, &self.inner));
+        }
+    }
+
+    /// Whether the scope has been disposed
+    pub fn is_disposed(&self) -> bool {
+        self.inner.state.lock().disposed
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -260,6 +543,95 @@ mod tests {
         assert_eq!(received.load(Ordering::SeqCst), 42);
     }
 
+    #[test]
+    fn test_disposing_a_scope_releases_what_it_owns() {
+        use crate::state::Signal;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let source = Signal::new(1);
+        let runs = Arc::new(AtomicUsize::new(0));
+        let cleanups = Arc::new(AtomicUsize::new(0));
+        let ((mapped, effect), scope) = create_scope(|scope| {
+            let counter = Arc::clone(&cleanups);
+            scope.on_cleanup(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+            let counter = Arc::clone(&runs);
+            let effect = Effect::new(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+            (source.map(|n| n + 1), effect)
+        });
+        assert_eq!(source.subscriber_count(), 1);
+
+        scope.dispose();
+        scope.dispose();
+        assert!(scope.is_disposed());
+        assert_eq!(cleanups.load(Ordering::SeqCst), 1);
+        assert_eq!(source.subscriber_count(), 0);
+        source.set(5);
+        assert_eq!(mapped.get(), 2);
+        effect.run();
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_child_scopes_are_disposed_with_their_parent() {
+        let (child, parent) = create_scope(|_| create_scope(|_| ()).1);
+        assert!(current_scope().is_none());
+
+        let grandchild = child.child();
+        parent.dispose();
+        assert!(child.is_disposed());
+        assert!(grandchild.is_disposed());
+
+        // Disposing a child alone leaves the parent running
+        let parent = Scope::new();
+        let child = parent.child();
+        child.dispose();
+        assert!(!parent.is_disposed());
+        assert_eq!(parent.inner.state.lock().children.len(), 0);
+    }
+
+    #[test]
+    fn test_setting_a_signal_marks_the_frame_dirty() {
+        use crate::state::Signal;
+
+        let signal = Signal::new(0);
+        let before = change_count();
+        signal.set(1);
+        let after_set = change_count();
+        assert!(after_set > before);
+
+        signal.update(|n| *n += 1);
+        assert!(change_count() > after_set);
+
+        signal.set(3);
+        assert!(take_dirty());
+    }
+
+    #[test]
+    fn test_changes_while_quiet_only_count_from_other_threads() {
+        use crate::state::Signal;
+
+        let signal = Signal::new(0);
+        let before = change_count();
+        without_marking_dirty(|| {
+            for n in 0..1000 {
+                signal.set(n);
+            }
+        });
+        // Other tests may mark changes meanwhile, but not a thousand
+        assert!(change_count() - before < 1000);
+
+        let before = change_count();
+        without_marking_dirty(|| {
+            let signal = signal.clone();
+            std::thread::spawn(move || signal.set(1)).join().unwrap();
+        });
+        assert!(change_count() > before);
+    }
+
     #[test]
     fn test_memo() {
         let call_count = Arc::new(RwLock::new(0));
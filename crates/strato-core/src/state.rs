@@ -9,7 +9,7 @@ use smallvec::SmallVec;
 use std::any::Any;
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Weak};
 
 // Helper for optional serialization
 #[cfg(feature = "serde")]
@@ -51,6 +51,12 @@ pub type StateCallback = Box<dyn Fn(&dyn Any) + Send + Sync>;
 /// Effect function that can be disposed
 pub type EffectFn = Box<dyn Fn() + Send + Sync>;
 
+/// A subscriber as stored by its signal, owned by its [`Subscription`]
+type SubscriberFn = dyn Fn(&dyn Any) + Send + Sync;
+
+/// Subscribers of a signal, shared by all of its clones
+type SubscriberList = RwLock<SmallVec<[Weak<SubscriberFn>; 4]>>;
+
 /// Disposable handle for effects and subscriptions
 pub struct Disposable {
     dispose_fn: Box<dyn FnOnce() + Send>,
@@ -68,6 +74,41 @@ impl Disposable {
     }
 }
 
+/// Guard keeping a signal subscription alive
+///
+/// The signal only holds a weak reference to the callback, so dropping the
+/// guard unsubscribes. A widget keeping the guards of its bindings therefore
+/// stops receiving updates, and stops being kept alive, once it is dropped.
+#[must_use = "the subscription ends when the guard is dropped"]
+pub struct Subscription {
+    callback: Option<Arc<SubscriberFn>>,
+    subscribers: Weak<SubscriberList>,
+}
+
+impl Subscription {
+    /// End the subscription now rather than when the guard goes out of scope
+    pub fn unsubscribe(self) {}
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        drop(self.callback.take());
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            subscribers
+                .write()
+                .retain(|callback| callback.strong_count() > 0);
+        }
+    }
+}
+
+impl std::fmt::Debug for Subscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Subscription")
+            .field("active", &(self.subscribers.strong_count() > 0))
+            .finish()
+    }
+}
+
 /// Reactive context for tracking dependencies
 #[derive(Default)]
 pub struct ReactiveContext {
@@ -143,7 +184,10 @@ impl<'a> Drop for ComputationGuard<'a> {
 pub struct Signal<T: Clone + Send + Sync + 'static> {
     id: StateId,
     value: Arc<RwLock<T>>,
-    subscribers: Arc<RwLock<SmallVec<[StateCallback; 4]>>>,
+    subscribers: Arc<SubscriberList>,
+    // Subscriptions living as long as the signal, e.g. those feeding
+    // derived signals
+    retained: Arc<Mutex<Vec<Subscription>>>,
     context: Arc<ReactiveContext>,
 }
 
@@ -167,6 +211,7 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
             id,
             value: Arc::new(RwLock::new(initial)),
             subscribers: Arc::new(RwLock::new(SmallVec::new())),
+            retained: Arc::new(Mutex::new(Vec::new())),
             context,
         }
     }
@@ -182,7 +227,7 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
         self.value.read().clone()
     }
 
-    /// Set new value and notify subscribers
+    /// Set new value, notify subscribers and mark the UI dirty
     pub fn set(&self, value: T) {
         {
             let mut guard = self.value.write();
@@ -201,11 +246,12 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
             crate::inspector::inspector()
                 .record_state_snapshot(self.id, format!("Updated {}", type_name));
         }
+        crate::reactive::mark_dirty();
         self.notify(&value);
         self.context.invalidate_dependents(self.id);
     }
 
-    /// Update value with a function
+    /// Update value with a function, like [`set`](Self::set)
     pub fn update(&self, f: impl FnOnce(&mut T)) {
         let value = {
             let mut guard = self.value.write();
@@ -224,29 +270,43 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
             crate::inspector::inspector()
                 .record_state_snapshot(self.id, format!("Updated {}", type_name));
         }
+        crate::reactive::mark_dirty();
         self.notify(&value);
         self.context.invalidate_dependents(self.id);
     }
 
     /// Subscribe to value changes
-    pub fn subscribe(&self, callback: StateCallback) -> Disposable {
-        let subscribers = Arc::clone(&self.subscribers);
-        let callback_id = {
-            let mut subs = subscribers.write();
-            let id = subs.len();
-            subs.push(callback);
-            id
-        };
+    ///
+    /// The callback runs until the returned guard is dropped.
+    pub fn subscribe(&self, callback: StateCallback) -> Subscription {
+        let callback: Arc<SubscriberFn> = Arc::from(callback);
+        self.subscribers.write().push(Arc::downgrade(&callback));
+
+        Subscription {
+            callback: Some(callback),
+            subscribers: Arc::downgrade(&self.subscribers),
+        }
+    }
 
-        Disposable::new(move || {
-            // Remove callback by replacing with no-op
-            if let Some(callback) = subscribers.write().get_mut(callback_id) {
-                *callback = Box::new(|_| {});
-            }
-        })
+    /// Keep `subscription` alive for as long as the signal
+    pub(crate) fn retain(&self, subscription: Subscription) {
+        self.retained.lock().push(subscription);
+    }
+
+    /// Number of live subscriptions
+    pub fn subscriber_count(&self) -> usize {
+        self.subscribers
+            .read()
+            .iter()
+            .filter(|callback| callback.strong_count() > 0)
+            .count()
     }
 
     /// Create a computed signal that derives from this signal
+    ///
+    /// The computed signal follows this one until the [scope](crate::reactive::Scope)
+    /// it was created in is disposed, or for as long as this signal lives
+    /// when created outside any scope.
     pub fn computed<U, F>(&self, f: F) -> Signal<U>
     where
         U: Clone + Send + Sync + 'static,
@@ -262,18 +322,25 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
         let computed_clone = computed.clone();
         let f = Arc::new(f);
 
-        self.subscribe(Box::new(move |value: &dyn Any| {
+        let subscription = self.subscribe(Box::new(move |value: &dyn Any| {
             if let Some(typed_value) = value.downcast_ref::<T>() {
                 let new_value = f(typed_value);
                 computed_clone.set(new_value);
             }
         }));
+        // Kept until the scope creating it ends, or else as long as this signal
+        match crate::reactive::current_scope() {
+            Some(scope) => scope.own(subscription),
+            None => self.retain(subscription),
+        }
 
         computed
     }
 
     /// Create an effect that runs when the signal changes
-    pub fn effect<F>(&self, f: F) -> Disposable
+    ///
+    /// The effect stops when the returned guard is dropped.
+    pub fn effect<F>(&self, f: F) -> Subscription
     where
         F: Fn(&T) + Send + Sync + 'static,
     {
@@ -311,10 +378,15 @@ impl<T: Clone + Send + Sync + 'static> Signal<T> {
         })
     }
 
-    /// Notify all subscribers
+    /// Notify all subscribers, pruning those whose guard was dropped
     fn notify(&self, value: &T) {
-        let subscribers = self.subscribers.read();
-        for callback in subscribers.iter() {
+        // Called outside the lock so callbacks may subscribe or unsubscribe
+        let callbacks: SmallVec<[Arc<SubscriberFn>; 4]> = {
+            let mut subscribers = self.subscribers.write();
+            subscribers.retain(|callback| callback.strong_count() > 0);
+            subscribers.iter().filter_map(Weak::upgrade).collect()
+        };
+        for callback in callbacks {
             callback(value as &dyn Any);
         }
     }
@@ -335,6 +407,7 @@ impl<T: Clone + Send + Sync + 'static> Clone for Signal<T> {
             id: self.id,
             value: Arc::clone(&self.value),
             subscribers: Arc::clone(&self.subscribers),
+            retained: Arc::clone(&self.retained),
             context: Arc::clone(&self.context),
         }
     }
@@ -531,6 +604,51 @@ mod tests {
         assert_eq!(counter.load(Ordering::Relaxed), 42);
     }
 
+    #[test]
+    fn test_dropped_subscription_stops_and_is_pruned() {
+        let signal = Signal::new(0);
+        let counter = Arc::new(AtomicI32::new(0));
+        let counter_clone = Arc::clone(&counter);
+
+        let subscription = signal.subscribe(Box::new(move |_: &dyn Any| {
+            counter_clone.fetch_add(1, Ordering::Relaxed);
+        }));
+        signal.set(1);
+        assert_eq!(signal.subscriber_count(), 1);
+
+        drop(subscription);
+        signal.set(2);
+        assert_eq!(counter.load(Ordering::Relaxed), 1);
+        assert_eq!(signal.subscribers.read().len(), 0);
+    }
+
+    #[test]
+    fn test_subscriber_list_stays_bounded() {
+        let signal = Signal::new(0);
+        for i in 0..1_000 {
+            let _subscription = signal.subscribe(Box::new(|_: &dyn Any| {}));
+            signal.set(i);
+        }
+
+        assert!(signal.subscribers.read().len() <= 1);
+        assert_eq!(signal.subscriber_count(), 0);
+    }
+
+    #[test]
+    fn test_callback_may_unsubscribe_during_notify() {
+        let signal = Signal::new(0);
+        let slot: Arc<Mutex<Option<Subscription>>> = Arc::new(Mutex::new(None));
+        let slot_clone = Arc::clone(&slot);
+
+        *slot.lock() = Some(signal.subscribe(Box::new(move |_: &dyn Any| {
+            slot_clone.lock().take();
+        })));
+        signal.set(1);
+
+        assert!(slot.lock().is_none());
+        assert_eq!(signal.subscriber_count(), 0);
+    }
+
     #[test]
     fn test_computed_signal() {
         let base = Signal::new(10);
@@ -3,11 +3,12 @@
 //! Provides comprehensive text layout, shaping, and rendering capabilities
 //! with support for complex scripts, bidirectional text, and typography features.
 
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
 
 /// Text alignment options
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TextAlign {
     Left,
     Center,
@@ -223,6 +224,84 @@ pub struct LayoutResult {
     pub line_count: usize,
 }
 
+/// Vertical metrics of a font at one size, in logical pixels
+///
+/// Text is drawn from the top of its glyphs: the baseline is `ascent` below
+/// the position given to the renderer, and descenders reach `descent` below
+/// the baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LineMetrics {
+    /// Distance from the top of the tallest glyphs down to the baseline
+    pub ascent: f32,
+    /// Distance from the baseline down to the bottom of descenders, positive
+    pub descent: f32,
+    /// Extra space the font asks for between lines
+    pub line_gap: f32,
+    /// Distance from one baseline to the next, `ascent + descent + line_gap`
+    pub line_height: f32,
+}
+
+impl LineMetrics {
+    /// Metrics guessed from the size alone, for when no font data is at hand
+    pub fn approximate(size: f32) -> Self {
+        Self {
+            ascent: size * 0.8,
+            descent: size * 0.2,
+            line_gap: 0.0,
+            line_height: size,
+        }
+    }
+
+    /// Height of a line's glyphs, from the ascent down to the descent
+    pub fn glyph_height(&self) -> f32 {
+        self.ascent + self.descent
+    }
+
+    /// Where to draw a line of text so its glyphs are centered vertically in
+    /// a box `height` tall starting at `top`
+    pub fn centered_top(&self, top: f32, height: f32) -> f32 {
+        top + (height - self.glyph_height()) / 2.0
+    }
+
+    /// The baseline of a line of text drawn at `top`
+    pub fn baseline(&self, top: f32) -> f32 {
+        top + self.ascent
+    }
+}
+
+/// Source of font metrics, see [`set_line_metrics_provider`]
+type LineMetricsProvider = Arc<dyn Fn(&FontDescriptor, f32) -> Option<LineMetrics> + Send + Sync>;
+
+static LINE_METRICS_PROVIDER: RwLock<Option<LineMetricsProvider>> = RwLock::new(None);
+
+/// Install where [`line_metrics`] gets metrics from
+///
+/// This crate parses no fonts, so the crate that loads the fonts text is
+/// drawn with installs a provider; the widgets crate does when an
+/// application is created. The provider returns `None` for fonts it doesn't
+/// know.
+pub fn set_line_metrics_provider(
+    provider: impl Fn(&FontDescriptor, f32) -> Option<LineMetrics> + Send + Sync + 'static,
+) {
+    *LINE_METRICS_PROVIDER
+        .write()
+        .unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(provider));
+}
+
+/// Vertical metrics of `font` at `size` logical pixels
+///
+/// Approximated from the size when no provider is installed or the provider
+/// doesn't know the font.
+pub fn line_metrics(font: &FontDescriptor, size: f32) -> LineMetrics {
+    let provider = LINE_METRICS_PROVIDER
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone();
+    provider
+        .and_then(|provider| provider(font, size))
+        .unwrap_or_else(|| LineMetrics::approximate(size))
+}
+
 /// Font loading and management
 pub struct FontManager {
     fonts: Arc<Mutex<HashMap<String, FontData>>>,
@@ -461,7 +540,7 @@ impl TextMeasurer {
 
     /// Get text baseline position
     pub fn get_baseline(&self, style: &TextStyle) -> f32 {
-        style.font.size * 0.8 // Simplified baseline calculation
+        line_metrics(&style.font, style.font.size).ascent
     }
 }
 
@@ -522,6 +601,11 @@ impl TextSystem {
     pub fn measurer(&self) -> &TextMeasurer {
         &self.measurer
     }
+
+    /// Vertical metrics of `font` at `size`, see [`line_metrics`]
+    pub fn line_metrics(&self, font: &FontDescriptor, size: f32) -> LineMetrics {
+        line_metrics(font, size)
+    }
 }
 
 /// Initialize the global text system
@@ -579,4 +663,29 @@ mod tests {
     fn test_text_system_init() {
         assert!(init_text_system().is_ok());
     }
+
+    #[test]
+    fn test_line_metrics_come_from_the_provider() {
+        set_line_metrics_provider(|font, size| {
+            (font.family == "Metrics Test").then_some(LineMetrics {
+                ascent: size * 0.9,
+                descent: size * 0.3,
+                line_gap: size * 0.1,
+                line_height: size * 1.3,
+            })
+        });
+        let font = FontDescriptor {
+            family: "Metrics Test".to_string(),
+            ..FontDescriptor::default()
+        };
+        let metrics = TextSystem::new().line_metrics(&font, 10.0);
+        assert_eq!(metrics.ascent, 9.0);
+        assert_eq!(metrics.glyph_height(), 12.0);
+        assert_eq!(metrics.centered_top(100.0, 20.0), 104.0);
+        assert_eq!(metrics.baseline(104.0), 113.0);
+
+        // Fonts the provider doesn't know are approximated
+        let unknown = line_metrics(&FontDescriptor::default(), 10.0);
+        assert_eq!(unknown, LineMetrics::approximate(10.0));
+    }
 }
@@ -0,0 +1,237 @@
+//! Mouse emulation and gestures from raw touches
+//!
+//! Platforms report every finger as its own stream of
+//! [`TouchStart`](Event::TouchStart), [`TouchMove`](Event::TouchMove) and
+//! [`TouchEnd`](Event::TouchEnd) events. [`TouchGestures`] follows those
+//! streams and derives the events most widgets understand:
+//!
+//! - A single finger drives the left mouse button, so tapping a button
+//!   clicks it and dragging a slider drags it.
+//! - Two fingers make [`GestureEvent::Pinch`] and [`GestureEvent::Pan`]
+//!   events. When the second finger lands, the emulated button is released
+//!   away from every widget, so a pinch started on a button doesn't click it.
+//!
+//! The desktop event loop runs every touch through a recognizer and
+//! dispatches the derived events right after the raw one.
+//!
+//! # Platforms
+//!
+//! Raw touches come from touch screens on Windows, Linux (X11 with XInput 2,
+//! and Wayland), Android, iOS and the web. macOS has no touch screens and
+//! reports none; its trackpad pinches arrive directly as
+//! [`GestureEvent::Pinch`], and two-finger trackpad scrolling stays precise
+//! [`MouseWheel`](Event::MouseWheel) events like on other platforms.
+
+use crate::event::{Event, GestureEvent, MouseButton, MouseEvent, TouchEvent};
+use glam::Vec2;
+
+/// Where the emulated mouse button is released when a touch stops driving
+/// it, outside the bounds of any widget
+const CANCELLED_POSITION: Vec2 = Vec2::new(f32::MIN, f32::MIN);
+
+/// Follows the fingers down and derives mouse and gesture events
+#[derive(Debug, Clone, Default)]
+pub struct TouchGestures {
+    /// Fingers down, in the order they landed
+    touches: Vec<(u64, Vec2)>,
+    /// The finger driving the emulated mouse, if any
+    pointer: Option<u64>,
+}
+
+impl TouchGestures {
+    /// Create a recognizer with no fingers down
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of fingers down
+    pub fn touch_count(&self) -> usize {
+        self.touches.len()
+    }
+
+    /// Follow `event` and return the events derived from it, in order
+    ///
+    /// Events other than touches derive nothing.
+    pub fn process(&mut self, event: &Event) -> Vec<Event> {
+        match event {
+            Event::TouchStart(touch) => self.start(touch),
+            Event::TouchMove(touch) => self.moved(touch),
+            Event::TouchEnd(touch) => self.end(touch, false),
+            Event::TouchCancel(touch) => self.end(touch, true),
+            _ => Vec::new(),
+        }
+    }
+
+    fn start(&mut self, touch: &TouchEvent) -> Vec<Event> {
+        // A reused id is a new finger
+        self.touches.retain(|&(id, _)| id != touch.id);
+        self.touches.push((touch.id, touch.position));
+
+        match self.touches.len() {
+            // Only a finger landing on an empty screen drives the mouse, so
+            // lifting one finger of a pinch doesn't start a drag
+            1 => {
+                self.pointer = Some(touch.id);
+                vec![
+                    Event::MouseMove(mouse(touch.position, None)),
+                    Event::MouseDown(mouse(touch.position, Some(MouseButton::Left))),
+                ]
+            }
+            _ => match self.pointer.take() {
+                Some(_) => vec![Event::MouseUp(mouse(
+                    CANCELLED_POSITION,
+                    Some(MouseButton::Left),
+                ))],
+                None => Vec::new(),
+            },
+        }
+    }
+
+    fn moved(&mut self, touch: &TouchEvent) -> Vec<Event> {
+        let Some(index) = self.touches.iter().position(|&(id, _)| id == touch.id) else {
+            return Vec::new();
+        };
+        let before = self.pair();
+        self.touches[index].1 = touch.position;
+
+        if self.pointer == Some(touch.id) {
+            return vec![Event::MouseMove(mouse(touch.position, None))];
+        }
+
+        // Only the first two fingers make gestures
+        let (Some((a, b)), Some((new_a, new_b))) = (before, self.pair()) else {
+            return Vec::new();
+        };
+        if index > 1 {
+            return Vec::new();
+        }
+        let center = (new_a + new_b) / 2.0;
+        let mut gestures = Vec::new();
+
+        let delta = center - (a + b) / 2.0;
+        if delta != Vec2::ZERO {
+            gestures.push(Event::Gesture(GestureEvent::Pan { center, delta }));
+        }
+        let distance = a.distance(b);
+        let new_distance = new_a.distance(new_b);
+        if distance > 0.0 && new_distance != distance {
+            gestures.push(Event::Gesture(GestureEvent::Pinch {
+                center,
+                scale: new_distance / distance,
+            }));
+        }
+        gestures
+    }
+
+    fn end(&mut self, touch: &TouchEvent, cancelled: bool) -> Vec<Event> {
+        self.touches.retain(|&(id, _)| id != touch.id);
+        if self.pointer != Some(touch.id) {
+            return Vec::new();
+        }
+        self.pointer = None;
+        let position = if cancelled {
+            CANCELLED_POSITION
+        } else {
+            touch.position
+        };
+        vec![Event::MouseUp(mouse(position, Some(MouseButton::Left)))]
+    }
+
+    /// Positions of the first two fingers down
+    fn pair(&self) -> Option<(Vec2, Vec2)> {
+        match self.touches.as_slice() {
+            [(_, a), (_, b), ..] => Some((*a, *b)),
+            _ => None,
+        }
+    }
+}
+
+fn mouse(position: Vec2, button: Option<MouseButton>) -> MouseEvent {
+    MouseEvent {
+        position,
+        button,
+        modifiers: Default::default(),
+        delta: Vec2::ZERO,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(id: u64, x: f32, y: f32) -> TouchEvent {
+        TouchEvent {
+            id,
+            position: Vec2::new(x, y),
+            force: None,
+        }
+    }
+
+    #[test]
+    fn test_a_single_finger_drives_the_left_button() {
+        let mut gestures = TouchGestures::new();
+        let down = gestures.process(&Event::TouchStart(touch(1, 10.0, 20.0)));
+        assert!(matches!(
+            down.as_slice(),
+            [Event::MouseMove(_), Event::MouseDown(mouse)]
+                if mouse.button == Some(MouseButton::Left) && mouse.position == Vec2::new(10.0, 20.0)
+        ));
+        let moved = gestures.process(&Event::TouchMove(touch(1, 15.0, 20.0)));
+        assert!(matches!(moved.as_slice(), [Event::MouseMove(_)]));
+        let up = gestures.process(&Event::TouchEnd(touch(1, 15.0, 20.0)));
+        assert!(matches!(
+            up.as_slice(),
+            [Event::MouseUp(mouse)] if mouse.position == Vec2::new(15.0, 20.0)
+        ));
+        assert_eq!(gestures.touch_count(), 0);
+    }
+
+    #[test]
+    fn test_two_fingers_pinch_and_pan() {
+        let mut gestures = TouchGestures::new();
+        gestures.process(&Event::TouchStart(touch(1, 0.0, 0.0)));
+        // The second finger releases the emulated button away from widgets
+        let cancel = gestures.process(&Event::TouchStart(touch(2, 100.0, 0.0)));
+        assert!(matches!(
+            cancel.as_slice(),
+            [Event::MouseUp(mouse)] if mouse.position == CANCELLED_POSITION
+        ));
+
+        let spread = gestures.process(&Event::TouchMove(touch(2, 200.0, 0.0)));
+        let spread: Vec<GestureEvent> = spread
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Gesture(gesture) => Some(gesture),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            spread,
+            vec![
+                GestureEvent::Pan {
+                    center: Vec2::new(100.0, 0.0),
+                    delta: Vec2::new(50.0, 0.0),
+                },
+                GestureEvent::Pinch {
+                    center: Vec2::new(100.0, 0.0),
+                    scale: 2.0,
+                },
+            ]
+        );
+
+        let slide = gestures.process(&Event::TouchMove(touch(1, 0.0, 10.0)));
+        assert!(matches!(
+            slide.as_slice(),
+            [Event::Gesture(GestureEvent::Pan { delta, .. }), Event::Gesture(GestureEvent::Pinch { .. })]
+                if *delta == Vec2::new(0.0, 5.0)
+        ));
+
+        // The finger left behind doesn't take over the mouse
+        assert!(gestures
+            .process(&Event::TouchEnd(touch(2, 200.0, 0.0)))
+            .is_empty());
+        assert!(gestures
+            .process(&Event::TouchMove(touch(1, 0.0, 50.0)))
+            .is_empty());
+    }
+}
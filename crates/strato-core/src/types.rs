@@ -1,6 +1,7 @@
 //! Common types used throughout StratoUI
 
 use glam::{Mat4, Vec2, Vec3, Vec4};
+use serde::{Deserialize, Serialize};
 // Removed unused std::fmt import
 
 /// Unique identifier for DOM nodes
@@ -73,6 +74,16 @@ impl Size {
     pub fn is_empty(&self) -> bool {
         self.width <= 0.0 || self.height <= 0.0
     }
+
+    /// The larger of each dimension
+    pub fn max(self, other: Size) -> Size {
+        Size::new(self.width.max(other.width), self.height.max(other.height))
+    }
+
+    /// The smaller of each dimension
+    pub fn min(self, other: Size) -> Size {
+        Size::new(self.width.min(other.width), self.height.min(other.height))
+    }
 }
 
 impl From<Vec2> for Size {
@@ -82,7 +93,7 @@ impl From<Vec2> for Size {
 }
 
 /// RGBA color representation
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Color {
     pub r: f32,
     pub g: f32,
@@ -257,7 +268,7 @@ impl Default for Color {
 }
 
 /// 2D point
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Point {
     pub x: f32,
     pub y: f32,
@@ -293,6 +304,67 @@ impl std::ops::Add for Point {
     }
 }
 
+impl std::ops::Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl std::ops::Mul<f32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl std::ops::Div<f32> for Point {
+    type Output = Point;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Point::new(self.x / rhs, self.y / rhs)
+    }
+}
+
+impl std::ops::Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl std::ops::AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl std::ops::SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+/// Offset a point by a size, e.g. from a rectangle's origin to its far corner
+impl std::ops::Add<Size> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Size) -> Self::Output {
+        Point::new(self.x + rhs.width, self.y + rhs.height)
+    }
+}
+
+impl std::ops::Sub<Size> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Size) -> Self::Output {
+        Point::new(self.x - rhs.width, self.y - rhs.height)
+    }
+}
+
 impl std::ops::Add for Size {
     type Output = Size;
 
@@ -301,6 +373,30 @@ impl std::ops::Add for Size {
     }
 }
 
+impl std::ops::Sub for Size {
+    type Output = Size;
+
+    fn sub(self, rhs: Size) -> Self::Output {
+        Size::new(self.width - rhs.width, self.height - rhs.height)
+    }
+}
+
+impl std::ops::Mul<f32> for Size {
+    type Output = Size;
+
+    fn mul(self, rhs: f32) -> Self::Output {
+        Size::new(self.width * rhs, self.height * rhs)
+    }
+}
+
+impl std::ops::Div<f32> for Size {
+    type Output = Size;
+
+    fn div(self, rhs: f32) -> Self::Output {
+        Size::new(self.width / rhs, self.height / rhs)
+    }
+}
+
 impl From<Vec2> for Point {
     fn from(vec: Vec2) -> Self {
         Self { x: vec.x, y: vec.y }
@@ -308,7 +404,7 @@ impl From<Vec2> for Point {
 }
 
 /// Rectangle
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -389,7 +485,7 @@ impl Rect {
 }
 
 /// 2D transformation matrix
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Transform {
     matrix: Mat4,
 }
@@ -455,7 +551,7 @@ impl Default for Transform {
 }
 
 /// Border radius for rounded rectangles
-#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
 pub struct BorderRadius {
     pub top_left: f32,
     pub top_right: f32,
@@ -486,14 +582,14 @@ impl BorderRadius {
 }
 
 /// Gradient stop
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct GradientStop {
     pub color: Color,
     pub position: f32,
 }
 
 /// Linear gradient
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LinearGradient {
     pub start: Point,
     pub end: Point,
@@ -525,6 +621,36 @@ impl LinearGradient {
     }
 }
 
+/// What a shape is filled with
+#[derive(Debug, Clone, PartialEq)]
+pub enum Background {
+    /// A single color
+    Solid(Color),
+    /// A linear gradient across the shape's bounds
+    Gradient(LinearGradient),
+    /// `width * height` RGBA8 pixels stretched over the shape's bounds
+    ///
+    /// Pixels are uploaded once per `id`, so keep it for the same image.
+    Image {
+        id: u64,
+        data: std::sync::Arc<Vec<u8>>,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl From<Color> for Background {
+    fn from(color: Color) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<LinearGradient> for Background {
+    fn from(gradient: LinearGradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
 /// Shadow effect
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Shadow {
@@ -590,6 +716,31 @@ mod tests {
         assert_eq!(intersection, Rect::new(5.0, 5.0, 5.0, 5.0));
     }
 
+    #[test]
+    fn test_point_and_size_operators() {
+        let a = Point::new(1.0, 2.0);
+        let b = Point::new(3.0, 5.0);
+        assert_eq!(a + b, Point::new(4.0, 7.0));
+        assert_eq!(b - a, Point::new(2.0, 3.0));
+        assert_eq!(a * 2.0, Point::new(2.0, 4.0));
+        assert_eq!(b / 2.0, Point::new(1.5, 2.5));
+        assert_eq!(-a, Point::new(-1.0, -2.0));
+
+        let mut c = a;
+        c += b;
+        c -= Point::new(1.0, 1.0);
+        assert_eq!(c, Point::new(3.0, 6.0));
+
+        let size = Size::new(10.0, 20.0);
+        assert_eq!(a + size, Point::new(11.0, 22.0));
+        assert_eq!(a - size, Point::new(-9.0, -18.0));
+        assert_eq!(size - Size::new(4.0, 5.0), Size::new(6.0, 15.0));
+        assert_eq!(size * 0.5, Size::new(5.0, 10.0));
+        assert_eq!(size / 4.0, Size::new(2.5, 5.0));
+        assert_eq!(size.max(Size::new(15.0, 5.0)), Size::new(15.0, 20.0));
+        assert_eq!(size.min(Size::new(15.0, 5.0)), Size::new(10.0, 5.0));
+    }
+
     #[test]
     fn test_transform() {
         let transform = Transform::translate(10.0, 20.0);
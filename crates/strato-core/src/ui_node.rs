@@ -60,22 +60,85 @@ impl Clone for PropValue {
     }
 }
 
-// Helpers for manual construction if needed (though macro handles this)
+/// Builder API
+///
+/// Props keep their insertion order and may repeat; widgets apply them in
+/// order, so a later value wins. How children are used is up to each widget:
+/// containers like `Column` take all of them, `Container` takes the first.
 impl UiNode {
+    /// Start a widget node
     pub fn widget(name: impl Into<String>) -> Self {
-        UiNode::Widget(WidgetNode {
-            name: name.into(),
-            props: Vec::new(),
-            children: Vec::new(),
-        })
+        UiNode::Widget(WidgetNode::new(name))
     }
 
+    /// Create a text node
     pub fn text(text: impl Into<String>) -> Self {
         UiNode::Text(text.into())
     }
+
+    /// Group nodes without a widget of their own
+    pub fn fragment(children: Vec<UiNode>) -> Self {
+        UiNode::Fragment(children)
+    }
+
+    /// Add a prop (ignored on text and fragment nodes)
+    pub fn prop(mut self, name: impl Into<String>, value: impl Into<PropValue>) -> Self {
+        if let UiNode::Widget(node) = &mut self {
+            node.props.push((name.into(), value.into()));
+        }
+        self
+    }
+
+    /// Add a child (ignored on text nodes)
+    pub fn child(mut self, child: UiNode) -> Self {
+        match &mut self {
+            UiNode::Widget(node) => node.children.push(child),
+            UiNode::Fragment(children) => children.push(child),
+            UiNode::Text(_) => {}
+        }
+        self
+    }
+
+    /// Add several children (ignored on text nodes)
+    pub fn children(self, children: impl IntoIterator<Item = UiNode>) -> Self {
+        children.into_iter().fold(self, UiNode::child)
+    }
+
+    /// Widget name, if this is a widget node
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            UiNode::Widget(node) => Some(&node.name),
+            _ => None,
+        }
+    }
+
+    /// Last value given for a prop
+    pub fn get_prop(&self, name: &str) -> Option<&PropValue> {
+        match self {
+            UiNode::Widget(node) => node.get_prop(name),
+            _ => None,
+        }
+    }
+
+    /// Child nodes
+    pub fn child_nodes(&self) -> &[UiNode] {
+        match self {
+            UiNode::Widget(node) => &node.children,
+            UiNode::Fragment(children) => children,
+            UiNode::Text(_) => &[],
+        }
+    }
 }
 
 impl WidgetNode {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            props: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
     pub fn prop(mut self, name: impl Into<String>, value: PropValue) -> Self {
         self.props.push((name.into(), value));
         self
@@ -85,6 +148,21 @@ impl WidgetNode {
         self.children.push(node);
         self
     }
+
+    /// Last value given for a prop
+    pub fn get_prop(&self, name: &str) -> Option<&PropValue> {
+        self.props
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl From<WidgetNode> for UiNode {
+    fn from(node: WidgetNode) -> Self {
+        UiNode::Widget(node)
+    }
 }
 
 // Initial `From` implementations for easy conversion in macro generation
@@ -128,3 +206,251 @@ impl From<Color> for PropValue {
         PropValue::Color(v)
     }
 }
+
+/// JSON encoding
+///
+/// Text nodes are strings, fragments are arrays and widgets are objects:
+///
+/// ```json
+/// { "widget": "Button", "props": [["text", "OK"], ["color", { "color": "#ff0000ff" }]], "children": [] }
+/// ```
+///
+/// Props are `[name, value]` pairs so their order survives; an object is also
+/// accepted when reading, in which case props are applied in key order. Values
+/// map to JSON strings, integers, floats and booleans; colors are
+/// `{ "color": "#rrggbbaa" }` and so round-trip at 8 bits per channel.
+/// Floats always carry a fractional part (`2.0`), which keeps them apart from
+/// integers.
+#[cfg(feature = "serde")]
+mod json {
+    use super::{PropValue, UiNode, WidgetNode};
+    use crate::error::{Result, StratoError};
+    use crate::types::Color;
+    use serde_json::{Map, Value};
+
+    impl UiNode {
+        /// Encode as a JSON value
+        pub fn to_json_value(&self) -> Value {
+            match self {
+                UiNode::Text(text) => Value::String(text.clone()),
+                UiNode::Fragment(children) => {
+                    Value::Array(children.iter().map(UiNode::to_json_value).collect())
+                }
+                UiNode::Widget(node) => {
+                    let mut object = Map::new();
+                    object.insert("widget".into(), Value::String(node.name.clone()));
+                    if !node.props.is_empty() {
+                        let props = node
+                            .props
+                            .iter()
+                            .map(|(name, value)| {
+                                Value::Array(vec![
+                                    Value::String(name.clone()),
+                                    value.to_json_value(),
+                                ])
+                            })
+                            .collect();
+                        object.insert("props".into(), Value::Array(props));
+                    }
+                    if !node.children.is_empty() {
+                        let children = node.children.iter().map(UiNode::to_json_value).collect();
+                        object.insert("children".into(), Value::Array(children));
+                    }
+                    Value::Object(object)
+                }
+            }
+        }
+
+        /// Decode from a JSON value
+        pub fn from_json_value(value: &Value) -> Result<Self> {
+            match value {
+                Value::String(text) => Ok(UiNode::Text(text.clone())),
+                Value::Array(children) => children
+                    .iter()
+                    .map(UiNode::from_json_value)
+                    .collect::<Result<Vec<_>>>()
+                    .map(UiNode::Fragment),
+                Value::Object(object) => {
+                    let name = object
+                        .get("widget")
+                        .and_then(Value::as_str)
+                        .ok_or_else(|| invalid("widget node without a \"widget\" name"))?;
+                    let mut node = WidgetNode::new(name);
+
+                    match object.get("props") {
+                        None => {}
+                        Some(Value::Array(props)) => {
+                            for prop in props {
+                                let (name, value) = match prop.as_array().map(Vec::as_slice) {
+                                    Some([Value::String(name), value]) => (name, value),
+                                    _ => {
+                                        return Err(invalid(format!(
+                                            "prop {} is not a [name, value] pair",
+                                            prop
+                                        )))
+                                    }
+                                };
+                                node.props
+                                    .push((name.clone(), PropValue::from_json_value(name, value)?));
+                            }
+                        }
+                        Some(Value::Object(props)) => {
+                            for (name, value) in props {
+                                node.props
+                                    .push((name.clone(), PropValue::from_json_value(name, value)?));
+                            }
+                        }
+                        Some(_) => return Err(invalid("\"props\" must be an array or object")),
+                    }
+
+                    match object.get("children") {
+                        None => {}
+                        Some(Value::Array(children)) => {
+                            for child in children {
+                                node.children.push(UiNode::from_json_value(child)?);
+                            }
+                        }
+                        Some(_) => return Err(invalid("\"children\" must be an array")),
+                    }
+
+                    Ok(UiNode::Widget(node))
+                }
+                other => Err(invalid(format!("unexpected node {}", other))),
+            }
+        }
+
+        /// Encode as pretty-printed JSON
+        pub fn to_json(&self) -> String {
+            serde_json::to_string_pretty(&self.to_json_value())
+                .expect("JSON values always serialize")
+        }
+
+        /// Decode from JSON text
+        pub fn from_json(json: &str) -> Result<Self> {
+            let value: Value = serde_json::from_str(json)
+                .map_err(|e| invalid(format!("malformed JSON: {}", e)))?;
+            UiNode::from_json_value(&value)
+        }
+    }
+
+    impl PropValue {
+        fn to_json_value(&self) -> Value {
+            match self {
+                PropValue::String(s) => Value::String(s.clone()),
+                PropValue::Int(i) => Value::from(*i),
+                PropValue::Float(f) => Value::from(*f),
+                PropValue::Bool(b) => Value::Bool(*b),
+                PropValue::Color(c) => {
+                    let mut object = Map::new();
+                    object.insert("color".into(), Value::String(c.to_hex()));
+                    Value::Object(object)
+                }
+            }
+        }
+
+        fn from_json_value(name: &str, value: &Value) -> Result<Self> {
+            let color = |object: &Map<String, Value>| {
+                let hex = object.get("color")?.as_str()?;
+                Color::from_hex(hex).ok()
+            };
+
+            match value {
+                Value::String(s) => Ok(PropValue::String(s.clone())),
+                Value::Bool(b) => Ok(PropValue::Bool(*b)),
+                Value::Number(n) => match n.as_i64() {
+                    Some(i) => Ok(PropValue::Int(i)),
+                    None => n
+                        .as_f64()
+                        .map(PropValue::Float)
+                        .ok_or_else(|| invalid(format!("prop '{}' is out of range", name))),
+                },
+                Value::Object(object) => color(object)
+                    .map(PropValue::Color)
+                    .ok_or_else(|| invalid(format!("prop '{}' is not a valid color", name))),
+                other => Err(invalid(format!(
+                    "prop '{}' has unsupported value {}",
+                    name, other
+                ))),
+            }
+        }
+    }
+
+    impl serde::Serialize for UiNode {
+        fn serialize<S: serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> std::result::Result<S::Ok, S::Error> {
+            self.to_json_value().serialize(serializer)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for UiNode {
+        fn deserialize<D: serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> std::result::Result<Self, D::Error> {
+            let value = Value::deserialize(deserializer)?;
+            UiNode::from_json_value(&value).map_err(serde::de::Error::custom)
+        }
+    }
+
+    fn invalid(message: impl std::fmt::Display) -> StratoError {
+        StratoError::state(format!("Invalid UI tree: {}", message))
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn sample() -> UiNode {
+            UiNode::widget("Column")
+                .prop("spacing", 8.0)
+                .prop("count", 3)
+                .child(
+                    UiNode::widget("Text")
+                        .prop("text", "Hello")
+                        .prop("color", Color::rgba(1.0, 0.0, 0.0, 1.0)),
+                )
+                .child(UiNode::fragment(vec![
+                    UiNode::text("raw"),
+                    UiNode::widget("Button").prop("disabled", true),
+                ]))
+        }
+
+        #[test]
+        fn test_json_round_trip() {
+            let node = sample();
+            let json = node.to_json();
+            assert_eq!(UiNode::from_json(&json).unwrap(), node);
+
+            let value = node.to_json_value();
+            assert_eq!(value["props"][0], serde_json::json!(["spacing", 8.0]));
+            assert_eq!(value["props"][1], serde_json::json!(["count", 3]));
+            assert_eq!(value["children"][0]["props"][1][1]["color"], "#ff0000ff");
+        }
+
+        #[test]
+        fn test_prop_order_and_last_value_win() {
+            let node = UiNode::from_json(
+                r#"{"widget": "Text", "props": [["text", "a"], ["size", 2.0], ["text", "b"]]}"#,
+            )
+            .unwrap();
+            assert_eq!(node.get_prop("size"), Some(&PropValue::Float(2.0)));
+            assert_eq!(node.get_prop("text"), Some(&PropValue::from("b")));
+
+            let node = UiNode::from_json(r#"{"widget": "Text", "props": {"size": 2}}"#).unwrap();
+            assert_eq!(node.get_prop("size"), Some(&PropValue::Int(2)));
+
+            let node = UiNode::widget("Text").prop("text", "a").prop("text", "b");
+            assert_eq!(node.get_prop("text"), Some(&PropValue::from("b")));
+        }
+
+        #[test]
+        fn test_invalid_json_is_rejected() {
+            assert!(UiNode::from_json("{").is_err());
+            assert!(UiNode::from_json(r#"{"props": {}}"#).is_err());
+            assert!(UiNode::from_json(r#"{"widget": "Text", "props": {"x": null}}"#).is_err());
+            assert!(UiNode::from_json(r#"{"widget": "Text", "props": [["x"]]}"#).is_err());
+            assert!(UiNode::from_json("42").is_err());
+        }
+    }
+}
@@ -10,10 +10,11 @@ use crate::{
     layout::{LayoutConstraints, Size},
     types::Rect,
 };
+use serde::{Deserialize, Serialize};
 use std::{any::Any, collections::HashMap, fmt::Debug};
 
 /// Unique identifier for widgets
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WidgetId(pub u64);
 
 impl WidgetId {
@@ -238,9 +238,10 @@ pub trait Window: Send + Sync {
 }
 
 /// Cursor icon types
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum CursorIcon {
     /// Default cursor
+    #[default]
     Default,
     /// Pointer cursor (hand)
     Pointer,
@@ -268,6 +269,27 @@ pub enum CursorIcon {
     Progress,
 }
 
+impl CursorIcon {
+    /// The CSS `cursor` property value for this icon, for browser targets
+    pub fn css_name(self) -> &'static str {
+        match self {
+            CursorIcon::Default => "default",
+            CursorIcon::Pointer => "pointer",
+            CursorIcon::Text => "text",
+            CursorIcon::Crosshair => "crosshair",
+            CursorIcon::Move => "move",
+            CursorIcon::ResizeNS => "ns-resize",
+            CursorIcon::ResizeEW => "ew-resize",
+            CursorIcon::ResizeNESW => "nesw-resize",
+            CursorIcon::ResizeNWSE => "nwse-resize",
+            CursorIcon::Wait => "wait",
+            CursorIcon::NotAllowed => "not-allowed",
+            CursorIcon::Help => "help",
+            CursorIcon::Progress => "progress",
+        }
+    }
+}
+
 /// Window builder for creating windows with a fluent API
 pub struct WindowBuilder {
     config: WindowConfig,
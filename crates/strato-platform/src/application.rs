@@ -1,15 +1,64 @@
 //! Application management
 
-use crate::{EventLoop, Window, WindowBuilder};
+use crate::frame::{FrameClock, FrameContext};
+use crate::plugin::{ApplicationPlugin, Plugin, PluginStack, Registered};
+use crate::scroll::{scroll_settings, set_scroll_settings, ScrollSettings};
+use crate::{EventLoop, EventLoopProxy, Window, WindowBuilder};
+use std::cell::OnceCell;
 use std::collections::HashMap;
-use strato_core::event::Event;
-use strato_widgets::widget::Widget;
+use std::time::Instant;
+use strato_core::event::{Event, EventResult};
+use strato_core::hit_test::LayoutTree;
+use strato_core::input::InputState;
+use strato_core::types::Color;
+use strato_core::window::CursorIcon;
+use strato_renderer::backend::Backend;
+use strato_renderer::gpu::{GpuInfo, TextHinting};
+use strato_renderer::PostProcessNode;
+use strato_widgets::focus::handle_tab;
+use strato_widgets::shortcut::ShortcutRegistry;
+use strato_widgets::state_view::{Dispatch, StateView};
+use strato_widgets::theme::Theme;
+use strato_widgets::widget::{
+    assign_path_ids, cursor_icon_at, dispatch_root_event, focused_widget, id_mode, layout_tree,
+    mount_tree, unmount_tree, update_tree, widgets_at, IdMode, MountedWidgets, Widget,
+    WidgetContext, WidgetId, WidgetState,
+};
+
+/// Callback run once per frame; see [`crate::frame`]
+type FrameCallback = Box<dyn FnMut(FrameContext) + Send>;
+
+/// When the event loop draws a frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RedrawMode {
+    /// Draw a frame whenever the event loop is idle
+    #[default]
+    Continuous,
+    /// Draw a frame only after input or a change marked with
+    /// [`strato_core::reactive::mark_dirty`], which every signal update does
+    ///
+    /// A static UI then skips layout and rendering between changes, though
+    /// the event loop still wakes about every 16ms to look for changes made
+    /// off the UI thread. Frame callbacks and animations only advance on
+    /// drawn frames, so they must change a signal, or mark the UI dirty, to
+    /// keep running.
+    OnDemand,
+}
 
 /// Application builder
 pub struct ApplicationBuilder {
     title: String,
     initial_window: WindowBuilder,
     use_taffy: bool,
+    plugins: Vec<Registered>,
+    post_process: Vec<Box<dyn PostProcessNode>>,
+    frame_callbacks: Vec<FrameCallback>,
+    clear_color: Option<Color>,
+    text_hinting: TextHinting,
+    resize_buffering: bool,
+    scroll_settings: Option<ScrollSettings>,
+    backend: Option<Box<dyn Backend>>,
+    redraw_mode: RedrawMode,
 }
 
 impl ApplicationBuilder {
@@ -19,6 +68,15 @@ impl ApplicationBuilder {
             title: "StratoUI Application".to_string(),
             initial_window: WindowBuilder::new(),
             use_taffy: false,
+            plugins: Vec::new(),
+            post_process: Vec::new(),
+            frame_callbacks: Vec::new(),
+            clear_color: None,
+            text_hinting: TextHinting::default(),
+            resize_buffering: false,
+            scroll_settings: None,
+            backend: None,
+            redraw_mode: RedrawMode::default(),
         }
     }
 
@@ -42,12 +100,110 @@ impl ApplicationBuilder {
         self
     }
 
+    /// Register a plugin; see [`crate::plugin`] for hook ordering
+    pub fn with_plugin(mut self, plugin: Box<dyn Plugin>) -> Self {
+        self.plugins.push(Registered::Core(plugin));
+        self
+    }
+
+    /// Register a plugin whose frame hooks receive the [`Application`]
+    pub fn with_app_plugin(mut self, plugin: Box<dyn ApplicationPlugin>) -> Self {
+        self.plugins.push(Registered::App(plugin));
+        self
+    }
+
+    /// Register a post-process pass run on the rendered UI, e.g. a
+    /// [`strato_renderer::GaussianBlur`]
+    pub fn with_post_process(mut self, node: Box<dyn PostProcessNode>) -> Self {
+        self.post_process.push(node);
+        self
+    }
+
+    /// Clear the window to `color` before each frame is drawn
+    ///
+    /// Transparent windows clear to fully transparent by default.
+    pub fn clear_color(mut self, color: Color) -> Self {
+        self.clear_color = Some(color);
+        self
+    }
+
+    /// Fit glyphs to the pixel grid as `hinting` says, unhinted by default
+    ///
+    /// [`TextHinting::Light`] keeps small text crisp on 1x displays.
+    pub fn text_hinting(mut self, hinting: TextHinting) -> Self {
+        self.text_hinting = hinting;
+        self
+    }
+
+    /// Hold the last frame while the window is resized, off by default
+    ///
+    /// Stops the clear color flashing through a window being resized, at the
+    /// cost of an extra surface-sized texture; see
+    /// [`strato_renderer::gpu::resize_buffer`].
+    pub fn resize_buffering(mut self, enabled: bool) -> Self {
+        self.resize_buffering = enabled;
+        self
+    }
+
+    /// Invert wheel and trackpad scrolling, so content follows the fingers,
+    /// off by default
+    pub fn natural_scroll(mut self, enabled: bool) -> Self {
+        self.scroll_settings.get_or_insert_with(scroll_settings).natural = enabled;
+        self
+    }
+
+    /// Scroll `height` logical pixels per mouse wheel notch, 20 by default
+    pub fn scroll_line_height(mut self, height: f32) -> Self {
+        self.scroll_settings.get_or_insert_with(scroll_settings).line_height = height;
+        self
+    }
+
+    /// Render with `backend` instead of creating a GPU backend for the window
+    ///
+    /// With a [`NoopBackend`](strato_renderer::backend::NoopBackend), tests
+    /// can drive frames through [`Application::render_frame`] without a GPU
+    /// or a window.
+    pub fn with_backend(mut self, backend: Box<dyn Backend>) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Choose when frames are drawn, continuously by default
+    pub fn redraw_mode(mut self, mode: RedrawMode) -> Self {
+        self.redraw_mode = mode;
+        self
+    }
+
+    /// Run `callback` once per rendered frame, before layout
+    ///
+    /// See [`crate::frame`] for how it is ordered against plugins, the
+    /// widget update pass and animations.
+    pub fn on_frame(mut self, callback: impl FnMut(FrameContext) + Send + 'static) -> Self {
+        self.frame_callbacks.push(Box::new(callback));
+        self
+    }
+
     /// Build the application
     pub fn build(self) -> Application {
         let mut app = Application::new(self.title, self.initial_window);
         if self.use_taffy {
             app.enable_taffy();
         }
+        for plugin in self.plugins {
+            app.register_plugin(plugin);
+        }
+        for node in self.post_process {
+            app.add_post_process(node);
+        }
+        app.frame_callbacks = self.frame_callbacks;
+        app.clear_color = self.clear_color;
+        app.text_hinting = self.text_hinting;
+        app.resize_buffering = self.resize_buffering;
+        if let Some(settings) = self.scroll_settings {
+            set_scroll_settings(settings);
+        }
+        app.backend = self.backend;
+        app.redraw_mode = self.redraw_mode;
         app
     }
 
@@ -57,6 +213,25 @@ impl ApplicationBuilder {
         app.set_root(Box::new(root));
         app.run()
     }
+
+    /// Run the application with a UI that `view` builds from `state`
+    ///
+    /// Widgets send messages through the [`Dispatch`] handed to `view`;
+    /// `update` applies them to the state and the UI is built again from it.
+    /// See [`StateView`] for the details of the loop.
+    pub fn run_with_state<S, Msg, W>(
+        self,
+        state: S,
+        view: impl Fn(&S, &Dispatch<Msg>) -> W + Send + Sync + 'static,
+        update: impl Fn(&mut S, Msg) + Send + Sync + 'static,
+    ) -> !
+    where
+        S: Clone + std::fmt::Debug + Send + Sync + 'static,
+        Msg: Send + 'static,
+        W: Widget + 'static,
+    {
+        self.run(StateView::new(state, view, update))
+    }
 }
 
 /// Main application structure
@@ -64,35 +239,276 @@ pub struct Application {
     title: String,
     windows: HashMap<u64, Window>,
     root_widget: Option<Box<dyn Widget>>,
-    event_loop: Option<EventLoop>,
+    /// Widgets of the tree that are mounted, see [`Widget::on_mount`]
+    mounted: MountedWidgets,
+    /// Mounted roots replaced since the last frame, yet to be unmounted
+    replaced_roots: Vec<Box<dyn Widget>>,
+    /// Created on first use, so headless applications never need a display
+    event_loop: OnceCell<EventLoop>,
     initial_window: Option<WindowBuilder>,
     render_batch: Option<strato_renderer::RenderBatch>,
     taffy_manager: Option<strato_core::taffy_layout::TaffyLayoutManager>,
-    // Renderer is managed by the event loop to avoid lifetime issues
+    plugins: PluginStack,
+    plugins_initialized: bool,
+    post_process: Vec<Box<dyn PostProcessNode>>,
+    frame_clock: FrameClock,
+    frame_callbacks: Vec<FrameCallback>,
+    theme: Theme,
+    /// Input held as of the last event
+    input: InputState,
+    clear_color: Option<Color>,
+    text_hinting: TextHinting,
+    resize_buffering: bool,
+    gpu_info: Option<GpuInfo>,
+    /// Backend given to the application, instead of the event loop's
+    backend: Option<Box<dyn Backend>>,
+    redraw_mode: RedrawMode,
+    /// [`change_count`](strato_core::reactive::change_count) the last frame
+    /// drew; once the count moves past it, a frame is due
+    drawn_changes: u64,
+    /// Count as the current frame began; what changed after that, in the
+    /// update pass or on other threads, is left for the next frame
+    frame_changes: u64,
 }
 
 impl Application {
     /// Create a new application
+    ///
+    /// Installs the system browser as the opener of clicked URL links, see
+    /// [`crate::links`], and the drawn font's metrics as those reported by
+    /// [`strato_core::text::line_metrics`].
     pub fn new(title: impl Into<String>, initial_window: WindowBuilder) -> Self {
+        crate::links::install_url_opener();
+        strato_widgets::text::install_line_metrics();
         Self {
             title: title.into(),
             windows: HashMap::new(),
             root_widget: None,
-            event_loop: Some(EventLoop::new().expect("Failed to create event loop")),
+            mounted: MountedWidgets::new(),
+            replaced_roots: Vec::new(),
+            event_loop: OnceCell::new(),
             initial_window: Some(initial_window),
             render_batch: None,
             taffy_manager: None,
+            plugins: PluginStack::default(),
+            plugins_initialized: false,
+            post_process: Vec::new(),
+            frame_clock: FrameClock::default(),
+            frame_callbacks: Vec::new(),
+            theme: Theme::default(),
+            input: InputState::new(),
+            clear_color: None,
+            text_hinting: TextHinting::default(),
+            resize_buffering: false,
+            gpu_info: None,
+            backend: None,
+            redraw_mode: RedrawMode::default(),
+            drawn_changes: 0,
+            frame_changes: 0,
         }
     }
 
+    /// Create a proxy for waking the application's event loop from other
+    /// threads, e.g. with [`EventLoopProxy::send_action`]
+    pub fn create_proxy(&self) -> EventLoopProxy {
+        self.event_loop
+            .get_or_init(|| EventLoop::new().expect("Failed to create event loop"))
+            .create_proxy()
+    }
+
     /// Enable Taffy layout engine
     pub fn enable_taffy(&mut self) {
         self.taffy_manager = Some(strato_core::taffy_layout::TaffyLayoutManager::new());
     }
 
     /// Set the root widget
+    ///
+    /// It is mounted on the next frame, and a root it replaces unmounted.
     pub fn set_root(&mut self, widget: Box<dyn Widget>) {
-        self.root_widget = Some(widget);
+        let previous = self.root_widget.replace(widget);
+        self.replaced_roots
+            .extend(previous.filter(|root| self.mounted.contains(root.id())));
+    }
+
+    /// Register a plugin
+    ///
+    /// Plugins added after the application started are initialized right away.
+    pub fn add_plugin(&mut self, plugin: Box<dyn Plugin>) {
+        self.register_plugin(Registered::Core(plugin));
+    }
+
+    /// Register a plugin whose frame hooks receive the application
+    ///
+    /// Plugins added after the application started are initialized right away.
+    pub fn add_app_plugin(&mut self, plugin: Box<dyn ApplicationPlugin>) {
+        self.register_plugin(Registered::App(plugin));
+    }
+
+    fn register_plugin(&mut self, plugin: Registered) {
+        self.plugins.push(plugin);
+        if self.plugins_initialized {
+            self.plugins.initialize();
+        }
+    }
+
+    /// Register a post-process pass
+    ///
+    /// Passes are handed to the renderer when the window is created, so they
+    /// must be added before the application runs.
+    pub fn add_post_process(&mut self, node: Box<dyn PostProcessNode>) {
+        self.post_process.push(node);
+    }
+
+    /// Take the passes registered so far, for the renderer
+    pub(crate) fn take_post_process(&mut self) -> Vec<Box<dyn PostProcessNode>> {
+        std::mem::take(&mut self.post_process)
+    }
+
+    /// Clear the window to `color` from the next frame on
+    pub fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = Some(color);
+    }
+
+    /// The color set with [`set_clear_color`](Self::set_clear_color), if any
+    pub fn clear_color(&self) -> Option<Color> {
+        self.clear_color
+    }
+
+    /// Fit glyphs to the pixel grid as `hinting` says from the next frame on
+    pub fn set_text_hinting(&mut self, hinting: TextHinting) {
+        self.text_hinting = hinting;
+    }
+
+    /// How glyphs are fitted to the pixel grid
+    pub fn text_hinting(&self) -> TextHinting {
+        self.text_hinting
+    }
+
+    /// Hold the last frame while the window is resized, from the next frame
+    /// on; see [`ApplicationBuilder::resize_buffering`]
+    pub fn set_resize_buffering(&mut self, enabled: bool) {
+        self.resize_buffering = enabled;
+    }
+
+    /// Whether the last frame is held while the window is resized
+    pub fn resize_buffering(&self) -> bool {
+        self.resize_buffering
+    }
+
+    /// Run `callback` once per rendered frame, before layout
+    ///
+    /// See [`crate::frame`] for ordering.
+    pub fn on_frame(&mut self, callback: impl FnMut(FrameContext) + Send + 'static) {
+        self.frame_callbacks.push(Box::new(callback));
+    }
+
+    /// Number of registered plugins
+    pub fn plugin_count(&self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Initialize and activate every plugin once
+    pub fn init_plugins(&mut self) {
+        if self.plugins_initialized {
+            return;
+        }
+        self.plugins_initialized = true;
+        self.plugins.initialize();
+    }
+
+    /// Shut the application down: stop its plugins, see
+    /// [`shutdown_plugins`](Self::shutdown_plugins), and write the values
+    /// persisted stores are still holding back, see
+    /// [`strato_core::persistence::flush_all`]
+    ///
+    /// The event loop calls this on exit, since the process then ends
+    /// without dropping the application or its stores.
+    pub fn shutdown(&mut self) {
+        self.shutdown_plugins();
+        if let Err(e) = strato_core::persistence::flush_all() {
+            tracing::error!("Failed to persist state on exit: {}", e);
+        }
+    }
+
+    /// Deactivate and clean up every plugin, last registered first
+    ///
+    /// [`shutdown`](Self::shutdown) calls this; dropping the application
+    /// does the same for plugins not yet shut down.
+    pub fn shutdown_plugins(&mut self) {
+        if !self.plugins_initialized {
+            return;
+        }
+        self.plugins_initialized = false;
+        self.plugins.shutdown();
+    }
+
+    /// Choose when frames are drawn
+    pub fn set_redraw_mode(&mut self, mode: RedrawMode) {
+        self.redraw_mode = mode;
+    }
+
+    /// When frames are drawn
+    pub fn redraw_mode(&self) -> RedrawMode {
+        self.redraw_mode
+    }
+
+    /// Whether the event loop should draw the next frame
+    ///
+    /// Always `true` when redrawing continuously. On demand, it is `true`
+    /// while anything changed since the last drawn frame; see
+    /// [`RedrawMode`]. Signals widgets set while laying out and rendering a
+    /// frame, e.g. to remember their bounds, don't count as changes.
+    pub fn should_redraw(&self) -> bool {
+        self.redraw_mode == RedrawMode::Continuous
+            || strato_core::reactive::change_count() > self.drawn_changes
+    }
+
+    /// Start a frame: run the plugins' `before_frame` hooks, the frame
+    /// callbacks and the widget update pass, in that order
+    pub fn begin_frame(&mut self) -> FrameContext {
+        self.frame_changes = strato_core::reactive::change_count();
+        let mut frame = self.frame_clock.tick(Instant::now());
+        frame.input = self.input;
+        self.with_plugins(|plugins, app| plugins.before_frame(app));
+        for callback in &mut self.frame_callbacks {
+            callback(frame);
+        }
+
+        let ctx = WidgetContext {
+            theme: &self.theme,
+            state: WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time: frame.delta_time,
+            input: frame.input,
+        };
+        for mut root in self.replaced_roots.drain(..) {
+            unmount_tree(root.as_mut(), &ctx, &mut self.mounted);
+        }
+        if let Some(root) = self.root_widget.as_mut() {
+            if !self.mounted.contains(root.id()) {
+                mount_tree(root.as_mut(), &ctx, &mut self.mounted);
+            }
+            update_tree(root.as_mut(), &ctx, &mut self.mounted);
+        }
+        frame
+    }
+
+    /// Run the plugins' `after_frame` hooks
+    pub fn end_frame(&mut self) {
+        self.with_plugins(|plugins, app| plugins.after_frame(app));
+        // Changes since the frame began, such as animations stepped in the
+        // update pass or signals set on other threads, need another frame
+        self.drawn_changes = self.frame_changes;
+    }
+
+    /// Detach the plugins so hooks can borrow the application mutably
+    fn with_plugins(&mut self, f: impl FnOnce(&mut PluginStack, &mut Application)) {
+        let mut plugins = self.plugins.detach();
+        f(&mut plugins, self);
+        // Keep plugins registered by hooks after the existing ones
+        plugins.extend(std::mem::take(&mut self.plugins));
+        self.plugins = plugins;
     }
 
     /// Add a window
@@ -116,9 +532,29 @@ impl Application {
     }
 
     /// Render the application with a simple approach (no actual GPU rendering)
+    ///
+    /// Signals widgets set while laying out and rendering don't mark the UI
+    /// dirty, see [`strato_core::reactive::without_marking_dirty`].
     pub fn render_simple(&mut self, window_width: f32, window_height: f32) -> anyhow::Result<()> {
+        strato_core::reactive::without_marking_dirty(|| {
+            self.layout_and_render(window_width, window_height)
+        })
+    }
+
+    fn layout_and_render(&mut self, window_width: f32, window_height: f32) -> anyhow::Result<()> {
         if let Some(root_widget) = self.root_widget.as_mut() {
             let mut batch = strato_renderer::RenderBatch::new();
+            // Widgets register their bounds again while rendering
+            strato_core::hit_test::hit_test().begin_frame();
+            if id_mode() == IdMode::TreePath {
+                assign_path_ids(root_widget.as_mut());
+            }
+            batch.set_viewport(strato_core::types::Rect::new(
+                0.0,
+                0.0,
+                window_width,
+                window_height,
+            ));
 
             // Compute layout constraints using actual window size
             let constraints = strato_core::layout::Constraints {
@@ -291,12 +727,70 @@ impl Application {
         self.render_batch.take()
     }
 
+    /// Render with `backend` instead of creating a GPU backend for the window
+    ///
+    /// See [`ApplicationBuilder::with_backend`].
+    pub fn set_backend(&mut self, backend: Box<dyn Backend>) {
+        self.backend = Some(backend);
+    }
+
+    /// The backend given to the application, for the event loop to render with
+    pub(crate) fn take_backend(&mut self) -> Option<Box<dyn Backend>> {
+        self.backend.take()
+    }
+
+    /// Run one whole frame at `width` x `height` logical pixels and submit it
+    /// to the application's own backend
+    ///
+    /// This is the frame the event loop runs, from the frame callbacks and
+    /// the widget update pass through layout and rendering, without a window.
+    /// Errors if no backend was given with
+    /// [`ApplicationBuilder::with_backend`] or the backend rejects the frame.
+    pub fn render_frame(&mut self, width: f32, height: f32) -> anyhow::Result<()> {
+        self.begin_frame();
+        let result = self
+            .render_simple(width, height)
+            .and_then(|()| self.submit_frame());
+        self.end_frame();
+        result
+    }
+
+    fn submit_frame(&mut self) -> anyhow::Result<()> {
+        let clear_color = self.clear_color;
+        let text_hinting = self.text_hinting;
+        let resize_buffering = self.resize_buffering;
+        let batch = self.get_render_batch();
+        let backend = self.backend.as_mut().ok_or_else(|| {
+            anyhow::anyhow!("no backend to render to, see ApplicationBuilder::with_backend")
+        })?;
+        if let Some(color) = clear_color {
+            backend.set_clear_color(color);
+        }
+        backend.set_text_hinting(text_hinting);
+        backend.set_resize_buffering(resize_buffering);
+        backend.begin_frame()?;
+        let submitted = batch.map_or(Ok(()), |batch| backend.submit_batch(&batch));
+        backend.end_frame()?;
+        submitted
+    }
+
+    fn take_event_loop(&mut self) -> Option<EventLoop> {
+        self.event_loop.take().or_else(|| {
+            EventLoop::new()
+                .map_err(|e| eprintln!("Failed to create event loop: {}", e))
+                .ok()
+        })
+    }
+
     /// Run the application
     pub fn run(mut self) -> ! {
+        crate::motion::apply_system_animation_settings();
+        self.init_plugins();
+
         #[cfg(not(target_arch = "wasm32"))]
         {
             if let Some(window_builder) = self.initial_window.take() {
-                if let Some(event_loop) = self.event_loop.take() {
+                if let Some(event_loop) = self.take_event_loop() {
                     match event_loop.run_with_window_and_app(window_builder, self, move |_event| {
                         // Event handling is now done inside the event loop
                     }) {
@@ -311,7 +805,7 @@ impl Application {
                     std::process::exit(1);
                 }
             } else {
-                if let Some(event_loop) = self.event_loop.take() {
+                if let Some(event_loop) = self.take_event_loop() {
                     event_loop.run(move |_event| {
                         // Handle event
                     });
@@ -328,11 +822,95 @@ impl Application {
         }
     }
 
+    /// The cursor to show with the pointer at `position`, in logical pixels
+    pub fn cursor_icon_at(&self, position: glam::Vec2) -> CursorIcon {
+        self.root_widget
+            .as_deref()
+            .and_then(|root| cursor_icon_at(root, position.into()))
+            .unwrap_or_default()
+    }
+
+    /// Feed a synthesized event to the application, e.g. from an automation
+    /// script or an end-to-end test
+    ///
+    /// The event takes the same path as real input: plugins, global
+    /// shortcuts, then the widget tree, so synthetic pointer events update
+    /// hover, press and focus state like real ones. Positions are in logical
+    /// pixels. Once the application is running, send events through
+    /// [`create_proxy`](Self::create_proxy) instead, and they arrive here.
+    pub fn send_event(&mut self, event: Event) {
+        self.handle_event(event);
+    }
+
+    /// The deepest widget under the pointer, as of the last pointer event
+    ///
+    /// Only widgets that registered their bounds while rendering are found.
+    pub fn hovered_widget(&self) -> Option<WidgetId> {
+        let root = self.root_widget.as_deref()?;
+        widgets_at(root, self.input.mouse_position()?.into())
+            .first()
+            .copied()
+    }
+
+    /// Where every widget was drawn in the last frame, by ID and type
+    ///
+    /// Built from the root widget on each call, so frames don't pay for it.
+    /// `None` without a root widget. Only widgets that registered their
+    /// bounds while rendering have bounds; see [`LayoutTree`] for lookups.
+    pub fn layout_tree(&self) -> Option<LayoutTree> {
+        self.root_widget.as_deref().map(layout_tree)
+    }
+
+    /// The adapter and device the window is rendered with
+    ///
+    /// `None` until the renderer is initialized, when the window is first
+    /// shown. Updated if the renderer recreates its device after losing it.
+    /// Include it in bug reports, or check
+    /// [`is_software`](GpuInfo::is_software) to warn that rendering will
+    /// be slow.
+    pub fn gpu_info(&self) -> Option<&GpuInfo> {
+        self.gpu_info.as_ref()
+    }
+
+    pub(crate) fn set_gpu_info(&mut self, gpu_info: Option<GpuInfo>) {
+        if let Some(info) = &gpu_info {
+            tracing::info!("Rendering with {}", info);
+        }
+        self.gpu_info = gpu_info;
+    }
+
+    /// Keys, modifiers and mouse buttons held and the pointer position, as
+    /// of the last event
+    ///
+    /// Frame callbacks and widget updates get the same state as of the start
+    /// of the frame, in [`FrameContext::input`] and
+    /// [`WidgetContext::input`].
+    pub fn input(&self) -> InputState {
+        self.input
+    }
+
+    /// The widget with keyboard focus, if any
+    pub fn focused_widget(&self) -> Option<WidgetId> {
+        focused_widget(self.root_widget.as_deref()?)
+    }
+
     /// Handle an event
     pub fn handle_event(&mut self, event: Event) {
-        // Dispatch event to root widget
+        self.input.apply(&event);
+        // Input may change hover or press state held outside signals
+        strato_core::reactive::mark_dirty();
+
+        // Plugins see every event first, then global shortcuts (e.g. menu
+        // accelerators), then the widget tree
+        let consumed = self.plugins.on_event(&event) == EventResult::Handled
+            || ShortcutRegistry::global().handle_event(&event) == EventResult::Handled;
+
+        // Dispatch event to root widget, open overlays first for pointer
+        // events; Tabs it leaves unhandled move focus
         if let Some(widget) = &mut self.root_widget {
-            widget.handle_event(&event);
+            if !consumed && dispatch_root_event(widget.as_mut(), &event) == EventResult::Ignored {
+                handle_tab(widget.as_mut(), &event);
+            }
         }
 
         // Handle application-level events
@@ -353,3 +931,158 @@ impl Default for ApplicationBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use strato_core::event::{MouseButton, MouseEvent};
+    use strato_renderer::backend::NoopBackend;
+    use strato_widgets::Button;
+
+    #[test]
+    fn test_headless_frames_reach_the_backend() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&clicks);
+        let backend = NoopBackend::new();
+        let mut app = ApplicationBuilder::new()
+            .clear_color(Color::WHITE)
+            .text_hinting(TextHinting::Light)
+            .resize_buffering(true)
+            .with_backend(Box::new(backend.clone()))
+            .build();
+        app.set_root(Box::new(Button::new("OK").on_click(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })));
+
+        app.render_frame(200.0, 100.0).unwrap();
+        let mouse = MouseEvent {
+            position: glam::Vec2::new(20.0, 10.0),
+            button: Some(MouseButton::Left),
+            modifiers: Default::default(),
+            delta: glam::Vec2::ZERO,
+        };
+        app.handle_event(Event::MouseDown(mouse.clone()));
+        app.handle_event(Event::MouseUp(mouse));
+        app.render_frame(200.0, 100.0).unwrap();
+
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+        let record = backend.record();
+        assert_eq!(record.frames, 2);
+        assert_eq!(record.clear_color, Some(Color::WHITE));
+        assert_eq!(record.text_hinting, TextHinting::Light);
+        assert!(record.resize_buffering);
+        assert!(!record.commands.is_empty());
+
+        let layout = app.layout_tree().expect("a root widget is set");
+        assert!(!layout.find_by_name("Button").is_empty());
+    }
+
+    #[test]
+    fn test_on_demand_redraws_after_a_signal_changes() {
+        let mut app = ApplicationBuilder::new()
+            .redraw_mode(RedrawMode::OnDemand)
+            .build();
+        let count = strato_core::state::Signal::new(0);
+        assert!(app.should_redraw(), "the first frame is always drawn");
+        app.end_frame();
+        count.set(1);
+        assert!(app.should_redraw());
+
+        app.set_redraw_mode(RedrawMode::Continuous);
+        app.end_frame();
+        assert!(app.should_redraw());
+    }
+
+    #[test]
+    fn test_signals_set_off_thread_during_a_frame_are_drawn() {
+        let mut app = ApplicationBuilder::new()
+            .redraw_mode(RedrawMode::OnDemand)
+            .build();
+        let count = strato_core::state::Signal::new(0);
+
+        app.begin_frame();
+        let setter = count.clone();
+        std::thread::spawn(move || setter.set(1)).join().unwrap();
+        app.end_frame();
+
+        assert!(app.should_redraw());
+    }
+
+    #[test]
+    fn test_unhandled_tab_moves_focus() {
+        let first = Button::new("First");
+        let second = Button::new("Second");
+        let ids = [first.id(), second.id()];
+        let mut app = ApplicationBuilder::new().build();
+        app.set_root(Box::new(
+            strato_widgets::Row::new().children(vec![Box::new(first), Box::new(second)]),
+        ));
+
+        let tab = Event::KeyDown(strato_core::event::KeyboardEvent {
+            key_code: strato_core::event::KeyCode::Tab,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        });
+        app.send_event(tab.clone());
+        assert_eq!(app.focused_widget(), Some(ids[0]));
+        app.send_event(tab);
+        assert_eq!(app.focused_widget(), Some(ids[1]));
+    }
+
+    #[test]
+    fn test_frames_see_the_input_held_as_they_begin() {
+        use strato_core::event::{KeyCode, KeyboardEvent};
+
+        let held = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let frames = Arc::clone(&held);
+        let mut app = ApplicationBuilder::new()
+            .on_frame(move |frame| {
+                frames.lock().unwrap().push(frame.input.is_key_down(KeyCode::Space))
+            })
+            .build();
+        let key = KeyboardEvent {
+            key_code: KeyCode::Space,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        };
+
+        app.begin_frame();
+        app.handle_event(Event::KeyDown(key.clone()));
+        app.begin_frame();
+        app.begin_frame();
+        app.handle_event(Event::KeyUp(key));
+        assert!(!app.input().is_key_down(KeyCode::Space));
+        app.begin_frame();
+        assert_eq!(*held.lock().unwrap(), [false, true, true, false]);
+    }
+
+    #[test]
+    fn test_render_frame_needs_a_backend() {
+        let mut app = ApplicationBuilder::new().build();
+        assert!(app.render_frame(200.0, 100.0).is_err());
+    }
+
+    #[test]
+    fn test_shutdown_persists_the_last_value() {
+        use strato_core::persistence::{JsonFileBackend, StateStore};
+
+        let dir = std::env::temp_dir().join(format!("strato-shutdown-{}", std::process::id()));
+        let path = dir.join("state.json");
+        let store = StateStore::with_debounce(
+            JsonFileBackend::new(&path),
+            std::time::Duration::from_secs(3600),
+        );
+        let mut app = ApplicationBuilder::new().build();
+        app.init_plugins();
+        store.set("count", &3).unwrap();
+
+        // What the event loop does before the process exits
+        app.shutdown();
+        assert_eq!(StateStore::json_file(&path).get::<i32>("count"), Some(3));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -68,6 +68,7 @@ impl Platform for DesktopPlatform {
         use winit::event::{Event as WinitEvent, WindowEvent as WinitWindowEvent};
 
         let mut cursor_position = winit::dpi::PhysicalPosition::new(0.0, 0.0);
+        let mut modifiers = strato_core::event::Modifiers::default();
         let mut scale_factor = 1.0;
 
         let _ = event_loop.run(move |event, elwt| {
@@ -87,11 +88,15 @@ impl Platform for DesktopPlatform {
                                 device_id,
                             },
                             cursor_position,
+                            modifiers,
                             scale_factor,
                         ) {
                             callback(strato_event);
                         }
                     }
+                    WinitWindowEvent::ModifiersChanged(changed) => {
+                        modifiers = crate::event_loop::convert_modifiers(changed.state());
+                    }
                     WinitWindowEvent::ScaleFactorChanged {
                         scale_factor: sf,
                         inner_size_writer,
@@ -103,6 +108,7 @@ impl Platform for DesktopPlatform {
                                 inner_size_writer,
                             },
                             cursor_position,
+                            modifiers,
                             scale_factor,
                         ) {
                             callback(strato_event);
@@ -112,6 +118,7 @@ impl Platform for DesktopPlatform {
                         if let Some(strato_event) = crate::event_loop::convert_window_event(
                             event,
                             cursor_position,
+                            modifiers,
                             scale_factor,
                         ) {
                             callback(strato_event);
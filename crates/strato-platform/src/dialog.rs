@@ -0,0 +1,250 @@
+//! Native file dialogs
+//!
+//! [`open_file`] and [`save_file`] show the system's file picker and resolve
+//! to the file the user chose, or `None` if they cancelled. They are async so
+//! the UI keeps drawing while the dialog is up; [`open_file_then`] and
+//! [`save_file_then`] run them as a [task](crate::task) and hand the result to
+//! a callback on the UI thread, where it can update signals safely:
+//!
+//! ```no_run
+//! use strato_core::state::Signal;
+//! use strato_platform::dialog::{self, FileDialog};
+//!
+//! let opened = Signal::new(None);
+//! let shown = opened.clone();
+//! let handle = dialog::open_file_then(
+//!     FileDialog::new().filter("Text", &["txt", "md"]),
+//!     move |file| shown.set(file.and_then(|file| file.path().map(|p| p.to_path_buf()))),
+//! );
+//! ```
+//!
+//! # Platform differences
+//!
+//! On desktop the native dialog is used (the XDG desktop portal on Linux),
+//! and a [`PickedFile`] has a [`path`](PickedFile::path) the app can read and
+//! write like any other file.
+//!
+//! The web can't give out paths. Opening uses a file input and the picked
+//! file can only be [read](PickedFile::read) through its handle. Saving
+//! shows no dialog: `save_file` resolves at once, and the browser asks where
+//! to save when [`PickedFile::write`] is called. Filters, the starting
+//! directory and the title are ignored on the web.
+//!
+//! With the `headless` feature, and in this crate's tests, no dialog is shown
+//! and every dialog resolves to `None`, as if the user cancelled.
+
+use crate::task::{self, TaskHandle};
+use std::path::{Path, PathBuf};
+
+/// A named group of file extensions offered by a dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileFilter {
+    /// Name shown in the dialog, like "Images"
+    pub name: String,
+    /// Extensions without the leading dot, like `png`
+    pub extensions: Vec<String>,
+}
+
+/// Options for [`open_file`] and [`save_file`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileDialog {
+    title: Option<String>,
+    filters: Vec<FileFilter>,
+    directory: Option<PathBuf>,
+    file_name: Option<String>,
+}
+
+impl FileDialog {
+    /// A dialog with the platform's default title, listing every file
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the dialog's title
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Offer a filter, the first one added being selected
+    ///
+    /// Extensions may be given with or without their leading dot.
+    pub fn filter(mut self, name: impl Into<String>, extensions: &[&str]) -> Self {
+        self.filters.push(FileFilter {
+            name: name.into(),
+            extensions: extensions
+                .iter()
+                .map(|extension| extension.trim_start_matches('.').to_string())
+                .collect(),
+        });
+        self
+    }
+
+    /// Start in `directory`
+    pub fn directory(mut self, directory: impl Into<PathBuf>) -> Self {
+        self.directory = Some(directory.into());
+        self
+    }
+
+    /// Suggest a file name, mostly useful when saving
+    pub fn file_name(mut self, file_name: impl Into<String>) -> Self {
+        self.file_name = Some(file_name.into());
+        self
+    }
+
+    /// The filters offered, in order
+    pub fn filters(&self) -> &[FileFilter] {
+        &self.filters
+    }
+
+    #[cfg_attr(any(test, feature = "headless"), allow(dead_code))]
+    fn to_rfd(&self) -> rfd::AsyncFileDialog {
+        let mut dialog = rfd::AsyncFileDialog::new();
+        if let Some(title) = &self.title {
+            dialog = dialog.set_title(title);
+        }
+        for filter in &self.filters {
+            dialog = dialog.add_filter(&filter.name, &filter.extensions);
+        }
+        if let Some(directory) = &self.directory {
+            dialog = dialog.set_directory(directory);
+        }
+        if let Some(file_name) = &self.file_name {
+            dialog = dialog.set_file_name(file_name);
+        }
+        dialog
+    }
+}
+
+/// A file the user chose in a dialog
+#[derive(Clone)]
+pub struct PickedFile {
+    handle: rfd::FileHandle,
+}
+
+impl std::fmt::Debug for PickedFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PickedFile")
+            .field("file_name", &self.file_name())
+            .field("path", &self.path())
+            .finish()
+    }
+}
+
+impl PickedFile {
+    /// Where the file is, `None` on the web where pages don't see paths
+    pub fn path(&self) -> Option<&Path> {
+        #[cfg(not(target_arch = "wasm32"))]
+        return Some(self.handle.path());
+
+        #[cfg(target_arch = "wasm32")]
+        None
+    }
+
+    /// The file's name without its directory
+    pub fn file_name(&self) -> String {
+        self.handle.file_name()
+    }
+
+    /// Read the whole file
+    ///
+    /// Resolves to an empty buffer if the file can't be read on desktop, and
+    /// isn't meant for files returned by [`save_file`] on the web.
+    pub async fn read(&self) -> Vec<u8> {
+        self.handle.read().await
+    }
+
+    /// Replace the file's contents with `data`
+    ///
+    /// On the web this is when the browser asks where to save, and it only
+    /// works for files returned by [`save_file`].
+    pub async fn write(&self, data: &[u8]) -> std::io::Result<()> {
+        self.handle.write(data).await
+    }
+}
+
+/// Ask the user for a file to open
+pub async fn open_file(dialog: FileDialog) -> Option<PickedFile> {
+    show(dialog, Kind::Open).await
+}
+
+/// Ask the user where to save a file
+///
+/// On the web this resolves at once, see the [module docs](self).
+pub async fn save_file(dialog: FileDialog) -> Option<PickedFile> {
+    show(dialog, Kind::Save).await
+}
+
+/// Show an open dialog and pass the result to `on_picked` on the UI thread
+///
+/// Dropping the returned handle closes nothing but skips the callback.
+pub fn open_file_then<C>(dialog: FileDialog, on_picked: C) -> TaskHandle
+where
+    C: FnOnce(Option<PickedFile>) + task::MaybeSend + 'static,
+{
+    task::spawn_then(open_file(dialog), on_picked)
+}
+
+/// Show a save dialog and pass the result to `on_picked` on the UI thread
+///
+/// Dropping the returned handle closes nothing but skips the callback.
+pub fn save_file_then<C>(dialog: FileDialog, on_picked: C) -> TaskHandle
+where
+    C: FnOnce(Option<PickedFile>) + task::MaybeSend + 'static,
+{
+    task::spawn_then(save_file(dialog), on_picked)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Open,
+    Save,
+}
+
+#[cfg(any(test, feature = "headless"))]
+async fn show(_dialog: FileDialog, kind: Kind) -> Option<PickedFile> {
+    tracing::info!("Headless build, cancelling {:?} file dialog", kind);
+    None
+}
+
+#[cfg(not(any(test, feature = "headless")))]
+async fn show(dialog: FileDialog, kind: Kind) -> Option<PickedFile> {
+    let dialog = dialog.to_rfd();
+    let handle = match kind {
+        Kind::Open => dialog.pick_file().await,
+        Kind::Save => dialog.save_file().await,
+    };
+    handle.map(|handle| PickedFile { handle })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_keep_order_and_drop_dots() {
+        let dialog = FileDialog::new()
+            .filter("Images", &[".png", "jpg"])
+            .filter("All", &["*"]);
+        assert_eq!(
+            dialog.filters(),
+            &[
+                FileFilter {
+                    name: "Images".into(),
+                    extensions: vec!["png".into(), "jpg".into()],
+                },
+                FileFilter {
+                    name: "All".into(),
+                    extensions: vec!["*".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_headless_dialogs_are_cancelled() {
+        let dialog = FileDialog::new().title("Open").directory("/tmp");
+        assert!(futures::executor::block_on(open_file(dialog.clone())).is_none());
+        assert!(futures::executor::block_on(save_file(dialog.file_name("a.txt"))).is_none());
+    }
+}
@@ -6,9 +6,13 @@ use std::rc::Rc;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use strato_core::event::{
-    Event, KeyCode, KeyboardEvent, Modifiers, MouseButton, MouseEvent, WindowEvent,
+    Event, GestureEvent, KeyCode, KeyboardEvent, Modifiers, MouseButton, MouseEvent, TouchEvent,
+    WindowEvent,
 };
+use strato_core::touch::TouchGestures;
+use strato_core::window::CursorIcon;
 use strato_renderer::backend::WgpuBackend;
+use strato_renderer::gpu::RecoveryEvent;
 use strato_renderer::Backend;
 use winit::window::Window;
 
@@ -28,10 +32,25 @@ struct AppState {
     last_update: Instant,
     app: Option<Application>,
     cursor_position: winit::dpi::PhysicalPosition<f64>,
+    modifiers: Modifiers,
     scale_factor: f64,
+    cursor_icon: CursorIcon,
+    touch_gestures: TouchGestures,
 }
 
 impl AppState {
+    /// Show the cursor the widget under the pointer asks for
+    fn update_cursor_icon(&mut self) {
+        let (Some(app), Some(window)) = (&self.app, &self.winit_window) else {
+            return;
+        };
+        let icon = app.cursor_icon_at(to_logical(self.cursor_position, self.scale_factor));
+        if icon != self.cursor_icon {
+            self.cursor_icon = icon;
+            window.set_cursor_icon(to_winit_cursor(icon));
+        }
+    }
+
     fn new() -> Self {
         Self {
             window_created: false,
@@ -42,7 +61,10 @@ impl AppState {
             last_update: Instant::now(),
             app: None,
             cursor_position: winit::dpi::PhysicalPosition::new(0.0, 0.0),
+            modifiers: Modifiers::default(),
             scale_factor: 1.0,
+            cursor_icon: CursorIcon::Default,
+            touch_gestures: TouchGestures::new(),
         }
     }
 }
@@ -51,6 +73,8 @@ impl AppState {
 pub struct EventLoop {
     #[cfg(not(target_arch = "wasm32"))]
     inner: winit::event_loop::EventLoop<CustomEvent>,
+    #[cfg(not(target_arch = "wasm32"))]
+    actions: ActionQueue,
     #[cfg(target_arch = "wasm32")]
     _phantom: std::marker::PhantomData<()>,
 }
@@ -64,8 +88,10 @@ impl EventLoop {
         let inner = EventLoopBuilder::with_user_event()
             .build()
             .map_err(|_| EventLoopError::CreationFailed)?;
+        let actions = ActionQueue::new(Some(inner.create_proxy()));
+        set_ui_actions(actions.clone());
 
-        Ok(Self { inner })
+        Ok(Self { inner, actions })
     }
 
     #[cfg(target_arch = "wasm32")]
@@ -81,6 +107,7 @@ impl EventLoop {
         {
             EventLoopProxy {
                 inner: self.inner.create_proxy(),
+                actions: self.actions.clone(),
             }
         }
 
@@ -91,6 +118,16 @@ impl EventLoop {
         }
     }
 
+    /// Run the actions sent through this loop's proxies since the last
+    /// call, in order, returning how many ran
+    ///
+    /// The built-in run methods call it when woken by an action and before
+    /// every frame; an application driving its own loop must do the same.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn run_pending_actions(&self) -> usize {
+        self.actions.run()
+    }
+
     /// Run the event loop with basic event handling
     #[cfg(not(target_arch = "wasm32"))]
     pub fn run<F>(self, mut handler: F) -> !
@@ -100,9 +137,12 @@ impl EventLoop {
         use winit::event::{Event as WinitEvent, WindowEvent as WinitWindowEvent};
         use winit::event_loop::ControlFlow;
 
+        let actions = self.actions.clone();
         let mut last_update = Instant::now();
         let mut cursor_position = winit::dpi::PhysicalPosition::new(0.0, 0.0);
+        let mut modifiers = Modifiers::default();
         let mut scale_factor = 1.0;
+        let mut touch_gestures = TouchGestures::new();
 
         self.inner
             .run(move |event, elwt| {
@@ -131,11 +171,15 @@ impl EventLoop {
                                         device_id,
                                     },
                                     cursor_position,
+                                    modifiers,
                                     scale_factor,
                                 ) {
                                     handler(strato_event);
                                 }
                             }
+                            WinitWindowEvent::ModifiersChanged(changed) => {
+                                modifiers = convert_modifiers(changed.state());
+                            }
                             WinitWindowEvent::ScaleFactorChanged {
                                 scale_factor: sf,
                                 inner_size_writer,
@@ -147,21 +191,29 @@ impl EventLoop {
                                         inner_size_writer,
                                     },
                                     cursor_position,
+                                    modifiers,
                                     scale_factor,
                                 ) {
                                     handler(strato_event);
                                 }
                             }
                             _ => {
-                                if let Some(strato_event) =
-                                    convert_window_event(event, cursor_position, scale_factor)
-                                {
+                                if let Some(strato_event) = convert_window_event(
+                                    event,
+                                    cursor_position,
+                                    modifiers,
+                                    scale_factor,
+                                ) {
+                                    let derived = touch_gestures.process(&strato_event);
                                     handler(strato_event);
+                                    derived.into_iter().for_each(&mut handler);
                                 }
                             }
                         }
                     }
                     WinitEvent::AboutToWait => {
+                        actions.run();
+
                         // Implement frame rate limiting
                         let now = Instant::now();
                         let frame_time = Duration::from_millis(16); // ~60 FPS
@@ -173,6 +225,9 @@ impl EventLoop {
                             elwt.set_control_flow(ControlFlow::WaitUntil(last_update + frame_time));
                         }
                     }
+                    WinitEvent::UserEvent(custom) if is_action_wakeup(&custom.event) => {
+                        actions.run();
+                    }
                     WinitEvent::UserEvent(custom) => {
                         handler(Event::Custom(Arc::new(custom.event)));
                     }
@@ -199,6 +254,7 @@ impl EventLoop {
         use winit::event_loop::ControlFlow;
 
         let state = Rc::new(RefCell::new(AppState::new()));
+        let actions = self.actions.clone();
 
         self.inner
             .run(move |event, event_loop_window_target| {
@@ -234,11 +290,15 @@ impl EventLoop {
                                     device_id,
                                 },
                                 state.cursor_position,
+                                state.modifiers,
                                 state.scale_factor,
                             ) {
                                 handler(strato_event);
                             }
                         }
+                        WinitWindowEvent::ModifiersChanged(changed) => {
+                            state.modifiers = convert_modifiers(changed.state());
+                        }
                         WinitWindowEvent::ScaleFactorChanged {
                             scale_factor,
                             inner_size_writer,
@@ -250,6 +310,7 @@ impl EventLoop {
                                     inner_size_writer,
                                 },
                                 state.cursor_position,
+                                state.modifiers,
                                 state.scale_factor,
                             ) {
                                 handler(strato_event);
@@ -259,13 +320,18 @@ impl EventLoop {
                             if let Some(strato_event) = convert_window_event(
                                 event,
                                 state.cursor_position,
+                                state.modifiers,
                                 state.scale_factor,
                             ) {
+                                let derived = state.touch_gestures.process(&strato_event);
                                 handler(strato_event);
+                                derived.into_iter().for_each(&mut handler);
                             }
                         }
                     },
                     WinitEvent::AboutToWait => {
+                        actions.run();
+
                         let now = Instant::now();
                         let frame_time = Duration::from_millis(16);
 
@@ -277,6 +343,10 @@ impl EventLoop {
                             }
                         }
                     }
+                    WinitEvent::UserEvent(custom) if is_action_wakeup(&custom.event) => {
+                        actions.run();
+                        state.needs_redraw = true;
+                    }
                     WinitEvent::UserEvent(custom) => {
                         handler(Event::Custom(Arc::new(custom.event)));
                     }
@@ -301,6 +371,7 @@ impl EventLoop {
 
         let app_state = Rc::new(RefCell::new(AppState::new()));
         let mut handler = handler;
+        let actions = self.actions.clone();
 
         // Store the application in the state
         app_state.borrow_mut().app = Some(app);
@@ -321,17 +392,34 @@ impl EventLoop {
                             // Store the window first
                             state.winit_window = Some(window.clone());
 
-                            // Initialize Backend
-                            println!("=== INITIALIZING BACKEND ===");
-                            let mut backend = Box::new(WgpuBackend::new());
-                            pollster::block_on(backend.init(&*window))
-                                .expect("Failed to init backend");
+                            // Initialize Backend, unless the application brought one
+                            let mut backend =
+                                match state.app.as_mut().and_then(|app| app.take_backend()) {
+                                    Some(backend) => backend,
+                                    None => {
+                                        println!("=== INITIALIZING BACKEND ===");
+                                        let mut backend = Box::new(WgpuBackend::new());
+                                        backend.set_transparent(window_builder.transparent);
+                                        pollster::block_on(backend.init(&*window))
+                                            .expect("Failed to init backend");
+                                        backend as Box<dyn Backend>
+                                    }
+                                };
 
                             // Set initial scale factor
                             let scale_factor = window.scale_factor();
                             backend.set_scale_factor(scale_factor);
                             state.scale_factor = scale_factor;
 
+                            if let Some(app) = state.app.as_mut() {
+                                app.set_gpu_info(backend.gpu_info());
+                                for node in app.take_post_process() {
+                                    if let Err(e) = backend.add_post_process(node) {
+                                        eprintln!("Failed to add post-process pass: {}", e);
+                                    }
+                                }
+                            }
+
                             state.backend = Some(backend);
 
                             state.renderer_initialized = true;
@@ -342,6 +430,9 @@ impl EventLoop {
                     }
                     WinitEvent::WindowEvent { event, .. } => {
                         match event {
+                            WindowEvent::ModifiersChanged(changed) => {
+                                state.modifiers = convert_modifiers(changed.state());
+                            }
                             WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                                 println!("Scale factor changed to: {}", scale_factor);
                                 state.scale_factor = scale_factor;
@@ -361,12 +452,14 @@ impl EventLoop {
                                         device_id,
                                     },
                                     state.cursor_position,
+                                    state.modifiers,
                                     state.scale_factor,
                                 ) {
                                     // println!("EventLoop: CursorMoved to {:?} (Logical), Scale: {}", strato_event, state.scale_factor);
                                     if let Some(app) = &mut state.app {
                                         app.handle_event(strato_event.clone());
                                     }
+                                    state.update_cursor_icon();
                                     handler(strato_event);
                                 }
                             }
@@ -412,15 +505,25 @@ impl EventLoop {
                                 let logical_height = physical_height / scale_factor;
 
                                 // Call the application's render method and get the render batch
+                                let mut recoveries = Vec::new();
                                 if let Some(app) = &mut state.app {
+                                    app.begin_frame();
                                     if let Err(e) = app.render_simple(logical_width, logical_height)
                                     {
                                         eprintln!("Render error: {}", e);
                                     } else {
+                                        let clear_color = app.clear_color();
+                                        let text_hinting = app.text_hinting();
+                                        let resize_buffering = app.resize_buffering();
                                         // Get the render batch
                                         if let Some(batch) = app.get_render_batch() {
                                             // Use Backend
                                             if let Some(backend) = &mut state.backend {
+                                                if let Some(color) = clear_color {
+                                                    backend.set_clear_color(color);
+                                                }
+                                                backend.set_text_hinting(text_hinting);
+                                                backend.set_resize_buffering(resize_buffering);
                                                 if let Err(e) = backend.begin_frame() {
                                                     tracing::error!(
                                                         "Backend begin_frame error: {}",
@@ -441,11 +544,45 @@ impl EventLoop {
                                                             e
                                                         );
                                                     }
+
+                                                    recoveries = backend.take_recovery_events();
                                                 }
                                             }
                                         }
                                     }
                                 }
+
+                                if let Some(app) = &mut state.app {
+                                    app.end_frame();
+                                }
+
+                                // A recreated device may be on another adapter
+                                if !recoveries.is_empty() {
+                                    let gpu_info = state
+                                        .backend
+                                        .as_ref()
+                                        .and_then(|backend| backend.gpu_info());
+                                    if let Some(app) = &mut state.app {
+                                        app.set_gpu_info(gpu_info);
+                                    }
+                                }
+
+                                // Tell the application the renderer recovered so it can
+                                // rebuild anything it cached on the GPU
+                                for recovery in recoveries {
+                                    let event = strato_core::event::Event::Window(
+                                        strato_core::event::WindowEvent::RendererRecovered {
+                                            device_recreated: matches!(
+                                                recovery,
+                                                RecoveryEvent::DeviceRecreated { .. }
+                                            ),
+                                        },
+                                    );
+                                    if let Some(app) = &mut state.app {
+                                        app.handle_event(event.clone());
+                                    }
+                                    handler(event);
+                                }
                             }
                             WindowEvent::CloseRequested => {
                                 let event = strato_core::event::Event::Window(
@@ -461,32 +598,65 @@ impl EventLoop {
                                 if let Some(strato_event) = convert_window_event(
                                     event,
                                     state.cursor_position,
+                                    state.modifiers,
                                     state.scale_factor,
                                 ) {
                                     // println!("EventLoop: Event {:?}", strato_event);
-                                    if let Some(app) = &mut state.app {
-                                        app.handle_event(strato_event.clone());
+                                    // Touches are followed by the mouse and gesture
+                                    // events derived from them
+                                    let derived = state.touch_gestures.process(&strato_event);
+                                    for event in std::iter::once(strato_event).chain(derived) {
+                                        if let Some(app) = &mut state.app {
+                                            app.handle_event(event.clone());
+                                        }
+                                        handler(event);
                                     }
-                                    handler(strato_event);
                                 }
                             }
                         }
                     }
                     WinitEvent::AboutToWait => {
-                        // Always request redraw to maintain continuous rendering
+                        // Finished tasks update state before the next frame
+                        actions.run();
+
+                        // Redraw continuously, or on demand once something changed
                         if state.renderer_initialized {
-                            if let Some(window) = &state.winit_window {
-                                window.request_redraw();
+                            let redraw = state.app.as_ref().is_none_or(|app| app.should_redraw());
+                            if redraw {
+                                if let Some(window) = &state.winit_window {
+                                    window.request_redraw();
+                                }
+                                state.needs_redraw = true; // Keep requesting redraws
+                            } else {
+                                // Signals set off the UI thread don't wake the
+                                // loop, so look at the change count once a frame
+                                event_loop_window_target.set_control_flow(
+                                    winit::event_loop::ControlFlow::WaitUntil(
+                                        Instant::now() + Duration::from_millis(16),
+                                    ),
+                                );
                             }
-                            state.needs_redraw = true; // Keep requesting redraws
+                        }
+                    }
+                    WinitEvent::UserEvent(custom_event)
+                        if is_action_wakeup(&custom_event.event) =>
+                    {
+                        actions.run();
+                        if let Some(window) = &state.winit_window {
+                            window.request_redraw();
                         }
                     }
                     WinitEvent::UserEvent(custom_event) => {
                         if let Some(app) = &mut state.app {
-                            app.handle_event(custom_event.event.clone());
+                            app.send_event(custom_event.event.clone());
                         }
                         handler(custom_event.event);
                     }
+                    WinitEvent::LoopExiting => {
+                        if let Some(app) = &mut state.app {
+                            app.shutdown();
+                        }
+                    }
                     _ => {}
                 }
             })
@@ -570,10 +740,13 @@ impl Default for EventLoop {
     }
 }
 
-/// Event loop proxy for sending custom events
+/// Event loop proxy for sending custom events and actions from any thread
+#[derive(Clone)]
 pub struct EventLoopProxy {
     #[cfg(not(target_arch = "wasm32"))]
     inner: winit::event_loop::EventLoopProxy<CustomEvent>,
+    #[cfg(not(target_arch = "wasm32"))]
+    actions: ActionQueue,
     #[cfg(target_arch = "wasm32")]
     sender: mpsc::Sender<Event>,
 }
@@ -595,6 +768,132 @@ impl EventLoopProxy {
                 .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
         }
     }
+
+    /// Run `action` on the UI thread, then redraw
+    ///
+    /// The event loop wakes up, runs the actions sent so far in the order
+    /// they were sent and requests a redraw, so a [`Signal`] set by the
+    /// action is shown right away. Fails if the event loop has exited, in
+    /// which case `action` is dropped without running.
+    ///
+    /// ```no_run
+    /// use strato_core::state::Signal;
+    /// use strato_platform::EventLoop;
+    ///
+    /// let event_loop = EventLoop::new().unwrap();
+    /// let status = Signal::new(String::from("Working..."));
+    ///
+    /// let proxy = event_loop.create_proxy();
+    /// let shown = status.clone();
+    /// std::thread::spawn(move || {
+    ///     let text = String::from("Done");
+    ///     let _ = proxy.send_action(Box::new(move || shown.set(text)));
+    /// });
+    /// ```
+    ///
+    /// [`Signal`]: strato_core::state::Signal
+    pub fn send_action(&self, action: Action) -> Result<(), Box<dyn std::error::Error>> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            self.actions.post(action).map_err(Into::into)
+        }
+
+        // There are no other threads on the web, so this is the UI thread
+        #[cfg(target_arch = "wasm32")]
+        {
+            action();
+            Ok(())
+        }
+    }
+}
+
+/// Closure run on the UI thread; see [`EventLoopProxy::send_action`]
+pub type Action = Box<dyn FnOnce() + Send>;
+
+/// Actions waiting for one event loop to run them, shared by the loop and
+/// its proxies
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Clone, Default)]
+pub(crate) struct ActionQueue {
+    actions: Arc<std::sync::Mutex<Vec<Action>>>,
+    /// Wakes the loop owning the queue, `None` for a queue that is only
+    /// ever drained by hand
+    waker: Option<winit::event_loop::EventLoopProxy<CustomEvent>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ActionQueue {
+    fn new(waker: Option<winit::event_loop::EventLoopProxy<CustomEvent>>) -> Self {
+        Self {
+            actions: Arc::default(),
+            waker,
+        }
+    }
+
+    /// Queue `action` and wake the event loop, or drop it if the loop has
+    /// exited
+    pub(crate) fn post(&self, action: Action) -> Result<(), EventLoopError> {
+        // Holding the lock across the wakeup keeps the loop from draining
+        // before the action is in
+        let mut actions = self.actions.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(waker) = &self.waker {
+            waker
+                .send_event(CustomEvent {
+                    event: Event::Custom(Arc::new(ActionWakeup)),
+                })
+                .map_err(|_| EventLoopError::SendFailed)?;
+        }
+        actions.push(action);
+        Ok(())
+    }
+
+    /// Run the actions posted since the last call, in order, returning how
+    /// many ran
+    pub(crate) fn run(&self) -> usize {
+        let actions = std::mem::take(&mut *self.actions.lock().unwrap_or_else(|e| e.into_inner()));
+        let count = actions.len();
+        for action in actions {
+            action();
+        }
+        count
+    }
+}
+
+/// Payload of the event waking the event loop to run actions
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+struct ActionWakeup;
+
+#[cfg(not(target_arch = "wasm32"))]
+fn is_action_wakeup(event: &Event) -> bool {
+    matches!(event, Event::Custom(payload) if payload.is::<ActionWakeup>())
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+thread_local! {
+    /// Queue of the event loop created on this thread
+    static THREAD_ACTIONS: RefCell<Option<ActionQueue>> = const { RefCell::new(None) };
+}
+
+/// Queue of the most recently created event loop, for threads without one
+#[cfg(not(target_arch = "wasm32"))]
+static UI_ACTIONS: std::sync::Mutex<Option<ActionQueue>> = std::sync::Mutex::new(None);
+
+/// Make `actions` the queue [`ui_actions`] finds, on this thread and on
+/// threads without an event loop
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn set_ui_actions(actions: ActionQueue) {
+    THREAD_ACTIONS.with(|slot| *slot.borrow_mut() = Some(actions.clone()));
+    *UI_ACTIONS.lock().unwrap_or_else(|e| e.into_inner()) = Some(actions);
+}
+
+/// Queue of the event loop created on the calling thread, or else of the
+/// most recently created one
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn ui_actions() -> Option<ActionQueue> {
+    THREAD_ACTIONS
+        .with(|slot| slot.borrow().clone())
+        .or_else(|| UI_ACTIONS.lock().unwrap_or_else(|e| e.into_inner()).clone())
 }
 
 /// Event loop error
@@ -608,13 +907,45 @@ pub enum EventLoopError {
     RunFailed,
 }
 
+/// Convert a physical pixel position to the logical space widgets use
+///
+/// Layout and the renderer's projection work in logical pixels, so every
+/// position delivered to widgets goes through this conversion.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn to_logical(position: winit::dpi::PhysicalPosition<f64>, scale_factor: f64) -> glam::Vec2 {
+    let logical = position.to_logical::<f64>(scale_factor);
+    glam::Vec2::new(logical.x as f32, logical.y as f32)
+}
+
+/// Convert winit's modifier state, as reported by `ModifiersChanged`
+#[cfg(not(target_arch = "wasm32"))]
+pub fn convert_modifiers(state: winit::keyboard::ModifiersState) -> Modifiers {
+    Modifiers {
+        shift: state.shift_key(),
+        control: state.control_key(),
+        alt: state.alt_key(),
+        super_key: state.super_key(),
+    }
+}
+
 /// Convert winit event to StratoUI event
+///
+/// Pointer positions and pixel scroll deltas are converted from the window's
+/// physical pixels to logical pixels using `scale_factor`, so they match the
+/// space widgets are laid out in. Wheel deltas follow the
+/// [`ScrollSettings`](crate::scroll::ScrollSettings) in effect. `cursor_position` is the last physical
+/// cursor position, used for button events which carry none of their own.
+/// Resize events keep the surface size in physical pixels. Keyboard and
+/// pointer events carry `modifiers`, the keys held at the time; callers keep
+/// them up to date from `ModifiersChanged` with [`convert_modifiers`].
 #[cfg(not(target_arch = "wasm32"))]
 pub fn convert_window_event(
     event: winit::event::WindowEvent,
     cursor_position: winit::dpi::PhysicalPosition<f64>,
+    modifiers: Modifiers,
     scale_factor: f64,
 ) -> Option<Event> {
+    use crate::scroll::scroll_settings;
     use glam::Vec2;
     use winit::event::{ElementState, MouseButton as MB, WindowEvent as WE};
 
@@ -630,16 +961,12 @@ pub fn convert_window_event(
 
         WE::Focused(focused) => Some(Event::Window(WindowEvent::Focus(focused))),
 
-        WE::CursorMoved { position, .. } => {
-            let logical_x = position.x / scale_factor;
-            let logical_y = position.y / scale_factor;
-            Some(Event::MouseMove(MouseEvent {
-                position: Vec2::new(logical_x as f32, logical_y as f32),
-                button: None,
-                modifiers: Modifiers::default(),
-                delta: Vec2::ZERO,
-            }))
-        }
+        WE::CursorMoved { position, .. } => Some(Event::MouseMove(MouseEvent {
+            position: to_logical(position, scale_factor),
+            button: None,
+            modifiers,
+            delta: Vec2::ZERO,
+        })),
 
         WE::MouseInput { state, button, .. } => {
             let button = match button {
@@ -651,36 +978,39 @@ pub fn convert_window_event(
                 MB::Other(n) => MouseButton::Other(n),
             };
 
-            let logical_x = cursor_position.x / scale_factor;
-            let logical_y = cursor_position.y / scale_factor;
+            let position = to_logical(cursor_position, scale_factor);
 
             match state {
                 ElementState::Pressed => Some(Event::MouseDown(MouseEvent {
-                    position: Vec2::new(logical_x as f32, logical_y as f32),
+                    position,
                     button: Some(button),
-                    modifiers: Modifiers::default(),
+                    modifiers,
                     delta: Vec2::ZERO,
                 })),
                 ElementState::Released => Some(Event::MouseUp(MouseEvent {
-                    position: Vec2::new(logical_x as f32, logical_y as f32),
+                    position,
                     button: Some(button),
-                    modifiers: Modifiers::default(),
+                    modifiers,
                     delta: Vec2::ZERO,
                 })),
             }
         }
 
         WE::MouseWheel { delta, .. } => {
-            let delta_vec = match delta {
-                winit::event::MouseScrollDelta::LineDelta(x, y) => Vec2::new(x * 20.0, y * 20.0),
+            let settings = scroll_settings();
+            let (delta_vec, precise) = match delta {
+                winit::event::MouseScrollDelta::LineDelta(x, y) => {
+                    (settings.lines(Vec2::new(x, y)), false)
+                }
                 winit::event::MouseScrollDelta::PixelDelta(pos) => {
-                    Vec2::new(pos.x as f32, pos.y as f32)
+                    (settings.pixels(to_logical(pos, scale_factor)), true)
                 }
             };
 
             Some(Event::MouseWheel {
                 delta: delta_vec,
-                modifiers: Modifiers::default(),
+                modifiers,
+                precise,
             })
         }
 
@@ -695,13 +1025,13 @@ pub fn convert_window_event(
                 match event.state {
                     ElementState::Pressed => Some(Event::KeyDown(KeyboardEvent {
                         key_code,
-                        modifiers: Modifiers::default(),
+                        modifiers,
                         is_repeat: event.repeat,
                         text: None,
                     })),
                     ElementState::Released => Some(Event::KeyUp(KeyboardEvent {
                         key_code,
-                        modifiers: Modifiers::default(),
+                        modifiers,
                         is_repeat: event.repeat,
                         text: None,
                     })),
@@ -713,10 +1043,52 @@ pub fn convert_window_event(
 
         WE::Ime(winit::event::Ime::Commit(text)) => Some(Event::TextInput(text)),
 
+        WE::Touch(touch) => {
+            let touch_event = TouchEvent {
+                id: touch.id,
+                position: to_logical(touch.location, scale_factor),
+                force: touch.force.map(|force| force.normalized() as f32),
+            };
+            Some(match touch.phase {
+                winit::event::TouchPhase::Started => Event::TouchStart(touch_event),
+                winit::event::TouchPhase::Moved => Event::TouchMove(touch_event),
+                winit::event::TouchPhase::Ended => Event::TouchEnd(touch_event),
+                winit::event::TouchPhase::Cancelled => Event::TouchCancel(touch_event),
+            })
+        }
+
+        // macOS trackpad pinches, around the pointer
+        WE::TouchpadMagnify { delta, .. } => Some(Event::Gesture(GestureEvent::Pinch {
+            center: to_logical(cursor_position, scale_factor),
+            scale: 1.0 + delta as f32,
+        })),
+
         _ => None,
     }
 }
 
+/// Convert a StratoUI cursor icon to the winit cursor
+#[cfg(not(target_arch = "wasm32"))]
+pub fn to_winit_cursor(icon: CursorIcon) -> winit::window::CursorIcon {
+    use winit::window::CursorIcon as WC;
+
+    match icon {
+        CursorIcon::Default => WC::Default,
+        CursorIcon::Pointer => WC::Pointer,
+        CursorIcon::Text => WC::Text,
+        CursorIcon::Crosshair => WC::Crosshair,
+        CursorIcon::Move => WC::Move,
+        CursorIcon::NotAllowed => WC::NotAllowed,
+        CursorIcon::Wait => WC::Wait,
+        CursorIcon::Progress => WC::Progress,
+        CursorIcon::Help => WC::Help,
+        CursorIcon::ResizeNS => WC::NsResize,
+        CursorIcon::ResizeEW => WC::EwResize,
+        CursorIcon::ResizeNESW => WC::NeswResize,
+        CursorIcon::ResizeNWSE => WC::NwseResize,
+    }
+}
+
 /// Convert winit key code to StratoUI key code
 #[cfg(not(target_arch = "wasm32"))]
 fn convert_physical_key_code(keycode: winit::keyboard::KeyCode) -> KeyCode {
@@ -800,3 +1172,117 @@ fn convert_physical_key_code(keycode: winit::keyboard::KeyCode) -> KeyCode {
         _ => KeyCode::A, // Default fallback
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use strato_core::layout::{Layout, Size};
+    use strato_renderer::batch::RenderBatch;
+    use strato_widgets::widget::{dispatch_event, Widget};
+    use strato_widgets::Container;
+    use winit::dpi::PhysicalPosition;
+    use winit::event::{
+        DeviceId, ElementState, MouseButton as MB, Touch, TouchPhase, WindowEvent as WE,
+    };
+
+    fn mouse_input(state: ElementState, physical: PhysicalPosition<f64>, scale: f64) -> Event {
+        // SAFETY: the dummy id is only compared, never passed back to winit
+        let device_id = unsafe { DeviceId::dummy() };
+        let event = WE::MouseInput {
+            device_id,
+            state,
+            button: MB::Left,
+        };
+        convert_window_event(event, physical, Modifiers::default(), scale)
+            .expect("mouse input converts")
+    }
+
+    #[test]
+    fn test_click_positions_are_logical_on_hidpi_surface() {
+        static CLICKED: AtomicBool = AtomicBool::new(false);
+        let mut container = Container::new().on_click(|| CLICKED.store(true, Ordering::SeqCst));
+        let layout = Layout::new(Vec2::new(100.0, 100.0), Size::new(20.0, 20.0));
+        container.render(&mut RenderBatch::new(), layout);
+
+        let physical = PhysicalPosition::new(200.0, 200.0);
+        let down = mouse_input(ElementState::Pressed, physical, 2.0);
+        match &down {
+            Event::MouseDown(mouse) => assert_eq!(mouse.position, Vec2::new(100.0, 100.0)),
+            other => panic!("expected MouseDown, got {:?}", other),
+        }
+
+        dispatch_event(&mut container, &down);
+        dispatch_event(
+            &mut container,
+            &mouse_input(ElementState::Released, physical, 2.0),
+        );
+        assert!(CLICKED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_touch_taps_click_like_the_mouse() {
+        static CLICKED: AtomicBool = AtomicBool::new(false);
+        let mut container = Container::new().on_click(|| CLICKED.store(true, Ordering::SeqCst));
+        let layout = Layout::new(Vec2::new(100.0, 100.0), Size::new(20.0, 20.0));
+        container.render(&mut RenderBatch::new(), layout);
+
+        let mut gestures = TouchGestures::new();
+        for phase in [TouchPhase::Started, TouchPhase::Ended] {
+            // SAFETY: the dummy id is only compared, never passed back to winit
+            let device_id = unsafe { DeviceId::dummy() };
+            let touch = WE::Touch(Touch {
+                device_id,
+                phase,
+                location: PhysicalPosition::new(220.0, 220.0),
+                force: None,
+                id: 7,
+            });
+            let event = convert_window_event(
+                touch,
+                PhysicalPosition::new(0.0, 0.0),
+                Modifiers::default(),
+                2.0,
+            )
+            .expect("touch converts");
+            assert!(matches!(
+                &event,
+                Event::TouchStart(touch) | Event::TouchEnd(touch)
+                    if touch.id == 7 && touch.position == Vec2::new(110.0, 110.0)
+            ));
+            for derived in gestures.process(&event) {
+                dispatch_event(&mut container, &derived);
+            }
+        }
+        assert!(CLICKED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_to_logical_divides_by_scale_factor() {
+        let position = PhysicalPosition::new(300.0, 90.0);
+        assert_eq!(to_logical(position, 1.5), Vec2::new(200.0, 60.0));
+        assert_eq!(to_logical(position, 1.0), Vec2::new(300.0, 90.0));
+    }
+
+    #[test]
+    fn test_converted_events_carry_the_given_modifiers() {
+        let held = convert_modifiers(
+            winit::keyboard::ModifiersState::CONTROL | winit::keyboard::ModifiersState::SHIFT,
+        );
+        assert!(held.control && held.shift && !held.alt && !held.super_key);
+
+        // SAFETY: the dummy id is only compared, never passed back to winit
+        let device_id = unsafe { DeviceId::dummy() };
+        let event = WE::MouseInput {
+            device_id,
+            state: ElementState::Pressed,
+            button: MB::Left,
+        };
+        let converted = convert_window_event(event, PhysicalPosition::new(0.0, 0.0), held, 1.0);
+        assert!(matches!(
+            converted,
+            Some(Event::MouseDown(MouseEvent { modifiers, .. })) if modifiers == held
+        ));
+    }
+}
@@ -0,0 +1,108 @@
+//! Per-frame timing
+//!
+//! Callbacks registered with
+//! [`ApplicationBuilder::on_frame`](crate::ApplicationBuilder::on_frame) run
+//! once per rendered frame with a [`FrameContext`] describing the frame's
+//! timing, for game-like updates and custom animations.
+//!
+//! The context also carries the input held as the frame begins, e.g. to
+//! move something while an arrow key is down:
+//!
+//! ```no_run
+//! use strato_core::event::KeyCode;
+//! use strato_platform::ApplicationBuilder;
+//! use strato_widgets::Text;
+//!
+//! ApplicationBuilder::new()
+//!     .on_frame(|frame| {
+//!         if frame.input.is_key_down(KeyCode::Right) {
+//!             let step = 120.0 * frame.delta_time;
+//!             // move the player by `step`
+//!         }
+//!     })
+//!     .run(Text::new("Game"));
+//! ```
+//!
+//! It is a snapshot: input arriving during the frame shows in the next one,
+//! and a key pressed and released between two frames never shows. Events
+//! remain the authoritative record of input.
+//!
+//! # Ordering
+//!
+//! At the start of every frame the application:
+//!
+//! 1. runs the plugins' [`before_frame`](crate::Plugin::before_frame) hooks,
+//! 2. runs the frame callbacks in registration order,
+//! 3. runs the widget update pass, calling
+//!    [`Widget::update`](strato_widgets::Widget::update) on every widget with
+//!    the same delta time,
+//! 4. lays the tree out and renders it.
+//!
+//! State changed by a frame callback is therefore visible to the update pass
+//! and drawn in the same frame. Widget animations sample the clock while
+//! rendering, after all of the above.
+
+use std::time::{Duration, Instant};
+use strato_core::input::InputState;
+
+/// Timing of the frame being produced
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameContext {
+    /// Seconds since the previous frame began, 0.0 for the first frame
+    pub delta_time: f32,
+    /// Number of frames begun before this one
+    pub frame_index: u64,
+    /// Time since the first frame began
+    pub elapsed: Duration,
+    /// Input held as the frame began
+    pub input: InputState,
+}
+
+/// Clock turning frame start times into [`FrameContext`]s
+#[derive(Debug, Default)]
+pub(crate) struct FrameClock {
+    first: Option<Instant>,
+    last: Option<Instant>,
+    frames: u64,
+}
+
+impl FrameClock {
+    /// Begin a frame at `now`
+    pub(crate) fn tick(&mut self, now: Instant) -> FrameContext {
+        let first = *self.first.get_or_insert(now);
+        let delta = self
+            .last
+            .replace(now)
+            .map_or(Duration::ZERO, |last| now.saturating_duration_since(last));
+
+        let frame = FrameContext {
+            delta_time: delta.as_secs_f32(),
+            frame_index: self.frames,
+            elapsed: now.saturating_duration_since(first),
+            input: InputState::default(),
+        };
+        self.frames += 1;
+        frame
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frames_count_and_measure_time() {
+        let start = Instant::now();
+        let mut clock = FrameClock::default();
+
+        let first = clock.tick(start);
+        assert_eq!((first.delta_time, first.frame_index), (0.0, 0));
+        assert_eq!(first.elapsed, Duration::ZERO);
+
+        clock.tick(start + Duration::from_millis(16));
+        let third = clock.tick(start + Duration::from_millis(50));
+        assert!((third.delta_time - 0.034).abs() < 1e-6);
+        assert_eq!(third.frame_index, 2);
+        assert_eq!(third.elapsed, Duration::from_millis(50));
+    }
+}
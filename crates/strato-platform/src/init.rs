@@ -5,7 +5,7 @@
 //! of the framework initialization.
 
 use std::sync::{Arc, OnceLock, RwLock};
-use strato_core::{Result, StratoError};
+use strato_core::{InitStep, Result, StratoError};
 use strato_renderer::text::TextRenderer;
 
 /// Global text renderer instance to avoid multiple cosmic_text initializations
@@ -74,7 +74,7 @@ impl InitBuilder {
         }
 
         // Initialize core first (which includes logging)
-        strato_core::init()?;
+        strato_core::init().map_err(|e| StratoError::init_failed(InitStep::Core, e))?;
         self.core_initialized = true;
 
         if self.config.enable_logging {
@@ -101,7 +101,7 @@ impl InitBuilder {
             );
         }
 
-        strato_widgets::init()?;
+        strato_widgets::init().map_err(|e| StratoError::init_failed(InitStep::Widgets, e))?;
         self.widgets_initialized = true;
 
         if self.config.enable_logging {
@@ -133,11 +133,15 @@ impl InitBuilder {
 
         // Initialize platform with our custom configuration
         crate::init().map_err(|e| {
-            strato_core::StratoError::platform(format!("Platform init failed: {}", e))
+            StratoError::init_failed(
+                InitStep::Platform,
+                StratoError::wrap("Platform init failed", e),
+            )
         })?;
 
         // Initialize the global text renderer with optimizations
-        self.init_optimized_text_renderer()?;
+        self.init_optimized_text_renderer()
+            .map_err(|e| StratoError::init_failed(InitStep::TextRenderer, e))?;
 
         self.platform_initialized = true;
 
@@ -152,6 +156,9 @@ impl InitBuilder {
     }
 
     /// Initialize all modules at once
+    ///
+    /// On failure the error names the step that failed, see
+    /// [`StratoError::failed_step`].
     pub fn init_all(&mut self) -> Result<()> {
         self.init_core()?.init_widgets()?.init_platform()?;
         Ok(())
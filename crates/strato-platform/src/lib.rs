@@ -3,7 +3,14 @@
 //! Provides cross-platform window management and event handling.
 
 pub mod application;
+pub mod dialog;
 pub mod event_loop;
+pub mod frame;
+pub mod links;
+pub mod motion;
+pub mod plugin;
+pub mod scroll;
+pub mod task;
 pub mod window;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -12,8 +19,12 @@ pub mod desktop;
 #[cfg(target_arch = "wasm32")]
 pub mod web;
 
-pub use application::{Application, ApplicationBuilder};
+pub use application::{Application, ApplicationBuilder, RedrawMode};
 pub use event_loop::{EventLoop, EventLoopProxy};
+pub use frame::FrameContext;
+pub use links::open_url;
+pub use plugin::{ApplicationPlugin, Plugin};
+pub use task::TaskHandle;
 pub use window::{Window, WindowBuilder, WindowId};
 
 use strato_core::event::Event;
@@ -30,6 +41,12 @@ pub enum PlatformError {
     #[error("Platform not supported")]
     Unsupported,
 
+    #[error("Refusing to open {0}: only http, https and mailto URLs are opened")]
+    UnsupportedUrl(String),
+
+    #[error("Failed to open URL: {0}")]
+    OpenUrl(String),
+
     #[error("WebAssembly error: {0}")]
     #[cfg(target_arch = "wasm32")]
     Wasm(String),
@@ -76,6 +93,9 @@ pub mod init;
 /// Initialize the platform layer
 pub fn init() -> Result<(), PlatformError> {
     tracing::info!("StratoUI Platform initialized");
+    motion::apply_system_animation_settings();
+    links::install_url_opener();
+    strato_widgets::text::install_line_metrics();
 
     #[cfg(target_arch = "wasm32")]
     {
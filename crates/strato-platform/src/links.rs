@@ -0,0 +1,150 @@
+//! Opening links in the system browser
+//!
+//! [`open_url`] hands a URL to the OS: the default browser for web pages,
+//! the mail client for `mailto:` links. The platform also installs it as the
+//! widgets' URL opener when it initializes and when an application is
+//! created, so clicking a
+//! [`TextSpan::link`](strato_widgets::text::TextSpan::link) opens the page.
+//! Apps can install their own opener afterwards with [`set_url_opener`].
+//!
+//! With the `headless` feature, and in this crate's tests, nothing is
+//! launched: URLs are still validated, then only logged.
+
+use crate::PlatformError;
+use strato_widgets::text::set_url_opener;
+
+/// Schemes [`open_url`] hands to the OS
+pub const ALLOWED_SCHEMES: &[&str] = &["http", "https", "mailto"];
+
+/// Open `url` in the default browser or mail client
+///
+/// Only `http`, `https` and `mailto` URLs are opened, so a URL taken from
+/// content can't make the OS run a program or open a local file. Uses
+/// `window.open` on the web, `xdg-open` on Linux, `open` on macOS and the
+/// URL protocol handler on Windows, without waiting for them. Failures are
+/// logged as well as returned.
+pub fn open_url(url: &str) -> Result<(), PlatformError> {
+    validate_url(url)?;
+    launch(url).map_err(|e| {
+        tracing::warn!("Failed to open {}: {}", url, e);
+        PlatformError::OpenUrl(format!("{url}: {e}"))
+    })
+}
+
+/// Make clicked URL links open with [`open_url`]
+pub fn install_url_opener() {
+    set_url_opener(|url| {
+        // Already logged
+        let _ = open_url(url);
+    });
+}
+
+/// Check that `url` is a URL [`open_url`] may open
+fn validate_url(url: &str) -> Result<(), PlatformError> {
+    let rejected = || {
+        tracing::warn!("Refusing to open {:?}", url);
+        Err(PlatformError::UnsupportedUrl(url.to_string()))
+    };
+
+    // Whitespace and control characters could split the URL into several
+    // arguments for the opener
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return rejected();
+    }
+    let Some((scheme, rest)) = url.split_once(':') else {
+        return rejected();
+    };
+    let scheme = scheme.to_ascii_lowercase();
+    if !ALLOWED_SCHEMES.contains(&scheme.as_str()) {
+        return rejected();
+    }
+    let valid = match scheme.as_str() {
+        "mailto" => !rest.is_empty(),
+        _ => rest
+            .strip_prefix("//")
+            .is_some_and(|rest| !rest.is_empty() && !rest.starts_with('/')),
+    };
+    if valid {
+        Ok(())
+    } else {
+        rejected()
+    }
+}
+
+#[cfg(any(test, feature = "headless"))]
+fn launch(url: &str) -> std::io::Result<()> {
+    tracing::info!("Headless build, not opening {}", url);
+    Ok(())
+}
+
+#[cfg(all(not(any(test, feature = "headless")), target_arch = "wasm32"))]
+fn launch(url: &str) -> std::io::Result<()> {
+    web_sys::window()
+        .and_then(|window| {
+            window
+                .open_with_url_and_target(url, "_blank")
+                .ok()
+                .flatten()
+        })
+        .map(|_| ())
+        .ok_or_else(|| std::io::Error::other("the browser refused to open a window"))
+}
+
+#[cfg(all(not(any(test, feature = "headless")), not(target_arch = "wasm32")))]
+fn launch(url: &str) -> std::io::Result<()> {
+    use std::process::Command;
+
+    #[cfg(target_os = "linux")]
+    let mut command = Command::new("xdg-open");
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    };
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    return Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("no URL opener on this platform for {url}"),
+    ));
+
+    #[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+    command.arg(url).spawn().map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_web_and_mail_urls_are_opened() {
+        for url in [
+            "https://example.com/docs",
+            "HTTP://example.com",
+            "mailto:support@example.com",
+        ] {
+            assert!(open_url(url).is_ok(), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_other_urls_are_refused() {
+        for url in [
+            "file:///etc/passwd",
+            "javascript:alert(1)",
+            "calc.exe",
+            "https:///etc/passwd",
+            "https://",
+            "mailto:",
+            "https://example.com --new-window",
+            "https://example.com\n/bin/sh",
+        ] {
+            assert!(
+                matches!(open_url(url), Err(PlatformError::UnsupportedUrl(_))),
+                "{url}"
+            );
+        }
+    }
+}
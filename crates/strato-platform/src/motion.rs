@@ -0,0 +1,133 @@
+//! System motion preferences
+//!
+//! Reads the OS "reduce motion" accessibility setting and hands it to the
+//! widget animation system, see [`strato_widgets::animation::AnimationSettings`].
+//! Apps can still override it with
+//! [`set_animation_settings`](strato_widgets::animation::set_animation_settings).
+
+use std::sync::OnceLock;
+use strato_widgets::animation::{set_system_animation_settings, AnimationSettings};
+
+/// The preference as first read, since reading may run a command
+static REDUCE_MOTION: OnceLock<Option<bool>> = OnceLock::new();
+
+/// Whether the user asked the OS to reduce motion, `None` where unknown
+///
+/// Reads `prefers-reduced-motion` on the web, the GNOME animation setting on
+/// Linux, the accessibility "Reduce motion" option on macOS and the client
+/// area animation setting on Windows. The setting is read once; on Linux and
+/// macOS that runs a command, so the first call belongs off the UI thread.
+pub fn system_reduce_motion() -> Option<bool> {
+    *REDUCE_MOTION.get_or_init(read_reduce_motion)
+}
+
+fn read_reduce_motion() -> Option<bool> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        web_sys::window()?
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .map(|query| query.matches())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let enabled = command_output(
+            "gsettings",
+            &["get", "org.gnome.desktop.interface", "enable-animations"],
+        )?;
+        Some(enabled == "false")
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        let reduce = command_output(
+            "defaults",
+            &["read", "com.apple.universalaccess", "reduceMotion"],
+        )?;
+        Some(reduce == "1")
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use windows_sys::Win32::Foundation::BOOL;
+        use windows_sys::Win32::UI::WindowsAndMessaging::{
+            SystemParametersInfoW, SPI_GETCLIENTAREAANIMATION,
+        };
+
+        let mut animate: BOOL = 0;
+        // SAFETY: SPI_GETCLIENTAREAANIMATION writes a single BOOL to the
+        // pointer it is given
+        let ok = unsafe {
+            SystemParametersInfoW(
+                SPI_GETCLIENTAREAANIMATION,
+                0,
+                (&mut animate as *mut BOOL).cast(),
+                0,
+            )
+        };
+        (ok != 0).then_some(animate == 0)
+    }
+
+    #[cfg(not(any(
+        target_arch = "wasm32",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "windows"
+    )))]
+    {
+        None
+    }
+}
+
+/// Read the system motion preference into the animation settings
+///
+/// Called when the platform initializes and when an application starts; a
+/// manual override stays in effect. Where reading the preference runs a
+/// command, the first read happens on a background thread and the settings
+/// follow once it finishes, so startup never waits for it.
+pub fn apply_system_animation_settings() {
+    #[cfg(all(
+        not(target_arch = "wasm32"),
+        any(target_os = "linux", target_os = "macos")
+    ))]
+    if REDUCE_MOTION.get().is_none() {
+        let spawned = std::thread::Builder::new()
+            .name("strato-motion".into())
+            .spawn(|| record_system_animation_settings(system_reduce_motion()));
+        if let Err(e) = spawned {
+            tracing::warn!("Could not read the system motion preference: {}", e);
+        }
+        return;
+    }
+
+    record_system_animation_settings(system_reduce_motion());
+}
+
+fn record_system_animation_settings(reduce_motion: Option<bool>) {
+    let reduce_motion = reduce_motion.unwrap_or(false);
+    if reduce_motion {
+        tracing::info!("System prefers reduced motion, animations will snap to their end");
+    }
+    set_system_animation_settings(AnimationSettings {
+        reduce_motion,
+        ..AnimationSettings::default()
+    });
+}
+
+/// Trimmed stdout of a successful run of `program`
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(target_os = "linux", target_os = "macos")
+))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new(program)
+        .args(args)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
@@ -0,0 +1,391 @@
+//! Application plugins
+//!
+//! Plugins add global behavior — logging, hot reload, analytics — without
+//! touching application code. They implement the core
+//! [`Plugin`] trait; register them with
+//! [`ApplicationBuilder::with_plugin`](crate::ApplicationBuilder::with_plugin)
+//! and the event loop calls their hooks at fixed points:
+//!
+//! 1. [`Plugin::initialize`], then [`Plugin::activate`], once before the
+//!    event loop starts.
+//! 2. For every frame, [`Plugin::before_frame`] before the frame callbacks,
+//!    the widget update pass, layout and rendering (see [`crate::frame`]),
+//!    then [`Plugin::after_frame`] once the frame has been submitted. Both
+//!    receive the [`Application`], to downcast from `&mut dyn Any`.
+//! 3. [`Plugin::handle_event`] for every event, before global shortcuts and
+//!    the widget tree see it.
+//!
+//! 4. When the application shuts down, or its plugins are dropped,
+//!    [`Plugin::deactivate`] then [`Plugin::cleanup`] for every plugin that
+//!    was initialized.
+//!
+//! Plugins that need the application itself, rather than `&mut dyn Any`,
+//! implement [`ApplicationPlugin`] and are registered with
+//! [`ApplicationBuilder::with_app_plugin`](crate::ApplicationBuilder::with_app_plugin).
+//!
+//! All plugins of an application share one [`PluginContext`].
+//!
+//! # Ordering
+//!
+//! Plugins run in registration order for initialization, `before_frame` and
+//! `handle_event`, and in reverse registration order for `after_frame` and
+//! shutdown, so the first plugin registered wraps all others. A plugin
+//! returning [`EventResult::Handled`] from `handle_event` stops the event:
+//! later plugins, shortcuts and widgets never receive it.
+
+use crate::Application;
+use strato_core::event::{Event, EventResult};
+pub use strato_core::plugin::{Plugin, PluginContext, PluginMetadata};
+
+/// Plugin whose frame hooks receive the [`Application`]
+///
+/// Its hooks run in place of [`Plugin::before_frame`] and
+/// [`Plugin::after_frame`], which are not called for it; the other
+/// [`Plugin`] hooks run as for any plugin.
+pub trait ApplicationPlugin: Plugin {
+    /// Called at the start of every frame, before the frame callbacks
+    fn before_app_frame(&mut self, _app: &mut Application) {}
+
+    /// Called after every frame has been submitted
+    fn after_app_frame(&mut self, _app: &mut Application) {}
+}
+
+/// A registered plugin, with the frame hooks it takes
+pub(crate) enum Registered {
+    Core(Box<dyn Plugin>),
+    App(Box<dyn ApplicationPlugin>),
+}
+
+impl Registered {
+    fn plugin(&mut self) -> &mut dyn Plugin {
+        match self {
+            Registered::Core(plugin) => plugin.as_mut(),
+            Registered::App(plugin) => plugin.as_mut(),
+        }
+    }
+}
+
+/// Ordered list of registered plugins and the context they share
+#[derive(Default)]
+pub(crate) struct PluginStack {
+    plugins: Vec<Registered>,
+    context: PluginContext,
+    /// How many plugins, from the first, were initialized
+    initialized: usize,
+}
+
+impl PluginStack {
+    pub(crate) fn push(&mut self, plugin: Registered) {
+        self.plugins.push(plugin);
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    /// Take the plugins out, leaving an empty stack sharing their context,
+    /// so hooks can borrow the application while plugins are added to it
+    pub(crate) fn detach(&mut self) -> PluginStack {
+        let empty = PluginStack {
+            plugins: Vec::new(),
+            context: self.context.clone(),
+            initialized: 0,
+        };
+        std::mem::replace(self, empty)
+    }
+
+    /// Append plugins registered while this stack was detached
+    pub(crate) fn extend(&mut self, mut other: PluginStack) {
+        if self.initialized == self.plugins.len() {
+            self.initialized += other.initialized;
+        }
+        // `other` is dropped empty, so its plugins aren't shut down
+        self.plugins.append(&mut other.plugins);
+        other.initialized = 0;
+    }
+
+    /// Initialize and activate the plugins registered since the last call
+    pub(crate) fn initialize(&mut self) {
+        for plugin in &mut self.plugins[self.initialized..] {
+            let plugin = plugin.plugin();
+            let name = plugin.metadata().name.clone();
+            tracing::debug!("Initializing plugin {}", name);
+            let mut started = plugin.initialize(&mut self.context);
+            if started.is_ok() {
+                started = plugin.activate(&mut self.context);
+            }
+            if let Err(e) = started {
+                tracing::error!("Failed to start plugin {}: {}", name, e);
+            }
+        }
+        self.initialized = self.plugins.len();
+    }
+
+    /// Deactivate and clean up the initialized plugins, last first
+    ///
+    /// Plugins stay registered; a later [`initialize`](Self::initialize)
+    /// starts them again.
+    pub(crate) fn shutdown(&mut self) {
+        let initialized = self.initialized.min(self.plugins.len());
+        for plugin in self.plugins[..initialized].iter_mut().rev() {
+            let plugin = plugin.plugin();
+            let name = plugin.metadata().name.clone();
+            tracing::debug!("Shutting down plugin {}", name);
+            let mut stopped = plugin.deactivate(&mut self.context);
+            if stopped.is_ok() {
+                stopped = plugin.cleanup(&mut self.context);
+            }
+            if let Err(e) = stopped {
+                tracing::error!("Failed to shut down plugin {}: {}", name, e);
+            }
+        }
+        self.initialized = 0;
+    }
+
+    pub(crate) fn before_frame(&mut self, app: &mut Application) {
+        for plugin in &mut self.plugins {
+            match plugin {
+                Registered::Core(plugin) => plugin.before_frame(app),
+                Registered::App(plugin) => plugin.before_app_frame(app),
+            }
+        }
+    }
+
+    pub(crate) fn after_frame(&mut self, app: &mut Application) {
+        for plugin in self.plugins.iter_mut().rev() {
+            match plugin {
+                Registered::Core(plugin) => plugin.after_frame(app),
+                Registered::App(plugin) => plugin.after_app_frame(app),
+            }
+        }
+    }
+
+    pub(crate) fn on_event(&mut self, event: &Event) -> EventResult {
+        for plugin in &mut self.plugins {
+            let plugin = plugin.plugin();
+            if plugin.handle_event(event, &mut self.context) == EventResult::Handled {
+                return EventResult::Handled;
+            }
+        }
+        EventResult::Ignored
+    }
+}
+
+impl Drop for PluginStack {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::any::Any;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder {
+        metadata: PluginMetadata,
+        log: Arc<Mutex<Vec<String>>>,
+        consume: bool,
+    }
+
+    impl Recorder {
+        fn new(name: &str, log: &Arc<Mutex<Vec<String>>>, consume: bool) -> Self {
+            Self {
+                metadata: strato_core::plugin_metadata! {
+                    name: name,
+                    version: "0.1.0",
+                    description: "Records the hooks it sees",
+                    author: "tests",
+                },
+                log: log.clone(),
+                consume,
+            }
+        }
+
+        fn record(&self, hook: &str) {
+            let entry = format!("{} {}", self.metadata.name, hook);
+            self.log.lock().unwrap().push(entry);
+        }
+    }
+
+    impl Plugin for Recorder {
+        fn metadata(&self) -> &PluginMetadata {
+            &self.metadata
+        }
+
+        fn activate(&mut self, _context: &mut PluginContext) -> strato_core::Result<()> {
+            self.record("activate");
+            Ok(())
+        }
+
+        fn deactivate(&mut self, _context: &mut PluginContext) -> strato_core::Result<()> {
+            self.record("deactivate");
+            Ok(())
+        }
+
+        fn cleanup(&mut self, _context: &mut PluginContext) -> strato_core::Result<()> {
+            self.record("cleanup");
+            Ok(())
+        }
+
+        fn handle_event(&mut self, _event: &Event, _context: &mut PluginContext) -> EventResult {
+            self.record("event");
+            if self.consume {
+                EventResult::Handled
+            } else {
+                EventResult::Ignored
+            }
+        }
+
+        fn before_frame(&mut self, app: &mut dyn Any) {
+            assert!(app.is::<Application>());
+            self.record("before");
+        }
+
+        fn after_frame(&mut self, _app: &mut dyn Any) {
+            self.record("after");
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    /// Records the frames it sees through the application
+    struct Frames {
+        inner: Recorder,
+    }
+
+    impl Plugin for Frames {
+        fn metadata(&self) -> &PluginMetadata {
+            self.inner.metadata()
+        }
+
+        fn before_frame(&mut self, _app: &mut dyn Any) {
+            self.inner.record("untyped before");
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+    }
+
+    impl ApplicationPlugin for Frames {
+        fn before_app_frame(&mut self, app: &mut Application) {
+            assert_eq!(app.clear_color(), None);
+            self.inner.record("before");
+        }
+
+        fn after_app_frame(&mut self, app: &mut Application) {
+            app.set_clear_color(strato_core::types::Color::BLACK);
+            self.inner.record("after");
+        }
+    }
+
+    #[test]
+    fn test_events_stop_at_first_handling_plugin() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut stack = PluginStack::default();
+        for (name, consume) in [("logger", false), ("hotkeys", true), ("analytics", false)] {
+            stack.push(Registered::Core(Box::new(Recorder::new(
+                name, &log, consume,
+            ))));
+        }
+
+        let event = Event::Window(strato_core::event::WindowEvent::Focus(true));
+        assert_eq!(stack.on_event(&event), EventResult::Handled);
+        assert_eq!(*log.lock().unwrap(), ["logger event", "hotkeys event"]);
+    }
+
+    #[test]
+    fn test_core_plugins_run_around_application_frames() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut app = crate::ApplicationBuilder::new()
+            .with_plugin(Box::new(Recorder::new("outer", &log, false)))
+            .with_plugin(Box::new(Recorder::new("inner", &log, false)))
+            .build();
+
+        app.init_plugins();
+        app.begin_frame();
+        app.end_frame();
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer activate",
+                "inner activate",
+                "outer before",
+                "inner before",
+                "inner after",
+                "outer after"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plugins_shut_down_in_reverse_order_once() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut app = crate::ApplicationBuilder::new()
+            .with_plugin(Box::new(Recorder::new("outer", &log, false)))
+            .with_plugin(Box::new(Recorder::new("inner", &log, false)))
+            .build();
+
+        app.init_plugins();
+        app.shutdown_plugins();
+        drop(app);
+        assert_eq!(
+            *log.lock().unwrap(),
+            [
+                "outer activate",
+                "inner activate",
+                "inner deactivate",
+                "inner cleanup",
+                "outer deactivate",
+                "outer cleanup"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dropping_stack_shuts_down_initialized_plugins() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut stack = PluginStack::default();
+        stack.push(Registered::Core(Box::new(Recorder::new(
+            "started", &log, false,
+        ))));
+        stack.initialize();
+        stack.push(Registered::Core(Box::new(Recorder::new(
+            "pending", &log, false,
+        ))));
+
+        drop(stack);
+        assert_eq!(
+            *log.lock().unwrap(),
+            ["started activate", "started deactivate", "started cleanup"]
+        );
+    }
+
+    #[test]
+    fn test_app_plugins_receive_the_application() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut app = crate::ApplicationBuilder::new()
+            .with_app_plugin(Box::new(Frames {
+                inner: Recorder::new("frames", &log, false),
+            }))
+            .build();
+
+        app.init_plugins();
+        app.begin_frame();
+        app.end_frame();
+        assert_eq!(app.clear_color(), Some(strato_core::types::Color::BLACK));
+        assert_eq!(*log.lock().unwrap(), ["frames before", "frames after"]);
+    }
+}
@@ -0,0 +1,88 @@
+//! Scroll wheel preferences
+//!
+//! How wheel and trackpad input turns into the
+//! [`Event::MouseWheel`](strato_core::event::Event::MouseWheel) deltas
+//! widgets see. Set from [`ApplicationBuilder::natural_scroll`](crate::ApplicationBuilder::natural_scroll)
+//! and [`ApplicationBuilder::scroll_line_height`](crate::ApplicationBuilder::scroll_line_height),
+//! or at any time with [`set_scroll_settings`]. A single scroll view can
+//! scroll faster or slower with `ScrollView::sensitivity`.
+
+use glam::Vec2;
+use std::sync::{OnceLock, RwLock};
+
+/// Global scroll preferences, applied as window events are converted
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScrollSettings {
+    /// Invert both axes, so content follows the fingers like on a touch
+    /// screen
+    pub natural: bool,
+    /// Logical pixels scrolled per wheel notch, for mice that report lines
+    pub line_height: f32,
+}
+
+impl Default for ScrollSettings {
+    fn default() -> Self {
+        Self {
+            natural: false,
+            line_height: 20.0,
+        }
+    }
+}
+
+impl ScrollSettings {
+    /// Delta in logical pixels for a wheel that reported `lines`
+    pub fn lines(&self, lines: Vec2) -> Vec2 {
+        self.apply(lines * self.line_height)
+    }
+
+    /// Delta in logical pixels for a trackpad that reported `pixels`
+    pub fn pixels(&self, pixels: Vec2) -> Vec2 {
+        self.apply(pixels)
+    }
+
+    fn apply(&self, delta: Vec2) -> Vec2 {
+        if self.natural {
+            -delta
+        } else {
+            delta
+        }
+    }
+}
+
+static SCROLL_SETTINGS: OnceLock<RwLock<ScrollSettings>> = OnceLock::new();
+
+fn scroll_settings_slot() -> &'static RwLock<ScrollSettings> {
+    SCROLL_SETTINGS.get_or_init(|| RwLock::new(ScrollSettings::default()))
+}
+
+/// The scroll settings in effect
+pub fn scroll_settings() -> ScrollSettings {
+    *scroll_settings_slot().read().unwrap()
+}
+
+/// Replace the scroll settings, from the next wheel event on
+pub fn set_scroll_settings(settings: ScrollSettings) {
+    *scroll_settings_slot().write().unwrap() = settings;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_natural_scroll_inverts_lines_and_pixels() {
+        let settings = ScrollSettings {
+            line_height: 32.0,
+            ..ScrollSettings::default()
+        };
+        assert_eq!(settings.lines(Vec2::new(0.0, 1.0)), Vec2::new(0.0, 32.0));
+        assert_eq!(settings.pixels(Vec2::new(3.0, -4.0)), Vec2::new(3.0, -4.0));
+
+        let natural = ScrollSettings {
+            natural: true,
+            ..settings
+        };
+        assert_eq!(natural.lines(Vec2::new(0.0, 1.0)), Vec2::new(0.0, -32.0));
+        assert_eq!(natural.pixels(Vec2::new(3.0, -4.0)), Vec2::new(-3.0, 4.0));
+    }
+}
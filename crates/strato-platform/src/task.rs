@@ -0,0 +1,269 @@
+//! Background tasks tied to the event loop
+//!
+//! [`spawn`] runs a future off the UI thread, on a shared thread pool on
+//! desktop and on the browser's microtask queue on the web, and
+//! [`spawn_then`] additionally hands its output to a callback on the UI
+//! thread. Completion wakes the event loop, so a [`Signal`] set by the
+//! callback is drawn in the next frame:
+//!
+//! ```no_run
+//! use strato_core::state::Signal;
+//! use strato_platform::task;
+//!
+//! # async fn fetch_greeting() -> String { String::new() }
+//! let greeting = Signal::new(String::from("Loading..."));
+//! let shown = greeting.clone();
+//! let handle = task::spawn_then(fetch_greeting(), move |text| shown.set(text));
+//! ```
+//!
+//! # Cancellation
+//!
+//! Every task is owned by the [`TaskHandle`] returned for it. Dropping the
+//! handle cancels the task: the future is dropped at its next await point
+//! and its completion callback never runs, even if the future had already
+//! finished. A widget that starts work keeps the handle in a field, so the
+//! work is cancelled when the widget is dropped and a late result never
+//! lands in state nobody shows. Call [`TaskHandle::detach`] for
+//! fire-and-forget work that should outlive its starter.
+//!
+//! # Threads
+//!
+//! On desktop the future must be `Send`. It runs on the Tokio runtime the
+//! calling thread is in, or on a runtime shared by the application, and its
+//! callback is sent like an
+//! [`EventLoopProxy::send_action`](crate::EventLoopProxy::send_action) to
+//! the event loop created on the spawning thread, or to the most recently
+//! created one when spawned elsewhere. A callback finishing with no event
+//! loop to run it is dropped. On the web everything runs on the UI thread and callbacks run as soon as
+//! the future finishes.
+
+use futures::future::{AbortHandle, Abortable};
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::event_loop::ActionQueue;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::OnceLock;
+
+/// `Send` on desktop, where tasks run on a thread pool, and no bound on the
+/// web, where they stay on the UI thread
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send> MaybeSend for T {}
+
+/// `Send` on desktop, where tasks run on a thread pool, and no bound on the
+/// web, where they stay on the UI thread
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T> MaybeSend for T {}
+
+#[derive(Debug, Default)]
+struct TaskState {
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+}
+
+/// Owner of a spawned task, cancelling it when dropped
+#[derive(Debug)]
+#[must_use = "dropping a TaskHandle cancels its task, call `detach` to keep it running"]
+pub struct TaskHandle {
+    abort: AbortHandle,
+    state: Arc<TaskState>,
+    detached: bool,
+}
+
+impl TaskHandle {
+    /// Cancel the task, dropping its future and skipping its completion
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+        self.abort.abort();
+    }
+
+    /// Whether the task was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Whether the task ran to completion, including its callback
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+
+    /// Let the task run to completion without an owner
+    pub fn detach(mut self) {
+        self.detached = true;
+    }
+}
+
+impl Drop for TaskHandle {
+    fn drop(&mut self) {
+        if !self.detached {
+            self.cancel();
+        }
+    }
+}
+
+/// Run `future` in the background
+///
+/// Completion wakes the event loop, so signals set by the future are drawn
+/// without waiting for other input. The task is cancelled when the returned
+/// handle is dropped.
+pub fn spawn<F>(future: F) -> TaskHandle
+where
+    F: Future<Output = ()> + MaybeSend + 'static,
+{
+    spawn_then(future, |()| {})
+}
+
+/// Run `future` in the background and pass its output to `on_complete` on
+/// the UI thread
+///
+/// The callback runs before the next frame and is skipped if the returned
+/// handle has been dropped or cancelled by then.
+pub fn spawn_then<F, C>(future: F, on_complete: C) -> TaskHandle
+where
+    F: Future + MaybeSend + 'static,
+    F::Output: MaybeSend,
+    C: FnOnce(F::Output) + MaybeSend + 'static,
+{
+    let (abort, registration) = AbortHandle::new_pair();
+    let state = Arc::new(TaskState::default());
+    #[cfg(not(target_arch = "wasm32"))]
+    let actions = crate::event_loop::ui_actions();
+
+    let task = {
+        let state = state.clone();
+        let future = Abortable::new(future, registration);
+        async move {
+            if let Ok(output) = future.await {
+                let finish = move || {
+                    if !state.cancelled.load(Ordering::Acquire) {
+                        on_complete(output);
+                        state.finished.store(true, Ordering::Release);
+                    }
+                };
+
+                #[cfg(not(target_arch = "wasm32"))]
+                complete(actions, Box::new(finish));
+
+                // Futures already run on the UI thread on the web
+                #[cfg(target_arch = "wasm32")]
+                finish();
+            }
+        }
+    };
+
+    #[cfg(not(target_arch = "wasm32"))]
+    runtime().spawn(task);
+
+    #[cfg(target_arch = "wasm32")]
+    wasm_bindgen_futures::spawn_local(task);
+
+    TaskHandle {
+        abort,
+        state,
+        detached: false,
+    }
+}
+
+/// Handle to the runtime desktop tasks are spawned on
+#[cfg(not(target_arch = "wasm32"))]
+fn runtime() -> tokio::runtime::Handle {
+    static RUNTIME: OnceLock<tokio::runtime::Runtime> = OnceLock::new();
+
+    tokio::runtime::Handle::try_current().unwrap_or_else(|_| {
+        RUNTIME
+            .get_or_init(|| {
+                tokio::runtime::Builder::new_multi_thread()
+                    .enable_all()
+                    .thread_name("strato-task")
+                    .build()
+                    .expect("Failed to start the task runtime")
+            })
+            .handle()
+            .clone()
+    })
+}
+
+/// Send a finished task's callback to the event loop it belongs to
+#[cfg(not(target_arch = "wasm32"))]
+fn complete(actions: Option<ActionQueue>, finish: crate::event_loop::Action) {
+    match actions.or_else(crate::event_loop::ui_actions) {
+        Some(actions) => {
+            if actions.post(finish).is_err() {
+                tracing::debug!("Task finished after its event loop exited");
+            }
+        }
+        None => tracing::warn!("Task finished with no event loop to run its callback"),
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use crate::event_loop::set_ui_actions;
+    use std::sync::Mutex;
+    use std::time::{Duration, Instant};
+    use strato_core::state::Signal;
+
+    /// Stand in for an event loop on the calling thread, so completions of
+    /// tasks spawned here queue up for this test alone
+    fn ui_actions() -> ActionQueue {
+        let actions = ActionQueue::default();
+        set_ui_actions(actions.clone());
+        actions
+    }
+
+    /// Run completions until `done` holds, failing after a few seconds
+    fn run_until(actions: &ActionQueue, done: impl Fn() -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while !done() {
+            assert!(Instant::now() < deadline, "task did not complete");
+            actions.run();
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_completion_runs_where_actions_are_run() {
+        let actions = ui_actions();
+        let ui_thread = std::thread::current().id();
+        let result = Signal::new(0);
+        let completed_on = Arc::new(Mutex::new(None));
+
+        let shown = result.clone();
+        let recorded = completed_on.clone();
+        let handle = spawn_then(async { 6 * 7 }, move |value| {
+            shown.set(value);
+            *recorded.lock().unwrap() = Some(std::thread::current().id());
+        });
+
+        run_until(&actions, || handle.is_finished());
+        assert_eq!(result.get(), 42);
+        assert_eq!(*completed_on.lock().unwrap(), Some(ui_thread));
+    }
+
+    #[test]
+    fn test_dropping_handle_cancels_task() {
+        let actions = ui_actions();
+        let result = Signal::new(0);
+        let (sender, receiver) = tokio::sync::oneshot::channel::<i32>();
+
+        let shown = result.clone();
+        let handle = spawn_then(async move { receiver.await.unwrap_or(-1) }, move |value| {
+            shown.set(value)
+        });
+        drop(handle);
+        let _ = sender.send(7);
+
+        // A task finishing in the meantime would be picked up here
+        let keep_alive = spawn(async { tokio::time::sleep(Duration::from_millis(20)).await });
+        run_until(&actions, || keep_alive.is_finished());
+        actions.run();
+        assert_eq!(result.get(), 0);
+    }
+}
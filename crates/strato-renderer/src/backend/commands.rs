@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use strato_core::types::{Color, Rect, Transform};
 
 /// High-level rendering commands for the UI engine.
 /// These are backend-agnostic and declarative.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum RenderCommand {
     /// Draw a filled rectangle
     DrawRect {
@@ -3,8 +3,12 @@ use anyhow::Result;
 use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
 
 pub mod commands;
+pub mod noop;
+pub mod recorder;
 pub mod wgpu;
 
+pub use noop::{NoopBackend, NoopRecord};
+pub use recorder::{replay, CommandLog, CommandRecorder, SharedCommandLog};
 pub use wgpu::WgpuBackend;
 
 /// Trait that all rendering backends must implement.
@@ -28,6 +32,43 @@ pub trait Backend: Send + Sync {
     /// Submit a list of render commands to be executed
     fn submit(&mut self, commands: &[RenderCommand]) -> Result<()>;
 
+    /// Take the device and surface recoveries performed since the last call
+    fn take_recovery_events(&mut self) -> Vec<crate::gpu::RecoveryEvent> {
+        Vec::new()
+    }
+
+    /// Clear frames to `color`, an sRGB color, from the next frame on
+    fn set_clear_color(&mut self, _color: strato_core::types::Color) {}
+
+    /// Fit glyphs drawn from the next frame on to the pixel grid as
+    /// `hinting` says
+    fn set_text_hinting(&mut self, _hinting: crate::gpu::TextHinting) {}
+
+    /// Keep each frame in an offscreen texture and present it again when the
+    /// surface is resized, so a resize never shows the surface cleared
+    ///
+    /// Costs a surface-sized texture; see [`crate::gpu::resize_buffer`].
+    fn set_resize_buffering(&mut self, _enabled: bool) {}
+
+    /// Report per-frame counters, like the shaped text cache's, to
+    /// `profiler` as each frame ends
+    fn set_profiler(&mut self, _profiler: std::sync::Arc<crate::profiler::Profiler>) {}
+
+    /// The adapter and device frames are rendered with, once initialized
+    fn gpu_info(&self) -> Option<crate::gpu::GpuInfo> {
+        None
+    }
+
+    /// Register a pass run on the rendered UI before it is presented
+    fn add_post_process(
+        &mut self,
+        _node: Box<dyn crate::post_process::PostProcessNode>,
+    ) -> Result<()> {
+        Err(anyhow::anyhow!(
+            "post-processing not supported by this backend"
+        ))
+    }
+
     /// Submit a render batch for execution (optimized path)
     fn submit_batch(&mut self, _batch: &crate::batch::RenderBatch) -> Result<()> {
         // Default implementation falls back to submit if possible, or errors?
@@ -0,0 +1,198 @@
+//! Backend that records frames instead of drawing them
+//!
+//! [`NoopBackend`] needs no GPU or window, so application and widget logic
+//! can run its whole event, layout and render loop in tests and on CI
+//! machines without a graphics driver.
+
+use crate::backend::{commands::RenderCommand, Backend};
+use crate::batch::{DrawCommand, RenderBatch};
+use crate::gpu::TextHinting;
+use anyhow::{anyhow, Result};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use strato_core::types::Color;
+
+/// What a [`NoopBackend`] was asked to do
+#[derive(Debug, Clone, Default)]
+pub struct NoopRecord {
+    /// Frames begun and ended
+    pub frames: u64,
+    /// Surface size in physical pixels from the last resize
+    pub size: (u32, u32),
+    /// Scale factor from the last change
+    pub scale_factor: f64,
+    /// Color from the last [`Backend::set_clear_color`]
+    pub clear_color: Option<Color>,
+    /// Hinting from the last [`Backend::set_text_hinting`]
+    pub text_hinting: TextHinting,
+    /// Whether [`Backend::set_resize_buffering`] last enabled it
+    pub resize_buffering: bool,
+    /// Draw commands of the last frame, overlays last
+    pub commands: Vec<DrawCommand>,
+    /// Draw commands submitted over all frames
+    pub total_commands: u64,
+    /// Low-level render commands submitted over all frames
+    pub total_render_commands: u64,
+}
+
+#[derive(Debug, Default)]
+struct NoopState {
+    record: NoopRecord,
+    in_frame: bool,
+    frame_commands: Vec<DrawCommand>,
+}
+
+/// Backend that counts and keeps submitted commands without rendering
+///
+/// Clones share what they record, so a test can keep one and hand the other
+/// to the application:
+///
+/// ```
+/// use strato_renderer::backend::{Backend, NoopBackend};
+/// use strato_renderer::RenderBatch;
+///
+/// let backend = NoopBackend::new();
+/// let mut boxed: Box<dyn Backend> = Box::new(backend.clone());
+/// boxed.begin_frame().unwrap();
+/// boxed.submit_batch(&RenderBatch::new()).unwrap();
+/// boxed.end_frame().unwrap();
+/// assert_eq!(backend.record().frames, 1);
+/// ```
+///
+/// Frames must be begun before commands are submitted and ended before the
+/// next one begins, like with the GPU backend, so mistakes in a frame loop
+/// show up in tests too.
+#[derive(Debug, Clone, Default)]
+pub struct NoopBackend {
+    state: Arc<Mutex<NoopState>>,
+}
+
+impl NoopBackend {
+    /// Create a backend with nothing recorded
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Everything recorded so far
+    pub fn record(&self) -> NoopRecord {
+        self.state.lock().record.clone()
+    }
+
+    /// Number of frames begun and ended
+    pub fn frames(&self) -> u64 {
+        self.state.lock().record.frames
+    }
+
+    /// Draw commands of the last ended frame
+    pub fn last_frame(&self) -> Vec<DrawCommand> {
+        self.state.lock().record.commands.clone()
+    }
+}
+
+impl Backend for NoopBackend {
+    fn resize(&mut self, width: u32, height: u32) {
+        self.state.lock().record.size = (width, height);
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.state.lock().record.scale_factor = scale_factor;
+    }
+
+    fn begin_frame(&mut self) -> Result<()> {
+        let mut state = self.state.lock();
+        if state.in_frame {
+            return Err(anyhow!("begin_frame called twice without end_frame"));
+        }
+        state.in_frame = true;
+        state.frame_commands.clear();
+        Ok(())
+    }
+
+    fn end_frame(&mut self) -> Result<()> {
+        let mut state = self.state.lock();
+        if !state.in_frame {
+            return Err(anyhow!("end_frame called without begin_frame"));
+        }
+        state.in_frame = false;
+        state.record.frames += 1;
+        state.record.commands = std::mem::take(&mut state.frame_commands);
+        Ok(())
+    }
+
+    fn submit(&mut self, commands: &[RenderCommand]) -> Result<()> {
+        let mut state = self.state.lock();
+        if !state.in_frame {
+            return Err(anyhow!("submit called outside a frame"));
+        }
+        state.record.total_render_commands += commands.len() as u64;
+        Ok(())
+    }
+
+    fn set_clear_color(&mut self, color: Color) {
+        self.state.lock().record.clear_color = Some(color);
+    }
+
+    fn set_text_hinting(&mut self, hinting: TextHinting) {
+        self.state.lock().record.text_hinting = hinting;
+    }
+
+    fn set_resize_buffering(&mut self, enabled: bool) {
+        self.state.lock().record.resize_buffering = enabled;
+    }
+
+    fn submit_batch(&mut self, batch: &RenderBatch) -> Result<()> {
+        let mut state = self.state.lock();
+        if !state.in_frame {
+            return Err(anyhow!("submit_batch called outside a frame"));
+        }
+        state.record.total_commands += batch.command_count() as u64;
+        state.frame_commands.extend(
+            batch
+                .commands
+                .iter()
+                .chain(&batch.overlay_commands)
+                .cloned(),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strato_core::types::{Rect, Transform};
+
+    #[test]
+    fn test_records_the_last_frame() {
+        let backend = NoopBackend::new();
+        let mut boxed: Box<dyn Backend> = Box::new(backend.clone());
+        boxed.resize(800, 600);
+
+        let mut batch = RenderBatch::new();
+        batch.add_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Color::RED,
+            Transform::identity(),
+        );
+        for _ in 0..2 {
+            boxed.begin_frame().unwrap();
+            boxed.submit_batch(&batch).unwrap();
+            boxed.end_frame().unwrap();
+        }
+
+        let record = backend.record();
+        assert_eq!(record.frames, 2);
+        assert_eq!(record.size, (800, 600));
+        assert_eq!(record.commands.len(), 1);
+        assert_eq!(record.total_commands, 2);
+    }
+
+    #[test]
+    fn test_rejects_submissions_outside_a_frame() {
+        let mut backend = NoopBackend::new();
+        assert!(backend.submit_batch(&RenderBatch::new()).is_err());
+        assert!(backend.end_frame().is_err());
+        backend.begin_frame().unwrap();
+        assert!(backend.begin_frame().is_err());
+    }
+}
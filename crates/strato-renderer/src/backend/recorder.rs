@@ -0,0 +1,475 @@
+//! Recording frames to a file and replaying them
+//!
+//! A [`CommandRecorder`] wraps another backend, forwards everything to it and
+//! keeps a copy of every batch and command list it submits. The recording
+//! is a [`CommandLog`] that can be saved as JSON and later re-submitted to any
+//! backend with [`replay`], so a bug report can carry the exact frames that
+//! showed the problem, and the command streams of two versions can be diffed:
+//!
+//! ```
+//! use strato_renderer::backend::{Backend, CommandRecorder, NoopBackend};
+//! use strato_renderer::RenderBatch;
+//!
+//! let recorder = CommandRecorder::new(NoopBackend::new());
+//! let log = recorder.log();
+//! let mut backend: Box<dyn Backend> = Box::new(recorder);
+//! backend.begin_frame().unwrap();
+//! backend.submit_batch(&RenderBatch::new()).unwrap();
+//! backend.end_frame().unwrap();
+//!
+//! let replayed = NoopBackend::new();
+//! log.snapshot().replay(&mut replayed.clone()).unwrap();
+//! assert_eq!(replayed.frames(), 1);
+//! ```
+//!
+//! Text and images are recorded as the draw commands that request them, so
+//! a replay shapes text with the fonts of the machine it runs on.
+
+use crate::backend::{commands::RenderCommand, Backend};
+use crate::batch::{DrawCommand, RenderBatch};
+use crate::vertex::Vertex;
+use anyhow::{Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use strato_core::types::{Color, Rect};
+
+/// Version of the [`CommandLog`] format written by this build
+///
+/// Bumped whenever the recorded types change in a way older logs can't be
+/// read with.
+pub const LOG_FORMAT_VERSION: u32 = 1;
+
+/// A [`RenderBatch`] as it was submitted
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedBatch {
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u16>,
+    pub commands: Vec<DrawCommand>,
+    /// Overlay commands, in the order they are drawn
+    pub overlay_commands: Vec<DrawCommand>,
+    pub viewport: Option<Rect>,
+}
+
+impl RecordedBatch {
+    /// Copy what `batch` draws
+    pub fn from_batch(batch: &RenderBatch) -> Self {
+        Self {
+            vertices: batch.vertices.clone(),
+            indices: batch.indices.clone(),
+            commands: batch.commands.clone(),
+            overlay_commands: batch.overlay_commands.clone(),
+            viewport: batch.viewport(),
+        }
+    }
+
+    /// A batch drawing the same as the recorded one
+    pub fn to_batch(&self) -> RenderBatch {
+        RenderBatch::from_parts(
+            self.vertices.clone(),
+            self.indices.clone(),
+            self.commands.clone(),
+            self.overlay_commands.clone(),
+            self.viewport,
+        )
+    }
+}
+
+/// One call submitting work within a frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Submission {
+    /// A [`Backend::submit_batch`] call
+    Batch(RecordedBatch),
+    /// A [`Backend::submit`] call
+    Commands(Vec<RenderCommand>),
+}
+
+/// Everything submitted between a `begin_frame` and its `end_frame`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    /// Surface size in physical pixels
+    pub size: (u32, u32),
+    pub scale_factor: f64,
+    /// Clear color set before the frame, if any was
+    pub clear_color: Option<Color>,
+    pub submissions: Vec<Submission>,
+}
+
+impl RecordedFrame {
+    /// Draw commands of the frame's batches, overlays of each batch last
+    pub fn draw_commands(&self) -> impl Iterator<Item = &DrawCommand> {
+        self.submissions
+            .iter()
+            .filter_map(|submission| match submission {
+                Submission::Batch(batch) => Some(batch),
+                Submission::Commands(_) => None,
+            })
+            .flat_map(|batch| batch.commands.iter().chain(&batch.overlay_commands))
+    }
+}
+
+/// Frames recorded by a [`CommandRecorder`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommandLog {
+    /// [`LOG_FORMAT_VERSION`] of the build that wrote the log
+    pub version: u32,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl Default for CommandLog {
+    fn default() -> Self {
+        Self {
+            version: LOG_FORMAT_VERSION,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl CommandLog {
+    /// Serialize the log as pretty-printed JSON, one field per line so logs
+    /// diff well
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a log written by [`to_json`](Self::to_json)
+    ///
+    /// Fails for logs written in another format version.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let log: Self = serde_json::from_str(json)?;
+        if log.version != LOG_FORMAT_VERSION {
+            anyhow::bail!(
+                "command log has format version {}, this build reads version {}",
+                log.version,
+                LOG_FORMAT_VERSION
+            );
+        }
+        Ok(log)
+    }
+
+    /// Write the log to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        std::fs::write(path, self.to_json()?)
+            .with_context(|| format!("failed to write command log {}", path.display()))
+    }
+
+    /// Read a log saved with [`save`](Self::save)
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let json = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read command log {}", path.display()))?;
+        Self::from_json(&json).with_context(|| format!("invalid command log {}", path.display()))
+    }
+
+    /// Submit every frame of the log to `backend`
+    ///
+    /// The backend is resized and rescaled whenever a frame was recorded at
+    /// another size or scale than the one before it.
+    pub fn replay(&self, backend: &mut dyn Backend) -> Result<()> {
+        let mut size = None;
+        let mut scale_factor = None;
+        for frame in &self.frames {
+            if size != Some(frame.size) {
+                backend.resize(frame.size.0, frame.size.1);
+                size = Some(frame.size);
+            }
+            if scale_factor != Some(frame.scale_factor) {
+                backend.set_scale_factor(frame.scale_factor);
+                scale_factor = Some(frame.scale_factor);
+            }
+            if let Some(color) = frame.clear_color {
+                backend.set_clear_color(color);
+            }
+
+            backend.begin_frame()?;
+            for submission in &frame.submissions {
+                match submission {
+                    Submission::Batch(batch) => backend.submit_batch(&batch.to_batch())?,
+                    Submission::Commands(commands) => backend.submit(commands)?,
+                }
+            }
+            backend.end_frame()?;
+        }
+        Ok(())
+    }
+}
+
+/// Submit every frame of the log saved at `path` to `backend`
+///
+/// Returns the number of frames replayed.
+pub fn replay(path: impl AsRef<Path>, backend: &mut dyn Backend) -> Result<usize> {
+    let log = CommandLog::load(path)?;
+    log.replay(backend)?;
+    Ok(log.frames.len())
+}
+
+/// Handle to the log a [`CommandRecorder`] writes to
+///
+/// Stays usable after the recorder has been boxed and handed to the
+/// application.
+#[derive(Debug, Clone, Default)]
+pub struct SharedCommandLog {
+    log: Arc<Mutex<CommandLog>>,
+}
+
+impl SharedCommandLog {
+    /// A copy of the frames recorded so far
+    pub fn snapshot(&self) -> CommandLog {
+        self.log.lock().clone()
+    }
+
+    /// Number of frames recorded so far
+    pub fn frames(&self) -> usize {
+        self.log.lock().frames.len()
+    }
+
+    /// Forget the frames recorded so far
+    pub fn clear(&self) {
+        self.log.lock().frames.clear();
+    }
+
+    /// Write the frames recorded so far to `path` as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<()> {
+        self.snapshot().save(path)
+    }
+}
+
+/// Backend that records what it submits to another backend
+///
+/// Every frame is recorded until [`max_frames`](Self::max_frames) are kept,
+/// after which the oldest frame is dropped for each new one, so a recorder
+/// can stay attached to a long-running app and save the frames leading up
+/// to a problem.
+pub struct CommandRecorder<B: Backend> {
+    inner: B,
+    log: SharedCommandLog,
+    max_frames: Option<usize>,
+    size: (u32, u32),
+    scale_factor: f64,
+    clear_color: Option<Color>,
+    frame: Option<RecordedFrame>,
+}
+
+impl<B: Backend> std::fmt::Debug for CommandRecorder<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CommandRecorder")
+            .field("frames", &self.log.frames())
+            .field("max_frames", &self.max_frames)
+            .field("recording_frame", &self.frame.is_some())
+            .finish()
+    }
+}
+
+impl<B: Backend> CommandRecorder<B> {
+    /// Record every frame submitted to `inner`
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            log: SharedCommandLog::default(),
+            max_frames: None,
+            size: (0, 0),
+            scale_factor: 1.0,
+            clear_color: None,
+            frame: None,
+        }
+    }
+
+    /// Keep only the last `max_frames` frames
+    pub fn max_frames(mut self, max_frames: usize) -> Self {
+        self.max_frames = Some(max_frames);
+        self
+    }
+
+    /// Handle to the recorded frames
+    pub fn log(&self) -> SharedCommandLog {
+        self.log.clone()
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Stop recording and return the wrapped backend
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Backend> Backend for CommandRecorder<B> {
+    fn resize(&mut self, width: u32, height: u32) {
+        self.size = (width, height);
+        self.inner.resize(width, height);
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.scale_factor = scale_factor;
+        self.inner.set_scale_factor(scale_factor);
+    }
+
+    fn begin_frame(&mut self) -> Result<()> {
+        self.inner.begin_frame()?;
+        self.frame = Some(RecordedFrame {
+            size: self.size,
+            scale_factor: self.scale_factor,
+            clear_color: self.clear_color.take(),
+            submissions: Vec::new(),
+        });
+        Ok(())
+    }
+
+    fn end_frame(&mut self) -> Result<()> {
+        let result = self.inner.end_frame();
+        if let Some(frame) = self.frame.take() {
+            let mut log = self.log.log.lock();
+            log.frames.push(frame);
+            if let Some(max_frames) = self.max_frames {
+                let excess = log.frames.len().saturating_sub(max_frames);
+                log.frames.drain(..excess);
+            }
+        }
+        result
+    }
+
+    fn submit(&mut self, commands: &[RenderCommand]) -> Result<()> {
+        self.inner.submit(commands)?;
+        if let Some(frame) = &mut self.frame {
+            frame
+                .submissions
+                .push(Submission::Commands(commands.to_vec()));
+        }
+        Ok(())
+    }
+
+    fn take_recovery_events(&mut self) -> Vec<crate::gpu::RecoveryEvent> {
+        self.inner.take_recovery_events()
+    }
+
+    fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = Some(color);
+        self.inner.set_clear_color(color);
+    }
+
+    fn set_text_hinting(&mut self, hinting: crate::gpu::TextHinting) {
+        self.inner.set_text_hinting(hinting);
+    }
+
+    fn set_resize_buffering(&mut self, enabled: bool) {
+        self.inner.set_resize_buffering(enabled);
+    }
+
+    fn gpu_info(&self) -> Option<crate::gpu::GpuInfo> {
+        self.inner.gpu_info()
+    }
+
+    fn add_post_process(
+        &mut self,
+        node: Box<dyn crate::post_process::PostProcessNode>,
+    ) -> Result<()> {
+        self.inner.add_post_process(node)
+    }
+
+    fn submit_batch(&mut self, batch: &RenderBatch) -> Result<()> {
+        self.inner.submit_batch(batch)?;
+        if let Some(frame) = &mut self.frame {
+            frame
+                .submissions
+                .push(Submission::Batch(RecordedBatch::from_batch(batch)));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::NoopBackend;
+    use strato_core::types::Transform;
+
+    fn record_frames(recorder: &mut dyn Backend, frames: usize) {
+        let mut batch = RenderBatch::new();
+        batch.set_viewport(Rect::new(0.0, 0.0, 200.0, 100.0));
+        batch.add_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Color::RED,
+            Transform::identity(),
+        );
+        batch.add_rounded_rect(
+            Rect::new(10.0, 10.0, 50.0, 20.0),
+            Color::BLUE,
+            4.0,
+            Transform::identity(),
+        );
+        batch.push_clip(Rect::new(0.0, 0.0, 100.0, 50.0));
+        batch.add_text("Hello".to_string(), (5.0, 5.0), Color::BLACK, 14.0, 0.0);
+        batch.pop_clip();
+
+        recorder.resize(200, 100);
+        recorder.set_scale_factor(2.0);
+        for _ in 0..frames {
+            recorder.begin_frame().unwrap();
+            recorder.submit_batch(&batch).unwrap();
+            recorder
+                .submit(&[
+                    RenderCommand::PushClip(Rect::new(0.0, 0.0, 1.0, 1.0)),
+                    RenderCommand::PopClip,
+                ])
+                .unwrap();
+            recorder.end_frame().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_saved_log_replays_the_same_commands() {
+        let original = NoopBackend::new();
+        let mut recorder = CommandRecorder::new(original.clone());
+        let log = recorder.log();
+        record_frames(&mut recorder, 2);
+        assert_eq!(log.frames(), 2);
+
+        let path =
+            std::env::temp_dir().join(format!("strato-command-log-{}.json", std::process::id()));
+        log.save(&path).unwrap();
+        let loaded = CommandLog::load(&path).unwrap();
+        assert_eq!(loaded.to_json().unwrap(), log.snapshot().to_json().unwrap());
+
+        let replayed = NoopBackend::new();
+        let frames = replay(&path, &mut replayed.clone()).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(frames, 2);
+        let (original, replayed) = (original.record(), replayed.record());
+        assert_eq!(replayed.frames, original.frames);
+        assert_eq!(replayed.size, (200, 100));
+        assert_eq!(replayed.scale_factor, 2.0);
+        assert_eq!(
+            replayed.total_render_commands,
+            original.total_render_commands
+        );
+        assert_eq!(
+            format!("{:?}", replayed.commands),
+            format!("{:?}", original.commands)
+        );
+    }
+
+    #[test]
+    fn test_keeps_only_the_last_frames() {
+        let mut recorder = CommandRecorder::new(NoopBackend::new()).max_frames(3);
+        let log = recorder.log();
+        record_frames(&mut recorder, 5);
+        assert_eq!(log.frames(), 3);
+        assert_eq!(log.snapshot().frames[0].draw_commands().count(), 5);
+    }
+
+    #[test]
+    fn test_rejects_logs_of_other_versions() {
+        let log = CommandLog {
+            version: LOG_FORMAT_VERSION + 1,
+            ..CommandLog::default()
+        };
+        let json = serde_json::to_string(&log).unwrap();
+        assert!(CommandLog::from_json(&json).is_err());
+    }
+}
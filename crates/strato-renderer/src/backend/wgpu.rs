@@ -1,12 +1,53 @@
 use crate::backend::{commands::RenderCommand, Backend};
+use crate::batch::{DrawCommand, RenderBatch};
+use crate::gpu::layer_cache::{self, LayerGeometry, LayerResources, FLAG_LAYER};
+use crate::gpu::surface::vsync_present_mode;
 use crate::gpu::{
-    BufferManager, DeviceManager, PipelineManager, ShaderManager, SimpleVertex, SurfaceManager,
-    TextureManager,
+    AcquireSurface, BufferManager, CachedLayer, DeviceHealth, DeviceManager, DrawBatch, GpuInfo, LayerCache,
+    OutputColorSpace, PipelineManager, RecoveryAction, RecoveryEvent, RecoveryStats,
+    RecoveryTracker, ResizeBuffer, ShaderManager, ShapedGlyph, ShapedRun, SimpleVertex,
+    SurfaceBackoff, SurfaceManager, TextCacheStats, TextHinting, TextLayoutCache, TextureManager,
+    DEFAULT_CLEAR_COLOR,
 };
+use crate::post_process::{PostProcessChain, PostProcessNode};
+use crate::profiler::Profiler;
 use anyhow::Result;
 use async_trait::async_trait;
-use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
-use wgpu::{Backends, CommandEncoderDescriptor, Surface};
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle};
+use std::sync::Arc;
+use std::time::Instant;
+use strato_core::types::Color;
+use wgpu::{
+    Backends, CommandEncoderDescriptor, Instance, PresentMode, Queue, Surface, SurfaceTargetUnsafe,
+    SurfaceTexture,
+};
+
+/// Raw handles of the window the backend renders to
+///
+/// Kept so the surface can be recreated after the device is lost.
+#[derive(Clone, Copy)]
+struct WindowHandles {
+    display: RawDisplayHandle,
+    window: RawWindowHandle,
+}
+
+// Safety: the handles are only used to create surfaces, and the window is
+// assumed to outlive the backend (see `WgpuBackend::init`).
+unsafe impl Send for WindowHandles {}
+unsafe impl Sync for WindowHandles {}
+
+impl WindowHandles {
+    fn create_surface(&self, instance: &Instance) -> Result<Surface<'static>> {
+        // Safety: the window outlives the backend, and therefore the surface.
+        let surface = unsafe {
+            instance.create_surface_unsafe(SurfaceTargetUnsafe::RawHandle {
+                raw_display_handle: self.display,
+                raw_window_handle: self.window,
+            })?
+        };
+        Ok(surface)
+    }
+}
 
 pub struct WgpuBackend {
     device_mgr: Option<DeviceManager>,
@@ -18,6 +59,34 @@ pub struct WgpuBackend {
 
     // State
     scale_factor: f64,
+    present_mode: PresentMode,
+    transparent: bool,
+    clear_color: Option<Color>,
+    text_hinting: TextHinting,
+    window_handles: Option<WindowHandles>,
+
+    // Device loss
+    health: DeviceHealth,
+    recovery: RecoveryTracker,
+    surface_backoff: SurfaceBackoff,
+    // Stands in for the window surface when acquiring frames
+    #[cfg(test)]
+    fake_surface: Option<Box<dyn AcquireSurface>>,
+
+    // Custom passes run on the rendered UI
+    post_process: PostProcessChain,
+
+    // Last frame, presented again on resize when enabled
+    resize_buffer: Option<ResizeBuffer>,
+
+    // Shaped text runs reused across frames
+    text_cache: TextLayoutCache,
+
+    // Textures of the layers drawn by recent frames
+    layers: LayerCache,
+
+    // Receives the text cache counters at the end of every frame
+    profiler: Option<Arc<Profiler>>,
 
     // Cache for reuse
     vertices: Vec<SimpleVertex>,
@@ -34,6 +103,21 @@ impl WgpuBackend {
             texture_mgr: None,
             pipeline_mgr: None,
             scale_factor: 1.0,
+            present_mode: PresentMode::AutoVsync,
+            transparent: false,
+            clear_color: None,
+            text_hinting: TextHinting::default(),
+            window_handles: None,
+            health: DeviceHealth::new(),
+            recovery: RecoveryTracker::new(),
+            surface_backoff: SurfaceBackoff::new(),
+            #[cfg(test)]
+            fake_surface: None,
+            post_process: PostProcessChain::new(),
+            resize_buffer: None,
+            text_cache: TextLayoutCache::default(),
+            layers: LayerCache::new(),
+            profiler: None,
             vertices: Vec::with_capacity(1024),
             indices: Vec::with_capacity(1536),
         }
@@ -45,20 +129,120 @@ impl WgpuBackend {
     {
         println!("=== INITIALIZING WGPU BACKEND ===");
 
+        // Safety: The surface must live as long as the window.
+        // We assume the window outlives the backend.
+        let handles = WindowHandles {
+            display: window.display_handle()?.as_raw(),
+            window: window.window_handle()?.as_raw(),
+        };
+        self.window_handles = Some(handles);
+
+        // We use a default size, it will be resized later
+        self.create_resources(handles, 800, 600).await
+    }
+
+    /// Render for a transparent window
+    ///
+    /// Frames are cleared to transparent, unless a
+    /// [clear color](Backend::set_clear_color) is set, and composited with
+    /// premultiplied alpha, see [`SurfaceManager::set_transparent`]. Takes effect when the
+    /// backend is initialized.
+    pub fn set_transparent(&mut self, transparent: bool) {
+        self.transparent = transparent;
+    }
+
+    /// The color frames are cleared to
+    ///
+    /// Without [`Backend::set_clear_color`] transparent windows
+    /// clear to fully transparent and others to [`DEFAULT_CLEAR_COLOR`].
+    pub fn clear_color(&self) -> Color {
+        match self.clear_color {
+            Some(color) => color,
+            None if self.transparent => Color::TRANSPARENT,
+            None => DEFAULT_CLEAR_COLOR,
+        }
+    }
+
+    /// Present modes the surface supports on the current adapter
+    pub fn supported_present_modes(&self) -> Vec<PresentMode> {
+        match (&self.surface_mgr, &self.device_mgr) {
+            (Some(surface_mgr), Some(device_mgr)) => {
+                surface_mgr.supported_present_modes(device_mgr.adapter())
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Current present mode
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Switch present mode, falling back to a supported one
+    ///
+    /// Returns the mode actually applied.
+    pub fn set_present_mode(&mut self, mode: PresentMode) -> PresentMode {
+        self.present_mode = match (&mut self.surface_mgr, &self.device_mgr) {
+            (Some(surface_mgr), Some(device_mgr)) => {
+                surface_mgr.set_present_mode(mode, device_mgr.device(), device_mgr.adapter())
+            }
+            _ => mode,
+        };
+        self.present_mode
+    }
+
+    /// Enable or disable vsync
+    pub fn set_vsync(&mut self, vsync: bool) -> PresentMode {
+        self.set_present_mode(vsync_present_mode(vsync))
+    }
+
+    /// Recoveries performed since the backend was initialized
+    pub fn recovery_stats(&self) -> &RecoveryStats {
+        self.recovery.stats()
+    }
+
+    /// Set how many shaped text runs are kept between frames
+    pub fn set_text_cache_capacity(&mut self, capacity: usize) {
+        self.text_cache.set_capacity(capacity);
+    }
+
+    /// Hit, miss and size counters of the shaped text cache
+    pub fn text_cache_stats(&self) -> TextCacheStats {
+        self.text_cache.stats()
+    }
+
+    async fn create_resources(
+        &mut self,
+        handles: WindowHandles,
+        width: u32,
+        height: u32,
+    ) -> Result<()> {
         // 1. Initialize DeviceManager
         let device_mgr = DeviceManager::new(Backends::all()).await?;
+        self.health.watch(device_mgr.device());
         println!("✅ DeviceManager initialized");
 
         // 2. Create Surface
-        // Safety: The surface must live as long as the window.
-        // We assume the window outlives the backend.
-        let surface = device_mgr.instance().create_surface(window)?;
-        let surface: Surface<'static> = unsafe { std::mem::transmute(surface) };
+        let surface = handles.create_surface(device_mgr.instance())?;
 
         // 3. Initialize SurfaceManager
-        // We use a default size, it will be resized later
-        let surface_mgr =
-            SurfaceManager::new(surface, device_mgr.device(), device_mgr.adapter(), 800, 600)?;
+        let mut surface_mgr = SurfaceManager::new(
+            surface,
+            device_mgr.device(),
+            device_mgr.adapter(),
+            width,
+            height,
+        )?;
+        if surface_mgr.present_mode() != self.present_mode {
+            surface_mgr.set_present_mode(
+                self.present_mode,
+                device_mgr.device(),
+                device_mgr.adapter(),
+            );
+        }
+        if self.transparent {
+            surface_mgr.set_transparent(true, device_mgr.device(), device_mgr.adapter());
+        }
         println!("✅ SurfaceManager initialized");
 
         // 4. Initialize ShaderManager
@@ -70,11 +254,14 @@ impl WgpuBackend {
         println!("✅ ShaderManager initialized");
 
         // 5. Initialize BufferManager
-        let mut buffer_mgr = BufferManager::new(device_mgr.device());
+        let buffer_mgr = BufferManager::new(device_mgr.device());
         println!("✅ BufferManager initialized");
 
         // 6. Initialize TextureManager
-        let texture_mgr = TextureManager::new_with_font(device_mgr.device(), device_mgr.queue());
+        // Glyphs and images are uploaded again on demand as frames reference them
+        let mut texture_mgr =
+            TextureManager::new_with_font(device_mgr.device(), device_mgr.queue());
+        texture_mgr.set_hinting(self.text_hinting);
         println!("✅ TextureManager initialized");
 
         // 7. Initialize PipelineManager
@@ -87,13 +274,6 @@ impl WgpuBackend {
         )?;
         println!("✅ PipelineManager initialized");
 
-        // Initialize projection matrix
-        let width = surface_mgr.width();
-        let height = surface_mgr.height();
-        let projection =
-            glam::Mat4::orthographic_rh(0.0, width as f32, height as f32, 0.0, -1.0, 1.0);
-        buffer_mgr.upload_projection(device_mgr.queue(), &projection.to_cols_array_2d());
-
         self.device_mgr = Some(device_mgr);
         self.surface_mgr = Some(surface_mgr);
         self.shader_mgr = Some(shader_mgr);
@@ -101,26 +281,38 @@ impl WgpuBackend {
         self.texture_mgr = Some(texture_mgr);
         self.pipeline_mgr = Some(pipeline_mgr);
 
+        // Initialize projection matrix
+        self.upload_projection();
+
         Ok(())
     }
-}
 
-#[async_trait]
-impl Backend for WgpuBackend {
-    fn resize(&mut self, width: u32, height: u32) {
-        if let (Some(surface_mgr), Some(device_mgr), Some(buffer_mgr)) = (
-            &mut self.surface_mgr,
-            &self.device_mgr,
-            &mut self.buffer_mgr,
-        ) {
-            if let Err(e) = surface_mgr.resize(width, height, device_mgr.device()) {
-                eprintln!("Failed to resize surface: {}", e);
-            }
+    /// Drop every GPU resource, surface before device
+    fn release_resources(&mut self) {
+        self.post_process.reset();
+        if let Some(resize_buffer) = &mut self.resize_buffer {
+            resize_buffer.reset();
+        }
+        // Cached runs point into the atlas that is about to be dropped
+        self.text_cache.clear();
+        self.layers.clear();
+        self.pipeline_mgr = None;
+        self.texture_mgr = None;
+        self.buffer_mgr = None;
+        self.shader_mgr = None;
+        self.surface_mgr = None;
+        self.device_mgr = None;
+    }
 
-            // Update projection matrix using logical coordinates
-            // This ensures that the UI coordinates (which are logical) map correctly to the physical viewport
-            let logical_width = width as f64 / self.scale_factor;
-            let logical_height = height as f64 / self.scale_factor;
+    /// Upload the projection matrix for the current surface size
+    fn upload_projection(&mut self) {
+        if let (Some(surface_mgr), Some(device_mgr), Some(buffer_mgr)) =
+            (&self.surface_mgr, &self.device_mgr, &mut self.buffer_mgr)
+        {
+            // Use logical coordinates so the UI coordinates (which are logical)
+            // map correctly to the physical viewport
+            let logical_width = surface_mgr.width() as f64 / self.scale_factor;
+            let logical_height = surface_mgr.height() as f64 / self.scale_factor;
 
             let projection = glam::Mat4::orthographic_rh(
                 0.0,
@@ -134,43 +326,202 @@ impl Backend for WgpuBackend {
         }
     }
 
-    fn set_scale_factor(&mut self, scale_factor: f64) {
-        self.scale_factor = scale_factor;
+    /// Recreate the device, surface, pipelines and GPU resources
+    fn recover_device(&mut self, reason: String) -> Result<()> {
+        let handles = self
+            .window_handles
+            .ok_or_else(|| anyhow::anyhow!("Backend not initialized"))?;
+        let (width, height) = self
+            .surface_mgr
+            .as_ref()
+            .map(|surface_mgr| (surface_mgr.width(), surface_mgr.height()))
+            .unwrap_or((800, 600));
 
-        // Update projection matrix if initialized
-        if let (Some(surface_mgr), Some(device_mgr), Some(buffer_mgr)) =
-            (&self.surface_mgr, &self.device_mgr, &mut self.buffer_mgr)
-        {
-            let width = surface_mgr.width();
-            let height = surface_mgr.height();
+        tracing::warn!("Recreating GPU device: {}", reason);
+        self.release_resources();
+        futures::executor::block_on(self.create_resources(handles, width, height))?;
+        self.recovery
+            .record(RecoveryEvent::DeviceRecreated { reason });
+        Ok(())
+    }
 
-            let logical_width = width as f64 / self.scale_factor;
-            let logical_height = height as f64 / self.scale_factor;
+    /// Acquire the next frame, recovering from device loss and surface errors
+    ///
+    /// Returns `None` when the frame should be skipped: the surface was
+    /// outdated or lost and has been reconfigured, acquisition timed out, or
+    /// repeated failures are being backed off.
+    fn acquire_frame(&mut self) -> Result<Option<SurfaceTexture>> {
+        if let Some(reason) = self.health.take_lost() {
+            self.recover_device(reason)?;
+        }
 
-            let projection = glam::Mat4::orthographic_rh(
-                0.0,
-                logical_width as f32,
-                logical_height as f32,
-                0.0,
-                -1.0,
-                1.0,
-            );
-            buffer_mgr.upload_projection(device_mgr.queue(), &projection.to_cols_array_2d());
+        let now = Instant::now();
+        if self.surface_backoff.is_waiting(now) {
+            return Ok(None);
+        }
+
+        let (surface, device) = self.acquire_target()?;
+        let error = match surface.acquire() {
+            Ok(frame) => {
+                self.surface_backoff.reset();
+                return Ok(Some(frame));
+            }
+            Err(error) => error,
+        };
+
+        let action = RecoveryAction::for_surface_error(&error);
+        if action == RecoveryAction::ReconfigureSurface {
+            surface.reconfigure(device);
+        }
+
+        let delay = self.surface_backoff.record_failure(now);
+        match action {
+            RecoveryAction::ReconfigureSurface => {
+                self.recovery.record(RecoveryEvent::SurfaceReconfigured {
+                    reason: error.to_string(),
+                });
+            }
+            RecoveryAction::RecreateDevice => self.recover_device(error.to_string())?,
+            RecoveryAction::SkipFrame => {}
+        }
+
+        tracing::debug!(
+            "Skipping frame after surface error ({}), retrying in {:?}",
+            error,
+            delay
+        );
+        Ok(None)
+    }
+
+    /// The surface frames are acquired from and the device it is configured on
+    fn acquire_target(&mut self) -> Result<(&mut dyn AcquireSurface, &wgpu::Device)> {
+        let device = self
+            .device_mgr
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("DeviceManager not initialized"))?
+            .device();
+        #[cfg(test)]
+        if let Some(surface) = self.fake_surface.as_deref_mut() {
+            return Ok((surface, device));
         }
+        let surface = self
+            .surface_mgr
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("SurfaceManager not initialized"))?;
+        Ok((surface, device))
+    }
+
+    /// Present the held frame on the freshly configured surface
+    ///
+    /// Errors are left to the next frame's acquisition, which recovers from
+    /// them.
+    fn present_held_frame(&mut self) {
+        let (Some(resize_buffer), Some(surface_mgr), Some(device_mgr)) =
+            (&self.resize_buffer, &mut self.surface_mgr, &self.device_mgr)
+        else {
+            return;
+        };
+        if !resize_buffer.has_frame() {
+            return;
+        }
+        let Ok(output) = surface_mgr.get_current_texture() else {
+            return;
+        };
+
+        let surface_view = surface_mgr.create_view(&output);
+        let mut encoder = device_mgr
+            .device()
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Resize Encoder"),
+            });
+        resize_buffer.present(device_mgr.device(), &mut encoder, &surface_view);
+        device_mgr.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+    }
+}
+
+#[async_trait]
+impl Backend for WgpuBackend {
+    fn resize(&mut self, width: u32, height: u32) {
+        if let (Some(surface_mgr), Some(device_mgr)) = (&mut self.surface_mgr, &self.device_mgr) {
+            if let Err(e) = surface_mgr.resize(width, height, device_mgr.device()) {
+                eprintln!("Failed to resize surface: {}", e);
+                return;
+            }
+        }
+        self.surface_backoff.reset();
+        self.upload_projection();
+        self.present_held_frame();
+    }
+
+    fn set_scale_factor(&mut self, scale_factor: f64) {
+        if scale_factor != self.scale_factor {
+            self.text_cache.clear();
+        }
+        self.scale_factor = scale_factor;
+
+        // Update projection matrix if initialized
+        self.upload_projection();
     }
 
     fn begin_frame(&mut self) -> Result<()> {
         if self.surface_mgr.is_none() {
             anyhow::bail!("Backend not initialized");
         }
+        self.layers.begin_frame();
         Ok(())
     }
 
     fn end_frame(&mut self) -> Result<()> {
+        self.layers.end_frame();
+        if let Some(ref profiler) = self.profiler {
+            profiler.record_text_cache_stats(&self.text_cache.stats());
+        }
         Ok(())
     }
 
+    fn set_profiler(&mut self, profiler: Arc<Profiler>) {
+        self.profiler = Some(profiler);
+    }
+
+    fn take_recovery_events(&mut self) -> Vec<RecoveryEvent> {
+        self.recovery.take_events()
+    }
+
+    fn set_clear_color(&mut self, color: Color) {
+        self.clear_color = Some(color);
+    }
+
+    fn set_text_hinting(&mut self, hinting: TextHinting) {
+        if hinting != self.text_hinting {
+            // Cached runs point at glyphs rasterized with the old hinting
+            self.text_cache.clear();
+        }
+        self.text_hinting = hinting;
+        if let Some(texture_mgr) = &mut self.texture_mgr {
+            texture_mgr.set_hinting(hinting);
+        }
+    }
+
+    fn set_resize_buffering(&mut self, enabled: bool) {
+        if enabled != self.resize_buffer.is_some() {
+            self.resize_buffer = enabled.then(ResizeBuffer::new);
+        }
+    }
+
+    fn gpu_info(&self) -> Option<GpuInfo> {
+        self.device_mgr.as_ref().map(DeviceManager::gpu_info)
+    }
+
+    fn add_post_process(&mut self, node: Box<dyn PostProcessNode>) -> Result<()> {
+        self.post_process.add(node)
+    }
+
     fn submit(&mut self, commands: &[RenderCommand]) -> Result<()> {
+        let Some(output) = self.acquire_frame()? else {
+            return Ok(());
+        };
+
         let device_mgr = self
             .device_mgr
             .as_ref()
@@ -179,14 +530,6 @@ impl Backend for WgpuBackend {
             .texture_mgr
             .as_mut()
             .ok_or_else(|| anyhow::anyhow!("TextureManager not initialized"))?;
-        let pipeline_mgr = self
-            .pipeline_mgr
-            .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("PipelineManager not initialized"))?;
-        let buffer_mgr = self
-            .buffer_mgr
-            .as_mut()
-            .ok_or_else(|| anyhow::anyhow!("BufferManager not initialized"))?;
         let surface_mgr = self
             .surface_mgr
             .as_mut()
@@ -214,6 +557,7 @@ impl Backend for WgpuBackend {
                             index_start: current_index_start,
                             index_count: current_index_count,
                             scissor: get_current_scissor(&scissor_stack),
+                            layer: None,
                         });
                         current_index_start += current_index_count;
                         current_index_count = 0;
@@ -255,6 +599,7 @@ impl Backend for WgpuBackend {
                             index_start: current_index_start,
                             index_count: current_index_count,
                             scissor: get_current_scissor(&scissor_stack),
+                            layer: None,
                         });
                         current_index_start += current_index_count;
                         current_index_count = 0;
@@ -409,21 +754,22 @@ impl Backend for WgpuBackend {
                 index_start: current_index_start,
                 index_count: current_index_count,
                 scissor: get_current_scissor(&scissor_stack),
+                layer: None,
             });
         }
 
-        Self::flush_and_render(
-            batches,
-            device_mgr,
-            surface_mgr,
-            buffer_mgr,
-            pipeline_mgr,
-            &self.vertices,
-            &self.indices,
-        )
+        self.flush_and_render(output, batches)
     }
 
-    fn submit_batch(&mut self, batch: &crate::batch::RenderBatch) -> Result<()> {
+    fn submit_batch(&mut self, batch: &RenderBatch) -> Result<()> {
+        let Some(output) = self.acquire_frame()? else {
+            return Ok(());
+        };
+
+        // Layers go to their textures first, so the frame can sample them
+        self.render_layers(&batch.commands)?;
+        self.render_layers(&batch.overlay_commands)?;
+
         let device_mgr = self
             .device_mgr
             .as_ref()
@@ -432,75 +778,354 @@ impl Backend for WgpuBackend {
             .texture_mgr
             .as_mut()
             .ok_or_else(|| anyhow::anyhow!("TextureManager not initialized"))?;
-        let pipeline_mgr = self
-            .pipeline_mgr
+        let surface_mgr = self
+            .surface_mgr
             .as_ref()
-            .ok_or_else(|| anyhow::anyhow!("PipelineManager not initialized"))?;
+            .ok_or_else(|| anyhow::anyhow!("SurfaceManager not initialized"))?;
+
+        let mut tessellation = Tessellation {
+            vertices: std::mem::take(&mut self.vertices),
+            indices: std::mem::take(&mut self.indices),
+            ..Tessellation::default()
+        };
+        tessellation.vertices.clear();
+        tessellation.indices.clear();
+        Tessellator {
+            queue: device_mgr.queue(),
+            texture_mgr,
+            text_cache: &mut self.text_cache,
+            layers: &self.layers,
+            scale_factor: self.scale_factor,
+            origin: [0, 0],
+            size: (surface_mgr.width(), surface_mgr.height()),
+        }
+        .add_batch(batch, &mut tessellation);
+        tessellation.flush(None);
+
+        self.vertices = tessellation.vertices;
+        self.indices = tessellation.indices;
+        self.flush_and_render(output, tessellation.draws)
+    }
+}
+
+impl WgpuBackend {
+    fn flush_and_render(&mut self, output: SurfaceTexture, batches: Vec<DrawBatch>) -> Result<()> {
+        let clear_color = self.clear_color();
+        let device_mgr = self
+            .device_mgr
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("DeviceManager not initialized"))?;
         let buffer_mgr = self
             .buffer_mgr
             .as_mut()
             .ok_or_else(|| anyhow::anyhow!("BufferManager not initialized"))?;
+        let pipeline_mgr = self
+            .pipeline_mgr
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("PipelineManager not initialized"))?;
         let surface_mgr = self
             .surface_mgr
-            .as_mut()
+            .as_ref()
             .ok_or_else(|| anyhow::anyhow!("SurfaceManager not initialized"))?;
+        let indices = &self.indices;
 
-        // 1. Clear buffers
-        self.vertices.clear();
-        self.indices.clear();
+        // Colors are sRGB, the target may expect them linear
+        let color_space = surface_mgr.color_space();
+        if color_space != OutputColorSpace::Srgb {
+            for vertex in &mut self.vertices {
+                vertex.color = color_space.encode(vertex.color);
+            }
+        }
+        let clear = color_space.clear_value(clear_color);
+
+        // 3. Update buffers
+        buffer_mgr.upload_vertices(device_mgr.device(), device_mgr.queue(), &self.vertices);
+        buffer_mgr.upload_indices(device_mgr.device(), device_mgr.queue(), indices);
+
+        // 4. Render Pass
+        let surface_view = surface_mgr.create_view(&output);
 
-        // 2. Pre-populate vertices from batch
-        // We convert them to SimpleVertex
-        self.vertices.reserve(batch.vertices.len());
-        for v in &batch.vertices {
-            self.vertices.push(SimpleVertex::from(v));
+        // With resize buffering the finished frame is kept in a texture and
+        // blitted to the surface
+        if let Some(resize_buffer) = &mut self.resize_buffer {
+            resize_buffer.prepare(
+                device_mgr.device(),
+                surface_mgr.format(),
+                output.texture.width(),
+                output.texture.height(),
+            );
         }
+        let frame_view = self
+            .resize_buffer
+            .as_ref()
+            .and_then(ResizeBuffer::view)
+            .unwrap_or(&surface_view);
 
-        // 3. Process commands
-        let mut batches: Vec<DrawBatch> = Vec::new();
-        let mut current_index_start = 0;
-        let mut current_index_count = 0;
-        let mut scissor_stack: Vec<[u32; 4]> = Vec::new();
-        let mut vertex_count = self.vertices.len() as u32; // Offset for new vertices (text)
+        // With post-processing the UI goes to an offscreen texture first
+        if !self.post_process.is_empty() {
+            self.post_process.prepare(
+                device_mgr.device(),
+                surface_mgr.format(),
+                output.texture.width(),
+                output.texture.height(),
+            );
+        }
+        let view = self.post_process.ui_view().unwrap_or(frame_view);
 
-        let get_current_scissor =
-            |stack: &[[u32; 4]]| -> Option<[u32; 4]> { stack.last().cloned() };
+        let mut encoder = device_mgr
+            .device()
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("Render Encoder"),
+            });
 
-        // Combine commands and overlay_commands for processing
-        let all_commands = batch.commands.iter().chain(batch.overlay_commands.iter());
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
 
-        for cmd in all_commands {
-            use crate::batch::DrawCommand;
-            match cmd {
+            if !indices.is_empty() {
+                render_pass.set_vertex_buffer(0, buffer_mgr.vertex_buffer().slice(..));
+                render_pass.set_index_buffer(
+                    buffer_mgr.index_buffer().slice(..),
+                    wgpu::IndexFormat::Uint32,
+                );
+
+                let layers = &self.layers;
+                layer_cache::draw_batches(
+                    &mut render_pass,
+                    pipeline_mgr,
+                    pipeline_mgr.bind_group(),
+                    &batches,
+                    (output.texture.width(), output.texture.height()),
+                    |id| layers.get(id).map(CachedLayer::bind_group),
+                );
+            }
+        }
+
+        if !self.post_process.is_empty() {
+            self.post_process.execute(
+                device_mgr.device(),
+                device_mgr.queue(),
+                &mut encoder,
+                frame_view,
+                self.scale_factor as f32,
+            )?;
+        }
+        if let Some(resize_buffer) = &self.resize_buffer {
+            resize_buffer.present(device_mgr.device(), &mut encoder, &surface_view);
+        }
+
+        device_mgr.queue().submit(std::iter::once(encoder.finish()));
+        output.present();
+        Ok(())
+    }
+}
+
+impl WgpuBackend {
+    /// Render the layers among `commands` whose texture is missing or shows
+    /// other content, inner layers before the layers drawing them
+    fn render_layers(&mut self, commands: &[DrawCommand]) -> Result<()> {
+        for command in commands {
+            let DrawCommand::Layer { id, rect, content } = command else {
+                continue;
+            };
+            self.render_layers(&content.batch().commands)?;
+
+            let (origin, size) = layer_cache::physical_bounds(*rect, self.scale_factor);
+            if self.layers.touch(*id, content, origin, size) {
+                continue;
+            }
+            let device_mgr = self
+                .device_mgr
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("DeviceManager not initialized"))?;
+            // Layers the device can't hold a texture for are drawn directly
+            let max_size = device_mgr.device().limits().max_texture_dimension_2d;
+            if size.0 == 0 || size.1 == 0 || size.0 > max_size || size.1 > max_size {
+                self.layers.remove(*id);
+                continue;
+            }
+            let texture_mgr = self
+                .texture_mgr
+                .as_mut()
+                .ok_or_else(|| anyhow::anyhow!("TextureManager not initialized"))?;
+            let surface_mgr = self
+                .surface_mgr
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("SurfaceManager not initialized"))?;
+            let pipeline_mgr = self
+                .pipeline_mgr
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("PipelineManager not initialized"))?;
+            let buffer_mgr = self
+                .buffer_mgr
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("BufferManager not initialized"))?;
+
+            let mut tessellation = Tessellation::default();
+            Tessellator {
+                queue: device_mgr.queue(),
+                texture_mgr: &mut *texture_mgr,
+                text_cache: &mut self.text_cache,
+                layers: &self.layers,
+                scale_factor: self.scale_factor,
+                origin,
+                size,
+            }
+            .add_commands(
+                content.batch(),
+                &content.batch().commands,
+                &mut tessellation,
+            );
+            tessellation.flush(None);
+
+            let color_space = surface_mgr.color_space();
+            if color_space != OutputColorSpace::Srgb {
+                for vertex in &mut tessellation.vertices {
+                    vertex.color = color_space.encode(vertex.color);
+                }
+            }
+            let gpu = LayerResources {
+                device: device_mgr.device(),
+                queue: device_mgr.queue(),
+                pipeline_mgr,
+                atlas: texture_mgr.atlas(),
+                frame_uniforms: buffer_mgr.uniform_buffer(),
+                format: surface_mgr.format(),
+                scale_factor: self.scale_factor,
+            };
+            self.layers.render(
+                &gpu,
+                *id,
+                content,
+                origin,
+                size,
+                LayerGeometry {
+                    vertices: &tessellation.vertices,
+                    indices: &tessellation.indices,
+                    draws: &tessellation.draws,
+                },
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Vertices, indices and draw calls built from a batch
+#[derive(Default)]
+struct Tessellation {
+    vertices: Vec<SimpleVertex>,
+    indices: Vec<u32>,
+    draws: Vec<DrawBatch>,
+    /// Indices added since the last draw call
+    pending: u32,
+    /// Clip rectangles in physical pixels of the target
+    scissors: Vec<[u32; 4]>,
+}
+
+impl Tessellation {
+    /// End the current draw call, which samples `layer` if given
+    fn flush(&mut self, layer: Option<u64>) {
+        if self.pending > 0 {
+            self.draws.push(DrawBatch {
+                index_start: self.indices.len() as u32 - self.pending,
+                index_count: self.pending,
+                scissor: self.scissors.last().copied(),
+                layer,
+            });
+            self.pending = 0;
+        }
+    }
+
+    /// Add a textured quad with the given corners, clockwise from top-left
+    fn push_quad(&mut self, corners: [([f32; 2], [f32; 2]); 4], color: [f32; 4], flags: u32) {
+        let first = self.vertices.len() as u32;
+        for (position, uv) in corners {
+            self.vertices.push(SimpleVertex {
+                position,
+                color,
+                uv,
+                params: [0.0; 4],
+                flags,
+            });
+        }
+        self.indices
+            .extend([0, 1, 2, 0, 2, 3].map(|corner| first + corner));
+        self.pending += 6;
+    }
+}
+
+/// Builds the geometry of batches for a target
+struct Tessellator<'a> {
+    queue: &'a Queue,
+    texture_mgr: &'a mut TextureManager,
+    text_cache: &'a mut TextLayoutCache,
+    /// Layers that can be drawn from their texture
+    layers: &'a LayerCache,
+    scale_factor: f64,
+    /// Top-left corner of the target in physical pixels of the window
+    origin: [i32; 2],
+    /// Size of the target in physical pixels
+    size: (u32, u32),
+}
+
+impl Tessellator<'_> {
+    /// Add a frame's batch, overlays last and unclipped
+    fn add_batch(&mut self, batch: &RenderBatch, out: &mut Tessellation) {
+        self.add_commands(batch, &batch.commands, out);
+        // Overlays are never clipped, even by a clip left unbalanced
+        if !out.scissors.is_empty() {
+            out.flush(None);
+            out.scissors.clear();
+        }
+        self.add_commands(batch, &batch.overlay_commands, out);
+    }
+
+    /// Add `commands`, drawing the geometry of `batch`
+    fn add_commands(
+        &mut self,
+        batch: &RenderBatch,
+        commands: &[DrawCommand],
+        out: &mut Tessellation,
+    ) {
+        let base = out.vertices.len() as u32;
+        out.vertices
+            .extend(batch.vertices.iter().map(SimpleVertex::from));
+
+        for command in commands {
+            match command {
                 DrawCommand::PushClip(rect) => {
-                    if current_index_count > 0 {
-                        batches.push(DrawBatch {
-                            index_start: current_index_start,
-                            index_count: current_index_count,
-                            scissor: get_current_scissor(&scissor_stack),
-                        });
-                        current_index_start += current_index_count;
-                        current_index_count = 0;
-                    }
+                    out.flush(None);
                     // Calculate scissor
                     let scale = self.scale_factor;
-                    let x = (rect.x as f64 * scale).round() as i32;
-                    let y = (rect.y as f64 * scale).round() as i32;
+                    let x = (rect.x as f64 * scale).round() as i32 - self.origin[0];
+                    let y = (rect.y as f64 * scale).round() as i32 - self.origin[1];
                     let w = (rect.width as f64 * scale).round() as i32;
                     let h = (rect.height as f64 * scale).round() as i32;
-                    let surface_w = surface_mgr.width() as i32;
-                    let surface_h = surface_mgr.height() as i32;
-                    let min_x = x.max(0);
-                    let min_y = y.max(0);
-                    let max_x = (x + w).min(surface_w).max(min_x);
-                    let max_y = (y + h).min(surface_h).max(min_y);
+                    let target_w = self.size.0 as i32;
+                    let target_h = self.size.1 as i32;
+                    let min_x = x.clamp(0, target_w);
+                    let min_y = y.clamp(0, target_h);
+                    let max_x = (x + w).min(target_w).max(min_x);
+                    let max_y = (y + h).min(target_h).max(min_y);
                     let mut new_rect = [
                         min_x as u32,
                         min_y as u32,
                         (max_x - min_x) as u32,
                         (max_y - min_y) as u32,
                     ];
-                    if let Some(parent) = scissor_stack.last() {
+                    if let Some(parent) = out.scissors.last() {
                         let px = parent[0];
                         let py = parent[1];
                         let pw = parent[2];
@@ -511,33 +1136,24 @@ impl Backend for WgpuBackend {
                         let ih = (new_rect[1] + new_rect[3]).min(py + ph).saturating_sub(iy);
                         new_rect = [ix, iy, iw, ih];
                     }
-                    scissor_stack.push(new_rect);
+                    out.scissors.push(new_rect);
                 }
                 DrawCommand::PopClip => {
-                    if current_index_count > 0 {
-                        batches.push(DrawBatch {
-                            index_start: current_index_start,
-                            index_count: current_index_count,
-                            scissor: get_current_scissor(&scissor_stack),
-                        });
-                        current_index_start += current_index_count;
-                        current_index_count = 0;
-                    }
-                    scissor_stack.pop();
+                    out.flush(None);
+                    out.scissors.pop();
                 }
                 DrawCommand::Rect { index_range, .. }
+                | DrawCommand::RoundedRect { index_range, .. }
+                | DrawCommand::Stroke { index_range, .. }
+                | DrawCommand::GradientRect { index_range, .. }
                 | DrawCommand::TexturedQuad { index_range, .. }
                 | DrawCommand::Circle { index_range, .. }
                 | DrawCommand::Line { index_range, .. } => {
-                    // Use pre-batched indices
-                    // We need to copy indices from batch.indices[index_range] to self.indices
-                    // self.vertices already contains batch vertices at offset 0
-                    // batch.indices are 0-based relative to batch vertices
-                    // So we can use them directly (just cast to u32)
+                    // Use pre-batched indices, relative to the batch's vertices
                     for i in index_range.clone() {
-                        if (i as usize) < batch.indices.len() {
-                            self.indices.push(batch.indices[i as usize] as u32);
-                            current_index_count += 1;
+                        if let Some(&index) = batch.indices.get(i as usize) {
+                            out.indices.push(base + index as u32);
+                            out.pending += 1;
                         }
                     }
                 }
@@ -547,209 +1163,223 @@ impl Backend for WgpuBackend {
                     color,
                     font_size,
                     align,
+                    fill,
                     ..
                 } => {
-                    // Generate text vertices/indices immediate mode style
-                    // Appending to self.vertices, so indices start at 'vertex_count'
+                    // Shaped runs are cached, only translated and tinted here
                     let (x_orig, y) = *position;
                     let color_arr = [color.r, color.g, color.b, color.a];
                     let font_size = *font_size;
                     let align = *align;
-                    let text_width = if align != strato_core::text::TextAlign::Left {
-                        let mut width = 0.0;
-                        for ch in text.chars() {
-                            if let Some(glyph) = texture_mgr.get_or_cache_glyph(
-                                device_mgr.queue(),
-                                ch,
-                                font_size as u32,
-                            ) {
-                                width += glyph.metrics.advance;
-                            } else if ch == ' ' {
-                                width += font_size * 0.3;
+                    let texture_mgr = &mut *self.texture_mgr;
+                    let queue = self.queue;
+                    let run = self.text_cache.get_or_shape(
+                        text,
+                        font_size,
+                        0.0,
+                        self.scale_factor as f32,
+                        || {
+                            let mut run = ShapedRun::default();
+                            let mut pen = 0.0;
+                            for ch in text.chars() {
+                                if let Some(glyph) =
+                                    texture_mgr.get_or_cache_glyph(queue, ch, font_size as u32)
+                                {
+                                    run.glyphs.push(ShapedGlyph {
+                                        offset: [
+                                            pen + glyph.metrics.bearing_x as f32,
+                                            font_size - glyph.metrics.bearing_y as f32,
+                                        ],
+                                        size: [
+                                            glyph.metrics.width as f32,
+                                            glyph.metrics.height as f32,
+                                        ],
+                                        uv_rect: glyph.uv_rect,
+                                    });
+                                    pen += glyph.metrics.advance;
+                                } else if ch == ' ' {
+                                    pen += font_size * 0.3;
+                                }
                             }
-                        }
-                        width
-                    } else {
-                        0.0
-                    };
+                            run.width = pen;
+                            run
+                        },
+                    );
                     let mut x = x_orig;
                     if align == strato_core::text::TextAlign::Center {
-                        x -= text_width / 2.0;
+                        x -= run.width / 2.0;
                     } else if align == strato_core::text::TextAlign::Right {
-                        x -= text_width;
+                        x -= run.width;
                     }
 
-                    for ch in text.chars() {
-                        if let Some(glyph) =
-                            texture_mgr.get_or_cache_glyph(device_mgr.queue(), ch, font_size as u32)
-                        {
-                            let (gx, gy, w, h) = (
-                                x + glyph.metrics.bearing_x as f32,
-                                y + font_size - glyph.metrics.bearing_y as f32,
-                                glyph.metrics.width as f32,
-                                glyph.metrics.height as f32,
-                            );
-                            let (u0, v0, u1, v1) = glyph.uv_rect;
-                            let p0 = [gx, gy];
-                            let p1 = [gx + w, gy];
-                            let p2 = [gx + w, gy + h];
-                            let p3 = [gx, gy + h];
-                            self.vertices.push(SimpleVertex {
-                                position: p0,
-                                color: color_arr,
-                                uv: [u0, v0],
-                                params: [0.0; 4],
-                                flags: 1,
-                            });
-                            self.vertices.push(SimpleVertex {
-                                position: p1,
-                                color: color_arr,
-                                uv: [u1, v0],
-                                params: [0.0; 4],
-                                flags: 1,
-                            });
-                            self.vertices.push(SimpleVertex {
-                                position: p2,
-                                color: color_arr,
-                                uv: [u1, v1],
-                                params: [0.0; 4],
-                                flags: 1,
-                            });
-                            self.vertices.push(SimpleVertex {
-                                position: p3,
-                                color: color_arr,
-                                uv: [u0, v1],
-                                params: [0.0; 4],
-                                flags: 1,
-                            });
-                            self.indices.push(vertex_count);
-                            self.indices.push(vertex_count + 1);
-                            self.indices.push(vertex_count + 2);
-                            self.indices.push(vertex_count);
-                            self.indices.push(vertex_count + 2);
-                            self.indices.push(vertex_count + 3);
-                            vertex_count += 4;
-                            current_index_count += 6;
-                            x += glyph.metrics.advance;
-                        } else if ch == ' ' {
-                            x += font_size * 0.3;
+                    for glyph in &run.glyphs {
+                        let (gx, gy) = (x + glyph.offset[0], y + glyph.offset[1]);
+                        let [w, h] = glyph.size;
+                        let (u0, v0, u1, v1) = glyph.uv_rect;
+                        let corners = [
+                            ([gx, gy], [u0, v0]),
+                            ([gx + w, gy], [u1, v0]),
+                            ([gx + w, gy + h], [u1, v1]),
+                            ([gx, gy + h], [u0, v1]),
+                        ];
+                        match fill {
+                            Some(fill) => {
+                                let first = out.vertices.len();
+                                out.push_quad(corners, color_arr, 1);
+                                for vertex in &mut out.vertices[first..] {
+                                    vertex.color = fill.color_at(vertex.position).to_array();
+                                }
+                            }
+                            None => out.push_quad(corners, color_arr, 1),
                         }
                     }
                 }
-                _ => {}
+                DrawCommand::Image {
+                    id,
+                    data,
+                    width,
+                    height,
+                    rect,
+                    color,
+                } => {
+                    if let Some(image) = self
+                        .texture_mgr
+                        .get_or_upload_image(self.queue, *id, data, *width, *height)
+                    {
+                        let (x, y, w, h) = (rect.x, rect.y, rect.width, rect.height);
+                        let (u0, v0, u1, v1) = image.uv_rect;
+                        out.push_quad(
+                            [
+                                ([x, y], [u0, v0]),
+                                ([x + w, y], [u1, v0]),
+                                ([x + w, y + h], [u1, v1]),
+                                ([x, y + h], [u0, v1]),
+                            ],
+                            [color.r, color.g, color.b, color.a],
+                            1,
+                        );
+                    }
+                }
+                DrawCommand::Layer { id, content, .. } => match self.layers.get(*id) {
+                    Some(layer) => {
+                        out.flush(None);
+                        let rect = layer.rect(self.scale_factor);
+                        let (x, y, w, h) = (rect.x, rect.y, rect.width, rect.height);
+                        out.push_quad(
+                            [
+                                ([x, y], [0.0, 0.0]),
+                                ([x + w, y], [1.0, 0.0]),
+                                ([x + w, y + h], [1.0, 1.0]),
+                                ([x, y + h], [0.0, 1.0]),
+                            ],
+                            [1.0; 4],
+                            FLAG_LAYER,
+                        );
+                        out.flush(Some(*id));
+                    }
+                    None => self.add_commands(content.batch(), &content.batch().commands, out),
+                },
             }
         }
+    }
+}
 
-        if current_index_count > 0 {
-            batches.push(DrawBatch {
-                index_start: current_index_start,
-                index_count: current_index_count,
-                scissor: get_current_scissor(&scissor_stack),
-            });
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::surface::srgb_to_linear;
+    use std::collections::VecDeque;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use wgpu::SurfaceError;
+
+    /// Surface failing with the queued errors, then timing out
+    struct FailingSurface {
+        errors: VecDeque<SurfaceError>,
+        reconfigured: Arc<AtomicUsize>,
+    }
+
+    impl AcquireSurface for FailingSurface {
+        fn acquire(&mut self) -> Result<SurfaceTexture, SurfaceError> {
+            Err(self.errors.pop_front().unwrap_or(SurfaceError::Timeout))
         }
 
-        Self::flush_and_render(
-            batches,
-            device_mgr,
-            surface_mgr,
-            buffer_mgr,
-            pipeline_mgr,
-            &self.vertices,
-            &self.indices,
-        )
+        fn reconfigure(&mut self, _device: &wgpu::Device) {
+            self.reconfigured.fetch_add(1, Ordering::SeqCst);
+        }
     }
-}
 
-impl WgpuBackend {
-    fn flush_and_render(
-        batches: Vec<DrawBatch>,
-        device_mgr: &DeviceManager,
-        surface_mgr: &mut SurfaceManager,
-        buffer_mgr: &mut BufferManager,
-        pipeline_mgr: &PipelineManager,
-        vertices: &[SimpleVertex],
-        indices: &[u32],
-    ) -> Result<()> {
-        // 3. Update buffers
-        buffer_mgr.upload_vertices(device_mgr.device(), device_mgr.queue(), vertices);
-        buffer_mgr.upload_indices(device_mgr.device(), device_mgr.queue(), indices);
+    #[tokio::test]
+    async fn test_outdated_surface_on_resize_is_reconfigured() {
+        let mut backend = WgpuBackend::new();
+        backend.device_mgr = Some(DeviceManager::new(Backends::all()).await.unwrap());
+        let reconfigured = Arc::new(AtomicUsize::new(0));
+        backend.fake_surface = Some(Box::new(FailingSurface {
+            errors: VecDeque::from([SurfaceError::Outdated]),
+            reconfigured: reconfigured.clone(),
+        }));
 
-        // 4. Render Pass
-        let output = surface_mgr.get_current_texture()?;
-        let view = output
-            .texture
-            .create_view(&wgpu::TextureViewDescriptor::default());
+        // A resize races with frame acquisition and the surface reports Outdated
+        backend.resize(1024, 768);
+        assert!(backend.acquire_frame().unwrap().is_none(), "frame is skipped");
+        assert_eq!(reconfigured.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            backend.take_recovery_events(),
+            [RecoveryEvent::SurfaceReconfigured {
+                reason: SurfaceError::Outdated.to_string(),
+            }]
+        );
 
-        let mut encoder = device_mgr
-            .device()
-            .create_command_encoder(&CommandEncoderDescriptor {
-                label: Some("Render Encoder"),
-            });
+        // A timeout skips the frame without touching the surface
+        assert!(backend.acquire_frame().unwrap().is_none());
+        assert_eq!(reconfigured.load(Ordering::SeqCst), 1);
+        assert!(backend.take_recovery_events().is_empty());
+        assert_eq!(backend.recovery_stats().surface_reconfigurations, 1);
+    }
 
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.1,
-                            g: 0.1,
-                            b: 0.1,
-                            a: 1.0,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                occlusion_query_set: None,
-                timestamp_writes: None,
-            });
+    #[test]
+    fn test_configured_clear_color_reaches_the_render_pass() {
+        let mut backend = WgpuBackend::new();
+        let clear = OutputColorSpace::Srgb.clear_value(backend.clear_color());
+        assert_eq!((clear.r as f32, clear.a), (DEFAULT_CLEAR_COLOR.r, 1.0));
 
-            if !indices.is_empty() {
-                render_pass.set_pipeline(pipeline_mgr.pipeline());
-                render_pass.set_bind_group(0, pipeline_mgr.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, buffer_mgr.vertex_buffer().slice(..));
-                render_pass.set_index_buffer(
-                    buffer_mgr.index_buffer().slice(..),
-                    wgpu::IndexFormat::Uint32,
-                );
+        backend.set_clear_color(Color::rgb(0.2, 0.4, 0.6));
+        let clear = OutputColorSpace::Srgb.clear_value(backend.clear_color());
+        assert_eq!(
+            [clear.r, clear.g, clear.b, clear.a].map(|c| c as f32),
+            [0.2, 0.4, 0.6, 1.0]
+        );
+        let clear = OutputColorSpace::Linear.clear_value(backend.clear_color());
+        assert!((clear.g as f32 - srgb_to_linear(0.4)).abs() < 1e-6);
+    }
 
-                for batch in batches {
-                    if batch.index_count == 0 {
-                        continue;
-                    }
-                    if let Some(scissor) = batch.scissor {
-                        if scissor[2] == 0 || scissor[3] == 0 {
-                            continue;
-                        }
-                        render_pass
-                            .set_scissor_rect(scissor[0], scissor[1], scissor[2], scissor[3]);
-                    } else {
-                        render_pass.set_scissor_rect(
-                            0,
-                            0,
-                            surface_mgr.width(),
-                            surface_mgr.height(),
-                        );
-                    }
-                    render_pass.draw_indexed(
-                        batch.index_start..batch.index_start + batch.index_count,
-                        0,
-                        0..1,
-                    );
-                }
-            }
-        }
-        device_mgr.queue().submit(std::iter::once(encoder.finish()));
-        output.present();
-        Ok(())
+    #[test]
+    fn test_transparent_window_clears_to_transparent_by_default() {
+        let mut backend = WgpuBackend::new();
+        backend.set_transparent(true);
+        assert_eq!(
+            OutputColorSpace::Srgb.clear_value(backend.clear_color()),
+            wgpu::Color::TRANSPARENT
+        );
+
+        // A translucent clear color is premultiplied
+        backend.set_clear_color(Color::rgba(1.0, 1.0, 1.0, 0.5));
+        let clear = OutputColorSpace::Srgb.clear_value(backend.clear_color());
+        assert_eq!((clear.r, clear.a), (0.5, 0.5));
     }
-}
 
-struct DrawBatch {
-    index_start: u32,
-    index_count: u32,
-    scissor: Option<[u32; 4]>,
+    #[tokio::test]
+    async fn test_text_cache_stats_reach_the_profiler_each_frame() {
+        let manager = crate::device::DeviceManager::new(None, None).await.unwrap();
+        let device = manager.initialize_device().await.unwrap();
+        let profiler = Arc::new(Profiler::new(device).unwrap());
+
+        let mut backend = WgpuBackend::new();
+        backend.set_text_cache_capacity(32);
+        backend.set_profiler(profiler.clone());
+        backend.end_frame().unwrap();
+
+        let text_stats = profiler.get_performance_report().text_stats;
+        assert_eq!(text_stats.get("text_cache_capacity"), Some(&32));
+        assert_eq!(text_stats.get("text_cache_entries"), Some(&0));
+    }
 }
@@ -1,15 +1,191 @@
 //! Render batching system for efficient GPU rendering
+//!
+//! [`RenderBatch`] is the drawing API widgets use in `Widget::render`.
+//!
+//! # Coordinate space
+//!
+//! All positions and sizes are in logical pixels, with the origin at the
+//! top-left corner of the window and `y` growing downwards. The backend maps
+//! logical pixels to physical ones using the window's scale factor, so
+//! widgets never deal with DPI themselves.
+//!
+//! # Transforms
+//!
+//! Shape commands (rectangles, rounded rectangles, strokes, gradients,
+//! circles, textured quads) take a [`Transform`] that is applied to every
+//! vertex on the CPU when the command is added; pass
+//! [`Transform::identity()`] to draw in window coordinates. Text, images,
+//! lines and clip rectangles are not transformed.
+//!
+//! # Ordering
+//!
+//! Commands are drawn in the order they were added. Commands added to the
+//! overlay layer are drawn after all regular commands and are not clipped;
+//! popups added with [`RenderBatch::push_overlay`] stack by their open order.
+//! Clip rectangles pushed with [`RenderBatch::push_clip`] apply until the
+//! matching [`RenderBatch::pop_clip`] and nest by intersection.
+//!
+//! # Parallel generation
+//!
+//! Subtrees can be drawn on other threads into [`RenderBatch::segment`]s of
+//! the batch, which [`RenderBatch::merge`] then concatenates in tree order.
+//! Merging rebases the segments' indices and replays their overlays, so the
+//! result is the same, byte for byte, as drawing the subtrees one after the
+//! other into the batch itself.
+//!
+//! # Layers
+//!
+//! A subtree that rarely changes can be drawn once into a [`LayerContent`]
+//! and added to every frame with [`RenderBatch::add_layer`]. The GPU backend
+//! renders the content into a texture of its own the first time it sees it
+//! and draws that texture as a single quad until the layer is given new
+//! content.
 
-use crate::text::TextRenderer;
 use crate::vertex::Vertex;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, PI};
 use std::ops::Range;
-use strato_core::types::{Color, Rect, Transform};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use strato_core::types::{
+    Background, BorderRadius, Color, GradientStop, LinearGradient, Rect, Transform,
+};
 
 use strato_core::text::TextAlign;
 
+/// Gradient painting a run of text, see [`RenderBatch::add_gradient_text`]
+///
+/// The gradient spans `bounds` rather than the run itself, so runs of one
+/// block of text, e.g. its lines, share a single gradient. Glyphs take the
+/// gradient's colors at their corners, which is exact for two stops and
+/// follows further stops glyph by glyph. Build it with [`TextFill::new`],
+/// which sorts the stops by position as [`color_at`](Self::color_at) expects.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextFill {
+    pub gradient: LinearGradient,
+    pub bounds: Rect,
+}
+
+impl TextFill {
+    /// Paint `gradient` across `bounds`
+    pub fn new(mut gradient: LinearGradient, bounds: Rect) -> Self {
+        gradient
+            .stops
+            .sort_by(|a, b| a.position.total_cmp(&b.position));
+        Self { gradient, bounds }
+    }
+
+    /// The fill's color at `point`, in logical pixels
+    pub fn color_at(&self, point: [f32; 2]) -> Color {
+        let stops = &self.gradient.stops;
+        let Some(first) = stops.first() else {
+            return Color::TRANSPARENT;
+        };
+        match gradient_offset(self.bounds, &self.gradient) {
+            Some(offset) => stop_color(stops, offset(point)),
+            None => first.color,
+        }
+    }
+}
+
+/// What a [layer](RenderBatch::add_layer) draws
+///
+/// Clones share the recorded batch, which is how backends tell that a layer
+/// still shows the same content: a layer is drawn from the texture rendered
+/// for it as long as it is added with the same content, or a clone of it.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(from = "RecordedLayer", into = "RecordedLayer")]
+pub struct LayerContent {
+    batch: Arc<RenderBatch>,
+    /// Overlays drawn by the content, added to the frame each time instead
+    /// of being cached with it
+    overlays: Arc<RenderBatch>,
+}
+
+impl LayerContent {
+    /// Content drawing what `batch` draws
+    ///
+    /// Draw `batch` with the positions the content has on screen; commands
+    /// outside the rectangle the layer is added with are cut off.
+    pub fn new(mut batch: RenderBatch) -> Self {
+        let mut overlays = RenderBatch::from_parts(
+            batch.vertices.clone(),
+            batch.indices.clone(),
+            Vec::new(),
+            std::mem::take(&mut batch.overlay_commands),
+            batch.viewport,
+        );
+        if overlays.overlay_commands.is_empty() {
+            overlays = RenderBatch::new();
+        }
+        batch.overlay_layers.clear();
+        batch.segment_overlays = None;
+        Self {
+            batch: Arc::new(batch),
+            overlays: Arc::new(overlays),
+        }
+    }
+
+    /// The batch drawn into the layer's texture, without overlays
+    pub fn batch(&self) -> &RenderBatch {
+        &self.batch
+    }
+
+    /// Whether `other` is this content or a clone of it
+    pub fn same_as(&self, other: &LayerContent) -> bool {
+        Arc::ptr_eq(&self.batch, &other.batch)
+    }
+}
+
+impl std::fmt::Debug for LayerContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayerContent")
+            .field("commands", &self.batch.commands.len())
+            .field("overlay_commands", &self.overlays.overlay_commands.len())
+            .finish()
+    }
+}
+
+/// [`LayerContent`] as it is serialized
+#[derive(Serialize, Deserialize)]
+struct RecordedLayer {
+    vertices: Vec<Vertex>,
+    indices: Vec<u16>,
+    commands: Vec<DrawCommand>,
+}
+
+impl From<LayerContent> for RecordedLayer {
+    fn from(content: LayerContent) -> Self {
+        Self {
+            vertices: content.batch.vertices.clone(),
+            indices: content.batch.indices.clone(),
+            commands: content.batch.commands.clone(),
+        }
+    }
+}
+
+impl From<RecordedLayer> for LayerContent {
+    fn from(layer: RecordedLayer) -> Self {
+        let batch = RenderBatch::from_parts(
+            layer.vertices,
+            layer.indices,
+            layer.commands,
+            Vec::new(),
+            None,
+        );
+        Self::new(batch)
+    }
+}
+
+/// A new id for [`RenderBatch::add_layer`], distinct from every other one
+pub fn next_layer_id() -> u64 {
+    static NEXT_LAYER_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_LAYER_ID.fetch_add(1, Ordering::Relaxed)
+}
+
 /// Draw command types
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DrawCommand {
     /// Draw a filled rectangle
     Rect {
@@ -22,8 +198,26 @@ pub enum DrawCommand {
     RoundedRect {
         rect: Rect,
         color: Color,
-        radius: f32,
+        radii: BorderRadius,
+        transform: Transform,
+        index_range: Range<u32>,
+    },
+    /// Draw the outline of a (rounded) rectangle, inside its bounds
+    Stroke {
+        rect: Rect,
+        color: Color,
+        width: f32,
+        radii: BorderRadius,
         transform: Transform,
+        index_range: Range<u32>,
+    },
+    /// Fill a (rounded) rectangle with a linear gradient
+    GradientRect {
+        rect: Rect,
+        gradient: LinearGradient,
+        radii: BorderRadius,
+        transform: Transform,
+        index_range: Range<u32>,
     },
     /// Draw text
     Text {
@@ -33,6 +227,9 @@ pub enum DrawCommand {
         font_size: f32,
         letter_spacing: f32,
         align: TextAlign,
+        /// Gradient painted over the glyphs instead of `color`
+        #[serde(default)]
+        fill: Option<TextFill>,
     },
     /// Draw an image
     Image {
@@ -52,6 +249,12 @@ pub enum DrawCommand {
         transform: Transform,
         index_range: Range<u32>,
     },
+    /// Draw a layer's content, through a texture kept between frames
+    Layer {
+        id: u64,
+        rect: Rect,
+        content: LayerContent,
+    },
     /// Draw a circle
     Circle {
         center: (f32, f32),
@@ -81,9 +284,21 @@ pub struct RenderBatch {
     pub indices: Vec<u16>,
     pub commands: Vec<DrawCommand>,
     pub overlay_commands: Vec<DrawCommand>,
+    /// Ordered overlays as (open order, first index in `overlay_commands`)
+    overlay_layers: Vec<(u64, usize)>,
+    /// Overlays pushed into a segment, kept in push order until it is merged
+    segment_overlays: Option<Vec<SegmentOverlay>>,
+    viewport: Option<Rect>,
     vertex_count: u16,
     texture_atlas: HashMap<u32, TextureInfo>,
-    text_renderer: TextRenderer,
+}
+
+/// An overlay pushed into a segment: its open order and where its commands
+/// are in the segment's `overlay_commands`
+#[derive(Debug, Clone)]
+struct SegmentOverlay {
+    order: u64,
+    commands: Range<usize>,
 }
 
 /// Texture information for batching
@@ -103,9 +318,80 @@ impl RenderBatch {
             indices: Vec::with_capacity(1536),
             commands: Vec::new(),
             overlay_commands: Vec::new(),
+            overlay_layers: Vec::new(),
+            segment_overlays: None,
+            viewport: None,
+            vertex_count: 0,
+            texture_atlas: HashMap::new(),
+        }
+    }
+
+    /// An empty batch for drawing a subtree, e.g. on another thread, to be
+    /// [merged](Self::merge) into this one
+    ///
+    /// It has this batch's viewport, so popups in the subtree are placed as
+    /// they would be here.
+    pub fn segment(&self) -> Self {
+        // Subtrees are often a single widget, so start without capacity
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            commands: Vec::new(),
+            overlay_commands: Vec::new(),
+            overlay_layers: Vec::new(),
+            segment_overlays: Some(Vec::new()),
+            viewport: self.viewport,
             vertex_count: 0,
             texture_atlas: HashMap::new(),
-            text_renderer: TextRenderer::new(),
+        }
+    }
+
+    /// Append a [segment](Self::segment), drawn after everything added so
+    /// far, as if it had been drawn into this batch directly
+    ///
+    /// Its geometry is copied with indices and index ranges rebased, and its
+    /// overlays are pushed again, so they stack by open order with this
+    /// batch's. Merge segments in tree order. Other batches merge like
+    /// [`append`](Self::append).
+    pub fn merge(&mut self, segment: RenderBatch) {
+        let mut commands = self.merge_geometry(
+            &segment,
+            segment.commands.iter().chain(&segment.overlay_commands),
+        );
+        let mut overlays = commands.split_off(segment.commands.len());
+        self.commands.extend(commands);
+
+        // Commands between pushed overlays were added directly to the
+        // overlay layer, which puts them last
+        let mut merged = 0;
+        for pushed in segment.segment_overlays.unwrap_or_default() {
+            let rest = overlays.split_off(pushed.commands.end - merged);
+            let popup = overlays.split_off(pushed.commands.start - merged);
+            self.overlay_commands.extend(overlays);
+            self.insert_overlay(pushed.order, popup);
+            overlays = rest;
+            merged = pushed.commands.end;
+        }
+        self.overlay_commands.extend(overlays);
+    }
+
+    /// A batch of already built geometry and the commands drawing it
+    pub(crate) fn from_parts(
+        vertices: Vec<Vertex>,
+        indices: Vec<u16>,
+        commands: Vec<DrawCommand>,
+        overlay_commands: Vec<DrawCommand>,
+        viewport: Option<Rect>,
+    ) -> Self {
+        let vertex_count = vertices.len() as u16;
+        Self {
+            vertices,
+            indices,
+            commands,
+            overlay_commands,
+            viewport,
+            vertex_count,
+            ..Self::new()
         }
     }
 
@@ -115,9 +401,25 @@ impl RenderBatch {
         self.indices.clear();
         self.commands.clear();
         self.overlay_commands.clear();
+        self.overlay_layers.clear();
+        if let Some(overlays) = &mut self.segment_overlays {
+            overlays.clear();
+        }
         self.vertex_count = 0;
     }
 
+    /// Set the visible area of the window, in logical pixels
+    ///
+    /// Kept across [`clear`](Self::clear). Popups use it to stay on screen.
+    pub fn set_viewport(&mut self, viewport: Rect) {
+        self.viewport = Some(viewport);
+    }
+
+    /// The visible area of the window, if the caller provided one
+    pub fn viewport(&self) -> Option<Rect> {
+        self.viewport
+    }
+
     /// Get the number of draw commands in the batch
     pub fn command_count(&self) -> usize {
         self.commands.len() + self.overlay_commands.len()
@@ -148,7 +450,7 @@ impl RenderBatch {
         self.commands.push(DrawCommand::PopClip);
     }
 
-    /// Add a rounded rectangle to the batch
+    /// Add a rectangle with the same radius on every corner
     pub fn add_rounded_rect(
         &mut self,
         rect: Rect,
@@ -156,13 +458,138 @@ impl RenderBatch {
         radius: f32,
         transform: Transform,
     ) {
-        let command = DrawCommand::RoundedRect {
+        self.add_rounded_rect_each(rect, color, BorderRadius::all(radius), transform);
+    }
+
+    /// Add a rectangle with a radius per corner
+    ///
+    /// Radii too large for the rectangle are scaled down proportionally, so
+    /// adjacent corners never overlap.
+    pub fn add_rounded_rect_each(
+        &mut self,
+        rect: Rect,
+        color: Color,
+        radii: BorderRadius,
+        transform: Transform,
+    ) {
+        let radii = clamp_radii(rect, radii);
+        let outline = rounded_rect_outline(rect, radii, corner_segments(radii));
+
+        let start_index = self.indices.len() as u32;
+        let points: Vec<([f32; 2], Color)> = outline.into_iter().map(|p| (p, color)).collect();
+        self.batch_convex_polygon(&points, transform);
+        let end_index = self.indices.len() as u32;
+
+        self.commands.push(DrawCommand::RoundedRect {
             rect,
             color,
-            radius,
+            radii,
             transform,
-        };
-        self.commands.push(command);
+            index_range: start_index..end_index,
+        });
+    }
+
+    /// Add the outline of a rectangle, `width` logical pixels thick
+    ///
+    /// The stroke lies inside `rect`, like a CSS border; pass
+    /// `BorderRadius::default()` for square corners.
+    pub fn add_stroke(
+        &mut self,
+        rect: Rect,
+        color: Color,
+        width: f32,
+        radii: BorderRadius,
+        transform: Transform,
+    ) {
+        let radii = clamp_radii(rect, radii);
+        let width = width.max(0.0).min(rect.width.min(rect.height) / 2.0);
+        let segments = corner_segments(radii);
+
+        let inner_rect = Rect::new(
+            rect.x + width,
+            rect.y + width,
+            rect.width - 2.0 * width,
+            rect.height - 2.0 * width,
+        );
+        let inner_radii = BorderRadius::new(
+            (radii.top_left - width).max(0.0),
+            (radii.top_right - width).max(0.0),
+            (radii.bottom_right - width).max(0.0),
+            (radii.bottom_left - width).max(0.0),
+        );
+
+        let outer = rounded_rect_outline(rect, radii, segments);
+        let inner = rounded_rect_outline(inner_rect, inner_radii, segments);
+
+        let start_index = self.indices.len() as u32;
+        self.batch_ring(&outer, &inner, color, transform);
+        let end_index = self.indices.len() as u32;
+
+        self.commands.push(DrawCommand::Stroke {
+            rect,
+            color,
+            width,
+            radii,
+            transform,
+            index_range: start_index..end_index,
+        });
+    }
+
+    /// Fill a rectangle with a linear gradient
+    ///
+    /// The gradient's `start` and `end` points are relative to `rect`:
+    /// `(0, 0)` is its top-left corner and `(1, 1)` its bottom-right, so
+    /// [`LinearGradient::vertical`] runs from top to bottom. Colors before
+    /// the first stop and after the last one are extended.
+    pub fn add_gradient_rect(
+        &mut self,
+        rect: Rect,
+        gradient: &LinearGradient,
+        radii: BorderRadius,
+        transform: Transform,
+    ) {
+        let radii = clamp_radii(rect, radii);
+        let outline = rounded_rect_outline(rect, radii, corner_segments(radii));
+
+        let start_index = self.indices.len() as u32;
+        for band in gradient_bands(rect, gradient, &outline) {
+            self.batch_convex_polygon(&band, transform);
+        }
+        let end_index = self.indices.len() as u32;
+
+        self.commands.push(DrawCommand::GradientRect {
+            rect,
+            gradient: gradient.clone(),
+            radii,
+            transform,
+            index_range: start_index..end_index,
+        });
+    }
+
+    /// Fill a rectangle with `background`
+    ///
+    /// Gradients follow [`add_gradient_rect`](Self::add_gradient_rect).
+    /// Images are stretched over `rect` like [`add_image`](Self::add_image),
+    /// which draws them untransformed and with square corners.
+    pub fn add_background(
+        &mut self,
+        rect: Rect,
+        background: &Background,
+        radii: BorderRadius,
+        transform: Transform,
+    ) {
+        match background {
+            Background::Solid(color) => self.add_rounded_rect_each(rect, *color, radii, transform),
+            Background::Gradient(gradient) => {
+                self.add_gradient_rect(rect, gradient, radii, transform)
+            }
+            Background::Image {
+                id,
+                data,
+                width,
+                height,
+            } => self.add_image(*id, data.clone(), *width, *height, rect, Color::WHITE),
+        }
     }
 
     /// Add text to the batch
@@ -201,21 +628,243 @@ impl RenderBatch {
             font_size,
             letter_spacing,
             align,
+            fill: None,
         };
         self.commands.push(command);
     }
 
+    /// Add aligned text painted with `fill` instead of a single color
+    ///
+    /// Backends without gradient text draw it in the color of the first stop.
+    pub fn add_gradient_text(
+        &mut self,
+        text: String,
+        position: (f32, f32),
+        fill: TextFill,
+        font_size: f32,
+        letter_spacing: f32,
+        align: TextAlign,
+    ) {
+        let color = fill
+            .gradient
+            .stops
+            .first()
+            .map_or(Color::TRANSPARENT, |stop| stop.color);
+        self.commands.push(DrawCommand::Text {
+            text,
+            position,
+            color,
+            font_size,
+            letter_spacing,
+            align,
+            fill: Some(fill),
+        });
+    }
+
     /// Add a rectangle to the overlay layer (drawn last)
     pub fn add_overlay_rect(&mut self, rect: Rect, color: Color, transform: Transform) {
+        let start_index = self.indices.len() as u32;
+        self.batch_rect(rect, color, transform);
+        let end_index = self.indices.len() as u32;
+
         let command = DrawCommand::Rect {
             rect,
             color,
             transform,
-            index_range: 0..0,
+            index_range: start_index..end_index,
         };
         self.overlay_commands.push(command);
     }
 
+    /// Append the contents of another batch to the overlay layer (drawn last)
+    ///
+    /// Geometry and index ranges are rebased so the commands keep pointing at
+    /// the right vertices once merged into this batch.
+    pub fn append_overlay(&mut self, other: RenderBatch) {
+        let commands =
+            self.merge_geometry(&other, other.commands.iter().chain(&other.overlay_commands));
+        self.overlay_commands.extend(commands);
+    }
+
+    /// Append a copy of another batch, drawn after everything added so far
+    ///
+    /// Its regular commands join this batch's and its overlay commands the
+    /// overlay layer, so a recorded sub-tree can be replayed on later frames
+    /// without rendering it again.
+    pub fn append(&mut self, other: &RenderBatch) {
+        let mut commands =
+            self.merge_geometry(other, other.commands.iter().chain(&other.overlay_commands));
+        let overlays = commands.split_off(other.commands.len());
+        self.commands.extend(commands);
+        self.overlay_commands.extend(overlays);
+    }
+
+    /// Add a popup (dropdown list, menu, tooltip) to the overlay layer
+    ///
+    /// Overlays bypass the clip rectangles of the widgets that draw them and
+    /// are drawn above all regular commands. Among overlays pushed this way,
+    /// a higher `order` is drawn on top; pass the order taken when the popup
+    /// opened so the most recently opened popup wins regardless of where its
+    /// owner sits in the tree. Equal orders keep the order they were pushed
+    /// in. Commands added with the `add_overlay_*` methods stay where they
+    /// were added, above the overlays pushed before them.
+    pub fn push_overlay(&mut self, order: u64, overlay: RenderBatch) {
+        let commands = self.merge_geometry(
+            &overlay,
+            overlay.commands.iter().chain(&overlay.overlay_commands),
+        );
+        self.insert_overlay(order, commands);
+    }
+
+    /// Add the commands of an overlay opened at `order` to the overlay layer
+    fn insert_overlay(&mut self, order: u64, commands: Vec<DrawCommand>) {
+        // Segments keep pushes in order, to be sorted in when merged
+        if let Some(pushed) = &mut self.segment_overlays {
+            let start = self.overlay_commands.len();
+            self.overlay_commands.extend(commands);
+            pushed.push(SegmentOverlay {
+                order,
+                commands: start..self.overlay_commands.len(),
+            });
+            return;
+        }
+
+        let count = commands.len();
+
+        let layer = self
+            .overlay_layers
+            .partition_point(|&(layer_order, _)| layer_order <= order);
+        let start = self
+            .overlay_layers
+            .get(layer)
+            .map_or(self.overlay_commands.len(), |&(_, start)| start);
+
+        self.overlay_commands.splice(start..start, commands);
+        for (_, later_start) in &mut self.overlay_layers[layer..] {
+            *later_start += count;
+        }
+        self.overlay_layers.insert(layer, (order, start));
+    }
+
+    /// Copy another batch's geometry into this one, returning `commands`
+    /// (taken from `other`) rebased onto the merged index buffer
+    fn merge_geometry<'a>(
+        &mut self,
+        other: &RenderBatch,
+        commands: impl Iterator<Item = &'a DrawCommand>,
+    ) -> Vec<DrawCommand> {
+        let vertex_offset = self.vertex_count;
+        let index_offset = self.indices.len() as u32;
+
+        self.vertices.extend_from_slice(&other.vertices);
+        self.indices
+            .extend(other.indices.iter().map(|&index| index + vertex_offset));
+        self.vertex_count += other.vertex_count;
+
+        let rebase = |range: Range<u32>| range.start + index_offset..range.end + index_offset;
+        commands
+            .cloned()
+            .map(|command| match command {
+                DrawCommand::Rect {
+                    rect,
+                    color,
+                    transform,
+                    index_range,
+                } => DrawCommand::Rect {
+                    rect,
+                    color,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::TexturedQuad {
+                    rect,
+                    texture_id,
+                    uv_rect,
+                    color,
+                    transform,
+                    index_range,
+                } => DrawCommand::TexturedQuad {
+                    rect,
+                    texture_id,
+                    uv_rect,
+                    color,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::Circle {
+                    center,
+                    radius,
+                    color,
+                    segments,
+                    transform,
+                    index_range,
+                } => DrawCommand::Circle {
+                    center,
+                    radius,
+                    color,
+                    segments,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::Line {
+                    start,
+                    end,
+                    color,
+                    thickness,
+                    index_range,
+                } => DrawCommand::Line {
+                    start,
+                    end,
+                    color,
+                    thickness,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::RoundedRect {
+                    rect,
+                    color,
+                    radii,
+                    transform,
+                    index_range,
+                } => DrawCommand::RoundedRect {
+                    rect,
+                    color,
+                    radii,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::Stroke {
+                    rect,
+                    color,
+                    width,
+                    radii,
+                    transform,
+                    index_range,
+                } => DrawCommand::Stroke {
+                    rect,
+                    color,
+                    width,
+                    radii,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                DrawCommand::GradientRect {
+                    rect,
+                    gradient,
+                    radii,
+                    transform,
+                    index_range,
+                } => DrawCommand::GradientRect {
+                    rect,
+                    gradient,
+                    radii,
+                    transform,
+                    index_range: rebase(index_range),
+                },
+                other => other,
+            })
+            .collect()
+    }
+
     /// Add aligned text to the overlay layer (drawn last)
     pub fn add_overlay_text_aligned(
         &mut self,
@@ -233,11 +882,17 @@ impl RenderBatch {
             font_size,
             letter_spacing,
             align,
+            fill: None,
         };
         self.overlay_commands.push(command);
     }
 
     /// Add an image to the batch
+    ///
+    /// `data` holds `width * height` RGBA8 pixels and is uploaded once per
+    /// `id`, so reuse the id for the same image across frames. The image is
+    /// stretched to fill `rect` and tinted by `color` (use [`Color::WHITE`]
+    /// for no tint).
     pub fn add_image(
         &mut self,
         id: u64,
@@ -283,6 +938,27 @@ impl RenderBatch {
         self.commands.push(command);
     }
 
+    /// Add a layer showing `content` in `rect`
+    ///
+    /// `id` identifies the layer across frames, take one from
+    /// [`next_layer_id`]. Backends that render to the GPU keep a texture per
+    /// id with `content` drawn into it, and render it again only when the
+    /// layer is added with other content or the scale factor changes; others
+    /// draw the content like any other commands. Overlays of the content are
+    /// added to this batch's overlay layer.
+    pub fn add_layer(&mut self, id: u64, rect: Rect, content: &LayerContent) {
+        if !content.overlays.overlay_commands.is_empty() {
+            let overlays = &content.overlays;
+            let commands = self.merge_geometry(overlays, overlays.overlay_commands.iter());
+            self.overlay_commands.extend(commands);
+        }
+        self.commands.push(DrawCommand::Layer {
+            id,
+            rect,
+            content: content.clone(),
+        });
+    }
+
     /// Add a circle to the batch
     pub fn add_circle(
         &mut self,
@@ -573,6 +1249,58 @@ impl RenderBatch {
         }
     }
 
+    /// Batch a convex polygon as a triangle fan with per-vertex colors
+    fn batch_convex_polygon(&mut self, points: &[([f32; 2], Color)], transform: Transform) {
+        if points.len() < 3 {
+            return;
+        }
+
+        let base = self.vertex_count;
+        for &(position, color) in points {
+            self.vertices.push(Vertex::solid(
+                self.apply_transform(position, transform),
+                color.to_array(),
+            ));
+        }
+        for i in 1..points.len() as u16 - 1 {
+            self.indices
+                .extend_from_slice(&[base, base + i, base + i + 1]);
+        }
+
+        self.vertex_count += points.len() as u16;
+    }
+
+    /// Batch the ring between two outlines with the same number of points
+    fn batch_ring(
+        &mut self,
+        outer: &[[f32; 2]],
+        inner: &[[f32; 2]],
+        color: Color,
+        transform: Transform,
+    ) {
+        debug_assert_eq!(outer.len(), inner.len());
+        let count = outer.len() as u16;
+        if count < 3 {
+            return;
+        }
+
+        let base = self.vertex_count;
+        for &position in outer.iter().chain(inner) {
+            self.vertices.push(Vertex::solid(
+                self.apply_transform(position, transform),
+                color.to_array(),
+            ));
+        }
+        for i in 0..count {
+            let next = (i + 1) % count;
+            let (o0, o1) = (base + i, base + next);
+            let (i0, i1) = (base + count + i, base + count + next);
+            self.indices.extend_from_slice(&[o0, o1, i1, o0, i1, i0]);
+        }
+
+        self.vertex_count += 2 * count;
+    }
+
     /// Batch a line as a rectangle
     fn batch_line(&mut self, start: (f32, f32), end: (f32, f32), color: Color, thickness: f32) {
         let (x1, y1) = start;
@@ -684,11 +1412,206 @@ impl Default for RenderBatch {
     }
 }
 
+/// Scale corner radii down so adjacent corners fit along every side
+fn clamp_radii(rect: Rect, radii: BorderRadius) -> BorderRadius {
+    let radii = BorderRadius::new(
+        radii.top_left.max(0.0),
+        radii.top_right.max(0.0),
+        radii.bottom_right.max(0.0),
+        radii.bottom_left.max(0.0),
+    );
+    let sides = [
+        (radii.top_left + radii.top_right, rect.width),
+        (radii.bottom_left + radii.bottom_right, rect.width),
+        (radii.top_left + radii.bottom_left, rect.height),
+        (radii.top_right + radii.bottom_right, rect.height),
+    ];
+    let scale = sides
+        .iter()
+        .filter(|(sum, _)| *sum > 0.0)
+        .map(|(sum, side)| (side.max(0.0) / sum).min(1.0))
+        .fold(1.0f32, f32::min);
+
+    BorderRadius::new(
+        radii.top_left * scale,
+        radii.top_right * scale,
+        radii.bottom_right * scale,
+        radii.bottom_left * scale,
+    )
+}
+
+/// Number of segments approximating each corner arc, in outline order
+fn corner_segments(radii: BorderRadius) -> [u32; 4] {
+    [
+        radii.top_left,
+        radii.top_right,
+        radii.bottom_right,
+        radii.bottom_left,
+    ]
+    .map(|radius| {
+        if radius > 0.0 {
+            ((radius / 2.0).ceil() as u32).clamp(2, 16)
+        } else {
+            0
+        }
+    })
+}
+
+/// Outline of a rounded rectangle, clockwise from the top-left corner
+///
+/// Each corner contributes `segments + 1` points, so outlines built with the
+/// same segment counts have the same number of points.
+fn rounded_rect_outline(rect: Rect, radii: BorderRadius, segments: [u32; 4]) -> Vec<[f32; 2]> {
+    let (left, top) = (rect.x, rect.y);
+    let (right, bottom) = (rect.x + rect.width, rect.y + rect.height);
+    // Corner centers and the angle each arc starts at (y grows downwards)
+    let corners = [
+        (
+            left + radii.top_left,
+            top + radii.top_left,
+            radii.top_left,
+            PI,
+        ),
+        (
+            right - radii.top_right,
+            top + radii.top_right,
+            radii.top_right,
+            1.5 * PI,
+        ),
+        (
+            right - radii.bottom_right,
+            bottom - radii.bottom_right,
+            radii.bottom_right,
+            0.0,
+        ),
+        (
+            left + radii.bottom_left,
+            bottom - radii.bottom_left,
+            radii.bottom_left,
+            FRAC_PI_2,
+        ),
+    ];
+
+    let mut points = Vec::with_capacity(segments.iter().map(|s| *s as usize + 1).sum());
+    for ((cx, cy, radius, start), segments) in corners.into_iter().zip(segments) {
+        for i in 0..=segments {
+            let angle = if segments == 0 {
+                start
+            } else {
+                start + FRAC_PI_2 * i as f32 / segments as f32
+            };
+            points.push([cx + radius * angle.cos(), cy + radius * angle.sin()]);
+        }
+    }
+    points
+}
+
+/// Split a convex outline into bands between gradient stops
+///
+/// Vertex colors are exact at every band edge, so interpolating them across
+/// each band reproduces the gradient.
+fn gradient_bands(
+    rect: Rect,
+    gradient: &LinearGradient,
+    outline: &[[f32; 2]],
+) -> Vec<Vec<([f32; 2], Color)>> {
+    let mut stops = gradient.stops.clone();
+    stops.sort_by(|a, b| a.position.total_cmp(&b.position));
+    let Some(first) = stops.first() else {
+        return Vec::new();
+    };
+    let Some(offset) = gradient_offset(rect, gradient) else {
+        return vec![outline.iter().map(|&p| (p, first.color)).collect()];
+    };
+
+    let mut edges = vec![f32::NEG_INFINITY];
+    edges.extend(stops.iter().map(|stop| stop.position));
+    edges.push(f32::INFINITY);
+
+    edges
+        .windows(2)
+        .filter(|edge| edge[0] < edge[1])
+        .filter_map(|edge| {
+            let band = clip_polygon(outline, |p| offset(p) - edge[0]);
+            let band = clip_polygon(&band, |p| edge[1] - offset(p));
+            (band.len() >= 3).then(|| {
+                band.into_iter()
+                    .map(|p| (p, stop_color(&stops, offset(p))))
+                    .collect()
+            })
+        })
+        .collect()
+}
+
+/// Position of points along `gradient` laid over `rect`, 0 at its `start`
+/// and 1 at its `end`, or `None` if the gradient or `rect` has no extent
+fn gradient_offset(rect: Rect, gradient: &LinearGradient) -> Option<impl Fn([f32; 2]) -> f32> {
+    let (dx, dy) = (
+        gradient.end.x - gradient.start.x,
+        gradient.end.y - gradient.start.y,
+    );
+    let length_squared = dx * dx + dy * dy;
+    if length_squared <= f32::EPSILON || rect.width <= 0.0 || rect.height <= 0.0 {
+        return None;
+    }
+    let start = gradient.start;
+    Some(move |p: [f32; 2]| {
+        let u = (p[0] - rect.x) / rect.width - start.x;
+        let v = (p[1] - rect.y) / rect.height - start.y;
+        (u * dx + v * dy) / length_squared
+    })
+}
+
+/// Color at offset `t` of non-empty `stops` sorted by position, extending
+/// the first and last colors
+fn stop_color(stops: &[GradientStop], t: f32) -> Color {
+    let (first, last) = (stops[0], stops[stops.len() - 1]);
+    if t <= first.position {
+        return first.color;
+    }
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t <= b.position {
+            let span = b.position - a.position;
+            let f = if span > 0.0 {
+                (t - a.position) / span
+            } else {
+                1.0
+            };
+            return Color::rgba(
+                a.color.r + (b.color.r - a.color.r) * f,
+                a.color.g + (b.color.g - a.color.g) * f,
+                a.color.b + (b.color.b - a.color.b) * f,
+                a.color.a + (b.color.a - a.color.a) * f,
+            );
+        }
+    }
+    last.color
+}
+
+/// Clip a convex polygon to the half-plane where `distance` is non-negative
+fn clip_polygon(points: &[[f32; 2]], distance: impl Fn([f32; 2]) -> f32) -> Vec<[f32; 2]> {
+    let mut clipped = Vec::with_capacity(points.len() + 2);
+    for (i, &p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        let (dp, dq) = (distance(p), distance(q));
+        if dp >= 0.0 {
+            clipped.push(p);
+        }
+        // Points on the boundary are kept as is, only strict crossings add one
+        if (dp > 0.0 && dq < 0.0) || (dp < 0.0 && dq > 0.0) {
+            let t = dp / (dp - dq);
+            clipped.push([p[0] + (q[0] - p[0]) * t, p[1] + (q[1] - p[1]) * t]);
+        }
+    }
+    clipped
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use glam::Vec2;
-    use strato_core::types::Color;
+    use strato_core::types::{Color, GradientStop};
 
     #[test]
     fn test_batch_rect() {
@@ -721,6 +1644,331 @@ mod tests {
         assert_eq!(batch.draw_call_count(), 1);
     }
 
+    #[test]
+    fn test_append_overlay_rebases_indices() {
+        let mut batch = RenderBatch::new();
+        batch.add_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+
+        let mut overlay = RenderBatch::new();
+        overlay.add_rect(
+            Rect::new(5.0, 5.0, 10.0, 10.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+        batch.append_overlay(overlay);
+
+        assert_eq!(batch.vertex_count(), 8);
+        assert_eq!(batch.overlay_commands.len(), 1);
+        match &batch.overlay_commands[0] {
+            DrawCommand::Rect { index_range, .. } => {
+                assert_eq!(*index_range, 6..12);
+                assert_eq!(batch.indices[index_range.start as usize], 4);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_append_copies_commands_and_overlays() {
+        let mut recorded = RenderBatch::new();
+        recorded.add_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+        recorded.add_overlay_rect(
+            Rect::new(0.0, 0.0, 20.0, 20.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+
+        let mut batch = RenderBatch::new();
+        batch.add_circle((0.0, 0.0), 4.0, Color::WHITE, 8, Transform::default());
+        let circle_vertices = batch.vertex_count();
+        batch.append(&recorded);
+        batch.append(&recorded);
+
+        assert_eq!(batch.commands.len(), 3);
+        assert_eq!(batch.overlay_commands.len(), 2);
+        assert_eq!(batch.vertex_count(), circle_vertices + 16);
+        match &batch.commands[2] {
+            DrawCommand::Rect { index_range, .. } => {
+                assert_eq!(
+                    batch.indices[index_range.start as usize] as usize,
+                    circle_vertices + 8
+                );
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_layers_draw_their_overlays_each_frame() {
+        let mut recorded = RenderBatch::new();
+        recorded.add_rect(
+            Rect::new(0.0, 0.0, 10.0, 10.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+        recorded.add_overlay_rect(
+            Rect::new(0.0, 0.0, 20.0, 20.0),
+            Color::RED,
+            Transform::default(),
+        );
+        let content = LayerContent::new(recorded);
+        assert_eq!(content.batch().commands.len(), 1);
+        assert!(content.batch().overlay_commands.is_empty());
+
+        let id = next_layer_id();
+        assert_ne!(next_layer_id(), id);
+        let mut batch = RenderBatch::new();
+        batch.add_circle((0.0, 0.0), 4.0, Color::WHITE, 8, Transform::default());
+        batch.add_layer(id, Rect::new(0.0, 0.0, 10.0, 10.0), &content);
+
+        match &batch.commands[1] {
+            DrawCommand::Layer {
+                id: layer,
+                content: drawn,
+                ..
+            } => {
+                assert_eq!(*layer, id);
+                assert!(drawn.same_as(&content));
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        match &batch.overlay_commands[..] {
+            [DrawCommand::Rect {
+                color, index_range, ..
+            }] => {
+                assert_eq!(*color, Color::RED);
+                let first = batch.indices[index_range.start as usize] as usize;
+                assert_eq!(batch.vertices[first].position, [0.0, 0.0]);
+                assert!(first >= 8, "the overlay should follow the circle");
+            }
+            other => panic!("unexpected overlays {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_layers_survive_serialization() {
+        let mut recorded = RenderBatch::new();
+        recorded.add_rect(
+            Rect::new(1.0, 2.0, 3.0, 4.0),
+            Color::WHITE,
+            Transform::default(),
+        );
+        let mut batch = RenderBatch::new();
+        batch.add_layer(
+            1,
+            Rect::new(0.0, 0.0, 8.0, 8.0),
+            &LayerContent::new(recorded),
+        );
+
+        let json = serde_json::to_string(&batch.commands).unwrap();
+        let commands: Vec<DrawCommand> = serde_json::from_str(&json).unwrap();
+        match &commands[..] {
+            [DrawCommand::Layer { content, .. }] => {
+                assert_eq!(content.batch().vertices.len(), 4);
+                assert!(matches!(
+                    content.batch().commands[..],
+                    [DrawCommand::Rect { rect, .. }] if rect == Rect::new(1.0, 2.0, 3.0, 4.0)
+                ));
+            }
+            other => panic!("unexpected commands {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_push_overlay_orders_by_open_order() {
+        let popup = |x: f32| {
+            let mut overlay = RenderBatch::new();
+            overlay.add_rect(
+                Rect::new(x, 0.0, 10.0, 10.0),
+                Color::WHITE,
+                Transform::default(),
+            );
+            overlay
+        };
+
+        // Drawn in tree order, but the first popup was opened last
+        let mut batch = RenderBatch::new();
+        batch.push_overlay(3, popup(0.0));
+        batch.push_overlay(1, popup(1.0));
+        batch.push_overlay(2, popup(2.0));
+        batch.push_overlay(1, popup(3.0));
+
+        let order: Vec<f32> = batch
+            .overlay_commands
+            .iter()
+            .map(|command| match command {
+                DrawCommand::Rect {
+                    rect, index_range, ..
+                } => {
+                    // Geometry still matches the command after reordering
+                    let first = batch.indices[index_range.start as usize] as usize;
+                    assert_eq!(batch.vertices[first].position[0], rect.x);
+                    rect.x
+                }
+                other => panic!("unexpected command {:?}", other),
+            })
+            .collect();
+        assert_eq!(order, vec![1.0, 3.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_merged_segments_match_serial_drawing() {
+        let rect = |batch: &mut RenderBatch, x: f32| {
+            batch.add_rect(
+                Rect::new(x, 0.0, 10.0, 10.0),
+                Color::WHITE,
+                Transform::default(),
+            );
+        };
+        let popup = |x: f32| {
+            let mut overlay = RenderBatch::new();
+            rect(&mut overlay, x);
+            overlay
+        };
+        // A subtree with popups opened before and after the page's, and a
+        // command added to the overlay layer between them
+        let subtree = |batch: &mut RenderBatch, x: f32| {
+            rect(batch, x);
+            batch.push_overlay(1, popup(x + 1.0));
+            batch.add_overlay_rect(
+                Rect::new(x + 2.0, 0.0, 1.0, 1.0),
+                Color::BLACK,
+                Transform::default(),
+            );
+            batch.push_overlay(3, popup(x + 3.0));
+            rect(batch, x + 4.0);
+        };
+        let page = || {
+            let mut batch = RenderBatch::new();
+            rect(&mut batch, -1.0);
+            batch.push_overlay(2, popup(-2.0));
+            batch
+        };
+
+        let mut serial = page();
+        subtree(&mut serial, 10.0);
+        subtree(&mut serial, 20.0);
+
+        let mut merged = page();
+        let segments: Vec<RenderBatch> = [10.0, 20.0]
+            .into_iter()
+            .map(|x| {
+                let mut segment = merged.segment();
+                subtree(&mut segment, x);
+                segment
+            })
+            .collect();
+        for segment in segments {
+            merged.merge(segment);
+        }
+
+        let bytes = |batch: &RenderBatch| {
+            (
+                bytemuck::cast_slice::<Vertex, u8>(&batch.vertices).to_vec(),
+                batch.indices.clone(),
+                format!("{:?} {:?}", batch.commands, batch.overlay_commands),
+            )
+        };
+        assert_eq!(bytes(&merged), bytes(&serial));
+    }
+
+    #[test]
+    fn test_rounded_rect_each_clamps_radii() {
+        let mut batch = RenderBatch::new();
+        let rect = Rect::new(10.0, 10.0, 40.0, 20.0);
+        // The left corners ask for more than the 20px height allows
+        batch.add_rounded_rect_each(
+            rect,
+            Color::WHITE,
+            BorderRadius::new(30.0, 0.0, 4.0, 30.0),
+            Transform::default(),
+        );
+
+        match &batch.commands[0] {
+            DrawCommand::RoundedRect { radii, .. } => {
+                assert_eq!(radii.top_left + radii.bottom_left, 20.0);
+                assert_eq!(radii.top_right, 0.0);
+            }
+            other => panic!("unexpected command {:?}", other),
+        }
+        assert!(batch.triangle_count() > 2);
+        for vertex in &batch.vertices {
+            let [x, y] = vertex.position;
+            assert!((10.0 - 1e-3..=50.0 + 1e-3).contains(&x));
+            assert!((10.0 - 1e-3..=30.0 + 1e-3).contains(&y));
+        }
+    }
+
+    #[test]
+    fn test_stroke_lies_inside_rect() {
+        let mut batch = RenderBatch::new();
+        let rect = Rect::new(0.0, 0.0, 100.0, 50.0);
+        batch.add_stroke(
+            rect,
+            Color::BLACK,
+            2.0,
+            BorderRadius::default(),
+            Transform::translate(5.0, 5.0),
+        );
+
+        // Four outer and four inner corners joined by eight triangles
+        assert_eq!(batch.vertex_count(), 8);
+        assert_eq!(batch.triangle_count(), 8);
+        let inner: Vec<[f32; 2]> = batch.vertices[4..].iter().map(|v| v.position).collect();
+        assert_eq!(
+            inner,
+            vec![[7.0, 7.0], [103.0, 7.0], [103.0, 53.0], [7.0, 53.0]]
+        );
+    }
+
+    #[test]
+    fn test_gradient_rect_interpolates_stops() {
+        let red = Color::rgba(1.0, 0.0, 0.0, 1.0);
+        let blue = Color::rgba(0.0, 0.0, 1.0, 1.0);
+        let gradient = LinearGradient::vertical(vec![
+            GradientStop {
+                color: red,
+                position: 0.0,
+            },
+            GradientStop {
+                color: Color::WHITE,
+                position: 0.5,
+            },
+            GradientStop {
+                color: blue,
+                position: 1.0,
+            },
+        ]);
+
+        let mut batch = RenderBatch::new();
+        batch.add_gradient_rect(
+            Rect::new(0.0, 0.0, 10.0, 100.0),
+            &gradient,
+            BorderRadius::default(),
+            Transform::default(),
+        );
+
+        // One band per pair of stops, split at the middle stop
+        assert_eq!(batch.triangle_count(), 4);
+        for vertex in &batch.vertices {
+            let expected = match vertex.position[1] {
+                0.0 => red,
+                50.0 => Color::WHITE,
+                100.0 => blue,
+                y => panic!("unexpected vertex at y = {}", y),
+            };
+            assert_eq!(vertex.color, expected.to_array());
+        }
+    }
+
     #[test]
     fn test_clear_batch() {
         let mut batch = RenderBatch::new();
@@ -123,12 +123,26 @@ pub struct BufferUsageStats {
     pub fragmentation_ratio: f32,
 }
 
+/// Frames the GPU may still be reading while the CPU records a new one
+pub const FRAMES_IN_FLIGHT: usize = 3;
+
+/// A pooled buffer owned by one frame of a [`DynamicBuffer`]
+type FrameBuffer = (ResourceHandle, Arc<Buffer>);
+
 /// Dynamic buffer that can grow and shrink
+///
+/// Keeps one buffer per frame in flight and rotates through them, so the
+/// data written for a frame never lands in a buffer an earlier frame is still
+/// reading. Buffers come from, and are returned to, the [`BufferPool`].
 pub struct DynamicBuffer {
-    _device: Arc<ManagedDevice>,
+    device: Arc<ManagedDevice>,
     buffer_pool: Arc<BufferPool>,
 
-    // Current buffer
+    // One buffer per frame in flight
+    frames: RwLock<Vec<Option<FrameBuffer>>>,
+    frame_index: AtomicUsize,
+
+    // Buffer of the current frame
     current_buffer: RwLock<Option<Arc<Buffer>>>,
     current_size: AtomicU64,
     used_size: AtomicU64,
@@ -261,8 +275,8 @@ impl BufferPool {
     pub fn allocate(&self, config: &BufferConfig) -> Result<ResourceHandle> {
         let start_time = Instant::now();
 
-        // Align size to requirements
-        let aligned_size = self.align_size(config.size);
+        // Round up to the size bucket so returned buffers fit later requests
+        let aligned_size = self.bucket_size(config.size);
         let pool_key = (aligned_size, config.usage);
 
         // Try to get from pool first
@@ -362,7 +376,7 @@ impl BufferPool {
             .remove(&handle)
             .context("Buffer allocation not found")?;
 
-        let pool_key = (allocation.size, allocation.buffer.usage());
+        let pool_key = (allocation.buffer.size(), allocation.buffer.usage());
 
         // Return to pool if there's space
         let mut pools = self.pools.write();
@@ -405,6 +419,19 @@ impl BufferPool {
         (size + alignment - 1) & !(alignment - 1)
     }
 
+    /// Size bucket for a request: aligned, then rounded up to a power of two
+    fn bucket_size(&self, size: u64) -> u64 {
+        self.align_size(size.max(1)).next_power_of_two()
+    }
+
+    /// Pool hits and misses since creation
+    pub fn hit_stats(&self) -> (u64, u64) {
+        (
+            self.pool_hits.load(Ordering::Relaxed),
+            self.pool_misses.load(Ordering::Relaxed),
+        )
+    }
+
     /// Update usage pattern statistics
     fn update_usage_stats(&self, pattern: BufferUsagePattern, size: u64) {
         let mut stats = self.usage_patterns.write();
@@ -466,8 +493,10 @@ impl DynamicBuffer {
         config: BufferConfig,
     ) -> Self {
         Self {
-            _device: device,
+            device,
             buffer_pool,
+            frames: RwLock::new(vec![None; FRAMES_IN_FLIGHT]),
+            frame_index: AtomicUsize::new(0),
             current_buffer: RwLock::new(None),
             current_size: AtomicU64::new(0),
             used_size: AtomicU64::new(0),
@@ -479,21 +508,54 @@ impl DynamicBuffer {
         }
     }
 
+    /// Move to the buffer of the next frame
+    ///
+    /// That buffer was last used [`FRAMES_IN_FLIGHT`] frames ago, so the GPU
+    /// is done with it.
+    pub fn begin_frame(&self) {
+        let index = (self.frame_index.load(Ordering::Relaxed) + 1) % FRAMES_IN_FLIGHT;
+        self.frame_index.store(index, Ordering::Relaxed);
+
+        let buffer = self.frames.read()[index]
+            .as_ref()
+            .map(|(_, buffer)| buffer.clone());
+        self.current_size.store(
+            buffer.as_ref().map_or(0, |buffer| buffer.size()),
+            Ordering::Relaxed,
+        );
+        *self.current_buffer.write() = buffer;
+    }
+
+    /// Upload `data` into the current frame's buffer, growing or shrinking it
+    ///
+    /// `data.len()` must be a multiple of 4 bytes.
+    pub fn write(&self, data: &[u8]) -> Result<Arc<Buffer>> {
+        let size = data.len() as u64;
+        self.set_used_size(size);
+        self.ensure_capacity(size)?;
+
+        let buffer = self
+            .get_buffer()
+            .context("Dynamic buffer has no backing buffer")?;
+        self.device.queue.write_buffer(&buffer, 0, data);
+        Ok(buffer)
+    }
+
     /// Ensure buffer has at least the specified capacity
     pub fn ensure_capacity(&self, required_size: u64) -> Result<()> {
         let current_size = self.current_size.load(Ordering::Relaxed);
 
-        if required_size <= current_size {
+        if required_size <= current_size && current_size > 0 {
             return Ok(());
         }
 
         let new_size = (required_size as f32 * self.growth_factor) as u64;
-        self.resize(new_size)?;
+        self.resize(new_size.max(self.config.size))?;
 
         Ok(())
     }
 
-    /// Resize the buffer
+    /// Replace the current frame's buffer, returning the old one to the pool
     fn resize(&self, new_size: u64) -> Result<()> {
         let mut config = self.config.clone();
         config.size = new_size;
@@ -504,11 +566,16 @@ impl DynamicBuffer {
             .get_allocation(handle)
             .context("Failed to get buffer allocation")?;
 
-        // Copy existing data if needed
-        if let Some(_old_buffer) = self.current_buffer.read().as_ref() {}
+        // The previous contents belong to this frame and are being replaced
+        let index = self.frame_index.load(Ordering::Relaxed);
+        let previous = self.frames.write()[index].replace((handle, allocation.clone()));
+        if let Some((previous, _)) = previous {
+            self.buffer_pool.deallocate(previous)?;
+        }
 
+        self.current_size
+            .store(allocation.size(), Ordering::Relaxed);
         *self.current_buffer.write() = Some(allocation);
-        self.current_size.store(new_size, Ordering::Relaxed);
         self.resize_count.fetch_add(1, Ordering::Relaxed);
         *self.last_resize.write() = Some(Instant::now());
 
@@ -539,6 +606,17 @@ impl DynamicBuffer {
             }
         }
     }
+
+    /// Return every frame's buffer to the pool
+    pub fn release(&self) {
+        for (handle, _) in self.frames.write().iter_mut().filter_map(Option::take) {
+            if let Err(e) = self.buffer_pool.deallocate(handle) {
+                warn!("Failed to release dynamic buffer: {}", e);
+            }
+        }
+        *self.current_buffer.write() = None;
+        self.current_size.store(0, Ordering::Relaxed);
+    }
 }
 
 impl RingBuffer {
@@ -724,6 +802,45 @@ impl BufferManager {
         Ok(handle)
     }
 
+    /// Advance every dynamic buffer to the next frame's buffer
+    pub fn begin_frame(&self) {
+        for dynamic in self.dynamic_buffers.read().values() {
+            dynamic.begin_frame();
+        }
+    }
+
+    /// Upload `data` into a dynamic buffer for the current frame
+    pub fn write_dynamic(&self, handle: ResourceHandle, data: &[u8]) -> Result<Arc<Buffer>> {
+        let dynamic = self
+            .dynamic_buffers
+            .read()
+            .get(&handle)
+            .cloned()
+            .context("Dynamic buffer not found")?;
+        dynamic.write(data)
+    }
+
+    /// Release a dynamic buffer, returning its buffers to the pool
+    pub fn destroy_dynamic_buffer(&self, handle: ResourceHandle) {
+        if let Some(dynamic) = self.dynamic_buffers.write().remove(&handle) {
+            dynamic.release();
+        }
+    }
+
+    /// Pool hits and misses summed over every pool
+    pub fn pool_hit_stats(&self) -> (u64, u64) {
+        [
+            &self.vertex_pool,
+            &self.index_pool,
+            &self.uniform_pool,
+            &self.storage_pool,
+            &self.staging_pool,
+        ]
+        .iter()
+        .map(|pool| pool.hit_stats())
+        .fold((0, 0), |(hits, misses), (h, m)| (hits + h, misses + m))
+    }
+
     /// Get pool for buffer usage
     fn get_pool_for_usage(&self, usage: BufferUsages) -> Arc<BufferPool> {
         if usage.contains(BufferUsages::VERTEX) {
@@ -849,3 +966,54 @@ impl BufferManager {
         let _ = self.defragment();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::device::DeviceManager;
+
+    #[tokio::test]
+    async fn test_dynamic_buffer_triple_buffering() {
+        let manager = DeviceManager::new(None, None).await.unwrap();
+        let device = manager.initialize_device().await.unwrap();
+        let memory_manager = Arc::new(Mutex::new(MemoryManager::new(device.clone())));
+        let pool = Arc::new(BufferPool::new(device.clone(), memory_manager));
+        let dynamic = DynamicBuffer::new(
+            device.clone(),
+            pool.clone(),
+            BufferConfig {
+                size: 256,
+                usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+                ..Default::default()
+            },
+        );
+
+        // Six frames, each writing its own data without waiting for the GPU
+        let mut buffers = Vec::new();
+        for frame in 0..6u8 {
+            dynamic.begin_frame();
+            buffers.push(dynamic.write(&[frame; 64]).unwrap());
+        }
+
+        // Frames in flight never share a buffer, later frames reuse them
+        for frame in 0..3 {
+            assert!(!Arc::ptr_eq(&buffers[frame], &buffers[frame + 1]));
+            assert!(!Arc::ptr_eq(&buffers[frame], &buffers[frame + 2]));
+            assert!(Arc::ptr_eq(&buffers[frame], &buffers[frame + 3]));
+        }
+        assert_eq!(pool.hit_stats(), (0, FRAMES_IN_FLIGHT as u64));
+
+        // Growing needs a larger bucket; the small buffer goes back to the pool
+        dynamic.begin_frame();
+        let grown = dynamic.write(&[9; 1024]).unwrap();
+        assert!(grown.size() >= 1024);
+        assert_eq!(pool.hit_stats(), (0, 4));
+
+        // Releasing returns every buffer, and a new dynamic buffer reuses them
+        dynamic.release();
+        let reused = DynamicBuffer::new(device.clone(), pool.clone(), dynamic.config.clone());
+        reused.begin_frame();
+        reused.write(&[1; 64]).unwrap();
+        assert_eq!(pool.hit_stats().0, 1);
+    }
+}
@@ -0,0 +1,276 @@
+//! Frame capture for screenshots
+//!
+//! Copies a rendered texture (the swapchain image or an offscreen target)
+//! into a CPU-readable RGBA buffer without stalling rendering:
+//!
+//! 1. [`FrameCapture::record`] encodes a texture-to-buffer copy into the
+//!    frame's command encoder, so it runs after the frame's draw commands.
+//! 2. Once the frame is submitted, [`FrameCapture::submitted`] starts mapping
+//!    the readback buffer asynchronously.
+//! 3. [`FrameCapture::try_read`] polls the device without blocking and
+//!    returns the image once the GPU has finished, usually a frame later.
+//!
+//! Capturing the swapchain requires the surface to be configured with
+//! `TextureUsages::COPY_SRC` in addition to `RENDER_ATTACHMENT`.
+
+use anyhow::{anyhow, bail, Result};
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::Arc;
+use wgpu::{
+    Buffer, BufferAsyncError, BufferDescriptor, BufferUsages, CommandEncoder, Device, Maintain,
+    MapMode, Texture, TextureFormat, TextureUsages,
+};
+
+/// A captured frame as tightly packed RGBA8 pixels
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageData {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major RGBA8 pixels, `width * 4` bytes per row
+    pub data: Vec<u8>,
+}
+
+impl ImageData {
+    /// Encode the image as PNG
+    pub fn to_png(&self) -> Result<Vec<u8>> {
+        let image = image::RgbaImage::from_raw(self.width, self.height, self.data.clone())
+            .ok_or_else(|| anyhow!("image data does not match {}x{}", self.width, self.height))?;
+        let mut png = Vec::new();
+        image.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+        Ok(png)
+    }
+
+    /// Encode the image as PNG and write it to `path`
+    pub fn save_png(&self, path: impl AsRef<Path>) -> Result<()> {
+        std::fs::write(path, self.to_png()?)?;
+        Ok(())
+    }
+}
+
+/// Bytes per row of a texture copy, padded to wgpu's copy alignment
+pub fn padded_bytes_per_row(width: u32) -> u32 {
+    let unpadded = width * 4;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Strip row padding and convert to RGBA8
+///
+/// `bgra` swaps the red and blue channels for BGRA textures.
+pub fn unpad_rows(padded: &[u8], width: u32, height: u32, bgra: bool) -> Vec<u8> {
+    let row_bytes = (width * 4) as usize;
+    let padded_row_bytes = padded_bytes_per_row(width) as usize;
+
+    let mut data = Vec::with_capacity(row_bytes * height as usize);
+    for row in padded.chunks(padded_row_bytes).take(height as usize) {
+        data.extend_from_slice(&row[..row_bytes]);
+    }
+    if bgra {
+        for pixel in data.chunks_exact_mut(4) {
+            pixel.swap(0, 2);
+        }
+    }
+    data
+}
+
+/// An in-flight copy of a texture into CPU memory
+pub struct FrameCapture {
+    buffer: Buffer,
+    width: u32,
+    height: u32,
+    bgra: bool,
+    mapped: Arc<Mutex<Option<Result<(), BufferAsyncError>>>>,
+    map_requested: bool,
+}
+
+impl FrameCapture {
+    /// Encode a copy of `texture` into a readback buffer
+    ///
+    /// The texture must have `COPY_SRC` usage and an 8-bit RGBA or BGRA format.
+    pub fn record(
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        texture: &Texture,
+    ) -> Result<Self> {
+        if !texture.usage().contains(TextureUsages::COPY_SRC) {
+            bail!("cannot capture a texture without COPY_SRC usage");
+        }
+        let bgra = match texture.format() {
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => false,
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => true,
+            format => bail!("cannot capture texture format {:?}", format),
+        };
+
+        let (width, height) = (texture.width(), texture.height());
+        let bytes_per_row = padded_bytes_per_row(width);
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("Frame Capture Buffer"),
+            size: bytes_per_row as u64 * height as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+
+        Ok(Self {
+            buffer,
+            width,
+            height,
+            bgra,
+            mapped: Arc::new(Mutex::new(None)),
+            map_requested: false,
+        })
+    }
+
+    /// Start mapping the readback buffer; call once the copy has been submitted
+    pub fn submitted(&mut self) {
+        if self.map_requested {
+            return;
+        }
+        self.map_requested = true;
+
+        let mapped = self.mapped.clone();
+        self.buffer
+            .slice(..)
+            .map_async(MapMode::Read, move |result| *mapped.lock() = Some(result));
+    }
+
+    /// Whether [`submitted`](Self::submitted) has been called
+    pub fn is_submitted(&self) -> bool {
+        self.map_requested
+    }
+
+    /// Return the image if the GPU has finished the copy, without blocking
+    pub fn try_read(&self, device: &Device) -> Option<Result<ImageData>> {
+        if !self.map_requested {
+            return None;
+        }
+        device.poll(Maintain::Poll);
+        self.read()
+    }
+
+    /// Block until the copy has finished and return the image
+    pub fn wait(mut self, device: &Device) -> Result<ImageData> {
+        self.submitted();
+        device.poll(Maintain::Wait);
+        self.read()
+            .unwrap_or_else(|| Err(anyhow!("frame capture did not complete")))
+    }
+
+    fn read(&self) -> Option<Result<ImageData>> {
+        let result = self.mapped.lock().take()?;
+        Some(result.map_err(Into::into).map(|()| {
+            let data = {
+                let padded = self.buffer.slice(..).get_mapped_range();
+                unpad_rows(&padded, self.width, self.height, self.bgra)
+            };
+            self.buffer.unmap();
+            ImageData {
+                width: self.width,
+                height: self.height,
+                data,
+            }
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::DeviceManager;
+
+    #[test]
+    fn test_row_padding_is_stripped() {
+        // 3 pixels wide: 12 bytes of data padded to 256 per row
+        assert_eq!(padded_bytes_per_row(3), 256);
+        assert_eq!(padded_bytes_per_row(64), 256);
+        assert_eq!(padded_bytes_per_row(65), 512);
+
+        let mut padded = vec![0u8; 256 * 2];
+        padded[..12].copy_from_slice(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+        padded[256..268].copy_from_slice(&[13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24]);
+
+        let rgba = unpad_rows(&padded, 3, 2, false);
+        assert_eq!(rgba.len(), 24);
+        assert_eq!(&rgba[12..16], &[13, 14, 15, 16]);
+
+        let swapped = unpad_rows(&padded, 3, 2, true);
+        assert_eq!(&swapped[..4], &[3, 2, 1, 4]);
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let image = ImageData {
+            width: 2,
+            height: 1,
+            data: vec![255, 0, 0, 255, 0, 0, 255, 128],
+        };
+        let png = image.to_png().unwrap();
+        let decoded = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(decoded.as_raw(), &image.data);
+    }
+
+    #[tokio::test]
+    async fn test_capture_offscreen_texture() {
+        let dm = DeviceManager::new(wgpu::Backends::all())
+            .await
+            .expect("Failed to create device manager");
+        let device = dm.device();
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Capture Target"),
+            size: wgpu::Extent3d {
+                width: 3,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: TextureFormat::Bgra8Unorm,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Capture Test"),
+        });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: None,
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::RED),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        let capture = FrameCapture::record(device, &mut encoder, &texture).unwrap();
+        dm.queue().submit(std::iter::once(encoder.finish()));
+
+        let image = capture.wait(device).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.data.len(), 3 * 2 * 4);
+        assert!(image
+            .data
+            .chunks_exact(4)
+            .all(|pixel| pixel == [255, 0, 0, 255]));
+    }
+}
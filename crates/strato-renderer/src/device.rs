@@ -8,7 +8,7 @@
 //! - Power management and thermal monitoring
 //! - Vendor-specific optimizations (NVIDIA, AMD, Intel, Apple)
 
-use anyhow::{bail, Result};
+use anyhow::Result;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -17,7 +17,9 @@ use std::sync::{
     Arc,
 };
 use std::time::{Duration, Instant};
-use strato_core::{logging::LogCategory, strato_debug, strato_error_rate_limited, strato_warn};
+use strato_core::{
+    logging::LogCategory, strato_debug, strato_error_rate_limited, strato_warn, StratoError,
+};
 use tracing::{debug, info, instrument, warn};
 use wgpu::{
     Adapter, Backends, Device, DeviceDescriptor, DeviceType, Dx12Compiler, Features,
@@ -65,6 +67,40 @@ pub enum PerformanceTier {
 /// AdapterInfo re-export for public API
 pub use wgpu::AdapterInfo;
 
+/// Identifies an adapter across enumerations
+///
+/// Obtained from an [`AdapterInfo`] returned by
+/// [`DeviceManager::enumerate_adapters`] and used to force adapter selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AdapterId {
+    pub vendor: u32,
+    pub device: u32,
+    pub backend: wgpu::Backend,
+}
+
+impl AdapterId {
+    /// Whether this id refers to the adapter described by `info`
+    pub fn matches(&self, info: &AdapterInfo) -> bool {
+        *self == AdapterId::from(info)
+    }
+}
+
+impl From<&AdapterInfo> for AdapterId {
+    fn from(info: &AdapterInfo) -> Self {
+        Self {
+            vendor: info.vendor,
+            device: info.device,
+            backend: info.backend,
+        }
+    }
+}
+
+impl From<AdapterInfo> for AdapterId {
+    fn from(info: AdapterInfo) -> Self {
+        Self::from(&info)
+    }
+}
+
 /// Comprehensive GPU capabilities and characteristics
 #[derive(Debug, Clone)]
 pub struct GpuCapabilities {
@@ -278,6 +314,8 @@ pub struct DeviceSelectionCriteria {
     pub min_performance_tier: PerformanceTier,
     pub require_timestamp_queries: bool,
     pub require_pipeline_statistics: bool,
+    /// Adapter to use regardless of the other criteria, if it is available
+    pub forced_adapter: Option<AdapterId>,
 }
 
 impl Default for DeviceSelectionCriteria {
@@ -290,6 +328,7 @@ impl Default for DeviceSelectionCriteria {
             min_performance_tier: PerformanceTier::Integrated,
             require_timestamp_queries: false,
             require_pipeline_statistics: false,
+            forced_adapter: None,
         }
     }
 }
@@ -344,12 +383,39 @@ pub struct ManagedDevice {
     pub health: Arc<DeviceHealth>,
 }
 
+impl ManagedDevice {
+    /// Run `f` and return the validation error it raised, if any
+    ///
+    /// Without an error scope wgpu reports invalid shaders and pipelines to
+    /// the uncaptured error handler, which panics by default.
+    pub fn catch_validation_errors<T>(&self, f: impl FnOnce(&Device) -> T) -> Result<T> {
+        self.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let value = f(&self.device);
+        match futures::executor::block_on(self.device.pop_error_scope()) {
+            Some(error) => Err(anyhow::anyhow!("{}", error)),
+            None => Ok(value),
+        }
+    }
+}
+
 impl DeviceManager {
     /// Get all available adapters
     pub fn adapters(&self) -> &[(Adapter, GpuCapabilities)] {
         &self.adapters
     }
 
+    /// Describe every available adapter
+    ///
+    /// Pass an entry (or its [`AdapterId`]) to
+    /// [`RendererBuilder::with_adapter`](crate::RendererBuilder::with_adapter)
+    /// to force its selection.
+    pub fn enumerate_adapters(&self) -> Vec<AdapterInfo> {
+        self.adapters
+            .iter()
+            .map(|(adapter, _)| adapter.get_info())
+            .collect()
+    }
+
     /// Create a new device manager
     #[instrument(skip(instance, surface))]
     pub async fn new(instance: Option<Instance>, surface: Option<&Surface<'_>>) -> Result<Self> {
@@ -363,17 +429,18 @@ impl DeviceManager {
         });
 
         info!("Enumerating GPU adapters...");
-        let adapters = Self::enumerate_adapters(&instance, surface).await?;
+        let adapters = Self::discover_adapters(&instance, surface).await?;
 
         if adapters.is_empty() {
-            bail!("No compatible GPU adapters found");
+            return Err(StratoError::adapter_unavailable(format!("{:?}", Backends::all())).into());
         }
 
         info!("Found {} compatible GPU adapter(s)", adapters.len());
-        for (i, (_, caps)) in adapters.iter().enumerate() {
+        for (i, (adapter, caps)) in adapters.iter().enumerate() {
+            let info = adapter.get_info();
             info!(
-                "  [{}] {} ({:?}, {:?})",
-                i, caps.device_name, caps.vendor, caps.performance_tier
+                "  [{}] {} ({:?}, {:?}, {:?})",
+                i, caps.device_name, info.backend, info.device_type, caps.vendor
             );
         }
 
@@ -392,11 +459,27 @@ impl DeviceManager {
     }
 
     /// Enumerate and analyze all available adapters
-    async fn enumerate_adapters(
+    async fn discover_adapters(
         instance: &Instance,
         surface: Option<&Surface<'_>>,
     ) -> Result<Vec<(Adapter, GpuCapabilities)>> {
-        let mut adapters = Vec::new();
+        let mut adapters: Vec<(Adapter, GpuCapabilities)> = Vec::new();
+
+        // List every adapter where the platform allows it, so multi-GPU
+        // machines expose all of their GPUs
+        #[cfg(not(target_arch = "wasm32"))]
+        for adapter in instance.enumerate_adapters(Backends::all()) {
+            if surface.is_some_and(|surface| !adapter.is_surface_supported(surface)) {
+                continue;
+            }
+
+            let capabilities = GpuCapabilities::from_adapter(&adapter);
+            if !adapters.iter().any(|(_, caps)| {
+                caps.device_id == capabilities.device_id && caps.vendor_id == capabilities.vendor_id
+            }) {
+                adapters.push((adapter, capabilities));
+            }
+        }
 
         // Try all power preferences to find all adapters
         for power_pref in [PowerPreference::HighPerformance, PowerPreference::LowPower] {
@@ -475,12 +558,34 @@ impl DeviceManager {
         &self,
         criteria: DeviceSelectionCriteria,
     ) -> Result<Arc<ManagedDevice>> {
-        let fallback_chain = self.fallback_chain.read().clone();
+        let mut fallback_chain = self.fallback_chain.read().clone();
+        let mut last_error = None;
+
+        // A forced adapter is tried first and bypasses the other criteria.
+        // If it is gone (unplugged eGPU, driver change) selection falls back
+        // to the automatic chain.
+        let forced_idx = criteria.forced_adapter.and_then(|id| {
+            let idx = self
+                .adapters
+                .iter()
+                .position(|(adapter, _)| id.matches(&adapter.get_info()));
+            if idx.is_none() {
+                warn!(
+                    "Forced adapter {:?} not available, falling back to automatic selection",
+                    id
+                );
+            }
+            idx
+        });
+        if let Some(idx) = forced_idx {
+            fallback_chain.retain(|&i| i != idx);
+            fallback_chain.insert(0, idx);
+        }
 
         for &adapter_idx in &fallback_chain {
             let (adapter, capabilities) = &self.adapters[adapter_idx];
 
-            if !Self::meets_criteria(capabilities, &criteria) {
+            if Some(adapter_idx) != forced_idx && !Self::meets_criteria(capabilities, &criteria) {
                 debug!(
                     "Adapter {} doesn't meet criteria, skipping",
                     capabilities.device_name
@@ -490,9 +595,17 @@ impl DeviceManager {
 
             match self.create_device(adapter, capabilities, &criteria).await {
                 Ok(device) => {
+                    let info = adapter.get_info();
                     info!(
-                        "Successfully initialized device: {}",
-                        capabilities.device_name
+                        "Successfully initialized device: {} ({:?}, {:?}){}",
+                        capabilities.device_name,
+                        info.backend,
+                        info.device_type,
+                        if Some(adapter_idx) == forced_idx {
+                            " [forced]"
+                        } else {
+                            ""
+                        }
                     );
                     let managed_device = Arc::new(device);
                     *self.active_device.write() = Some(managed_device.clone());
@@ -504,12 +617,19 @@ impl DeviceManager {
                         "Failed to create device {}: {}",
                         capabilities.device_name, e
                     );
+                    last_error = Some(e);
                     continue;
                 }
             }
         }
 
-        bail!("Failed to initialize any compatible device");
+        Err(StratoError::DeviceInit {
+            message: "no adapter could create a compatible device".to_string(),
+            backend: None,
+            adapter: None,
+            source: last_error.map(Into::into),
+        }
+        .into())
     }
 
     /// Check if capabilities meet selection criteria
@@ -653,7 +773,11 @@ impl DeviceManager {
                 );
 
                 // All device creation errors are treated the same way
-                bail!("Failed to create device: {}", e);
+                Err(StratoError::device_request_failed(
+                    crate::gpu::device::adapter_details(&adapter.get_info()),
+                    e,
+                )
+                .into())
             }
         }
     }
@@ -753,6 +877,41 @@ mod tests {
         assert!(manager.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_forced_adapter_selection() {
+        let manager = DeviceManager::new(None, None).await.unwrap();
+        let adapters = manager.enumerate_adapters();
+        assert_eq!(adapters.len(), manager.adapters().len());
+
+        let forced = AdapterId::from(adapters.last().unwrap());
+        let criteria = DeviceSelectionCriteria {
+            forced_adapter: Some(forced),
+            ..Default::default()
+        };
+        manager
+            .initialize_device_with_criteria(criteria)
+            .await
+            .unwrap();
+        let active = manager.get_active_adapter().unwrap().get_info();
+        assert!(forced.matches(&active));
+
+        // An adapter that does not exist falls back to automatic selection
+        let missing = AdapterId {
+            vendor: 0xFFFF,
+            device: 0xFFFF,
+            backend: wgpu::Backend::Empty,
+        };
+        let criteria = DeviceSelectionCriteria {
+            forced_adapter: Some(missing),
+            min_memory_size: 0,
+            ..Default::default()
+        };
+        assert!(manager
+            .initialize_device_with_criteria(criteria)
+            .await
+            .is_ok());
+    }
+
     #[test]
     fn test_gpu_vendor_detection() {
         assert_eq!(GpuVendor::from(0x10DE), GpuVendor::Nvidia);
@@ -3,11 +3,80 @@
 //! BLOCCO 1: Device Setup
 //! Handles wgpu instance, adapter, device, and queue initialization
 
+use std::fmt;
+use strato_core::error::{AdapterDetails, StratoError};
 use wgpu::{
-    Adapter, AdapterInfo, Backends, Device, DeviceDescriptor, Features, Instance,
-    InstanceDescriptor, Limits, PowerPreference, Queue, RequestAdapterOptions,
+    Adapter, AdapterInfo, Backend, Backends, Device, DeviceDescriptor, DeviceType, Features,
+    Instance, InstanceDescriptor, Limits, PowerPreference, Queue, RequestAdapterOptions,
 };
 
+/// Describe an adapter for error reports
+pub fn adapter_details(info: &AdapterInfo) -> AdapterDetails {
+    let driver = format!("{} {}", info.driver, info.driver_info);
+    AdapterDetails {
+        name: info.name.clone(),
+        backend: format!("{:?}", info.backend),
+        device_type: format!("{:?}", info.device_type),
+        driver: driver.trim().to_string(),
+    }
+}
+
+/// The adapter and device the renderer runs on
+///
+/// Meant for about and diagnostics screens and for bug reports: its
+/// `Display` form names the adapter, backend, device type and driver.
+#[derive(Debug, Clone)]
+pub struct GpuInfo {
+    /// Adapter name as reported by the driver
+    pub name: String,
+    /// PCI vendor ID, or a backend-specific ID when there is none
+    pub vendor: u32,
+    /// PCI device ID, or a backend-specific ID when there is none
+    pub device: u32,
+    /// Discrete, integrated, virtual or CPU
+    pub device_type: DeviceType,
+    /// Graphics API in use
+    pub backend: Backend,
+    /// Driver name and version, empty when the backend doesn't report them
+    pub driver: String,
+    /// Limits of the created device
+    pub limits: Limits,
+}
+
+impl GpuInfo {
+    /// Describe an adapter and the limits of a device created on it
+    pub fn new(info: &AdapterInfo, limits: Limits) -> Self {
+        Self {
+            name: info.name.clone(),
+            vendor: info.vendor,
+            device: info.device,
+            device_type: info.device_type,
+            backend: info.backend,
+            driver: adapter_details(info).driver,
+            limits,
+        }
+    }
+
+    /// Whether frames are rasterized on the CPU, like with llvmpipe or WARP
+    pub fn is_software(&self) -> bool {
+        self.device_type == DeviceType::Cpu
+    }
+}
+
+impl fmt::Display for GpuInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "'{}' ({:?}, {:?}",
+            self.name, self.backend, self.device_type
+        )?;
+        if !self.driver.is_empty() {
+            write!(f, ", driver {}", self.driver)?;
+        }
+        write!(f, ")")
+    }
+}
+
 /// Manages wgpu device and queue
 pub struct DeviceManager {
     instance: Instance,
@@ -39,7 +108,7 @@ impl DeviceManager {
                 force_fallback_adapter: false,
             })
             .await
-            .ok_or_else(|| anyhow::anyhow!("Failed to find suitable GPU adapter"))?;
+            .ok_or_else(|| StratoError::adapter_unavailable(format!("{:?}", backend)))?;
 
         // Log adapter info for debugging
         let adapter_info = adapter.get_info();
@@ -61,7 +130,7 @@ impl DeviceManager {
                 None,
             )
             .await
-            .map_err(|e| anyhow::anyhow!("Failed to create device: {}", e))?;
+            .map_err(|e| StratoError::device_request_failed(adapter_details(&adapter_info), e))?;
 
         Ok(Self {
             instance,
@@ -105,6 +174,11 @@ impl DeviceManager {
     pub fn features(&self) -> Features {
         self.device.features()
     }
+
+    /// Describe the adapter and device for diagnostics
+    pub fn gpu_info(&self) -> GpuInfo {
+        GpuInfo::new(&self.adapter_info(), self.limits())
+    }
 }
 
 #[cfg(test)]
@@ -158,4 +232,24 @@ mod tests {
         // Verify queue is accessible
         let _queue = dm.queue();
     }
+
+    #[test]
+    fn test_gpu_info_flags_software_rasterizers() {
+        let info = AdapterInfo {
+            name: "llvmpipe (LLVM 17.0.6, 256 bits)".into(),
+            vendor: 0x10005,
+            device: 0,
+            device_type: DeviceType::Cpu,
+            driver: "llvmpipe".into(),
+            driver_info: "Mesa 24.0.5".into(),
+            backend: Backend::Vulkan,
+        };
+        let gpu = GpuInfo::new(&info, Limits::downlevel_defaults());
+        assert!(gpu.is_software());
+        assert_eq!(gpu.driver, "llvmpipe Mesa 24.0.5");
+        assert_eq!(
+            gpu.to_string(),
+            "'llvmpipe (LLVM 17.0.6, 256 bits)' (Vulkan, Cpu, driver llvmpipe Mesa 24.0.5)"
+        );
+    }
 }
@@ -6,15 +6,20 @@
 use super::{
     buffer_mgr::{BufferManager, SimpleVertex},
     device::DeviceManager,
+    hinting::TextHinting,
     pipeline_mgr::PipelineManager,
+    recovery::{RecoveryAction, SurfaceBackoff},
     render_pass_mgr::RenderPassManager,
+    resize_buffer::ResizeBuffer,
     shader_mgr::ShaderManager,
     surface::SurfaceManager,
+    text_cache::{ShapedGlyph, ShapedRun, TextCacheStats, TextLayoutCache},
     texture_mgr::TextureManager,
 };
-use crate::batch::RenderBatch;
-use crate::vertex::VertexBuilder;
+use crate::batch::{DrawCommand, RenderBatch};
+use crate::profiler::Profiler;
 use std::sync::Arc;
+use std::time::Instant;
 use wgpu::{CommandEncoderDescriptor, IndexFormat};
 use winit::window::Window;
 
@@ -27,6 +32,10 @@ pub struct DrawingSystem {
     texture_mgr: TextureManager,
     pipeline_mgr: PipelineManager,
     render_pass_mgr: RenderPassManager,
+    surface_backoff: SurfaceBackoff,
+    text_cache: TextLayoutCache,
+    resize_buffer: Option<ResizeBuffer>,
+    profiler: Option<Arc<Profiler>>,
     scale_factor: f32,
 }
 
@@ -93,17 +102,63 @@ impl DrawingSystem {
             texture_mgr,
             pipeline_mgr,
             render_pass_mgr,
+            surface_backoff: SurfaceBackoff::new(),
+            text_cache: TextLayoutCache::default(),
+            resize_buffer: None,
+            profiler: None,
             scale_factor: 1.0,
         })
     }
 
     /// Set the DPI scale factor
     pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if scale_factor != self.scale_factor {
+            // Cached runs were laid out from glyphs rasterized at the old scale
+            self.text_cache.clear();
+        }
         self.scale_factor = scale_factor;
     }
 
+    /// Set how glyphs are fitted to the pixel grid
+    pub fn set_text_hinting(&mut self, hinting: TextHinting) {
+        if hinting != self.texture_mgr.hinting() {
+            // Cached runs point at glyphs rasterized with the old hinting
+            self.text_cache.clear();
+        }
+        self.texture_mgr.set_hinting(hinting);
+    }
+
+    /// Set how many shaped text runs are kept between frames
+    pub fn set_text_cache_capacity(&mut self, capacity: usize) {
+        self.text_cache.set_capacity(capacity);
+    }
+
+    /// Hit, miss and size counters of the shaped text cache
+    pub fn text_cache_stats(&self) -> TextCacheStats {
+        self.text_cache.stats()
+    }
+
+    /// Report the text cache counters to `profiler` after every frame
+    pub fn set_profiler(&mut self, profiler: Arc<Profiler>) {
+        self.profiler = Some(profiler);
+    }
+
+    /// Keep each frame in a texture and present it again on resize
+    ///
+    /// See [`super::resize_buffer`].
+    pub fn set_resize_buffering(&mut self, enabled: bool) {
+        if enabled != self.resize_buffer.is_some() {
+            self.resize_buffer = enabled.then(ResizeBuffer::new);
+        }
+    }
+
     /// Render a batch
     pub fn render(&mut self, batch: &RenderBatch) -> anyhow::Result<()> {
+        // Repeated surface failures are backed off instead of retried every frame
+        if self.surface_backoff.is_waiting(Instant::now()) {
+            return Ok(());
+        }
+
         // 1. Process batch commands to generate vertices (including text)
         let mut vertices: Vec<SimpleVertex> = Vec::new();
         let mut indices: Vec<u32> = Vec::new();
@@ -126,7 +181,19 @@ impl DrawingSystem {
         // Note: We ignore batch.vertices here because we regenerate everything from commands
         // to ensure correct Z-ordering and support interleaved clipping.
 
-        for command in &batch.commands {
+        // Layers are drawn like the rest, their content in place
+        fn flatten<'a>(batch: &'a RenderBatch, out: &mut Vec<(&'a RenderBatch, &'a DrawCommand)>) {
+            for command in &batch.commands {
+                match command {
+                    DrawCommand::Layer { content, .. } => flatten(content.batch(), out),
+                    _ => out.push((batch, command)),
+                }
+            }
+        }
+        let mut commands = Vec::new();
+        flatten(batch, &mut commands);
+
+        for (batch, command) in commands {
             match command {
                 crate::batch::DrawCommand::PushClip(rect) => {
                     // Finish current batch if needed
@@ -193,40 +260,17 @@ impl DrawingSystem {
                     }
                     scissor_stack.pop();
                 }
-                crate::batch::DrawCommand::RoundedRect {
-                    rect,
-                    color,
-                    radius,
-                    transform,
-                } => {
-                    let color_arr = [color.r, color.g, color.b, color.a];
-                    let (v_list, i_list) = VertexBuilder::rounded_rectangle(
-                        rect.x,
-                        rect.y,
-                        rect.width,
-                        rect.height,
-                        *radius,
-                        color_arr,
-                        8,
-                    );
-
-                    let added_count = v_list.len() as u32;
-                    let index_count = i_list.len() as u32;
-
-                    for v in v_list {
-                        let mut sv = SimpleVertex::from(&v);
-                        // Apply transform
-                        let p = strato_core::types::Point::new(sv.position[0], sv.position[1]);
-                        let transformed = transform.transform_point(p);
-                        sv.position = [transformed.x, transformed.y];
-                        vertices.push(sv);
-                    }
-
-                    for i in i_list {
-                        indices.push((i as u32) + vertex_count);
+                crate::batch::DrawCommand::RoundedRect { index_range, .. }
+                | crate::batch::DrawCommand::Stroke { index_range, .. }
+                | crate::batch::DrawCommand::GradientRect { index_range, .. } => {
+                    // Tessellated when added to the batch
+                    for i in index_range.clone() {
+                        let vertex = &batch.vertices[batch.indices[i as usize] as usize];
+                        vertices.push(SimpleVertex::from(vertex));
+                        indices.push(vertex_count);
+                        vertex_count += 1;
+                        current_index_count += 1;
                     }
-                    vertex_count += added_count;
-                    current_index_count += index_count;
                 }
                 crate::batch::DrawCommand::Rect {
                     rect,
@@ -281,8 +325,9 @@ impl DrawingSystem {
                     font_size,
                     letter_spacing,
                     align,
+                    fill,
                 } => {
-                    let (mut x, y) = *position;
+                    let (x, y) = *position;
                     let color_arr = [color.r, color.g, color.b, color.a];
                     let font_size_val = *font_size;
                     let spacing_val = *letter_spacing;
@@ -291,95 +336,86 @@ impl DrawingSystem {
                     let scale = self.scale_factor;
                     let physical_font_size = (font_size_val * scale).round() as u32;
 
-                    // Handle alignment
-                    if *align != strato_core::text::TextAlign::Left {
-                        let mut width = 0.0;
-                        for ch in text.chars() {
-                            if let Some(glyph) = self.texture_mgr.get_or_cache_glyph(
-                                self.device_mgr.queue(),
-                                ch,
-                                physical_font_size,
-                            ) {
-                                // Scale metrics back to logical coordinates for layout
-                                let advance = glyph.metrics.advance / scale;
-                                width += advance + spacing_val;
-                            } else if ch == ' ' {
-                                width += font_size_val * 0.3 + spacing_val;
+                    let texture_mgr = &mut self.texture_mgr;
+                    let queue = self.device_mgr.queue();
+                    let run = self.text_cache.get_or_shape(
+                        text,
+                        font_size_val,
+                        spacing_val,
+                        scale,
+                        || {
+                            let ascent = if let Some(metrics) =
+                                texture_mgr.get_line_metrics(physical_font_size as f32)
+                            {
+                                metrics.ascent / scale
+                            } else {
+                                font_size_val * 0.8 // Fallback approximation
+                            };
+
+                            let mut run = ShapedRun::default();
+                            let mut pen = 0.0;
+                            for ch in text.chars() {
+                                if let Some(glyph) =
+                                    texture_mgr.get_or_cache_glyph(queue, ch, physical_font_size)
+                                {
+                                    // Scale metrics back to logical coordinates for layout
+                                    let bearing_x = glyph.metrics.bearing_x as f32 / scale;
+                                    let bearing_y = glyph.metrics.bearing_y as f32 / scale;
+                                    run.glyphs.push(ShapedGlyph {
+                                        offset: [pen + bearing_x, ascent - bearing_y],
+                                        size: [
+                                            glyph.metrics.width as f32 / scale,
+                                            glyph.metrics.height as f32 / scale,
+                                        ],
+                                        uv_rect: glyph.uv_rect,
+                                    });
+                                    pen += glyph.metrics.advance / scale + spacing_val;
+                                } else if ch == ' ' {
+                                    pen += font_size_val * 0.3 + spacing_val;
+                                }
                             }
-                        }
-
-                        match align {
-                            strato_core::text::TextAlign::Center => x -= width / 2.0,
-                            strato_core::text::TextAlign::Right => x -= width,
-                            _ => {} // Justify not implemented yet
-                        }
-                    }
+                            run.width = pen;
+                            run
+                        },
+                    );
 
-                    let ascent = if let Some(metrics) =
-                        self.texture_mgr.get_line_metrics(physical_font_size as f32)
-                    {
-                        metrics.ascent / scale
-                    } else {
-                        font_size_val * 0.8 // Fallback approximation
+                    // Handle alignment
+                    let x = match align {
+                        strato_core::text::TextAlign::Center => x - run.width / 2.0,
+                        strato_core::text::TextAlign::Right => x - run.width,
+                        _ => x, // Justify not implemented yet
                     };
 
-                    let baseline = y + ascent;
-
-                    for ch in text.chars() {
-                        if let Some(glyph) = self.texture_mgr.get_or_cache_glyph(
-                            self.device_mgr.queue(),
-                            ch,
-                            physical_font_size,
-                        ) {
-                            // Scale metrics back to logical coordinates for rendering
-                            let bearing_x = glyph.metrics.bearing_x as f32 / scale;
-                            let bearing_y = glyph.metrics.bearing_y as f32 / scale;
-                            let w = glyph.metrics.width as f32 / scale;
-                            let h = glyph.metrics.height as f32 / scale;
-                            let advance = glyph.metrics.advance / scale;
-
-                            let glyph_x = (x + bearing_x).round();
-                            let glyph_y = (baseline - bearing_y).round();
-
-                            let (u0, v0, u1, v1) = glyph.uv_rect;
-
-                            vertices.push(SimpleVertex::from(&crate::vertex::Vertex::textured(
-                                [glyph_x, glyph_y],
-                                [u0, v0],
-                                color_arr,
-                            )));
+                    for glyph in &run.glyphs {
+                        let glyph_x = (x + glyph.offset[0]).round();
+                        let glyph_y = (y + glyph.offset[1]).round();
+                        let [w, h] = glyph.size;
+                        let (u0, v0, u1, v1) = glyph.uv_rect;
+
+                        for (position, uv) in [
+                            ([glyph_x, glyph_y], [u0, v0]),
+                            ([glyph_x + w, glyph_y], [u1, v0]),
+                            ([glyph_x + w, glyph_y + h], [u1, v1]),
+                            ([glyph_x, glyph_y + h], [u0, v1]),
+                        ] {
+                            let color = match fill {
+                                Some(fill) => fill.color_at(position).to_array(),
+                                None => color_arr,
+                            };
                             vertices.push(SimpleVertex::from(&crate::vertex::Vertex::textured(
-                                [glyph_x + w, glyph_y],
-                                [u1, v0],
-                                color_arr,
+                                position, uv, color,
                             )));
-                            vertices.push(SimpleVertex::from(&crate::vertex::Vertex::textured(
-                                [glyph_x + w, glyph_y + h],
-                                [u1, v1],
-                                color_arr,
-                            )));
-                            vertices.push(SimpleVertex::from(&crate::vertex::Vertex::textured(
-                                [glyph_x, glyph_y + h],
-                                [u0, v1],
-                                color_arr,
-                            )));
-
-                            indices.push(vertex_count);
-                            indices.push(vertex_count + 1);
-                            indices.push(vertex_count + 2);
-                            indices.push(vertex_count);
-                            indices.push(vertex_count + 2);
-                            indices.push(vertex_count + 3);
+                        }
 
-                            vertex_count += 4;
-                            current_index_count += 6;
+                        indices.push(vertex_count);
+                        indices.push(vertex_count + 1);
+                        indices.push(vertex_count + 2);
+                        indices.push(vertex_count);
+                        indices.push(vertex_count + 2);
+                        indices.push(vertex_count + 3);
 
-                            x += advance + spacing_val;
-                        } else {
-                            if ch == ' ' {
-                                x += font_size_val * 0.3 + spacing_val;
-                            }
-                        }
+                        vertex_count += 4;
+                        current_index_count += 6;
                     }
                 }
                 crate::batch::DrawCommand::Image {
@@ -580,6 +616,8 @@ impl DrawingSystem {
                         current_index_count += 6;
                     }
                 }
+                // Flattened above
+                crate::batch::DrawCommand::Layer { .. } => {}
             }
         }
 
@@ -618,10 +656,46 @@ impl DrawingSystem {
             .upload_projection(self.device_mgr.queue(), &projection);
 
         // 5. Get surface texture
-        let surface_texture = self.surface_mgr.get_current_texture()?;
+        // Outdated/Lost surfaces are reconfigured and the frame skipped;
+        // timeouts just skip the frame. Only fatal errors propagate.
+        let surface_texture = match self.surface_mgr.get_current_texture() {
+            Ok(surface_texture) => {
+                self.surface_backoff.reset();
+                surface_texture
+            }
+            Err(error) => {
+                match RecoveryAction::for_surface_error(&error) {
+                    RecoveryAction::ReconfigureSurface => {
+                        self.surface_mgr.reconfigure(self.device_mgr.device())
+                    }
+                    RecoveryAction::SkipFrame => {}
+                    RecoveryAction::RecreateDevice => return Err(error.into()),
+                }
+                let delay = self.surface_backoff.record_failure(Instant::now());
+                tracing::debug!(
+                    "Skipping frame after surface error ({}), retrying in {:?}",
+                    error,
+                    delay
+                );
+                return Ok(());
+            }
+        };
         let view = surface_texture
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
+        if let Some(resize_buffer) = &mut self.resize_buffer {
+            resize_buffer.prepare(
+                self.device_mgr.device(),
+                self.surface_mgr.format(),
+                surface_texture.texture.width(),
+                surface_texture.texture.height(),
+            );
+        }
+        let frame_view = self
+            .resize_buffer
+            .as_ref()
+            .and_then(ResizeBuffer::view)
+            .unwrap_or(&view);
 
         // 6. Create command encoder
         let mut encoder =
@@ -633,7 +707,7 @@ impl DrawingSystem {
 
         // 7. Begin render pass
         {
-            let mut render_pass = self.render_pass_mgr.begin(&mut encoder, &view);
+            let mut render_pass = self.render_pass_mgr.begin(&mut encoder, frame_view);
 
             // 8. Set pipeline and bind groups
             render_pass.set_pipeline(self.pipeline_mgr.pipeline());
@@ -675,6 +749,10 @@ impl DrawingSystem {
             }
         }
 
+        if let Some(resize_buffer) = &self.resize_buffer {
+            resize_buffer.present(self.device_mgr.device(), &mut encoder, &view);
+        }
+
         // 11. Submit command buffer
         self.device_mgr
             .queue()
@@ -683,6 +761,10 @@ impl DrawingSystem {
         // 12. Present surface
         surface_texture.present();
 
+        if let Some(ref profiler) = self.profiler {
+            profiler.record_text_cache_stats(&self.text_cache.stats());
+        }
+
         Ok(())
     }
 
@@ -690,6 +772,7 @@ impl DrawingSystem {
     pub fn resize(&mut self, width: u32, height: u32) -> anyhow::Result<()> {
         self.surface_mgr
             .resize(width, height, self.device_mgr.device())?;
+        self.surface_backoff.reset();
 
         // Update projection matrix
         // Use logical size for projection to match render() behavior
@@ -700,8 +783,39 @@ impl DrawingSystem {
         self.buffer_mgr
             .upload_projection(self.device_mgr.queue(), &projection);
 
+        self.present_held_frame();
         Ok(())
     }
+
+    /// Present the held frame on the freshly configured surface
+    ///
+    /// Errors are left to the next render, which recovers from them.
+    fn present_held_frame(&mut self) {
+        let Some(resize_buffer) = &self.resize_buffer else {
+            return;
+        };
+        if !resize_buffer.has_frame() {
+            return;
+        }
+        let Ok(surface_texture) = self.surface_mgr.get_current_texture() else {
+            return;
+        };
+
+        let view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor::default());
+        let mut encoder =
+            self.device_mgr
+                .device()
+                .create_command_encoder(&CommandEncoderDescriptor {
+                    label: Some("Resize Encoder"),
+                });
+        resize_buffer.present(self.device_mgr.device(), &mut encoder, &view);
+        self.device_mgr
+            .queue()
+            .submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+    }
 }
 
 /// Create orthographic projection matrix for 2D rendering
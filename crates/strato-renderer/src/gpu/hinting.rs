@@ -0,0 +1,154 @@
+//! Glyph hinting for crisp small text
+//!
+//! Glyph outlines rarely line up with the pixel grid, so at UI sizes most
+//! of their edges land mid-pixel and are drawn as rows and columns of half
+//! covered pixels, which reads as blur on 1x displays. [`TextHinting::Light`]
+//! moves the edges of a glyph to pixel boundaries before it is rasterized:
+//!
+//! - Horizontal edges (the baseline, x-height and cap height, the bars of
+//!   `e` or `H`, the flat tops and bottoms of round letters) snap vertically.
+//! - Vertical stems (the strokes of `l`, `H` or `n`) snap horizontally.
+//!
+//! The points between edges are moved in proportion, so curves keep their
+//! shape, and edges that were apart stay at least a pixel apart so thin
+//! strokes don't vanish. Advances are left alone: text takes the same room
+//! with and without hinting. Glyph origins are snapped to whole pixels when
+//! text is drawn, so snapped edges stay on the screen's pixel grid.
+
+use ab_glyph::{point, Outline, OutlineCurve, Point, Rect};
+
+/// How glyph outlines are fitted to the pixel grid before rasterization
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TextHinting {
+    /// Rasterize outlines as designed
+    #[default]
+    None,
+    /// Snap horizontal edges and vertical stems to whole pixels
+    Light,
+}
+
+/// Edges closer than this, in pixels, are treated as one
+const EDGE_MERGE_DISTANCE: f32 = 0.25;
+
+/// Snap the edges of `outline`, in font units, for rasterization at `scale`
+/// pixels per font unit
+pub(crate) fn hint_outline(outline: &mut Outline, scale: f32) {
+    let mut horizontal = vec![0.0];
+    let mut vertical = Vec::new();
+    for curve in &outline.curves {
+        let (start, start_control, end_control, end) = match *curve {
+            OutlineCurve::Line(p0, p1) => (p0, p1, p0, p1),
+            OutlineCurve::Quad(p0, p1, p2) => (p0, p1, p1, p2),
+            OutlineCurve::Cubic(p0, p1, p2, p3) => (p0, p1, p2, p3),
+        };
+        // A flat tangent at either end of a segment marks an edge there
+        for (point, control) in [(start, start_control), (end, end_control)] {
+            if point == control {
+                continue;
+            }
+            if (point.y - control.y).abs() < 1.0 {
+                horizontal.push(point.y * scale);
+            }
+            if (point.x - control.x).abs() < 1.0 {
+                vertical.push(point.x * scale);
+            }
+        }
+    }
+
+    let rows = snap_edges(horizontal);
+    let columns = snap_edges(vertical);
+    let fit = |p: Point| {
+        point(
+            remap(p.x * scale, &columns) / scale,
+            remap(p.y * scale, &rows) / scale,
+        )
+    };
+    for curve in &mut outline.curves {
+        match curve {
+            OutlineCurve::Line(p0, p1) => {
+                *p0 = fit(*p0);
+                *p1 = fit(*p1);
+            }
+            OutlineCurve::Quad(p0, p1, p2) => {
+                *p0 = fit(*p0);
+                *p1 = fit(*p1);
+                *p2 = fit(*p2);
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                *p0 = fit(*p0);
+                *p1 = fit(*p1);
+                *p2 = fit(*p2);
+                *p3 = fit(*p3);
+            }
+        }
+    }
+    outline.bounds = bounds(&outline.curves).unwrap_or(outline.bounds);
+}
+
+/// Pair each edge, in pixels, with the whole pixel it moves to, in order
+fn snap_edges(mut edges: Vec<f32>) -> Vec<(f32, f32)> {
+    edges.sort_by(f32::total_cmp);
+    let mut snapped: Vec<(f32, f32)> = Vec::new();
+    for edge in edges {
+        match snapped.last() {
+            Some(&(last, _)) if edge - last < EDGE_MERGE_DISTANCE => {}
+            Some(&(_, target)) => snapped.push((edge, edge.round().max(target + 1.0))),
+            None => snapped.push((edge, edge.round())),
+        }
+    }
+    snapped
+}
+
+/// Move `value` with the edges around it
+fn remap(value: f32, edges: &[(f32, f32)]) -> f32 {
+    let (Some(&(first, first_target)), Some(&(last, last_target))) = (edges.first(), edges.last())
+    else {
+        return value;
+    };
+    if value <= first {
+        return value + first_target - first;
+    }
+    if value >= last {
+        return value + last_target - last;
+    }
+    let above = edges.partition_point(|&(edge, _)| edge <= value);
+    let (low, low_target) = edges[above - 1];
+    let (high, high_target) = edges[above];
+    low_target + (value - low) / (high - low) * (high_target - low_target)
+}
+
+/// Bounds of `curves` the way ab_glyph keeps them, `min` holding the top
+/// and `max` the bottom
+fn bounds(curves: &[OutlineCurve]) -> Option<Rect> {
+    let points = curves.iter().flat_map(|curve| match *curve {
+        OutlineCurve::Line(p0, p1) => vec![p0, p1],
+        OutlineCurve::Quad(p0, p1, p2) => vec![p0, p1, p2],
+        OutlineCurve::Cubic(p0, p1, p2, p3) => vec![p0, p1, p2, p3],
+    });
+    points.fold(None, |bounds: Option<Rect>, p| {
+        Some(match bounds {
+            Some(Rect { min, max }) => Rect {
+                min: point(min.x.min(p.x), min.y.max(p.y)),
+                max: point(max.x.max(p.x), max.y.min(p.y)),
+            },
+            None => Rect { min: p, max: p },
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edges_snap_to_whole_pixels_and_stay_apart() {
+        let snapped = snap_edges(vec![6.4, 0.0, 3.3, 3.6, 6.5]);
+        // 6.4 and 6.5 merge; 3.3 and 3.6 would round together
+        assert_eq!(
+            snapped,
+            vec![(0.0, 0.0), (3.3, 3.0), (3.6, 4.0), (6.4, 6.0)]
+        );
+        assert!((remap(5.0, &snapped) - 5.0).abs() < 1e-4);
+        assert!((remap(7.4, &snapped) - 7.0).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,505 @@
+//! Layer textures
+//!
+//! Layers added with [`RenderBatch::add_layer`](crate::batch::RenderBatch::add_layer)
+//! are rendered into a texture of their own, sized to the layer in physical
+//! pixels, and composited onto the frame as a single quad. A [`LayerCache`]
+//! keeps the textures between frames, rendering a layer again only when it
+//! is added with new content or at a new size, and drops the textures of
+//! layers a frame no longer draws.
+//!
+//! Textures have the surface's format and are drawn with the UI pipeline,
+//! so a layer looks exactly as its content would drawn into the frame.
+
+use super::{PipelineManager, SimpleVertex, TextureAtlas};
+use crate::batch::LayerContent;
+use crate::post_process::RenderTarget;
+use std::collections::HashMap;
+use std::ops::Range;
+use strato_core::types::Rect;
+use wgpu::{
+    util::{BufferInitDescriptor, DeviceExt},
+    BindGroup, Buffer, BufferUsages, CommandEncoderDescriptor, Device, Queue, RenderPass,
+    TextureFormat,
+};
+
+/// Vertex flag of the quads compositing a layer, whose texture holds colors
+/// as the target stores them
+pub const FLAG_LAYER: u32 = 2;
+
+/// A draw call of the UI pipeline
+#[derive(Clone, Copy)]
+pub struct DrawBatch {
+    pub index_start: u32,
+    pub index_count: u32,
+    /// Scissor rectangle in physical pixels of the target
+    pub scissor: Option<[u32; 4]>,
+    /// Layer whose texture the draw samples instead of the atlas
+    pub layer: Option<u64>,
+}
+
+impl DrawBatch {
+    fn indices(&self) -> Range<u32> {
+        self.index_start..self.index_start + self.index_count
+    }
+}
+
+/// A layer rendered into its texture
+pub struct CachedLayer {
+    content: LayerContent,
+    /// Top-left corner and size in physical pixels
+    origin: [i32; 2],
+    size: (u32, u32),
+    target: RenderTarget,
+    /// Samples the texture with the frame's projection
+    bind_group: BindGroup,
+    /// Whether the current frame draws the layer
+    used: bool,
+}
+
+impl CachedLayer {
+    /// Where the texture is drawn, in logical pixels
+    pub fn rect(&self, scale_factor: f64) -> Rect {
+        let scale = scale_factor as f32;
+        Rect::new(
+            self.origin[0] as f32 / scale,
+            self.origin[1] as f32 / scale,
+            self.size.0 as f32 / scale,
+            self.size.1 as f32 / scale,
+        )
+    }
+
+    /// Samples the texture with the frame's projection
+    pub fn bind_group(&self) -> &BindGroup {
+        &self.bind_group
+    }
+}
+
+/// Where a layer covering `rect` lands in physical pixels: its top-left
+/// corner, rounded to a pixel so the texture maps 1:1 onto the frame, and
+/// its size
+pub fn physical_bounds(rect: Rect, scale_factor: f64) -> ([i32; 2], (u32, u32)) {
+    let scale = scale_factor;
+    let x = (rect.x as f64 * scale).round();
+    let y = (rect.y as f64 * scale).round();
+    let width = ((rect.x + rect.width) as f64 * scale).round() - x;
+    let height = ((rect.y + rect.height) as f64 * scale).round() - y;
+    (
+        [x as i32, y as i32],
+        (width.max(0.0) as u32, height.max(0.0) as u32),
+    )
+}
+
+/// What a layer is rendered from
+pub struct LayerGeometry<'a> {
+    pub vertices: &'a [SimpleVertex],
+    pub indices: &'a [u32],
+    pub draws: &'a [DrawBatch],
+}
+
+/// What layers are rendered with
+#[derive(Clone, Copy)]
+pub struct LayerResources<'a> {
+    pub device: &'a Device,
+    pub queue: &'a Queue,
+    pub pipeline_mgr: &'a PipelineManager,
+    pub atlas: &'a TextureAtlas,
+    /// Holds the frame's projection, used to composite the textures
+    pub frame_uniforms: &'a Buffer,
+    /// Format of the frame, and of the textures
+    pub format: TextureFormat,
+    pub scale_factor: f64,
+}
+
+/// Textures of the layers drawn by recent frames
+#[derive(Default)]
+pub struct LayerCache {
+    layers: HashMap<u64, CachedLayer>,
+}
+
+impl LayerCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start a frame, after which only the layers it draws are kept
+    pub fn begin_frame(&mut self) {
+        for layer in self.layers.values_mut() {
+            layer.used = false;
+        }
+    }
+
+    /// Drop the textures of layers the frame didn't draw
+    pub fn end_frame(&mut self) {
+        self.layers.retain(|_, layer| layer.used);
+    }
+
+    /// Drop every texture, e.g. when the device is recreated
+    pub fn clear(&mut self) {
+        self.layers.clear();
+    }
+
+    /// Number of textures held
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether no texture is held
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// The texture of layer `id`, if it has one
+    pub fn get(&self, id: u64) -> Option<&CachedLayer> {
+        self.layers.get(&id)
+    }
+
+    /// Mark layer `id` as drawn this frame, returning whether its texture
+    /// already shows `content` at `origin` and `size`
+    pub fn touch(
+        &mut self,
+        id: u64,
+        content: &LayerContent,
+        origin: [i32; 2],
+        size: (u32, u32),
+    ) -> bool {
+        let Some(layer) = self.layers.get_mut(&id) else {
+            return false;
+        };
+        layer.used = true;
+        layer.content.same_as(content) && layer.origin == origin && layer.size == size
+    }
+
+    /// Forget layer `id`, so it is drawn without a texture
+    pub fn remove(&mut self, id: u64) {
+        self.layers.remove(&id);
+    }
+
+    /// Render `geometry` into the texture of layer `id`
+    ///
+    /// `geometry` is in logical pixels of the window; its scissors are in
+    /// physical pixels of the texture. It may draw the layers already in
+    /// the cache.
+    pub fn render(
+        &mut self,
+        gpu: &LayerResources<'_>,
+        id: u64,
+        content: &LayerContent,
+        origin: [i32; 2],
+        size: (u32, u32),
+        geometry: LayerGeometry<'_>,
+    ) {
+        let LayerResources {
+            device,
+            queue,
+            pipeline_mgr,
+            atlas,
+            frame_uniforms,
+            format,
+            scale_factor,
+        } = *gpu;
+        let target = match self.layers.remove(&id) {
+            Some(layer) if layer.target.matches(format, size.0, size.1) => layer.target,
+            _ => RenderTarget::new(device, "Layer Texture", format, size.0, size.1),
+        };
+
+        // Maps the layer's rectangle in logical pixels onto the texture
+        let scale = scale_factor as f32;
+        let left = origin[0] as f32 / scale;
+        let top = origin[1] as f32 / scale;
+        let projection = glam::Mat4::orthographic_rh(
+            left,
+            left + size.0 as f32 / scale,
+            top + size.1 as f32 / scale,
+            top,
+            -1.0,
+            1.0,
+        );
+        let uniforms = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Layer Uniforms"),
+            contents: bytemuck::cast_slice(&projection.to_cols_array()),
+            usage: BufferUsages::UNIFORM,
+        });
+        let content_bind_group =
+            pipeline_mgr.create_bind_group(device, &uniforms, atlas.view(), atlas.sampler());
+
+        let buffers = (!geometry.indices.is_empty()).then(|| {
+            let vertices = device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("Layer Vertices"),
+                contents: bytemuck::cast_slice(geometry.vertices),
+                usage: BufferUsages::VERTEX,
+            });
+            let indices = device.create_buffer_init(&BufferInitDescriptor {
+                label: Some("Layer Indices"),
+                contents: bytemuck::cast_slice(geometry.indices),
+                usage: BufferUsages::INDEX,
+            });
+            (vertices, indices)
+        });
+
+        // Layers drawn by this one are projected like its content
+        let nested: HashMap<u64, BindGroup> = geometry
+            .draws
+            .iter()
+            .filter_map(|draw| draw.layer)
+            .filter_map(|id| {
+                let layer = self.layers.get(&id)?;
+                let bind_group = pipeline_mgr.create_bind_group(
+                    device,
+                    &uniforms,
+                    &layer.target.view,
+                    atlas.sampler(),
+                );
+                Some((id, bind_group))
+            })
+            .collect();
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+            label: Some("Layer Encoder"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Layer Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            if let Some((vertices, indices)) = &buffers {
+                pass.set_vertex_buffer(0, vertices.slice(..));
+                pass.set_index_buffer(indices.slice(..), wgpu::IndexFormat::Uint32);
+                draw_batches(
+                    &mut pass,
+                    pipeline_mgr,
+                    &content_bind_group,
+                    geometry.draws,
+                    size,
+                    |id| nested.get(&id),
+                );
+            }
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+
+        let bind_group =
+            pipeline_mgr.create_bind_group(device, frame_uniforms, &target.view, atlas.sampler());
+        self.layers.insert(
+            id,
+            CachedLayer {
+                content: content.clone(),
+                origin,
+                size,
+                target,
+                bind_group,
+                used: true,
+            },
+        );
+    }
+}
+
+/// Record `draws` into `pass`, whose vertex and index buffers are set
+///
+/// Draws of layers sample the bind group `layer_bind_group` returns for
+/// them and are skipped if there is none; others use `bind_group`. `size` is
+/// the target's size in physical pixels.
+pub fn draw_batches<'a>(
+    pass: &mut RenderPass<'a>,
+    pipeline_mgr: &'a PipelineManager,
+    bind_group: &'a BindGroup,
+    draws: &[DrawBatch],
+    size: (u32, u32),
+    layer_bind_group: impl Fn(u64) -> Option<&'a BindGroup>,
+) {
+    pass.set_pipeline(pipeline_mgr.pipeline());
+    for draw in draws {
+        if draw.index_count == 0 {
+            continue;
+        }
+        let bind_group = match draw.layer {
+            Some(id) => match layer_bind_group(id) {
+                Some(bind_group) => bind_group,
+                None => continue,
+            },
+            None => bind_group,
+        };
+        match draw.scissor {
+            Some([_, _, 0, _] | [_, _, _, 0]) => continue,
+            Some([x, y, width, height]) => pass.set_scissor_rect(x, y, width, height),
+            None => pass.set_scissor_rect(0, 0, size.0, size.1),
+        }
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.draw_indexed(draw.indices(), 0, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::batch::RenderBatch;
+    use crate::capture::FrameCapture;
+    use crate::gpu::{BufferManager, DeviceManager, ShaderManager, TextureManager};
+    use crate::vertex::Vertex;
+
+    #[tokio::test]
+    async fn test_layer_texture_holds_its_content() {
+        let dm = DeviceManager::new(wgpu::Backends::all())
+            .await
+            .expect("Failed to create device manager");
+        let (device, queue) = (dm.device(), dm.queue());
+        let format = TextureFormat::Rgba8Unorm;
+        let shader = ShaderManager::from_wgsl(
+            device,
+            include_str!("../shaders/simple.wgsl"),
+            Some("Test Shader"),
+        )
+        .unwrap();
+        let buffer_mgr = BufferManager::new(device);
+        let texture_mgr = TextureManager::new(device, queue);
+        let pipeline_mgr =
+            PipelineManager::new(device, &shader, &buffer_mgr, &texture_mgr, format).unwrap();
+
+        // A red square over the left half of a 4x4 layer at (10, 10)
+        let vertices: Vec<SimpleVertex> = [[10.0, 10.0], [12.0, 10.0], [12.0, 14.0], [10.0, 14.0]]
+            .map(|position| SimpleVertex::from(&Vertex::solid(position, [1.0, 0.0, 0.0, 1.0])))
+            .to_vec();
+        let draws = [DrawBatch {
+            index_start: 0,
+            index_count: 6,
+            scissor: None,
+            layer: None,
+        }];
+        let content = LayerContent::new(RenderBatch::new());
+        let mut layers = LayerCache::new();
+        let gpu = LayerResources {
+            device,
+            queue,
+            pipeline_mgr: &pipeline_mgr,
+            atlas: texture_mgr.atlas(),
+            frame_uniforms: buffer_mgr.uniform_buffer(),
+            format,
+            scale_factor: 1.0,
+        };
+        layers.render(
+            &gpu,
+            7,
+            &content,
+            [10, 10],
+            (4, 4),
+            LayerGeometry {
+                vertices: &vertices,
+                indices: &[0, 1, 2, 0, 2, 3],
+                draws: &draws,
+            },
+        );
+
+        let layer = layers.get(7).expect("the layer should be cached");
+        assert_eq!(layer.rect(1.0), Rect::new(10.0, 10.0, 4.0, 4.0));
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Layer Test"),
+        });
+        let capture = FrameCapture::record(device, &mut encoder, &layer.target.texture).unwrap();
+        queue.submit(std::iter::once(encoder.finish()));
+        let image = capture.wait(device).unwrap();
+        let pixel = |x: usize, y: usize| &image.data[(y * 4 + x) * 4..][..4];
+        assert_eq!(pixel(0, 0), [255, 0, 0, 255]);
+        assert_eq!(pixel(1, 3), [255, 0, 0, 255]);
+        assert_eq!(pixel(2, 0), [0, 0, 0, 0]);
+
+        // Composited onto a 16x16 frame as a single quad
+        let mut buffer_mgr = buffer_mgr;
+        let projection = glam::Mat4::orthographic_rh(0.0, 16.0, 16.0, 0.0, -1.0, 1.0);
+        buffer_mgr.upload_projection(queue, &projection.to_cols_array_2d());
+        let quad: Vec<SimpleVertex> = [
+            ([10.0, 10.0], [0.0, 0.0]),
+            ([14.0, 10.0], [1.0, 0.0]),
+            ([14.0, 14.0], [1.0, 1.0]),
+            ([10.0, 14.0], [0.0, 1.0]),
+        ]
+        .map(|(position, uv)| SimpleVertex {
+            position,
+            color: [1.0; 4],
+            uv,
+            params: [0.0; 4],
+            flags: FLAG_LAYER,
+        })
+        .to_vec();
+        let quad_vertices = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Test Vertices"),
+            contents: bytemuck::cast_slice(&quad),
+            usage: BufferUsages::VERTEX,
+        });
+        let quad_indices = device.create_buffer_init(&BufferInitDescriptor {
+            label: Some("Test Indices"),
+            contents: bytemuck::cast_slice(&[0u32, 1, 2, 0, 2, 3]),
+            usage: BufferUsages::INDEX,
+        });
+        let frame = RenderTarget::new(device, "Test Frame", format, 16, 16);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Composite Test"),
+        });
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Composite Test"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &frame.view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            pass.set_vertex_buffer(0, quad_vertices.slice(..));
+            pass.set_index_buffer(quad_indices.slice(..), wgpu::IndexFormat::Uint32);
+            draw_batches(
+                &mut pass,
+                &pipeline_mgr,
+                pipeline_mgr.bind_group(),
+                &[DrawBatch {
+                    layer: Some(7),
+                    ..draws[0]
+                }],
+                (16, 16),
+                |id| layers.get(id).map(CachedLayer::bind_group),
+            );
+        }
+        let capture = FrameCapture::record(device, &mut encoder, &frame.texture).unwrap();
+        queue.submit(std::iter::once(encoder.finish()));
+        let image = capture.wait(device).unwrap();
+        let pixel = |x: usize, y: usize| &image.data[(y * 16 + x) * 4..][..4];
+        assert_eq!(pixel(10, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(11, 13), [255, 0, 0, 255]);
+        assert_eq!(pixel(12, 10), [0, 0, 0, 0]);
+        assert_eq!(pixel(9, 10), [0, 0, 0, 0]);
+
+        // Kept while drawn with the same content at the same place
+        layers.begin_frame();
+        assert!(layers.touch(7, &content, [10, 10], (4, 4)));
+        assert!(!layers.touch(7, &content, [11, 10], (4, 4)));
+        assert!(!layers.touch(7, &LayerContent::new(RenderBatch::new()), [10, 10], (4, 4)));
+        layers.end_frame();
+        assert_eq!(layers.len(), 1);
+        layers.begin_frame();
+        layers.end_frame();
+        assert!(layers.is_empty());
+    }
+
+    #[test]
+    fn test_layers_snap_to_physical_pixels() {
+        let (origin, size) = physical_bounds(Rect::new(10.2, 5.0, 20.0, 10.4), 2.0);
+        assert_eq!(origin, [20, 10]);
+        assert_eq!(size, (40, 21));
+
+        let (_, empty) = physical_bounds(Rect::new(0.0, 0.0, -4.0, 3.0), 1.0);
+        assert_eq!(empty, (0, 3));
+    }
+}
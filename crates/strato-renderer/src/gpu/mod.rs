@@ -26,12 +26,34 @@ pub mod drawing;
 // BLOCCO 8: Texture Management
 pub mod texture_mgr;
 
+// BLOCCO 9: Device Recovery
+pub mod recovery;
+
+// BLOCCO 10: Text Layout Cache
+pub mod text_cache;
+
+// BLOCCO 11: Glyph Hinting
+pub mod hinting;
+
+// BLOCCO 12: Resize Buffering
+pub mod resize_buffer;
+
+// BLOCCO 13: Layer Textures
+pub mod layer_cache;
+
 // Re-exports
 pub use buffer_mgr::{BufferManager, SimpleVertex};
-pub use device::DeviceManager;
+pub use device::{DeviceManager, GpuInfo};
 pub use drawing::DrawingSystem;
+pub use hinting::TextHinting;
+pub use layer_cache::{CachedLayer, DrawBatch, LayerCache};
 pub use pipeline_mgr::PipelineManager;
+pub use recovery::{
+    AcquireSurface, DeviceHealth, RecoveryAction, RecoveryEvent, RecoveryStats, RecoveryTracker, SurfaceBackoff,
+};
 pub use render_pass_mgr::RenderPassManager;
+pub use resize_buffer::ResizeBuffer;
 pub use shader_mgr::ShaderManager;
-pub use surface::SurfaceManager;
+pub use surface::{OutputColorSpace, SurfaceManager, DEFAULT_CLEAR_COLOR};
+pub use text_cache::{ShapedGlyph, ShapedRun, TextCacheStats, TextLayoutCache};
 pub use texture_mgr::{TextureAtlas, TextureManager};
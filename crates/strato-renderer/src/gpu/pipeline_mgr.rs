@@ -5,17 +5,20 @@
 
 use wgpu::{
     BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
-    BindGroupLayoutEntry, BindingType, BlendState, BufferBindingType, ColorTargetState,
+    BindGroupLayoutEntry, BindingType, BlendState, Buffer, BufferBindingType, ColorTargetState,
     ColorWrites, Device, Face, FragmentState, FrontFace, MultisampleState,
-    PipelineLayoutDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology, RenderPipeline,
-    RenderPipelineDescriptor, ShaderStages, TextureFormat, VertexState,
+    PipelineCompilationOptions, PipelineLayoutDescriptor, PolygonMode, PrimitiveState,
+    PrimitiveTopology, RenderPipeline, RenderPipelineDescriptor, Sampler, ShaderStages,
+    TextureFormat, TextureView, VertexState,
 };
 
 use super::{
     buffer_mgr::{BufferManager, SimpleVertex},
     shader_mgr::ShaderManager,
+    surface::OutputColorSpace,
     texture_mgr::TextureManager,
 };
+use std::collections::HashMap;
 
 /// Manages render pipeline and bind groups
 pub struct PipelineManager {
@@ -40,8 +43,6 @@ impl PipelineManager {
         texture_mgr: &TextureManager,
         surface_format: TextureFormat,
     ) -> anyhow::Result<Self> {
-        println!("=== PIPELINE CREATION ===");
-
         // Create bind group layout for uniform buffer + texture + sampler
         let bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
             label: Some("Bind Group Layout"),
@@ -79,27 +80,13 @@ impl PipelineManager {
         });
 
         // Create bind group with actual resources
-        let bind_group = device.create_bind_group(&BindGroupDescriptor {
-            label: Some("Bind Group"),
-            layout: &bind_group_layout,
-            entries: &[
-                // Binding 0: Uniform buffer
-                BindGroupEntry {
-                    binding: 0,
-                    resource: buffer_mgr.uniform_buffer().as_entire_binding(),
-                },
-                // Binding 1: Texture view
-                BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(texture_mgr.atlas().view()),
-                },
-                // Binding 2: Sampler
-                BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Sampler(texture_mgr.atlas().sampler()),
-                },
-            ],
-        });
+        let bind_group = Self::bind_group_for(
+            device,
+            &bind_group_layout,
+            buffer_mgr.uniform_buffer(),
+            texture_mgr.atlas().view(),
+            texture_mgr.atlas().sampler(),
+        );
 
         // Create pipeline layout
         let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
@@ -108,6 +95,11 @@ impl PipelineManager {
             push_constant_ranges: &[],
         });
 
+        // Textures need encoding again on targets storing sRGB values as written
+        let output_srgb = OutputColorSpace::for_format(surface_format) == OutputColorSpace::Srgb;
+        let constants =
+            HashMap::from([("output_srgb".to_string(), f64::from(u8::from(output_srgb)))]);
+
         // Create render pipeline
         let render_pipeline = device.create_render_pipeline(&RenderPipelineDescriptor {
             label: Some("Render Pipeline"),
@@ -123,10 +115,13 @@ impl PipelineManager {
                 entry_point: shader.fragment_entry(),
                 targets: &[Some(ColorTargetState {
                     format: surface_format,
-                    blend: Some(BlendState::ALPHA_BLENDING),
+                    blend: Some(BlendState::PREMULTIPLIED_ALPHA_BLENDING),
                     write_mask: ColorWrites::ALL,
                 })],
-                compilation_options: Default::default(),
+                compilation_options: PipelineCompilationOptions {
+                    constants: &constants,
+                    ..Default::default()
+                },
             }),
             primitive: PrimitiveState {
                 topology: PrimitiveTopology::TriangleList,
@@ -146,12 +141,10 @@ impl PipelineManager {
             multiview: None,
         });
 
-        println!("Bind group layout: created");
-        println!("Bind group: created");
-        println!("Render pipeline: created");
-        println!("Surface format: {:?}", surface_format);
-        println!("Blend mode: ALPHA_BLENDING");
-        println!("=========================");
+        tracing::debug!(
+            "Render pipeline created for {:?} with premultiplied alpha blending",
+            surface_format
+        );
 
         Ok(Self {
             bind_group_layout,
@@ -160,6 +153,48 @@ impl PipelineManager {
         })
     }
 
+    /// A bind group drawing with the projection in `uniforms` and sampling
+    /// `texture`, e.g. an offscreen layer instead of the atlas
+    pub fn create_bind_group(
+        &self,
+        device: &Device,
+        uniforms: &Buffer,
+        texture: &TextureView,
+        sampler: &Sampler,
+    ) -> BindGroup {
+        Self::bind_group_for(device, &self.bind_group_layout, uniforms, texture, sampler)
+    }
+
+    fn bind_group_for(
+        device: &Device,
+        layout: &BindGroupLayout,
+        uniforms: &Buffer,
+        texture: &TextureView,
+        sampler: &Sampler,
+    ) -> BindGroup {
+        device.create_bind_group(&BindGroupDescriptor {
+            label: Some("Bind Group"),
+            layout,
+            entries: &[
+                // Binding 0: Uniform buffer
+                BindGroupEntry {
+                    binding: 0,
+                    resource: uniforms.as_entire_binding(),
+                },
+                // Binding 1: Texture view
+                BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(texture),
+                },
+                // Binding 2: Sampler
+                BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(sampler),
+                },
+            ],
+        })
+    }
+
     /// Get bind group reference
     pub fn bind_group(&self) -> &BindGroup {
         &self.bind_group
@@ -224,13 +259,13 @@ mod tests {
 
     #[test]
     fn test_blend_state_configuration() {
-        // Verify blend state is correct (ALPHA_BLENDING)
-        let blend = BlendState::ALPHA_BLENDING;
+        // Verify blend state is correct (PREMULTIPLIED_ALPHA_BLENDING)
+        let blend = BlendState::PREMULTIPLIED_ALPHA_BLENDING;
 
-        // ALPHA_BLENDING should have:
-        // - color: src_alpha * src + (1 - src_alpha) * dst
+        // The shader premultiplies, so PREMULTIPLIED_ALPHA_BLENDING should have:
+        // - color: 1 * src + (1 - src_alpha) * dst
         // - alpha: 1 * src + (1 - src_alpha) * dst
-        assert_eq!(blend.color.src_factor, wgpu::BlendFactor::SrcAlpha);
+        assert_eq!(blend.color.src_factor, wgpu::BlendFactor::One);
         assert_eq!(blend.color.dst_factor, wgpu::BlendFactor::OneMinusSrcAlpha);
         assert_eq!(blend.alpha.src_factor, wgpu::BlendFactor::One);
         assert_eq!(blend.alpha.dst_factor, wgpu::BlendFactor::OneMinusSrcAlpha);
@@ -0,0 +1,337 @@
+//! Device loss detection and recovery
+//!
+//! BLOCCO 9: Device Recovery
+//! Watches the wgpu device for loss (driver reset, sleep/wake, out of memory),
+//! decides how to react to surface errors and keeps track of the recoveries
+//! performed so the application can be told about them.
+
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use wgpu::{Device, DeviceLostReason, SurfaceError, SurfaceTexture};
+
+use super::SurfaceManager;
+
+/// What the backend should do after a frame could not be acquired
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// Reconfigure the surface with the current size and skip the frame
+    ReconfigureSurface,
+    /// Recreate the device, surface, pipelines and GPU resources
+    RecreateDevice,
+    /// Drop this frame and try again on the next one
+    SkipFrame,
+}
+
+impl RecoveryAction {
+    /// Pick the recovery action for a surface error
+    pub fn for_surface_error(error: &SurfaceError) -> Self {
+        match error {
+            SurfaceError::Outdated | SurfaceError::Lost => Self::ReconfigureSurface,
+            SurfaceError::OutOfMemory => Self::RecreateDevice,
+            SurfaceError::Timeout => Self::SkipFrame,
+        }
+    }
+}
+
+/// Surface the backend acquires frames from
+///
+/// Implemented by [`SurfaceManager`]. Tests stand in surfaces that fail on
+/// cue to drive the recovery paths without a window.
+pub trait AcquireSurface: Send + Sync {
+    /// Get the next frame to render to
+    fn acquire(&mut self) -> Result<SurfaceTexture, SurfaceError>;
+
+    /// Configure the surface again with its current size and settings
+    fn reconfigure(&mut self, device: &Device);
+}
+
+impl AcquireSurface for SurfaceManager {
+    fn acquire(&mut self) -> Result<SurfaceTexture, SurfaceError> {
+        self.get_current_texture()
+    }
+
+    fn reconfigure(&mut self, device: &Device) {
+        SurfaceManager::reconfigure(self, device);
+    }
+}
+
+/// Failures retried immediately before backing off
+const BACKOFF_FREE_RETRIES: u32 = 2;
+/// Delay after the first failure past the free retries
+const BACKOFF_BASE: Duration = Duration::from_millis(16);
+/// Longest delay between two attempts
+const BACKOFF_MAX: Duration = Duration::from_secs(1);
+
+/// Backoff for repeated frame acquisition failures
+///
+/// A couple of failures (a resize racing with acquisition) are retried on the
+/// next frame. Past that the delay doubles on every failure, up to
+/// [`BACKOFF_MAX`], so a surface that keeps failing (minimized window,
+/// display asleep) does not spin. A successful acquisition resets it.
+#[derive(Debug, Clone, Default)]
+pub struct SurfaceBackoff {
+    failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl SurfaceBackoff {
+    /// Create a backoff with no recorded failures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether acquisition should not be attempted yet
+    pub fn is_waiting(&self, now: Instant) -> bool {
+        self.retry_at.is_some_and(|retry_at| now < retry_at)
+    }
+
+    /// Record a failed acquisition, returning the delay before the next attempt
+    pub fn record_failure(&mut self, now: Instant) -> Duration {
+        self.failures = self.failures.saturating_add(1);
+        let delay = match self.failures.checked_sub(BACKOFF_FREE_RETRIES + 1) {
+            None => Duration::ZERO,
+            Some(exponent) => BACKOFF_BASE
+                .saturating_mul(1 << exponent.min(16))
+                .min(BACKOFF_MAX),
+        };
+        self.retry_at = Some(now + delay);
+        delay
+    }
+
+    /// Forget recorded failures, after a successful acquisition or a resize
+    pub fn reset(&mut self) {
+        self.failures = 0;
+        self.retry_at = None;
+    }
+
+    /// Number of consecutive failures
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+}
+
+/// Shared flag raised when the GPU device is lost
+///
+/// wgpu reports device loss from its own callbacks, possibly on another
+/// thread; the backend checks the flag at the start of every frame.
+#[derive(Debug, Clone, Default)]
+pub struct DeviceHealth {
+    lost: Arc<AtomicBool>,
+    reason: Arc<Mutex<Option<String>>>,
+}
+
+impl DeviceHealth {
+    /// Create a healthy flag
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Install device-lost and uncaptured-error handlers on `device`
+    ///
+    /// Losses caused by dropping the device ourselves (for example while
+    /// recovering) are ignored.
+    pub fn watch(&self, device: &Device) {
+        let health = self.clone();
+        device.set_device_lost_callback(move |reason, message| match reason {
+            DeviceLostReason::Dropped | DeviceLostReason::ReplacedCallback => {}
+            _ => health.mark_lost(format!("device lost ({:?}): {}", reason, message)),
+        });
+
+        let health = self.clone();
+        device.on_uncaptured_error(Box::new(move |error| match error {
+            wgpu::Error::OutOfMemory { .. } => health.mark_lost(error.to_string()),
+            _ => tracing::error!("Uncaptured GPU error: {}", error),
+        }));
+    }
+
+    /// Flag the device as lost
+    pub fn mark_lost(&self, reason: impl Into<String>) {
+        let reason = reason.into();
+        tracing::warn!("GPU device lost: {}", reason);
+        *self.reason.lock() = Some(reason);
+        self.lost.store(true, Ordering::Release);
+    }
+
+    /// Whether the device has been lost since the last [`take_lost`](Self::take_lost)
+    pub fn is_lost(&self) -> bool {
+        self.lost.load(Ordering::Acquire)
+    }
+
+    /// Clear the flag, returning the loss reason if the device was lost
+    pub fn take_lost(&self) -> Option<String> {
+        if self.lost.swap(false, Ordering::AcqRel) {
+            Some(
+                self.reason
+                    .lock()
+                    .take()
+                    .unwrap_or_else(|| "device lost".to_string()),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+/// A recovery performed by the backend
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryEvent {
+    /// The surface was reconfigured after it became outdated or lost
+    SurfaceReconfigured { reason: String },
+    /// The device and every GPU resource were recreated
+    DeviceRecreated { reason: String },
+}
+
+impl RecoveryEvent {
+    /// Why the recovery happened
+    pub fn reason(&self) -> &str {
+        match self {
+            Self::SurfaceReconfigured { reason } | Self::DeviceRecreated { reason } => reason,
+        }
+    }
+}
+
+/// Counters describing the recoveries performed so far
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryStats {
+    /// Number of times the surface was reconfigured
+    pub surface_reconfigurations: u32,
+    /// Number of times the device was recreated
+    pub device_recoveries: u32,
+    /// When the last recovery happened
+    pub last_recovery: Option<Instant>,
+    /// The last recovery performed
+    pub last_event: Option<RecoveryEvent>,
+}
+
+/// Records recoveries and queues them until the application picks them up
+#[derive(Debug, Default)]
+pub struct RecoveryTracker {
+    stats: RecoveryStats,
+    pending: Vec<RecoveryEvent>,
+}
+
+impl RecoveryTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a recovery
+    pub fn record(&mut self, event: RecoveryEvent) {
+        match &event {
+            RecoveryEvent::SurfaceReconfigured { .. } => self.stats.surface_reconfigurations += 1,
+            RecoveryEvent::DeviceRecreated { .. } => self.stats.device_recoveries += 1,
+        }
+        tracing::info!("GPU recovery: {:?}", event);
+        self.stats.last_recovery = Some(Instant::now());
+        self.stats.last_event = Some(event.clone());
+        self.pending.push(event);
+    }
+
+    /// Recovery counters
+    pub fn stats(&self) -> &RecoveryStats {
+        &self.stats
+    }
+
+    /// Take the recoveries recorded since the last call
+    pub fn take_events(&mut self) -> Vec<RecoveryEvent> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gpu::DeviceManager;
+    use wgpu::Backends;
+
+    #[test]
+    fn test_tracker_counts_and_hands_out_events_once() {
+        let mut tracker = RecoveryTracker::new();
+        tracker.record(RecoveryEvent::SurfaceReconfigured {
+            reason: SurfaceError::Outdated.to_string(),
+        });
+
+        assert_eq!(tracker.stats().surface_reconfigurations, 1);
+        assert_eq!(tracker.stats().device_recoveries, 0);
+        assert!(tracker.stats().last_recovery.is_some());
+
+        let events = tracker.take_events();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            events[0],
+            RecoveryEvent::SurfaceReconfigured { .. }
+        ));
+        assert!(tracker.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_surface_error_actions() {
+        assert_eq!(
+            RecoveryAction::for_surface_error(&SurfaceError::Lost),
+            RecoveryAction::ReconfigureSurface
+        );
+        assert_eq!(
+            RecoveryAction::for_surface_error(&SurfaceError::OutOfMemory),
+            RecoveryAction::RecreateDevice
+        );
+        assert_eq!(
+            RecoveryAction::for_surface_error(&SurfaceError::Timeout),
+            RecoveryAction::SkipFrame
+        );
+    }
+
+    #[test]
+    fn test_backoff_grows_and_resets() {
+        let mut backoff = SurfaceBackoff::new();
+        let now = Instant::now();
+        assert!(!backoff.is_waiting(now));
+
+        // The first failures are retried on the next frame
+        assert_eq!(backoff.record_failure(now), Duration::ZERO);
+        assert_eq!(backoff.record_failure(now), Duration::ZERO);
+        assert!(!backoff.is_waiting(now));
+
+        // Then the delay doubles up to the maximum
+        assert_eq!(backoff.record_failure(now), BACKOFF_BASE);
+        assert!(backoff.is_waiting(now));
+        assert!(!backoff.is_waiting(now + BACKOFF_BASE));
+        assert_eq!(backoff.record_failure(now), BACKOFF_BASE * 2);
+        for _ in 0..40 {
+            backoff.record_failure(now);
+        }
+        assert_eq!(backoff.record_failure(now), BACKOFF_MAX);
+
+        backoff.reset();
+        assert_eq!(backoff.failures(), 0);
+        assert!(!backoff.is_waiting(now));
+    }
+
+    #[test]
+    fn test_health_flag_is_taken_once() {
+        let health = DeviceHealth::new();
+        assert!(health.take_lost().is_none());
+
+        health.clone().mark_lost("driver reset");
+        assert!(health.is_lost());
+        assert_eq!(health.take_lost().as_deref(), Some("driver reset"));
+        assert!(!health.is_lost());
+        assert!(health.take_lost().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_destroyed_device_is_detected() {
+        let dm = DeviceManager::new(Backends::all())
+            .await
+            .expect("Failed to create device manager");
+        let health = DeviceHealth::new();
+        health.watch(dm.device());
+
+        dm.device().destroy();
+        dm.device().poll(wgpu::Maintain::Wait);
+
+        assert!(health.take_lost().is_some());
+    }
+}
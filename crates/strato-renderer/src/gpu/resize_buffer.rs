@@ -0,0 +1,166 @@
+//! Resize buffering
+//!
+//! Reconfiguring the surface on resize throws away what it showed, and until
+//! the next frame is presented the compositor may show the surface cleared,
+//! a flash of the clear color that is most visible on transparent windows
+//! dragged quickly. With a [`ResizeBuffer`] each frame is rendered to an
+//! offscreen texture and blitted to the surface, and the texture is kept: on
+//! resize the last frame is presented again, stretched to the new size, so
+//! the window holds its previous image until the next frame replaces it.
+//!
+//! It costs a surface-sized texture and a blit per frame, so it is off unless
+//! enabled with [`Backend::set_resize_buffering`](crate::backend::Backend::set_resize_buffering).
+
+use crate::post_process::{FullscreenPass, RenderTarget};
+use wgpu::{CommandEncoder, Device, TextureFormat, TextureView};
+
+/// The last rendered frame, kept to cover the surface while it is resized
+#[derive(Default)]
+pub struct ResizeBuffer {
+    frame: Option<RenderTarget>,
+    /// Blit pipeline and the format it was built for
+    blit: Option<(TextureFormat, FullscreenPass)>,
+}
+
+impl ResizeBuffer {
+    /// Create an empty buffer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Size the texture for the next frame
+    ///
+    /// The texture is recreated when the surface size or format changes, so
+    /// the previous frame is only kept until the next one is rendered.
+    pub fn prepare(&mut self, device: &Device, format: TextureFormat, width: u32, height: u32) {
+        let up_to_date = self
+            .frame
+            .as_ref()
+            .is_some_and(|frame| frame.matches(format, width, height));
+        if up_to_date {
+            return;
+        }
+
+        self.frame = Some(RenderTarget::new(
+            device,
+            "Resize Buffer Frame",
+            format,
+            width,
+            height,
+        ));
+        if self.blit.as_ref().map(|(blit_format, _)| *blit_format) != Some(format) {
+            let blit = FullscreenPass::new(
+                device,
+                "Resize Buffer Blit",
+                include_str!("../shaders/blit.wgsl"),
+                format,
+                None,
+            );
+            self.blit = Some((format, blit));
+        }
+    }
+
+    /// The texture the next frame should be rendered into, once prepared
+    pub fn view(&self) -> Option<&TextureView> {
+        self.frame.as_ref().map(|frame| &frame.view)
+    }
+
+    /// Whether a frame is held
+    pub fn has_frame(&self) -> bool {
+        self.frame.is_some()
+    }
+
+    /// Size of the held frame in physical pixels
+    pub fn size(&self) -> Option<(u32, u32)> {
+        self.frame
+            .as_ref()
+            .map(|frame| (frame.texture.width(), frame.texture.height()))
+    }
+
+    /// Record a blit of the held frame onto `surface_view`, stretched to fit
+    ///
+    /// Returns whether a frame was held to blit.
+    pub fn present(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        surface_view: &TextureView,
+    ) -> bool {
+        let (Some(frame), Some((_, blit))) = (&self.frame, &self.blit) else {
+            return false;
+        };
+        blit.draw(device, encoder, &frame.view, surface_view);
+        true
+    }
+
+    /// Drop the held frame and the blit pipeline, e.g. when the device is
+    /// recreated
+    pub fn reset(&mut self) {
+        self.frame = None;
+        self.blit = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::FrameCapture;
+    use crate::gpu::DeviceManager;
+
+    #[tokio::test]
+    async fn test_held_frame_covers_every_size_of_a_rapid_resize() {
+        let dm = DeviceManager::new(wgpu::Backends::all())
+            .await
+            .expect("Failed to create device manager");
+        let (device, queue) = (dm.device(), dm.queue());
+        let format = TextureFormat::Rgba8Unorm;
+
+        let mut buffer = ResizeBuffer::new();
+        assert!(!buffer.has_frame());
+        buffer.prepare(device, format, 8, 8);
+        assert_eq!(buffer.size(), Some((8, 8)));
+
+        // The last frame drawn before the resize is solid red
+        let pixels = [255u8, 0, 0, 255].repeat(8 * 8);
+        queue.write_texture(
+            buffer.frame.as_ref().unwrap().texture.as_image_copy(),
+            &pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(8 * 4),
+                rows_per_image: Some(8),
+            },
+            wgpu::Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        // Each reconfigured surface shows the held frame, not the clear color
+        for (width, height) in [(12, 6), (5, 9), (16, 16), (7, 7)] {
+            let surface = RenderTarget::new(device, "Test Surface", format, width, height);
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("Resize Buffer Test"),
+            });
+            assert!(buffer.present(device, &mut encoder, &surface.view));
+            let capture = FrameCapture::record(device, &mut encoder, &surface.texture).unwrap();
+            queue.submit(std::iter::once(encoder.finish()));
+            let image = capture.wait(device).unwrap();
+
+            for y in 0..height as usize {
+                for x in 0..width as usize {
+                    let i = (y * width as usize + x) * 4;
+                    assert_eq!(
+                        &image.data[i..i + 4],
+                        &[255, 0, 0, 255],
+                        "pixel ({x}, {y}) of the {width}x{height} surface"
+                    );
+                }
+            }
+        }
+
+        buffer.reset();
+        assert!(!buffer.has_frame());
+    }
+}
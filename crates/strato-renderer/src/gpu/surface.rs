@@ -2,18 +2,173 @@
 //!
 //! BLOCCO 2: Surface Configuration
 //! Handles wgpu Surface creation, configuration, and resize management
+//!
+//! # Color space
+//!
+//! Colors are specified in sRGB, so `Color::rgb(0.2, 0.4, 0.6)` should land
+//! on screen as the bytes `(51, 102, 153)` whatever format the platform's
+//! surface uses. Frames are rendered in an sRGB format whenever possible
+//! (see [`select_surface_format`]), and vertex colors are converted to the
+//! [`OutputColorSpace`] of the render format before they are uploaded.
 
 use std::sync::Arc;
+use strato_core::types::Color;
 use wgpu::{
-    Adapter, Device, PresentMode, Surface, SurfaceConfiguration, SurfaceError, SurfaceTexture,
-    TextureFormat, TextureUsages,
+    Adapter, CompositeAlphaMode, Device, DownlevelFlags, PresentMode, Surface,
+    SurfaceConfiguration, SurfaceError, SurfaceTexture, TextureFormat, TextureUsages, TextureView,
+    TextureViewDescriptor,
+};
+
+/// Pick `requested` if the surface supports it, otherwise the closest supported mode
+///
+/// `AutoVsync` and `AutoNoVsync` are resolved by wgpu itself and always
+/// accepted. Uncapped modes fall back to each other, then to `Fifo`, which
+/// every surface supports.
+pub fn resolve_present_mode(requested: PresentMode, supported: &[PresentMode]) -> PresentMode {
+    let fallbacks: &[PresentMode] = match requested {
+        PresentMode::AutoVsync | PresentMode::AutoNoVsync => return requested,
+        _ if supported.contains(&requested) => return requested,
+        PresentMode::Immediate => &[PresentMode::Mailbox],
+        PresentMode::Mailbox => &[PresentMode::Immediate],
+        PresentMode::FifoRelaxed | PresentMode::Fifo => &[],
+    };
+
+    fallbacks
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(PresentMode::Fifo)
+}
+
+/// Present mode used for a vsync setting
+pub fn vsync_present_mode(vsync: bool) -> PresentMode {
+    if vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    }
+}
+
+/// The color frames are cleared to unless the application sets one
+pub const DEFAULT_CLEAR_COLOR: Color = Color {
+    r: 0.1,
+    g: 0.1,
+    b: 0.1,
+    a: 1.0,
 };
 
+/// Color space the fragment shader has to write in for a render target
+///
+/// Colors throughout the toolkit are sRGB, as written in hex codes and
+/// themes. An sRGB target encodes what the shader writes and a float target
+/// stores linear light, so both need linear colors; any other target stores
+/// the values unchanged and needs them sRGB encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputColorSpace {
+    /// Linear light, encoded by the GPU if needed
+    Linear,
+    /// sRGB encoded values, stored as written
+    Srgb,
+}
+
+impl OutputColorSpace {
+    /// The color space to write in when rendering to `format`
+    pub fn for_format(format: TextureFormat) -> Self {
+        let float = matches!(
+            format,
+            TextureFormat::Rgba16Float | TextureFormat::Rgba32Float | TextureFormat::Rg11b10Float
+        );
+        if format.is_srgb() || float {
+            Self::Linear
+        } else {
+            Self::Srgb
+        }
+    }
+
+    /// Convert an sRGB color to this color space, leaving alpha unchanged
+    pub fn encode(self, [r, g, b, a]: [f32; 4]) -> [f32; 4] {
+        match self {
+            Self::Srgb => [r, g, b, a],
+            Self::Linear => [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a],
+        }
+    }
+
+    /// The render pass clear value for an sRGB `color`
+    ///
+    /// Premultiplied by alpha, like everything the pipeline blends, so a
+    /// translucent clear color composites correctly in transparent windows.
+    pub fn clear_value(self, color: Color) -> wgpu::Color {
+        let [r, g, b, a] = self.encode([color.r, color.g, color.b, color.a]);
+        wgpu::Color {
+            r: (r * a) as f64,
+            g: (g * a) as f64,
+            b: (b * a) as f64,
+            a: a as f64,
+        }
+    }
+}
+
+/// Decode an sRGB color component to linear light
+pub fn srgb_to_linear(component: f32) -> f32 {
+    if component <= 0.04045 {
+        component / 12.92
+    } else {
+        ((component + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Pick the surface format and the format frames are rendered in
+///
+/// Prefers an sRGB surface format. Without one the first format is used,
+/// rendered through an sRGB view of it when `srgb_views` says the backend
+/// allows that, so images and colors are encoded the same way everywhere.
+pub fn select_surface_format(
+    formats: &[TextureFormat],
+    srgb_views: bool,
+) -> (TextureFormat, TextureFormat) {
+    if let Some(srgb) = formats.iter().copied().find(|format| format.is_srgb()) {
+        return (srgb, srgb);
+    }
+    let format = formats[0];
+    let view_format = if srgb_views {
+        format.add_srgb_suffix()
+    } else {
+        format
+    };
+    (format, view_format)
+}
+
+/// Pick how the compositor blends the window with what is behind it
+///
+/// Transparent windows want `PreMultiplied`, matching the pipeline's
+/// output, or `Inherit` to leave it to the platform; opaque windows want
+/// `Opaque`. Falls back to the first supported mode.
+pub fn select_alpha_mode(
+    transparent: bool,
+    supported: &[CompositeAlphaMode],
+) -> CompositeAlphaMode {
+    let preferred: &[CompositeAlphaMode] = if transparent {
+        &[
+            CompositeAlphaMode::PreMultiplied,
+            CompositeAlphaMode::Inherit,
+        ]
+    } else {
+        &[CompositeAlphaMode::Opaque]
+    };
+
+    preferred
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(supported[0])
+}
+
 /// Manages wgpu surface and its configuration
 pub struct SurfaceManager {
     surface: Surface<'static>,
     config: SurfaceConfiguration,
     format: TextureFormat,
+    srgb_views: bool,
 }
 
 impl SurfaceManager {
@@ -39,18 +194,19 @@ impl SurfaceManager {
         let capabilities = surface.get_capabilities(adapter);
 
         // Select best format (prefer sRGB)
-        let format = capabilities
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(capabilities.formats[0]);
-
-        println!("=== SURFACE CONFIGURATION ===");
-        println!("Selected format: {:?}", format);
-        println!("Available formats: {:?}", capabilities.formats);
-        println!("Alpha modes: {:?}", capabilities.alpha_modes);
-        println!("============================");
+        let srgb_views = adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::SURFACE_VIEW_FORMATS);
+        let (surface_format, format) = select_surface_format(&capabilities.formats, srgb_views);
+
+        tracing::debug!(
+            "Surface format {:?}, rendering as {:?} (available formats: {:?}, alpha modes: {:?})",
+            surface_format,
+            format,
+            capabilities.formats,
+            capabilities.alpha_modes
+        );
 
         // Ensure valid dimensions
         let width = width.max(1);
@@ -59,12 +215,16 @@ impl SurfaceManager {
         // Configure surface
         let config = SurfaceConfiguration {
             usage: TextureUsages::RENDER_ATTACHMENT,
-            format,
+            format: surface_format,
             width,
             height,
             present_mode: PresentMode::AutoVsync,
-            alpha_mode: capabilities.alpha_modes[0],
-            view_formats: vec![],
+            alpha_mode: select_alpha_mode(false, &capabilities.alpha_modes),
+            view_formats: if format == surface_format {
+                vec![]
+            } else {
+                vec![format]
+            },
             desired_maximum_frame_latency: 2,
         };
 
@@ -76,6 +236,7 @@ impl SurfaceManager {
             surface,
             config,
             format,
+            srgb_views,
         })
     }
 
@@ -103,16 +264,120 @@ impl SurfaceManager {
         Ok(())
     }
 
+    /// Configure the surface again with its current settings
+    ///
+    /// Used after the surface reports `Outdated` or `Lost`.
+    pub fn reconfigure(&mut self, device: &Device) {
+        self.surface.configure(device, &self.config);
+    }
+
+    /// Present modes the surface supports on `adapter`
+    pub fn supported_present_modes(&self, adapter: &Adapter) -> Vec<PresentMode> {
+        self.surface.get_capabilities(adapter).present_modes
+    }
+
+    /// Get the current present mode
+    pub fn present_mode(&self) -> PresentMode {
+        self.config.present_mode
+    }
+
+    /// Switch present mode and reconfigure the surface
+    ///
+    /// Falls back to a supported mode (see [`resolve_present_mode`]) and
+    /// returns the mode actually applied.
+    pub fn set_present_mode(
+        &mut self,
+        mode: PresentMode,
+        device: &Device,
+        adapter: &Adapter,
+    ) -> PresentMode {
+        let resolved = resolve_present_mode(mode, &self.supported_present_modes(adapter));
+        if resolved != mode {
+            tracing::warn!(
+                "Present mode {:?} not supported, using {:?}",
+                mode,
+                resolved
+            );
+        }
+
+        self.config.present_mode = resolved;
+        self.surface.configure(device, &self.config);
+        resolved
+    }
+
+    /// Configure the surface for a transparent or an opaque window
+    ///
+    /// A transparent window is composited with premultiplied alpha and, where
+    /// the backend allows it, rendered without sRGB encoding: compositors
+    /// blend the stored values directly, so blending has to happen in sRGB
+    /// space for a 50% white to come out halfway between the desktop and
+    /// white. Returns the alpha mode applied; pipelines must be recreated for
+    /// the new [`format`](Self::format).
+    pub fn set_transparent(
+        &mut self,
+        transparent: bool,
+        device: &Device,
+        adapter: &Adapter,
+    ) -> CompositeAlphaMode {
+        let supported = self.surface.get_capabilities(adapter).alpha_modes;
+        let alpha_mode = select_alpha_mode(transparent, &supported);
+        if transparent && alpha_mode == CompositeAlphaMode::Opaque {
+            tracing::warn!("Surface does not support transparency, rendering opaque");
+        }
+
+        let surface_format = self.config.format;
+        let view_format = if transparent {
+            surface_format.remove_srgb_suffix()
+        } else {
+            surface_format.add_srgb_suffix()
+        };
+        self.format = if self.srgb_views {
+            view_format
+        } else {
+            surface_format
+        };
+        self.config.view_formats = if self.format == surface_format {
+            vec![]
+        } else {
+            vec![self.format]
+        };
+        self.config.alpha_mode = alpha_mode;
+        self.surface.configure(device, &self.config);
+        alpha_mode
+    }
+
+    /// How the compositor blends the window with what is behind it
+    pub fn alpha_mode(&self) -> CompositeAlphaMode {
+        self.config.alpha_mode
+    }
+
     /// Get current surface texture for rendering
     pub fn get_current_texture(&mut self) -> Result<SurfaceTexture, SurfaceError> {
         self.surface.get_current_texture()
     }
 
-    /// Get surface format
+    /// Format frames are rendered in
+    ///
+    /// The surface format itself, or an sRGB view of it when the surface has
+    /// no sRGB format (see [`select_surface_format`]). Pipelines targeting
+    /// the surface must use this format.
     pub fn format(&self) -> TextureFormat {
         self.format
     }
 
+    /// Color space vertex colors have to be converted to for this surface
+    pub fn color_space(&self) -> OutputColorSpace {
+        OutputColorSpace::for_format(self.format)
+    }
+
+    /// A view of `frame` in the [render format](Self::format)
+    pub fn create_view(&self, frame: &SurfaceTexture) -> TextureView {
+        frame.texture.create_view(&TextureViewDescriptor {
+            format: Some(self.format),
+            ..Default::default()
+        })
+    }
+
     /// Get surface width
     pub fn width(&self) -> u32 {
         self.config.width
@@ -202,6 +467,226 @@ mod tests {
         assert!(result_valid.is_ok());
     }
 
+    #[test]
+    fn test_present_mode_fallback() {
+        let fifo_only = [PresentMode::Fifo];
+        let all = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+        ];
+
+        assert_eq!(
+            resolve_present_mode(PresentMode::Mailbox, &all),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::Immediate, &fifo_only),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            resolve_present_mode(
+                PresentMode::Immediate,
+                &[PresentMode::Fifo, PresentMode::Mailbox]
+            ),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::FifoRelaxed, &fifo_only),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::AutoNoVsync, &fifo_only),
+            PresentMode::AutoNoVsync
+        );
+        assert_eq!(vsync_present_mode(false), PresentMode::AutoNoVsync);
+    }
+
+    #[test]
+    fn test_surface_format_prefers_srgb() {
+        let formats = [TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb];
+        assert_eq!(
+            select_surface_format(&formats, true),
+            (TextureFormat::Bgra8UnormSrgb, TextureFormat::Bgra8UnormSrgb)
+        );
+
+        // Without an sRGB format, render through an sRGB view if allowed
+        let linear = [TextureFormat::Rgba8Unorm, TextureFormat::Rgba16Float];
+        assert_eq!(
+            select_surface_format(&linear, true),
+            (TextureFormat::Rgba8Unorm, TextureFormat::Rgba8UnormSrgb)
+        );
+        assert_eq!(
+            select_surface_format(&linear, false),
+            (TextureFormat::Rgba8Unorm, TextureFormat::Rgba8Unorm)
+        );
+    }
+
+    #[test]
+    fn test_color_space_follows_format() {
+        use OutputColorSpace::{Linear, Srgb};
+        assert_eq!(
+            OutputColorSpace::for_format(TextureFormat::Bgra8UnormSrgb),
+            Linear
+        );
+        assert_eq!(
+            OutputColorSpace::for_format(TextureFormat::Rgba16Float),
+            Linear
+        );
+        assert_eq!(
+            OutputColorSpace::for_format(TextureFormat::Bgra8Unorm),
+            Srgb
+        );
+
+        let color = [0.5, 0.0, 1.0, 0.5];
+        assert_eq!(Srgb.encode(color), color);
+        let [r, g, b, a] = Linear.encode(color);
+        assert!((r - 0.214).abs() < 1e-3);
+        assert_eq!([g, b, a], [0.0, 1.0, 0.5]);
+    }
+
+    /// Render a quad of `color` over `clear` into a 4x4 texture of `format`
+    /// and read it back
+    async fn render_color(
+        dm: &DeviceManager,
+        format: TextureFormat,
+        clear: wgpu::Color,
+        color: Color,
+    ) -> Vec<u8> {
+        use crate::capture::FrameCapture;
+        use crate::gpu::{BufferManager, PipelineManager, ShaderManager, SimpleVertex};
+
+        let device = dm.device();
+        let shader = ShaderManager::from_wgsl(
+            device,
+            include_str!("../shaders/simple.wgsl"),
+            Some("Test Shader"),
+        )
+        .unwrap();
+        let mut buffer_mgr = BufferManager::new(device);
+        let texture_mgr = crate::gpu::TextureManager::new(device, dm.queue());
+        let pipeline_mgr =
+            PipelineManager::new(device, &shader, &buffer_mgr, &texture_mgr, format).unwrap();
+
+        let projection = glam::Mat4::orthographic_rh(0.0, 4.0, 4.0, 0.0, -1.0, 1.0);
+        buffer_mgr.upload_projection(dm.queue(), &projection.to_cols_array_2d());
+        let vertex_color =
+            OutputColorSpace::for_format(format).encode([color.r, color.g, color.b, color.a]);
+        let vertices: Vec<SimpleVertex> = [[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]]
+            .into_iter()
+            .map(|position| SimpleVertex {
+                position,
+                color: vertex_color,
+                uv: [0.0, 0.0],
+                params: [0.0; 4],
+                flags: 0,
+            })
+            .collect();
+        buffer_mgr.upload_vertices(device, dm.queue(), &vertices);
+        buffer_mgr.upload_indices(device, dm.queue(), &[0, 1, 2, 0, 2, 3]);
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Color Target"),
+            size: wgpu::Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&TextureViewDescriptor::default());
+
+        let mut encoder = device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(pipeline_mgr.pipeline());
+            pass.set_bind_group(0, pipeline_mgr.bind_group(), &[]);
+            pass.set_vertex_buffer(0, buffer_mgr.vertex_buffer().slice(..));
+            pass.set_index_buffer(
+                buffer_mgr.index_buffer().slice(..),
+                wgpu::IndexFormat::Uint32,
+            );
+            pass.draw_indexed(0..6, 0, 0..1);
+        }
+        let capture = FrameCapture::record(device, &mut encoder, &texture).unwrap();
+        dm.queue().submit(std::iter::once(encoder.finish()));
+        capture.wait(device).unwrap().data
+    }
+
+    #[tokio::test]
+    async fn test_color_reads_back_the_same_on_srgb_and_linear_targets() {
+        let dm = DeviceManager::new(Backends::all()).await.unwrap();
+        let color = Color::rgb(0.2, 0.4, 0.6);
+        let expected = [51, 102, 153, 255];
+
+        for format in [TextureFormat::Rgba8UnormSrgb, TextureFormat::Rgba8Unorm] {
+            let pixels = render_color(&dm, format, wgpu::Color::BLACK, color).await;
+            for pixel in pixels.chunks_exact(4) {
+                let off = pixel
+                    .iter()
+                    .zip(expected)
+                    .any(|(&got, want)| got.abs_diff(want) > 1);
+                assert!(!off, "{format:?} rendered {pixel:?}, expected {expected:?}");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_half_transparent_white_is_premultiplied() {
+        // A transparent window renders without sRGB encoding, cleared to
+        // transparent (see `SurfaceManager::set_transparent`)
+        let dm = DeviceManager::new(Backends::all()).await.unwrap();
+        let pixels = render_color(
+            &dm,
+            TextureFormat::Rgba8Unorm,
+            wgpu::Color::TRANSPARENT,
+            Color::rgba(1.0, 1.0, 1.0, 0.5),
+        )
+        .await;
+
+        // Composited over a dark desktop, as the compositor does it
+        let desktop = 32u8;
+        for pixel in pixels.chunks_exact(4) {
+            assert!(
+                pixel.iter().all(|&value| value.abs_diff(128) <= 1),
+                "{pixel:?}"
+            );
+            let alpha = pixel[3] as f32 / 255.0;
+            let shown = pixel[0] as f32 + (1.0 - alpha) * desktop as f32;
+            assert!((shown - 144.0).abs() <= 1.5, "composited to {shown}");
+        }
+    }
+
+    #[test]
+    fn test_alpha_mode_selection() {
+        use CompositeAlphaMode::{Inherit, Opaque, PostMultiplied, PreMultiplied};
+        let all = [Opaque, PreMultiplied, PostMultiplied, Inherit];
+        assert_eq!(select_alpha_mode(true, &all), PreMultiplied);
+        assert_eq!(select_alpha_mode(false, &all), Opaque);
+        assert_eq!(select_alpha_mode(true, &[Opaque, Inherit]), Inherit);
+        assert_eq!(select_alpha_mode(true, &[Opaque]), Opaque);
+        assert_eq!(select_alpha_mode(false, &[Inherit]), Inherit);
+    }
+
     // Helper function for unit testing dimension validation
     fn validate_dimensions(width: u32, height: u32) -> anyhow::Result<()> {
         if width == 0 || height == 0 {
@@ -0,0 +1,278 @@
+//! Shaped text run cache
+//!
+//! BLOCCO 10: Text Layout Cache
+//! Laying out a label walks every character, looks its glyph up in the atlas
+//! and computes the quad it covers. For labels that stay the same from frame
+//! to frame the result is identical, so the shaped run (quad offsets, sizes
+//! and atlas UVs) is kept and only translated and tinted when drawn.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Shaped runs kept by default
+pub const DEFAULT_TEXT_CACHE_CAPACITY: usize = 1024;
+
+/// One glyph quad of a shaped run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapedGlyph {
+    /// Top-left corner, relative to the run origin, before alignment
+    pub offset: [f32; 2],
+    /// Quad size in logical pixels
+    pub size: [f32; 2],
+    /// Atlas UVs as (u0, v0, u1, v1)
+    pub uv_rect: (f32, f32, f32, f32),
+}
+
+/// Glyph quads of a laid out string
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShapedRun {
+    pub glyphs: Vec<ShapedGlyph>,
+    /// Advance width, used to align the run
+    pub width: f32,
+}
+
+/// Everything a shaped run depends on besides the text
+///
+/// The atlas rasterizes a single font, so the font size identifies the face.
+/// Floats are compared by bit pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RunKey {
+    text_hash: u64,
+    font_size: u32,
+    letter_spacing: u32,
+    scale_factor: u32,
+}
+
+impl RunKey {
+    fn new(text: &str, font_size: f32, letter_spacing: f32, scale_factor: f32) -> Self {
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        Self {
+            text_hash: hasher.finish(),
+            font_size: font_size.to_bits(),
+            letter_spacing: letter_spacing.to_bits(),
+            scale_factor: scale_factor.to_bits(),
+        }
+    }
+}
+
+struct CachedRun {
+    // Kept to tell hash collisions apart
+    text: Box<str>,
+    run: ShapedRun,
+    last_used: u64,
+}
+
+/// Text cache counters
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl TextCacheStats {
+    /// Fraction of lookups served from the cache, 0.0 before any lookup
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Least-recently-used cache of shaped text runs
+///
+/// Runs hold atlas UVs, so the cache must be cleared whenever the glyph atlas
+/// is recreated, and whenever the scale factor changes since glyphs are then
+/// rasterized at a different size.
+pub struct TextLayoutCache {
+    runs: HashMap<RunKey, CachedRun>,
+    capacity: usize,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl TextLayoutCache {
+    /// Create a cache holding up to `capacity` runs, at least one
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            runs: HashMap::new(),
+            capacity: capacity.max(1),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    /// Return the cached run for `text`, shaping and caching it on a miss
+    pub fn get_or_shape(
+        &mut self,
+        text: &str,
+        font_size: f32,
+        letter_spacing: f32,
+        scale_factor: f32,
+        shape: impl FnOnce() -> ShapedRun,
+    ) -> &ShapedRun {
+        self.tick += 1;
+        let key = RunKey::new(text, font_size, letter_spacing, scale_factor);
+
+        let hit = self
+            .runs
+            .get(&key)
+            .is_some_and(|cached| &*cached.text == text);
+        if hit {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+            let run = shape();
+            if !self.runs.contains_key(&key) && self.runs.len() >= self.capacity {
+                self.evict_least_recent();
+            }
+            self.runs.insert(
+                key,
+                CachedRun {
+                    text: text.into(),
+                    run,
+                    last_used: 0,
+                },
+            );
+        }
+
+        let cached = self.runs.get_mut(&key).expect("run was just cached");
+        cached.last_used = self.tick;
+        &cached.run
+    }
+
+    fn evict_least_recent(&mut self) {
+        let oldest = self
+            .runs
+            .iter()
+            .min_by_key(|(_, cached)| cached.last_used)
+            .map(|(key, _)| *key);
+        if let Some(key) = oldest {
+            self.runs.remove(&key);
+            self.evictions += 1;
+        }
+    }
+
+    /// Change the number of runs kept, evicting the least recently used ones
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity.max(1);
+        while self.runs.len() > self.capacity {
+            self.evict_least_recent();
+        }
+    }
+
+    /// Maximum number of runs kept
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Drop every cached run, keeping the counters
+    pub fn clear(&mut self) {
+        self.runs.clear();
+    }
+
+    /// Current counters
+    pub fn stats(&self) -> TextCacheStats {
+        TextCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            evictions: self.evictions,
+            entries: self.runs.len(),
+            capacity: self.capacity,
+        }
+    }
+}
+
+impl Default for TextLayoutCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_TEXT_CACHE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(width: f32) -> ShapedRun {
+        ShapedRun {
+            glyphs: vec![ShapedGlyph {
+                offset: [0.0, 0.0],
+                size: [width, 10.0],
+                uv_rect: (0.0, 0.0, 0.1, 0.1),
+            }],
+            width,
+        }
+    }
+
+    #[test]
+    fn test_unchanged_label_is_shaped_once() {
+        let mut cache = TextLayoutCache::new(8);
+        let mut shaped = 0;
+        for _ in 0..3 {
+            cache.get_or_shape("Revenue", 14.0, 0.0, 1.0, || {
+                shaped += 1;
+                run(50.0)
+            });
+        }
+
+        assert_eq!(shaped, 1);
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+        assert!((stats.hit_rate() - 2.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_changed_text_size_or_scale_is_reshaped() {
+        let mut cache = TextLayoutCache::new(8);
+        cache.get_or_shape("42", 14.0, 0.0, 1.0, || run(10.0));
+        cache.get_or_shape("43", 14.0, 0.0, 1.0, || run(11.0));
+        cache.get_or_shape("42", 16.0, 0.0, 1.0, || run(12.0));
+        let width = cache.get_or_shape("42", 14.0, 0.0, 2.0, || run(13.0)).width;
+
+        assert_eq!(width, 13.0);
+        assert_eq!(cache.stats().misses, 4);
+        assert_eq!(cache.stats().entries, 4);
+    }
+
+    #[test]
+    fn test_least_recently_used_run_is_evicted() {
+        let mut cache = TextLayoutCache::new(2);
+        cache.get_or_shape("a", 14.0, 0.0, 1.0, || run(1.0));
+        cache.get_or_shape("b", 14.0, 0.0, 1.0, || run(2.0));
+        cache.get_or_shape("a", 14.0, 0.0, 1.0, || run(1.0));
+        cache.get_or_shape("c", 14.0, 0.0, 1.0, || run(3.0));
+
+        let mut reshaped = false;
+        cache.get_or_shape("a", 14.0, 0.0, 1.0, || {
+            reshaped = true;
+            run(1.0)
+        });
+        assert!(!reshaped);
+        assert_eq!(cache.stats().evictions, 1);
+
+        cache.set_capacity(1);
+        assert_eq!(cache.stats().entries, 1);
+    }
+
+    #[test]
+    fn test_clear_drops_runs() {
+        let mut cache = TextLayoutCache::default();
+        cache.get_or_shape("a", 14.0, 0.0, 1.0, || run(1.0));
+        cache.clear();
+        cache.get_or_shape("a", 14.0, 0.0, 1.0, || run(1.0));
+
+        assert_eq!(cache.stats().misses, 2);
+        assert_eq!(cache.capacity(), DEFAULT_TEXT_CACHE_CAPACITY);
+    }
+}
@@ -3,6 +3,8 @@
 //! BLOCCO 8: Texture Management
 //! Handles texture atlas creation, glyph caching, and texture binding
 
+use super::hinting::{hint_outline, TextHinting};
+use ab_glyph::{point, Font as _, FontRef, Glyph, PxScale, PxScaleFactor};
 use anyhow::Result;
 use std::collections::HashMap;
 use wgpu::{
@@ -40,6 +42,7 @@ pub struct CachedImage {
 pub struct GlyphKey {
     pub character: char,
     pub font_size: u32, // Size in pixels
+    pub hinting: TextHinting,
 }
 
 /// Glyph cache for fast lookup
@@ -72,8 +75,13 @@ impl GlyphCache {
 }
 
 /// Glyph rasterizer using fontdue
+///
+/// Hinted glyphs are rasterized from their outlines with ab_glyph, see
+/// [`hinting`](super::hinting); their advances still come from fontdue.
 pub struct GlyphRasterizer {
     pub font: fontdue::Font,
+    outlines: FontRef<'static>,
+    hinting: TextHinting,
 }
 
 impl GlyphRasterizer {
@@ -84,14 +92,33 @@ impl GlyphRasterizer {
 
         let font = fontdue::Font::from_bytes(FONT_DATA, fontdue::FontSettings::default())
             .map_err(|e| anyhow::anyhow!("Failed to load font: {}", e))?;
+        let outlines = FontRef::try_from_slice(FONT_DATA)
+            .map_err(|e| anyhow::anyhow!("Failed to load font outlines: {}", e))?;
 
         println!("=== GLYPH RASTERIZER INITIALIZED ===");
 
-        Ok(Self { font })
+        Ok(Self {
+            font,
+            outlines,
+            hinting: TextHinting::default(),
+        })
+    }
+
+    /// Set how outlines are fitted to the pixel grid
+    pub fn set_hinting(&mut self, hinting: TextHinting) {
+        self.hinting = hinting;
+    }
+
+    /// How outlines are fitted to the pixel grid
+    pub fn hinting(&self) -> TextHinting {
+        self.hinting
     }
 
     /// Rasterize a character at given size
     pub fn rasterize(&self, character: char, size: f32) -> Option<(Vec<u8>, GlyphMetrics)> {
+        if self.hinting == TextHinting::Light {
+            return self.rasterize_hinted(character, size);
+        }
         let (metrics, bitmap) = self.font.rasterize(character, size);
 
         if metrics.width == 0 || metrics.height == 0 {
@@ -114,6 +141,49 @@ impl GlyphRasterizer {
 
         Some((rgba_data, glyph_metrics))
     }
+
+    fn rasterize_hinted(&self, character: char, size: f32) -> Option<(Vec<u8>, GlyphMetrics)> {
+        let id = self.outlines.glyph_id(character);
+        let mut outline = self.outlines.outline(id)?;
+        // Sizes are em sizes, as for fontdue
+        let scale = size / self.outlines.units_per_em()?;
+        hint_outline(&mut outline, scale);
+
+        let glyph = Glyph {
+            id,
+            scale: PxScale::from(size),
+            position: point(0.0, 0.0),
+        };
+        let outlined = ab_glyph::OutlinedGlyph::new(
+            glyph,
+            outline,
+            PxScaleFactor {
+                horizontal: scale,
+                vertical: scale,
+            },
+        );
+        let bounds = outlined.px_bounds();
+        let (width, height) = (bounds.width() as u32, bounds.height() as u32);
+        if width == 0 || height == 0 {
+            return None;
+        }
+
+        let mut rgba_data = vec![255u8; (width * height * 4) as usize];
+        outlined.draw(|x, y, coverage| {
+            let alpha = (coverage.clamp(0.0, 1.0) * 255.0).round() as u8;
+            rgba_data[((y * width + x) * 4 + 3) as usize] = alpha;
+        });
+
+        let glyph_metrics = GlyphMetrics {
+            width,
+            height,
+            bearing_x: bounds.min.x as i32,
+            bearing_y: -bounds.min.y as i32,
+            advance: self.font.metrics(character, size).advance_width,
+        };
+
+        Some((rgba_data, glyph_metrics))
+    }
 }
 
 /// Texture atlas for efficient texture management
@@ -326,6 +396,19 @@ impl TextureManager {
         }
     }
 
+    /// Set how glyphs rasterized from now on are fitted to the pixel grid
+    ///
+    /// Glyphs are cached per hinting mode, so switching back reuses the
+    /// glyphs rasterized before.
+    pub fn set_hinting(&mut self, hinting: TextHinting) {
+        self.rasterizer.set_hinting(hinting);
+    }
+
+    /// How glyphs are fitted to the pixel grid
+    pub fn hinting(&self) -> TextHinting {
+        self.rasterizer.hinting()
+    }
+
     /// Get or cache a glyph, rasterizing if needed
     pub fn get_or_cache_glyph(
         &mut self,
@@ -336,6 +419,7 @@ impl TextureManager {
         let key = GlyphKey {
             character,
             font_size,
+            hinting: self.rasterizer.hinting(),
         };
 
         // Check cache first
@@ -450,14 +534,17 @@ mod tests {
         let key1 = GlyphKey {
             character: 'A',
             font_size: 24,
+            hinting: TextHinting::None,
         };
         let key2 = GlyphKey {
             character: 'A',
             font_size: 24,
+            hinting: TextHinting::None,
         };
         let key3 = GlyphKey {
             character: 'B',
             font_size: 24,
+            hinting: TextHinting::None,
         };
 
         assert_eq!(key1, key2);
@@ -471,6 +558,7 @@ mod tests {
         let key = GlyphKey {
             character: 'A',
             font_size: 24,
+            hinting: TextHinting::None,
         };
         let glyph = CachedGlyph {
             metrics: GlyphMetrics {
@@ -502,6 +590,37 @@ mod tests {
         assert_eq!(data.len(), (metrics.width * metrics.height * 4) as usize);
     }
 
+    /// Share of the ink of `text` at `size` drawn in partially covered pixels
+    fn blur(rasterizer: &GlyphRasterizer, text: &str, size: f32) -> f32 {
+        let (mut partial, mut ink) = (0.0, 0.0);
+        for ch in text.chars() {
+            let (data, _) = rasterizer.rasterize(ch, size).unwrap();
+            for alpha in data.chunks(4).map(|pixel| pixel[3] as f32 / 255.0) {
+                partial += alpha.min(1.0 - alpha);
+                ink += alpha;
+            }
+        }
+        partial / ink
+    }
+
+    #[test]
+    fn test_light_hinting_sharpens_small_text() {
+        let mut rasterizer = GlyphRasterizer::new().unwrap();
+        for size in [11.0, 12.0, 14.0] {
+            rasterizer.set_hinting(TextHinting::None);
+            let before = blur(&rasterizer, "HElTnmh", size);
+            let (_, plain) = rasterizer.rasterize('H', size).unwrap();
+
+            rasterizer.set_hinting(TextHinting::Light);
+            let after = blur(&rasterizer, "HElTnmh", size);
+            let (_, hinted) = rasterizer.rasterize('H', size).unwrap();
+
+            assert!(after < before * 0.8, "{size}px: {before} -> {after}");
+            // Hinting moves edges, not the pen
+            assert_eq!(hinted.advance, plain.advance);
+        }
+    }
+
     #[tokio::test]
     #[ignore] // TODO: Fix shelf packing test expectations
     async fn test_atlas_allocation() {
@@ -20,12 +20,16 @@ use wgpu::*;
 
 use crate::{
     buffer::BufferManager,
-    device::{DeviceManager, ManagedDevice},
+    capture::{FrameCapture, ImageData},
+    device::{AdapterId, DeviceManager, ManagedDevice},
+    gpu::surface::{
+        resolve_present_mode, vsync_present_mode, OutputColorSpace, DEFAULT_CLEAR_COLOR,
+    },
     memory::{AllocationStrategy, MemoryManager},
     pipeline::PipelineManager,
     profiler::{PerformanceReport, Profiler},
     resources::{ResourceHandle, ResourceManager},
-    shader::{CompiledShader, ShaderManager},
+    shader::{CompiledShader, HotReloadEvent, ShaderManager},
 };
 
 /// Configuration for the integrated renderer system
@@ -45,10 +49,16 @@ pub struct RendererConfig {
     pub enable_shader_hot_reload: bool,
     /// Preferred GPU adapter type
     pub preferred_adapter: Option<PowerPreference>,
+    /// Specific adapter to use, see [`RendererBuilder::with_adapter`]
+    pub forced_adapter: Option<AdapterId>,
     /// Enable validation layers
     pub enable_validation: bool,
     /// Maximum number of frames in flight
     pub max_frames_in_flight: u32,
+    /// Initial vsync setting, see [`IntegratedRenderer::set_vsync`]
+    pub vsync: bool,
+    /// Color frames are cleared to, see [`IntegratedRenderer::set_clear_color`]
+    pub clear_color: strato_core::types::Color,
 }
 
 impl Default for RendererConfig {
@@ -61,8 +71,11 @@ impl Default for RendererConfig {
             max_memory_pool_size: 512 * 1024 * 1024, // 512MB
             enable_shader_hot_reload: cfg!(debug_assertions),
             preferred_adapter: Some(PowerPreference::HighPerformance),
+            forced_adapter: None,
             enable_validation: cfg!(debug_assertions),
             max_frames_in_flight: 2,
+            vsync: true,
+            clear_color: DEFAULT_CLEAR_COLOR,
         }
     }
 }
@@ -89,6 +102,9 @@ pub struct IntegratedRenderer {
     // State
     initialized: bool,
     frame_count: u64,
+    present_mode: PresentMode,
+    pending_capture: Option<FrameCapture>,
+    shader_reloads: Vec<HotReloadEvent>,
 }
 
 /// Render context for a single frame
@@ -97,6 +113,7 @@ pub struct RenderContext {
     pub encoder: CommandEncoder,
     pub profiler: Option<Arc<Profiler>>,
     pub frame_id: u64,
+    clear_color: strato_core::types::Color,
 
     // Timing queries
     gpu_timer_id: Option<u32>,
@@ -131,7 +148,10 @@ impl IntegratedRenderer {
         let device_manager = Arc::new(DeviceManager::new(instance, surface).await?);
 
         // Configure device selection based on renderer config
-        let mut criteria = crate::device::DeviceSelectionCriteria::default();
+        let mut criteria = crate::device::DeviceSelectionCriteria {
+            forced_adapter: config.forced_adapter,
+            ..Default::default()
+        };
 
         // Check feature support
         let has_timestamp = device_manager
@@ -199,6 +219,8 @@ impl IntegratedRenderer {
             None
         };
 
+        let present_mode = vsync_present_mode(config.vsync);
+
         Ok(Self {
             device_manager,
             device,
@@ -211,6 +233,9 @@ impl IntegratedRenderer {
             config,
             initialized: false,
             frame_count: 0,
+            present_mode,
+            pending_capture: None,
+            shader_reloads: Vec::new(),
         })
     }
 
@@ -248,6 +273,24 @@ impl IntegratedRenderer {
 
         self.frame_count += 1;
 
+        // Rotate per-frame dynamic buffers
+        self.buffer_manager.begin_frame();
+
+        // Recompile shaders edited since the last frame
+        if self.config.enable_shader_hot_reload {
+            match self.shader_manager.check_for_reloads() {
+                Ok(events) => self
+                    .shader_reloads
+                    .extend(events.into_iter().filter(|event| {
+                        matches!(
+                            event,
+                            HotReloadEvent::ShaderReloaded(..) | HotReloadEvent::ReloadFailed(..)
+                        )
+                    })),
+                Err(e) => warn!("Shader hot-reload check failed: {}", e),
+            }
+        }
+
         // Begin profiling if enabled
         if let Some(ref profiler) = self.profiler {
             profiler.begin_frame();
@@ -274,6 +317,7 @@ impl IntegratedRenderer {
             encoder,
             profiler: self.profiler.clone(),
             frame_id: self.frame_count,
+            clear_color: self.config.clear_color,
             gpu_timer_id,
         })
     }
@@ -290,6 +334,11 @@ impl IntegratedRenderer {
         let command_buffer = context.encoder.finish();
         self.device.queue.submit(std::iter::once(command_buffer));
 
+        // Start reading back a capture recorded in this frame
+        if let Some(capture) = &mut self.pending_capture {
+            capture.submitted();
+        }
+
         // End profiling if enabled
         if let Some(ref profiler) = self.profiler {
             profiler.end_frame();
@@ -313,11 +362,11 @@ impl IntegratedRenderer {
                 let report = profiler.get_performance_report();
                 (
                     report.frame_stats.average_frame_time,
-                    0, // Would need to track in shader manager
+                    self.shader_manager.get_stats().hot_reloads as u32,
                     0, // Would need to track in pipeline manager
                 )
             } else {
-                (0.0, 0, 0)
+                (0.0, self.shader_manager.get_stats().hot_reloads as u32, 0)
             };
 
         RenderStats {
@@ -350,6 +399,28 @@ impl IntegratedRenderer {
         self.buffer_manager.create_buffer(&config)
     }
 
+    /// Create a buffer rewritten every frame, e.g. for per-frame geometry
+    ///
+    /// Each frame in flight gets its own buffer from the buffer pool.
+    pub fn create_dynamic_buffer(&self, size: u64, usage: BufferUsages) -> Result<ResourceHandle> {
+        let config = crate::buffer::BufferConfig {
+            name: "dynamic_buffer".to_string(),
+            size,
+            usage: usage | BufferUsages::COPY_DST,
+            usage_pattern: crate::buffer::BufferUsagePattern::Streaming,
+            allocation_strategy: crate::buffer::AllocationStrategy::Pool,
+            alignment: 256,
+            mapped_at_creation: false,
+            persistent_mapping: false,
+        };
+        self.buffer_manager.create_dynamic_buffer(config)
+    }
+
+    /// Upload this frame's contents of a dynamic buffer
+    pub fn write_dynamic_buffer(&self, handle: ResourceHandle, data: &[u8]) -> Result<Arc<Buffer>> {
+        self.buffer_manager.write_dynamic(handle, data)
+    }
+
     /// Get a buffer by handle
     pub fn get_buffer(&self, handle: ResourceHandle) -> Option<Arc<Buffer>> {
         self.buffer_manager.get_buffer(handle)
@@ -370,6 +441,39 @@ impl IntegratedRenderer {
         self.shader_manager.load_shader(path, stage, variant)
     }
 
+    /// Watch shader files and recompile them when they are edited
+    ///
+    /// Requires [`RendererConfig::enable_shader_hot_reload`]. Reloads are
+    /// applied in [`begin_frame`](Self::begin_frame); collect them with
+    /// [`take_shader_reloads`](Self::take_shader_reloads) and rebuild the
+    /// pipelines using the reloaded shaders.
+    pub fn enable_shader_hot_reload<P: AsRef<std::path::Path>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> Result<()> {
+        if !self.config.enable_shader_hot_reload {
+            warn!("Shader hot-reload is disabled in the renderer configuration");
+        }
+        self.shader_manager.enable_hot_reload(paths)
+    }
+
+    /// Take the shader reloads and reload failures since the last call
+    pub fn take_shader_reloads(&mut self) -> Vec<HotReloadEvent> {
+        std::mem::take(&mut self.shader_reloads)
+    }
+
+    /// Create a render pipeline, returning validation errors instead of panicking
+    ///
+    /// Useful for pipelines built from hot-reloaded shaders, where an edit can
+    /// break the pipeline's interface (entry points, vertex inputs).
+    pub fn try_create_render_pipeline(
+        &self,
+        descriptor: &RenderPipelineDescriptor,
+    ) -> Result<RenderPipeline> {
+        self.device
+            .catch_validation_errors(|device| device.create_render_pipeline(descriptor))
+    }
+
     /// Create a render pipeline
     pub fn create_render_pipeline(&self) -> Result<()> {
         self.pipeline_manager.create_render_pipeline()
@@ -411,18 +515,26 @@ impl IntegratedRenderer {
         // Clean up unused resources
         self.resource_manager.cleanup_unused();
 
-        // Defragment memory pools
-        let _ = self.memory_manager.lock().defragment();
-
-        // Check for shader hot-reloads
-        if self.config.enable_shader_hot_reload {
-            if let Err(e) = self.shader_manager.check_for_reloads() {
-                warn!("Shader hot-reload check failed: {}", e);
+        // Defragment memory pools and report their health
+        {
+            let mut memory_manager = self.memory_manager.lock();
+            let reclaimed = memory_manager.defragment()?;
+            if let Some(ref profiler) = self.profiler {
+                profiler
+                    .memory_profiler
+                    .record_pool_stats(memory_manager.stats(), reclaimed);
             }
         }
 
         // Collect garbage in buffer pools
         self.buffer_manager.collect_garbage();
+        if let Some(ref profiler) = self.profiler {
+            let (hits, misses) = self.buffer_manager.pool_hit_stats();
+            profiler.memory_profiler.record_buffer_pool(hits, misses);
+
+            let (cached_variants, _, _) = self.shader_manager.get_cache_stats();
+            profiler.record_shader_stats(&self.shader_manager.get_stats(), cached_variants);
+        }
 
         Ok(())
     }
@@ -437,6 +549,98 @@ impl IntegratedRenderer {
         Ok(())
     }
 
+    /// Capture `texture` at the end of the current frame
+    ///
+    /// Call after recording the frame's rendering into `texture` (usually the
+    /// surface texture, which needs `COPY_SRC` usage) and before
+    /// [`end_frame`](Self::end_frame). The copy is submitted with the frame
+    /// and read back asynchronously; collect it with
+    /// [`take_captured_frame`](Self::take_captured_frame) on a later frame.
+    pub fn capture_frame(&mut self, context: &mut RenderContext, texture: &Texture) -> Result<()> {
+        if self.pending_capture.is_some() {
+            return Err(anyhow::anyhow!("A frame capture is already in progress"));
+        }
+        self.pending_capture = Some(FrameCapture::record(
+            &self.device.device,
+            &mut context.encoder,
+            texture,
+        )?);
+        Ok(())
+    }
+
+    /// Return the captured frame once the GPU has finished copying it
+    ///
+    /// Never blocks: returns `None` while no capture is pending or the copy
+    /// is still in flight.
+    pub fn take_captured_frame(&mut self) -> Option<Result<ImageData>> {
+        let capture = self.pending_capture.as_ref()?;
+        let image = capture.try_read(&self.device.device)?;
+        self.pending_capture = None;
+        Some(image)
+    }
+
+    /// Present modes `surface` supports on the active adapter
+    ///
+    /// Use this to offer only valid choices in a settings UI.
+    pub fn supported_present_modes(&self, surface: &Surface<'_>) -> Vec<PresentMode> {
+        match self.device_manager.get_active_adapter() {
+            Some(adapter) => surface.get_capabilities(adapter).present_modes,
+            None => vec![PresentMode::Fifo],
+        }
+    }
+
+    /// Present mode used for new surface configurations
+    pub fn present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
+    /// Switch present mode at runtime and reconfigure `surface`
+    ///
+    /// Falls back to a supported mode if the adapter does not support `mode`
+    /// and returns the mode actually applied; `config` is updated to match.
+    pub fn set_present_mode(
+        &mut self,
+        surface: &Surface<'_>,
+        config: &mut SurfaceConfiguration,
+        mode: PresentMode,
+    ) -> PresentMode {
+        let resolved = resolve_present_mode(mode, &self.supported_present_modes(surface));
+        if resolved != mode {
+            warn!(
+                "Present mode {:?} not supported, using {:?}",
+                mode, resolved
+            );
+        }
+        info!("Switching present mode to {:?}", resolved);
+
+        self.present_mode = resolved;
+        config.present_mode = resolved;
+        surface.configure(&self.device.device, config);
+        resolved
+    }
+
+    /// Enable or disable vsync at runtime, see [`set_present_mode`](Self::set_present_mode)
+    pub fn set_vsync(
+        &mut self,
+        surface: &Surface<'_>,
+        config: &mut SurfaceConfiguration,
+        vsync: bool,
+    ) -> PresentMode {
+        self.set_present_mode(surface, config, vsync_present_mode(vsync))
+    }
+
+    /// The color frames are cleared to
+    pub fn clear_color(&self) -> strato_core::types::Color {
+        self.config.clear_color
+    }
+
+    /// Clear frames to `color`, an sRGB color, from the next frame on
+    ///
+    /// Render passes pick it up through [`RenderContext::clear_value`].
+    pub fn set_clear_color(&mut self, color: strato_core::types::Color) {
+        self.config.clear_color = color;
+    }
+
     /// Shutdown the renderer gracefully
     #[instrument(skip(self))]
     pub fn shutdown(&mut self) {
@@ -465,6 +669,14 @@ impl Drop for IntegratedRenderer {
 }
 
 impl RenderContext {
+    /// The `LoadOp::Clear` value for a target of `format`
+    ///
+    /// The renderer's clear color, converted to the color space the target
+    /// stores and premultiplied by alpha.
+    pub fn clear_value(&self, format: TextureFormat) -> Color {
+        OutputColorSpace::for_format(format).clear_value(self.clear_color)
+    }
+
     /// Begin a render pass with profiling
     pub fn begin_render_pass<'a>(
         &'a mut self,
@@ -564,6 +776,28 @@ impl<'a> RendererBuilder<'a> {
         self
     }
 
+    /// Force a specific adapter, e.g. the integrated GPU to save power
+    ///
+    /// Accepts an [`AdapterId`] or an [`AdapterInfo`](crate::AdapterInfo) from
+    /// [`DeviceManager::enumerate_adapters`]. Falls back to automatic
+    /// selection if the adapter is not available.
+    pub fn with_adapter(mut self, adapter: impl Into<AdapterId>) -> Self {
+        self.config.forced_adapter = Some(adapter.into());
+        self
+    }
+
+    /// Enable or disable vsync
+    pub fn with_vsync(mut self, enabled: bool) -> Self {
+        self.config.vsync = enabled;
+        self
+    }
+
+    /// Set the color frames are cleared to
+    pub fn with_clear_color(mut self, color: strato_core::types::Color) -> Self {
+        self.config.clear_color = color;
+        self
+    }
+
     /// Enable or disable validation layers
     pub fn with_validation(mut self, enabled: bool) -> Self {
         self.config.enable_validation = enabled;
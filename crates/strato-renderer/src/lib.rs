@@ -15,6 +15,7 @@
 
 pub mod batch;
 pub mod buffer;
+pub mod capture;
 pub mod device;
 pub mod font_config;
 pub mod font_system;
@@ -22,6 +23,7 @@ pub mod glyph_atlas;
 pub mod gpu; // Modular GPU pipeline
 pub mod memory;
 pub mod pipeline;
+pub mod post_process;
 pub mod profiler;
 pub mod resources;
 pub mod shader;
@@ -38,13 +40,15 @@ pub use backend::commands::RenderCommand;
 pub use backend::Backend;
 pub use batch::RenderBatch;
 pub use buffer::{BufferManager, BufferPool, DynamicBuffer};
-pub use device::{AdapterInfo, DeviceManager, ManagedDevice};
+pub use capture::{FrameCapture, ImageData};
+pub use device::{AdapterId, AdapterInfo, DeviceManager, ManagedDevice};
 pub use integration::{IntegratedRenderer, RenderContext, RenderStats, RendererBuilder};
-pub use memory::{AllocationStrategy, MemoryManager, MemoryPool};
+pub use memory::{AllocationStrategy, MemoryManager, MemoryPool, MemoryStats, PoolStats};
 pub use pipeline::{PipelineManager, RenderGraph, RenderNode};
-pub use profiler::{FrameStats, PerformanceReport, Profiler};
+pub use post_process::{GaussianBlur, PostProcessChain, PostProcessNode};
+pub use profiler::{FramePacing, FrameStats, PerformanceReport, Profiler};
 pub use resources::{ResourceHandle, ResourceManager, ResourceType};
-pub use shader::{CompiledShader, ShaderManager, ShaderSource};
+pub use shader::{CompiledShader, HotReloadEvent, ShaderManager, ShaderSource};
 
 /// Renderer configuration
 #[derive(Debug, Clone)]
@@ -305,11 +305,13 @@ impl MemoryPool {
 
     /// Deallocate a memory block
     pub fn deallocate(&mut self, block: &MemoryBlock) {
-        if let Some(size) = self.allocated_regions.remove(&block.offset) {
-            self.free_regions.push(FreeRegion {
-                offset: block.offset,
-                size,
-            });
+        self.deallocate_region(block.offset);
+    }
+
+    /// Return the allocation at `offset` to the free list
+    fn deallocate_region(&mut self, offset: u64) {
+        if let Some(size) = self.allocated_regions.remove(&offset) {
+            self.free_regions.push(FreeRegion { offset, size });
 
             self.used_size.fetch_sub(size, Ordering::Relaxed);
             self.deallocation_count.fetch_add(1, Ordering::Relaxed);
@@ -366,6 +368,48 @@ impl MemoryPool {
         fragmentation.min(100.0)
     }
 
+    /// Snapshot of the pool's health
+    pub fn stats(&self) -> PoolStats {
+        let total_bytes = self.total_size.load(Ordering::Relaxed);
+        let used_bytes = self.used_size.load(Ordering::Relaxed).min(total_bytes);
+        let free_bytes: u64 = self.free_regions.iter().map(|region| region.size).sum();
+        let largest_free_block = self
+            .free_regions
+            .iter()
+            .map(|region| region.size)
+            .max()
+            .unwrap_or(0);
+
+        PoolStats {
+            usage_pattern: self.usage_pattern,
+            tier: self.tier,
+            allocation_strategy: self.allocation_strategy,
+            total_bytes,
+            used_bytes,
+            free_bytes,
+            largest_free_block,
+            free_region_count: self.free_regions.len(),
+            live_allocations: self.allocated_regions.len(),
+            allocation_count: self.allocation_count.load(Ordering::Relaxed),
+            fragmentation: fragmentation_ratio(free_bytes, largest_free_block),
+        }
+    }
+
+    /// Release the pool's buffers if nothing is allocated from them
+    ///
+    /// Returns the number of bytes released.
+    fn release_if_unused(&mut self) -> u64 {
+        if !self.allocated_regions.is_empty() {
+            return 0;
+        }
+
+        let released = self.total_size.swap(0, Ordering::Relaxed);
+        self.blocks.clear();
+        self.free_regions.clear();
+        self.used_size.store(0, Ordering::Relaxed);
+        released
+    }
+
     /// Get buffer usage flags for this pool
     fn get_buffer_usage(&self) -> BufferUsages {
         match self.usage_pattern {
@@ -404,6 +448,78 @@ impl MemoryPool {
     }
 }
 
+/// Share of free memory that cannot be handed out as one allocation
+///
+/// 0.0 when all free memory is contiguous (or there is none), approaching
+/// 1.0 as the free memory is split into many small regions.
+fn fragmentation_ratio(free_bytes: u64, largest_free_block: u64) -> f32 {
+    if free_bytes == 0 {
+        return 0.0;
+    }
+    1.0 - largest_free_block as f32 / free_bytes as f32
+}
+
+/// Health of a single memory pool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolStats {
+    pub usage_pattern: UsagePattern,
+    pub tier: MemoryTier,
+    pub allocation_strategy: AllocationStrategy,
+    /// Bytes of GPU buffers owned by the pool
+    pub total_bytes: u64,
+    /// Bytes handed out to live allocations
+    pub used_bytes: u64,
+    /// Bytes available in free regions
+    pub free_bytes: u64,
+    /// Size of the largest free region
+    pub largest_free_block: u64,
+    pub free_region_count: usize,
+    /// Allocations not yet returned to the pool
+    pub live_allocations: usize,
+    /// Allocations made since the pool was created
+    pub allocation_count: u64,
+    /// See [`MemoryStats::fragmentation`]
+    pub fragmentation: f32,
+}
+
+/// Health of every memory pool, from [`MemoryManager::stats`]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryStats {
+    /// Pools that own GPU memory
+    pub pools: Vec<PoolStats>,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    pub free_bytes: u64,
+    /// Largest free region across all pools
+    pub largest_free_block: u64,
+    pub live_allocations: usize,
+    /// Share of free memory that cannot be handed out as one allocation,
+    /// from 0.0 (contiguous) to 1.0 (scattered), weighted by free bytes
+    pub fragmentation: f32,
+}
+
+impl MemoryStats {
+    fn from_pools(mut pools: Vec<PoolStats>) -> Self {
+        pools.sort_by_key(|pool| (pool.usage_pattern as u8, pool.tier));
+
+        let mut stats = MemoryStats::default();
+        let mut weighted_fragmentation = 0.0;
+        for pool in &pools {
+            stats.total_bytes += pool.total_bytes;
+            stats.used_bytes += pool.used_bytes;
+            stats.free_bytes += pool.free_bytes;
+            stats.largest_free_block = stats.largest_free_block.max(pool.largest_free_block);
+            stats.live_allocations += pool.live_allocations;
+            weighted_fragmentation += pool.fragmentation as f64 * pool.free_bytes as f64;
+        }
+        if stats.free_bytes > 0 {
+            stats.fragmentation = (weighted_fragmentation / stats.free_bytes as f64) as f32;
+        }
+        stats.pools = pools;
+        stats
+    }
+}
+
 /// Advanced memory manager with multiple allocation strategies
 pub struct MemoryManager {
     device: Arc<ManagedDevice>,
@@ -712,9 +828,42 @@ impl MemoryManager {
         stats.total_allocated
     }
 
-    /// Defragment memory (integration method)
-    pub fn defragment(&mut self) -> Result<()> {
-        self.relieve_memory_pressure()
+    /// Per-pool used and free bytes, largest free block and fragmentation
+    ///
+    /// Pools that never allocated are left out.
+    pub fn stats(&self) -> MemoryStats {
+        MemoryStats::from_pools(
+            self.pools
+                .values()
+                .filter(|pool| pool.total_size.load(Ordering::Relaxed) > 0)
+                .map(MemoryPool::stats)
+                .collect(),
+        )
+    }
+
+    /// Compact every pool, returning the number of bytes reclaimed
+    ///
+    /// Relieves memory pressure first, then merges adjacent free regions and
+    /// releases the buffers of pools with no live allocation. Live
+    /// allocations are never moved.
+    pub fn defragment(&mut self) -> Result<u64> {
+        self.relieve_memory_pressure()?;
+
+        let mut reclaimed = 0;
+        for pool in self.pools.values_mut() {
+            pool.coalesce_free_regions();
+            reclaimed += pool.release_if_unused();
+
+            let fragmentation = pool.calculate_fragmentation();
+            pool.fragmentation_ratio
+                .store((fragmentation * 1000.0) as u64, Ordering::Relaxed);
+            *pool.last_defrag.write() = Instant::now();
+        }
+
+        if reclaimed > 0 {
+            debug!("Defragmentation reclaimed {} bytes", reclaimed);
+        }
+        Ok(reclaimed)
     }
 
     /// Cleanup memory (integration method)
@@ -750,6 +899,72 @@ mod tests {
         assert_eq!(regions.pop().unwrap().size, 100);
     }
 
+    #[test]
+    fn test_pool_stats_and_release() {
+        let mut pool = MemoryPool::new(
+            UsagePattern::Transient,
+            MemoryTier::Standard,
+            4096,
+            16 * 1024 * 1024,
+            AllocationStrategy::Linear,
+        );
+        // 4KB buffer: two live allocations with three scattered free regions
+        pool.total_size.store(4096, Ordering::Relaxed);
+        pool.used_size.store(1024, Ordering::Relaxed);
+        pool.allocated_regions.insert(512, 512);
+        pool.allocated_regions.insert(2048, 512);
+        for (offset, size) in [(0, 512), (1024, 1024), (2560, 1536)] {
+            pool.free_regions.push(FreeRegion { offset, size });
+        }
+
+        let stats = pool.stats();
+        assert_eq!(stats.used_bytes, 1024);
+        assert_eq!(stats.free_bytes, 3072);
+        assert_eq!(stats.largest_free_block, 1536);
+        assert_eq!(stats.live_allocations, 2);
+        assert!((stats.fragmentation - 0.5).abs() < f32::EPSILON);
+
+        // Nothing can be released while allocations are live
+        assert_eq!(pool.release_if_unused(), 0);
+
+        // Once everything is returned, the regions merge and the buffer goes
+        pool.deallocate_region(512);
+        pool.deallocate_region(2048);
+        assert_eq!(pool.stats().free_region_count, 1);
+        assert_eq!(pool.stats().fragmentation, 0.0);
+        assert_eq!(pool.release_if_unused(), 4096);
+        assert_eq!(pool.stats().total_bytes, 0);
+    }
+
+    #[test]
+    fn test_memory_stats_aggregate() {
+        let pool = |pattern, free_bytes, largest_free_block| PoolStats {
+            usage_pattern: pattern,
+            tier: MemoryTier::Standard,
+            allocation_strategy: AllocationStrategy::BestFit,
+            total_bytes: 4096,
+            used_bytes: 4096 - free_bytes,
+            free_bytes,
+            largest_free_block,
+            free_region_count: 2,
+            live_allocations: 1,
+            allocation_count: 1,
+            fragmentation: fragmentation_ratio(free_bytes, largest_free_block),
+        };
+        let stats = MemoryStats::from_pools(vec![
+            pool(UsagePattern::Static, 1000, 1000),
+            pool(UsagePattern::Transient, 3000, 1500),
+        ]);
+
+        assert_eq!(stats.pools[0].usage_pattern, UsagePattern::Transient);
+        assert_eq!(stats.total_bytes, 8192);
+        assert_eq!(stats.free_bytes, 4000);
+        assert_eq!(stats.largest_free_block, 1500);
+        assert_eq!(stats.live_allocations, 2);
+        // 3000 free bytes at 0.5, 1000 at 0.0
+        assert!((stats.fragmentation - 0.375).abs() < 1e-6);
+    }
+
     #[test]
     fn test_alignment() {
         assert_eq!(MemoryPool::align_size(100, 256), 256);
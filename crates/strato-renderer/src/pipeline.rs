@@ -14,7 +14,7 @@ use crate::vertex::Vertex;
 use anyhow::{Context, Result};
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 use std::time::Duration;
 use wgpu::{
@@ -370,15 +370,53 @@ impl TextPipeline {
 }
 
 /// Render graph node for managing render passes
+///
+/// Nodes are scheduled after the nodes listed in `dependencies` and after
+/// every node writing one of the resources they read.
 #[derive(Debug, Clone)]
 pub struct RenderNode {
     pub id: String,
     pub dependencies: Vec<String>,
     pub pass_type: RenderPassType,
+    /// Named resources (textures) this node reads
+    pub inputs: Vec<String>,
+    /// Named resources (textures) this node writes
+    pub outputs: Vec<String>,
+}
+
+impl RenderNode {
+    /// Create a node without dependencies
+    pub fn new(id: impl Into<String>, pass_type: RenderPassType) -> Self {
+        Self {
+            id: id.into(),
+            dependencies: Vec::new(),
+            pass_type,
+            inputs: Vec::new(),
+            outputs: Vec::new(),
+        }
+    }
+
+    /// Run after the node with the given id
+    pub fn depends_on(mut self, id: impl Into<String>) -> Self {
+        self.dependencies.push(id.into());
+        self
+    }
+
+    /// Declare a resource read by this node
+    pub fn reads(mut self, resource: impl Into<String>) -> Self {
+        self.inputs.push(resource.into());
+        self
+    }
+
+    /// Declare a resource written by this node
+    pub fn writes(mut self, resource: impl Into<String>) -> Self {
+        self.outputs.push(resource.into());
+        self
+    }
 }
 
 /// Type of render pass
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RenderPassType {
     UI,
     Text,
@@ -386,7 +424,7 @@ pub enum RenderPassType {
 }
 
 /// Render graph for managing render pass dependencies
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct RenderGraph {
     nodes: Vec<RenderNode>,
     execution_order: Vec<usize>,
@@ -400,19 +438,83 @@ impl RenderGraph {
         }
     }
 
-    pub fn add_node(&mut self, node: RenderNode) {
+    /// Add a node and reschedule the graph
+    ///
+    /// Fails, leaving the graph unchanged, if the id is already used or the
+    /// node would introduce a dependency cycle.
+    pub fn add_node(&mut self, node: RenderNode) -> Result<()> {
+        if self.nodes.iter().any(|existing| existing.id == node.id) {
+            anyhow::bail!("Render node '{}' already exists", node.id);
+        }
+
         self.nodes.push(node);
-        self.update_execution_order();
+        if let Err(e) = self.update_execution_order() {
+            self.nodes.pop();
+            return Err(e);
+        }
+        Ok(())
     }
 
-    fn update_execution_order(&mut self) {
-        // Simple topological sort for now
-        self.execution_order = (0..self.nodes.len()).collect();
+    /// Topologically sort the nodes, keeping insertion order among independent nodes
+    fn update_execution_order(&mut self) -> Result<()> {
+        let count = self.nodes.len();
+        let mut successors: Vec<Vec<usize>> = vec![Vec::new(); count];
+        let mut in_degree = vec![0usize; count];
+
+        for (to, node) in self.nodes.iter().enumerate() {
+            for (from, other) in self.nodes.iter().enumerate() {
+                if from == to {
+                    continue;
+                }
+                let explicit = node.dependencies.contains(&other.id);
+                let resource = node
+                    .inputs
+                    .iter()
+                    .any(|input| other.outputs.contains(input));
+                if explicit || resource {
+                    successors[from].push(to);
+                    in_degree[to] += 1;
+                }
+            }
+        }
+
+        let mut ready: BTreeSet<usize> = (0..count).filter(|&i| in_degree[i] == 0).collect();
+        let mut order = Vec::with_capacity(count);
+        while let Some(index) = ready.pop_first() {
+            order.push(index);
+            for &next in &successors[index] {
+                in_degree[next] -= 1;
+                if in_degree[next] == 0 {
+                    ready.insert(next);
+                }
+            }
+        }
+
+        if order.len() != count {
+            let cyclic: Vec<&str> = (0..count)
+                .filter(|i| !order.contains(i))
+                .map(|i| self.nodes[i].id.as_str())
+                .collect();
+            anyhow::bail!("Render graph has a dependency cycle between {:?}", cyclic);
+        }
+
+        self.execution_order = order;
+        Ok(())
     }
 
     pub fn get_execution_order(&self) -> &[usize] {
         &self.execution_order
     }
+
+    /// Nodes in insertion order
+    pub fn nodes(&self) -> &[RenderNode] {
+        &self.nodes
+    }
+
+    /// Nodes in execution order
+    pub fn scheduled_nodes(&self) -> impl Iterator<Item = &RenderNode> {
+        self.execution_order.iter().map(|&i| &self.nodes[i])
+    }
 }
 
 /// Pipeline manager for handling multiple render pipelines
@@ -465,6 +567,42 @@ mod tests {
         assert_eq!(uniforms.time, 1.0);
     }
 
+    #[test]
+    fn test_render_graph_schedules_by_resources() {
+        let mut graph = RenderGraph::new();
+        // Added out of order: the composite reads what the blur writes
+        graph
+            .add_node(
+                RenderNode::new("composite", RenderPassType::PostProcess)
+                    .reads("blurred")
+                    .writes("final"),
+            )
+            .unwrap();
+        graph
+            .add_node(
+                RenderNode::new("blur", RenderPassType::PostProcess)
+                    .reads("ui_color")
+                    .writes("blurred"),
+            )
+            .unwrap();
+        graph
+            .add_node(RenderNode::new("debug", RenderPassType::UI).depends_on("composite"))
+            .unwrap();
+
+        let order: Vec<&str> = graph.scheduled_nodes().map(|n| n.id.as_str()).collect();
+        assert_eq!(order, vec!["blur", "composite", "debug"]);
+
+        // A node closing a cycle is rejected and the graph is unchanged
+        let cycle = RenderNode::new("feedback", RenderPassType::PostProcess)
+            .reads("final")
+            .writes("ui_color");
+        assert!(graph.add_node(cycle).is_err());
+        assert_eq!(graph.nodes().len(), 3);
+        assert!(graph
+            .add_node(RenderNode::new("blur", RenderPassType::UI))
+            .is_err());
+    }
+
     #[test]
     fn test_orthographic_projection() {
         let proj = UIUniforms::orthographic_projection(0.0, 800.0, 600.0, 0.0, -1.0, 1.0);
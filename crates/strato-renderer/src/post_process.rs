@@ -0,0 +1,697 @@
+//! Post-processing passes
+//!
+//! Applications register [`PostProcessNode`]s that read the rendered UI and
+//! write a processed image. Each node declares the named textures it reads
+//! and writes through its [`RenderNode`], and the [`RenderGraph`] schedules
+//! the nodes so every texture is written before it is read.
+//!
+//! When a chain is active the backend renders the UI into the offscreen
+//! [`UI_COLOR`] texture instead of the swapchain, runs the nodes in graph
+//! order and blits the output of the last node to the surface.
+//!
+//! [`GaussianBlur`] is the built-in example node, used to give translucent
+//! panels a frosted-glass background.
+
+use crate::pipeline::{RenderGraph, RenderNode, RenderPassType};
+use anyhow::{bail, Result};
+use bytemuck::{Pod, Zeroable};
+use std::collections::HashMap;
+use strato_core::types::Rect;
+use wgpu::{
+    BindGroupLayout, Buffer, CommandEncoder, Device, Queue, RenderPipeline, Sampler, Texture,
+    TextureFormat, TextureUsages, TextureView,
+};
+
+/// Resource name of the texture the UI is rendered into
+pub const UI_COLOR: &str = "ui_color";
+
+/// Everything a post-process node needs to record its passes
+pub struct PostProcessContext<'a> {
+    pub device: &'a Device,
+    pub queue: &'a Queue,
+    pub encoder: &'a mut CommandEncoder,
+    /// The texture the node reads
+    pub input: &'a TextureView,
+    /// The texture the node writes
+    pub output: &'a TextureView,
+    /// Format of both textures
+    pub format: TextureFormat,
+    /// Size of both textures in physical pixels
+    pub width: u32,
+    pub height: u32,
+    /// Physical pixels per logical pixel
+    pub scale_factor: f32,
+}
+
+/// A custom pass run on the rendered UI
+///
+/// The node returned by [`node`](Self::node) must read exactly one resource
+/// and write exactly one other resource.
+pub trait PostProcessNode: Send + Sync {
+    /// Graph node describing the pass and the resources it reads and writes
+    fn node(&self) -> RenderNode;
+
+    /// Record the pass, reading `ctx.input` and writing `ctx.output`
+    fn run(&mut self, ctx: &mut PostProcessContext<'_>) -> Result<()>;
+
+    /// Drop GPU resources, called when the device is recreated
+    fn reset(&mut self) {}
+}
+
+/// An offscreen texture usable as render target and shader input
+pub(crate) struct RenderTarget {
+    pub(crate) texture: Texture,
+    pub(crate) view: TextureView,
+}
+
+impl RenderTarget {
+    pub(crate) fn new(
+        device: &Device,
+        label: &str,
+        format: TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(label),
+            size: wgpu::Extent3d {
+                width: width.max(1),
+                height: height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: TextureUsages::RENDER_ATTACHMENT
+                | TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::COPY_DST,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Self { texture, view }
+    }
+
+    pub(crate) fn matches(&self, format: TextureFormat, width: u32, height: u32) -> bool {
+        self.texture.format() == format
+            && self.texture.width() == width.max(1)
+            && self.texture.height() == height.max(1)
+    }
+}
+
+/// A fullscreen-triangle pass sampling one texture
+///
+/// The shader provides `vs_main`/`fs_main`, samples `@binding(0)` with the
+/// sampler at `@binding(1)` and may read a uniform block at `@binding(2)`.
+pub struct FullscreenPass {
+    pipeline: RenderPipeline,
+    layout: BindGroupLayout,
+    sampler: Sampler,
+    uniforms: Option<Buffer>,
+}
+
+impl FullscreenPass {
+    /// Build the pass for `format`, with a uniform block of `uniform_size` bytes if any
+    pub fn new(
+        device: &Device,
+        label: &str,
+        source: &str,
+        format: TextureFormat,
+        uniform_size: Option<u64>,
+    ) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some(label),
+            source: wgpu::ShaderSource::Wgsl(source.into()),
+        });
+
+        let mut entries = vec![
+            wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: true },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+                count: None,
+            },
+            wgpu::BindGroupLayoutEntry {
+                binding: 1,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                count: None,
+            },
+        ];
+        if uniform_size.is_some() {
+            entries.push(wgpu::BindGroupLayoutEntry {
+                binding: 2,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            });
+        }
+        let layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some(label),
+            entries: &entries,
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some(label),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some(label),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let uniforms = uniform_size.map(|size| {
+            device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some(label),
+                size,
+                usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            })
+        });
+
+        Self {
+            pipeline,
+            layout,
+            sampler,
+            uniforms,
+        }
+    }
+
+    /// Update the uniform block
+    ///
+    /// The write lands when the queue is next submitted, so passes recorded
+    /// into the same frame need their own `FullscreenPass`.
+    pub fn write_uniforms(&self, queue: &Queue, data: &[u8]) {
+        if let Some(buffer) = &self.uniforms {
+            queue.write_buffer(buffer, 0, data);
+        }
+    }
+
+    /// Record the pass, sampling `input` and overwriting `output`
+    pub fn draw(
+        &self,
+        device: &Device,
+        encoder: &mut CommandEncoder,
+        input: &TextureView,
+        output: &TextureView,
+    ) {
+        let mut entries = vec![
+            wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(input),
+            },
+            wgpu::BindGroupEntry {
+                binding: 1,
+                resource: wgpu::BindingResource::Sampler(&self.sampler),
+            },
+        ];
+        if let Some(buffer) = &self.uniforms {
+            entries.push(wgpu::BindGroupEntry {
+                binding: 2,
+                resource: buffer.as_entire_binding(),
+            });
+        }
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Fullscreen Pass Bind Group"),
+            layout: &self.layout,
+            entries: &entries,
+        });
+
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Fullscreen Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: output,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// The registered post-process nodes and the textures they exchange
+#[derive(Default)]
+pub struct PostProcessChain {
+    graph: RenderGraph,
+    nodes: Vec<Box<dyn PostProcessNode>>,
+    targets: HashMap<String, RenderTarget>,
+    blit: Option<FullscreenPass>,
+}
+
+impl PostProcessChain {
+    /// Create an empty chain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a node
+    ///
+    /// Fails if the node does not read one resource and write another, if
+    /// its id is already used or if it would create a dependency cycle.
+    pub fn add(&mut self, node: Box<dyn PostProcessNode>) -> Result<()> {
+        let description = node.node();
+        match (
+            description.inputs.as_slice(),
+            description.outputs.as_slice(),
+        ) {
+            ([input], [output]) if input != output => {}
+            _ => bail!(
+                "Post-process node '{}' must read one resource and write another",
+                description.id
+            ),
+        }
+        if description.outputs[0] == UI_COLOR {
+            bail!(
+                "Post-process node '{}' cannot write '{}'",
+                description.id,
+                UI_COLOR
+            );
+        }
+
+        self.graph.add_node(description)?;
+        self.nodes.push(node);
+        Ok(())
+    }
+
+    /// Whether no node is registered
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The scheduling graph
+    pub fn graph(&self) -> &RenderGraph {
+        &self.graph
+    }
+
+    /// Size the textures for the next frame
+    ///
+    /// Textures are recreated when the surface size or format changes.
+    pub fn prepare(&mut self, device: &Device, format: TextureFormat, width: u32, height: u32) {
+        let up_to_date = self
+            .targets
+            .get(UI_COLOR)
+            .is_some_and(|target| target.matches(format, width, height));
+        if up_to_date {
+            return;
+        }
+
+        self.targets.clear();
+        self.targets.insert(
+            UI_COLOR.to_string(),
+            RenderTarget::new(device, "Post-process UI Color", format, width, height),
+        );
+        self.blit = Some(FullscreenPass::new(
+            device,
+            "Post-process Blit",
+            include_str!("shaders/blit.wgsl"),
+            format,
+            None,
+        ));
+    }
+
+    /// The texture the UI should be rendered into, once prepared
+    pub fn ui_view(&self) -> Option<&TextureView> {
+        self.targets.get(UI_COLOR).map(|target| &target.view)
+    }
+
+    /// The texture holding `resource`, if it has been written
+    pub fn texture(&self, resource: &str) -> Option<&Texture> {
+        self.targets.get(resource).map(|target| &target.texture)
+    }
+
+    /// Run the nodes in graph order and blit the final image to `surface_view`
+    pub fn execute(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        surface_view: &TextureView,
+        scale_factor: f32,
+    ) -> Result<()> {
+        let Some(ui_color) = self.targets.get(UI_COLOR) else {
+            bail!("Post-process chain executed before prepare");
+        };
+        let format = ui_color.texture.format();
+        let (width, height) = (ui_color.texture.width(), ui_color.texture.height());
+
+        let mut last_output = UI_COLOR.to_string();
+        for &index in self.graph.get_execution_order() {
+            let description = &self.graph.nodes()[index];
+            let (input, output) = (&description.inputs[0], &description.outputs[0]);
+            if !self.targets.contains_key(input) {
+                bail!(
+                    "Resource '{}' read by post-process node '{}' is never written",
+                    input,
+                    description.id
+                );
+            }
+            if !self.targets.contains_key(output) {
+                let label = format!("Post-process {}", output);
+                let target = RenderTarget::new(device, &label, format, width, height);
+                self.targets.insert(output.clone(), target);
+            }
+
+            let mut ctx = PostProcessContext {
+                device,
+                queue,
+                encoder: &mut *encoder,
+                input: &self.targets[input].view,
+                output: &self.targets[output].view,
+                format,
+                width,
+                height,
+                scale_factor,
+            };
+            self.nodes[index].run(&mut ctx)?;
+            last_output = output.clone();
+        }
+
+        if let Some(blit) = &self.blit {
+            blit.draw(
+                device,
+                encoder,
+                &self.targets[&last_output].view,
+                surface_view,
+            );
+        }
+        Ok(())
+    }
+
+    /// Drop every GPU resource, keeping the registered nodes
+    pub fn reset(&mut self) {
+        self.targets.clear();
+        self.blit = None;
+        for node in &mut self.nodes {
+            node.reset();
+        }
+    }
+}
+
+/// Uniform block of `blur.wgsl`
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+struct BlurParams {
+    direction: [f32; 2],
+    radius: f32,
+    _padding: f32,
+    region: [f32; 4],
+}
+
+/// GPU state of a [`GaussianBlur`], created on first use
+struct BlurPasses {
+    format: TextureFormat,
+    horizontal: FullscreenPass,
+    vertical: FullscreenPass,
+    intermediate: Option<RenderTarget>,
+}
+
+/// Separable gaussian blur
+///
+/// Reads [`UI_COLOR`] and writes `"blurred"` by default. With a region set,
+/// only that part of the image is blurred and the rest is copied unchanged,
+/// which is how frosted-glass panels are drawn.
+pub struct GaussianBlur {
+    id: String,
+    input: String,
+    output: String,
+    radius: f32,
+    region: Option<Rect>,
+    passes: Option<BlurPasses>,
+}
+
+impl GaussianBlur {
+    /// Blur with the given radius in logical pixels
+    pub fn new(radius: f32) -> Self {
+        Self {
+            id: "gaussian_blur".to_string(),
+            input: UI_COLOR.to_string(),
+            output: "blurred".to_string(),
+            radius: radius.max(0.0),
+            region: None,
+            passes: None,
+        }
+    }
+
+    /// Set the node id, needed to register more than one blur
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Read another resource than [`UI_COLOR`]
+    pub fn reading(mut self, resource: impl Into<String>) -> Self {
+        self.input = resource.into();
+        self
+    }
+
+    /// Write another resource than `"blurred"`
+    pub fn writing(mut self, resource: impl Into<String>) -> Self {
+        self.output = resource.into();
+        self
+    }
+
+    /// Only blur the given area, in logical pixels
+    pub fn with_region(mut self, region: Rect) -> Self {
+        self.region = Some(region);
+        self
+    }
+
+    /// Change the blurred area, `None` for the whole image
+    pub fn set_region(&mut self, region: Option<Rect>) {
+        self.region = region;
+    }
+
+    /// Blur radius in logical pixels
+    pub fn radius(&self) -> f32 {
+        self.radius
+    }
+
+    fn params(&self, direction: [f32; 2], scale_factor: f32) -> BlurParams {
+        let region = self.region.map_or([0.0; 4], |region| {
+            [
+                region.x * scale_factor,
+                region.y * scale_factor,
+                region.width * scale_factor,
+                region.height * scale_factor,
+            ]
+        });
+        BlurParams {
+            direction,
+            radius: self.radius * scale_factor,
+            _padding: 0.0,
+            region,
+        }
+    }
+}
+
+impl PostProcessNode for GaussianBlur {
+    fn node(&self) -> RenderNode {
+        RenderNode::new(self.id.clone(), RenderPassType::PostProcess)
+            .reads(self.input.clone())
+            .writes(self.output.clone())
+    }
+
+    fn run(&mut self, ctx: &mut PostProcessContext<'_>) -> Result<()> {
+        let horizontal = self.params([1.0 / ctx.width as f32, 0.0], ctx.scale_factor);
+        let vertical = self.params([0.0, 1.0 / ctx.height as f32], ctx.scale_factor);
+
+        if self.passes.as_ref().map(|passes| passes.format) != Some(ctx.format) {
+            let size = std::mem::size_of::<BlurParams>() as u64;
+            let source = include_str!("shaders/blur.wgsl");
+            self.passes = Some(BlurPasses {
+                format: ctx.format,
+                horizontal: FullscreenPass::new(
+                    ctx.device,
+                    "Blur Horizontal",
+                    source,
+                    ctx.format,
+                    Some(size),
+                ),
+                vertical: FullscreenPass::new(
+                    ctx.device,
+                    "Blur Vertical",
+                    source,
+                    ctx.format,
+                    Some(size),
+                ),
+                intermediate: None,
+            });
+        }
+        let Some(passes) = self.passes.as_mut() else {
+            return Ok(());
+        };
+
+        let intermediate_is_stale = !passes
+            .intermediate
+            .as_ref()
+            .is_some_and(|target| target.matches(ctx.format, ctx.width, ctx.height));
+        if intermediate_is_stale {
+            passes.intermediate = Some(RenderTarget::new(
+                ctx.device,
+                "Blur Intermediate",
+                ctx.format,
+                ctx.width,
+                ctx.height,
+            ));
+        }
+        let Some(intermediate) = passes.intermediate.as_ref() else {
+            return Ok(());
+        };
+
+        passes
+            .horizontal
+            .write_uniforms(ctx.queue, bytemuck::bytes_of(&horizontal));
+        passes
+            .vertical
+            .write_uniforms(ctx.queue, bytemuck::bytes_of(&vertical));
+        passes
+            .horizontal
+            .draw(ctx.device, ctx.encoder, ctx.input, &intermediate.view);
+        passes
+            .vertical
+            .draw(ctx.device, ctx.encoder, &intermediate.view, ctx.output);
+        Ok(())
+    }
+
+    fn reset(&mut self) {
+        self.passes = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::capture::FrameCapture;
+    use crate::gpu::DeviceManager;
+
+    #[test]
+    fn test_chain_rejects_invalid_nodes() {
+        let mut chain = PostProcessChain::new();
+        assert!(chain.is_empty());
+
+        chain.add(Box::new(GaussianBlur::new(4.0))).unwrap();
+        // Same id twice
+        assert!(chain.add(Box::new(GaussianBlur::new(2.0))).is_err());
+        // Reading and writing the same texture
+        let in_place = GaussianBlur::new(2.0).with_id("in_place").writing(UI_COLOR);
+        assert!(chain.add(Box::new(in_place)).is_err());
+        // Overwriting the UI with a processed copy of itself
+        let feedback = GaussianBlur::new(2.0)
+            .with_id("feedback")
+            .reading("blurred")
+            .writing(UI_COLOR);
+        assert!(chain.add(Box::new(feedback)).is_err());
+
+        // A second blur chained after the first is scheduled after it
+        let second = GaussianBlur::new(2.0)
+            .with_id("second")
+            .reading("blurred")
+            .writing("blurred_twice");
+        chain.add(Box::new(second)).unwrap();
+        let order: Vec<&str> = chain
+            .graph()
+            .scheduled_nodes()
+            .map(|node| node.id.as_str())
+            .collect();
+        assert_eq!(order, vec!["gaussian_blur", "second"]);
+    }
+
+    #[tokio::test]
+    async fn test_blur_spreads_a_single_pixel() {
+        let dm = DeviceManager::new(wgpu::Backends::all())
+            .await
+            .expect("Failed to create device manager");
+        let (device, queue) = (dm.device(), dm.queue());
+        let format = TextureFormat::Rgba8Unorm;
+
+        let mut chain = PostProcessChain::new();
+        chain.add(Box::new(GaussianBlur::new(2.0))).unwrap();
+        chain.prepare(device, format, 8, 8);
+
+        // A single white pixel in the middle of a black image
+        let mut pixels = vec![0u8; 8 * 8 * 4];
+        for pixel in pixels.chunks_exact_mut(4) {
+            pixel[3] = 255;
+        }
+        let center = (4 * 8 + 4) * 4;
+        pixels[center..center + 4].copy_from_slice(&[255, 255, 255, 255]);
+        queue.write_texture(
+            chain.texture(UI_COLOR).unwrap().as_image_copy(),
+            &pixels,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(8 * 4),
+                rows_per_image: Some(8),
+            },
+            wgpu::Extent3d {
+                width: 8,
+                height: 8,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let surface = RenderTarget::new(device, "Test Surface", format, 8, 8);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Post-process Test"),
+        });
+        chain
+            .execute(device, queue, &mut encoder, &surface.view, 1.0)
+            .unwrap();
+        let capture = FrameCapture::record(device, &mut encoder, &surface.texture).unwrap();
+        queue.submit(std::iter::once(encoder.finish()));
+        let image = capture.wait(device).unwrap();
+
+        let red = |x: usize, y: usize| image.data[(y * 8 + x) * 4];
+        assert!(red(4, 4) < 255, "the center should be spread out");
+        assert!(red(5, 4) > 0 && red(4, 5) > 0, "neighbours should be lit");
+        assert!(red(4, 4) > red(5, 4), "the center should stay brightest");
+        assert_eq!(red(0, 0), 0, "far pixels should stay black");
+    }
+}
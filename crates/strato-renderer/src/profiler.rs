@@ -28,7 +28,10 @@ use wgpu::{
 };
 
 use crate::device::ManagedDevice;
+use crate::gpu::TextCacheStats;
+use crate::memory::MemoryStats;
 use crate::resources::ResourceHandle;
+use crate::shader::CompilationStats;
 
 /// Performance metric types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -143,6 +146,14 @@ pub struct MemoryProfiler {
     // Historical data
     memory_history: RwLock<VecDeque<MemorySample>>,
     leak_detection: RwLock<HashMap<ResourceHandle, AllocationInfo>>,
+
+    // Memory pool health, reported by the memory manager
+    pool_stats: RwLock<Option<MemoryStats>>,
+    reclaimed_bytes: AtomicU64,
+
+    // Buffer pool reuse, reported by the buffer manager
+    buffer_pool_hits: AtomicU64,
+    buffer_pool_misses: AtomicU64,
 }
 
 /// Memory allocation sample
@@ -230,13 +241,67 @@ pub struct OptimizationSuggestion {
     pub category: OptimizationCategory,
 }
 
+/// Frames kept for the pacing statistics in [`FrameStats`]
+pub const FRAME_HISTORY_LEN: usize = 1000;
+
+/// Default frame budget, one frame at 60 Hz
+pub const DEFAULT_FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+
 /// Frame statistics
+///
+/// Times are in milliseconds. The percentiles, long frames and longest
+/// recent frame cover the last [`FRAME_HISTORY_LEN`] frames, so they show
+/// stutter that an average over the whole run hides.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FrameStats {
     pub total_frames: u64,
     pub average_frame_time: f64,
     pub min_frame_time: f64,
     pub max_frame_time: f64,
+    /// Median frame time
+    pub p50_frame_time: f64,
+    pub p95_frame_time: f64,
+    pub p99_frame_time: f64,
+    /// Budget a frame has to stay within, see [`Profiler::set_frame_budget`]
+    pub frame_budget: f64,
+    /// Recent frames that took longer than the budget
+    pub long_frames: u64,
+    pub longest_recent_frame: f64,
+}
+
+/// Frame time percentiles and long frames of a window of frames
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct FramePacing {
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub long_frames: u64,
+    pub longest: f64,
+}
+
+impl FramePacing {
+    /// Pacing of frames taking `frame_times` milliseconds
+    pub fn from_frame_times(frame_times: impl IntoIterator<Item = f64>, budget_ms: f64) -> Self {
+        let mut times: Vec<f64> = frame_times.into_iter().collect();
+        if times.is_empty() {
+            return Self::default();
+        }
+        times.sort_by(f64::total_cmp);
+
+        Self {
+            p50: percentile(&times, 50.0),
+            p95: percentile(&times, 95.0),
+            p99: percentile(&times, 99.0),
+            long_frames: times.iter().filter(|&&time| time > budget_ms).count() as u64,
+            longest: times[times.len() - 1],
+        }
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`
+fn percentile(values: &[f64], percent: f64) -> f64 {
+    let rank = (percent / 100.0 * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
 }
 
 /// Performance report
@@ -245,6 +310,8 @@ pub struct PerformanceReport {
     pub frame_stats: FrameStats,
     pub cpu_samples: Vec<PerformanceSample>,
     pub memory_stats: HashMap<String, u64>,
+    pub shader_stats: HashMap<String, u64>,
+    pub text_stats: HashMap<String, u64>,
     pub bottlenecks: Vec<Bottleneck>,
     pub optimization_suggestions: Vec<OptimizationSuggestion>,
 }
@@ -286,6 +353,8 @@ pub struct Profiler {
     pub cpu_profiler: Arc<CpuProfiler>,
     pub memory_profiler: Arc<MemoryProfiler>,
     performance_analyzer: Arc<PerformanceAnalyzer>,
+    shader_stats: RwLock<HashMap<String, u64>>,
+    text_stats: RwLock<HashMap<String, u64>>,
 
     // Configuration
     enabled: AtomicBool,
@@ -301,6 +370,7 @@ pub struct Profiler {
     average_frame_time: RwLock<f64>,
     min_frame_time: RwLock<f64>,
     max_frame_time: RwLock<f64>,
+    frame_budget: RwLock<Duration>,
 }
 
 impl GpuTimer {
@@ -515,9 +585,31 @@ impl MemoryProfiler {
             pipeline_memory: AtomicU64::new(0),
             memory_history: RwLock::new(VecDeque::with_capacity(1000)),
             leak_detection: RwLock::new(HashMap::new()),
+            pool_stats: RwLock::new(None),
+            reclaimed_bytes: AtomicU64::new(0),
+            buffer_pool_hits: AtomicU64::new(0),
+            buffer_pool_misses: AtomicU64::new(0),
         }
     }
 
+    /// Record the buffer pools' hit and miss counters
+    pub fn record_buffer_pool(&self, hits: u64, misses: u64) {
+        self.buffer_pool_hits.store(hits, Ordering::Relaxed);
+        self.buffer_pool_misses.store(misses, Ordering::Relaxed);
+    }
+
+    /// Record the memory pools' health and the bytes reclaimed by the last
+    /// defragmentation
+    pub fn record_pool_stats(&self, stats: MemoryStats, reclaimed: u64) {
+        self.reclaimed_bytes.fetch_add(reclaimed, Ordering::Relaxed);
+        *self.pool_stats.write() = Some(stats);
+    }
+
+    /// Last recorded memory pool health
+    pub fn pool_stats(&self) -> Option<MemoryStats> {
+        self.pool_stats.read().clone()
+    }
+
     /// Record allocation
     pub fn record_allocation(&self, handle: ResourceHandle, size: u64, resource_type: &str) {
         if !self.enabled.load(Ordering::Relaxed) {
@@ -653,6 +745,36 @@ impl MemoryProfiler {
             "pipeline_memory".to_string(),
             self.pipeline_memory.load(Ordering::Relaxed),
         );
+        stats.insert(
+            "buffer_pool_hits".to_string(),
+            self.buffer_pool_hits.load(Ordering::Relaxed),
+        );
+        stats.insert(
+            "buffer_pool_misses".to_string(),
+            self.buffer_pool_misses.load(Ordering::Relaxed),
+        );
+        if let Some(pools) = self.pool_stats.read().as_ref() {
+            stats.insert("pool_total_bytes".to_string(), pools.total_bytes);
+            stats.insert("pool_used_bytes".to_string(), pools.used_bytes);
+            stats.insert("pool_free_bytes".to_string(), pools.free_bytes);
+            stats.insert(
+                "pool_largest_free_block".to_string(),
+                pools.largest_free_block,
+            );
+            stats.insert(
+                "pool_live_allocations".to_string(),
+                pools.live_allocations as u64,
+            );
+            // Fixed point (x1000), like MemoryPool::fragmentation_ratio
+            stats.insert(
+                "pool_fragmentation".to_string(),
+                (pools.fragmentation * 1000.0) as u64,
+            );
+            stats.insert(
+                "pool_reclaimed_bytes".to_string(),
+                self.reclaimed_bytes.load(Ordering::Relaxed),
+            );
+        }
         stats
     }
 }
@@ -661,7 +783,7 @@ impl PerformanceAnalyzer {
     /// Create a new performance analyzer
     pub fn new() -> Self {
         Self {
-            frame_history: RwLock::new(VecDeque::with_capacity(1000)),
+            frame_history: RwLock::new(VecDeque::with_capacity(FRAME_HISTORY_LEN)),
             bottleneck_detector: BottleneckDetector::new(),
             regression_detector: RegressionDetector::new(),
             optimization_suggestions: RwLock::new(Vec::new()),
@@ -674,6 +796,27 @@ impl PerformanceAnalyzer {
         // Placeholder for frame analysis
     }
 
+    /// Add a finished frame to the history, dropping the oldest one once
+    /// [`FRAME_HISTORY_LEN`] frames are kept
+    pub fn record_frame(&self, frame_timing: FrameTiming) {
+        let mut history = self.frame_history.write();
+        if history.len() == FRAME_HISTORY_LEN {
+            history.pop_front();
+        }
+        history.push_back(frame_timing);
+    }
+
+    /// Pacing of the frames in the history against `budget`
+    pub fn frame_pacing(&self, budget: Duration) -> FramePacing {
+        let history = self.frame_history.read();
+        FramePacing::from_frame_times(
+            history
+                .iter()
+                .map(|frame| (frame.end_time - frame.start_time).as_secs_f64() * 1000.0),
+            budget.as_secs_f64() * 1000.0,
+        )
+    }
+
     /// Analyze frame timing
     pub fn analyze_frame_timing(&self, _frame_time: Duration) {
         // Placeholder for frame timing analysis
@@ -809,6 +952,8 @@ impl Profiler {
             cpu_profiler,
             memory_profiler,
             performance_analyzer,
+            shader_stats: RwLock::new(HashMap::new()),
+            text_stats: RwLock::new(HashMap::new()),
             enabled: AtomicBool::new(true),
             detailed_profiling: AtomicBool::new(false),
             auto_analysis: AtomicBool::new(true),
@@ -818,6 +963,7 @@ impl Profiler {
             average_frame_time: RwLock::new(0.0),
             min_frame_time: RwLock::new(f64::MAX),
             max_frame_time: RwLock::new(0.0),
+            frame_budget: RwLock::new(DEFAULT_FRAME_BUDGET),
         })
     }
 
@@ -844,7 +990,8 @@ impl Profiler {
         self.cpu_profiler.end_section("frame");
 
         if let Some(start_time) = *self.frame_start_time.read() {
-            let frame_time = start_time.elapsed();
+            let end_time = Instant::now();
+            let frame_time = end_time - start_time;
             let frame_time_ms = frame_time.as_secs_f64() * 1000.0;
 
             // Update statistics
@@ -864,6 +1011,14 @@ impl Profiler {
                 *max = frame_time_ms;
             }
 
+            self.performance_analyzer.record_frame(FrameTiming {
+                frame_id: self.current_frame.load(Ordering::Relaxed),
+                start_time,
+                end_time,
+                cpu_time: frame_time,
+                ..FrameTiming::default()
+            });
+
             // Analyze performance if enabled
             if self.auto_analysis.load(Ordering::Relaxed) {
                 self.performance_analyzer.analyze_frame_timing(frame_time);
@@ -900,12 +1055,42 @@ impl Profiler {
         }
     }
 
+    /// Record the shader manager's compilation and variant cache counters
+    pub fn record_shader_stats(&self, stats: &CompilationStats, cached_variants: usize) {
+        let mut shader_stats = self.shader_stats.write();
+        shader_stats.insert("shader_cache_hits".to_string(), stats.cache_hits);
+        shader_stats.insert("shader_cache_misses".to_string(), stats.cache_misses);
+        shader_stats.insert("shader_variants".to_string(), cached_variants as u64);
+        shader_stats.insert("shader_compilations".to_string(), stats.total_compilations);
+        shader_stats.insert(
+            "shader_failed_compilations".to_string(),
+            stats.failed_compilations,
+        );
+        shader_stats.insert("shader_hot_reloads".to_string(), stats.hot_reloads);
+    }
+
+    /// Record the shaped text cache counters
+    pub fn record_text_cache_stats(&self, stats: &TextCacheStats) {
+        let mut text_stats = self.text_stats.write();
+        text_stats.insert("text_cache_hits".to_string(), stats.hits);
+        text_stats.insert("text_cache_misses".to_string(), stats.misses);
+        text_stats.insert("text_cache_evictions".to_string(), stats.evictions);
+        text_stats.insert("text_cache_entries".to_string(), stats.entries as u64);
+        text_stats.insert("text_cache_capacity".to_string(), stats.capacity as u64);
+        text_stats.insert(
+            "text_cache_hit_rate_percent".to_string(),
+            (stats.hit_rate() * 100.0).round() as u64,
+        );
+    }
+
     /// Get comprehensive performance report
     pub fn get_performance_report(&self) -> PerformanceReport {
         let cpu_samples = self.cpu_profiler.collect_samples();
         let memory_stats = self.memory_profiler.get_stats();
         let bottlenecks = self.performance_analyzer.get_bottlenecks();
         let suggestions = self.performance_analyzer.get_optimization_suggestions();
+        let frame_budget = *self.frame_budget.read();
+        let pacing = self.performance_analyzer.frame_pacing(frame_budget);
 
         PerformanceReport {
             frame_stats: FrameStats {
@@ -913,14 +1098,29 @@ impl Profiler {
                 average_frame_time: *self.average_frame_time.read(),
                 min_frame_time: *self.min_frame_time.read(),
                 max_frame_time: *self.max_frame_time.read(),
+                p50_frame_time: pacing.p50,
+                p95_frame_time: pacing.p95,
+                p99_frame_time: pacing.p99,
+                frame_budget: frame_budget.as_secs_f64() * 1000.0,
+                long_frames: pacing.long_frames,
+                longest_recent_frame: pacing.longest,
             },
             cpu_samples,
             memory_stats,
+            shader_stats: self.shader_stats.read().clone(),
+            text_stats: self.text_stats.read().clone(),
             bottlenecks,
             optimization_suggestions: suggestions,
         }
     }
 
+    /// Count frames longer than `budget` as long frames, 16.7ms by default
+    ///
+    /// Set it to the display's refresh interval, like 6.9ms at 144 Hz.
+    pub fn set_frame_budget(&self, budget: Duration) {
+        *self.frame_budget.write() = budget;
+    }
+
     /// Enable/disable profiling
     pub fn set_enabled(&self, enabled: bool) {
         self.enabled.store(enabled, Ordering::Relaxed);
@@ -933,3 +1133,44 @@ impl Profiler {
 }
 
 // All implementations have been consolidated above
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentiles_and_long_frames() {
+        // 90 smooth frames and 10 hitches
+        let times = (0..90)
+            .map(|_| 10.0)
+            .chain((0..10).map(|i| 20.0 + i as f64));
+        let pacing = FramePacing::from_frame_times(times, 16.6);
+        assert_eq!(pacing.p50, 10.0);
+        assert_eq!(pacing.p95, 24.0);
+        assert_eq!(pacing.p99, 28.0);
+        assert_eq!(pacing.long_frames, 10);
+        assert_eq!(pacing.longest, 29.0);
+        assert_eq!(
+            FramePacing::from_frame_times([], 16.6),
+            FramePacing::default()
+        );
+    }
+
+    #[test]
+    fn test_history_keeps_the_recent_frames() {
+        let analyzer = PerformanceAnalyzer::new();
+        let start = Instant::now();
+        for i in 0..FRAME_HISTORY_LEN + 10 {
+            // The first frames are the slow ones and fall out of the history
+            let ms = if i < 10 { 50 } else { 5 };
+            analyzer.record_frame(FrameTiming {
+                start_time: start,
+                end_time: start + Duration::from_millis(ms),
+                ..FrameTiming::default()
+            });
+        }
+        let pacing = analyzer.frame_pacing(DEFAULT_FRAME_BUDGET);
+        assert_eq!(pacing.long_frames, 0);
+        assert!((pacing.longest - 5.0).abs() < 1e-9);
+    }
+}
@@ -25,7 +25,7 @@ use std::sync::{
     Arc,
 };
 use std::time::{Duration, Instant, SystemTime};
-use tracing::{info, instrument};
+use tracing::{error, info, instrument};
 use wgpu::*;
 
 use crate::device::ManagedDevice;
@@ -66,13 +66,151 @@ pub struct ShaderMacro {
 }
 
 /// Shader variant configuration
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// Macros are expanded into the source by [`preprocess_macros`]; every
+/// feature is defined as a flag macro, so shaders can branch on it with
+/// `#ifdef`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct ShaderVariant {
     pub macros: Vec<ShaderMacro>,
     pub features: Vec<String>,
     pub optimization_level: u32,
 }
 
+impl ShaderVariant {
+    /// Add a macro definition
+    pub fn with_macro(mut self, name: impl Into<String>, value: Option<String>) -> Self {
+        self.macros.push(ShaderMacro {
+            name: name.into(),
+            value,
+        });
+        self
+    }
+
+    /// Add a feature flag
+    pub fn with_feature(mut self, feature: impl Into<String>) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    /// The same variant with macros and features in a canonical order
+    ///
+    /// Later definitions of a macro win, as they would when preprocessing.
+    pub fn normalized(&self) -> Self {
+        let mut macros: Vec<ShaderMacro> = Vec::with_capacity(self.macros.len());
+        for macro_def in &self.macros {
+            macros.retain(|m| m.name != macro_def.name);
+            macros.push(macro_def.clone());
+        }
+        macros.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut features = self.features.clone();
+        features.sort();
+        features.dedup();
+
+        Self {
+            macros,
+            features,
+            optimization_level: self.optimization_level,
+        }
+    }
+}
+
+/// A conditional block opened by `#ifdef` or `#ifndef`
+struct Conditional {
+    parent_active: bool,
+    active: bool,
+    seen_else: bool,
+}
+
+/// Expand C-style macros in WGSL source
+///
+/// Handles `#define NAME [value]`, `#undef`, `#ifdef`, `#ifndef`, `#else`
+/// and `#endif`. `defines` take precedence over `#define` lines in the
+/// source, which act as defaults. Names defined with a value are replaced by
+/// it wherever they appear as a whole identifier. Directive lines and
+/// disabled lines are blanked, so compiler errors keep their line numbers.
+pub fn preprocess_macros(source: &str, defines: &[ShaderMacro]) -> Result<String> {
+    let mut values: HashMap<String, Option<String>> = defines
+        .iter()
+        .map(|m| (m.name.clone(), m.value.clone()))
+        .collect();
+    let external: HashSet<String> = values.keys().cloned().collect();
+    let identifier = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*")?;
+
+    let mut stack: Vec<Conditional> = Vec::new();
+    let mut output = String::with_capacity(source.len());
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let active = stack.last().is_none_or(|c| c.active);
+
+        if let Some(directive) = line.trim_start().strip_prefix('#') {
+            let mut parts = directive.split_whitespace();
+            let keyword = parts.next().unwrap_or_default();
+            let name = parts.next();
+            match (keyword, name) {
+                ("ifdef" | "ifndef", Some(name)) => {
+                    let condition = values.contains_key(name) == (keyword == "ifdef");
+                    stack.push(Conditional {
+                        parent_active: active,
+                        active: active && condition,
+                        seen_else: false,
+                    });
+                }
+                ("else", _) => match stack.last_mut() {
+                    Some(c) if !c.seen_else => {
+                        c.seen_else = true;
+                        c.active = c.parent_active && !c.active;
+                    }
+                    _ => bail!("line {}: unexpected #else", line_number),
+                },
+                ("endif", _) => {
+                    if stack.pop().is_none() {
+                        bail!("line {}: #endif without #ifdef", line_number);
+                    }
+                }
+                ("define", Some(name)) => {
+                    if active && !external.contains(name) {
+                        let value = directive
+                            .trim_start()
+                            .strip_prefix("define")
+                            .map(str::trim_start)
+                            .and_then(|rest| rest.strip_prefix(name))
+                            .map(str::trim)
+                            .filter(|value| !value.is_empty())
+                            .map(str::to_string);
+                        values.insert(name.to_string(), value);
+                    }
+                }
+                ("undef", Some(name)) => {
+                    if active {
+                        values.remove(name);
+                    }
+                }
+                _ => bail!(
+                    "line {}: unsupported directive #{}",
+                    line_number,
+                    directive.trim()
+                ),
+            }
+        } else if active {
+            let expanded =
+                identifier.replace_all(line, |caps: &regex::Captures| match values.get(&caps[0]) {
+                    Some(Some(value)) => value.clone(),
+                    _ => caps[0].to_string(),
+                });
+            output.push_str(&expanded);
+        }
+        output.push('\n');
+    }
+
+    if !stack.is_empty() {
+        bail!("unterminated #ifdef at end of shader");
+    }
+    Ok(output)
+}
+
 /// Shader source information
 #[derive(Debug, Clone)]
 pub struct ShaderSource {
@@ -129,12 +267,65 @@ pub enum HotReloadEvent {
     FileDeleted(PathBuf),
     FileCreated(PathBuf),
     DependencyChanged(PathBuf, HashSet<PathBuf>),
+    /// A watched shader was recompiled; rebuild pipelines using its module
+    ShaderReloaded(PathBuf, Arc<CompiledShader>),
+    /// A watched shader failed to recompile; the previous module stays valid
+    ReloadFailed(PathBuf, String),
+}
+
+/// Quiet period after the last change to a file before it is recompiled
+///
+/// Editors often save in several steps (truncate, write, rename), each of
+/// which produces a file system event.
+const HOT_RELOAD_DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Collects changed files until they have stopped changing
+#[derive(Debug, Default)]
+struct ReloadDebouncer {
+    pending: HashMap<PathBuf, Instant>,
+}
+
+impl ReloadDebouncer {
+    /// Record a change to `path`, restarting its quiet period
+    fn record(&mut self, path: PathBuf, now: Instant) {
+        self.pending.insert(path, now);
+    }
+
+    /// Take the files that have not changed for [`HOT_RELOAD_DEBOUNCE`]
+    fn take_due(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut due = Vec::new();
+        self.pending.retain(|path, changed_at| {
+            if now.duration_since(*changed_at) >= HOT_RELOAD_DEBOUNCE {
+                due.push(path.clone());
+                false
+            } else {
+                true
+            }
+        });
+        due.sort();
+        due
+    }
+}
+
+/// Resolve `path` the way file system events report it
+///
+/// Only the parent directory is canonicalized, so files that are briefly
+/// missing while an editor replaces them still resolve.
+fn event_path(path: &Path) -> PathBuf {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    match (fs::canonicalize(parent), path.file_name()) {
+        (Ok(parent), Some(name)) => parent.join(name),
+        _ => path.to_path_buf(),
+    }
 }
 
 /// Shader manager with advanced features
 pub struct ShaderManager {
     device: Arc<ManagedDevice>,
-    shader_cache: RwLock<HashMap<([u8; 32], ShaderVariant), Arc<CompiledShader>>>,
+    shader_cache: RwLock<HashMap<(PathBuf, ShaderVariant), Arc<CompiledShader>>>,
     source_cache: RwLock<HashMap<PathBuf, Arc<ShaderSource>>>,
     dependency_graph: RwLock<HashMap<PathBuf, DependencyNode>>,
     compilation_stats: RwLock<CompilationStats>,
@@ -144,6 +335,9 @@ pub struct ShaderManager {
     hot_reload_enabled: AtomicBool,
     hot_reload_receiver: Arc<Mutex<Option<mpsc::Receiver<Event>>>>,
     watched_directories: RwLock<HashSet<PathBuf>>,
+    hot_reload_files: RwLock<HashMap<PathBuf, PathBuf>>,
+    loaded_variants: RwLock<HashMap<PathBuf, Vec<(ShaderStage, ShaderVariant)>>>,
+    pending_reloads: Mutex<ReloadDebouncer>,
 
     // Shader preprocessing
     include_directories: RwLock<Vec<PathBuf>>,
@@ -181,6 +375,9 @@ impl ShaderManager {
             hot_reload_enabled: AtomicBool::new(true),
             hot_reload_receiver: Arc::new(Mutex::new(Some(rx))),
             watched_directories: RwLock::new(HashSet::new()),
+            hot_reload_files: RwLock::new(HashMap::new()),
+            loaded_variants: RwLock::new(HashMap::new()),
+            pending_reloads: Mutex::new(ReloadDebouncer::default()),
 
             include_directories: RwLock::new(Vec::new()),
             global_macros: RwLock::new(Vec::new()),
@@ -204,13 +401,28 @@ impl ShaderManager {
         variant: ShaderVariant,
     ) -> Result<Arc<CompiledShader>> {
         let path = path.as_ref().to_path_buf();
+        let variant = variant.normalized();
 
         // Load source if not cached
         let source = self.load_source(&path, stage)?;
 
-        // Check cache first
-        let cache_key = (source.content_hash, variant.clone());
-        if let Some(cached) = self.shader_cache.read().get(&cache_key) {
+        // Remember the variant so hot-reload can recompile it
+        {
+            let mut loaded = self.loaded_variants.write();
+            let variants = loaded.entry(path.clone()).or_default();
+            if !variants.iter().any(|(s, v)| *s == stage && *v == variant) {
+                variants.push((stage, variant.clone()));
+            }
+        }
+
+        // Check cache first, ignoring modules compiled from an older source
+        let cache_key = (path, variant.clone());
+        if let Some(cached) = self
+            .shader_cache
+            .read()
+            .get(&cache_key)
+            .filter(|cached| cached.source_hash == source.content_hash)
+        {
             let mut stats = self.compilation_stats.write();
             stats.cache_hits += 1;
             cached.usage_count.fetch_add(1, Ordering::Relaxed);
@@ -316,26 +528,11 @@ impl ShaderManager {
         }
     }
 
-    /// Preprocess shader with includes and macros
+    /// Preprocess shader includes
+    ///
+    /// Macros are expanded per variant when compiling, see [`preprocess_macros`].
     fn preprocess_shader(&self, content: &str, base_path: &Path) -> Result<String> {
-        let mut processed = content.to_string();
-
-        // Apply global macros
-        for macro_def in self.global_macros.read().iter() {
-            let replacement = match &macro_def.value {
-                Some(value) => format!("#define {} {}", macro_def.name, value),
-                None => format!("#define {}", macro_def.name),
-            };
-
-            let pattern = format!(r"#define\s+{}\s*.*", regex::escape(&macro_def.name));
-            let re = Regex::new(&pattern)?;
-            processed = re.replace_all(&processed, replacement.as_str()).to_string();
-        }
-
-        // Process includes
-        processed = self.process_includes(&processed, base_path)?;
-
-        Ok(processed)
+        self.process_includes(content, base_path)
     }
 
     /// Process #include directives
@@ -463,15 +660,15 @@ impl ShaderManager {
     ) -> Result<Arc<CompiledShader>> {
         let start_time = Instant::now();
 
-        // Apply variant macros
-        let mut shader_source = source.content.clone();
-        for macro_def in &variant.macros {
-            let definition = match &macro_def.value {
-                Some(value) => format!("#define {} {}\n", macro_def.name, value),
-                None => format!("#define {}\n", macro_def.name),
-            };
-            shader_source = format!("{}{}", definition, shader_source);
-        }
+        // Expand global and variant macros; variant definitions win
+        let mut defines = self.global_macros.read().clone();
+        defines.extend(variant.macros.iter().cloned());
+        defines.extend(variant.features.iter().map(|feature| ShaderMacro {
+            name: feature.clone(),
+            value: None,
+        }));
+        let shader_source = preprocess_macros(&source.content, &defines)
+            .with_context(|| format!("Failed to preprocess shader {}", source.path.display()))?;
 
         // Create shader module descriptor
         let descriptor = ShaderModuleDescriptor {
@@ -479,8 +676,19 @@ impl ShaderManager {
             source: wgpu::ShaderSource::Wgsl(shader_source.clone().into()),
         };
 
-        // Compile shader
-        let module = self.device.device.create_shader_module(descriptor);
+        // Compile shader, reporting invalid source as an error instead of panicking
+        let module = match self
+            .device
+            .catch_validation_errors(|device| device.create_shader_module(descriptor))
+        {
+            Ok(module) => module,
+            Err(e) => {
+                let mut stats = self.compilation_stats.write();
+                stats.total_compilations += 1;
+                stats.failed_compilations += 1;
+                bail!("Failed to compile shader {}: {}", source.path.display(), e);
+            }
+        };
 
         let compilation_time = start_time.elapsed();
 
@@ -549,8 +757,12 @@ impl ShaderManager {
     }
 
     /// Process hot-reload events
+    ///
+    /// Changed and created shader files are queued for recompilation by
+    /// [`check_for_reloads`](Self::check_for_reloads) once they stop changing.
     pub fn process_hot_reload_events(&self) -> Result<Vec<HotReloadEvent>> {
         let mut events = Vec::new();
+        let now = Instant::now();
 
         if let Some(ref receiver) = *self.hot_reload_receiver.lock() {
             while let Ok(event) = receiver.try_recv() {
@@ -558,7 +770,7 @@ impl ShaderManager {
                     notify::EventKind::Modify(_) => {
                         for path in event.paths {
                             if self.is_shader_file(&path) {
-                                self.invalidate_shader_cache(&path);
+                                self.pending_reloads.lock().record(path.clone(), now);
                                 events.push(HotReloadEvent::FileChanged(path));
                             }
                         }
@@ -574,6 +786,7 @@ impl ShaderManager {
                     notify::EventKind::Create(_) => {
                         for path in event.paths {
                             if self.is_shader_file(&path) {
+                                self.pending_reloads.lock().record(path.clone(), now);
                                 events.push(HotReloadEvent::FileCreated(path));
                             }
                         }
@@ -586,6 +799,70 @@ impl ShaderManager {
         Ok(events)
     }
 
+    /// Recompile the watched shaders affected by a change to `changed`
+    ///
+    /// Shaders that fail to compile are logged and reported as
+    /// [`HotReloadEvent::ReloadFailed`]; their previous module stays cached.
+    fn reload_file(&self, changed: &Path) -> Vec<HotReloadEvent> {
+        let changed = event_path(changed);
+        let affected: Vec<PathBuf> = self
+            .hot_reload_files
+            .read()
+            .iter()
+            .filter(|(canonical, path)| {
+                **canonical == changed
+                    || self.source_cache.read().get(*path).is_some_and(|source| {
+                        source.includes.iter().any(|inc| event_path(inc) == changed)
+                    })
+            })
+            .map(|(_, path)| path.clone())
+            .collect();
+
+        let mut events = Vec::new();
+        for path in affected {
+            let variants = self
+                .loaded_variants
+                .read()
+                .get(&path)
+                .cloned()
+                .unwrap_or_default();
+            if variants.is_empty() {
+                continue;
+            }
+
+            // Force the source to be read again even if the timestamp is unchanged
+            let previous = self.source_cache.write().remove(&path);
+
+            let mut reloaded = true;
+            for (stage, variant) in variants {
+                match self.load_shader(&path, stage, variant) {
+                    Ok(shader) => events.push(HotReloadEvent::ShaderReloaded(path.clone(), shader)),
+                    Err(e) => {
+                        error!("Shader hot-reload failed for {}: {:#}", path.display(), e);
+                        events.push(HotReloadEvent::ReloadFailed(
+                            path.clone(),
+                            format!("{:#}", e),
+                        ));
+                        reloaded = false;
+                    }
+                }
+            }
+
+            // Reloaded modules replaced their cache entries; on failure keep
+            // serving the modules compiled from the previous source
+            if let Some(previous) = previous.filter(|_| !reloaded) {
+                self.source_cache.write().insert(path.clone(), previous);
+            }
+
+            if reloaded {
+                self.compilation_stats.write().hot_reloads += 1;
+                info!("Hot-reloaded shader: {}", path.display());
+            }
+        }
+
+        events
+    }
+
     /// Check if file is a shader file
     fn is_shader_file(&self, path: &Path) -> bool {
         if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
@@ -598,45 +875,12 @@ impl ShaderManager {
         }
     }
 
-    /// Invalidate shader cache for a file
-    fn invalidate_shader_cache(&self, path: &Path) {
-        // Remove from source cache
-        self.source_cache.write().remove(path);
-
-        // Find and remove dependent shaders from compiled cache
-        let dependents = self.find_dependents(path);
-        let mut cache = self.shader_cache.write();
-
-        cache.retain(|_, compiled| !dependents.contains(&compiled.source_hash));
-
-        let mut stats = self.compilation_stats.write();
-        stats.hot_reloads += 1;
-
-        info!("Invalidated shader cache for: {}", path.display());
-    }
-
     /// Remove shader from cache
     fn remove_from_cache(&self, path: &Path) {
         self.source_cache.write().remove(path);
         self.dependency_graph.write().remove(path);
     }
 
-    /// Find all shaders dependent on a file
-    fn find_dependents(&self, path: &Path) -> HashSet<[u8; 32]> {
-        let mut dependents = HashSet::new();
-        let graph = self.dependency_graph.read();
-
-        if let Some(node) = graph.get(path) {
-            for dependent_path in &node.dependents {
-                if let Some(source) = self.source_cache.read().get(dependent_path) {
-                    dependents.insert(source.content_hash);
-                }
-            }
-        }
-
-        dependents
-    }
-
     /// Add include directory
     pub fn add_include_directory(&self, path: impl AsRef<Path>) {
         self.include_directories
@@ -645,11 +889,14 @@ impl ShaderManager {
     }
 
     /// Add global macro
+    ///
+    /// Applies to every shader; cached variants are recompiled on next load.
     pub fn add_global_macro(&self, name: impl Into<String>, value: Option<String>) {
         self.global_macros.write().push(ShaderMacro {
             name: name.into(),
             value,
         });
+        self.shader_cache.write().clear();
     }
 
     /// Enable or disable hot-reload
@@ -657,6 +904,27 @@ impl ShaderManager {
         self.hot_reload_enabled.store(enabled, Ordering::Relaxed);
     }
 
+    /// Watch shader files and recompile them when they change
+    ///
+    /// Recompilation happens in [`check_for_reloads`](Self::check_for_reloads),
+    /// on the thread calling it, for every variant previously loaded with
+    /// [`load_shader`](Self::load_shader). Changes to included files reload
+    /// the shaders including them.
+    pub fn enable_hot_reload<P: AsRef<Path>>(
+        &self,
+        paths: impl IntoIterator<Item = P>,
+    ) -> Result<()> {
+        self.set_hot_reload_enabled(true);
+        for path in paths {
+            let path = path.as_ref();
+            self.watch_file(&event_path(path))?;
+            self.hot_reload_files
+                .write()
+                .insert(event_path(path), path.to_path_buf());
+        }
+        Ok(())
+    }
+
     /// Enable or disable validation
     pub fn set_validation_enabled(&self, enabled: bool) {
         self.validation_enabled.store(enabled, Ordering::Relaxed);
@@ -673,10 +941,17 @@ impl ShaderManager {
         Ok(())
     }
 
-    /// Check for shader reloads (integration method)
-    pub fn check_for_reloads(&self) -> Result<()> {
-        let _events = self.process_hot_reload_events()?;
-        Ok(())
+    /// Process file events and recompile watched shaders that stopped changing
+    ///
+    /// Call once per frame from the render thread; the returned
+    /// [`HotReloadEvent::ShaderReloaded`] events carry the new modules.
+    pub fn check_for_reloads(&self) -> Result<Vec<HotReloadEvent>> {
+        let mut events = self.process_hot_reload_events()?;
+        let due = self.pending_reloads.lock().take_due(Instant::now());
+        for path in due {
+            events.extend(self.reload_file(&path));
+        }
+        Ok(events)
     }
 
     /// Get compilation statistics
@@ -755,4 +1030,193 @@ mod tests {
     fn test_language_detection() {
         assert_eq!(ShaderLanguage::WGSL, ShaderLanguage::WGSL);
     }
+
+    #[test]
+    fn test_reload_debouncer_waits_for_quiet_period() {
+        let mut debouncer = ReloadDebouncer::default();
+        let start = Instant::now();
+        let path = PathBuf::from("triangle.wgsl");
+
+        // Three saves in quick succession
+        debouncer.record(path.clone(), start);
+        debouncer.record(path.clone(), start + Duration::from_millis(30));
+        debouncer.record(path.clone(), start + Duration::from_millis(60));
+
+        // Still changing 100ms after the first save
+        assert!(debouncer.take_due(start + HOT_RELOAD_DEBOUNCE).is_empty());
+
+        // A single reload once the file settles
+        let due = debouncer.take_due(start + Duration::from_millis(60) + HOT_RELOAD_DEBOUNCE);
+        assert_eq!(due, vec![path]);
+        assert!(debouncer
+            .take_due(start + Duration::from_secs(1))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_macro_preprocessing() {
+        let source = "#define SAMPLES 4\n\
+                      #ifdef SDF\n\
+                      let coverage = sdf(SAMPLES);\n\
+                      #else\n\
+                      let coverage = 1.0;\n\
+                      #endif\n\
+                      #ifndef GAMMA\n\
+                      let SAMPLES_TOTAL = SAMPLES;\n\
+                      #endif\n";
+
+        let plain = preprocess_macros(source, &[]).unwrap();
+        let lines: Vec<&str> = plain.lines().collect();
+        assert_eq!(lines.len(), 9);
+        assert_eq!(lines[4], "let coverage = 1.0;");
+        assert_eq!(lines[7], "let SAMPLES_TOTAL = 4;");
+        assert!(!plain.contains('#'));
+
+        let variant = ShaderVariant::default()
+            .with_feature("SDF")
+            .with_feature("GAMMA")
+            .with_macro("SAMPLES", Some("8".to_string()));
+        let mut defines = variant.macros.clone();
+        defines.extend(variant.features.iter().map(|name| ShaderMacro {
+            name: name.clone(),
+            value: None,
+        }));
+        let sdf = preprocess_macros(source, &defines).unwrap();
+        assert_eq!(sdf.lines().nth(2), Some("let coverage = sdf(8);"));
+        assert!(!sdf.contains("coverage = 1.0"));
+        assert!(!sdf.contains("SAMPLES_TOTAL"));
+
+        assert!(preprocess_macros("#ifdef SDF\n", &[]).is_err());
+        assert!(preprocess_macros("#endif\n", &[]).is_err());
+        assert!(preprocess_macros("#pragma once\n", &[]).is_err());
+    }
+
+    #[test]
+    fn test_variant_normalization() {
+        let a = ShaderVariant::default()
+            .with_macro("B", None)
+            .with_macro("A", Some("1".to_string()))
+            .with_feature("sdf")
+            .with_feature("gamma");
+        let b = ShaderVariant::default()
+            .with_feature("gamma")
+            .with_macro("A", Some("0".to_string()))
+            .with_feature("sdf")
+            .with_macro("A", Some("1".to_string()))
+            .with_macro("B", None);
+
+        assert_ne!(a, b);
+        assert_eq!(a.normalized(), b.normalized());
+        assert_eq!(a.normalized().macros[0].value.as_deref(), Some("1"));
+    }
+
+    #[tokio::test]
+    async fn test_variants_are_cached_by_path_and_variant() {
+        let manager = crate::device::DeviceManager::new(None, None).await.unwrap();
+        let device = manager.initialize_device().await.unwrap();
+        let shaders = ShaderManager::new(device).unwrap();
+        shaders.set_hot_reload_enabled(false);
+
+        let dir = std::env::temp_dir().join(format!("strato-variants-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fill.wgsl");
+        fs::write(
+            &path,
+            "#define ALPHA 1.0\n\
+             @fragment fn fs_main() -> @location(0) vec4<f32> {\n\
+             #ifdef GAMMA\n\
+                 return pow(vec4<f32>(0.5), vec4<f32>(2.2));\n\
+             #else\n\
+                 return vec4<f32>(0.5, 0.5, 0.5, ALPHA);\n\
+             #endif\n\
+             }\n",
+        )
+        .unwrap();
+
+        let load = |variant: ShaderVariant| {
+            shaders
+                .load_shader(&path, ShaderStage::Fragment, variant)
+                .unwrap()
+        };
+        let plain = load(ShaderVariant::default());
+        let gamma = load(ShaderVariant::default().with_feature("GAMMA"));
+        let opaque = load(ShaderVariant::default().with_macro("ALPHA", Some("0.5".into())));
+        assert!(!Arc::ptr_eq(&plain, &gamma));
+        assert!(!Arc::ptr_eq(&plain, &opaque));
+
+        // Requesting the same variant again is a cache hit
+        assert!(Arc::ptr_eq(&plain, &load(ShaderVariant::default())));
+        assert!(Arc::ptr_eq(
+            &gamma,
+            &load(
+                ShaderVariant::default()
+                    .with_feature("GAMMA")
+                    .with_feature("GAMMA")
+            )
+        ));
+
+        let stats = shaders.get_stats();
+        assert_eq!(stats.cache_misses, 3);
+        assert_eq!(stats.cache_hits, 2);
+        assert_eq!(shaders.get_cache_stats().0, 3);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn test_failed_reload_keeps_previous_shader() {
+        let manager = crate::device::DeviceManager::new(None, None).await.unwrap();
+        let device = manager.initialize_device().await.unwrap();
+        let shaders = ShaderManager::new(device).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("strato-hot-reload-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fill.wgsl");
+        let shader = |color: &str| {
+            format!(
+                "@vertex fn vs_main() -> @builtin(position) vec4<f32> {{ return vec4<f32>(0.0); }}\n\
+                 @fragment fn fs_main() -> @location(0) vec4<f32> {{ return {}; }}\n",
+                color
+            )
+        };
+        fs::write(&path, shader("vec4<f32>(1.0)")).unwrap();
+
+        shaders.enable_hot_reload([&path]).unwrap();
+        let variant = ShaderVariant {
+            macros: Vec::new(),
+            features: Vec::new(),
+            optimization_level: 0,
+        };
+        let original = shaders
+            .load_shader(&path, ShaderStage::Fragment, variant.clone())
+            .unwrap();
+
+        // A syntax error is reported and the compiled shader stays cached
+        fs::write(&path, shader("vec4<f32>(1.0")).unwrap();
+        let events = shaders.reload_file(&path);
+        assert!(matches!(
+            events.as_slice(),
+            [HotReloadEvent::ReloadFailed(..)]
+        ));
+        assert_eq!(shaders.get_stats().hot_reloads, 0);
+        let cached = shaders.shader_cache.read();
+        assert!(cached.values().any(|s| Arc::ptr_eq(s, &original)));
+        drop(cached);
+
+        // Fixing the file produces a new module
+        fs::write(&path, shader("vec4<f32>(0.5)")).unwrap();
+        let events = shaders.reload_file(&path);
+        match events.as_slice() {
+            [HotReloadEvent::ShaderReloaded(reloaded_path, reloaded)] => {
+                assert_eq!(reloaded_path, &path);
+                assert!(!Arc::ptr_eq(reloaded, &original));
+                assert_eq!(reloaded.variant, variant);
+            }
+            other => panic!("unexpected reload events: {:?}", other),
+        }
+        assert_eq!(shaders.get_stats().hot_reloads, 1);
+        assert_eq!(shaders.shader_cache.read().len(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -0,0 +1,62 @@
+// Separable gaussian blur, one direction per pass
+// Pixels outside the region (if any) are copied unchanged
+
+@group(0) @binding(0)
+var source_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var source_sampler: sampler;
+
+struct BlurParams {
+    // Step between taps in UV space: (1/width, 0) or (0, 1/height)
+    direction: vec2<f32>,
+    // Blur radius in pixels
+    radius: f32,
+    _padding: f32,
+    // x, y, width, height in pixels; zero width means the whole target
+    region: vec4<f32>,
+};
+
+@group(0) @binding(2)
+var<uniform> params: BlurParams;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VertexOutput {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+    out.uv = vec2<f32>(uv.x, 1.0 - uv.y);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let pixel = in.clip_position.xy;
+    let region = params.region;
+    if (region.z > 0.0 && (pixel.x < region.x || pixel.y < region.y
+        || pixel.x >= region.x + region.z || pixel.y >= region.y + region.w)) {
+        return textureSampleLevel(source_texture, source_sampler, in.uv, 0.0);
+    }
+
+    let sigma = max(params.radius * 0.5, 0.5);
+    let taps = i32(ceil(params.radius));
+    var sum = vec4<f32>(0.0);
+    var total = 0.0;
+    for (var i = -taps; i <= taps; i = i + 1) {
+        let offset = f32(i);
+        let weight = exp(-(offset * offset) / (2.0 * sigma * sigma));
+        sum = sum + textureSampleLevel(
+            source_texture,
+            source_sampler,
+            in.uv + params.direction * offset,
+            0.0,
+        ) * weight;
+        total = total + weight;
+    }
+    return sum / total;
+}
@@ -16,11 +16,20 @@ var texture: texture_2d<f32>;
 @group(0) @binding(2)
 var texture_sampler: sampler;
 
+// Whether the render target stores values as written, so that sampled
+// textures (decoded to linear by their sRGB format) must be encoded again
+override output_srgb: bool = false;
+
+// Quads compositing an offscreen layer, whose texture holds premultiplied
+// colors already encoded as the target stores them
+const FLAG_LAYER: u32 = 2u;
+
 // Vertex input
 struct VertexInput {
     @location(0) position: vec2<f32>,
     @location(1) color: vec4<f32>,
     @location(2) uv: vec2<f32>,
+    @location(4) flags: u32,
 };
 
 // Vertex output / Fragment input
@@ -28,6 +37,7 @@ struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) uv: vec2<f32>,
+    @location(2) @interpolate(flat) flags: u32,
 };
 
 // Vertex shader
@@ -37,9 +47,16 @@ fn vs_main(in: VertexInput) -> VertexOutput {
     out.clip_position = uniforms.projection * vec4<f32>(in.position, 0.0, 1.0);
     out.color = in.color;
     out.uv = in.uv;
+    out.flags = in.flags;
     return out;
 }
 
+fn linear_to_srgb(color: vec3<f32>) -> vec3<f32> {
+    let low = color * 12.92;
+    let high = 1.055 * pow(color, vec3<f32>(1.0 / 2.4)) - 0.055;
+    return select(high, low, color <= vec3<f32>(0.0031308));
+}
+
 // Fragment shader
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
@@ -47,9 +64,18 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     // If UV is (0,0), use solid color (for non-textured geometry)
     // Otherwise, modulate texture with vertex color
     var tex_color = textureSample(texture, texture_sampler, in.uv);
+    if (in.flags & FLAG_LAYER) != 0u {
+        return tex_color * in.color.a;
+    }
+    if output_srgb {
+        tex_color = vec4<f32>(linear_to_srgb(tex_color.rgb), tex_color.a);
+    }
     
     // Mix texture and vertex color
     // If texture is white (1,1,1,1) or UV is at origin, use vertex color
     // Otherwise blend texture with color
-    return tex_color * in.color;
+    let color = tex_color * in.color;
+
+    // Premultiplied alpha, so transparent windows composite without fringes
+    return vec4<f32>(color.rgb * color.a, color.a);
 }
@@ -0,0 +1,71 @@
+// Benchmarks for serial and parallel widget layout and drawing
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use glam::Vec2;
+use strato_core::layout::{set_layout_config, Constraints, Layout, LayoutConfig};
+use strato_renderer::batch::RenderBatch;
+use strato_widgets::{Column, Row, Text, Widget};
+
+/// A feed of 100 rows of 50 labels: 5,000 text widgets plus their containers
+fn feed() -> Column {
+    Column::new().children(
+        (0..100)
+            .map(|row| {
+                Box::new(
+                    Row::new().children(
+                        (0..50)
+                            .map(|column| {
+                                Box::new(Text::new(format!("Item {row}.{column}")))
+                                    as Box<dyn Widget>
+                            })
+                            .collect(),
+                    ),
+                ) as Box<dyn Widget>
+            })
+            .collect(),
+    )
+}
+
+fn bench_feed_layout(c: &mut Criterion) {
+    let constraints = Constraints::loose(1920.0, 1080.0);
+    let mut tree = feed();
+
+    let mut group = c.benchmark_group("layout_5k_widgets");
+    for (name, parallel) in [("serial", false), ("parallel", true)] {
+        set_layout_config(LayoutConfig {
+            parallel,
+            ..LayoutConfig::default()
+        });
+        group.bench_function(name, |b| b.iter(|| black_box(tree.layout(constraints))));
+    }
+    group.finish();
+
+    set_layout_config(LayoutConfig::default());
+}
+
+fn bench_feed_render(c: &mut Criterion) {
+    let mut tree = feed();
+    let size = tree.layout(Constraints::loose(1920.0, 1080.0));
+    let layout = Layout::new(Vec2::ZERO, size);
+
+    let mut group = c.benchmark_group("render_5k_widgets");
+    for (name, parallel) in [("serial", false), ("parallel", true)] {
+        set_layout_config(LayoutConfig {
+            parallel,
+            ..LayoutConfig::default()
+        });
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let mut batch = RenderBatch::new();
+                tree.render(&mut batch, layout);
+                black_box(batch.command_count())
+            })
+        });
+    }
+    group.finish();
+
+    set_layout_config(LayoutConfig::default());
+}
+
+criterion_group!(benches, bench_feed_layout, bench_feed_render);
+criterion_main!(benches);
@@ -0,0 +1,338 @@
+//! Implicit animation of layout changes
+//!
+//! Wrapping a widget in an [`AnimatedLayout`], or calling
+//! [`BuilderExt::animate_layout`](crate::builder::BuilderExt::animate_layout)
+//! on it, makes it glide to a new position or size instead of snapping when
+//! the layout around it changes, e.g. when a list item is inserted above it
+//! or a section opens. Only wrapped widgets animate.
+//!
+//! Each wrapper keeps the transition it is playing. A tree rebuilt every
+//! frame keeps animating as long as the wrapper has a stable ID from
+//! [`keyed_id`](crate::widget::keyed_id) or
+//! [`assign_path_ids`](crate::widget::assign_path_ids): a dropped wrapper
+//! hands its transition on to the next one with its ID, and a transition
+//! nobody picks up within [`HANDOFF_TIMEOUT`] is forgotten.
+
+use crate::animation::{animation_settings, AnimationSettings, Curve, Tweenable};
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, is_stable_id, Widget, WidgetContext, WidgetId,
+};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use strato_core::{
+    event::{Event, EventResult},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    types::Rect,
+};
+use strato_renderer::batch::RenderBatch;
+
+/// A move from one layout rectangle to another
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    from: Rect,
+    to: Rect,
+    elapsed: f32,
+}
+
+impl Transition {
+    fn settled(rect: Rect) -> Self {
+        Self {
+            from: rect,
+            to: rect,
+            elapsed: 0.0,
+        }
+    }
+}
+
+/// How long the transition of a dropped wrapper with a stable ID waits for
+/// a wrapper with the same ID to take it over
+pub const HANDOFF_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Transitions of dropped wrappers, with when they were dropped
+static HANDOFFS: OnceLock<Mutex<HashMap<WidgetId, (Transition, Instant)>>> = OnceLock::new();
+
+fn handoffs() -> &'static Mutex<HashMap<WidgetId, (Transition, Instant)>> {
+    HANDOFFS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Keep `transition` for the next wrapper with the stable `id`
+fn hand_off(id: WidgetId, transition: Transition) {
+    let now = Instant::now();
+    let mut handoffs = handoffs().lock();
+    handoffs.retain(|_, (_, dropped)| now.duration_since(*dropped) < HANDOFF_TIMEOUT);
+    handoffs.insert(id, (transition, now));
+}
+
+/// The transition a dropped wrapper with `id` left behind, if still fresh
+fn take_over(id: WidgetId) -> Option<Transition> {
+    let (transition, dropped) = handoffs().lock().remove(&id)?;
+    (dropped.elapsed() < HANDOFF_TIMEOUT).then_some(transition)
+}
+
+/// Animates its child from the rectangle it was last drawn in to a new one
+///
+/// The child is laid out as usual and drawn along the way at interpolated
+/// positions and sizes. A change in the middle of a transition starts the
+/// next one from where the child is shown. Transitions follow the global
+/// [`AnimationSettings`], snapping when motion is reduced, unless
+/// [`animation_settings`](Self::animation_settings) sets others.
+#[derive(Debug)]
+pub struct AnimatedLayout {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    duration: Duration,
+    curve: Curve,
+    settings: Option<AnimationSettings>,
+    /// Set when first drawn; written while rendering
+    transition: Mutex<Option<Transition>>,
+}
+
+impl AnimatedLayout {
+    /// Animate layout changes of `child` over `duration`
+    pub fn new(child: impl Widget + 'static, duration: Duration) -> Self {
+        Self {
+            id: generate_id(),
+            child: Box::new(child),
+            duration,
+            curve: Curve::EaseInOut,
+            settings: None,
+            transition: Mutex::new(None),
+        }
+    }
+
+    /// Set the easing curve, ease-in-out by default
+    pub fn curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    /// Follow `settings` instead of the global animation settings
+    pub fn animation_settings(mut self, settings: AnimationSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Whether the child is moving towards its latest layout
+    pub fn is_animating(&self) -> bool {
+        self.transition
+            .lock()
+            .is_some_and(|transition| self.progress(&transition) < 1.0)
+    }
+
+    fn progress(&self, transition: &Transition) -> f32 {
+        if transition.from == transition.to {
+            return 1.0;
+        }
+        let settings = self.settings.unwrap_or_else(animation_settings);
+        let duration = settings.scale(self.duration).as_secs_f32();
+        if duration == 0.0 {
+            1.0
+        } else {
+            (transition.elapsed / duration).min(1.0)
+        }
+    }
+
+    /// Where the child is shown partway through `transition`
+    fn shown(&self, transition: &Transition) -> Rect {
+        let t = self.curve.transform(self.progress(transition));
+        Rect::lerp(transition.from, transition.to, t)
+    }
+}
+
+impl Drop for AnimatedLayout {
+    fn drop(&mut self) {
+        // Stable IDs are kept for the wrapper rebuilt in the next frame
+        if let Some(transition) = *self.transition.get_mut() {
+            if is_stable_id(self.id) {
+                hand_off(self.id, transition);
+            }
+        }
+    }
+}
+
+impl Widget for AnimatedLayout {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.child.layout(constraints)
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        self.child.layout_cache()
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let target = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+
+        let shown = {
+            let mut transition = self.transition.lock();
+            let transition = transition.get_or_insert_with(|| {
+                is_stable_id(self.id)
+                    .then(|| take_over(self.id))
+                    .flatten()
+                    .unwrap_or_else(|| Transition::settled(target))
+            });
+            if transition.to != target {
+                *transition = Transition {
+                    from: self.shown(transition),
+                    to: target,
+                    elapsed: 0.0,
+                };
+            }
+            self.shown(transition)
+        };
+
+        self.child.render(
+            batch,
+            Layout::new(
+                glam::Vec2::new(shown.x, shown.y),
+                Size::new(shown.width, shown.height),
+            ),
+        );
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        dispatch_event(self.child.as_mut(), event)
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        if let Some(transition) = self.transition.get_mut() {
+            transition.elapsed += ctx.delta_time;
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(AnimatedLayout {
+            id: clone_id(self.id),
+            child: self.child.clone_widget(),
+            duration: self.duration,
+            curve: self.curve,
+            settings: self.settings,
+            transition: Mutex::new(*self.transition.lock()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::BuilderExt;
+    use crate::container::Container;
+    use crate::test_util::WidgetTester;
+    use strato_core::types::Color;
+    use strato_renderer::batch::DrawCommand;
+
+    /// A red box filling the window, resizing with it
+    fn filler() -> WidgetTester<AnimatedLayout> {
+        let filler = Container::new()
+            .background(Color::RED)
+            .animate_layout(Duration::from_millis(100))
+            .curve(Curve::Linear)
+            .animation_settings(AnimationSettings::default());
+        WidgetTester::new(filler, 100.0, 100.0)
+    }
+
+    fn shown_height(tester: &WidgetTester<AnimatedLayout>) -> f32 {
+        tester
+            .frame()
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                DrawCommand::Rect { rect, color, .. } if *color == Color::RED => Some(rect.height),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_moves_to_new_layout_over_the_duration() {
+        let mut tester = filler();
+        assert_eq!(shown_height(&tester), 100.0);
+
+        // Resized, the box starts at its old size and grows
+        tester.resize(100.0, 200.0);
+        assert_eq!(shown_height(&tester), 100.0);
+        assert!(tester.root().is_animating());
+        tester.advance(0.05);
+        assert!((shown_height(&tester) - 150.0).abs() < 1e-3);
+        tester.advance(0.05);
+        assert_eq!(shown_height(&tester), 200.0);
+        assert!(!tester.root().is_animating());
+
+        // Interrupted, the next change starts from the shown size
+        tester.resize(100.0, 100.0);
+        tester.advance(0.05);
+        tester.resize(100.0, 300.0);
+        assert!((shown_height(&tester) - 150.0).abs() < 1e-3);
+        tester.advance(0.1);
+        assert_eq!(shown_height(&tester), 300.0);
+    }
+
+    #[test]
+    fn test_rebuilt_keyed_widget_continues_from_last_layout() {
+        let build = || {
+            Container::new()
+                .background(Color::RED)
+                .animate_layout(Duration::from_millis(100))
+                .curve(Curve::Linear)
+                .animation_settings(AnimationSettings::default())
+                .key("animated-layout-rebuilt")
+        };
+
+        let first = WidgetTester::new(build(), 100.0, 100.0);
+        drop(first);
+        let mut second = WidgetTester::new(build(), 100.0, 200.0);
+        assert_eq!(shown_height(&second), 100.0);
+        second.advance(0.1);
+        assert_eq!(shown_height(&second), 200.0);
+
+        // The transition moved to the new wrapper, nothing is left behind
+        let id = crate::widget::keyed_id("animated-layout-rebuilt");
+        assert!(!handoffs().lock().contains_key(&id));
+    }
+
+    #[test]
+    fn test_unclaimed_handoffs_expire() {
+        let id = crate::widget::keyed_id("animated-layout-expired");
+        let stale = Instant::now() - HANDOFF_TIMEOUT;
+        handoffs()
+            .lock()
+            .insert(id, (Transition::settled(Rect::default()), stale));
+        assert!(take_over(id).is_none());
+
+        // Handing off another transition sweeps out the expired ones
+        handoffs()
+            .lock()
+            .insert(id, (Transition::settled(Rect::default()), stale));
+        hand_off(
+            crate::widget::keyed_id("animated-layout-fresh"),
+            Transition::settled(Rect::default()),
+        );
+        assert!(!handoffs().lock().contains_key(&id));
+    }
+}
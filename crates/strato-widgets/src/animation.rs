@@ -1,6 +1,8 @@
 //! Animation system for widgets
+use parking_lot::RwLock;
+use std::sync::OnceLock;
 use std::time::{Duration, Instant};
-use strato_core::types::Color;
+use strato_core::types::{Color, Rect};
 
 /// Animation curve
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -30,6 +32,92 @@ impl Curve {
     }
 }
 
+/// Global animation preferences
+///
+/// [`AnimationController`] and [`KeyframeAnimation`], and through them
+/// [`Timeline`], [`Sequence`] and [`Parallel`], play for
+/// [`scale`](Self::scale)d durations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnimationSettings {
+    /// Snap animations to their end state instead of playing them, for
+    /// users who asked the OS to reduce motion
+    pub reduce_motion: bool,
+    /// Playback speed; 2.0 plays animations in half their duration, and
+    /// zero or less snaps them like `reduce_motion`
+    pub speed_scale: f32,
+}
+
+impl Default for AnimationSettings {
+    fn default() -> Self {
+        Self {
+            reduce_motion: false,
+            speed_scale: 1.0,
+        }
+    }
+}
+
+impl AnimationSettings {
+    /// Settings that snap every animation to its end
+    pub fn reduced_motion() -> Self {
+        Self {
+            reduce_motion: true,
+            ..Self::default()
+        }
+    }
+
+    /// How long an animation authored to last `duration` plays
+    pub fn scale(&self, duration: Duration) -> Duration {
+        if self.reduce_motion || !(self.speed_scale > 0.0 && self.speed_scale.is_finite()) {
+            Duration::ZERO
+        } else {
+            duration.div_f32(self.speed_scale)
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct AnimationSettingsSlot {
+    system: AnimationSettings,
+    overridden: Option<AnimationSettings>,
+}
+
+impl AnimationSettingsSlot {
+    fn effective(&self) -> AnimationSettings {
+        self.overridden.unwrap_or(self.system)
+    }
+}
+
+static ANIMATION_SETTINGS: OnceLock<RwLock<AnimationSettingsSlot>> = OnceLock::new();
+
+fn animation_settings_slot() -> &'static RwLock<AnimationSettingsSlot> {
+    ANIMATION_SETTINGS.get_or_init(|| RwLock::new(AnimationSettingsSlot::default()))
+}
+
+/// The animation settings in effect: the manual override if set, otherwise
+/// the ones read from the platform
+pub fn animation_settings() -> AnimationSettings {
+    animation_settings_slot().read().effective()
+}
+
+/// Override the platform's animation settings, e.g. from an in-app
+/// accessibility option
+pub fn set_animation_settings(settings: AnimationSettings) {
+    animation_settings_slot().write().overridden = Some(settings);
+}
+
+/// Drop the manual override and follow the platform's settings again
+pub fn clear_animation_settings() {
+    animation_settings_slot().write().overridden = None;
+}
+
+/// Record the animation settings read from the platform
+///
+/// Called by the platform layer; apps override with
+/// [`set_animation_settings`] instead.
+pub fn set_system_animation_settings(settings: AnimationSettings) {
+    animation_settings_slot().write().system = settings;
+}
+
 /// Controls an animation's state and progress
 #[derive(Debug, Clone)]
 pub struct AnimationController {
@@ -38,6 +126,7 @@ pub struct AnimationController {
     curve: Curve,
     is_repeating: bool,
     is_reversed: bool,
+    settings: Option<AnimationSettings>,
 }
 
 impl AnimationController {
@@ -49,6 +138,7 @@ impl AnimationController {
             curve: Curve::Linear,
             is_repeating: false,
             is_reversed: false,
+            settings: None,
         }
     }
 
@@ -58,6 +148,12 @@ impl AnimationController {
         self
     }
 
+    /// Play under `settings` instead of the global [`animation_settings`]
+    pub fn animation_settings(mut self, settings: AnimationSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
     /// Set repeating
     pub fn loop_forever(mut self) -> Self {
         self.is_repeating = true;
@@ -81,7 +177,7 @@ impl AnimationController {
         };
 
         let elapsed = start.elapsed().as_secs_f32();
-        let duration = self.duration.as_secs_f32();
+        let duration = self.scaled_duration().as_secs_f32();
 
         if duration == 0.0 {
             return 1.0;
@@ -109,11 +205,19 @@ impl AnimationController {
             return false;
         }
         if let Some(start) = self.start_time {
-            start.elapsed() >= self.duration
+            start.elapsed() >= self.scaled_duration()
         } else {
             false
         }
     }
+
+    /// The duration under this controller's settings or the current
+    /// [`animation_settings`]
+    fn scaled_duration(&self) -> Duration {
+        self.settings
+            .unwrap_or_else(animation_settings)
+            .scale(self.duration)
+    }
 }
 
 /// Tween interface for interpolating values
@@ -138,6 +242,17 @@ impl Tweenable for Color {
     }
 }
 
+impl Tweenable for Rect {
+    fn lerp(start: Self, end: Self, t: f32) -> Self {
+        Rect::new(
+            f32::lerp(start.x, end.x, t),
+            f32::lerp(start.y, end.y, t),
+            f32::lerp(start.width, end.width, t),
+            f32::lerp(start.height, end.height, t),
+        )
+    }
+}
+
 /// Simple tween object
 #[derive(Debug, Clone, Copy)]
 pub struct Tween<T: Tweenable> {
@@ -167,6 +282,7 @@ pub enum AnimationStatus {
 }
 
 /// Advanced Timeline for managing complex animations
+#[derive(Debug)]
 pub struct Timeline {
     animations: Vec<Box<dyn Animation>>,
     status: AnimationStatus,
@@ -238,7 +354,9 @@ impl Timeline {
 }
 
 /// Trait for any animation
-pub trait Animation: std::fmt::Debug {
+///
+/// Animations are `Send + Sync` so widgets can own the timelines they play.
+pub trait Animation: std::fmt::Debug + Send + Sync {
     fn update(&mut self, elapsed: Duration);
     fn is_finished(&self) -> bool;
     fn reset(&mut self);
@@ -269,18 +387,23 @@ impl<T: Tweenable + std::fmt::Debug + Send + Sync> KeyframeAnimation<T> {
         self.controller = self.controller.with_curve(curve);
         self
     }
+
+    /// Play under `settings` instead of the global [`animation_settings`]
+    pub fn animation_settings(mut self, settings: AnimationSettings) -> Self {
+        self.controller = self.controller.animation_settings(settings);
+        self
+    }
 }
 
 impl<T: Tweenable + std::fmt::Debug + Send + Sync> Animation for KeyframeAnimation<T> {
     fn update(&mut self, elapsed: Duration) {
-        let d_secs = self.controller.duration.as_secs_f32();
-        if d_secs == 0.0 {
-            self.finished = true;
-            return;
-        }
-
-        let t_secs = elapsed.as_secs_f32();
-        let raw_t = t_secs / d_secs;
+        let d_secs = self.duration().as_secs_f32();
+        // Zero-length animations, e.g. under reduced motion, jump to the end
+        let raw_t = if d_secs == 0.0 {
+            1.0
+        } else {
+            elapsed.as_secs_f32() / d_secs
+        };
 
         self.finished = raw_t >= 1.0;
 
@@ -303,7 +426,7 @@ impl<T: Tweenable + std::fmt::Debug + Send + Sync> Animation for KeyframeAnimati
     }
 
     fn duration(&self) -> Duration {
-        self.controller.duration
+        self.controller.scaled_duration()
     }
 }
 
@@ -398,3 +521,61 @@ impl Animation for Parallel {
             .unwrap_or(Duration::ZERO)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use strato_core::state::Signal;
+
+    #[test]
+    fn test_settings_scale_durations() {
+        let second = Duration::from_secs(1);
+        let faster = AnimationSettings {
+            speed_scale: 2.0,
+            ..AnimationSettings::default()
+        };
+        let stopped = AnimationSettings {
+            speed_scale: 0.0,
+            ..AnimationSettings::default()
+        };
+
+        assert_eq!(AnimationSettings::default().scale(second), second);
+        assert_eq!(faster.scale(second), Duration::from_millis(500));
+        assert_eq!(stopped.scale(second), Duration::ZERO);
+        assert_eq!(
+            AnimationSettings::reduced_motion().scale(second),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn test_reduced_motion_snaps_animations_to_end() {
+        let target = Signal::new(0.0);
+        let mut keyframes = KeyframeAnimation::new(
+            Duration::from_secs(10),
+            Tween::new(0.0, 1.0),
+            target.clone(),
+        )
+        .animation_settings(AnimationSettings::reduced_motion());
+        keyframes.update(Duration::ZERO);
+        let mut controller = AnimationController::new(Duration::from_secs(10))
+            .animation_settings(AnimationSettings::reduced_motion());
+        controller.start();
+
+        assert_eq!(target.get(), 1.0);
+        assert!(keyframes.is_finished());
+        assert_eq!(controller.value(), 1.0);
+    }
+
+    #[test]
+    fn test_override_wins_over_system_settings() {
+        let mut slot = AnimationSettingsSlot {
+            system: AnimationSettings::reduced_motion(),
+            overridden: None,
+        };
+        assert_eq!(slot.effective(), AnimationSettings::reduced_motion());
+
+        slot.overridden = Some(AnimationSettings::default());
+        assert_eq!(slot.effective(), AnimationSettings::default());
+    }
+}
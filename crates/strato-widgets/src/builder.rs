@@ -1,7 +1,127 @@
 //! Widget builder utilities
+//!
+//! [`WidgetBuilder`] composes widget trees in plain Rust, for trees with
+//! enough logic that the `view!` macro gets in the way. Children are boxed
+//! for you, and [`child_if`](WidgetBuilder::child_if),
+//! [`children_from_iter`](WidgetBuilder::children_from_iter) and
+//! [`when`](WidgetBuilder::when) keep conditional parts of the tree inline:
+//!
+//! ```
+//! use strato_widgets::prelude::*;
+//!
+//! fn card(name: &str, content: &str, tags: &[&str], verified: bool) -> Box<dyn Widget> {
+//!     Container::new()
+//!         .padding(16.0)
+//!         .border_radius(10.0)
+//!         .builder()
+//!         .child(
+//!             Column::new()
+//!                 .spacing(12.0)
+//!                 .builder()
+//!                 .child(
+//!                     Row::new()
+//!                         .spacing(12.0)
+//!                         .builder()
+//!                         .child(Text::new(name).size(15.0))
+//!                         .child_if(verified, Text::new("✔").size(12.0)),
+//!                 )
+//!                 .child(Text::new(content).size(14.0))
+//!                 .when(!tags.is_empty(), |card| {
+//!                     card.child(
+//!                         Row::new()
+//!                             .spacing(6.0)
+//!                             .builder()
+//!                             .children_from_iter(tags.iter().map(|tag| Text::new(*tag))),
+//!                     )
+//!                 }),
+//!         )
+//!         .boxed()
+//! }
+//!
+//! let feed = Column::new()
+//!     .spacing(16.0)
+//!     .builder()
+//!     .child(card("NDR", "Moin!", &["news"], true))
+//!     .child(card("Simon", "Tools for journalists", &[], false))
+//!     .build();
+//! assert_eq!(Widget::children(&feed).len(), 2);
+//! ```
 
-use crate::widget::Widget;
-// Removed unused strato_core::layout::Constraints import
+use crate::animated_layout::AnimatedLayout;
+use crate::container::Container;
+use crate::grid::Grid;
+use crate::layout::{Column, Row, Stack};
+use crate::widget::{keyed_id, Widget};
+use crate::wrap::Wrap;
+use std::time::Duration;
+
+/// A widget that other widgets can be added to
+pub trait ParentWidget: Widget + Sized {
+    /// Add a child after the existing ones
+    ///
+    /// Widgets with a single child, like [`Container`], replace it.
+    fn push_child(self, child: Box<dyn Widget>) -> Self;
+}
+
+impl ParentWidget for Column {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child(child)
+    }
+}
+
+impl ParentWidget for Row {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child(child)
+    }
+}
+
+impl ParentWidget for Stack {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child(child)
+    }
+}
+
+impl ParentWidget for Grid {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child(child)
+    }
+}
+
+impl ParentWidget for Wrap {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child(child)
+    }
+}
+
+impl ParentWidget for Container {
+    fn push_child(self, child: Box<dyn Widget>) -> Self {
+        self.child_boxed(child)
+    }
+}
+
+/// Anything that can be added to a [`WidgetBuilder`] as a child
+pub trait IntoChild {
+    /// Box the child for its parent
+    fn into_child(self) -> Box<dyn Widget>;
+}
+
+impl<W: Widget + 'static> IntoChild for W {
+    fn into_child(self) -> Box<dyn Widget> {
+        Box::new(self)
+    }
+}
+
+impl IntoChild for Box<dyn Widget> {
+    fn into_child(self) -> Box<dyn Widget> {
+        self
+    }
+}
+
+impl<W: Widget + 'static> IntoChild for WidgetBuilder<W> {
+    fn into_child(self) -> Box<dyn Widget> {
+        self.boxed()
+    }
+}
 
 /// Widget builder for fluent API
 pub struct WidgetBuilder<W: Widget> {
@@ -14,10 +134,68 @@ impl<W: Widget> WidgetBuilder<W> {
         Self { widget }
     }
 
+    /// Give the widget a stable ID derived from `key`
+    pub fn key(mut self, key: &str) -> Self {
+        self.widget.set_id(keyed_id(key));
+        self
+    }
+
+    /// Configure the widget being built, e.g. with its own builder methods
+    pub fn with(self, configure: impl FnOnce(W) -> W) -> Self {
+        Self::new(configure(self.widget))
+    }
+
+    /// Apply `build` only when `condition` holds
+    pub fn when(self, condition: bool, build: impl FnOnce(Self) -> Self) -> Self {
+        if condition {
+            build(self)
+        } else {
+            self
+        }
+    }
+
     /// Build the widget
     pub fn build(self) -> W {
         self.widget
     }
+
+    /// Build the widget, boxed for use as a child or return value
+    pub fn boxed(self) -> Box<dyn Widget>
+    where
+        W: 'static,
+    {
+        Box::new(self.widget)
+    }
+}
+
+impl<W: ParentWidget> WidgetBuilder<W> {
+    /// Add a child
+    pub fn child(self, child: impl IntoChild) -> Self {
+        Self::new(self.widget.push_child(child.into_child()))
+    }
+
+    /// Add a child only when `condition` holds
+    ///
+    /// The child is built either way; use [`when`](Self::when) when building
+    /// it is expensive.
+    pub fn child_if(self, condition: bool, child: impl IntoChild) -> Self {
+        if condition {
+            self.child(child)
+        } else {
+            self
+        }
+    }
+
+    /// Add every child an iterator yields, in order
+    pub fn children_from_iter<I>(self, children: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: IntoChild,
+    {
+        children
+            .into_iter()
+            .fold(self, |builder, child| builder.child(child))
+    }
 }
 
 /// Extension trait for widget building
@@ -29,6 +207,136 @@ pub trait BuilderExt: Sized {
     {
         WidgetBuilder::new(self)
     }
+
+    /// Give the widget a stable ID derived from `key`
+    ///
+    /// The ID is the same in every run and wherever the widget sits in the
+    /// tree; see [`keyed_id`].
+    fn key(mut self, key: &str) -> Self
+    where
+        Self: Widget,
+    {
+        self.set_id(keyed_id(key));
+        self
+    }
+
+    /// Animate changes to the widget's layout over `duration`
+    ///
+    /// See [`AnimatedLayout`]; give the widget a [`key`](Self::key) first in
+    /// trees that are rebuilt.
+    fn animate_layout(self, duration: Duration) -> AnimatedLayout
+    where
+        Self: Widget + 'static,
+    {
+        AnimatedLayout::new(self, duration)
+    }
 }
 
 impl<T> BuilderExt for T where T: Widget {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::Text;
+    use crate::widget::{assign_path_ids, is_keyed_id, WidgetId};
+
+    #[test]
+    fn test_conditional_children() {
+        let column = Column::new()
+            .builder()
+            .child(Text::new("always"))
+            .child_if(false, Text::new("skipped"))
+            .child_if(true, Text::new("kept"))
+            .when(false, |b| b.child(Text::new("skipped")))
+            .when(true, |b| {
+                b.children_from_iter((0..3).map(|i| Text::new(i.to_string())))
+            })
+            .build();
+
+        let texts: Vec<String> = Widget::children(&column)
+            .iter()
+            .map(|child| {
+                child
+                    .as_any()
+                    .downcast_ref::<Text>()
+                    .expect("text child")
+                    .content()
+            })
+            .collect();
+        assert_eq!(texts, ["always", "kept", "0", "1", "2"]);
+    }
+
+    #[test]
+    fn test_nested_builders_keep_ids() {
+        let text = Text::new("inner");
+        let text_id = text.id();
+        let row = Row::new().builder().child(text);
+        let row_id = row.widget.id();
+
+        let root = Container::new().builder().child(row).boxed();
+        let row = root.children()[0];
+        assert_eq!(row.id(), row_id);
+        assert_eq!(row.children()[0].id(), text_id);
+    }
+
+    #[test]
+    fn test_keyed_ids_are_stable() {
+        let first = Text::new("a").key("title");
+        let second = Row::new().builder().key("title").build();
+        assert_eq!(first.id(), second.id());
+        assert_ne!(first.id(), Text::new("a").key("subtitle").id());
+
+        // Unkeyed widgets keep counting up
+        assert_ne!(Text::new("a").id(), Text::new("a").id());
+    }
+
+    /// IDs of the tree at `widget`, depth first
+    fn ids(widget: &dyn Widget) -> Vec<WidgetId> {
+        let mut tree = vec![widget.id()];
+        for child in widget.children() {
+            tree.extend(ids(child));
+        }
+        tree
+    }
+
+    fn list(labels: &[&str]) -> Column {
+        Column::new()
+            .builder()
+            .child(Text::new("header"))
+            .children_from_iter(
+                labels
+                    .iter()
+                    .map(|label| Row::new().builder().key(label).child(Text::new(*label))),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_path_ids_match_between_builds() {
+        let mut first = list(&["a", "b"]);
+        let mut second = list(&["a", "b"]);
+        assert_ne!(ids(&first), ids(&second));
+
+        assign_path_ids(&mut first);
+        assign_path_ids(&mut second);
+        assert_eq!(ids(&first), ids(&second));
+
+        // Assigning again changes nothing
+        let before = ids(&first);
+        assign_path_ids(&mut first);
+        assert_eq!(ids(&first), before);
+    }
+
+    #[test]
+    fn test_keyed_subtree_keeps_ids_when_moved() {
+        let mut before = list(&["a", "b"]);
+        let mut after = list(&["b", "a"]);
+        assign_path_ids(&mut before);
+        assign_path_ids(&mut after);
+
+        let subtree = |column: &Column, index: usize| ids(Widget::children(column)[index]);
+        assert_eq!(subtree(&before, 1), subtree(&after, 2));
+        assert_eq!(subtree(&before, 2), subtree(&after, 1));
+        assert!(is_keyed_id(Widget::children(&after)[1].id()));
+    }
+}
@@ -2,21 +2,28 @@
 //!
 //! Provides interactive button components with various styles, states, and event handling.
 
+use crate::animation::Curve;
 use crate::control::{ControlRole, ControlState};
-use crate::widget::{generate_id, Widget, WidgetContext, WidgetId, WidgetState};
-use std::{any::Any, sync::Arc};
+use crate::gesture::PressGestures;
+use crate::text::measure_line_metrics;
+use crate::theme::ColorPalette;
+use crate::widget::{
+    clone_id, generate_id, register_bounds, Widget, WidgetContext, WidgetId, WidgetState,
+};
+use std::{any::Any, sync::Arc, time::Duration};
 use strato_core::{
     event::{Event, EventResult},
     layout::{Constraints, Layout, Size},
     state::Signal,
-    theme::{Color, Theme},
+    theme::{Color, Spacing, Theme},
     types::Rect,
-    types::{Point, Transform},
+    types::{Background, BorderRadius, Point, Transform},
     taffy::{
         prelude::*,
         style::{Dimension, LengthPercentage},
     },
     taffy_layout::{TaffyLayoutError, TaffyLayoutResult, TaffyWidget},
+    window::CursorIcon,
 };
 use strato_renderer::{batch::RenderBatch, vertex::VertexBuilder};
 
@@ -24,109 +31,408 @@ use strato_renderer::{batch::RenderBatch, vertex::VertexBuilder};
 pub type ButtonState = WidgetState;
 
 /// Button style configuration
+///
+/// A button draws the background of its current state: hovering fades from
+/// `background_color` to `hover_color` over [`HOVER_TRANSITION`] seconds,
+/// while the pressed and disabled colors apply at once. Build one state by
+/// state with [`ButtonStyle::builder`].
+///
+/// A `fill` such as a gradient or an image replaces the background colors
+/// while the button is enabled. Hover, focus and press then show as a wash
+/// of the text color over the fill.
 #[derive(Debug, Clone)]
 pub struct ButtonStyle {
+    /// Background at rest
     pub background_color: Color,
+    /// Background under the pointer
     pub hover_color: Color,
+    /// Background while held down
     pub pressed_color: Color,
-    pub disabled_color: Color,
+    /// Background while disabled, text and border are faded instead
+    ///
+    /// `None` fades the background color by the theme's disabled opacity.
+    pub disabled_color: Option<Color>,
+    /// Background while focused from the keyboard
+    ///
+    /// `None` mixes the background and hover colors.
+    pub focused_color: Option<Color>,
+    /// Fill drawn instead of the background colors while enabled
+    pub fill: Option<Background>,
     pub text_color: Color,
     pub border_radius: f32,
     pub border_width: f32,
     pub border_color: Color,
+    /// Space between the text and the left and right edges
     pub padding: f32,
+    /// Space between the line of text and the top and bottom edges
+    pub vertical_padding: f32,
     pub font_size: f32,
+    /// Narrowest the button is laid out, so short labels aren't cramped
     pub min_width: f32,
     pub min_height: f32,
+    /// Spacing set explicitly, which [`apply_spacing`](Self::apply_spacing)
+    /// leaves alone
+    explicit: ExplicitSpacing,
+}
+
+/// Which of a [`ButtonStyle`]'s spacing values were set explicitly
+#[derive(Debug, Clone, Copy, Default)]
+struct ExplicitSpacing {
+    padding: bool,
+    vertical_padding: bool,
+    min_width: bool,
 }
 
 impl Default for ButtonStyle {
     fn default() -> Self {
-        Self {
-            background_color: Color::rgba(0.2, 0.4, 0.8, 1.0),
-            hover_color: Color::rgba(0.3, 0.5, 0.9, 1.0),
+        let background_color = Color::rgba(0.2, 0.4, 0.8, 1.0);
+        let hover_color = Color::rgba(0.3, 0.5, 0.9, 1.0);
+        let mut style = Self {
+            background_color,
+            hover_color,
             pressed_color: Color::rgba(0.1, 0.3, 0.7, 1.0),
-            disabled_color: Color::rgba(0.5, 0.5, 0.5, 1.0),
+            disabled_color: None,
+            focused_color: None,
+            fill: None,
             text_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             border_radius: 4.0,
             border_width: 0.0,
             border_color: Color::rgba(0.0, 0.0, 0.0, 0.0),
-            padding: 12.0,
+            padding: 0.0,
+            vertical_padding: 0.0,
             font_size: 14.0,
-            min_width: 80.0,
-            min_height: 32.0,
+            min_width: 0.0,
+            min_height: 0.0,
+            explicit: ExplicitSpacing::default(),
+        };
+        style.apply_spacing(&Spacing::default());
+        style
+    }
+}
+
+/// Border drawn around a button
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonBorder {
+    pub width: f32,
+    pub color: Color,
+}
+
+/// Full description of a custom button look, see [`ButtonStyle::custom`]
+#[derive(Debug, Clone)]
+pub struct ButtonStyleSpec {
+    /// Background in the normal state
+    pub bg: Color,
+    /// Background while hovered
+    pub bg_hover: Color,
+    /// Background while pressed
+    pub bg_pressed: Color,
+    /// Text color
+    pub fg: Color,
+    pub border: Option<ButtonBorder>,
+    pub radius: f32,
+    /// Space between the text and the left and right edges
+    pub padding: f32,
+}
+
+impl Default for ButtonStyleSpec {
+    fn default() -> Self {
+        let style = ButtonStyle::default();
+        Self {
+            bg: style.background_color,
+            bg_hover: style.hover_color,
+            bg_pressed: style.pressed_color,
+            fg: style.text_color,
+            border: None,
+            radius: style.border_radius,
+            padding: style.padding,
         }
     }
 }
 
 impl ButtonStyle {
-    /// Create a primary button style
-    pub fn primary() -> Self {
+    /// Create a fully custom style from a spec
+    ///
+    /// The disabled background is the normal one faded, and the focused one
+    /// lies between the normal and hover backgrounds, both following the
+    /// background if it is restyled later. Properties the spec
+    /// doesn't cover (font size, vertical padding and minimum size) keep
+    /// their defaults.
+    pub fn custom(spec: ButtonStyleSpec) -> Self {
+        let border = spec.border.unwrap_or(ButtonBorder {
+            width: 0.0,
+            color: Color::rgba(0.0, 0.0, 0.0, 0.0),
+        });
         Self {
-            background_color: Color::rgba(0.0, 0.4, 0.8, 1.0),
-            hover_color: Color::rgba(0.1, 0.5, 0.9, 1.0),
-            pressed_color: Color::rgba(0.0, 0.3, 0.7, 1.0),
+            background_color: spec.bg,
+            hover_color: spec.bg_hover,
+            pressed_color: spec.bg_pressed,
+            disabled_color: None,
+            focused_color: None,
+            text_color: spec.fg,
+            border_radius: spec.radius,
+            border_width: border.width,
+            border_color: border.color,
+            padding: spec.padding,
+            explicit: ExplicitSpacing {
+                padding: spec.padding != ButtonStyle::default().padding,
+                ..Default::default()
+            },
             ..Default::default()
         }
     }
 
+    /// Create a primary button style
+    pub fn primary() -> Self {
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.0, 0.4, 0.8, 1.0),
+            bg_hover: Color::rgba(0.1, 0.5, 0.9, 1.0),
+            bg_pressed: Color::rgba(0.0, 0.3, 0.7, 1.0),
+            ..Default::default()
+        })
+    }
+
     /// Create a secondary button style
     pub fn secondary() -> Self {
-        Self {
-            background_color: Color::rgba(0.6, 0.6, 0.6, 1.0),
-            hover_color: Color::rgba(0.7, 0.7, 0.7, 1.0),
-            pressed_color: Color::rgba(0.5, 0.5, 0.5, 1.0),
-            text_color: Color::rgba(0.0, 0.0, 0.0, 1.0),
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.6, 0.6, 0.6, 1.0),
+            bg_hover: Color::rgba(0.7, 0.7, 0.7, 1.0),
+            bg_pressed: Color::rgba(0.5, 0.5, 0.5, 1.0),
+            fg: Color::rgba(0.0, 0.0, 0.0, 1.0),
             ..Default::default()
-        }
+        })
     }
 
     /// Create a danger button style
     pub fn danger() -> Self {
-        Self {
-            background_color: Color::rgba(0.8, 0.2, 0.2, 1.0),
-            hover_color: Color::rgba(0.9, 0.3, 0.3, 1.0),
-            pressed_color: Color::rgba(0.7, 0.1, 0.1, 1.0),
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.8, 0.2, 0.2, 1.0),
+            bg_hover: Color::rgba(0.9, 0.3, 0.3, 1.0),
+            bg_pressed: Color::rgba(0.7, 0.1, 0.1, 1.0),
             ..Default::default()
-        }
+        })
     }
 
     /// Create an outline button style
     pub fn outline() -> Self {
-        Self {
-            background_color: Color::rgba(0.0, 0.0, 0.0, 0.0),
-            hover_color: Color::rgba(0.0, 0.4, 0.8, 0.1),
-            pressed_color: Color::rgba(0.0, 0.4, 0.8, 0.2),
-            text_color: Color::rgba(0.0, 0.4, 0.8, 1.0),
-            border_width: 1.0,
-            border_color: Color::rgba(0.0, 0.4, 0.8, 1.0),
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.0, 0.0, 0.0, 0.0),
+            bg_hover: Color::rgba(0.0, 0.4, 0.8, 0.1),
+            bg_pressed: Color::rgba(0.0, 0.4, 0.8, 0.2),
+            fg: Color::rgba(0.0, 0.4, 0.8, 1.0),
+            border: Some(ButtonBorder {
+                width: 1.0,
+                color: Color::rgba(0.0, 0.4, 0.8, 1.0),
+            }),
             ..Default::default()
-        }
+        })
     }
 
     /// Create a ghost button style
     pub fn ghost() -> Self {
-        Self {
-            background_color: Color::rgba(0.0, 0.0, 0.0, 0.0),
-            hover_color: Color::rgba(0.0, 0.0, 0.0, 0.05),
-            pressed_color: Color::rgba(0.0, 0.0, 0.0, 0.1),
-            text_color: Color::rgba(0.3, 0.3, 0.3, 1.0),
-            border_width: 0.0,
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.0, 0.0, 0.0, 0.0),
+            bg_hover: Color::rgba(0.0, 0.0, 0.0, 0.05),
+            bg_pressed: Color::rgba(0.0, 0.0, 0.0, 0.1),
+            fg: Color::rgba(0.3, 0.3, 0.3, 1.0),
             ..Default::default()
-        }
+        })
     }
 
     /// Create a text button style (transparent background)
     pub fn text() -> Self {
-        Self {
-            background_color: Color::rgba(0.0, 0.0, 0.0, 0.0),
-            hover_color: Color::rgba(0.0, 0.0, 0.0, 0.05),
-            pressed_color: Color::rgba(0.0, 0.0, 0.0, 0.1),
-            text_color: Color::rgba(0.0, 0.4, 0.8, 1.0),
-            border_width: 0.0,
+        Self::custom(ButtonStyleSpec {
+            bg: Color::rgba(0.0, 0.0, 0.0, 0.0),
+            bg_hover: Color::rgba(0.0, 0.0, 0.0, 0.05),
+            bg_pressed: Color::rgba(0.0, 0.0, 0.0, 0.1),
+            fg: Color::rgba(0.0, 0.4, 0.8, 1.0),
             ..Default::default()
+        })
+    }
+
+    /// Start a style from the defaults, one state at a time
+    pub fn builder() -> ButtonStyleBuilder {
+        ButtonStyleBuilder::new()
+    }
+
+    /// Start a style from this one, e.g. to restyle one state of a preset
+    pub fn to_builder(&self) -> ButtonStyleBuilder {
+        ButtonStyleBuilder {
+            style: self.clone(),
+        }
+    }
+
+    /// Take the padding and minimum width from a theme's spacing scale
+    ///
+    /// Buttons are padded by `md` at the sides and `sm` above and below, and
+    /// are at least four `md` wide. Values set through the style builder,
+    /// a spec or [`Button::min_width`] are kept.
+    pub fn apply_spacing(&mut self, spacing: &Spacing) {
+        if !self.explicit.padding {
+            self.padding = spacing.md;
+        }
+        if !self.explicit.vertical_padding {
+            self.vertical_padding = spacing.sm;
+        }
+        if !self.explicit.min_width {
+            self.min_width = spacing.md * 4.0;
         }
     }
+
+    /// Background color used for a given state once its transition is over
+    pub fn background_for(&self, state: ButtonState) -> Color {
+        let hover = if state == ButtonState::Hovered {
+            1.0
+        } else {
+            0.0
+        };
+        self.background_at(state, hover)
+    }
+
+    /// Background color for `state`, `hover` of the way into hovering
+    ///
+    /// `hover` runs from 0 at rest to 1 under the pointer. Focused buttons
+    /// fade from their focused color to the hover color instead.
+    pub fn background_at(&self, state: ButtonState, hover: f32) -> Color {
+        let hover = hover.clamp(0.0, 1.0);
+        match state {
+            ButtonState::Normal | ButtonState::Hovered => {
+                blend_colors(self.background_color, self.hover_color, hover)
+            }
+            ButtonState::Pressed => self.pressed_color,
+            ButtonState::Disabled => self.disabled_background(&ColorPalette::default()),
+            ButtonState::Focused => blend_colors(self.focused_background(), self.hover_color, hover),
+        }
+    }
+
+    /// Background while disabled, faded by `palette` unless set explicitly
+    pub fn disabled_background(&self, palette: &ColorPalette) -> Color {
+        self.disabled_color.unwrap_or_else(|| {
+            let faded = palette.disabled(self.background_color.to_types_color());
+            Color::rgba(faded.r, faded.g, faded.b, faded.a)
+        })
+    }
+
+    /// Background while focused, between the background and hover colors
+    /// unless set explicitly
+    pub fn focused_background(&self) -> Color {
+        self.focused_color.unwrap_or_else(|| {
+            blend_colors(self.background_color, self.hover_color, FOCUSED_BLEND)
+        })
+    }
+}
+
+/// Builder for a [`ButtonStyle`] with a color per state
+///
+/// ```
+/// use strato_core::theme::Color;
+/// use strato_widgets::ButtonStyle;
+///
+/// let style = ButtonStyle::builder()
+///     .background(Color::rgb(0.1, 0.6, 0.3))
+///     .hover(Color::rgb(0.15, 0.7, 0.35))
+///     .pressed(Color::rgb(0.05, 0.5, 0.25))
+///     .radius(8.0)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ButtonStyleBuilder {
+    style: ButtonStyle,
+}
+
+impl ButtonStyleBuilder {
+    /// Start from [`ButtonStyle::default`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Background at rest
+    pub fn background(mut self, color: Color) -> Self {
+        self.style.background_color = color;
+        self
+    }
+
+    /// Background under the pointer
+    pub fn hover(mut self, color: Color) -> Self {
+        self.style.hover_color = color;
+        self
+    }
+
+    /// Background while held down
+    pub fn pressed(mut self, color: Color) -> Self {
+        self.style.pressed_color = color;
+        self
+    }
+
+    /// Background while disabled
+    pub fn disabled(mut self, color: Color) -> Self {
+        self.style.disabled_color = Some(color);
+        self
+    }
+
+    /// Background while focused from the keyboard
+    pub fn focused(mut self, color: Color) -> Self {
+        self.style.focused_color = Some(color);
+        self
+    }
+
+    /// Fill drawn instead of the background colors while enabled, e.g. a
+    /// [`LinearGradient`](strato_core::types::LinearGradient)
+    pub fn fill(mut self, fill: impl Into<Background>) -> Self {
+        self.style.fill = Some(fill.into());
+        self
+    }
+
+    /// Text color
+    pub fn text_color(mut self, color: Color) -> Self {
+        self.style.text_color = color;
+        self
+    }
+
+    /// Border drawn around the button, `width` 0 for none
+    pub fn border(mut self, width: f32, color: Color) -> Self {
+        self.style.border_width = width;
+        self.style.border_color = color;
+        self
+    }
+
+    /// Corner radius
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.style.border_radius = radius;
+        self
+    }
+
+    /// Space between the text and the left and right edges
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.style.padding = padding;
+        self.style.explicit.padding = true;
+        self
+    }
+
+    /// Space between the text and the top and bottom edges
+    pub fn vertical_padding(mut self, padding: f32) -> Self {
+        self.style.vertical_padding = padding;
+        self.style.explicit.vertical_padding = true;
+        self
+    }
+
+    /// Text size
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.style.font_size = font_size;
+        self
+    }
+
+    /// Smallest size the button is laid out at
+    pub fn min_size(mut self, width: f32, height: f32) -> Self {
+        self.style.min_width = width;
+        self.style.min_height = height;
+        self.style.explicit.min_width = true;
+        self
+    }
+
+    /// Finish the style
+    pub fn build(self) -> ButtonStyle {
+        self.style
+    }
 }
 
 fn blend_colors(from: Color, to: Color, t: f32) -> Color {
@@ -139,6 +445,34 @@ fn blend_colors(from: Color, to: Color, t: f32) -> Color {
     )
 }
 
+/// Feedback animation played while a button is pressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PressEffect {
+    /// Nudge the button down-right by a pixel
+    #[default]
+    None,
+    /// Shrink the button slightly around its center
+    Scale,
+    /// Expand a fading circle from the press point, clipped to the button
+    Ripple,
+}
+
+/// Seconds a button takes to fade between its normal and hover backgrounds
+pub const HOVER_TRANSITION: f32 = 0.12;
+/// How far the default focused background is from normal toward hover
+const FOCUSED_BLEND: f32 = 0.35;
+/// Opacity of the text color washed over a fill under the pointer
+const FILL_HOVER_WASH: f32 = 0.08;
+/// Opacity of the text color washed over a fill while held down
+const FILL_PRESSED_WASH: f32 = 0.16;
+
+/// Scale applied to a fully pressed button with [`PressEffect::Scale`]
+const PRESSED_SCALE: f32 = 0.95;
+/// Duration of a ripple in seconds
+const RIPPLE_DURATION: f32 = 0.45;
+/// Number of horizontal strips used to draw a clipped ripple
+const RIPPLE_STRIPS: usize = 24;
+
 /// Button widget
 pub struct Button {
     id: WidgetId,
@@ -148,9 +482,20 @@ pub struct Button {
     bounds: Signal<Rect>,
     enabled: Signal<bool>,
     visible: Signal<bool>,
-    on_click: Option<Box<dyn Fn() + Send + Sync>>,
-    on_hover: Option<Box<dyn Fn(bool) + Send + Sync>>,
+    tab_index: Option<i32>,
+    on_click: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_hover: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    gestures: PressGestures,
     theme: Option<Arc<Theme>>,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
+    press_effect: PressEffect,
+    /// How far into hovering the background is, from 0 to 1
+    hover_progress: Signal<f32>,
+    /// Current scale for [`PressEffect::Scale`]
+    press_scale: Signal<f32>,
+    /// Origin and age in seconds of the active ripple
+    ripple: Signal<Option<(Point, f32)>>,
 }
 
 impl std::fmt::Debug for Button {
@@ -171,6 +516,7 @@ impl std::fmt::Debug for Button {
                 "on_hover",
                 &self.on_hover.as_ref().map(|_| "Fn(bool) + Send + Sync"),
             )
+            .field("gestures", &self.gestures)
             .field("theme", &self.theme)
             .finish()
     }
@@ -190,9 +536,16 @@ impl Button {
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
             enabled: Signal::new(true),
             visible: Signal::new(true),
+            tab_index: None,
             on_click: None,
             on_hover: None,
+            gestures: PressGestures::new(),
             theme: None,
+            palette: ColorPalette::default(),
+            press_effect: PressEffect::None,
+            hover_progress: Signal::new(0.0),
+            press_scale: Signal::new(1.0),
+            ripple: Signal::new(None),
         }
     }
 
@@ -232,12 +585,48 @@ impl Button {
         self
     }
 
+    /// Set the press feedback animation
+    pub fn press_effect(mut self, effect: PressEffect) -> Self {
+        self.press_effect = effect;
+        self
+    }
+
     /// Set click handler
     pub fn on_click<F>(mut self, handler: F) -> Self
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_click = Some(Box::new(handler));
+        self.on_click = Some(Arc::new(handler));
+        self
+    }
+
+    /// Set a handler for holding the button down for `duration`
+    ///
+    /// A click that turned into a long press doesn't also click.
+    pub fn on_long_press<F>(mut self, duration: Duration, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.gestures.set_long_press(duration, handler);
+        self
+    }
+
+    /// Set a handler repeated while the button is held down
+    ///
+    /// It first runs after `initial_delay`, then every `interval` until the
+    /// pointer is released or leaves the button. Pair it with
+    /// [`on_click`](Self::on_click) so a tap acts once; a hold that repeated
+    /// doesn't also click.
+    pub fn on_press_repeat<F>(
+        mut self,
+        initial_delay: Duration,
+        interval: Duration,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.gestures.set_repeat(initial_delay, interval, handler);
         self
     }
 
@@ -246,7 +635,7 @@ impl Button {
     where
         F: Fn(bool) + Send + Sync + 'static,
     {
-        self.on_hover = Some(Box::new(handler));
+        self.on_hover = Some(Arc::new(handler));
         self
     }
 
@@ -263,6 +652,12 @@ impl Button {
         self
     }
 
+    /// Set the button's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
     /// Set theme
     pub fn theme(mut self, theme: Arc<Theme>) -> Self {
         self.theme = Some(theme);
@@ -273,6 +668,14 @@ impl Button {
     pub fn size(mut self, width: f32, height: f32) -> Self {
         self.style.min_width = width;
         self.style.min_height = height;
+        self.style.explicit.min_width = true;
+        self
+    }
+
+    /// Set the narrowest the button is laid out, however short its label
+    pub fn min_width(mut self, width: f32) -> Self {
+        self.style.min_width = width;
+        self.style.explicit.min_width = true;
         self
     }
 
@@ -373,16 +776,24 @@ impl Button {
 
     /// Calculate button size
     pub fn calculate_size(&self, available_size: Size) -> Size {
-        // Use accurate text measurement
-        let text_width = crate::text::measure_text_width(&self.text, self.style.font_size, 0.0);
-        let text_height = self.style.font_size;
-
-        let width = (text_width + self.style.padding * 2.0).max(self.style.min_width);
-        let height = (text_height + self.style.padding * 2.0).max(self.style.min_height);
+        let size = self.preferred_size();
+        Size::new(
+            size.width.min(available_size.width),
+            size.height.min(available_size.height),
+        )
+    }
 
+    /// The label's width and line height plus padding, at least the minimum
+    /// size
+    ///
+    /// Buttons with the same font size and padding are the same height
+    /// whatever their label.
+    fn preferred_size(&self) -> Size {
+        let text_width = crate::text::measure_text_width(&self.text, self.style.font_size, 0.0);
+        let text_height = measure_line_metrics(self.style.font_size).line_height;
         Size::new(
-            width.min(available_size.width),
-            height.min(available_size.height),
+            (text_width + self.style.padding * 2.0).max(self.style.min_width),
+            (text_height + self.style.vertical_padding * 2.0).max(self.style.min_height),
         )
     }
 
@@ -398,6 +809,108 @@ impl Button {
         self.style.text_color = theme.colors.on_primary;
         self.style.border_radius = theme.spacing.md;
         self.style.font_size = theme.typography.base_size;
+        self.style.apply_spacing(&theme.spacing);
+    }
+
+    /// Start the press effect at `origin`
+    fn start_press_effect(&self, origin: Point) {
+        if self.press_effect == PressEffect::Ripple {
+            self.ripple.set(Some((origin, 0.0)));
+        }
+    }
+
+    /// Move the hover fade `delta_time` seconds toward the current state
+    fn advance_hover(&self, delta_time: f32) {
+        let target = match self.get_state() {
+            ButtonState::Hovered | ButtonState::Pressed => 1.0,
+            _ => 0.0,
+        };
+        let current = self.hover_progress.get();
+        if current != target {
+            let step = delta_time / HOVER_TRANSITION;
+            self.hover_progress.set(if target > current {
+                (current + step).min(target)
+            } else {
+                (current - step).max(target)
+            });
+        }
+    }
+
+    /// Advance press effect animations by `delta_time` seconds
+    fn advance_press_effect(&self, delta_time: f32) {
+        match self.press_effect {
+            PressEffect::Scale => {
+                let target = if self.get_state() == ButtonState::Pressed {
+                    PRESSED_SCALE
+                } else {
+                    1.0
+                };
+                let current = self.press_scale.get();
+                let step = (target - current) * (delta_time * 20.0).clamp(0.0, 1.0);
+                self.press_scale.set(current + step);
+            }
+            PressEffect::Ripple => {
+                if let Some((origin, age)) = self.ripple.get() {
+                    let age = age + delta_time;
+                    self.ripple
+                        .set((age < RIPPLE_DURATION).then_some((origin, age)));
+                }
+            }
+            PressEffect::None => {}
+        }
+    }
+
+    /// Draw the active ripple as horizontal strips clipped to `bounds`
+    fn render_ripple(&self, batch: &mut RenderBatch, bounds: Rect) {
+        let Some((origin, age)) = self.ripple.get() else {
+            return;
+        };
+        let progress = Curve::EaseOut.transform(age / RIPPLE_DURATION);
+        let max_radius = [
+            Point::new(bounds.x, bounds.y),
+            Point::new(bounds.x + bounds.width, bounds.y),
+            Point::new(bounds.x, bounds.y + bounds.height),
+            Point::new(bounds.x + bounds.width, bounds.y + bounds.height),
+        ]
+        .iter()
+        .map(|corner| origin.distance_to(*corner))
+        .fold(0.0, f32::max);
+        let radius = max_radius * progress;
+
+        let mut color = self.style.text_color.to_types_color();
+        color.a *= 0.3 * (1.0 - progress);
+
+        // Strips stay inside the rounded corners, measured at the strip's
+        // edge nearest the top or bottom so none pokes past the curve
+        let corner = self
+            .style
+            .border_radius
+            .clamp(0.0, bounds.width.min(bounds.height) / 2.0);
+        let strip_height = bounds.height / RIPPLE_STRIPS as f32;
+        for strip in 0..RIPPLE_STRIPS {
+            let y = bounds.y + strip as f32 * strip_height;
+            let dy = (y + strip_height / 2.0 - origin.y).abs();
+            if dy >= radius {
+                continue;
+            }
+            let half_width = (radius * radius - dy * dy).sqrt();
+            let from_edge = (y - bounds.y).min(bounds.y + bounds.height - y - strip_height);
+            let inset = if from_edge < corner {
+                let rise = corner - from_edge.max(0.0);
+                corner - (corner * corner - rise * rise).sqrt()
+            } else {
+                0.0
+            };
+            let left = (origin.x - half_width).max(bounds.x + inset);
+            let right = (origin.x + half_width).min(bounds.x + bounds.width - inset);
+            if right > left {
+                batch.add_rect(
+                    Rect::new(left, y, right - left, strip_height),
+                    color,
+                    Transform::identity(),
+                );
+            }
+        }
     }
 }
 
@@ -459,6 +972,31 @@ impl ButtonBuilder {
         self
     }
 
+    /// Set long press handler
+    pub fn on_long_press<F>(mut self, duration: Duration, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.button = self.button.on_long_press(duration, handler);
+        self
+    }
+
+    /// Set press-and-hold repeat handler
+    pub fn on_press_repeat<F>(
+        mut self,
+        initial_delay: Duration,
+        interval: Duration,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.button = self
+            .button
+            .on_press_repeat(initial_delay, interval, handler);
+        self
+    }
+
     /// Set hover handler
     pub fn on_hover<F>(mut self, handler: F) -> Self
     where
@@ -540,6 +1078,386 @@ mod tests {
         assert!(button.is_enabled());
     }
 
+    #[test]
+    fn test_custom_style_spec() {
+        let spec = ButtonStyleSpec {
+            bg: Color::rgba(0.1, 0.1, 0.1, 1.0),
+            bg_hover: Color::rgba(0.2, 0.2, 0.2, 1.0),
+            bg_pressed: Color::rgba(0.3, 0.3, 0.3, 1.0),
+            fg: Color::rgba(1.0, 1.0, 0.0, 1.0),
+            border: Some(ButtonBorder {
+                width: 2.0,
+                color: Color::rgba(1.0, 0.0, 0.0, 1.0),
+            }),
+            radius: 10.0,
+            padding: 6.0,
+        };
+        let style = ButtonStyle::custom(spec.clone());
+
+        assert_eq!(style.background_for(ButtonState::Normal), spec.bg);
+        assert_eq!(style.background_for(ButtonState::Hovered), spec.bg_hover);
+        assert_eq!(style.background_for(ButtonState::Pressed), spec.bg_pressed);
+        assert_eq!(style.text_color, spec.fg);
+        assert_eq!(style.border_width, 2.0);
+        assert_eq!(style.border_radius, 10.0);
+        assert_eq!(style.padding, 6.0);
+    }
+
+    #[test]
+    fn test_presets_built_on_spec() {
+        let outline = ButtonStyle::outline();
+        assert_eq!(outline.border_width, 1.0);
+        assert_eq!(outline.min_height, ButtonStyle::default().min_height);
+        assert_ne!(
+            outline.background_for(ButtonState::Hovered),
+            outline.background_for(ButtonState::Pressed)
+        );
+    }
+
+    #[test]
+    fn test_style_builder_sets_each_state() {
+        let color = |v: f32| Color::rgba(v, v, v, 1.0);
+        let style = ButtonStyle::builder()
+            .background(color(0.1))
+            .hover(color(0.2))
+            .pressed(color(0.3))
+            .disabled(color(0.4))
+            .focused(color(0.5))
+            .border(2.0, color(0.6))
+            .min_size(40.0, 20.0)
+            .build();
+        assert_eq!(style.background_for(ButtonState::Normal), color(0.1));
+        assert_eq!(style.background_for(ButtonState::Hovered), color(0.2));
+        assert_eq!(style.background_for(ButtonState::Pressed), color(0.3));
+        assert_eq!(style.background_for(ButtonState::Disabled), color(0.4));
+        assert_eq!(style.background_for(ButtonState::Focused), color(0.5));
+        assert_eq!((style.border_width, style.min_width), (2.0, 40.0));
+
+        let restyled = ButtonStyle::outline()
+            .to_builder()
+            .pressed(color(0.9))
+            .build();
+        assert_eq!(restyled.pressed_color, color(0.9));
+        assert_eq!(restyled.border_width, 1.0);
+    }
+
+    #[test]
+    fn test_hover_background_fades_in_and_out() {
+        use crate::test_util::WidgetTester;
+
+        let style = ButtonStyle::builder()
+            .background(Color::rgba(0.0, 0.0, 0.0, 1.0))
+            .hover(Color::rgba(1.0, 1.0, 1.0, 1.0))
+            .build();
+        let mut tester = WidgetTester::new(Button::new("Fade").style(style), 100.0, 40.0);
+        let background = |tester: &WidgetTester<Button>| {
+            let button = tester.root();
+            button
+                .style
+                .background_at(button.get_state(), button.hover_progress.get())
+                .r
+        };
+
+        tester.move_to(50.0, 20.0);
+        assert_eq!(tester.root().get_state(), ButtonState::Hovered);
+        tester.advance_by(HOVER_TRANSITION / 2.0, 1);
+        assert!((background(&tester) - 0.5).abs() < 0.01);
+        tester.advance_by(HOVER_TRANSITION, 1);
+        assert_eq!(background(&tester), 1.0);
+
+        tester.move_to(150.0, 20.0);
+        tester.advance_by(HOVER_TRANSITION / 2.0, 1);
+        assert!((background(&tester) - 0.5).abs() < 0.01);
+        tester.advance_by(HOVER_TRANSITION, 1);
+        assert_eq!(background(&tester), 0.0);
+    }
+
+    #[test]
+    fn test_themed_button_derives_disabled_and_focused_backgrounds() {
+        use crate::test_util::WidgetTester;
+
+        let theme = Theme::dark();
+        let mut button = Button::new("Save").enabled(false);
+        button.apply_theme(&theme);
+        let primary = theme.colors.primary;
+        assert_eq!(
+            button.style.focused_background(),
+            blend_colors(primary, button.style.hover_color, FOCUSED_BLEND)
+        );
+
+        let mut tester = WidgetTester::new(button, 200.0, 100.0);
+        tester.advance(0.0);
+        let bounds = tester.root().bounds.get();
+        let corner = tester
+            .frame()
+            .vertices
+            .iter()
+            .find(|vertex| vertex.position == [bounds.x, bounds.y])
+            .map(|vertex| vertex.color);
+        let faded = ColorPalette::default().disabled(primary.to_types_color());
+        assert_eq!(corner, Some(faded.to_array()));
+    }
+
+    #[test]
+    fn test_gradient_fill_runs_corner_to_corner() {
+        use crate::test_util::WidgetTester;
+        use strato_core::types::{GradientStop, LinearGradient};
+
+        let red = strato_core::types::Color::rgba(1.0, 0.0, 0.0, 1.0);
+        let blue = strato_core::types::Color::rgba(0.0, 0.0, 1.0, 1.0);
+        let gradient = LinearGradient::new(
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 1.0),
+            vec![
+                GradientStop {
+                    color: red,
+                    position: 0.0,
+                },
+                GradientStop {
+                    color: blue,
+                    position: 1.0,
+                },
+            ],
+        );
+        let style = ButtonStyle::builder().fill(gradient).build();
+        let tester = WidgetTester::new(Button::new("Brand").style(style), 200.0, 100.0);
+
+        let bounds = tester.root().bounds.get();
+        let color_at = |x: f32, y: f32| {
+            tester
+                .frame()
+                .vertices
+                .iter()
+                .find(|vertex| vertex.position == [x, y])
+                .map(|vertex| vertex.color)
+        };
+        assert_eq!(color_at(bounds.x, bounds.y), Some(red.to_array()));
+        assert_eq!(
+            color_at(bounds.x + bounds.width, bounds.y + bounds.height),
+            Some(blue.to_array())
+        );
+    }
+
+    fn press_at(button: &mut Button, x: f32, y: f32) {
+        button.handle_event(&Event::MouseDown(strato_core::event::MouseEvent {
+            position: glam::Vec2::new(x, y),
+            button: Some(strato_core::event::MouseButton::Left),
+            modifiers: Default::default(),
+            delta: glam::Vec2::ZERO,
+        }));
+    }
+
+    fn tick(button: &mut Button, delta_time: f32) {
+        let theme = crate::theme::Theme::default();
+        button.update(&WidgetContext {
+            theme: &theme,
+            state: WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time,
+            input: Default::default(),
+        });
+    }
+
+    fn click_and_activate(widget: &mut dyn Widget) {
+        let mouse = strato_core::event::MouseEvent {
+            position: glam::Vec2::new(50.0, 20.0),
+            button: Some(strato_core::event::MouseButton::Left),
+            modifiers: Default::default(),
+            delta: glam::Vec2::ZERO,
+        };
+        let key = strato_core::event::KeyboardEvent {
+            key_code: strato_core::event::KeyCode::Enter,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        };
+        for event in [
+            Event::MouseDown(mouse.clone()),
+            Event::MouseUp(mouse),
+            Event::KeyDown(key.clone()),
+            Event::KeyUp(key),
+        ] {
+            widget.handle_event(&event);
+        }
+    }
+
+    fn counting_button(enabled: bool) -> (Button, Arc<std::sync::atomic::AtomicUsize>) {
+        let clicks = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let button = Button::new("Count").enabled(enabled).on_click(move || {
+            counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        });
+        button.layout(Rect::new(0.0, 0.0, 100.0, 40.0));
+        (button, clicks)
+    }
+
+    #[test]
+    fn test_disabled_button_never_clicks() {
+        let (mut enabled, clicks) = counting_button(true);
+        click_and_activate(&mut enabled);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let (mut disabled, clicks) = counting_button(false);
+        assert!(!Widget::is_enabled(&disabled));
+        click_and_activate(&mut disabled);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_cloned_button_keeps_click_handler() {
+        let (button, clicks) = counting_button(true);
+        let mut clone = button.clone_widget();
+        assert_ne!(clone.id(), button.id());
+
+        click_and_activate(clone.as_mut());
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        drop(button);
+        click_and_activate(clone.as_mut());
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 4);
+    }
+
+    #[test]
+    fn test_cloned_button_keeps_keyed_id() {
+        let mut button = Button::new("Save");
+        button.set_id(crate::widget::keyed_id("save"));
+        assert_eq!(button.clone_widget().id(), button.id());
+    }
+
+    #[test]
+    fn test_dispatcher_skips_disabled_button() {
+        let (button, clicks) = counting_button(false);
+        let mut column = crate::layout::Column::new().child(Box::new(button));
+        column.layout(Constraints::loose(200.0, 100.0));
+        click_and_activate(&mut column);
+        assert_eq!(clicks.load(std::sync::atomic::Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_cursor_follows_hovered_widget() {
+        let (button, _) = counting_button(true);
+        let input = crate::input::TextInput::new();
+        input.layout(Rect::new(0.0, 50.0, 100.0, 30.0));
+        let column = crate::layout::Column::new()
+            .child(Box::new(button))
+            .child(Box::new(input));
+
+        let at = |x, y| crate::widget::cursor_icon_at(&column, Point::new(x, y));
+        assert_eq!(at(10.0, 10.0), Some(CursorIcon::Pointer));
+        assert_eq!(at(10.0, 60.0), Some(CursorIcon::Text));
+        assert_eq!(at(10.0, 45.0), None);
+
+        let (disabled, _) = counting_button(false);
+        assert_eq!(
+            crate::widget::cursor_icon_at(&disabled, Point::new(10.0, 10.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ripple_press_effect() {
+        let mut button = Button::new("Ripple").press_effect(PressEffect::Ripple);
+        button.layout(Rect::new(0.0, 0.0, 100.0, 40.0));
+        press_at(&mut button, 20.0, 10.0);
+        assert_eq!(
+            button.ripple.get().map(|(origin, _)| origin),
+            Some(Point::new(20.0, 10.0))
+        );
+
+        tick(&mut button, RIPPLE_DURATION / 2.0);
+        let mut batch = RenderBatch::new();
+        button.render_ripple(&mut batch, Rect::new(0.0, 0.0, 100.0, 40.0));
+        assert!(batch.command_count() > 0);
+
+        tick(&mut button, RIPPLE_DURATION);
+        assert!(button.ripple.get().is_none());
+    }
+
+    #[test]
+    fn test_ripple_stays_inside_rounded_corners() {
+        let style = ButtonStyle::builder().radius(12.0).build();
+        let mut button = Button::new("Ripple")
+            .style(style)
+            .press_effect(PressEffect::Ripple);
+        let bounds = Rect::new(0.0, 0.0, 100.0, 40.0);
+        button.layout(bounds);
+        press_at(&mut button, 50.0, 20.0);
+        tick(&mut button, RIPPLE_DURATION * 0.9);
+
+        let mut batch = RenderBatch::new();
+        button.render_ripple(&mut batch, bounds);
+        let inside = |x: f32, y: f32| {
+            let cx = x.clamp(12.0, bounds.width - 12.0);
+            let cy = y.clamp(12.0, bounds.height - 12.0);
+            (x - cx).powi(2) + (y - cy).powi(2) <= 12.0 * 12.0 + 0.01
+        };
+        let mut strips = 0;
+        for command in &batch.commands {
+            if let strato_renderer::batch::DrawCommand::Rect { rect, .. } = command {
+                strips += 1;
+                for (x, y) in [
+                    (rect.x, rect.y),
+                    (rect.x + rect.width, rect.y),
+                    (rect.x, rect.y + rect.height),
+                    (rect.x + rect.width, rect.y + rect.height),
+                ] {
+                    assert!(inside(x, y), "strip corner ({x}, {y}) outside the button");
+                }
+            }
+        }
+        assert_eq!(strips, RIPPLE_STRIPS);
+    }
+
+    #[test]
+    fn test_scale_press_effect() {
+        let mut button = Button::new("Scale").press_effect(PressEffect::Scale);
+        button.layout(Rect::new(0.0, 0.0, 100.0, 40.0));
+        press_at(&mut button, 50.0, 20.0);
+
+        for _ in 0..20 {
+            tick(&mut button, 0.016);
+        }
+        assert!((button.press_scale.get() - PRESSED_SCALE).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_held_stepper_repeats_without_extra_click() {
+        use crate::test_util::WidgetTester;
+        use std::sync::atomic::{AtomicI32, Ordering};
+        use strato_core::event::MouseButton;
+
+        let value = Arc::new(AtomicI32::new(0));
+        let (clicked, repeated) = (value.clone(), value.clone());
+        let stepper = Button::new("+")
+            .on_click(move || {
+                clicked.fetch_add(1, Ordering::SeqCst);
+            })
+            .on_press_repeat(
+                Duration::from_millis(400),
+                Duration::from_millis(100),
+                move || {
+                    repeated.fetch_add(1, Ordering::SeqCst);
+                },
+            );
+        let mut tester = WidgetTester::new(stepper, 100.0, 40.0);
+
+        tester.click(50.0, 20.0);
+        assert_eq!(value.load(Ordering::SeqCst), 1);
+
+        tester.press(50.0, 20.0, MouseButton::Left);
+        tester.advance_by(0.05, 14);
+        tester.release(50.0, 20.0, MouseButton::Left);
+        assert_eq!(value.load(Ordering::SeqCst), 1 + 4);
+
+        // Leaving the button stops the repeat
+        tester.press(50.0, 20.0, MouseButton::Left);
+        tester.advance_by(0.05, 10);
+        tester.move_to(150.0, 20.0);
+        tester.advance_by(0.05, 10);
+        assert_eq!(value.load(Ordering::SeqCst), 5 + 2);
+    }
+
     #[test]
     fn test_button_size_calculation() {
         let button = Button::new("Test");
@@ -551,6 +1469,40 @@ mod tests {
         assert!(size.width <= available.width);
         assert!(size.height <= available.height);
     }
+
+    #[test]
+    fn test_one_character_button_respects_min_width() {
+        let plus = Button::new("+").min_width(72.0);
+        let size = plus.calculate_size(Size::new(400.0, 100.0));
+        assert_eq!(size.width, 72.0);
+
+        // The height comes from the line height, not the label
+        let cancel = Button::new("Cancel").min_width(72.0);
+        let cancel_size = cancel.calculate_size(Size::new(400.0, 100.0));
+        assert!(cancel_size.width > 72.0);
+        assert_eq!(cancel_size.height, size.height);
+        let style = ButtonStyle::default();
+        assert_eq!(
+            size.height,
+            measure_line_metrics(style.font_size).line_height + style.vertical_padding * 2.0
+        );
+    }
+
+    #[test]
+    fn test_theme_spacing_keeps_explicit_values() {
+        let theme = Theme::light();
+        let style = ButtonStyle::builder().padding(3.0).build();
+        let mut button = Button::new("+").style(style).min_width(72.0);
+        button.apply_theme(&theme);
+        assert_eq!(button.style.padding, 3.0);
+        assert_eq!(button.style.min_width, 72.0);
+        assert_eq!(button.style.vertical_padding, theme.spacing.sm);
+
+        let mut themed = Button::new("+");
+        themed.apply_theme(&theme);
+        assert_eq!(themed.style.padding, theme.spacing.md);
+        assert_eq!(themed.style.min_width, theme.spacing.md * 4.0);
+    }
 }
 
 // Implement Widget trait for Button
@@ -559,23 +1511,12 @@ impl Widget for Button {
         self.id
     }
 
-    fn layout(&mut self, constraints: Constraints) -> Size {
-        let text_width = crate::text::measure_text_width(&self.text, self.style.font_size, 0.0);
-        let text_height = self.style.font_size;
-
-        let content_width = text_width + self.style.padding * 2.0;
-        let content_height = text_height + self.style.padding * 2.0;
-
-        let width = content_width.max(self.style.min_width);
-        let height = content_height.max(self.style.min_height);
-
-        // Respect constraints
-        let width = width.min(constraints.max_width).max(constraints.min_width);
-        let height = height
-            .min(constraints.max_height)
-            .max(constraints.min_height);
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
 
-        Size::new(width, height)
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        constraints.constrain(self.preferred_size())
     }
 
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
@@ -586,44 +1527,77 @@ impl Widget for Button {
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         if !self.is_visible() {
             return;
         }
 
         let state = self.get_state();
-        let target_color = match state {
-            ButtonState::Normal => self.style.background_color,
-            ButtonState::Hovered => self.style.hover_color,
-            ButtonState::Pressed => self.style.pressed_color,
-            ButtonState::Disabled => self.style.disabled_color,
-            ButtonState::Focused => {
-                blend_colors(self.style.background_color, self.style.hover_color, 0.35)
-            }
-        };
-        let background_color = if matches!(state, ButtonState::Disabled) {
-            self.style.disabled_color
+        let disabled = !self.is_enabled();
+        let background_color = if disabled {
+            self.style.disabled_background(&self.palette)
         } else {
-            blend_colors(
-                self.style.background_color,
-                target_color,
-                self.control.interaction_factor(),
-            )
+            self.style.background_at(state, self.hover_progress.get())
+        };
+        // Disabled buttons keep their text and border colors, faded by the
+        // theme
+        let paint = |color: Color| {
+            if disabled {
+                self.palette.disabled(color.to_types_color())
+            } else {
+                color.to_types_color()
+            }
         };
 
-        // Apply a subtle offset when pressed to give physical feedback
+        // Give physical feedback while pressed
         let mut draw_bounds = bounds;
-        if state == ButtonState::Pressed {
-            draw_bounds.x += 1.0;
-            draw_bounds.y += 1.0;
+        let mut font_size = self.style.font_size;
+        match self.press_effect {
+            PressEffect::None if state == ButtonState::Pressed => {
+                draw_bounds.x += 1.0;
+                draw_bounds.y += 1.0;
+            }
+            PressEffect::Scale => {
+                let scale = self.press_scale.get();
+                draw_bounds = Rect::new(
+                    bounds.x + bounds.width * (1.0 - scale) / 2.0,
+                    bounds.y + bounds.height * (1.0 - scale) / 2.0,
+                    bounds.width * scale,
+                    bounds.height * scale,
+                );
+                font_size *= scale;
+            }
+            _ => {}
         }
 
         // Draw background
-        batch.add_rect(
-            draw_bounds,
-            background_color.to_types_color(),
-            Transform::identity(),
-        );
+        match &self.style.fill {
+            Some(fill) if !disabled => {
+                batch.add_background(
+                    draw_bounds,
+                    fill,
+                    BorderRadius::default(),
+                    Transform::identity(),
+                );
+                let hover = self.hover_progress.get();
+                let wash = match state {
+                    ButtonState::Pressed => FILL_PRESSED_WASH,
+                    ButtonState::Focused => FILL_HOVER_WASH * hover.max(FOCUSED_BLEND),
+                    _ => FILL_HOVER_WASH * hover,
+                };
+                if wash > 0.0 {
+                    let mut color = self.style.text_color.to_types_color();
+                    color.a *= wash;
+                    batch.add_rect(draw_bounds, color, Transform::identity());
+                }
+            }
+            _ => batch.add_rect(
+                draw_bounds,
+                background_color.to_types_color(),
+                Transform::identity(),
+            ),
+        }
 
         // Render border if needed
         if self.style.border_width > 0.0 {
@@ -642,42 +1616,86 @@ impl Widget for Button {
                     border_bounds.width,
                     border_bounds.height,
                     self.style.border_radius + self.style.border_width / 2.0,
-                    self.style.border_color.to_array(),
+                    paint(self.style.border_color).to_array(),
                     8, // corner segments
                 );
                 batch.add_vertices(&vertices, &indices);
             }
         }
 
-        // Render text
-        let text_x = draw_bounds.x + draw_bounds.width / 2.0;
-        let text_y = draw_bounds.y + draw_bounds.height / 2.0 - self.style.font_size / 2.0;
-        let mut text_color = self.style.text_color;
-        if matches!(state, ButtonState::Disabled) {
-            text_color.a *= 0.35;
+        if self.press_effect == PressEffect::Ripple {
+            self.render_ripple(batch, draw_bounds);
         }
 
+        // Render text
+        let text_x = draw_bounds.x + draw_bounds.width / 2.0;
+        let text_y = measure_line_metrics(font_size).centered_top(draw_bounds.y, draw_bounds.height);
         batch.add_text_aligned(
             self.text.clone(),
             (text_x, text_y),
-            text_color.to_types_color(),
-            self.style.font_size,
+            paint(self.style.text_color),
+            font_size,
             0.0, // Default letter spacing
             strato_core::text::TextAlign::Center,
         );
     }
 
     fn update(&mut self, ctx: &WidgetContext) {
-        self.control.update(ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
+        self.advance_hover(ctx.delta_time);
+        self.advance_press_effect(ctx.delta_time);
+        if self.is_enabled() {
+            self.gestures.advance(ctx.delta_time);
+        } else {
+            self.gestures.cancel();
+        }
+    }
+
+    fn is_enabled(&self) -> bool {
+        Button::is_enabled(self)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.control.state() == ButtonState::Focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.is_enabled()
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.control.focus();
+        } else {
+            self.control.blur();
+        }
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.bounds.get().contains(point).then_some(CursorIcon::Pointer)
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.is_enabled() {
+            return EventResult::Ignored;
+        }
+
         let previous_state = self.get_state();
         let bounds = self.bounds.get();
+        let held_gesture = self.gestures.handle_pointer_event(event, bounds);
 
         // Pointer interactions and hover callbacks
         if let EventResult::Handled = self.control.handle_pointer_event(event, bounds) {
-            if matches!(event, Event::MouseUp(_)) && matches!(previous_state, ButtonState::Pressed)
+            if let Event::MouseDown(mouse_event) = event {
+                self.start_press_effect(Point::new(mouse_event.position.x, mouse_event.position.y));
+            }
+            if matches!(event, Event::MouseUp(_))
+                && matches!(previous_state, ButtonState::Pressed)
+                && !held_gesture
             {
                 if let Some(handler) = &self.on_click {
                     handler();
@@ -695,6 +1713,9 @@ impl Widget for Button {
 
         // Keyboard accessibility
         if let EventResult::Handled = self.control.handle_keyboard_activation(event) {
+            if matches!(event, Event::KeyDown(_)) {
+                self.start_press_effect(bounds.center());
+            }
             if matches!(event, Event::KeyUp(_)) {
                 if let Some(handler) = &self.on_click {
                     handler();
@@ -716,16 +1737,23 @@ impl Widget for Button {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Button {
-            id: generate_id(),
+            id: clone_id(self.id),
             text: self.text.clone(),
             style: self.style.clone(),
             control: self.control.clone(),
             bounds: Signal::new(self.bounds.get()),
             enabled: Signal::new(self.enabled.get()),
             visible: Signal::new(self.visible.get()),
-            on_click: None,
-            on_hover: None,
+            tab_index: self.tab_index,
+            on_click: self.on_click.clone(),
+            on_hover: self.on_hover.clone(),
+            gestures: self.gestures.clone(),
             theme: self.theme.clone(),
+            palette: self.palette.clone(),
+            press_effect: self.press_effect,
+            hover_progress: Signal::new(self.hover_progress.get()),
+            press_scale: Signal::new(1.0),
+            ripple: Signal::new(None),
         })
     }
 
@@ -736,16 +1764,12 @@ impl Widget for Button {
 
 impl TaffyWidget for Button {
     fn build_layout(&self, tree: &mut TaffyTree<()>) -> TaffyLayoutResult<NodeId> {
-        let text_width = crate::text::measure_text_width(&self.text, self.style.font_size, 0.0);
-        let text_height = self.style.font_size;
-        
-        let width = (text_width + self.style.padding * 2.0).max(self.style.min_width);
-        let height = (text_height + self.style.padding * 2.0).max(self.style.min_height);
+        let size = self.preferred_size();
 
         let style = Style {
             size: strato_core::taffy::geometry::Size {
-                width: length(width),
-                height: length(height),
+                width: length(size.width),
+                height: length(size.height),
             },
             min_size: strato_core::taffy::geometry::Size {
                 width: length(self.style.min_width),
@@ -754,8 +1778,8 @@ impl TaffyWidget for Button {
             padding: strato_core::taffy::geometry::Rect {
                 left: length(self.style.padding),
                 right: length(self.style.padding),
-                top: length(self.style.padding),
-                bottom: length(self.style.padding),
+                top: length(self.style.vertical_padding),
+                bottom: length(self.style.vertical_padding),
             },
             ..Default::default()
         };
@@ -0,0 +1,286 @@
+//! Caching the drawing of static sub-trees
+//!
+//! A [`CacheLayer`] renders its child once into a texture and draws that
+//! texture on later frames, so a complex chart or a static sidebar costs a
+//! single quad per frame instead of a full walk of its widgets. The texture is
+//! rendered again when the child's layout changes, when the layer is drawn at
+//! a different position or size, or when [`CacheLayer::invalidate`] is called.
+
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, layout_tree, register_bounds, Widget,
+    WidgetId,
+};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    state::Signal,
+    types::Rect,
+};
+use strato_renderer::batch::{next_layer_id, LayerContent, RenderBatch};
+
+/// What the child drew the last time it was rendered
+struct Recording {
+    rect: Rect,
+    content: LayerContent,
+    /// Bounds registered below the layer, in tree order
+    bounds: Vec<(WidgetId, Rect)>,
+}
+
+/// Draws its child from a texture until the child changes
+///
+/// The child is rendered into a batch of its own the first time the layer is
+/// drawn, and that batch is added to the frame as a
+/// [layer](RenderBatch::add_layer): the GPU backend renders it into a texture
+/// sized to the layer and composites the texture as one quad as long as the
+/// child's layout and the layer's rectangle stay the same. Anything the child
+/// draws outside its rectangle is cut off, except popups, which are drawn
+/// above the frame as usual. Hit testing keeps working on the cached
+/// frames: the bounds the child's widgets registered while recording are
+/// registered again each frame.
+///
+/// Changes that only affect how the child looks, such as a hover highlight
+/// or a new value in a widget without a layout cache, are not seen by the
+/// layer. Call [`invalidate`](Self::invalidate) after them, or
+/// [`watch`](Self::watch) the signals they come from.
+pub struct CacheLayer {
+    id: WidgetId,
+    /// Names the layer's texture in the backend
+    layer_id: u64,
+    child: Box<dyn Widget>,
+    layout_cache: LayoutCache,
+    recording: Arc<Mutex<Option<Recording>>>,
+}
+
+impl std::fmt::Debug for CacheLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheLayer")
+            .field("id", &self.id)
+            .field("child", &self.child)
+            .field("recorded", &self.is_recorded())
+            .finish()
+    }
+}
+
+impl CacheLayer {
+    /// Cache the drawing of `child`
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self::new_boxed(Box::new(child))
+    }
+
+    fn new_boxed(child: Box<dyn Widget>) -> Self {
+        Self {
+            id: generate_id(),
+            layer_id: next_layer_id(),
+            child,
+            layout_cache: LayoutCache::new(),
+            recording: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Render the child again on the next frame
+    pub fn invalidate(&self) {
+        self.recording.lock().take();
+    }
+
+    /// Render the child again whenever `signal` changes
+    pub fn watch<T: Clone + Send + Sync + 'static>(self, signal: &Signal<T>) -> Self {
+        self.layout_cache.watch(signal);
+        self
+    }
+
+    /// Whether the next frame will be drawn from the texture
+    pub fn is_recorded(&self) -> bool {
+        self.recording.lock().is_some()
+    }
+
+    fn record(&self, rect: Rect, viewport: Option<Rect>, layout: Layout) -> Recording {
+        let mut batch = RenderBatch::new();
+        if let Some(viewport) = viewport {
+            batch.set_viewport(viewport);
+        }
+        self.child.render(&mut batch, layout);
+
+        let bounds = layout_tree(self.child.as_ref())
+            .nodes()
+            .into_iter()
+            .filter_map(|node| node.bounds.map(|bounds| (node.id.0, bounds)))
+            .collect();
+        Recording {
+            rect,
+            content: LayerContent::new(batch),
+            bounds,
+        }
+    }
+}
+
+impl Widget for CacheLayer {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = &self.layout_cache;
+        let recording = &self.recording;
+        let child = self.child.as_mut();
+        // A child without a cache can't tell the layer it changed, so it
+        // only lays out again when the constraints do
+        cache.get_or_layout(constraints, || {
+            recording.lock().take();
+            match child.layout_cache() {
+                Some(_) => layout_child(cache, child, constraints),
+                None => child.layout(constraints),
+            }
+        })
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let rect = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+
+        let mut recording = self.recording.lock();
+        if recording
+            .as_ref()
+            .is_none_or(|recording| recording.rect != rect)
+        {
+            *recording = Some(self.record(rect, batch.viewport(), layout));
+        }
+        let recording = recording.as_ref().expect("recorded above");
+
+        register_bounds(self.id, rect);
+        for &(id, bounds) in &recording.bounds {
+            register_bounds(id, bounds);
+        }
+        batch.add_layer(self.layer_id, rect, &recording.content);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        dispatch_event(self.child.as_mut(), event)
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let mut clone = CacheLayer::new_boxed(self.child.clone_widget());
+        clone.id = clone_id(self.id);
+        Box::new(clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::canvas::Canvas;
+    use crate::test_util::WidgetTester;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use strato_core::types::Color;
+    use strato_renderer::batch::DrawCommand;
+
+    /// A layer over a canvas counting how often it is drawn
+    fn counted() -> (WidgetTester<CacheLayer>, Arc<AtomicUsize>) {
+        let draws = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&draws);
+        let canvas = Canvas::new(move |batch, rect| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            batch.add_rect(rect, Color::RED, Default::default());
+        });
+        (
+            WidgetTester::new(CacheLayer::new(canvas), 100.0, 100.0),
+            draws,
+        )
+    }
+
+    /// The content of the frame's only command, a layer
+    fn layer_content(tester: &WidgetTester<CacheLayer>) -> LayerContent {
+        match tester.frame().commands.as_slice() {
+            [DrawCommand::Layer { content, .. }] => content.clone(),
+            commands => panic!("expected a single layer, got {commands:?}"),
+        }
+    }
+
+    #[test]
+    fn test_replays_the_recording_until_invalidated() {
+        let (mut tester, draws) = counted();
+        let content = layer_content(&tester);
+        assert!(matches!(
+            content.batch().commands.as_slice(),
+            [
+                DrawCommand::PushClip(_),
+                DrawCommand::Rect { .. },
+                DrawCommand::PopClip
+            ]
+        ));
+        tester.pump();
+        tester.pump();
+        assert_eq!(draws.load(Ordering::SeqCst), 1);
+        assert!(layer_content(&tester).same_as(&content));
+
+        tester.root().invalidate();
+        tester.pump();
+        assert_eq!(draws.load(Ordering::SeqCst), 2);
+        assert!(!layer_content(&tester).same_as(&content));
+
+        tester.resize(200.0, 100.0);
+        assert_eq!(draws.load(Ordering::SeqCst), 3);
+        tester.pump();
+        assert_eq!(draws.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_watched_signal_renders_again() {
+        let color = Signal::new(Color::RED);
+        let draws = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&draws);
+        let shown = color.clone();
+        let canvas = Canvas::new(move |batch, rect| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            batch.add_rect(rect, shown.get(), Default::default());
+        });
+        let mut tester = WidgetTester::new(CacheLayer::new(canvas).watch(&color), 100.0, 100.0);
+        tester.pump();
+        assert_eq!(draws.load(Ordering::SeqCst), 1);
+
+        color.set(Color::BLUE);
+        tester.pump();
+        assert_eq!(draws.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_replayed_children_still_receive_clicks() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&clicks);
+        let button = Button::new("OK").on_click(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+        let mut tester = WidgetTester::new(CacheLayer::new(button), 100.0, 40.0);
+        tester.pump();
+        assert!(tester.root().is_recorded());
+        tester.click(50.0, 20.0);
+        tester.click(50.0, 20.0);
+        assert_eq!(clicks.load(Ordering::SeqCst), 2);
+    }
+}
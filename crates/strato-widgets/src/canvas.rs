@@ -0,0 +1,236 @@
+//! Immediate-mode drawing surface
+//!
+//! A [`Canvas`] hands its bounds and the frame's [`RenderBatch`] to a closure
+//! every time it is rendered, for charts, diagrams and other content that is
+//! easier to draw than to compose from widgets:
+//!
+//! ```
+//! use strato_widgets::canvas::Canvas;
+//! use strato_core::types::Color;
+//!
+//! let sparkline = Canvas::new(|batch, bounds| {
+//!     let values = [0.2, 0.8, 0.5, 0.9];
+//!     let step = bounds.width / (values.len() - 1) as f32;
+//!     let point = |i: usize| {
+//!         let y = bounds.y + bounds.height * (1.0 - values[i]);
+//!         (bounds.x + i as f32 * step, y)
+//!     };
+//!     for i in 1..values.len() {
+//!         batch.add_line(point(i - 1), point(i), Color::rgb(0.2, 0.5, 1.0), 2.0);
+//!     }
+//! })
+//! .size(120.0, 32.0);
+//! ```
+//!
+//! With [`on_gesture`](Canvas::on_gesture), touch pinches and pans over the
+//! canvas reach a handler, e.g. to zoom and move a map kept in signals.
+
+use crate::widget::{clone_id, generate_id, register_bounds, Widget, WidgetId};
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult, GestureEvent},
+    layout::{Constraints, Layout, Size},
+    state::Signal,
+    types::{Point, Rect},
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Closure drawing a canvas's content into its bounds
+pub type DrawFn = Arc<dyn Fn(&mut RenderBatch, Rect) + Send + Sync>;
+
+/// Handler for gestures over a canvas
+pub type GestureFn = Arc<dyn Fn(GestureEvent) + Send + Sync>;
+
+/// A widget whose content is drawn by a closure
+///
+/// The closure runs on every render with the canvas's laid out bounds, in
+/// window coordinates, and everything it draws is clipped to them. Without
+/// [`size`](Self::size) the canvas asks for 300x150, like an HTML canvas.
+#[derive(Clone)]
+pub struct Canvas {
+    id: WidgetId,
+    draw: DrawFn,
+    on_gesture: Option<GestureFn>,
+    width: f32,
+    height: f32,
+    bounds: Signal<Rect>,
+}
+
+impl std::fmt::Debug for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Canvas")
+            .field("id", &self.id)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .finish()
+    }
+}
+
+impl Canvas {
+    /// Create a canvas drawn by `draw`
+    pub fn new<F>(draw: F) -> Self
+    where
+        F: Fn(&mut RenderBatch, Rect) + Send + Sync + 'static,
+    {
+        Self {
+            id: generate_id(),
+            draw: Arc::new(draw),
+            on_gesture: None,
+            width: 300.0,
+            height: 150.0,
+            bounds: Signal::new(Rect::default()),
+        }
+    }
+
+    /// Call `handler` with the pinches and pans centered over the canvas
+    pub fn on_gesture<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(GestureEvent) + Send + Sync + 'static,
+    {
+        self.on_gesture = Some(Arc::new(handler));
+        self
+    }
+
+    /// Set the preferred size, which the layout constraints may override
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// The size the canvas asks for in layout
+    pub fn preferred_size(&self) -> Size {
+        Size::new(self.width, self.height)
+    }
+
+    /// Set the preferred width
+    pub fn width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set the preferred height
+    pub fn height(mut self, height: f32) -> Self {
+        self.height = height;
+        self
+    }
+}
+
+impl Widget for Canvas {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        constraints.constrain(Size::new(self.width, self.height))
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        register_bounds(self.id, bounds);
+        self.bounds.set(bounds);
+
+        batch.push_clip(bounds);
+        (self.draw)(batch, bounds);
+        batch.pop_clip();
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match (event, &self.on_gesture) {
+            (Event::Gesture(gesture), Some(handler))
+                if self.bounds.get().contains(Point::from(gesture.center())) =>
+            {
+                handler(*gesture);
+                EventResult::Handled
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let mut canvas = self.clone();
+        canvas.id = clone_id(self.id);
+        canvas.bounds = Signal::new(self.bounds.get());
+        Box::new(canvas)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+    use strato_core::types::{Color, Transform};
+    use strato_renderer::batch::DrawCommand;
+
+    #[test]
+    fn test_layout_uses_preferred_size_within_constraints() {
+        let mut canvas = Canvas::new(|_, _| {});
+        assert_eq!(
+            canvas.layout(Constraints::loose(1000.0, 1000.0)),
+            Size::new(300.0, 150.0)
+        );
+
+        let mut canvas = canvas.size(80.0, 400.0);
+        assert_eq!(
+            canvas.layout(Constraints::loose(1000.0, 200.0)),
+            Size::new(80.0, 200.0)
+        );
+    }
+
+    #[test]
+    fn test_gestures_over_the_canvas_reach_the_handler() {
+        let zoom = Signal::new(1.0);
+        let zoomed = zoom.clone();
+        let canvas = Canvas::new(|_, _| {}).on_gesture(move |gesture| {
+            if let GestureEvent::Pinch { scale, .. } = gesture {
+                zoomed.update(|zoom| *zoom *= scale);
+            }
+        });
+        let mut tester = crate::test_util::WidgetTester::new(canvas, 300.0, 150.0);
+        let pinch = |x: f32| {
+            Event::Gesture(GestureEvent::Pinch {
+                center: glam::Vec2::new(x, 50.0),
+                scale: 2.0,
+            })
+        };
+        assert_eq!(tester.dispatch(pinch(100.0)), EventResult::Handled);
+        assert_eq!(tester.dispatch(pinch(400.0)), EventResult::Ignored);
+        assert_eq!(zoom.get(), 2.0);
+    }
+
+    #[test]
+    fn test_draws_clipped_to_its_bounds() {
+        let seen = Arc::new(Mutex::new(None));
+        let recorded = seen.clone();
+        let canvas = Canvas::new(move |batch, bounds| {
+            *recorded.lock() = Some(bounds);
+            batch.add_rect(bounds, Color::rgb(1.0, 0.0, 0.0), Transform::identity());
+        });
+
+        let mut batch = RenderBatch::new();
+        canvas.render(
+            &mut batch,
+            Layout::new(glam::Vec2::new(10.0, 20.0), Size::new(40.0, 30.0)),
+        );
+
+        let bounds = Rect::new(10.0, 20.0, 40.0, 30.0);
+        assert_eq!(*seen.lock(), Some(bounds));
+        assert!(
+            matches!(batch.commands.first(), Some(DrawCommand::PushClip(clip)) if *clip == bounds)
+        );
+        assert!(matches!(
+            batch.commands.get(1),
+            Some(DrawCommand::Rect { .. })
+        ));
+        assert!(matches!(batch.commands.last(), Some(DrawCommand::PopClip)));
+    }
+}
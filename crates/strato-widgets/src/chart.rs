@@ -0,0 +1,715 @@
+//! Line and bar charts
+//!
+//! A [`Chart`] plots one or more [`Series`] on a [`Canvas`], with linear axes
+//! fitted to the data, tick labels, a legend and a tooltip for the data point
+//! nearest the pointer. Series values live in signals and are read on every
+//! render, so a chart follows its data without being rebuilt:
+//!
+//! ```
+//! use strato_core::state::Signal;
+//! use strato_widgets::chart::{Chart, Series};
+//!
+//! let requests = Signal::new(vec![120.0, 180.0, 150.0]);
+//! let chart = Chart::new(vec![
+//!     Series::line("Requests", vec![]).bind(requests.clone()),
+//!     Series::bar("Errors", vec![3.0, 7.0, 2.0]),
+//! ])
+//! .labels(vec!["Mon".into(), "Tue".into(), "Wed".into()]);
+//!
+//! requests.update(|values| values.push(210.0));
+//! ```
+
+use crate::canvas::Canvas;
+use crate::text::{measure_line_metrics, measure_text_width};
+use crate::widget::{clone_id, Widget, WidgetId};
+use parking_lot::Mutex;
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult},
+    layout::{Constraints, Layout, Size},
+    state::Signal,
+    text::TextAlign,
+    types::{Color, Point, Rect, Transform},
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Colors given to series without one of their own, in order
+const PALETTE: [(f32, f32, f32); 6] = [
+    (0.26, 0.52, 0.96),
+    (0.96, 0.55, 0.16),
+    (0.20, 0.70, 0.45),
+    (0.86, 0.26, 0.33),
+    (0.58, 0.40, 0.85),
+    (0.15, 0.70, 0.78),
+];
+
+// Share of a category's width taken by its bars
+const BAR_GROUP_WIDTH: f32 = 0.8;
+// Gap between labels and what they label
+const LABEL_GAP: f32 = 4.0;
+
+/// How a series is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeriesKind {
+    /// Points joined by straight lines
+    Line,
+    /// A bar per value, side by side with the other bar series
+    Bar,
+}
+
+/// A named sequence of values plotted by a [`Chart`]
+///
+/// Value `i` of every series belongs to the chart's `i`th category.
+/// Non-finite values leave a gap.
+#[derive(Debug, Clone)]
+pub struct Series {
+    name: String,
+    kind: SeriesKind,
+    color: Option<Color>,
+    values: Signal<Vec<f32>>,
+}
+
+impl Series {
+    /// A series drawn as a line
+    pub fn line(name: impl Into<String>, values: Vec<f32>) -> Self {
+        Self::new(name.into(), SeriesKind::Line, values)
+    }
+
+    /// A series drawn as bars
+    pub fn bar(name: impl Into<String>, values: Vec<f32>) -> Self {
+        Self::new(name.into(), SeriesKind::Bar, values)
+    }
+
+    fn new(name: String, kind: SeriesKind, values: Vec<f32>) -> Self {
+        Self {
+            name,
+            kind,
+            color: None,
+            values: Signal::new(values),
+        }
+    }
+
+    /// Bind the values to a signal
+    ///
+    /// The chart shows the signal's current values every time it renders.
+    pub fn bind(mut self, values: Signal<Vec<f32>>) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// Set the color, picked from a palette by default
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// The name shown in the legend and tooltip
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How the series is drawn
+    pub fn kind(&self) -> SeriesKind {
+        self.kind
+    }
+
+    /// The signal holding the values
+    pub fn values_signal(&self) -> &Signal<Vec<f32>> {
+        &self.values
+    }
+}
+
+/// Chart styling
+#[derive(Debug, Clone)]
+pub struct ChartStyle {
+    pub background: Color,
+    pub axis_color: Color,
+    pub grid_color: Color,
+    pub label_color: Color,
+    pub tooltip_background: Color,
+    pub tooltip_text_color: Color,
+    pub font_size: f32,
+    pub line_width: f32,
+}
+
+impl Default for ChartStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::TRANSPARENT,
+            axis_color: Color::rgb(0.55, 0.55, 0.6),
+            grid_color: Color::rgba(0.55, 0.55, 0.6, 0.25),
+            label_color: Color::rgb(0.45, 0.45, 0.5),
+            tooltip_background: Color::rgba(0.1, 0.1, 0.12, 0.9),
+            tooltip_text_color: Color::WHITE,
+            font_size: 11.0,
+            line_width: 2.0,
+        }
+    }
+}
+
+/// Round values spaced evenly from at or below `min` to at or above `max`
+///
+/// Steps are 1, 2 or 5 times a power of ten, chosen to give about `count`
+/// ticks.
+fn nice_ticks(min: f32, max: f32, count: usize) -> Vec<f32> {
+    let rough = (max - min) / count.saturating_sub(1).max(1) as f32;
+    let magnitude = 10f32.powf(rough.log10().floor());
+    let step = [1.0, 2.0, 5.0, 10.0]
+        .into_iter()
+        .map(|factor| factor * magnitude)
+        .find(|step| *step >= rough)
+        .unwrap_or(10.0 * magnitude);
+
+    let first = (min / step).floor() as i32;
+    let last = (max / step).ceil() as i32;
+    (first..=last).map(|i| i as f32 * step).collect()
+}
+
+/// `value` with at most two decimals and no trailing zeros
+fn format_value(value: f32) -> String {
+    let text = format!("{value:.2}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        "0".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+/// Where the plot of a chart with given bounds goes
+struct Geometry {
+    area: Rect,
+    ticks: Vec<f32>,
+    categories: usize,
+    // Whether categories are slots for bars rather than points on the edges
+    slotted: bool,
+}
+
+impl Geometry {
+    fn min(&self) -> f32 {
+        self.ticks.first().copied().unwrap_or(0.0)
+    }
+
+    fn max(&self) -> f32 {
+        self.ticks.last().copied().unwrap_or(1.0)
+    }
+
+    /// Width given to one category
+    fn slot_width(&self) -> f32 {
+        if self.slotted {
+            self.area.width / self.categories.max(1) as f32
+        } else {
+            self.area.width / self.categories.saturating_sub(1).max(1) as f32
+        }
+    }
+
+    /// Horizontal center of category `index`
+    fn x(&self, index: usize) -> f32 {
+        if self.slotted {
+            self.area.x + (index as f32 + 0.5) * self.slot_width()
+        } else if self.categories == 1 {
+            self.area.x + self.area.width / 2.0
+        } else {
+            self.area.x + index as f32 * self.slot_width()
+        }
+    }
+
+    fn y(&self, value: f32) -> f32 {
+        let t = (value - self.min()) / (self.max() - self.min());
+        self.area.y + self.area.height * (1.0 - t)
+    }
+
+    /// The category whose center is nearest to `x`
+    fn category_at(&self, x: f32) -> Option<usize> {
+        (0..self.categories)
+            .min_by(|a, b| (self.x(*a) - x).abs().total_cmp(&(self.x(*b) - x).abs()))
+    }
+}
+
+/// Everything a chart draws, shared with its canvas
+#[derive(Debug, Clone)]
+struct Plot {
+    series: Vec<Series>,
+    labels: Vec<String>,
+    style: ChartStyle,
+    axes: bool,
+    legend: bool,
+    // Bounds of the last render, for hover hit testing
+    bounds: Rect,
+    // Hovered (series, category)
+    hovered: Option<(usize, usize)>,
+}
+
+impl Plot {
+    fn color(&self, series: usize) -> Color {
+        self.series[series].color.unwrap_or_else(|| {
+            let (r, g, b) = PALETTE[series % PALETTE.len()];
+            Color::rgb(r, g, b)
+        })
+    }
+
+    fn show_legend(&self) -> bool {
+        self.legend && self.series.iter().any(|series| !series.name.is_empty())
+    }
+
+    fn geometry(&self, bounds: Rect, values: &[Vec<f32>]) -> Geometry {
+        let has_bars = self.series.iter().any(|s| s.kind == SeriesKind::Bar);
+        let finite = values.iter().flatten().copied().filter(|v| v.is_finite());
+        let (mut min, mut max) = finite.fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), v| {
+            (lo.min(v), hi.max(v))
+        });
+        if min > max {
+            (min, max) = (0.0, 1.0);
+        }
+        if has_bars {
+            // Bars grow from zero
+            (min, max) = (min.min(0.0), max.max(0.0));
+        }
+        if min == max {
+            (min, max) = (min - 1.0, max + 1.0);
+        }
+        let ticks = nice_ticks(min, max, 5);
+
+        let font_size = self.style.font_size;
+        let mut area = bounds;
+        if self.show_legend() {
+            let legend_height = font_size + 2.0 * LABEL_GAP;
+            area.y += legend_height;
+            area.height -= legend_height;
+        }
+        if self.axes {
+            let label_width = ticks
+                .iter()
+                .map(|tick| measure_text_width(&format_value(*tick), font_size, 0.0))
+                .fold(0.0, f32::max);
+            area.x += label_width + 2.0 * LABEL_GAP;
+            area.width -= label_width + 2.0 * LABEL_GAP;
+            // Room for the x labels below and half a y label above
+            area.y += font_size / 2.0;
+            area.height -= font_size * 1.5 + 2.0 * LABEL_GAP;
+        }
+        area.width = area.width.max(0.0);
+        area.height = area.height.max(0.0);
+
+        Geometry {
+            area,
+            ticks,
+            categories: values.iter().map(Vec::len).max().unwrap_or(0),
+            slotted: has_bars,
+        }
+    }
+
+    /// Rectangle of bar series `series` in category `index`
+    fn bar_rect(&self, geometry: &Geometry, series: usize, index: usize, value: f32) -> Rect {
+        let bars: Vec<usize> = (0..self.series.len())
+            .filter(|i| self.series[*i].kind == SeriesKind::Bar)
+            .collect();
+        let slot = bars.iter().position(|i| *i == series).unwrap_or(0);
+        let group = geometry.slot_width() * BAR_GROUP_WIDTH;
+        let width = group / bars.len().max(1) as f32;
+        let x = geometry.x(index) - group / 2.0 + slot as f32 * width;
+
+        let base = geometry.y(0.0f32.clamp(geometry.min(), geometry.max()));
+        let top = geometry.y(value);
+        Rect::new(x, top.min(base), width, (top - base).abs())
+    }
+
+    /// Where the tooltip for a value points
+    fn anchor(&self, geometry: &Geometry, series: usize, index: usize, value: f32) -> (f32, f32) {
+        match self.series[series].kind {
+            SeriesKind::Line => (geometry.x(index), geometry.y(value)),
+            SeriesKind::Bar => {
+                let bar = self.bar_rect(geometry, series, index, value);
+                (bar.x + bar.width / 2.0, geometry.y(value))
+            }
+        }
+    }
+
+    /// The data point nearest to `point`, as (series, category)
+    fn nearest(&self, point: Point) -> Option<(usize, usize)> {
+        if !self.bounds.contains(point) {
+            return None;
+        }
+        let values: Vec<Vec<f32>> = self.series.iter().map(|s| s.values.get()).collect();
+        let geometry = self.geometry(self.bounds, &values);
+        let index = geometry.category_at(point.x)?;
+
+        values
+            .iter()
+            .enumerate()
+            .filter_map(|(series, values)| {
+                let value = *values.get(index)?;
+                value.is_finite().then(|| {
+                    let (x, y) = self.anchor(&geometry, series, index, value);
+                    (series, (x - point.x).hypot(y - point.y))
+                })
+            })
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(series, _)| (series, index))
+    }
+
+    fn draw(&mut self, batch: &mut RenderBatch, bounds: Rect) {
+        self.bounds = bounds;
+        let style = &self.style;
+        if style.background.a > 0.0 {
+            batch.add_rect(bounds, style.background, Transform::identity());
+        }
+
+        let values: Vec<Vec<f32>> = self.series.iter().map(|s| s.values.get()).collect();
+        let geometry = self.geometry(bounds, &values);
+
+        if self.show_legend() {
+            self.draw_legend(batch, bounds);
+        }
+        if self.axes {
+            self.draw_axes(batch, &geometry);
+        }
+
+        for (series, values) in values.iter().enumerate() {
+            let color = self.color(series);
+            match self.series[series].kind {
+                SeriesKind::Bar => {
+                    for (index, value) in values.iter().enumerate() {
+                        if value.is_finite() {
+                            let bar = self.bar_rect(&geometry, series, index, *value);
+                            batch.add_rect(bar, color, Transform::identity());
+                        }
+                    }
+                }
+                SeriesKind::Line => {
+                    let points: Vec<Option<(f32, f32)>> = values
+                        .iter()
+                        .enumerate()
+                        .map(|(index, value)| {
+                            value
+                                .is_finite()
+                                .then(|| (geometry.x(index), geometry.y(*value)))
+                        })
+                        .collect();
+                    for pair in points.windows(2) {
+                        if let [Some(start), Some(end)] = pair {
+                            batch.add_line(*start, *end, color, self.style.line_width);
+                        }
+                    }
+                }
+            }
+        }
+
+        let hovered = self.hovered.and_then(|(series, index)| {
+            let value = *values.get(series)?.get(index)?;
+            value.is_finite().then_some((series, index, value))
+        });
+        if let Some((series, index, value)) = hovered {
+            self.draw_tooltip(batch, &geometry, series, index, value);
+        }
+    }
+
+    fn draw_legend(&self, batch: &mut RenderBatch, bounds: Rect) {
+        let font_size = self.style.font_size;
+        let mut x = bounds.x + LABEL_GAP;
+        let y = bounds.y + LABEL_GAP;
+        for (index, series) in self.series.iter().enumerate() {
+            if series.name.is_empty() {
+                continue;
+            }
+            let swatch = Rect::new(x, y + font_size * 0.1, font_size * 0.8, font_size * 0.8);
+            batch.add_rect(swatch, self.color(index), Transform::identity());
+            x += swatch.width + LABEL_GAP;
+            batch.add_text(
+                series.name.clone(),
+                (x, y),
+                self.style.label_color,
+                font_size,
+                0.0,
+            );
+            x += measure_text_width(&series.name, font_size, 0.0) + 3.0 * LABEL_GAP;
+        }
+    }
+
+    fn draw_axes(&self, batch: &mut RenderBatch, geometry: &Geometry) {
+        let style = &self.style;
+        let area = geometry.area;
+
+        for tick in &geometry.ticks {
+            let y = geometry.y(*tick);
+            batch.add_line((area.x, y), (area.x + area.width, y), style.grid_color, 1.0);
+            batch.add_text_aligned(
+                format_value(*tick),
+                (
+                    area.x - LABEL_GAP,
+                    measure_line_metrics(style.font_size).centered_top(y, 0.0),
+                ),
+                style.label_color,
+                style.font_size,
+                0.0,
+                TextAlign::Right,
+            );
+        }
+        let bottom = area.y + area.height;
+        batch.add_line(
+            (area.x, bottom),
+            (area.x + area.width, bottom),
+            style.axis_color,
+            1.0,
+        );
+        batch.add_line((area.x, area.y), (area.x, bottom), style.axis_color, 1.0);
+
+        // Label every category that fits, keeping the spacing even
+        let label = |index: usize| {
+            self.labels
+                .get(index)
+                .cloned()
+                .unwrap_or_else(|| (index + 1).to_string())
+        };
+        let widest = (0..geometry.categories)
+            .map(|index| measure_text_width(&label(index), style.font_size, 0.0))
+            .fold(0.0, f32::max);
+        let every = ((widest + 2.0 * LABEL_GAP) / geometry.slot_width().max(1.0))
+            .ceil()
+            .max(1.0) as usize;
+        for index in (0..geometry.categories).step_by(every) {
+            batch.add_text_aligned(
+                label(index),
+                (geometry.x(index), bottom + LABEL_GAP),
+                style.label_color,
+                style.font_size,
+                0.0,
+                TextAlign::Center,
+            );
+        }
+    }
+
+    fn draw_tooltip(
+        &self,
+        batch: &mut RenderBatch,
+        geometry: &Geometry,
+        series: usize,
+        index: usize,
+        value: f32,
+    ) {
+        let style = &self.style;
+        let (x, y) = self.anchor(geometry, series, index, value);
+        batch.add_circle((x, y), 4.0, self.color(series), 16, Transform::identity());
+
+        let name = &self.series[series].name;
+        let text = match self.labels.get(index) {
+            Some(label) if !name.is_empty() => format!("{label} · {name}: {}", format_value(value)),
+            Some(label) => format!("{label}: {}", format_value(value)),
+            None if !name.is_empty() => format!("{name}: {}", format_value(value)),
+            None => format_value(value),
+        };
+        let width = measure_text_width(&text, style.font_size, 0.0) + 4.0 * LABEL_GAP;
+        let height = style.font_size + 2.0 * LABEL_GAP;
+
+        // Above the point, kept inside the chart
+        let bounds = self.bounds;
+        let left =
+            (x - width / 2.0).clamp(bounds.x, (bounds.x + bounds.width - width).max(bounds.x));
+        let mut top = y - height - 2.0 * LABEL_GAP;
+        if top < bounds.y {
+            top = y + 2.0 * LABEL_GAP;
+        }
+        let tip = Rect::new(left, top, width, height);
+        batch.add_rounded_rect(tip, style.tooltip_background, 4.0, Transform::identity());
+        batch.add_text(
+            text,
+            (tip.x + 2.0 * LABEL_GAP, tip.y + LABEL_GAP),
+            style.tooltip_text_color,
+            style.font_size,
+            0.0,
+        );
+    }
+}
+
+/// A line and bar chart
+///
+/// Axes are linear and fitted to the values of all series, with bar charts
+/// always including zero. Hovering shows the value nearest the pointer.
+pub struct Chart {
+    plot: Arc<Mutex<Plot>>,
+    canvas: Canvas,
+}
+
+impl std::fmt::Debug for Chart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Chart")
+            .field("plot", &*self.plot.lock())
+            .field("canvas", &self.canvas)
+            .finish()
+    }
+}
+
+impl Chart {
+    /// Create a chart of `series` with axes and a legend
+    pub fn new(series: Vec<Series>) -> Self {
+        Self::from_plot(Plot {
+            series,
+            labels: Vec::new(),
+            style: ChartStyle::default(),
+            axes: true,
+            legend: true,
+            bounds: Rect::new(0.0, 0.0, 0.0, 0.0),
+            hovered: None,
+        })
+    }
+
+    /// A small chart of a single series without axes or legend
+    pub fn sparkline(series: Series) -> Self {
+        Self::new(vec![series])
+            .axes(false)
+            .legend(false)
+            .size(120.0, 32.0)
+    }
+
+    fn from_plot(plot: Plot) -> Self {
+        let plot = Arc::new(Mutex::new(plot));
+        let shared = plot.clone();
+        let canvas = Canvas::new(move |batch, bounds| shared.lock().draw(batch, bounds));
+        Self { plot, canvas }
+    }
+
+    /// Set the category labels along the x axis, numbered from 1 by default
+    pub fn labels(self, labels: Vec<String>) -> Self {
+        self.plot.lock().labels = labels;
+        self
+    }
+
+    /// Show or hide the axes, ticks and labels
+    pub fn axes(self, axes: bool) -> Self {
+        self.plot.lock().axes = axes;
+        self
+    }
+
+    /// Show or hide the legend
+    pub fn legend(self, legend: bool) -> Self {
+        self.plot.lock().legend = legend;
+        self
+    }
+
+    /// Set the style
+    pub fn style(self, style: ChartStyle) -> Self {
+        self.plot.lock().style = style;
+        self
+    }
+
+    /// Set the preferred size, 300x150 by default
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.canvas = self.canvas.size(width, height);
+        self
+    }
+
+    /// The hovered data point as (series index, category index)
+    pub fn hovered(&self) -> Option<(usize, usize)> {
+        self.plot.lock().hovered
+    }
+}
+
+impl Widget for Chart {
+    fn id(&self) -> WidgetId {
+        self.canvas.id()
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.canvas.set_id(id);
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.canvas.layout(constraints)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.canvas.render(batch, layout);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::MouseMove(mouse) = event {
+            let mut plot = self.plot.lock();
+            plot.hovered = plot.nearest(Point::new(mouse.position.x, mouse.position.y));
+        }
+        EventResult::Ignored
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let plot = Plot {
+            hovered: None,
+            ..self.plot.lock().clone()
+        };
+        let size = self.canvas.preferred_size();
+        let mut chart = Chart::from_plot(plot).size(size.width, size.height);
+        chart.set_id(clone_id(self.id()));
+        Box::new(chart)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::WidgetTester;
+    use strato_renderer::batch::DrawCommand;
+
+    #[test]
+    fn test_ticks_are_round_and_cover_the_range() {
+        assert_eq!(nice_ticks(0.0, 10.0, 5), vec![0.0, 5.0, 10.0]);
+        assert_eq!(nice_ticks(3.0, 97.0, 5), vec![0.0, 50.0, 100.0]);
+        assert_eq!(nice_ticks(-0.3, 0.7, 5), vec![-0.5, 0.0, 0.5, 1.0]);
+        assert_eq!(format_value(2.50), "2.5");
+        assert_eq!(format_value(-0.0001), "0");
+    }
+
+    fn bars(batch: &RenderBatch) -> Vec<Rect> {
+        batch
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Rect { rect, .. } => Some(*rect),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bars_scale_to_the_data_and_follow_the_signal() {
+        let values = Signal::new(vec![1.0, 2.0]);
+        let mut tester = WidgetTester::new(
+            Chart::new(vec![Series::bar("", vec![]).bind(values.clone())])
+                .axes(false)
+                .size(100.0, 100.0),
+            100.0,
+            100.0,
+        );
+
+        // Axis from 0 to 2: the taller bar fills the height
+        let heights: Vec<f32> = bars(tester.frame()).iter().map(|r| r.height).collect();
+        assert_eq!(heights, vec![50.0, 100.0]);
+
+        values.set(vec![1.0, 2.0, 4.0, 4.0]);
+        tester.pump();
+        let heights: Vec<f32> = bars(tester.frame()).iter().map(|r| r.height).collect();
+        assert_eq!(heights, vec![25.0, 50.0, 100.0, 100.0]);
+    }
+
+    #[test]
+    fn test_hover_picks_the_nearest_point() {
+        let mut tester = WidgetTester::new(
+            Chart::new(vec![
+                Series::line("Low", vec![0.0, 1.0, 0.0]),
+                Series::line("High", vec![10.0, 9.0, 10.0]),
+            ])
+            .axes(false)
+            .legend(false)
+            .size(100.0, 100.0),
+            100.0,
+            100.0,
+        );
+        // Category 1 sits in the middle; high values near the top
+        tester.move_to(52.0, 15.0);
+        assert_eq!(tester.root().hovered(), Some((1, 1)));
+        tester.move_to(5.0, 95.0);
+        assert_eq!(tester.root().hovered(), Some((0, 0)));
+
+        assert_eq!(tester.drawn_text(), vec!["Low: 0"]);
+
+        tester.move_to(150.0, 50.0);
+        assert_eq!(tester.root().hovered(), None);
+    }
+}
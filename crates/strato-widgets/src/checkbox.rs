@@ -1,18 +1,124 @@
 //! Checkbox widget implementation for StratoUI
 
 use crate::control::{ControlRole, ControlState};
-use crate::widget::{generate_id, Widget, WidgetContext, WidgetId, WidgetState};
+use crate::image::{Image, ImageFit, ImageSource, ImageState};
+use crate::text::{measure_line_metrics, measure_text_width};
+use crate::theme::ColorPalette;
+use crate::widget::{
+    clone_id, generate_id, register_bounds, Widget, WidgetContext, WidgetId, WidgetState,
+};
 use std::any::Any;
+use std::sync::Arc;
 use strato_core::{
     event::{Event, EventResult, MouseButton},
     layout::{Constraints, Layout, Size},
     state::Signal,
     theme::Theme,
-    types::{Color, Point, Rect, Transform},
+    types::{BorderRadius, Color, Point, Rect, Transform},
     vdom::VNode,
+    window::CursorIcon,
 };
 use strato_renderer::batch::RenderBatch;
 
+/// Font size of checkbox and radio button labels
+const LABEL_FONT_SIZE: f32 = 14.0;
+/// Gap between the box or circle and the label
+const LABEL_GAP: f32 = 8.0;
+
+/// What clicking the label of a checkbox or radio button does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LabelClick {
+    /// Clicking the label activates the control, like an HTML `<label>`
+    #[default]
+    Activate,
+    /// Only the box or circle responds to the pointer
+    Ignore,
+}
+
+/// Closure drawing a check mark into a box with the given color
+pub type CheckDrawFn = Arc<dyn Fn(&mut RenderBatch, Rect, Color) + Send + Sync>;
+
+/// Mark drawn inside a checked checkbox
+#[derive(Clone, Default)]
+pub enum CheckGlyph {
+    /// A check mark stroked in the style's check color
+    #[default]
+    Check,
+    /// An image fitted into the box
+    Image(ImageSource),
+    /// A closure drawing into the box's rectangle with the check color
+    Custom(CheckDrawFn),
+}
+
+impl CheckGlyph {
+    /// Draw the mark with `draw`
+    pub fn custom<F>(draw: F) -> Self
+    where
+        F: Fn(&mut RenderBatch, Rect, Color) + Send + Sync + 'static,
+    {
+        Self::Custom(Arc::new(draw))
+    }
+}
+
+impl std::fmt::Debug for CheckGlyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Check => f.write_str("Check"),
+            Self::Image(source) => f.debug_tuple("Image").field(source).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Width the label takes next to the box or circle, including the gap
+fn label_width(label: Option<&str>) -> f32 {
+    label.map_or(0.0, |label| {
+        LABEL_GAP + measure_text_width(label, LABEL_FONT_SIZE, 0.0)
+    })
+}
+
+/// The square the box or circle is drawn in, centered vertically in `bounds`
+fn indicator_rect(bounds: Rect, size: f32) -> Rect {
+    Rect::new(
+        bounds.x,
+        bounds.y + (bounds.height - size) / 2.0,
+        size,
+        size,
+    )
+}
+
+/// The area that responds to the pointer
+fn hit_area(bounds: Rect, size: f32, label_click: LabelClick) -> Rect {
+    match label_click {
+        LabelClick::Activate => bounds,
+        LabelClick::Ignore => indicator_rect(bounds, size),
+    }
+}
+
+fn draw_label(
+    batch: &mut RenderBatch,
+    bounds: Rect,
+    size: f32,
+    label: &str,
+    enabled: bool,
+    palette: &ColorPalette,
+) {
+    let text_x = bounds.x + size + LABEL_GAP;
+    let text_y = measure_line_metrics(LABEL_FONT_SIZE).centered_top(bounds.y, bounds.height);
+    let label_color = if enabled {
+        Color::BLACK
+    } else {
+        palette.disabled(Color::BLACK)
+    };
+    batch.add_text(
+        label.to_string(),
+        (text_x, text_y),
+        label_color,
+        LABEL_FONT_SIZE,
+        0.0,
+    );
+}
+
 /// Checkbox widget for boolean selection
 #[derive(Debug, Clone)]
 pub struct Checkbox {
@@ -22,21 +128,32 @@ pub struct Checkbox {
     enabled: bool,
     size: f32,
     style: CheckboxStyle,
+    /// Loaded image of a [`CheckGlyph::Image`] glyph
+    glyph_image: Option<Image>,
+    label_click: LabelClick,
+    tab_index: Option<i32>,
     bounds: Signal<Rect>,
     control: ControlState,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
 }
 
 /// Styling options for checkbox
 #[derive(Debug, Clone)]
 pub struct CheckboxStyle {
+    /// Width and height of the box
     pub size: f32,
     pub border_width: f32,
+    /// Corner radius of the box
     pub border_radius: f32,
+    /// Color of the check mark
     pub check_color: [f32; 4],
     pub border_color: [f32; 4],
     pub background_color: [f32; 4],
     pub hover_color: [f32; 4],
     pub disabled_color: [f32; 4],
+    /// Mark drawn when checked
+    pub glyph: CheckGlyph,
 }
 
 impl Default for CheckboxStyle {
@@ -50,6 +167,7 @@ impl Default for CheckboxStyle {
             background_color: [0.2, 0.6, 1.0, 1.0], // Blue
             hover_color: [0.3, 0.7, 1.0, 1.0],      // Light blue
             disabled_color: [0.7, 0.7, 0.7, 1.0],   // Light gray
+            glyph: CheckGlyph::Check,
         }
     }
 }
@@ -75,8 +193,12 @@ impl Checkbox {
             enabled: true,
             size: 20.0,
             style: CheckboxStyle::default(),
+            glyph_image: None,
+            label_click: LabelClick::default(),
+            tab_index: None,
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
             control,
+            palette: ColorPalette::default(),
         }
     }
 
@@ -111,10 +233,86 @@ impl Checkbox {
 
     /// Set custom style
     pub fn style(mut self, style: CheckboxStyle) -> Self {
+        self.size = style.size;
         self.style = style;
+        self.load_glyph();
+        self
+    }
+
+    /// Set the corner radius of the box
+    pub fn corner_radius(mut self, radius: f32) -> Self {
+        self.style.border_radius = radius;
+        self
+    }
+
+    /// Set the color of the check mark
+    pub fn check_color(mut self, color: Color) -> Self {
+        self.style.check_color = [color.r, color.g, color.b, color.a];
+        self
+    }
+
+    /// Set the mark drawn when checked
+    pub fn glyph(mut self, glyph: CheckGlyph) -> Self {
+        self.style.glyph = glyph;
+        self.load_glyph();
+        self
+    }
+
+    /// Set what clicking the label does
+    pub fn label_click(mut self, label_click: LabelClick) -> Self {
+        self.label_click = label_click;
         self
     }
 
+    /// Set the checkbox's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
+    /// Start loading the glyph's image, if it has one
+    fn load_glyph(&mut self) {
+        self.glyph_image = match &self.style.glyph {
+            CheckGlyph::Image(source) => Some(Image::new(source.clone()).fit(ImageFit::Contain)),
+            _ => None,
+        };
+    }
+
+    /// Draw the checked mark into `rect`
+    fn draw_glyph(&self, batch: &mut RenderBatch, rect: Rect) {
+        let color = if self.enabled {
+            color_from(self.style.check_color)
+        } else {
+            self.palette.disabled(color_from(self.style.check_color))
+        };
+
+        match &self.style.glyph {
+            CheckGlyph::Check => {
+                let point = |x: f32, y: f32| (rect.x + rect.width * x, rect.y + rect.height * y);
+                let thickness = (rect.width * 0.12).max(1.0);
+                batch.add_line(point(0.25, 0.5), point(0.42, 0.68), color, thickness);
+                batch.add_line(point(0.42, 0.68), point(0.76, 0.32), color, thickness);
+            }
+            CheckGlyph::Image(_) => {
+                let Some(image) = &self.glyph_image else {
+                    return;
+                };
+                // Nothing is drawn until the image has loaded
+                if matches!(image.state(), ImageState::Loaded(_)) {
+                    let inset = rect.width * 0.15;
+                    image.render(
+                        batch,
+                        Layout::new(
+                            glam::Vec2::new(rect.x + inset, rect.y + inset),
+                            Size::new(rect.width - 2.0 * inset, rect.height - 2.0 * inset),
+                        ),
+                    );
+                }
+            }
+            CheckGlyph::Custom(draw) => draw(batch, rect, color),
+        }
+    }
+
     /// Get the checked state signal
     pub fn checked_signal(&self) -> &Signal<bool> {
         &self.checked
@@ -132,6 +330,11 @@ impl Checkbox {
         self.control.set_toggled(!current);
     }
 
+    /// The area that responds to the pointer
+    fn hit_area(&self) -> Rect {
+        hit_area(self.bounds.get(), self.style.size, self.label_click)
+    }
+
     /// Handle click event
     fn handle_click(&mut self) -> EventResult {
         if self.enabled {
@@ -216,16 +419,13 @@ impl Widget for Checkbox {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         let checkbox_size = self.style.size;
-        let label_width = if let Some(ref label) = self.label {
-            // Estimate label width (this would be more accurate with actual text measurement)
-            label.len() as f32 * 8.0 + 8.0 // 8px per char + 8px spacing
-        } else {
-            0.0
-        };
-
-        let total_width = checkbox_size + label_width;
+        let total_width = checkbox_size + label_width(self.label.as_deref());
         let height = checkbox_size.max(20.0); // Minimum height for text
 
         constraints.constrain(Size::new(total_width, height))
@@ -239,63 +439,113 @@ impl Widget for Checkbox {
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         // Draw checkbox background
-        // Center vertically
-        let box_y = bounds.y + (bounds.height - self.style.size) / 2.0;
-        let box_rect = Rect::new(bounds.x, box_y, self.style.size, self.style.size);
+        let box_rect = indicator_rect(bounds, self.style.size);
         let state = self.control.state();
-        let base_color = if !self.enabled {
-            color_from(self.style.disabled_color)
-        } else if self.is_checked() {
+        let checked = self.is_checked();
+        let base_color = if checked {
             color_from(self.style.background_color)
         } else {
             Color::WHITE
         };
 
-        let hover_color = if self.enabled {
-            color_from(self.style.hover_color)
+        let bg_color = if self.enabled {
+            let target_color = match state {
+                WidgetState::Hovered | WidgetState::Pressed => color_from(self.style.hover_color),
+                _ => base_color,
+            };
+            blend_color(base_color, target_color, self.control.interaction_factor())
         } else {
-            base_color
+            self.palette.disabled(base_color)
         };
 
-        let target_color = match state {
-            WidgetState::Hovered | WidgetState::Pressed => hover_color,
-            WidgetState::Disabled => color_from(self.style.disabled_color),
-            _ => base_color,
-        };
-        let bg_color = blend_color(base_color, target_color, self.control.interaction_factor());
+        batch.add_rounded_rect(
+            box_rect,
+            bg_color,
+            self.style.border_radius,
+            Transform::identity(),
+        );
 
-        batch.add_rect(box_rect, bg_color, Transform::identity());
+        if checked {
+            self.draw_glyph(batch, box_rect);
+        } else if self.style.border_width > 0.0 {
+            let border_color = if self.enabled {
+                color_from(self.style.border_color)
+            } else {
+                self.palette.disabled(color_from(self.style.border_color))
+            };
+            batch.add_stroke(
+                box_rect,
+                border_color,
+                self.style.border_width,
+                BorderRadius::all(self.style.border_radius),
+                Transform::identity(),
+            );
+        }
 
-        // Draw label
         if let Some(label) = &self.label {
-            let text_x = bounds.x + self.style.size + 8.0;
-            let text_y = bounds.y + bounds.height / 2.0 - 7.0; // approx center
-            let mut label_color = Color::BLACK;
-            if !self.enabled {
-                label_color.a = 0.6;
-            }
-            batch.add_text(label.clone(), (text_x, text_y), label_color, 14.0, 0.0);
+            draw_label(
+                batch,
+                bounds,
+                self.style.size,
+                label,
+                self.enabled,
+                &self.palette,
+            );
         }
     }
 
     fn update(&mut self, ctx: &WidgetContext) {
         self.control.update(ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.control.state() == WidgetState::Focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.control.focus();
+        } else {
+            self.control.blur();
+        }
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.hit_area()
+            .contains(point)
+            .then_some(CursorIcon::Pointer)
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        if let EventResult::Handled = self.control.handle_pointer_event(event, self.bounds.get()) {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        if let EventResult::Handled = self.control.handle_pointer_event(event, self.hit_area()) {
             if matches!(event, Event::MouseUp(_)) {
-                if self.enabled {
-                    self.handle_click();
-                }
+                self.handle_click();
             }
             return EventResult::Handled;
         }
 
         if let EventResult::Handled = self.control.handle_keyboard_activation(event) {
-            if matches!(event, Event::KeyUp(_)) && self.enabled {
+            if matches!(event, Event::KeyUp(_)) {
                 self.handle_click();
             }
             return EventResult::Handled;
@@ -313,7 +563,9 @@ impl Widget for Checkbox {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(self.clone())
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        Box::new(widget)
     }
 
     // Removed state method as it's not part of Widget trait
@@ -329,15 +581,22 @@ pub struct RadioButton {
     label: Option<String>,
     enabled: bool,
     style: RadioStyle,
+    label_click: LabelClick,
+    tab_index: Option<i32>,
     bounds: Signal<Rect>,
     control: ControlState,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
 }
 
 /// Styling options for radio button
 #[derive(Debug, Clone)]
 pub struct RadioStyle {
+    /// Diameter of the circle
     pub size: f32,
     pub border_width: f32,
+    /// Diameter of the dot shown when selected
+    pub dot_size: f32,
     pub dot_color: [f32; 4],
     pub border_color: [f32; 4],
     pub background_color: [f32; 4],
@@ -350,6 +609,7 @@ impl Default for RadioStyle {
         Self {
             size: 20.0,
             border_width: 2.0,
+            dot_size: 8.0,
             dot_color: [1.0, 1.0, 1.0, 1.0],        // White
             border_color: [0.5, 0.5, 0.5, 1.0],     // Gray
             background_color: [0.2, 0.6, 1.0, 1.0], // Blue
@@ -372,8 +632,11 @@ impl RadioButton {
             label: None,
             enabled: true,
             style: RadioStyle::default(),
+            label_click: LabelClick::default(),
+            tab_index: None,
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
             control,
+            palette: ColorPalette::default(),
         }
     }
 
@@ -405,6 +668,35 @@ impl RadioButton {
         self
     }
 
+    /// Set the diameter of the circle
+    pub fn size(mut self, size: f32) -> Self {
+        self.style.size = size;
+        self
+    }
+
+    /// Set the diameter of the dot shown when selected
+    pub fn dot_size(mut self, size: f32) -> Self {
+        self.style.dot_size = size;
+        self
+    }
+
+    /// Set what clicking the label does
+    pub fn label_click(mut self, label_click: LabelClick) -> Self {
+        self.label_click = label_click;
+        self
+    }
+
+    /// Set the radio button's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
+    /// The area that responds to the pointer
+    fn hit_area(&self) -> Rect {
+        hit_area(self.bounds.get(), self.style.size, self.label_click)
+    }
+
     /// Get the selected state signal
     pub fn selected_signal(&self) -> &Signal<bool> {
         &self.selected
@@ -443,15 +735,13 @@ impl Widget for RadioButton {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         let radio_size = self.style.size;
-        let label_width = if let Some(ref label) = self.label {
-            label.len() as f32 * 8.0 + 8.0
-        } else {
-            0.0
-        };
-
-        let total_width = radio_size + label_width;
+        let total_width = radio_size + label_width(self.label.as_deref());
         let height = radio_size.max(20.0);
 
         constraints.constrain(Size::new(total_width, height))
@@ -465,73 +755,119 @@ impl Widget for RadioButton {
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         // Draw radio background (circle)
-        let radio_y = bounds.y + (bounds.height - self.style.size) / 2.0;
+        let circle = indicator_rect(bounds, self.style.size);
         let center = (
-            bounds.x + self.style.size / 2.0,
-            radio_y + self.style.size / 2.0,
+            circle.x + circle.width / 2.0,
+            circle.y + circle.height / 2.0,
         );
         let radius = self.style.size / 2.0;
 
         let state = self.control.state();
-        let base_color = if !self.enabled {
-            color_from(self.style.disabled_color)
-        } else if self.is_selected() {
+        let selected = self.is_selected();
+        let base_color = if selected {
             color_from(self.style.background_color)
         } else {
             Color::WHITE
         };
 
-        let hover_color = if self.enabled {
-            color_from(self.style.hover_color)
+        let bg_color = if self.enabled {
+            let target_color = match state {
+                WidgetState::Hovered | WidgetState::Pressed => color_from(self.style.hover_color),
+                _ => base_color,
+            };
+            blend_color(base_color, target_color, self.control.interaction_factor())
         } else {
-            base_color
+            self.palette.disabled(base_color)
         };
 
-        let target_color = match state {
-            WidgetState::Hovered | WidgetState::Pressed => hover_color,
-            WidgetState::Disabled => color_from(self.style.disabled_color),
-            _ => base_color,
+        let inner_radius = if selected {
+            radius
+        } else {
+            // An unselected circle shows its border as a ring
+            let border_color = if self.enabled {
+                color_from(self.style.border_color)
+            } else {
+                self.palette.disabled(color_from(self.style.border_color))
+            };
+            batch.add_circle(center, radius, border_color, 32, Transform::default());
+            (radius - self.style.border_width).max(0.0)
         };
-        let bg_color = blend_color(base_color, target_color, self.control.interaction_factor());
-
-        batch.add_circle(
-            center,
-            radius,
-            bg_color,
-            16,
-            strato_core::types::Transform::default(),
-        );
+        batch.add_circle(center, inner_radius, bg_color, 32, Transform::default());
+
+        if selected && self.style.dot_size > 0.0 {
+            let dot_color = if self.enabled {
+                color_from(self.style.dot_color)
+            } else {
+                self.palette.disabled(color_from(self.style.dot_color))
+            };
+            let dot_radius = self.style.dot_size.min(self.style.size) / 2.0;
+            batch.add_circle(center, dot_radius, dot_color, 32, Transform::default());
+        }
 
-        // Draw label
         if let Some(label) = &self.label {
-            let text_x = bounds.x + self.style.size + 8.0;
-            let text_y = bounds.y + bounds.height / 2.0 - 7.0; // approx center
-            let mut label_color = Color::BLACK;
-            if !self.enabled {
-                label_color.a = 0.6;
-            }
-            batch.add_text(label.clone(), (text_x, text_y), label_color, 14.0, 0.0);
+            draw_label(
+                batch,
+                bounds,
+                self.style.size,
+                label,
+                self.enabled,
+                &self.palette,
+            );
         }
     }
 
     fn update(&mut self, ctx: &WidgetContext) {
         self.control.update(ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.control.state() == WidgetState::Focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.control.focus();
+        } else {
+            self.control.blur();
+        }
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.hit_area()
+            .contains(point)
+            .then_some(CursorIcon::Pointer)
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        if let EventResult::Handled = self.control.handle_pointer_event(event, self.bounds.get()) {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
+        if let EventResult::Handled = self.control.handle_pointer_event(event, self.hit_area()) {
             if matches!(event, Event::MouseUp(_)) {
-                if self.enabled {
-                    self.select();
-                }
+                self.select();
             }
             return EventResult::Handled;
         }
 
         if let EventResult::Handled = self.control.handle_keyboard_activation(event) {
-            if matches!(event, Event::KeyUp(_)) && self.enabled {
+            if matches!(event, Event::KeyUp(_)) {
                 self.select();
             }
             return EventResult::Handled;
@@ -549,7 +885,9 @@ impl Widget for RadioButton {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(self.clone())
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        Box::new(widget)
     }
 
     // Removed state method as it's not part of Widget trait
@@ -558,6 +896,9 @@ impl Widget for RadioButton {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::WidgetTester;
+    use parking_lot::Mutex;
+    use strato_renderer::batch::DrawCommand;
 
     #[test]
     fn test_checkbox_creation() {
@@ -597,4 +938,84 @@ mod tests {
         radio.deselect();
         assert!(!radio.is_selected());
     }
+
+    #[test]
+    fn test_label_click_behavior() {
+        // The label starts right of the 20px box and its 8px gap
+        let mut tester = WidgetTester::new(Checkbox::new().label("Subscribe"), 200.0, 40.0);
+        tester.click(40.0, 20.0);
+        assert!(tester.root().is_checked());
+
+        let checkbox = Checkbox::new()
+            .label("Subscribe")
+            .label_click(LabelClick::Ignore);
+        let mut tester = WidgetTester::new(checkbox, 200.0, 40.0);
+        tester.click(40.0, 20.0);
+        assert!(!tester.root().is_checked());
+        tester.click(10.0, 20.0);
+        assert!(tester.root().is_checked());
+    }
+
+    #[test]
+    fn test_custom_glyph_draws_into_box_when_checked() {
+        let drawn = Arc::new(Mutex::new(Vec::new()));
+        let recorded = drawn.clone();
+        let checkbox =
+            Checkbox::new()
+                .size(24.0)
+                .check_color(Color::RED)
+                .glyph(CheckGlyph::custom(move |_, rect, color| {
+                    recorded.lock().push((rect, color));
+                }));
+
+        let mut tester = WidgetTester::new(checkbox, 24.0, 24.0);
+        assert!(drawn.lock().is_empty());
+
+        tester.click(12.0, 12.0);
+        tester.pump();
+        assert_eq!(
+            drawn.lock().last(),
+            Some(&(Rect::new(0.0, 0.0, 24.0, 24.0), Color::RED))
+        );
+    }
+
+    #[test]
+    fn test_radio_dot_size() {
+        let radio = RadioButton::new("group1", "value1")
+            .size(30.0)
+            .dot_size(12.0)
+            .selected(true);
+        let tester = WidgetTester::new(radio, 30.0, 30.0);
+
+        let radii: Vec<f32> = tester
+            .frame()
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Circle { radius, .. } => Some(*radius),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(radii, vec![15.0, 6.0]);
+    }
+
+    #[test]
+    fn test_disabled_label_fades_with_the_theme() {
+        let mut theme = crate::theme::Theme::default();
+        theme.colors.disabled_opacity = 0.25;
+        let checkbox = Checkbox::new().label("Remember me").enabled(false);
+        let mut tester = WidgetTester::new(checkbox, 200.0, 30.0).with_theme(theme);
+        tester.advance(0.0);
+
+        let alphas: Vec<f32> = tester
+            .frame()
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Text { color, .. } => Some(color.a),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(alphas, vec![0.25]);
+    }
 }
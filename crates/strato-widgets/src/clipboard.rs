@@ -0,0 +1,64 @@
+//! Clipboard access for widgets
+//!
+//! Widgets read and write text through [`clipboard`]. Until a platform
+//! installs a system clipboard with [`set_clipboard`], an in-process
+//! [`MemoryClipboard`] is used, so copy and paste work between widgets of the
+//! same application.
+
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::{Mutex, RwLock};
+
+/// A place to copy text to and paste it from
+pub trait Clipboard: Send + Sync {
+    /// The text on the clipboard, if any
+    fn text(&self) -> Option<String>;
+
+    /// Replace the clipboard contents
+    fn set_text(&self, text: String);
+}
+
+impl std::fmt::Debug for dyn Clipboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Clipboard")
+    }
+}
+
+/// Clipboard that lives only as long as the process
+#[derive(Debug, Default)]
+pub struct MemoryClipboard {
+    text: Mutex<Option<String>>,
+}
+
+impl MemoryClipboard {
+    /// Create an empty clipboard
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Clipboard for MemoryClipboard {
+    fn text(&self) -> Option<String> {
+        self.text.lock().clone()
+    }
+
+    fn set_text(&self, text: String) {
+        *self.text.lock() = Some(text);
+    }
+}
+
+static CLIPBOARD: OnceLock<RwLock<Arc<dyn Clipboard>>> = OnceLock::new();
+
+fn slot() -> &'static RwLock<Arc<dyn Clipboard>> {
+    CLIPBOARD.get_or_init(|| RwLock::new(Arc::new(MemoryClipboard::new())))
+}
+
+/// The clipboard widgets copy to and paste from
+pub fn clipboard() -> Arc<dyn Clipboard> {
+    slot().read().clone()
+}
+
+/// Install the clipboard used by all widgets, e.g. the system clipboard
+pub fn set_clipboard(clipboard: impl Clipboard + 'static) {
+    *slot().write() = Arc::new(clipboard);
+}
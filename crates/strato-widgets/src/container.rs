@@ -1,26 +1,92 @@
 //! Container widget for layout and styling
 
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::gesture::PressGestures;
+use crate::reconcile::ChildSlots;
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, hit, layout_child, register_bounds, Widget,
+    WidgetContext, WidgetId,
+};
 use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
 use strato_core::{
     event::{Event, EventResult},
-    layout::{Constraints, EdgeInsets, Layout, Size},
+    hit_test::hit_test,
+    layout::{Constraints, EdgeInsets, Layout, LayoutCache, Size},
     state::Signal,
     types::{BorderRadius, Color, Point, Rect, Shadow},
+    window::CursorIcon,
     Transform,
 };
 use strato_renderer::batch::RenderBatch;
 
+/// What a container does with content that doesn't fit inside it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Draw the content past the container's edges
+    #[default]
+    Visible,
+    /// Clip the content to the container's bounds, margin excluded
+    ///
+    /// The clip is a rectangle and ignores the border radius.
+    Hidden,
+}
+
+/// How a container sizes itself along one axis
+///
+/// Sizes exclude the margin. Whatever the behavior, the size is kept within
+/// the constraints the parent lays the container out with, so under tight
+/// constraints, like those of the window's root widget, a container always
+/// takes the size it is given.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SizeBehavior {
+    /// Wrap the child and the padding around it
+    #[default]
+    FitContent,
+    /// Take all the space the parent offers, or fit the content when that
+    /// space is unbounded, like along a scroll view's scrolling axis
+    Fill,
+    /// Take exactly this size; the child is laid out within it
+    Fixed(f32),
+}
+
+impl SizeBehavior {
+    /// The size along the axis for content of size `content` when at most
+    /// `available` space is offered
+    fn resolve(self, content: f32, available: f32) -> f32 {
+        match self {
+            SizeBehavior::FitContent => content,
+            SizeBehavior::Fill if available.is_finite() => available,
+            SizeBehavior::Fill => content,
+            SizeBehavior::Fixed(size) => size,
+        }
+    }
+
+    /// The most space the child may take along the axis
+    fn child_max(self, available: f32, padding: f32) -> f32 {
+        match self {
+            SizeBehavior::Fixed(size) => (size.min(available) - padding).max(0.0),
+            _ => (available - padding).max(0.0),
+        }
+    }
+}
+
 /// Container widget for grouping and styling child widgets
+///
+/// Without a size set, a container fits its content on both axes, see
+/// [`SizeBehavior`]. Use [`width_behavior`](Self::width_behavior) and
+/// [`height_behavior`](Self::height_behavior) to make it fill its parent
+/// instead.
 pub struct Container {
     id: WidgetId,
     child: Option<Box<dyn Widget>>,
     style: ContainerStyle,
     constraints: Option<Constraints>,
-    on_click: Option<Box<dyn Fn() + Send + Sync>>,
-    on_hover: Option<Box<dyn Fn(bool) + Send + Sync>>,
+    on_click: Option<Arc<dyn Fn(Point) + Send + Sync>>,
+    on_hover: Option<Arc<dyn Fn(bool) + Send + Sync>>,
+    gestures: PressGestures,
     state: Signal<ContainerState>,
-    bounds: Signal<Rect>,
+    layout_cache: LayoutCache,
 }
 
 impl std::fmt::Debug for Container {
@@ -30,10 +96,10 @@ impl std::fmt::Debug for Container {
             .field("child", &self.child)
             .field("style", &self.style)
             .field("constraints", &self.constraints)
-            .field("on_click", &self.on_click.as_ref().map(|_| "Fn()"))
+            .field("on_click", &self.on_click.as_ref().map(|_| "Fn(Point)"))
             .field("on_hover", &self.on_hover.as_ref().map(|_| "Fn(bool)"))
+            .field("gestures", &self.gestures)
             .field("state", &self.state)
-            .field("bounds", &self.bounds)
             .finish()
     }
 }
@@ -54,8 +120,9 @@ impl Container {
             constraints: None,
             on_click: None,
             on_hover: None,
+            gestures: PressGestures::new(),
             state: Signal::new(ContainerState::default()),
-            bounds: Signal::new(Rect::default()),
+            layout_cache: LayoutCache::new(),
         }
     }
 
@@ -65,6 +132,18 @@ impl Container {
         self
     }
 
+    /// Set an already boxed child widget
+    pub fn child_boxed(mut self, child: Box<dyn Widget>) -> Self {
+        self.child = Some(child);
+        self
+    }
+
+    /// Set what happens to content that overflows the container
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        self.style.overflow = overflow;
+        self
+    }
+
     /// Set padding
     pub fn padding(mut self, padding: f32) -> Self {
         self.style.padding = EdgeInsets::all(padding);
@@ -115,20 +194,32 @@ impl Container {
 
     /// Set width
     pub fn width(mut self, width: f32) -> Self {
-        self.style.width = Some(width);
+        self.style.width = SizeBehavior::Fixed(width);
         self
     }
 
     /// Set height
     pub fn height(mut self, height: f32) -> Self {
-        self.style.height = Some(height);
+        self.style.height = SizeBehavior::Fixed(height);
         self
     }
 
     /// Set both width and height
     pub fn size(mut self, width: f32, height: f32) -> Self {
-        self.style.width = Some(width);
-        self.style.height = Some(height);
+        self.style.width = SizeBehavior::Fixed(width);
+        self.style.height = SizeBehavior::Fixed(height);
+        self
+    }
+
+    /// Set how the width is chosen, [`SizeBehavior::FitContent`] by default
+    pub fn width_behavior(mut self, behavior: SizeBehavior) -> Self {
+        self.style.width = behavior;
+        self
+    }
+
+    /// Set how the height is chosen, [`SizeBehavior::FitContent`] by default
+    pub fn height_behavior(mut self, behavior: SizeBehavior) -> Self {
+        self.style.height = behavior;
         self
     }
 
@@ -149,26 +240,78 @@ impl Container {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_click = Some(Box::new(handler));
+        self.on_click = Some(Arc::new(move |_| handler()));
+        self
+    }
+
+    /// Set a click handler that receives the click position
+    ///
+    /// The position is relative to the top-left corner of the content box,
+    /// inside the margin and padding, so a click on the first pixel of the
+    /// child arrives as (0, 0). Clicks on the padding have negative
+    /// coordinates or ones past the content size.
+    pub fn on_click_at<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(Point) + Send + Sync + 'static,
+    {
+        self.on_click = Some(Arc::new(handler));
+        self
+    }
+
+    /// Set a handler for holding the container down for `duration`
+    ///
+    /// A click that turned into a long press doesn't also click.
+    pub fn on_long_press<F>(mut self, duration: Duration, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.gestures.set_long_press(duration, handler);
+        self
+    }
+
+    /// Set a handler repeated while the container is held down
+    ///
+    /// It first runs after `initial_delay`, then every `interval` until the
+    /// pointer is released or leaves the container. Pair it with
+    /// [`on_click`](Self::on_click) so a tap acts once; a hold that repeated
+    /// doesn't also click.
+    pub fn on_press_repeat<F>(
+        mut self,
+        initial_delay: Duration,
+        interval: Duration,
+        handler: F,
+    ) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.gestures.set_repeat(initial_delay, interval, handler);
         self
     }
 
+    /// `point` relative to the top-left corner of the content box
+    fn to_content(&self, point: Point) -> Point {
+        let bounds = hit_test()
+            .bounds(strato_core::widget::WidgetId(self.id))
+            .unwrap_or_default();
+        let margin = self.style.margin;
+        let padding = self.style.padding;
+        Point::new(
+            point.x - bounds.x - margin.left - padding.left,
+            point.y - bounds.y - margin.top - padding.top,
+        )
+    }
+
     /// Set hover handler
     pub fn on_hover<F>(mut self, handler: F) -> Self
     where
         F: Fn(bool) + Send + Sync + 'static,
     {
-        self.on_hover = Some(Box::new(handler));
+        self.on_hover = Some(Arc::new(handler));
         self
     }
-}
-
-impl Widget for Container {
-    fn id(&self) -> WidgetId {
-        self.id
-    }
 
-    fn layout(&mut self, constraints: Constraints) -> Size {
+    /// Lay out the child and size the container around it
+    fn compute_layout(&mut self, constraints: Constraints) -> Size {
         let constraints = self.constraints.unwrap_or(constraints);
 
         // Apply margin to constraints
@@ -182,41 +325,52 @@ impl Widget for Container {
 
         // Apply padding to child constraints
         let padding = self.style.padding;
+        let (width_behavior, height_behavior) = (self.style.width, self.style.height);
         let child_constraints = Constraints {
             min_width: (inner_constraints.min_width - padding.horizontal()).max(0.0),
-            max_width: (inner_constraints.max_width - padding.horizontal()).max(0.0),
+            max_width: width_behavior.child_max(inner_constraints.max_width, padding.horizontal()),
             min_height: (inner_constraints.min_height - padding.vertical()).max(0.0),
-            max_height: (inner_constraints.max_height - padding.vertical()).max(0.0),
+            max_height: height_behavior.child_max(inner_constraints.max_height, padding.vertical()),
         };
 
         // Calculate child size
         let child_size = if let Some(child) = &mut self.child {
-            child.layout(child_constraints)
+            layout_child(&self.layout_cache, child.as_mut(), child_constraints)
         } else {
             Size::zero()
         };
 
         // Calculate container size
-        let mut width = child_size.width + padding.horizontal();
-        let mut height = child_size.height + padding.vertical();
+        let width = width_behavior.resolve(
+            child_size.width + padding.horizontal(),
+            inner_constraints.max_width,
+        ) + margin.horizontal();
+        let height = height_behavior.resolve(
+            child_size.height + padding.vertical(),
+            inner_constraints.max_height,
+        ) + margin.vertical();
 
-        // Apply fixed dimensions if specified
-        if let Some(fixed_width) = self.style.width {
-            width = fixed_width;
-        }
-        if let Some(fixed_height) = self.style.height {
-            height = fixed_height;
-        }
+        // Constrain to limits
+        constraints.constrain(Size::new(width, height))
+    }
+}
+
+impl Widget for Container {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
 
-        // Add margin
-        width += margin.horizontal();
-        height += margin.vertical();
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
 
-        // Constrain to limits
-        Size::new(
-            width.clamp(constraints.min_width, constraints.max_width),
-            height.clamp(constraints.min_height, constraints.max_height),
-        )
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || self.compute_layout(constraints))
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
     }
 
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
@@ -226,7 +380,7 @@ impl Widget for Container {
             layout.size.width,
             layout.size.height,
         );
-        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         let margin = self.style.margin;
         let padding = self.style.padding;
@@ -273,18 +427,36 @@ impl Widget for Container {
                     content_rect.height - padding.vertical(),
                 ),
             );
+            let clip = self.style.overflow == Overflow::Hidden;
+            if clip {
+                batch.push_clip(content_rect);
+            }
             child.render(batch, child_layout);
+            if clip {
+                batch.pop_clip();
+            }
         }
     }
 
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        (self.on_click.is_some() && hit(self.id, point)).then_some(CursorIcon::Pointer)
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        self.gestures.advance(ctx.delta_time);
+    }
+
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        let held_gesture = hit_test()
+            .bounds(strato_core::widget::WidgetId(self.id))
+            .is_some_and(|bounds| self.gestures.handle_pointer_event(event, bounds));
+
         // Handle interactions if callbacks are present
         if self.on_click.is_some() || self.on_hover.is_some() {
             match event {
                 Event::MouseMove(mouse_event) => {
-                    let bounds = self.bounds.get();
                     let point = Point::new(mouse_event.position.x, mouse_event.position.y);
-                    let is_hovered = bounds.contains(point);
+                    let is_hovered = hit(self.id, point);
                     let mut state = self.state.get();
 
                     if is_hovered != state.hovered {
@@ -299,25 +471,23 @@ impl Widget for Container {
                     }
                 }
                 Event::MouseDown(mouse_event) => {
-                    let bounds = self.bounds.get();
                     let point = Point::new(mouse_event.position.x, mouse_event.position.y);
-                    if bounds.contains(point) {
+                    if hit(self.id, point) {
                         let mut state = self.state.get();
                         state.pressed = true;
                         self.state.set(state);
                     }
                 }
                 Event::MouseUp(mouse_event) => {
-                    let bounds = self.bounds.get();
                     let point = Point::new(mouse_event.position.x, mouse_event.position.y);
                     let mut state = self.state.get();
 
                     if state.pressed {
                         state.pressed = false;
                         self.state.set(state);
-                        if bounds.contains(point) {
+                        if hit(self.id, point) && !held_gesture {
                             if let Some(handler) = &self.on_click {
-                                handler();
+                                handler(self.to_content(point));
                                 // If we clicked, we probably handled it. But child might have handled it?
                                 // If child handled it, its result would be Handled.
                             }
@@ -330,24 +500,22 @@ impl Widget for Container {
 
         // Delegate to child FIRST to allow inner interactive elements to work
         if let Some(child) = &mut self.child {
-            let child_result = child.handle_event(event);
+            let child_result = dispatch_event(child.as_mut(), event);
             if child_result == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
 
         // If child didn't handle it, AND we have interactions, check if we should handle it
-        if self.on_click.is_some() {
+        if self.on_click.is_some() || !self.gestures.is_empty() {
             match event {
                 Event::MouseDown(e) => {
-                    let bounds = self.bounds.get();
-                    if bounds.contains(Point::new(e.position.x, e.position.y)) {
+                    if hit(self.id, Point::new(e.position.x, e.position.y)) {
                         return EventResult::Handled;
                     }
                 }
                 Event::MouseUp(e) => {
-                    let bounds = self.bounds.get();
-                    if bounds.contains(Point::new(e.position.x, e.position.y)) {
+                    if hit(self.id, Point::new(e.position.x, e.position.y)) {
                         // And was pressed logic...
                         return EventResult::Handled;
                     }
@@ -375,6 +543,25 @@ impl Widget for Container {
         }
     }
 
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        let new = new.as_any().downcast_ref::<Container>()?;
+        // Callbacks can't be compared, so the new ones are always taken
+        self.on_click = new.on_click.clone();
+        self.on_hover = new.on_hover.clone();
+        self.gestures.set_handlers_from(&new.gestures);
+        let changed = self.style != new.style || self.constraints != new.constraints;
+        if changed {
+            self.style = new.style.clone();
+            self.constraints = new.constraints;
+            self.layout_cache.mark_dirty();
+        }
+        Some(changed)
+    }
+
+    fn child_slots(&mut self) -> Option<ChildSlots<'_>> {
+        Some(ChildSlots::Optional(&mut self.child))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -385,14 +572,15 @@ impl Widget for Container {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Container {
-            id: generate_id(),
+            id: clone_id(self.id),
             child: self.child.as_ref().map(|c| c.clone_widget()),
             style: self.style.clone(),
             constraints: self.constraints,
-            on_click: None,
-            on_hover: None,
+            on_click: self.on_click.clone(),
+            on_hover: self.on_hover.clone(),
+            gestures: self.gestures.clone(),
             state: Signal::new(self.state.get()),
-            bounds: Signal::new(self.bounds.get()),
+            layout_cache: LayoutCache::new(),
         })
     }
 }
@@ -404,7 +592,7 @@ impl Default for Container {
 }
 
 /// Container style configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ContainerStyle {
     pub background_color: Color,
     pub border_color: Color,
@@ -413,8 +601,9 @@ pub struct ContainerStyle {
     pub padding: EdgeInsets,
     pub margin: EdgeInsets,
     pub shadow: Option<Shadow>,
-    pub width: Option<f32>,
-    pub height: Option<f32>,
+    pub width: SizeBehavior,
+    pub height: SizeBehavior,
+    pub overflow: Overflow,
 }
 
 impl Default for ContainerStyle {
@@ -427,8 +616,9 @@ impl Default for ContainerStyle {
             padding: EdgeInsets::all(0.0),
             margin: EdgeInsets::all(0.0),
             shadow: None,
-            width: None,
-            height: None,
+            width: SizeBehavior::FitContent,
+            height: SizeBehavior::FitContent,
+            overflow: Overflow::Visible,
         }
     }
 }
@@ -444,8 +634,9 @@ impl ContainerStyle {
             padding: EdgeInsets::all(16.0),
             margin: EdgeInsets::all(8.0),
             shadow: Some(Shadow::drop(4.0)),
-            width: None,
-            height: None,
+            width: SizeBehavior::FitContent,
+            height: SizeBehavior::FitContent,
+            overflow: Overflow::Visible,
         }
     }
 
@@ -459,8 +650,154 @@ impl ContainerStyle {
             padding: EdgeInsets::all(12.0),
             margin: EdgeInsets::all(0.0),
             shadow: None,
-            width: None,
-            height: None,
+            width: SizeBehavior::FitContent,
+            height: SizeBehavior::FitContent,
+            overflow: Overflow::Visible,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::WidgetTester;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use strato_core::event::MouseButton;
+
+    #[test]
+    fn test_click_position_is_relative_to_content_box() {
+        let clicked = Arc::new(Mutex::new(None));
+        let recorder = clicked.clone();
+        let container = Container::new()
+            .margin(5.0)
+            .padding(10.0)
+            .on_click_at(move |point| *recorder.lock() = Some(point));
+
+        let mut tester = WidgetTester::new(container, 200.0, 100.0);
+        tester.click(45.0, 35.0);
+        assert_eq!(*clicked.lock(), Some(Point::new(30.0, 20.0)));
+
+        tester.click(15.0, 15.0);
+        assert_eq!(*clicked.lock(), Some(Point::new(0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_long_press_replaces_click() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let (clicks, long_presses) = (events.clone(), events.clone());
+        let container = Container::new()
+            .on_click(move || clicks.lock().push("click"))
+            .on_long_press(Duration::from_millis(500), move || {
+                long_presses.lock().push("long press")
+            });
+        let mut tester = WidgetTester::new(container, 100.0, 100.0);
+
+        tester.press(50.0, 50.0, MouseButton::Left);
+        tester.advance_by(0.1, 6);
+        tester.release(50.0, 50.0, MouseButton::Left);
+        tester.click(50.0, 50.0);
+
+        assert_eq!(*events.lock(), ["long press", "click"]);
+    }
+
+    #[test]
+    fn test_plain_click_handler_still_fires() {
+        let clicks = Arc::new(Mutex::new(0));
+        let counter = clicks.clone();
+        let container = Container::new().on_click(move || *counter.lock() += 1);
+
+        let mut tester = WidgetTester::new(container, 100.0, 100.0);
+        tester.click(50.0, 50.0);
+        assert_eq!(*clicks.lock(), 1);
+    }
+
+    #[test]
+    fn test_hidden_overflow_clips_child_to_bounds() {
+        use strato_renderer::batch::DrawCommand;
+
+        let container = |overflow| {
+            Container::new()
+                .margin(5.0)
+                .overflow(overflow)
+                .child(Container::new().size(500.0, 500.0))
+        };
+
+        let tester = WidgetTester::new(container(Overflow::Hidden), 100.0, 50.0);
+        let commands = &tester.frame().commands;
+        assert!(matches!(
+            commands.first(),
+            Some(DrawCommand::PushClip(clip)) if *clip == Rect::new(5.0, 5.0, 90.0, 40.0)
+        ));
+        assert!(matches!(commands.last(), Some(DrawCommand::PopClip)));
+
+        let tester = WidgetTester::new(container(Overflow::Visible), 100.0, 50.0);
+        assert!(!tester
+            .frame()
+            .commands
+            .iter()
+            .any(|command| matches!(command, DrawCommand::PushClip(_))));
+    }
+
+    /// A padded container around a 40x20 child
+    fn sized(width: SizeBehavior, height: SizeBehavior) -> Container {
+        Container::new()
+            .padding(5.0)
+            .margin(2.0)
+            .width_behavior(width)
+            .height_behavior(height)
+            .child(Container::new().size(40.0, 20.0))
+    }
+
+    #[test]
+    fn test_size_behaviors_under_loose_constraints() {
+        use SizeBehavior::*;
+        let loose = Constraints::loose(300.0, 200.0);
+
+        // Content plus padding plus margin
+        let mut fit = sized(FitContent, FitContent);
+        assert_eq!(fit.layout(loose), Size::new(54.0, 34.0));
+
+        let mut fill = sized(Fill, FitContent);
+        assert_eq!(fill.layout(loose), Size::new(300.0, 34.0));
+        let mut fill = sized(FitContent, Fill);
+        assert_eq!(fill.layout(loose), Size::new(54.0, 200.0));
+
+        let mut fixed = sized(Fixed(100.0), Fixed(30.0));
+        assert_eq!(fixed.layout(loose), Size::new(104.0, 34.0));
+
+        // Fill falls back to fitting the content when space is unbounded
+        let mut fill = sized(Fill, Fill);
+        assert_eq!(fill.layout(Constraints::unbounded()), Size::new(54.0, 34.0));
+    }
+
+    #[test]
+    fn test_size_behaviors_under_tight_constraints() {
+        use SizeBehavior::*;
+        let tight = Constraints::tight(300.0, 200.0);
+        for (width, height) in [
+            (FitContent, FitContent),
+            (Fill, Fill),
+            (Fixed(100.0), Fixed(30.0)),
+        ] {
+            assert_eq!(
+                sized(width, height).layout(tight),
+                Size::new(300.0, 200.0),
+                "{width:?} x {height:?}"
+            );
         }
     }
+
+    #[test]
+    fn test_fixed_size_bounds_the_child() {
+        let mut container = Container::new()
+            .padding(10.0)
+            .width(100.0)
+            .child(Container::new().width_behavior(SizeBehavior::Fill));
+        let size = container.layout(Constraints::loose(500.0, 500.0));
+        container.render(&mut RenderBatch::new(), Layout::new(glam::Vec2::ZERO, size));
+        let tree = crate::widget::layout_tree(&container);
+        let child = &tree.nodes()[1];
+        assert_eq!(child.bounds.map(|bounds| bounds.width), Some(80.0));
+    }
 }
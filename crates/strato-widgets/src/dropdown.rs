@@ -1,13 +1,18 @@
 //! Dropdown and Select widgets implementation for StratoUI
 
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::text::{measure_line_metrics, measure_text_width};
+use crate::theme::ColorPalette;
+use crate::widget::{
+    clone_id, generate_id, next_overlay_order, register_bounds, Widget, WidgetContext, WidgetId,
+};
 use strato_core::{
     event::{Event, EventResult, KeyCode, KeyboardEvent, MouseButton, MouseEvent},
     layout::{Constraints, Layout, Size},
     state::Signal,
     types::Transform,
-    types::{Color, Rect},
+    types::{BorderRadius, Color, Point, Rect},
     vdom::VNode,
+    window::CursorIcon,
 };
 use strato_renderer::batch::RenderBatch;
 
@@ -18,15 +23,28 @@ pub struct Dropdown<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug>
     options: Vec<DropdownOption<T>>,
     selected_index: Signal<Option<usize>>,
     is_open: Signal<bool>,
+    overlay_order: Signal<u64>,
     bounds: Signal<Rect>,
+    /// Where the open list was last drawn, for hit testing
+    list_rect: Signal<Rect>,
+    scroll_offset: Signal<f32>,
+    pointer_in_list: Signal<bool>,
+    focused: Signal<bool>,
+    /// Option highlighted in the open list, as an index into all options
+    highlighted: Signal<Option<usize>>,
     width: f32,
     height: f32,
     max_height: f32,
+    match_width: bool,
     enabled: bool,
+    readonly: bool,
     searchable: bool,
+    tab_index: Option<i32>,
     search_text: Signal<String>,
     placeholder: String,
     style: DropdownStyle,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
 }
 
 /// Option in a dropdown
@@ -35,6 +53,17 @@ pub struct DropdownOption<T: Clone + PartialEq + std::fmt::Display + std::fmt::D
     pub value: T,
     pub label: String,
     pub enabled: bool,
+    /// Label of the group the option is listed under, see
+    /// [`DropdownOption::group`]
+    pub group: Option<String>,
+}
+
+/// A row of the open list
+enum ListRow<'a, T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> {
+    /// Label of the group the following options belong to
+    Header(&'a str),
+    /// An option and its index into all options
+    Option(usize, &'a DropdownOption<T>),
 }
 
 /// Styling options for dropdown
@@ -84,6 +113,7 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> DropdownOption<
             value,
             label,
             enabled: true,
+            group: None,
         }
     }
 
@@ -103,6 +133,39 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> DropdownOption<
         self.enabled = enabled;
         self
     }
+
+    /// List `options` under a header reading `label`
+    ///
+    /// Add the result with [`Dropdown::options`]. The header can't be
+    /// selected or highlighted, and it is left out while searching when none
+    /// of its options match:
+    ///
+    /// ```
+    /// use strato_widgets::dropdown::{Dropdown, DropdownOption};
+    ///
+    /// let fonts = Dropdown::new()
+    ///     .options(DropdownOption::group(
+    ///         "Recent",
+    ///         vec![DropdownOption::from_value("Inter")],
+    ///     ))
+    ///     .options(DropdownOption::group(
+    ///         "All",
+    ///         vec![
+    ///             DropdownOption::from_value("Fira Sans"),
+    ///             DropdownOption::from_value("Inter"),
+    ///         ],
+    ///     ));
+    /// ```
+    pub fn group(label: impl Into<String>, options: Vec<DropdownOption<T>>) -> Vec<Self> {
+        let label = label.into();
+        options
+            .into_iter()
+            .map(|option| Self {
+                group: Some(label.clone()),
+                ..option
+            })
+            .collect()
+    }
 }
 
 impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
@@ -113,15 +176,25 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
             options: Vec::new(),
             selected_index: Signal::new(None),
             is_open: Signal::new(false),
+            overlay_order: Signal::new(0),
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            list_rect: Signal::new(Rect::default()),
+            scroll_offset: Signal::new(0.0),
+            pointer_in_list: Signal::new(false),
+            focused: Signal::new(false),
+            highlighted: Signal::new(None),
             width: 200.0,
             height: 36.0,
             max_height: 200.0,
+            match_width: true,
             enabled: true,
+            readonly: false,
             searchable: false,
+            tab_index: None,
             search_text: Signal::new(String::new()),
             placeholder: "Select an option...".to_string(),
             style: DropdownStyle::default(),
+            palette: ColorPalette::default(),
         }
     }
 
@@ -172,18 +245,55 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
         self
     }
 
-    /// Set maximum dropdown height
+    /// Set maximum dropdown height; longer lists scroll
     pub fn max_height(mut self, max_height: f32) -> Self {
         self.max_height = max_height;
         self
     }
 
+    /// Whether the open list is as wide as the control (the default)
+    ///
+    /// With `false` the list is sized to its widest option instead.
+    pub fn match_width(mut self, match_width: bool) -> Self {
+        self.match_width = match_width;
+        self
+    }
+
     /// Set enabled state
+    ///
+    /// A disabled dropdown is drawn muted, never opens and ignores pointer
+    /// and keyboard input.
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.enabled = enabled;
+        if !enabled {
+            self.blur();
+        }
+        self
+    }
+
+    /// Set the dropdown's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
+    /// Set readonly state
+    ///
+    /// A readonly dropdown shows its value at full color but never opens,
+    /// so the selection can't be changed from the UI.
+    pub fn readonly(mut self, readonly: bool) -> Self {
+        self.readonly = readonly;
+        if readonly {
+            self.close();
+        }
         self
     }
 
+    /// Check if dropdown is readonly
+    pub fn is_readonly(&self) -> bool {
+        self.readonly
+    }
+
     /// Enable search functionality
     pub fn searchable(mut self, searchable: bool) -> Self {
         self.searchable = searchable;
@@ -225,9 +335,12 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
         self.is_open.get()
     }
 
-    /// Open the dropdown
+    /// Open the dropdown, highlighting the selected option
     pub fn open(&self) {
-        if self.enabled {
+        if self.enabled && !self.readonly && !self.is_open() {
+            self.overlay_order.set(next_overlay_order());
+            self.scroll_offset.set(0.0);
+            self.highlighted.set(self.selected_index.get());
             self.is_open.set(true);
         }
     }
@@ -235,6 +348,8 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
     /// Close the dropdown
     pub fn close(&self) {
         self.is_open.set(false);
+        self.highlighted.set(None);
+        self.pointer_in_list.set(false);
         self.search_text.set(String::new());
     }
 
@@ -247,6 +362,29 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
         }
     }
 
+    /// Check if the dropdown has keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    /// Give the dropdown keyboard focus
+    pub fn focus(&self) {
+        if self.enabled {
+            self.focused.set(true);
+        }
+    }
+
+    /// Remove keyboard focus, closing the list
+    pub fn blur(&self) {
+        self.focused.set(false);
+        self.close();
+    }
+
+    /// Index of the option highlighted in the open list
+    pub fn highlighted_index(&self) -> Option<usize> {
+        self.highlighted.get()
+    }
+
     /// Select an option by index
     pub fn select_index(&self, index: usize) {
         if index < self.options.len() && self.options[index].enabled {
@@ -270,8 +408,93 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
         }
     }
 
+    /// Rows of the open list: the filtered options, each run of options of
+    /// the same group preceded by the group's header
+    fn list_rows(&self) -> Vec<ListRow<'_, T>> {
+        let mut rows = Vec::new();
+        let mut current_group = None;
+        for (index, option) in self.filtered_options() {
+            let group = option.group.as_deref();
+            if group != current_group {
+                if let Some(label) = group {
+                    rows.push(ListRow::Header(label));
+                }
+                current_group = group;
+            }
+            rows.push(ListRow::Option(index, option));
+        }
+        rows
+    }
+
+    /// How far a grouped option's label is moved right of a header's
+    fn group_indent(&self) -> f32 {
+        self.style.padding
+    }
+
+    /// Where the open list goes for a control at `bounds`
+    ///
+    /// The list opens below the control unless it doesn't fit in `viewport`
+    /// and there is more room above. It is capped to the space available on
+    /// the chosen side and kept horizontally inside the viewport.
+    fn list_placement(&self, bounds: Rect, viewport: Option<Rect>) -> Rect {
+        let content_height = self.list_rows().len() as f32 * self.height;
+        let height = content_height.min(self.max_height);
+        let width = if self.match_width {
+            bounds.width
+        } else {
+            let label_width = |label: &str| measure_text_width(label, self.style.font_size, 0.0);
+            self.options
+                .iter()
+                .map(|option| match &option.group {
+                    Some(group) => {
+                        label_width(group).max(label_width(&option.label) + self.group_indent())
+                    }
+                    None => label_width(&option.label),
+                })
+                .fold(0.0, f32::max)
+                + self.style.padding * 2.0
+        };
+
+        let below = Rect::new(bounds.x, bounds.y + bounds.height, width, height);
+        let Some(viewport) = viewport else {
+            return below;
+        };
+
+        let space_below = (viewport.y + viewport.height - below.y).max(0.0);
+        let space_above = (bounds.y - viewport.y).max(0.0);
+        let x = bounds
+            .x
+            .min(viewport.x + viewport.width - width)
+            .max(viewport.x);
+        if height > space_below && space_above > space_below {
+            let height = height.min(space_above);
+            Rect::new(x, bounds.y - height, width, height)
+        } else {
+            Rect::new(x, below.y, width, height.min(space_below))
+        }
+    }
+
+    /// How far the open list can scroll
+    fn max_scroll(&self, list_height: f32) -> f32 {
+        (self.list_rows().len() as f32 * self.height - list_height).max(0.0)
+    }
+
+    /// Original index of the option under `point` in the open list, `None`
+    /// over a group header
+    fn option_at(&self, point: Point) -> Option<usize> {
+        let list = self.list_rect.get();
+        if !list.contains(point) {
+            return None;
+        }
+        let row = ((point.y - list.y + self.scroll_offset.get()) / self.height) as usize;
+        match self.list_rows().get(row) {
+            Some(ListRow::Option(index, _)) => Some(*index),
+            _ => None,
+        }
+    }
+
     /// Handle mouse events
-    fn handle_mouse_event(&self, event: &MouseEvent, bounds: Rect) -> EventResult {
+    fn handle_mouse_event(&self, event: &MouseEvent) -> EventResult {
         if !self.enabled {
             return EventResult::Ignored;
         }
@@ -279,22 +502,20 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
         if let Some(MouseButton::Left) = event.button {
             if self.is_open() {
                 // Check if clicking on an option
-                let dropdown_y = bounds.y + self.height;
-                let option_height = self.height;
-                let filtered_options = self.filtered_options();
-
-                if event.position.y >= dropdown_y {
-                    let option_index = ((event.position.y - dropdown_y) / option_height) as usize;
-                    if let Some((original_index, _)) = filtered_options.get(option_index) {
-                        self.select_index(*original_index);
-                        return EventResult::Handled;
-                    }
+                let point = Point::new(event.position.x, event.position.y);
+                if let Some(original_index) = self.option_at(point) {
+                    self.select_index(original_index);
+                    return EventResult::Handled;
                 }
 
-                // Click outside dropdown - close it
-                self.close();
+                // Click outside the list - close it. Clicks on group headers
+                // keep it open
+                if !self.list_rect.get().contains(point) {
+                    self.close();
+                }
             } else {
                 // Click on dropdown button - open it
+                self.focus();
                 self.toggle();
             }
             EventResult::Handled
@@ -304,98 +525,160 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug> Dropdown<T> {
     }
 
     /// Handle keyboard events
+    ///
+    /// A focused dropdown opens with Enter, Space or the arrow keys. While
+    /// open it consumes navigation keys: arrows move the highlight, Enter
+    /// selects it, Escape closes without changing the selection and typing a
+    /// letter jumps to the next option starting with it (or filters the list
+    /// when searchable).
     fn handle_keyboard_event(&self, event: &KeyboardEvent) -> EventResult {
-        if !self.enabled {
+        if !self.enabled || !self.is_focused() {
             return EventResult::Ignored;
         }
 
-        match event.key_code {
-            KeyCode::Escape => {
-                if self.is_open() {
-                    self.close();
-                    EventResult::Handled
-                } else {
-                    EventResult::Ignored
-                }
-            }
-            KeyCode::Enter => {
-                if !self.is_open() {
+        if !self.is_open() {
+            return match event.key_code {
+                KeyCode::Enter | KeyCode::Space | KeyCode::Down | KeyCode::Up => {
                     self.open();
                     EventResult::Handled
-                } else {
-                    EventResult::Ignored
                 }
+                _ => EventResult::Ignored,
+            };
+        }
+
+        match event.key_code {
+            KeyCode::Escape => self.close(),
+            KeyCode::Enter => match self.highlighted.get() {
+                Some(index) => self.select_index(index),
+                None => self.close(),
+            },
+            KeyCode::Tab => {
+                // Let focus move on
+                self.close();
+                return EventResult::Ignored;
             }
-            KeyCode::Down => {
-                if self.is_open() {
-                    let filtered = self.filtered_options();
-                    let current = self.selected_index.get();
-
-                    let next_index = if let Some(current_idx) = current {
-                        filtered
-                            .iter()
-                            .position(|(idx, _)| *idx == current_idx)
-                            .map(|pos| (pos + 1).min(filtered.len() - 1))
-                            .unwrap_or(0)
-                    } else {
-                        0
-                    };
-
-                    if let Some((original_idx, _)) = filtered.get(next_index) {
-                        self.selected_index.set(Some(*original_idx));
-                    }
-                } else {
-                    self.open();
-                }
-                EventResult::Handled
-            }
-            KeyCode::Up => {
-                if self.is_open() {
-                    let filtered = self.filtered_options();
-                    let current = self.selected_index.get();
-
-                    let prev_index = if let Some(current_idx) = current {
-                        filtered
-                            .iter()
-                            .position(|(idx, _)| *idx == current_idx)
-                            .map(|pos| pos.saturating_sub(1))
-                            .unwrap_or(0)
-                    } else {
-                        filtered.len().saturating_sub(1)
-                    };
-
-                    if let Some((original_idx, _)) = filtered.get(prev_index) {
-                        self.selected_index.set(Some(*original_idx));
-                    }
-                }
-                EventResult::Handled
-            }
-            KeyCode::Backspace if self.searchable && self.is_open() => {
+            KeyCode::Down => self.move_highlight(1),
+            KeyCode::Up => self.move_highlight(-1),
+            KeyCode::Backspace if self.searchable => {
                 let mut search = self.search_text.get();
                 search.pop();
                 self.search_text.set(search);
-                EventResult::Handled
+                self.highlight_first();
             }
-            _ => {
-                // Handle text input from KeyboardEvent
-                if let Some(ref text) = event.text {
-                    if self.searchable && self.is_open() {
-                        for ch in text.chars() {
-                            if !ch.is_control() {
-                                let mut search = self.search_text.get();
-                                search.push(ch);
-                                self.search_text.set(search);
-                            }
-                        }
-                        EventResult::Handled
-                    } else {
-                        EventResult::Ignored
-                    }
-                } else {
-                    EventResult::Ignored
-                }
+            _ if self.searchable => {
+                let Some(text) = event.text.as_ref() else {
+                    return EventResult::Ignored;
+                };
+                let mut search = self.search_text.get();
+                search.extend(text.chars().filter(|ch| !ch.is_control()));
+                self.search_text.set(search);
+                self.highlight_first();
             }
+            _ => match typed_char(event) {
+                Some(ch) => self.type_ahead(ch),
+                None => return EventResult::Ignored,
+            },
         }
+        EventResult::Handled
+    }
+
+    /// Move the highlight by `step` enabled options, stopping at the ends
+    ///
+    /// Group headers aren't options, so the highlight moves past them.
+    fn move_highlight(&self, step: isize) {
+        let enabled: Vec<usize> = self
+            .filtered_options()
+            .into_iter()
+            .filter(|(_, option)| option.enabled)
+            .map(|(index, _)| index)
+            .collect();
+        let Some(&last) = enabled.last() else {
+            return;
+        };
+
+        let next = match self
+            .highlighted
+            .get()
+            .and_then(|current| enabled.iter().position(|&index| index == current))
+        {
+            Some(position) => {
+                let position = position.saturating_add_signed(step);
+                enabled[position.min(enabled.len() - 1)]
+            }
+            None if step < 0 => last,
+            None => enabled[0],
+        };
+        self.set_highlight(next);
+    }
+
+    /// Highlight the first enabled option in the (filtered) list
+    fn highlight_first(&self) {
+        match self
+            .filtered_options()
+            .into_iter()
+            .find(|(_, option)| option.enabled)
+        {
+            Some((index, _)) => self.set_highlight(index),
+            None => self.highlighted.set(None),
+        }
+    }
+
+    /// Highlight the next enabled option after the current one whose label
+    /// starts with `ch`, wrapping around
+    fn type_ahead(&self, ch: char) {
+        let ch = ch.to_lowercase().collect::<String>();
+        let options = self.filtered_options();
+        let start = self
+            .highlighted
+            .get()
+            .and_then(|current| options.iter().position(|(index, _)| *index == current))
+            .map_or(0, |position| position + 1);
+
+        let found = options
+            .iter()
+            .cycle()
+            .skip(start)
+            .take(options.len())
+            .find(|(_, option)| option.enabled && option.label.to_lowercase().starts_with(&ch));
+        if let Some((index, _)) = found {
+            self.set_highlight(*index);
+        }
+    }
+
+    /// Highlight an option and scroll the list so it is visible
+    fn set_highlight(&self, index: usize) {
+        self.highlighted.set(Some(index));
+
+        let Some(row) = self
+            .list_rows()
+            .iter()
+            .position(|row| matches!(row, ListRow::Option(original, _) if *original == index))
+        else {
+            return;
+        };
+        let list_height = self.list_rect.get().height;
+        let top = row as f32 * self.height;
+        let scroll = self.scroll_offset.get();
+        if top < scroll {
+            self.scroll_offset.set(top);
+        } else if top + self.height > scroll + list_height {
+            self.scroll_offset.set(top + self.height - list_height);
+        }
+    }
+}
+
+/// Character typed by a key press, for type-ahead
+fn typed_char(event: &KeyboardEvent) -> Option<char> {
+    if let Some(ch) = event.text.as_deref().and_then(|text| text.chars().next()) {
+        return Some(ch);
+    }
+    // Platforms may only report the key; letters and digits are enough here
+    let name = format!("{:?}", event.key_code);
+    let name = name.strip_prefix("Num").unwrap_or(&name);
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) if ch.is_ascii_alphanumeric() => Some(ch),
+        _ => None,
     }
 }
 
@@ -412,6 +695,10 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         let size = Size::new(self.width, self.height);
         constraints.constrain(size)
@@ -425,17 +712,22 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
-        // Background
-        let bg_color = if !self.enabled {
-            self.style.disabled_color
-        } else {
-            self.style.background_color
+        // Disabled dropdowns keep their colors, faded by the theme
+        let paint = |color: [f32; 4]| {
+            let color = Color::rgba(color[0], color[1], color[2], color[3]);
+            if self.enabled {
+                color
+            } else {
+                self.palette.disabled(color)
+            }
         };
 
+        // Background
         batch.add_rounded_rect(
             bounds,
-            Color::rgba(bg_color[0], bg_color[1], bg_color[2], bg_color[3]),
+            paint(self.style.background_color),
             self.style.border_radius,
             Transform::identity(),
         );
@@ -445,6 +737,17 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
             // TODO: Proper border rendering
         }
 
+        // Focus ring
+        if self.is_focused() {
+            batch.add_stroke(
+                bounds,
+                paint(self.style.selected_color),
+                2.0,
+                BorderRadius::all(self.style.border_radius),
+                Transform::identity(),
+            );
+        }
+
         // Text
         let selected_text = if let Some(index) = self.selected_index.get() {
             self.options
@@ -465,15 +768,15 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
             selected_text,
             (
                 bounds.x + self.style.padding,
-                bounds.y + bounds.height / 2.0 - self.style.font_size / 2.0,
+                measure_line_metrics(self.style.font_size).centered_top(bounds.y, bounds.height),
             ),
-            Color::rgba(text_color[0], text_color[1], text_color[2], text_color[3]),
+            paint(text_color),
             self.style.font_size,
             0.0,
             strato_core::text::TextAlign::Left,
         );
 
-        // Arrow (Simple triangle)
+        // Arrow (Simple triangle), left out when the list can't open
         let arrow_color = self.style.text_color;
         let arrow_x = bounds.x + bounds.width - self.style.padding - 10.0;
         let arrow_y = bounds.y + bounds.height / 2.0;
@@ -482,54 +785,74 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
         // Vertices for arrow
         // This requires manual vertex adding or a shape primitive
         // For now, let's skip drawing arrow or use a small rect
-        batch.add_rect(
-            Rect::new(arrow_x, arrow_y - 2.0, 10.0, 4.0),
-            Color::rgba(
-                arrow_color[0],
-                arrow_color[1],
-                arrow_color[2],
-                arrow_color[3],
-            ),
-            Transform::identity(),
-        );
+        if !self.readonly {
+            batch.add_rect(
+                Rect::new(arrow_x, arrow_y - 2.0, 10.0, 4.0),
+                paint(arrow_color),
+                Transform::identity(),
+            );
+        }
 
         // Dropdown List
         if self.is_open.get() {
-            let filtered_options = self.filtered_options();
+            let rows = self.list_rows();
             let option_height = self.height;
-            let list_height = (filtered_options.len() as f32 * option_height).min(self.max_height);
+            let list_bounds = self.list_placement(bounds, batch.viewport());
+            self.list_rect.set(list_bounds);
 
-            let list_bounds = Rect::new(
-                bounds.x,
-                bounds.y + bounds.height,
-                bounds.width,
-                list_height,
-            );
+            let max_scroll = self.max_scroll(list_bounds.height);
+            let scroll = self.scroll_offset.get().clamp(0.0, max_scroll);
+            self.scroll_offset.set(scroll);
+
+            // The list is drawn as an overlay so ancestor clips don't cut it off
+            let mut overlay = RenderBatch::new();
 
             // List Background
             let list_bg = self.style.dropdown_background;
-            batch.add_overlay_rect(
+            overlay.add_rect(
                 list_bounds,
                 Color::rgba(list_bg[0], list_bg[1], list_bg[2], list_bg[3]),
                 Transform::identity(),
             );
 
-            // Options
-            let mut y = list_bounds.y;
-            for (original_index, option) in filtered_options {
-                if y + option_height > list_bounds.y + list_bounds.height {
-                    break; // Clip
+            // Options, scrolled inside the list
+            overlay.push_clip(list_bounds);
+            let first_visible = (scroll / option_height) as usize;
+            for (row, list_row) in rows.into_iter().enumerate().skip(first_visible) {
+                let y = list_bounds.y + row as f32 * option_height - scroll;
+                if y >= list_bounds.y + list_bounds.height {
+                    break;
                 }
+                let text_y =
+                    measure_line_metrics(self.style.font_size).centered_top(y, option_height);
+
+                let (original_index, option) = match list_row {
+                    ListRow::Header(label) => {
+                        let color = self.style.placeholder_color;
+                        overlay.add_text_aligned(
+                            label.to_string(),
+                            (list_bounds.x + self.style.padding, text_y),
+                            Color::rgba(color[0], color[1], color[2], color[3]),
+                            self.style.font_size,
+                            0.0,
+                            strato_core::text::TextAlign::Left,
+                        );
+                        continue;
+                    }
+                    ListRow::Option(original_index, option) => (original_index, option),
+                };
 
                 let is_selected = self.selected_index.get() == Some(original_index);
                 let opt_bg = if is_selected {
                     self.style.selected_color
+                } else if self.highlighted.get() == Some(original_index) {
+                    self.style.hover_color
                 } else {
                     self.style.dropdown_background
                 };
 
                 let opt_rect = Rect::new(list_bounds.x, y, list_bounds.width, option_height);
-                batch.add_overlay_rect(
+                overlay.add_rect(
                     opt_rect,
                     Color::rgba(opt_bg[0], opt_bg[1], opt_bg[2], opt_bg[3]),
                     Transform::identity(),
@@ -541,12 +864,14 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
                     self.style.text_color
                 };
 
-                batch.add_overlay_text_aligned(
+                let indent = if option.group.is_some() {
+                    self.group_indent()
+                } else {
+                    0.0
+                };
+                overlay.add_text_aligned(
                     option.label.clone(),
-                    (
-                        opt_rect.x + self.style.padding,
-                        opt_rect.y + opt_rect.height / 2.0 - self.style.font_size / 2.0,
-                    ),
+                    (opt_rect.x + self.style.padding + indent, text_y),
                     Color::rgba(
                         opt_text_color[0],
                         opt_text_color[1],
@@ -557,13 +882,77 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
                     0.0,
                     strato_core::text::TextAlign::Left,
                 );
-
-                y += option_height;
             }
+            overlay.pop_clip();
+
+            // Scroll thumb
+            if max_scroll > 0.0 {
+                let visible = list_bounds.height / (list_bounds.height + max_scroll);
+                let thumb_height = (list_bounds.height * visible).max(16.0);
+                let thumb_y =
+                    list_bounds.y + (list_bounds.height - thumb_height) * scroll / max_scroll;
+                let thumb = self.style.dropdown_border_color;
+                overlay.add_rect(
+                    Rect::new(
+                        list_bounds.x + list_bounds.width - 4.0,
+                        thumb_y,
+                        4.0,
+                        thumb_height,
+                    ),
+                    Color::rgba(thumb[0], thumb[1], thumb[2], thumb[3]),
+                    Transform::identity(),
+                );
+            }
+
+            batch.push_overlay(self.overlay_order.get(), overlay);
         }
     }
 
+    fn update(&mut self, ctx: &WidgetContext) {
+        self.palette = ctx.theme.colors.clone();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.is_open().then(|| self.overlay_order.get())
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.focus();
+        } else {
+            self.blur();
+        }
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        if self.readonly {
+            return None;
+        }
+        let over_list = self.is_open() && self.list_rect.get().contains(point);
+        (over_list || self.bounds.get().contains(point)).then_some(CursorIcon::Pointer)
+    }
+
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         let bounds = self.bounds.get();
         match event {
             Event::MouseDown(mouse_event) => {
@@ -572,34 +961,37 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
                     strato_core::types::Point::new(mouse_event.position.x, mouse_event.position.y);
 
                 // If open, check if we clicked inside the list
-                if self.is_open.get() {
-                    let list_height =
-                        (self.filtered_options().len() as f32 * self.height).min(self.max_height);
-                    let list_bounds = Rect::new(
-                        bounds.x,
-                        bounds.y + bounds.height,
-                        bounds.width,
-                        list_height,
-                    );
-
-                    if list_bounds.contains(point) {
-                        return self.handle_mouse_event(mouse_event, bounds);
-                    }
+                if self.is_open.get() && self.list_rect.get().contains(point) {
+                    return self.handle_mouse_event(mouse_event);
                 }
 
                 if bounds.contains(point) {
-                    return self.handle_mouse_event(mouse_event, bounds);
+                    return self.handle_mouse_event(mouse_event);
                 } else if self.is_open.get() {
                     // Click outside closes
-                    self.close();
+                    self.blur();
                     return EventResult::Handled;
                 }
 
+                self.focused.set(false);
                 EventResult::Ignored
             }
-            Event::KeyDown(keyboard_event) | Event::KeyUp(keyboard_event) => {
-                self.handle_keyboard_event(keyboard_event)
+            Event::MouseMove(mouse_event) if self.is_open.get() => {
+                let point = Point::new(mouse_event.position.x, mouse_event.position.y);
+                self.pointer_in_list
+                    .set(self.list_rect.get().contains(point));
+                if let Some(index) = self.option_at(point) {
+                    self.highlighted.set(Some(index));
+                }
+                EventResult::Ignored
+            }
+            Event::MouseWheel { delta, .. } if self.pointer_in_list.get() => {
+                let max_scroll = self.max_scroll(self.list_rect.get().height);
+                self.scroll_offset
+                    .set((self.scroll_offset.get() - delta.y).clamp(0.0, max_scroll));
+                EventResult::Handled
             }
+            Event::KeyDown(keyboard_event) => self.handle_keyboard_event(keyboard_event),
             _ => EventResult::Ignored,
         }
     }
@@ -613,7 +1005,9 @@ impl<T: Clone + PartialEq + std::fmt::Display + std::fmt::Debug + Send + Sync +
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(self.clone())
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        Box::new(widget)
     }
 }
 
@@ -660,4 +1054,287 @@ mod tests {
         dropdown.close();
         assert!(!dropdown.is_open());
     }
+
+    #[test]
+    fn test_open_lists_stack_by_open_order() {
+        let first = Dropdown::new().add_value("A".to_string());
+        let second = Dropdown::new().add_value("B".to_string());
+        second.open();
+        first.open();
+
+        // Rendered in tree order, inside a clip the lists must escape
+        let mut batch = RenderBatch::new();
+        batch.push_clip(Rect::new(0.0, 0.0, 200.0, 40.0));
+        first.render(
+            &mut batch,
+            Layout::new(glam::Vec2::new(0.0, 0.0), Size::new(200.0, 36.0)),
+        );
+        second.render(
+            &mut batch,
+            Layout::new(glam::Vec2::new(0.0, 20.0), Size::new(200.0, 36.0)),
+        );
+        batch.pop_clip();
+
+        let list_tops: Vec<f32> = batch
+            .overlay_commands
+            .iter()
+            .filter_map(|command| match command {
+                strato_renderer::batch::DrawCommand::Rect { rect, .. } if rect.width == 200.0 => {
+                    Some(rect.y)
+                }
+                _ => None,
+            })
+            .collect();
+        // The list opened last (the first dropdown's) is drawn on top
+        assert_eq!(list_tops.first(), Some(&56.0));
+        assert_eq!(list_tops.last(), Some(&36.0));
+    }
+
+    fn mouse(x: f32, y: f32) -> MouseEvent {
+        MouseEvent {
+            position: glam::Vec2::new(x, y),
+            button: Some(MouseButton::Left),
+            modifiers: Default::default(),
+            delta: glam::Vec2::ZERO,
+        }
+    }
+
+    fn render_at(dropdown: &Dropdown<String>, y: f32, viewport: Rect) {
+        let mut batch = RenderBatch::new();
+        batch.set_viewport(viewport);
+        dropdown.render(
+            &mut batch,
+            Layout::new(glam::Vec2::new(10.0, y), Size::new(200.0, 36.0)),
+        );
+    }
+
+    #[test]
+    fn test_list_opens_upward_at_bottom_edge() {
+        let viewport = Rect::new(0.0, 0.0, 400.0, 300.0);
+        let dropdown = Dropdown::new()
+            .add_value("A".to_string())
+            .add_value("B".to_string())
+            .add_value("C".to_string());
+        dropdown.open();
+
+        // Plenty of room below: the list opens downward
+        render_at(&dropdown, 20.0, viewport);
+        assert_eq!(
+            dropdown.list_rect.get(),
+            Rect::new(10.0, 56.0, 200.0, 108.0)
+        );
+
+        // At the bottom edge: the list ends where the control starts
+        render_at(&dropdown, 260.0, viewport);
+        let list = dropdown.list_rect.get();
+        assert_eq!(list, Rect::new(10.0, 152.0, 200.0, 108.0));
+
+        // Clicking the last row selects the last option
+        let mut dropdown = dropdown;
+        dropdown.handle_event(&Event::MouseDown(mouse(20.0, 250.0)));
+        assert_eq!(dropdown.get_selected_index(), Some(2));
+    }
+
+    #[test]
+    fn test_long_list_scrolls_inside_max_height() {
+        let viewport = Rect::new(0.0, 0.0, 400.0, 600.0);
+        let mut dropdown = (0..20)
+            .fold(Dropdown::new(), |dropdown, i| {
+                dropdown.add_value(i.to_string())
+            })
+            .max_height(180.0);
+        dropdown.open();
+        render_at(&dropdown, 0.0, viewport);
+        assert_eq!(dropdown.list_rect.get().height, 180.0);
+
+        // Wheel over the list scrolls it by two rows
+        dropdown.handle_event(&Event::MouseMove(mouse(20.0, 100.0)));
+        let wheel = Event::MouseWheel {
+            delta: glam::Vec2::new(0.0, -72.0),
+            modifiers: Default::default(),
+            precise: false,
+        };
+        assert_eq!(dropdown.handle_event(&wheel), EventResult::Handled);
+
+        dropdown.handle_event(&Event::MouseDown(mouse(20.0, 40.0)));
+        assert_eq!(dropdown.get_selected_index(), Some(2));
+    }
+
+    #[test]
+    fn test_list_can_size_to_options() {
+        let viewport = Rect::new(0.0, 0.0, 400.0, 300.0);
+        let dropdown = Dropdown::new()
+            .add_value("A much longer option than the control".to_string())
+            .match_width(false);
+        dropdown.open();
+        render_at(&dropdown, 0.0, viewport);
+        assert!(dropdown.list_rect.get().width > 200.0);
+    }
+
+    fn key(key_code: KeyCode) -> Event {
+        Event::KeyDown(KeyboardEvent {
+            key_code,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        })
+    }
+
+    fn fruits() -> Dropdown<String> {
+        ["Apple", "Banana", "Blueberry", "Cherry"]
+            .into_iter()
+            .fold(Dropdown::new(), |dropdown, fruit| {
+                dropdown.add_value(fruit.to_string())
+            })
+    }
+
+    #[test]
+    fn test_keyboard_needs_focus() {
+        let mut dropdown = fruits();
+        assert_eq!(
+            dropdown.handle_event(&key(KeyCode::Down)),
+            EventResult::Ignored
+        );
+        assert!(!dropdown.is_open());
+
+        dropdown.focus();
+        assert_eq!(
+            dropdown.handle_event(&key(KeyCode::Down)),
+            EventResult::Handled
+        );
+        assert!(dropdown.is_open());
+    }
+
+    #[test]
+    fn test_keyboard_moves_highlight_and_selects() {
+        let mut dropdown = fruits().selected("Banana".to_string());
+        dropdown.focus();
+        dropdown.handle_event(&key(KeyCode::Enter));
+        assert_eq!(dropdown.highlighted_index(), Some(1));
+
+        // Arrows move the highlight, not the selection
+        dropdown.handle_event(&key(KeyCode::Down));
+        dropdown.handle_event(&key(KeyCode::Down));
+        dropdown.handle_event(&key(KeyCode::Down));
+        assert_eq!(dropdown.highlighted_index(), Some(3));
+        assert_eq!(dropdown.get_selected_index(), Some(1));
+
+        dropdown.handle_event(&key(KeyCode::Up));
+        dropdown.handle_event(&key(KeyCode::Enter));
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.get_selected(), Some(&"Blueberry".to_string()));
+
+        // Escape cancels without changing the selection
+        dropdown.handle_event(&key(KeyCode::Space));
+        dropdown.handle_event(&key(KeyCode::Up));
+        assert_eq!(
+            dropdown.handle_event(&key(KeyCode::Escape)),
+            EventResult::Handled
+        );
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.get_selected_index(), Some(2));
+    }
+
+    #[test]
+    fn test_type_ahead_cycles_matching_options() {
+        let mut dropdown = fruits();
+        dropdown.focus();
+        dropdown.open();
+
+        dropdown.handle_event(&key(KeyCode::B));
+        assert_eq!(dropdown.highlighted_index(), Some(1));
+        dropdown.handle_event(&key(KeyCode::B));
+        assert_eq!(dropdown.highlighted_index(), Some(2));
+        dropdown.handle_event(&key(KeyCode::B));
+        assert_eq!(dropdown.highlighted_index(), Some(1));
+
+        dropdown.handle_event(&key(KeyCode::C));
+        assert_eq!(dropdown.highlighted_index(), Some(3));
+        // No match keeps the highlight
+        dropdown.handle_event(&key(KeyCode::Z));
+        assert_eq!(dropdown.highlighted_index(), Some(3));
+    }
+
+    fn grouped() -> Dropdown<String> {
+        let option = |label: &str| DropdownOption::from_value(label.to_string());
+        Dropdown::new()
+            .options(DropdownOption::group("Recent", vec![option("Cherry")]))
+            .options(DropdownOption::group(
+                "All",
+                vec![option("Apple"), option("Banana"), option("Cherry")],
+            ))
+    }
+
+    #[test]
+    fn test_group_headers_are_skipped_by_keys_and_clicks() {
+        let mut dropdown = grouped();
+        dropdown.focus();
+        dropdown.handle_event(&key(KeyCode::Down));
+        dropdown.handle_event(&key(KeyCode::Down));
+        assert_eq!(dropdown.highlighted_index(), Some(0));
+        dropdown.handle_event(&key(KeyCode::Down));
+        assert_eq!(dropdown.highlighted_index(), Some(1));
+
+        // Rows: Recent, Cherry, All, Apple, Banana, Cherry
+        render_at(&dropdown, 0.0, Rect::new(0.0, 0.0, 400.0, 600.0));
+        dropdown.handle_event(&Event::MouseDown(mouse(20.0, 36.0 + 2.0 * 36.0 + 10.0)));
+        assert!(dropdown.is_open());
+        assert_eq!(dropdown.get_selected_index(), None);
+
+        dropdown.handle_event(&Event::MouseDown(mouse(20.0, 36.0 + 4.0 * 36.0 + 10.0)));
+        assert_eq!(dropdown.get_selected(), Some(&"Banana".to_string()));
+    }
+
+    #[test]
+    fn test_filter_hides_groups_without_matches() {
+        let dropdown = grouped().searchable(true);
+        dropdown.search_text.set("ban".to_string());
+        let rows: Vec<_> = dropdown
+            .list_rows()
+            .into_iter()
+            .map(|row| match row {
+                ListRow::Header(label) => label.to_string(),
+                ListRow::Option(_, option) => option.label.clone(),
+            })
+            .collect();
+        assert_eq!(rows, ["All", "Banana"]);
+    }
+
+    fn click(dropdown: &mut Dropdown<String>) -> EventResult {
+        dropdown.handle_event(&Event::MouseDown(mouse(20.0, 10.0)))
+    }
+
+    #[test]
+    fn test_disabled_dropdown_does_not_open_on_click() {
+        let mut dropdown = fruits().enabled(false);
+        render_at(&dropdown, 0.0, Rect::new(0.0, 0.0, 400.0, 300.0));
+
+        assert_eq!(click(&mut dropdown), EventResult::Ignored);
+        assert!(!dropdown.is_open());
+        assert!(!dropdown.is_focused());
+
+        dropdown.focus();
+        dropdown.handle_event(&key(KeyCode::Enter));
+        assert!(!dropdown.is_open());
+        assert_eq!(
+            crate::widget::cursor_icon_at(&dropdown, Point::new(20.0, 10.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_readonly_dropdown_keeps_its_value() {
+        let mut dropdown = fruits().selected("Cherry".to_string()).readonly(true);
+        render_at(&dropdown, 0.0, Rect::new(0.0, 0.0, 400.0, 300.0));
+
+        // Clicking focuses it, but the list never opens
+        assert_eq!(click(&mut dropdown), EventResult::Handled);
+        assert!(dropdown.is_focused());
+        assert!(!dropdown.is_open());
+
+        dropdown.handle_event(&key(KeyCode::Down));
+        dropdown.handle_event(&key(KeyCode::Enter));
+        assert!(!dropdown.is_open());
+        assert_eq!(dropdown.get_selected(), Some(&"Cherry".to_string()));
+    }
 }
@@ -0,0 +1,397 @@
+//! Moving keyboard focus with Tab
+//!
+//! Tab moves keyboard focus to the next focusable widget and Shift+Tab to the
+//! previous one, wrapping around at either end. The application does this for
+//! every Tab the widget tree leaves unhandled, through [`handle_tab`].
+//!
+//! # Order
+//!
+//! Widgets are visited in tree order unless their `tab_index` builder gives
+//! them a place, the way HTML does:
+//!
+//! - Positive indices come first, lowest first, ties in tree order.
+//! - Widgets without an index, or with index 0, follow in tree order.
+//! - Negative indices take focus from the pointer or from code, but Tab skips
+//!   them.
+//!
+//! # Scopes
+//!
+//! A [`FocusScope`] groups the focusable widgets below it: they are ordered
+//! among themselves, then visited one after the other at the scope's place in
+//! the order around it, which the scope's own
+//! [`tab_index`](FocusScope::tab_index) can change.
+//!
+//! A trapping scope keeps focus inside. While focus is in it, Tab cycles
+//! through its widgets only, and while focus is outside every trapping scope,
+//! Tab moves into the last one in the tree. Modal dialogs wrap their content
+//! in one, so that Tab can't reach the page behind them:
+//!
+//! ```
+//! use strato_core::state::Signal;
+//! use strato_widgets::{focus::FocusScope, Button, Column, Show, TextInput};
+//!
+//! let open = Signal::new(true);
+//! let dialog = Show::new(open.clone(), || {
+//!     FocusScope::new(Column::new().children(vec![
+//!         Box::new(TextInput::new().placeholder("Name")),
+//!         Box::new(Button::new("Save")),
+//!         Box::new(Button::new("Cancel")),
+//!     ]))
+//!     .trap(true)
+//! });
+//! ```
+
+use crate::widget::{
+    clone_id, dispatch_event, focused_widget, generate_id, layout_child, Widget, WidgetId,
+};
+use strato_core::{
+    event::{Event, EventResult, KeyCode},
+    layout::{Constraints, Layout, LayoutCache, Size},
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Groups the focusable widgets below it in the Tab order, and optionally
+/// keeps focus inside
+pub struct FocusScope {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    trap: bool,
+    tab_index: Option<i32>,
+    layout_cache: LayoutCache,
+}
+
+impl std::fmt::Debug for FocusScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FocusScope")
+            .field("id", &self.id)
+            .field("child", &self.child)
+            .field("trap", &self.trap)
+            .field("tab_index", &self.tab_index)
+            .finish()
+    }
+}
+
+impl FocusScope {
+    /// Group the focusable widgets in `child`
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            id: generate_id(),
+            child: Box::new(child),
+            trap: false,
+            tab_index: None,
+            layout_cache: LayoutCache::new(),
+        }
+    }
+
+    /// Keep Tab cycling inside the scope, e.g. for a modal dialog
+    pub fn trap(mut self, trap: bool) -> Self {
+        self.trap = trap;
+        self
+    }
+
+    /// Place the whole group in the Tab order around it
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
+    /// Whether Tab stays inside the scope
+    pub fn is_trap(&self) -> bool {
+        self.trap
+    }
+
+    /// The scoped widget
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+}
+
+impl Widget for FocusScope {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = &self.layout_cache;
+        let child = self.child.as_mut();
+        cache.get_or_layout(constraints, || layout_child(cache, child, constraints))
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.child.render(batch, layout);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        dispatch_event(self.child.as_mut(), event)
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(FocusScope {
+            id: clone_id(self.id),
+            child: self.child.clone_widget(),
+            trap: self.trap,
+            tab_index: self.tab_index,
+            layout_cache: LayoutCache::new(),
+        })
+    }
+}
+
+/// Widgets visited together in the Tab order: one focusable widget, or all
+/// of a scope's
+struct Stop {
+    index: i32,
+    ids: Vec<WidgetId>,
+}
+
+/// The widgets Tab visits from the current focus, in order
+///
+/// Inside a trapping scope, only that scope's widgets are visited.
+pub fn tab_order(root: &dyn Widget) -> Vec<WidgetId> {
+    let region = match trap_around_focus(root, None) {
+        Some(Some(trap)) => trap,
+        _ => last_trap(root).unwrap_or(root),
+    };
+    let mut stops = Vec::new();
+    collect_stops(region, &mut stops);
+    flatten(stops)
+}
+
+/// Move focus to the next widget in the [`tab_order`], returning its ID
+///
+/// Starts from the first widget when none in the order has focus.
+pub fn focus_next(root: &mut dyn Widget) -> Option<WidgetId> {
+    step_focus(root, false)
+}
+
+/// Move focus to the previous widget in the [`tab_order`], returning its ID
+///
+/// Starts from the last widget when none in the order has focus.
+pub fn focus_previous(root: &mut dyn Widget) -> Option<WidgetId> {
+    step_focus(root, true)
+}
+
+/// Give keyboard focus to the widget `id`, taking it from any other
+///
+/// Returns `false`, changing nothing, when `id` isn't in the tree.
+pub fn focus_widget(root: &mut dyn Widget, id: WidgetId) -> bool {
+    if !contains(root, id) {
+        return false;
+    }
+    move_focus(root, id);
+    true
+}
+
+/// Move focus for an unhandled Tab or Shift+Tab press
+///
+/// Call after the tree has seen `event`, so widgets using Tab themselves get
+/// it first. Returns [`EventResult::Handled`] when focus moved.
+pub fn handle_tab(root: &mut dyn Widget, event: &Event) -> EventResult {
+    let Event::KeyDown(key) = event else {
+        return EventResult::Ignored;
+    };
+    if key.key_code != KeyCode::Tab {
+        return EventResult::Ignored;
+    }
+    match step_focus(root, key.modifiers.shift) {
+        Some(_) => EventResult::Handled,
+        None => EventResult::Ignored,
+    }
+}
+
+fn step_focus(root: &mut dyn Widget, backwards: bool) -> Option<WidgetId> {
+    let order = tab_order(root);
+    if order.is_empty() {
+        return None;
+    }
+    let len = order.len();
+    let current = focused_widget(root).and_then(|id| order.iter().position(|&other| other == id));
+    let next = match (current, backwards) {
+        (Some(at), false) => (at + 1) % len,
+        (Some(at), true) => (at + len - 1) % len,
+        (None, false) => 0,
+        (None, true) => len - 1,
+    };
+    move_focus(root, order[next]);
+    Some(order[next])
+}
+
+fn move_focus(widget: &mut (dyn Widget + '_), id: WidgetId) {
+    if widget.id() == id {
+        widget.set_focused(true);
+    } else if widget.is_focused() {
+        widget.set_focused(false);
+    }
+    for child in widget.children_mut() {
+        move_focus(child, id);
+    }
+}
+
+fn contains(widget: &dyn Widget, id: WidgetId) -> bool {
+    widget.id() == id
+        || widget
+            .children()
+            .into_iter()
+            .any(|child| contains(child, id))
+}
+
+fn as_trap<'a>(widget: &'a (dyn Widget + 'a)) -> Option<&'a FocusScope> {
+    widget
+        .as_any()
+        .downcast_ref::<FocusScope>()
+        .filter(|scope| scope.trap)
+}
+
+/// The innermost trapping scope around the focused widget, `Some(None)` when
+/// the focused widget isn't in one, and `None` when nothing has focus
+fn trap_around_focus<'a>(
+    widget: &'a (dyn Widget + 'a),
+    trap: Option<&'a (dyn Widget + 'a)>,
+) -> Option<Option<&'a (dyn Widget + 'a)>> {
+    if !widget.is_enabled() {
+        return None;
+    }
+    if widget.is_focused() {
+        return Some(trap);
+    }
+    let trap = match as_trap(widget) {
+        Some(_) => Some(widget),
+        None => trap,
+    };
+    widget
+        .children()
+        .into_iter()
+        .find_map(|child| trap_around_focus(child, trap))
+}
+
+/// The last trapping scope in the tree that isn't inside another
+fn last_trap<'a>(widget: &'a (dyn Widget + 'a)) -> Option<&'a (dyn Widget + 'a)> {
+    if !widget.is_enabled() {
+        return None;
+    }
+    if as_trap(widget).is_some() {
+        return Some(widget);
+    }
+    widget.children().into_iter().rev().find_map(last_trap)
+}
+
+fn collect_stops(widget: &dyn Widget, stops: &mut Vec<Stop>) {
+    if !widget.is_enabled() {
+        return;
+    }
+    if let Some(scope) = widget.as_any().downcast_ref::<FocusScope>() {
+        let mut inner = Vec::new();
+        collect_stops(scope.child(), &mut inner);
+        let ids = flatten(inner);
+        let index = scope.tab_index.unwrap_or(0);
+        if !ids.is_empty() && index >= 0 {
+            stops.push(Stop { index, ids });
+        }
+        return;
+    }
+    if widget.is_focusable() {
+        let index = widget.tab_index().unwrap_or(0);
+        if index >= 0 {
+            stops.push(Stop {
+                index,
+                ids: vec![widget.id()],
+            });
+        }
+    }
+    for child in widget.children() {
+        collect_stops(child, stops);
+    }
+}
+
+fn flatten(mut stops: Vec<Stop>) -> Vec<WidgetId> {
+    // Stable, so equal indices keep their tree order
+    stops.sort_by_key(|stop| (stop.index == 0, stop.index));
+    stops.into_iter().flat_map(|stop| stop.ids).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Column;
+    use crate::test_util::WidgetTester;
+    use crate::{Button, TextInput};
+    use strato_core::event::Modifiers;
+
+    fn focused(tester: &WidgetTester<Column>) -> Option<WidgetId> {
+        focused_widget(tester.root())
+    }
+
+    #[test]
+    fn test_tab_index_orders_before_tree_order() {
+        let first = Button::new("First");
+        let second = Button::new("Second").tab_index(2);
+        let third = TextInput::new().tab_index(1);
+        let skipped = Button::new("Skipped").tab_index(-1);
+        let ids = [first.id(), second.id(), third.id()];
+        let column = Column::new().children(vec![
+            Box::new(first),
+            Box::new(second),
+            Box::new(third),
+            Box::new(skipped),
+        ]);
+        let mut tester = WidgetTester::new(column, 200.0, 200.0);
+        assert_eq!(tab_order(tester.root()), vec![ids[2], ids[1], ids[0]]);
+
+        tester.press_key(KeyCode::Tab);
+        assert_eq!(focused(&tester), Some(ids[2]));
+        tester.press_key(KeyCode::Tab);
+        assert_eq!(focused(&tester), Some(ids[1]));
+        tester.set_modifiers(Modifiers {
+            shift: true,
+            ..Default::default()
+        });
+        tester.press_key(KeyCode::Tab);
+        tester.press_key(KeyCode::Tab);
+        // Wraps around from the first widget to the last
+        assert_eq!(focused(&tester), Some(ids[0]));
+    }
+
+    #[test]
+    fn test_trapping_scope_keeps_tab_inside() {
+        let page = Button::new("Page");
+        let name = TextInput::new();
+        let save = Button::new("Save");
+        let ids = [page.id(), name.id(), save.id()];
+        let dialog = FocusScope::new(Column::new().children(vec![Box::new(name), Box::new(save)]))
+            .trap(true);
+        let column = Column::new().children(vec![Box::new(page), Box::new(dialog)]);
+        let mut tester = WidgetTester::new(column, 200.0, 200.0);
+
+        // Focus on the page moves into the dialog, then cycles there
+        assert!(focus_widget(tester.root_mut(), ids[0]));
+        tester.press_key(KeyCode::Tab);
+        assert_eq!(focused(&tester), Some(ids[1]));
+        tester.press_key(KeyCode::Tab);
+        assert_eq!(focused(&tester), Some(ids[2]));
+        tester.press_key(KeyCode::Tab);
+        assert_eq!(focused(&tester), Some(ids[1]));
+    }
+}
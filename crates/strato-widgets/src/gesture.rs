@@ -0,0 +1,287 @@
+//! Press-and-hold gesture recognizers
+//!
+//! [`PressGestures`] turns a pointer held down on a widget into long presses
+//! and repeated presses, e.g. for numeric steppers, scroll arrows and volume
+//! controls. The widget feeds it pointer events together with its bounds and
+//! advances it from [`Widget::update`](crate::widget::Widget::update), so a
+//! hold is timed by the same per-frame clock as animations.
+
+use std::sync::Arc;
+use std::time::Duration;
+use strato_core::event::{Event, MouseButton};
+use strato_core::types::{Point, Rect};
+
+// Keeps a zero interval from firing forever within one frame
+const MIN_REPEAT_INTERVAL: Duration = Duration::from_millis(1);
+
+#[derive(Clone)]
+struct LongPress {
+    after: Duration,
+    handler: Arc<dyn Fn() + Send + Sync>,
+}
+
+#[derive(Clone)]
+struct PressRepeat {
+    initial_delay: Duration,
+    interval: Duration,
+    handler: Arc<dyn Fn() + Send + Sync>,
+}
+
+/// The hold in progress
+#[derive(Debug, Clone, Copy)]
+struct Hold {
+    held_for: Duration,
+    next_repeat: Duration,
+    long_pressed: bool,
+    /// Whether a gesture acted on this hold, which then isn't a click
+    fired: bool,
+}
+
+/// Long-press and press-repeat recognizers for one widget
+///
+/// A hold starts with a left press inside the widget's bounds and ends when
+/// the button is released. Moving the pointer out of the bounds cancels it
+/// without firing anything further.
+#[derive(Default)]
+pub struct PressGestures {
+    long_press: Option<LongPress>,
+    repeat: Option<PressRepeat>,
+    hold: Option<Hold>,
+}
+
+/// Clones share the handlers but not a hold in progress
+impl Clone for PressGestures {
+    fn clone(&self) -> Self {
+        Self {
+            long_press: self.long_press.clone(),
+            repeat: self.repeat.clone(),
+            hold: None,
+        }
+    }
+}
+
+impl PressGestures {
+    /// Recognizers with no gestures set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call `handler` once the pointer has been held down for `after`
+    pub fn set_long_press(&mut self, after: Duration, handler: impl Fn() + Send + Sync + 'static) {
+        self.long_press = Some(LongPress {
+            after,
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// Call `handler` after `initial_delay` of holding, then every `interval`
+    pub fn set_repeat(
+        &mut self,
+        initial_delay: Duration,
+        interval: Duration,
+        handler: impl Fn() + Send + Sync + 'static,
+    ) {
+        self.repeat = Some(PressRepeat {
+            initial_delay,
+            interval: interval.max(MIN_REPEAT_INTERVAL),
+            handler: Arc::new(handler),
+        });
+    }
+
+    /// Take the handlers of `other`, keeping the hold in progress
+    pub fn set_handlers_from(&mut self, other: &PressGestures) {
+        self.long_press = other.long_press.clone();
+        self.repeat = other.repeat.clone();
+    }
+
+    /// Whether no gesture is set
+    pub fn is_empty(&self) -> bool {
+        self.long_press.is_none() && self.repeat.is_none()
+    }
+
+    /// Whether a hold is in progress
+    pub fn is_held(&self) -> bool {
+        self.hold.is_some()
+    }
+
+    /// Track the pointer over a widget laid out in `bounds`
+    ///
+    /// Returns `true` for the release ending a hold that a gesture already
+    /// acted on; the widget should not treat that release as a click.
+    pub fn handle_pointer_event(&mut self, event: &Event, bounds: Rect) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        match event {
+            Event::MouseDown(mouse) if mouse.button == Some(MouseButton::Left) => {
+                if bounds.contains(Point::new(mouse.position.x, mouse.position.y)) {
+                    self.hold = Some(Hold {
+                        held_for: Duration::ZERO,
+                        next_repeat: self
+                            .repeat
+                            .as_ref()
+                            .map_or(Duration::ZERO, |repeat| repeat.initial_delay),
+                        long_pressed: false,
+                        fired: false,
+                    });
+                }
+                false
+            }
+            Event::MouseMove(mouse) => {
+                if !bounds.contains(Point::new(mouse.position.x, mouse.position.y)) {
+                    self.cancel();
+                }
+                false
+            }
+            Event::MouseUp(mouse) if mouse.button == Some(MouseButton::Left) => {
+                self.hold.take().is_some_and(|hold| hold.fired)
+            }
+            _ => false,
+        }
+    }
+
+    /// End the hold in progress without firing anything further
+    pub fn cancel(&mut self) {
+        self.hold = None;
+    }
+
+    /// Advance the hold in progress by `delta_time` seconds, firing the
+    /// gestures that came due
+    pub fn advance(&mut self, delta_time: f32) {
+        let Some(hold) = self.hold.as_mut() else {
+            return;
+        };
+        hold.held_for += Duration::from_secs_f32(delta_time.max(0.0));
+
+        if let Some(long_press) = &self.long_press {
+            if !hold.long_pressed && hold.held_for >= long_press.after {
+                hold.long_pressed = true;
+                hold.fired = true;
+                (long_press.handler)();
+            }
+        }
+        if let Some(repeat) = &self.repeat {
+            // A slow frame fires every repeat it skipped over
+            while hold.next_repeat <= hold.held_for {
+                hold.next_repeat += repeat.interval;
+                hold.fired = true;
+                (repeat.handler)();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for PressGestures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PressGestures")
+            .field("long_press", &self.long_press.as_ref().map(|lp| lp.after))
+            .field(
+                "repeat",
+                &self
+                    .repeat
+                    .as_ref()
+                    .map(|repeat| (repeat.initial_delay, repeat.interval)),
+            )
+            .field("hold", &self.hold)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use strato_core::event::MouseEvent;
+
+    const BOUNDS: Rect = Rect {
+        x: 0.0,
+        y: 0.0,
+        width: 100.0,
+        height: 40.0,
+    };
+
+    fn mouse(x: f32, y: f32) -> MouseEvent {
+        MouseEvent {
+            position: Vec2::new(x, y),
+            button: Some(MouseButton::Left),
+            modifiers: Default::default(),
+            delta: Vec2::ZERO,
+        }
+    }
+
+    fn counter() -> (Arc<AtomicUsize>, impl Fn() + Send + Sync + 'static) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let handle = count.clone();
+        (count, move || {
+            handle.fetch_add(1, Ordering::SeqCst);
+        })
+    }
+
+    #[test]
+    fn test_long_press_fires_once_after_duration() {
+        let (count, handler) = counter();
+        let mut gestures = PressGestures::new();
+        gestures.set_long_press(Duration::from_millis(500), handler);
+
+        gestures.handle_pointer_event(&Event::MouseDown(mouse(10.0, 10.0)), BOUNDS);
+        gestures.advance(0.4);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        gestures.advance(0.2);
+        gestures.advance(1.0);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+
+        let consumed = gestures.handle_pointer_event(&Event::MouseUp(mouse(10.0, 10.0)), BOUNDS);
+        assert!(consumed);
+    }
+
+    #[test]
+    fn test_repeat_fires_after_delay_then_every_interval() {
+        let (count, handler) = counter();
+        let mut gestures = PressGestures::new();
+        gestures.set_repeat(
+            Duration::from_millis(400),
+            Duration::from_millis(100),
+            handler,
+        );
+
+        gestures.handle_pointer_event(&Event::MouseDown(mouse(10.0, 10.0)), BOUNDS);
+        gestures.advance(0.3);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+        gestures.advance(0.1);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        gestures.advance(0.25);
+        assert_eq!(count.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_short_press_is_left_as_a_click() {
+        let (count, handler) = counter();
+        let mut gestures = PressGestures::new();
+        gestures.set_long_press(Duration::from_millis(500), handler);
+
+        gestures.handle_pointer_event(&Event::MouseDown(mouse(10.0, 10.0)), BOUNDS);
+        gestures.advance(0.1);
+        let consumed = gestures.handle_pointer_event(&Event::MouseUp(mouse(10.0, 10.0)), BOUNDS);
+
+        assert!(!consumed);
+        gestures.advance(1.0);
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_leaving_bounds_cancels_hold() {
+        let (count, handler) = counter();
+        let mut gestures = PressGestures::new();
+        gestures.set_repeat(Duration::ZERO, Duration::from_millis(100), handler);
+
+        gestures.handle_pointer_event(&Event::MouseDown(mouse(10.0, 10.0)), BOUNDS);
+        gestures.advance(0.05);
+        gestures.handle_pointer_event(&Event::MouseMove(mouse(150.0, 10.0)), BOUNDS);
+        gestures.advance(1.0);
+
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+        assert!(!gestures.is_held());
+    }
+}
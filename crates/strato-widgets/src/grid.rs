@@ -1,9 +1,9 @@
 //! Grid widget for 2D layout
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::widget::{clone_id, dispatch_event, generate_id, Widget, WidgetId};
 use std::any::Any;
 use strato_core::{
     event::{Event, EventResult},
-    layout::{Constraints, Layout, Size},
+    layout::{Constraints, EdgeInsets, Layout, Size},
 };
 use strato_renderer::batch::RenderBatch;
 
@@ -18,15 +18,108 @@ pub enum GridUnit {
     Auto,
 }
 
+/// Placement of a grid item along one axis of its cell
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellAlign {
+    /// At the left or top of the cell
+    Start,
+    /// In the middle of the cell
+    Center,
+    /// At the right or bottom of the cell
+    End,
+    /// Filling the cell
+    #[default]
+    Stretch,
+}
+
+impl CellAlign {
+    /// Offset of an item of `size` in `space`
+    fn offset(self, size: f32, space: f32) -> f32 {
+        match self {
+            CellAlign::Start | CellAlign::Stretch => 0.0,
+            CellAlign::Center => (space - size) / 2.0,
+            CellAlign::End => space - size,
+        }
+    }
+}
+
+/// Placement of a grid item within its cell, by default stretched to fill it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Alignment {
+    pub horizontal: CellAlign,
+    pub vertical: CellAlign,
+}
+
+impl Alignment {
+    /// Fill the cell
+    pub const STRETCH: Self = Self::new(CellAlign::Stretch, CellAlign::Stretch);
+    /// Keep the item's size and center it
+    pub const CENTER: Self = Self::new(CellAlign::Center, CellAlign::Center);
+    /// Keep the item's size and place it in the top left corner
+    pub const TOP_LEFT: Self = Self::new(CellAlign::Start, CellAlign::Start);
+    /// Keep the item's size and place it in the bottom right corner
+    pub const BOTTOM_RIGHT: Self = Self::new(CellAlign::End, CellAlign::End);
+
+    /// Align horizontally and vertically
+    pub const fn new(horizontal: CellAlign, vertical: CellAlign) -> Self {
+        Self {
+            horizontal,
+            vertical,
+        }
+    }
+}
+
+/// A grid child with its own alignment or padding
+///
+/// Settings left unset use the grid's [`cell_alignment`](Grid::cell_alignment)
+/// and [`cell_padding`](Grid::cell_padding).
+#[derive(Debug)]
+pub struct GridCell {
+    child: Box<dyn Widget>,
+    style: CellStyle,
+}
+
+impl GridCell {
+    /// Wrap `child` for placement in a grid cell
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            child: Box::new(child),
+            style: CellStyle::default(),
+        }
+    }
+
+    /// Align the child within its cell
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.style.alignment = Some(alignment);
+        self
+    }
+
+    /// Pad the child within its cell
+    pub fn padding(mut self, padding: EdgeInsets) -> Self {
+        self.style.padding = Some(padding);
+        self
+    }
+}
+
+/// Per-child overrides of the grid's cell settings
+#[derive(Debug, Clone, Copy, Default)]
+struct CellStyle {
+    alignment: Option<Alignment>,
+    padding: Option<EdgeInsets>,
+}
+
 /// Grid widget for 2D layout
 #[derive(Debug)]
 pub struct Grid {
     id: WidgetId,
     children: Vec<Box<dyn Widget>>,
+    cell_styles: Vec<CellStyle>,
     rows: Vec<GridUnit>,
     cols: Vec<GridUnit>,
     row_gap: f32,
     col_gap: f32,
+    cell_alignment: Alignment,
+    cell_padding: EdgeInsets,
     // Store layout results for rendering
     cached_child_layouts: Vec<Layout>,
 }
@@ -37,10 +130,13 @@ impl Grid {
         Self {
             id: generate_id(),
             children: Vec::new(),
+            cell_styles: Vec::new(),
             rows: Vec::new(),
             cols: Vec::new(),
             row_gap: 0.0,
             col_gap: 0.0,
+            cell_alignment: Alignment::STRETCH,
+            cell_padding: EdgeInsets::default(),
             cached_child_layouts: Vec::new(),
         }
     }
@@ -69,8 +165,21 @@ impl Grid {
         self
     }
 
+    /// Align children within their cells, stretching them by default
+    pub fn cell_alignment(mut self, alignment: Alignment) -> Self {
+        self.cell_alignment = alignment;
+        self
+    }
+
+    /// Pad every child within its cell, inside the row and column gaps
+    pub fn cell_padding(mut self, padding: EdgeInsets) -> Self {
+        self.cell_padding = padding;
+        self
+    }
+
     /// Add children
     pub fn children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.cell_styles = vec![CellStyle::default(); children.len()];
         self.children = children;
         self
     }
@@ -78,8 +187,25 @@ impl Grid {
     /// Add a single child
     pub fn child(mut self, child: Box<dyn Widget>) -> Self {
         self.children.push(child);
+        self.cell_styles.push(CellStyle::default());
+        self
+    }
+
+    /// Add a child with its own alignment or padding
+    pub fn cell(mut self, cell: GridCell) -> Self {
+        self.children.push(cell.child);
+        self.cell_styles.push(cell.style);
         self
     }
+
+    /// Alignment and padding of the child at `index`
+    fn cell_style(&self, index: usize) -> (Alignment, EdgeInsets) {
+        let style = self.cell_styles.get(index).copied().unwrap_or_default();
+        (
+            style.alignment.unwrap_or(self.cell_alignment),
+            style.padding.unwrap_or(self.cell_padding),
+        )
+    }
 }
 
 impl Widget for Grid {
@@ -87,6 +213,10 @@ impl Widget for Grid {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         // If no columns defined, default to 1 column auto
         if self.cols.is_empty() {
@@ -132,14 +262,18 @@ impl Widget for Grid {
                     // Measure content
                     // TODO: This is naive. True grid layout is complex.
                     // We measure with loose constraints to get content size.
-                    let measure_constraints = Constraints::loose(available_width, available_height);
+                    let (_, padding) = self.cell_style(idx);
+                    let measure_constraints = Constraints::loose(
+                        (available_width - padding.horizontal()).max(0.0),
+                        (available_height - padding.vertical()).max(0.0),
+                    );
                     let size = self.children[idx].layout(measure_constraints);
 
                     if is_col_auto {
-                        col_widths[c] = f32::max(col_widths[c], size.width);
+                        col_widths[c] = f32::max(col_widths[c], size.width + padding.horizontal());
                     }
                     if is_row_auto {
-                        row_heights[r] = f32::max(row_heights[r], size.height);
+                        row_heights[r] = f32::max(row_heights[r], size.height + padding.vertical());
                     }
                 }
             }
@@ -219,22 +353,35 @@ impl Widget for Grid {
                 let col_w = col_widths[c];
 
                 if idx < self.children.len() {
-                    let cell_x = current_x;
-                    let cell_y = current_y;
-
-                    // Re-layout child with exact cell size
-                    // We force the child to fit the cell? Or align it?
-                    // Typically grid items stretch to fill cell unless aligned.
-                    // We'll enforce loose constraints up to cell size, but tight might be better for stretch.
-                    // Let's use tight for compatibility with "stretch" default behavior.
-                    let cell_constraints = Constraints::tight(col_w, row_h);
-                    // Note: If row_h is 0 (e.g. empty fr track), this hides the child.
+                    let (alignment, padding) = self.cell_style(idx);
+                    let inner_w = (col_w - padding.horizontal()).max(0.0);
+                    let inner_h = (row_h - padding.vertical()).max(0.0);
 
-                    self.children[idx].layout(cell_constraints);
+                    // Stretched axes are tight to the padded cell, aligned
+                    // ones let the child pick its size up to it.
+                    // Note: If row_h is 0 (e.g. empty fr track), this hides the child.
+                    let stretch_w = alignment.horizontal == CellAlign::Stretch;
+                    let stretch_h = alignment.vertical == CellAlign::Stretch;
+                    let cell_constraints = Constraints {
+                        min_width: if stretch_w { inner_w } else { 0.0 },
+                        max_width: inner_w,
+                        min_height: if stretch_h { inner_h } else { 0.0 },
+                        max_height: inner_h,
+                    };
+
+                    let size = self.children[idx].layout(cell_constraints);
+                    let size = Size::new(size.width.min(inner_w), size.height.min(inner_h));
 
                     self.cached_child_layouts.push(Layout::new(
-                        glam::Vec2::new(cell_x, cell_y),
-                        Size::new(col_w, row_h),
+                        glam::Vec2::new(
+                            current_x
+                                + padding.left
+                                + alignment.horizontal.offset(size.width, inner_w),
+                            current_y
+                                + padding.top
+                                + alignment.vertical.offset(size.height, inner_h),
+                        ),
+                        size,
                     ));
                 }
 
@@ -261,7 +408,7 @@ impl Widget for Grid {
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         for child in &mut self.children {
-            if child.handle_event(event) == EventResult::Handled {
+            if dispatch_event(child.as_mut(), event) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
@@ -289,13 +436,74 @@ impl Widget for Grid {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Grid {
-            id: generate_id(),
+            id: clone_id(self.id),
             children: self.children.iter().map(|c| c.clone_widget()).collect(),
+            cell_styles: self.cell_styles.clone(),
             rows: self.rows.clone(),
             cols: self.cols.clone(),
             row_gap: self.row_gap,
             col_gap: self.col_gap,
+            cell_alignment: self.cell_alignment,
+            cell_padding: self.cell_padding,
             cached_child_layouts: Vec::new(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Container;
+
+    fn placed(grid: &Grid, index: usize) -> (glam::Vec2, Size) {
+        let layout = grid.cached_child_layouts[index];
+        (layout.position, layout.size)
+    }
+
+    #[test]
+    fn test_undersized_child_centers_in_its_cell() {
+        let mut grid = Grid::new()
+            .columns(vec![GridUnit::Pixel(100.0), GridUnit::Pixel(100.0)])
+            .rows(vec![GridUnit::Pixel(80.0)])
+            .col_gap(10.0)
+            .cell_alignment(Alignment::CENTER)
+            .child(Box::new(Container::new().size(40.0, 20.0)))
+            .cell(GridCell::new(Container::new()).alignment(Alignment::STRETCH));
+        grid.layout(Constraints::loose(400.0, 400.0));
+
+        assert_eq!(
+            placed(&grid, 0),
+            (glam::Vec2::new(30.0, 30.0), Size::new(40.0, 20.0))
+        );
+        assert_eq!(
+            placed(&grid, 1),
+            (glam::Vec2::new(110.0, 0.0), Size::new(100.0, 80.0))
+        );
+    }
+
+    #[test]
+    fn test_cell_padding_insets_children() {
+        let mut grid = Grid::new()
+            .columns(vec![GridUnit::Pixel(100.0), GridUnit::Auto])
+            .rows(vec![GridUnit::Pixel(50.0)])
+            .cell_padding(EdgeInsets::all(5.0))
+            .child(Box::new(Container::new()))
+            .cell(
+                GridCell::new(Container::new().size(30.0, 20.0))
+                    .padding(EdgeInsets::symmetric(10.0, 0.0))
+                    .alignment(Alignment::BOTTOM_RIGHT),
+            );
+        let size = grid.layout(Constraints::loose(400.0, 400.0));
+
+        assert_eq!(
+            placed(&grid, 0),
+            (glam::Vec2::new(5.0, 5.0), Size::new(90.0, 40.0))
+        );
+        // The auto column fits the child and its padding
+        assert_eq!(size.width, 150.0);
+        assert_eq!(
+            placed(&grid, 1),
+            (glam::Vec2::new(110.0, 30.0), Size::new(30.0, 20.0))
+        );
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Supports various image formats, scaling modes, and loading states.
 
-use crate::widget::{generate_id, Widget, WidgetContext, WidgetId};
+use crate::widget::{clone_id, generate_id, register_bounds, Widget, WidgetContext, WidgetId};
 use std::path::PathBuf;
 use std::sync::Arc;
 use strato_core::{
@@ -121,9 +121,9 @@ pub struct Image {
     style: ImageStyle,
     state: Signal<ImageState>,
     alt_text: Option<String>,
-    on_load: Option<Box<dyn Fn(&ImageData) + Send + Sync>>,
-    on_error: Option<Box<dyn Fn(&str) + Send + Sync>>,
-    on_click: Option<Box<dyn Fn() + Send + Sync>>,
+    on_load: Option<Arc<dyn Fn(&ImageData) + Send + Sync>>,
+    on_error: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    on_click: Option<Arc<dyn Fn() + Send + Sync>>,
     loading_placeholder: Option<VNode>,
     error_placeholder: Option<VNode>,
     bounds: Signal<Rect>,
@@ -155,9 +155,9 @@ impl Clone for Image {
             style: self.style.clone(),
             state: self.state.clone(),
             alt_text: self.alt_text.clone(),
-            on_load: None, // Function pointers can't be cloned
-            on_error: None,
-            on_click: None,
+            on_load: self.on_load.clone(),
+            on_error: self.on_error.clone(),
+            on_click: self.on_click.clone(),
             loading_placeholder: self.loading_placeholder.clone(),
             error_placeholder: self.error_placeholder.clone(),
             bounds: self.bounds.clone(),
@@ -257,7 +257,7 @@ impl Image {
     where
         F: Fn(&ImageData) + Send + Sync + 'static,
     {
-        self.on_load = Some(Box::new(callback));
+        self.on_load = Some(Arc::new(callback));
         self
     }
 
@@ -266,7 +266,7 @@ impl Image {
     where
         F: Fn(&str) + Send + Sync + 'static,
     {
-        self.on_error = Some(Box::new(callback));
+        self.on_error = Some(Arc::new(callback));
         self
     }
 
@@ -275,7 +275,7 @@ impl Image {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_click = Some(Box::new(callback));
+        self.on_click = Some(Arc::new(callback));
         self
     }
 
@@ -482,6 +482,10 @@ impl Widget for Image {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn handle_event(&mut self, event: &Event) -> EventResult {
         let bounds = self.bounds.get();
         match event {
@@ -535,6 +539,7 @@ impl Widget for Image {
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         let mut background_color = self
             .style
@@ -611,19 +616,9 @@ impl Widget for Image {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(Image {
-            id: self.id,
-            source: self.source.clone(),
-            style: self.style.clone(),
-            state: self.state.clone(),
-            alt_text: self.alt_text.clone(),
-            on_load: None,  // Cannot clone function pointers
-            on_error: None, // Cannot clone function pointers
-            on_click: None, // Cannot clone function pointers
-            loading_placeholder: self.loading_placeholder.clone(),
-            error_placeholder: self.error_placeholder.clone(),
-            bounds: self.bounds.clone(),
-        })
+        let mut image = self.clone();
+        image.id = clone_id(self.id);
+        Box::new(image)
     }
 
     fn as_any(&self) -> &dyn std::any::Any {
@@ -2,15 +2,20 @@
 //!
 //! Provides text input components with various input types, validation, and formatting options.
 
-use crate::widget::{generate_id, Widget, WidgetId};
-use std::{any::Any, sync::Arc};
+use crate::clipboard::clipboard;
+use crate::menu_bar::{ContextMenu, ContextMenuResponse, MenuItem};
+use crate::shortcut::Shortcut;
+use crate::theme::ColorPalette;
+use crate::widget::{clone_id, generate_id, register_bounds, Widget, WidgetContext, WidgetId};
+use std::{any::Any, sync::Arc, time::Duration};
 use strato_core::{
-    event::{Event, EventResult, KeyCode, KeyEvent, KeyboardEvent, MouseEvent},
+    event::{Event, EventResult, KeyCode, KeyEvent, KeyboardEvent, MouseButton, MouseEvent},
     layout::{Constraints, Layout, Size},
     state::Signal,
     theme::Theme,
     types::{Color, Point, Rect, Transform},
     vdom::VNode,
+    window::CursorIcon,
 };
 use strato_renderer::{
     batch::RenderBatch,
@@ -30,6 +35,57 @@ pub enum InputType {
     Multiline,
 }
 
+/// Standard clipboard and selection actions of text fields
+///
+/// They are offered from the right-click menu and bound to the usual
+/// shortcuts, and all act on the same selection as keyboard selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditAction {
+    Cut,
+    Copy,
+    Paste,
+    SelectAll,
+}
+
+impl EditAction {
+    /// Every action, in menu order
+    pub const ALL: [EditAction; 4] = [
+        EditAction::Cut,
+        EditAction::Copy,
+        EditAction::Paste,
+        EditAction::SelectAll,
+    ];
+
+    /// Label shown in the context menu
+    pub fn label(self) -> &'static str {
+        match self {
+            EditAction::Cut => "Cut",
+            EditAction::Copy => "Copy",
+            EditAction::Paste => "Paste",
+            EditAction::SelectAll => "Select All",
+        }
+    }
+
+    /// Keyboard shortcut that triggers the action
+    pub fn shortcut(self) -> Shortcut {
+        match self {
+            EditAction::Cut => Shortcut::ctrl(KeyCode::X),
+            EditAction::Copy => Shortcut::ctrl(KeyCode::C),
+            EditAction::Paste => Shortcut::ctrl(KeyCode::V),
+            EditAction::SelectAll => Shortcut::ctrl(KeyCode::A),
+        }
+    }
+}
+
+/// Context menu entries; `None` is a separator
+const CONTEXT_MENU_ACTIONS: [Option<EditAction>; 5] = [
+    Some(EditAction::Cut),
+    Some(EditAction::Copy),
+    Some(EditAction::Paste),
+    None,
+    Some(EditAction::SelectAll),
+];
+
 /// Validation state for input
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ValidationState {
@@ -65,6 +121,8 @@ pub struct InputStyle {
     pub font_size: f32,
     pub font_family: String,
     pub line_height: f32,
+    /// Full on-off period of the caret blink; `None` keeps the caret steady
+    pub caret_blink: Option<Duration>,
 }
 
 impl Default for InputStyle {
@@ -95,6 +153,7 @@ impl Default for InputStyle {
             font_size: 14.0,
             font_family: font_family.to_string(),
             line_height: 1.2,
+            caret_blink: Some(Duration::from_secs(1)),
         }
     }
 }
@@ -120,8 +179,16 @@ impl InputStyle {
         }
     }
 
+    /// Set the caret blink period, or `None` for a steady caret
+    pub fn caret_blink(mut self, period: Option<Duration>) -> Self {
+        self.caret_blink = period;
+        self
+    }
+
     /// Get style for a specific input state
-    pub fn for_state(&self, state: InputState) -> Self {
+    ///
+    /// Disabled inputs are faded with `palette`'s disabled opacity.
+    pub fn for_state(&self, state: InputState, palette: &ColorPalette) -> Self {
         let mut style = self.clone();
         match state {
             InputState::Focused => {
@@ -131,8 +198,10 @@ impl InputStyle {
                 style.border_color = Color::DARK_GRAY;
             }
             InputState::Disabled => {
-                style.background_color = Color::LIGHT_GRAY;
-                style.text_color = Color::GRAY;
+                style.background_color = palette.disabled(style.background_color);
+                style.border_color = palette.disabled(style.border_color);
+                style.text_color = palette.disabled(style.text_color);
+                style.placeholder_color = palette.disabled(style.placeholder_color);
             }
             InputState::ReadOnly => {
                 style.background_color = Color::LIGHT_GRAY;
@@ -147,7 +216,123 @@ impl InputStyle {
 }
 
 /// Validation function type
-pub type ValidationFn = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+pub type ValidationFn = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+/// Maps char offsets in a text to visual `(line, column)` positions
+///
+/// Lines are split at `'\n'` and, when a wrap width is given, soft-wrapped at
+/// word boundaries so that no line is wider than the wrap width.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineIndex {
+    /// Char range of each visual line, excluding the trailing newline
+    lines: Vec<std::ops::Range<usize>>,
+}
+
+impl LineIndex {
+    /// Index hard line breaks only
+    pub fn new(text: &str) -> Self {
+        Self::build(text, None)
+    }
+
+    /// Index hard line breaks and wrap lines wider than `max_width`
+    pub fn wrapped(text: &str, max_width: f32, font_size: f32) -> Self {
+        Self::build(text, Some((max_width, font_size)))
+    }
+
+    fn build(text: &str, wrap: Option<(f32, f32)>) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let mut lines = Vec::new();
+        let mut start = 0;
+
+        for hard_end in chars
+            .iter()
+            .enumerate()
+            .filter(|(_, ch)| **ch == '\n')
+            .map(|(i, _)| i)
+            .chain(std::iter::once(chars.len()))
+        {
+            if let Some((max_width, font_size)) = wrap {
+                start = Self::wrap_line(&chars, start, hard_end, max_width, font_size, &mut lines);
+            }
+            lines.push(start..hard_end);
+            start = hard_end + 1;
+        }
+
+        Self { lines }
+    }
+
+    /// Push soft-wrapped lines for `chars[start..end]`, returning the start of
+    /// the last (unpushed) segment.
+    fn wrap_line(
+        chars: &[char],
+        mut start: usize,
+        end: usize,
+        max_width: f32,
+        font_size: f32,
+        lines: &mut Vec<std::ops::Range<usize>>,
+    ) -> usize {
+        let mut width = 0.0;
+        let mut last_space = None;
+        let mut i = start;
+        while i < end {
+            let ch = chars[i];
+            width += crate::text::measure_text_width(ch.encode_utf8(&mut [0; 4]), font_size, 0.0);
+            if ch.is_whitespace() {
+                last_space = Some(i);
+            }
+            if width > max_width && i > start {
+                // Break after the last space on this line, or mid-word if there is none.
+                let break_at = match last_space {
+                    Some(space) if space >= start => space + 1,
+                    _ => i,
+                };
+                lines.push(start..break_at);
+                start = break_at;
+                i = break_at;
+                width = 0.0;
+                last_space = None;
+                continue;
+            }
+            i += 1;
+        }
+        start
+    }
+
+    /// Number of visual lines (always at least one)
+    pub fn line_count(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// Char range of a visual line
+    pub fn line_range(&self, line: usize) -> std::ops::Range<usize> {
+        self.lines[line.min(self.lines.len() - 1)].clone()
+    }
+
+    /// Visual `(line, column)` of a char offset
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        // A soft-wrapped line ends where the next begins, so pick the last
+        // line starting at or before the offset.
+        let line = self
+            .lines
+            .partition_point(|range| range.start <= offset)
+            .saturating_sub(1);
+        let range = &self.lines[line];
+        (line, offset.clamp(range.start, range.end) - range.start)
+    }
+
+    /// Char offset of a `(line, column)` position, clamping the column
+    pub fn offset(&self, line: usize, column: usize) -> usize {
+        let range = self.line_range(line);
+        (range.start + column).min(range.end)
+    }
+}
+
+/// Convert a char offset into a byte index of `text`
+fn byte_index(text: &str, char_offset: usize) -> usize {
+    text.char_indices()
+        .nth(char_offset)
+        .map_or(text.len(), |(index, _)| index)
+}
 
 /// Text input widget
 pub struct TextInput {
@@ -164,6 +349,7 @@ pub struct TextInput {
     multiline: bool,
     rows: usize,
     cols: usize,
+    tab_index: Option<i32>,
 
     // State management
     state: Signal<InputState>,
@@ -185,19 +371,22 @@ pub struct TextInput {
     // Styling
     style: InputStyle,
     theme: Option<Arc<Theme>>,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
 
     // Validation
     validators: Vec<ValidationFn>,
 
     // Event handlers
-    on_change: Option<Box<dyn Fn(&str) + Send + Sync>>,
-    on_focus: Option<Box<dyn Fn() + Send + Sync>>,
-    on_blur: Option<Box<dyn Fn() + Send + Sync>>,
-    on_submit: Option<Box<dyn Fn(&str) + Send + Sync>>,
+    on_change: Option<Arc<dyn Fn(&str) + Send + Sync>>,
+    on_focus: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_blur: Option<Arc<dyn Fn() + Send + Sync>>,
+    on_submit: Option<Arc<dyn Fn(&str) + Send + Sync>>,
 
     // Internal state
     cursor_blink_timer: Signal<f32>,
     scroll_offset: Signal<f32>,
+    context_menu: ContextMenu,
 }
 
 impl std::fmt::Debug for TextInput {
@@ -216,6 +405,7 @@ impl std::fmt::Debug for TextInput {
             .field("multiline", &self.multiline)
             .field("rows", &self.rows)
             .field("cols", &self.cols)
+            .field("tab_index", &self.tab_index)
             .field("state", &self.state)
             .field("validation_state", &self.validation_state)
             .field("validation_message", &self.validation_message)
@@ -248,6 +438,7 @@ impl std::fmt::Debug for TextInput {
             )
             .field("cursor_blink_timer", &self.cursor_blink_timer)
             .field("scroll_offset", &self.scroll_offset)
+            .field("context_menu", &self.context_menu)
             .finish()
     }
 }
@@ -269,6 +460,7 @@ impl TextInput {
             multiline: false,
             rows: 1,
             cols: 20,
+            tab_index: None,
 
             // State management
             state: Signal::new(InputState::Normal),
@@ -290,6 +482,7 @@ impl TextInput {
             // Styling
             style: InputStyle::default(),
             theme: None,
+            palette: ColorPalette::default(),
 
             // Validation
             validators: Vec::new(),
@@ -303,6 +496,7 @@ impl TextInput {
             // Internal state
             cursor_blink_timer: Signal::new(0.0),
             scroll_offset: Signal::new(0.0),
+            context_menu: ContextMenu::new(),
         }
     }
 
@@ -370,6 +564,12 @@ impl TextInput {
         self
     }
 
+    /// Set the input's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
     /// Set multiline mode
     pub fn multiline(mut self, multiline: bool) -> Self {
         self.multiline = multiline;
@@ -408,7 +608,7 @@ impl TextInput {
     where
         F: Fn(&str) -> Result<(), String> + Send + Sync + 'static,
     {
-        self.validators.push(Box::new(validator));
+        self.validators.push(Arc::new(validator));
         self
     }
 
@@ -417,7 +617,7 @@ impl TextInput {
     where
         F: Fn(&str) + Send + Sync + 'static,
     {
-        self.on_change = Some(Box::new(callback));
+        self.on_change = Some(Arc::new(callback));
         self
     }
 
@@ -426,7 +626,7 @@ impl TextInput {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_focus = Some(Box::new(callback));
+        self.on_focus = Some(Arc::new(callback));
         self
     }
 
@@ -435,7 +635,7 @@ impl TextInput {
     where
         F: Fn() + Send + Sync + 'static,
     {
-        self.on_blur = Some(Box::new(callback));
+        self.on_blur = Some(Arc::new(callback));
         self
     }
 
@@ -444,7 +644,7 @@ impl TextInput {
     where
         F: Fn(&str) + Send + Sync + 'static,
     {
-        self.on_submit = Some(Box::new(callback));
+        self.on_submit = Some(Arc::new(callback));
         self
     }
 
@@ -516,6 +716,99 @@ impl TextInput {
         self.selection_end.set(None);
     }
 
+    /// The selected characters as an ordered, non-empty range
+    pub fn selected_range(&self) -> Option<(usize, usize)> {
+        let len = self.value.get().chars().count();
+        let (anchor, cursor) = self.get_selection()?;
+        let (start, end) = (anchor.min(cursor).min(len), anchor.max(cursor).min(len));
+        (start < end).then_some((start, end))
+    }
+
+    /// The selected text, if any
+    pub fn selected_text(&self) -> Option<String> {
+        let (start, end) = self.selected_range()?;
+        Some(
+            self.value
+                .get()
+                .chars()
+                .skip(start)
+                .take(end - start)
+                .collect(),
+        )
+    }
+
+    /// Select the whole value, leaving the cursor at the end
+    pub fn select_all(&self) {
+        let len = self.value.get().chars().count();
+        self.set_selection(Some(0), Some(len));
+        self.cursor_position.set(len);
+    }
+
+    /// Check if an edit action applies to the current value and selection
+    ///
+    /// Read-only inputs can still copy and select; password inputs never
+    /// put their value on the clipboard.
+    pub fn can_perform(&self, action: EditAction) -> bool {
+        if self.is_disabled() {
+            return false;
+        }
+        let editable = !self.is_readonly();
+        let has_selection = self.selected_range().is_some();
+        let revealable = self.input_type != InputType::Password;
+        match action {
+            EditAction::Cut => editable && revealable && has_selection,
+            EditAction::Copy => revealable && has_selection,
+            EditAction::Paste => editable && clipboard().text().is_some_and(|t| !t.is_empty()),
+            EditAction::SelectAll => !self.value.get().is_empty(),
+        }
+    }
+
+    /// Run an edit action; returns false if it does not apply right now
+    pub fn perform(&self, action: EditAction) -> bool {
+        if !self.can_perform(action) {
+            return false;
+        }
+        match action {
+            EditAction::Cut => {
+                if let Some(text) = self.selected_text() {
+                    clipboard().set_text(text);
+                }
+                self.replace_selection("");
+            }
+            EditAction::Copy => {
+                if let Some(text) = self.selected_text() {
+                    clipboard().set_text(text);
+                }
+            }
+            EditAction::Paste => {
+                if let Some(text) = clipboard().text() {
+                    self.replace_selection(&text);
+                }
+            }
+            EditAction::SelectAll => self.select_all(),
+        }
+        true
+    }
+
+    /// Open the edit context menu at `position`
+    pub fn open_context_menu(&mut self, position: Point) {
+        let items = CONTEXT_MENU_ACTIONS
+            .iter()
+            .map(|action| match action {
+                Some(action) => MenuItem::new(action.label())
+                    .accelerator(action.shortcut())
+                    .enabled(self.can_perform(*action)),
+                None => MenuItem::separator(),
+            })
+            .collect();
+        self.context_menu.open(items, position);
+    }
+
+    /// The edit context menu
+    pub fn context_menu(&self) -> &ContextMenu {
+        &self.context_menu
+    }
+
     /// Focus the input
     pub fn focus(&self) {
         if !self.is_disabled() && !self.is_readonly() {
@@ -598,7 +891,7 @@ impl TextInput {
 
     /// Calculate preferred size
     pub fn calculate_size(&self, available_size: Size) -> Size {
-        let style = self.style.for_state(self.state.get());
+        let style = self.style.for_state(self.state.get(), &self.palette);
         let padding = style.padding;
 
         let text_width = if self.multiline {
@@ -627,7 +920,7 @@ impl TextInput {
     pub fn layout(&self, bounds: Rect) {
         self.bounds.set(bounds);
 
-        let style = self.style.for_state(self.state.get());
+        let style = self.style.for_state(self.state.get(), &self.palette);
         let padding = style.padding;
 
         let content_bounds = Rect::new(
@@ -659,9 +952,24 @@ impl TextInput {
                 let content_bounds = self.content_bounds.get();
                 let relative_x = point.x - content_bounds.x;
 
+                if self.multiline {
+                    let relative_y = point.y - content_bounds.y + self.scroll_offset.get();
+                    let line = (relative_y / self.line_height()).max(0.0) as usize;
+                    let index = self.line_index();
+                    let line = line.min(index.line_count() - 1);
+                    self.cursor_position
+                        .set(self.offset_at_x(&index, line, relative_x));
+                    self.clear_selection();
+                    self.reset_caret_blink();
+                    return true;
+                }
+
+                self.reset_caret_blink();
+
                 // Simple cursor positioning (would need proper text measurement)
                 let char_width = self.style.font_size * 0.6;
-                let cursor_pos = ((relative_x / char_width) as usize).min(self.value.get().len());
+                let cursor_pos =
+                    ((relative_x / char_width) as usize).min(self.value.get().chars().count());
                 self.cursor_position.set(cursor_pos);
 
                 return true;
@@ -673,8 +981,29 @@ impl TextInput {
     }
 
     /// Handle keyboard events
+    ///
+    /// Any key the input acts on restarts the caret blink with the caret shown.
     pub fn handle_key_event(&self, event: &KeyboardEvent) -> bool {
-        if !self.is_focused() || self.is_disabled() || self.is_readonly() {
+        let handled = self.apply_key_event(event);
+        if handled {
+            self.reset_caret_blink();
+        }
+        handled
+    }
+
+    fn apply_key_event(&self, event: &KeyboardEvent) -> bool {
+        if !self.is_focused() || self.is_disabled() {
+            return false;
+        }
+
+        if let Some(action) = EditAction::ALL
+            .into_iter()
+            .find(|action| action.shortcut().matches(event))
+        {
+            return self.perform(action);
+        }
+
+        if self.is_readonly() {
             return false;
         }
 
@@ -702,11 +1031,21 @@ impl TextInput {
                 true
             }
             KeyCode::Left => {
-                self.move_cursor_left();
+                self.extend_selection_while(event.modifiers.shift, || self.move_cursor_left());
                 true
             }
             KeyCode::Right => {
-                self.move_cursor_right();
+                self.extend_selection_while(event.modifiers.shift, || self.move_cursor_right());
+                true
+            }
+            KeyCode::Up if self.multiline => {
+                self.extend_selection_while(event.modifiers.shift, || {
+                    self.move_cursor_vertical(-1)
+                });
+                true
+            }
+            KeyCode::Down if self.multiline => {
+                self.extend_selection_while(event.modifiers.shift, || self.move_cursor_vertical(1));
                 true
             }
             KeyCode::Enter => {
@@ -718,43 +1057,71 @@ impl TextInput {
                 true
             }
             KeyCode::Home => {
-                self.cursor_position.set(0);
+                let target = if self.multiline {
+                    let index = self.line_index();
+                    let (line, _) = index.position(self.cursor_position.get());
+                    index.line_range(line).start
+                } else {
+                    0
+                };
+                self.extend_selection_while(event.modifiers.shift, || {
+                    self.cursor_position.set(target)
+                });
                 true
             }
             KeyCode::End => {
-                self.cursor_position.set(self.value.get().len());
+                let target = if self.multiline {
+                    let index = self.line_index();
+                    let (line, _) = index.position(self.cursor_position.get());
+                    index.line_range(line).end
+                } else {
+                    self.value.get().chars().count()
+                };
+                self.extend_selection_while(event.modifiers.shift, || {
+                    self.cursor_position.set(target)
+                });
                 true
             }
             _ => false,
         }
     }
 
-    /// Insert character at cursor
+    /// Insert character at cursor, replacing the selection if any
     fn insert_char(&self, ch: char) {
-        let mut value = self.value.get();
-        let cursor_pos = self.cursor_position.get();
-
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if value.len() >= max_len {
-                return;
-            }
-        }
+        self.replace_selection(ch.encode_utf8(&mut [0; 4]));
+    }
 
-        // Insert character
-        if cursor_pos <= value.len() {
-            value.insert(cursor_pos, ch);
-            self.value.set(value.clone());
-            self.cursor_position.set(cursor_pos + 1);
+    /// Replace the selection, or insert at the cursor, with `text`
+    ///
+    /// Control characters are dropped (newlines are kept in multiline
+    /// inputs) and the text is cut short to respect the maximum length.
+    fn replace_selection(&self, text: &str) {
+        let mut chars: Vec<char> = self.value.get().chars().collect();
+        let cursor = self.cursor_position.get().min(chars.len());
+        let (start, end) = self.selected_range().unwrap_or((cursor, cursor));
 
-            // Trigger change callback
-            if let Some(ref callback) = self.on_change {
-                callback(&value);
-            }
+        let room = self.max_length.map_or(usize::MAX, |max| {
+            max.saturating_sub(chars.len() - (end - start))
+        });
+        let inserted: Vec<char> = text
+            .chars()
+            .filter(|&ch| !ch.is_control() || (self.multiline && ch == '\n'))
+            .take(room)
+            .collect();
+
+        chars.splice(start..end, inserted.iter().copied());
+        let value: String = chars.into_iter().collect();
+        self.value.set(value.clone());
+        self.cursor_position.set(start + inserted.len());
+        self.clear_selection();
+        if self.multiline {
+            self.ensure_cursor_visible();
+        }
 
-            // Validate
-            self.validate();
+        if let Some(ref callback) = self.on_change {
+            callback(&value);
         }
+        self.validate();
     }
 
     /// Delete character before cursor
@@ -762,8 +1129,8 @@ impl TextInput {
         let mut value = self.value.get();
         let cursor_pos = self.cursor_position.get();
 
-        if cursor_pos > 0 && cursor_pos <= value.len() {
-            value.remove(cursor_pos - 1);
+        if cursor_pos > 0 && cursor_pos <= value.chars().count() {
+            value.remove(byte_index(&value, cursor_pos - 1));
             self.value.set(value.clone());
             self.cursor_position.set(cursor_pos - 1);
 
@@ -782,8 +1149,8 @@ impl TextInput {
         let mut value = self.value.get();
         let cursor_pos = self.cursor_position.get();
 
-        if cursor_pos < value.len() {
-            value.remove(cursor_pos);
+        if cursor_pos < value.chars().count() {
+            value.remove(byte_index(&value, cursor_pos));
             self.value.set(value.clone());
 
             // Trigger change callback
@@ -807,32 +1174,225 @@ impl TextInput {
     /// Move cursor right
     fn move_cursor_right(&self) {
         let cursor_pos = self.cursor_position.get();
-        let value_len = self.value.get().len();
+        let value_len = self.value.get().chars().count();
         if cursor_pos < value_len {
             self.cursor_position.set(cursor_pos + 1);
         }
     }
 
+    /// Move the cursor, extending the selection from its anchor if `extend` is set
+    fn extend_selection_while(&self, extend: bool, move_cursor: impl FnOnce()) {
+        let anchor = self
+            .selection_start
+            .get()
+            .unwrap_or(self.cursor_position.get());
+        move_cursor();
+        if extend {
+            self.set_selection(Some(anchor), Some(self.cursor_position.get()));
+        } else {
+            self.clear_selection();
+        }
+        if self.multiline {
+            self.ensure_cursor_visible();
+        }
+    }
+
+    /// Move the cursor to the visually adjacent line, keeping its x position
+    fn move_cursor_vertical(&self, direction: isize) {
+        let index = self.line_index();
+        let (line, column) = index.position(self.cursor_position.get());
+        let Some(target) = line
+            .checked_add_signed(direction)
+            .filter(|&target| target < index.line_count())
+        else {
+            return;
+        };
+
+        let value = self.value.get();
+        let range = index.line_range(line);
+        let prefix: String = value.chars().skip(range.start).take(column).collect();
+        let x = crate::text::measure_text_width(&prefix, self.style.font_size, 0.0);
+        self.cursor_position
+            .set(self.offset_at_x(&index, target, x));
+    }
+
+    /// Char offset on `line` closest to the horizontal position `x`
+    fn offset_at_x(&self, index: &LineIndex, line: usize, x: f32) -> usize {
+        let value = self.value.get();
+        let range = index.line_range(line);
+        let mut width = 0.0;
+        for (column, ch) in value
+            .chars()
+            .skip(range.start)
+            .take(range.len())
+            .enumerate()
+        {
+            let advance = crate::text::measure_text_width(
+                ch.encode_utf8(&mut [0; 4]),
+                self.style.font_size,
+                0.0,
+            );
+            if x < width + advance / 2.0 {
+                return range.start + column;
+            }
+            width += advance;
+        }
+        range.end
+    }
+
+    /// Height of one line of text
+    fn line_height(&self) -> f32 {
+        self.style.font_size * self.style.line_height
+    }
+
+    /// Line index of the current value, wrapped to the content width
+    pub fn line_index(&self) -> LineIndex {
+        let width = self.content_bounds.get().width;
+        if self.multiline && width > 0.0 {
+            LineIndex::wrapped(&self.value.get(), width, self.style.font_size)
+        } else {
+            LineIndex::new(&self.value.get())
+        }
+    }
+
+    /// Scroll vertically so the cursor line is inside the content area
+    fn ensure_cursor_visible(&self) {
+        let (line, _) = self.line_index().position(self.cursor_position.get());
+        let line_height = self.line_height();
+        let visible_height = self.content_bounds.get().height.max(line_height);
+        let top = line as f32 * line_height;
+        let scroll = self.scroll_offset.get();
+        if top < scroll {
+            self.scroll_offset.set(top);
+        } else if top + line_height > scroll + visible_height {
+            self.scroll_offset.set(top + line_height - visible_height);
+        }
+    }
+
+    /// Render the wrapped lines, selection, cursor and scrollbar of a multiline input
+    fn render_multiline(&self, batch: &mut RenderBatch, style: &InputStyle) {
+        let content_bounds = self.content_bounds.get();
+        let line_height = self.line_height();
+        let scroll = self.scroll_offset.get();
+        let value = self.value.get();
+        let chars: Vec<char> = value.chars().collect();
+        let index = self.line_index();
+        let selection = self
+            .get_selection()
+            .map(|(start, end)| (start.min(end), start.max(end)));
+
+        let width_of = |range: std::ops::Range<usize>| {
+            let text: String = chars[range].iter().collect();
+            crate::text::measure_text_width(&text, style.font_size, 0.0)
+        };
+
+        // Lines scrolled partly out of view are cut at the content edges
+        batch.push_clip(content_bounds);
+        for line in 0..index.line_count() {
+            let y = content_bounds.y + line as f32 * line_height - scroll;
+            if y + line_height < content_bounds.y || y > content_bounds.y + content_bounds.height {
+                continue;
+            }
+            let range = index.line_range(line);
+
+            if let Some((start, end)) = selection {
+                let from = start.clamp(range.start, range.end);
+                let to = end.clamp(range.start, range.end);
+                if from < to {
+                    let x = content_bounds.x + width_of(range.start..from);
+                    batch.add_rect(
+                        Rect::new(x, y, width_of(from..to), line_height),
+                        style.selection_color,
+                        Transform::identity(),
+                    );
+                }
+            }
+
+            let text: String = chars[range.clone()].iter().collect();
+            if !text.is_empty() {
+                batch.add_text(
+                    text,
+                    (content_bounds.x, y),
+                    style.text_color,
+                    style.font_size,
+                    0.0,
+                );
+            }
+        }
+
+        if self.caret_visible() {
+            let (line, column) = index.position(self.cursor_position.get());
+            let range = index.line_range(line);
+            let x = content_bounds.x + width_of(range.start..range.start + column);
+            let y = content_bounds.y + line as f32 * line_height - scroll;
+            batch.add_line((x, y), (x, y + line_height), style.cursor_color, 1.0);
+        }
+        batch.pop_clip();
+
+        // Vertical scrollbar once the text no longer fits
+        let content_height = index.line_count() as f32 * line_height;
+        if content_height > content_bounds.height && content_bounds.height > 0.0 {
+            let bounds = self.bounds.get();
+            let track = Rect::new(
+                bounds.x + bounds.width - 6.0,
+                content_bounds.y,
+                4.0,
+                content_bounds.height,
+            );
+            let thumb_height = (content_bounds.height / content_height * track.height).max(12.0);
+            let max_scroll = content_height - content_bounds.height;
+            let thumb_y = track.y + (scroll / max_scroll).min(1.0) * (track.height - thumb_height);
+            batch.add_rect(track, style.border_color, Transform::identity());
+            batch.add_rect(
+                Rect::new(track.x, thumb_y, track.width, thumb_height),
+                style.placeholder_color,
+                Transform::identity(),
+            );
+        }
+    }
+
     /// Update input (called each frame)
     pub fn update(&self, delta_time: f32) {
-        // Update cursor blink timer
-        let mut timer = self.cursor_blink_timer.get();
-        timer += delta_time;
-        if timer >= 1.0 {
-            timer = 0.0;
-        }
+        // Advance the caret blink phase; a steady caret stays at the start
+        let timer = match self.style.caret_blink {
+            Some(period) if period.as_secs_f32() > 0.0 => {
+                (self.cursor_blink_timer.get() + delta_time) % period.as_secs_f32()
+            }
+            _ => 0.0,
+        };
         self.cursor_blink_timer.set(timer);
     }
 
+    /// Whether the caret is drawn this frame
+    fn caret_visible(&self) -> bool {
+        if !self.is_focused() {
+            return false;
+        }
+        match self.style.caret_blink {
+            Some(period) => self.cursor_blink_timer.get() < period.as_secs_f32() / 2.0,
+            None => true,
+        }
+    }
+
+    /// Show the caret and restart its blink, after typing or moving the cursor
+    fn reset_caret_blink(&self) {
+        self.cursor_blink_timer.set(0.0);
+    }
+
     /// Render the input
     pub fn render(&self, batch: &mut RenderBatch) {
         let bounds = self.bounds.get();
         let content_bounds = self.content_bounds.get();
-        let style = self.style.for_state(self.state.get());
+        let style = self.style.for_state(self.state.get(), &self.palette);
 
         // Render background
         batch.add_rect(bounds, style.background_color, Transform::identity());
 
+        if self.multiline && !self.value.get().is_empty() {
+            self.render_multiline(batch, &style);
+            return;
+        }
+
         // Render text or placeholder
         let value = self.value.get();
         let text_to_render = if value.is_empty() && !self.placeholder.is_empty() {
@@ -860,7 +1420,7 @@ impl TextInput {
         }
 
         // Render cursor if focused
-        if self.is_focused() && self.cursor_blink_timer.get() < 0.5 {
+        if self.caret_visible() {
             let cursor_pos = self.cursor_position.get();
             let char_width = style.font_size * 0.6;
             let cursor_x = content_bounds.x + (cursor_pos as f32) * char_width;
@@ -874,7 +1434,7 @@ impl TextInput {
         }
 
         // Render selection if any
-        if let Some((start, end)) = self.get_selection() {
+        if let Some((start, end)) = self.selected_range() {
             let char_width = style.font_size * 0.6;
             let selection_start_x = content_bounds.x + (start as f32) * char_width;
             let selection_end_x = content_bounds.x + (end as f32) * char_width;
@@ -905,9 +1465,13 @@ impl Widget for TextInput {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         let available_size = Size::new(constraints.max_width, constraints.max_height);
-        let size = self.calculate_size(available_size);
+        let size = constraints.constrain(self.calculate_size(available_size));
         let bounds = Rect::new(0.0, 0.0, size.width, size.height);
         TextInput::layout(self, bounds);
         size
@@ -920,12 +1484,77 @@ impl Widget for TextInput {
             layout.size.width,
             layout.size.height,
         );
+        register_bounds(self.id, bounds);
         self.layout(bounds);
         self.render(batch);
+        self.context_menu.render(batch);
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.is_disabled()
+    }
+
+    fn is_focused(&self) -> bool {
+        TextInput::is_focused(self)
+    }
+
+    fn is_focusable(&self) -> bool {
+        !self.is_disabled() && !self.is_readonly()
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.context_menu.overlay_order()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.focus();
+        } else {
+            self.blur();
+        }
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        TextInput::update(self, ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.bounds
+            .get()
+            .contains(point)
+            .then_some(CursorIcon::Text)
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        if self.is_disabled() {
+            return EventResult::Ignored;
+        }
+
+        match self.context_menu.handle_event(event) {
+            ContextMenuResponse::Selected(index) => {
+                if let Some(Some(action)) = CONTEXT_MENU_ACTIONS.get(index) {
+                    self.perform(*action);
+                }
+                return EventResult::Handled;
+            }
+            ContextMenuResponse::Handled => return EventResult::Handled,
+            ContextMenuResponse::Ignored => {}
+        }
+
         match event {
+            Event::MouseDown(mouse_event)
+                if mouse_event.button == Some(MouseButton::Right)
+                    && self.bounds.get().contains(mouse_event.position.into()) =>
+            {
+                self.focus();
+                self.open_context_menu(mouse_event.position.into());
+                EventResult::Handled
+            }
             Event::MouseDown(mouse_event) => {
                 if self.handle_mouse_event(mouse_event) {
                     EventResult::Handled
@@ -954,6 +1583,7 @@ impl Widget for TextInput {
                             self.insert_char(ch);
                         }
                     }
+                    self.reset_caret_blink();
                     EventResult::Handled
                 } else {
                     EventResult::Ignored
@@ -976,10 +1606,11 @@ impl Widget for TextInput {
     }
 }
 
+/// Clones share the validators and handlers but start unfocused
 impl Clone for TextInput {
     fn clone(&self) -> Self {
         Self {
-            id: generate_id(), // Generate new ID for clone
+            id: clone_id(self.id),
             input_type: self.input_type,
             value: Signal::new(self.value.get()),
             placeholder: self.placeholder.clone(),
@@ -992,11 +1623,15 @@ impl Clone for TextInput {
             multiline: self.multiline,
             rows: self.rows,
             cols: self.cols,
-            state: Signal::new(self.state.get()),
+            tab_index: self.tab_index,
+            state: Signal::new(match self.state.get() {
+                InputState::Focused | InputState::Hovered => InputState::Normal,
+                state => state,
+            }),
             validation_state: Signal::new(self.validation_state.get()),
             validation_message: Signal::new(self.validation_message.get()),
-            focused: Signal::new(self.focused.get()),
-            hovered: Signal::new(self.hovered.get()),
+            focused: Signal::new(false),
+            hovered: Signal::new(false),
             cursor_position: Signal::new(self.cursor_position.get()),
             selection_start: Signal::new(self.selection_start.get()),
             selection_end: Signal::new(self.selection_end.get()),
@@ -1005,13 +1640,15 @@ impl Clone for TextInput {
             visible: Signal::new(self.visible.get()),
             style: self.style.clone(),
             theme: self.theme.clone(),
-            validators: Vec::new(), // Don't clone validators as they contain closures
-            on_change: None,        // Don't clone event handlers
-            on_focus: None,
-            on_blur: None,
-            on_submit: None,
+            palette: self.palette.clone(),
+            validators: self.validators.clone(),
+            on_change: self.on_change.clone(),
+            on_focus: self.on_focus.clone(),
+            on_blur: self.on_blur.clone(),
+            on_submit: self.on_submit.clone(),
             cursor_blink_timer: Signal::new(self.cursor_blink_timer.get()),
             scroll_offset: Signal::new(self.scroll_offset.get()),
+            context_menu: ContextMenu::new(),
         }
     }
 }
@@ -1092,6 +1729,7 @@ impl Default for TextInputBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use strato_renderer::batch::DrawCommand;
 
     #[test]
     fn test_input_creation() {
@@ -1124,6 +1762,253 @@ mod tests {
         assert!(input.validate());
     }
 
+    #[test]
+    fn test_line_index_positions() {
+        let index = LineIndex::new("ab\ncde\n");
+        assert_eq!(index.line_count(), 3);
+        assert_eq!(index.position(2), (0, 2));
+        assert_eq!(index.position(3), (1, 0));
+        assert_eq!(index.position(7), (2, 0));
+        assert_eq!(index.offset(1, 10), 6);
+    }
+
+    #[test]
+    fn test_line_index_wraps_at_words() {
+        let width = crate::text::measure_text_width("hello wor", 14.0, 0.0);
+        let index = LineIndex::wrapped("hello world", width, 14.0);
+        assert_eq!(index.line_count(), 2);
+        assert_eq!(index.line_range(1), 6..11);
+        // The end of a soft-wrapped line belongs to the next line
+        assert_eq!(index.position(6), (1, 0));
+    }
+
+    fn focused_text_area(value: &str) -> TextInput {
+        let mut input = TextInput::new().multiline(true).rows(2).value(value);
+        Widget::layout(&mut input, Constraints::loose(400.0, 400.0));
+        input.focus();
+        input
+    }
+
+    fn press(input: &TextInput, key_code: KeyCode) {
+        input.handle_key_event(&KeyboardEvent {
+            key_code,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        });
+    }
+
+    #[test]
+    fn test_multiline_vertical_navigation() {
+        let input = focused_text_area("first line\nab\nthird line");
+        input.cursor_position.set(5);
+
+        press(&input, KeyCode::Down);
+        // "ab" is shorter than the column, so the cursor lands at its end
+        assert_eq!(
+            input.line_index().position(input.cursor_position.get()),
+            (1, 2)
+        );
+
+        press(&input, KeyCode::Down);
+        assert_eq!(
+            input.line_index().position(input.cursor_position.get()).0,
+            2
+        );
+
+        press(&input, KeyCode::Up);
+        press(&input, KeyCode::Up);
+        assert_eq!(
+            input.line_index().position(input.cursor_position.get()).0,
+            0
+        );
+    }
+
+    #[test]
+    fn test_multiline_enter_inserts_newline_and_scrolls() {
+        let input = focused_text_area("");
+        for _ in 0..3 {
+            press(&input, KeyCode::Enter);
+        }
+
+        assert_eq!(input.get_value(), "\n\n\n");
+        assert_eq!(input.line_index().line_count(), 4);
+        // Only two rows are visible, so the view scrolled to the cursor
+        assert!(input.scroll_offset.get() > 0.0);
+    }
+
+    #[test]
+    fn test_typing_replaces_the_selection() {
+        let input = TextInput::new().value("hello world");
+        input.focus();
+        input.set_selection(Some(0), Some(5));
+        input.cursor_position.set(5);
+        input.handle_key_event(&KeyboardEvent {
+            key_code: KeyCode::H,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: Some("J".to_string()),
+        });
+        assert_eq!(input.get_value(), "J world");
+        assert_eq!(input.cursor_position.get(), 1);
+        assert_eq!(input.get_selection(), None);
+    }
+
+    #[test]
+    fn test_multiline_text_is_clipped_to_the_content() {
+        let input = focused_text_area("one\ntwo\nthree\nfour");
+        let mut batch = RenderBatch::new();
+        input.render(&mut batch);
+
+        let content = input.content_bounds.get();
+        let clip = batch
+            .commands
+            .iter()
+            .position(|command| matches!(command, DrawCommand::PushClip(rect) if *rect == content));
+        let text = batch
+            .commands
+            .iter()
+            .position(|command| matches!(command, DrawCommand::Text { .. }));
+        assert!(clip.is_some() && clip < text, "{clip:?} before {text:?}");
+        assert!(batch
+            .commands
+            .iter()
+            .any(|command| matches!(command, DrawCommand::PopClip)));
+    }
+
+    fn shortcut(input: &TextInput, action: EditAction) -> bool {
+        let shortcut = action.shortcut();
+        input.handle_key_event(&KeyboardEvent {
+            key_code: shortcut.key,
+            modifiers: shortcut.modifiers,
+            is_repeat: false,
+            text: None,
+        })
+    }
+
+    fn mouse_down(x: f32, y: f32, button: MouseButton) -> Event {
+        Event::MouseDown(MouseEvent {
+            position: glam::Vec2::new(x, y),
+            button: Some(button),
+            modifiers: Default::default(),
+            delta: glam::Vec2::ZERO,
+        })
+    }
+
+    #[test]
+    fn test_edit_shortcuts_share_keyboard_selection() {
+        let input = TextInput::new().value("hello world");
+        input.focus();
+
+        assert!(shortcut(&input, EditAction::SelectAll));
+        assert_eq!(input.selected_range(), Some((0, 11)));
+
+        // Shift+Left shrinks the same selection the shortcuts act on
+        input.handle_key_event(&KeyboardEvent {
+            key_code: KeyCode::Left,
+            modifiers: strato_core::event::Modifiers {
+                shift: true,
+                ..Default::default()
+            },
+            is_repeat: false,
+            text: None,
+        });
+        assert_eq!(input.selected_text().as_deref(), Some("hello worl"));
+
+        assert!(shortcut(&input, EditAction::Cut));
+        assert_eq!(input.get_value(), "d");
+        assert_eq!(input.cursor_position.get(), 0);
+
+        assert!(shortcut(&input, EditAction::Paste));
+        assert_eq!(input.get_value(), "hello world");
+
+        // Pasting replaces the selection and respects the maximum length
+        let short = TextInput::new().value("abc").max_length(5);
+        short.focus();
+        short.set_selection(Some(1), Some(2));
+        assert!(shortcut(&short, EditAction::Paste));
+        assert_eq!(short.get_value(), "ahelc");
+    }
+
+    #[test]
+    fn test_context_menu_enables_items_from_state() {
+        let mut input = TextInput::new().value("secret").readonly(true);
+        Widget::layout(&mut input, Constraints::loose(400.0, 400.0));
+        input.set_selection(Some(0), Some(3));
+
+        assert_eq!(
+            input.handle_event(&mouse_down(10.0, 10.0, MouseButton::Right)),
+            EventResult::Handled
+        );
+        let enabled: Vec<(&str, bool)> = input
+            .context_menu()
+            .items()
+            .iter()
+            .filter(|item| !item.is_separator())
+            .map(|item| (item.label(), item.is_enabled()))
+            .collect();
+        assert_eq!(
+            enabled,
+            vec![
+                ("Cut", false),
+                ("Copy", true),
+                ("Paste", false),
+                ("Select All", true)
+            ]
+        );
+
+        // Choosing an item runs the action on the input's selection
+        let select_all_y = 10.0 + 4.0 + 26.0 * 3.0 + 9.0 + 13.0;
+        input.handle_event(&mouse_down(20.0, select_all_y, MouseButton::Left));
+        assert!(!input.context_menu().is_open());
+        assert_eq!(input.selected_range(), Some((0, 6)));
+        assert_eq!(input.get_value(), "secret");
+
+        let password = TextInput::new()
+            .value("secret")
+            .input_type(InputType::Password);
+        password.select_all();
+        assert!(!password.can_perform(EditAction::Copy));
+        assert!(!password.can_perform(EditAction::Cut));
+    }
+
+    #[test]
+    fn test_typing_resets_caret_blink() {
+        let mut input = TextInput::new();
+        input.focus();
+        assert!(input.caret_visible());
+
+        // Past the visible half of the one second default period
+        input.update(0.7);
+        assert!(!input.caret_visible());
+
+        input.handle_event(&Event::TextInput("a".to_string()));
+        assert!(input.caret_visible());
+        assert_eq!(input.cursor_blink_timer.get(), 0.0);
+
+        input.update(0.7);
+        press(&input, KeyCode::Left);
+        assert!(input.caret_visible());
+    }
+
+    #[test]
+    fn test_steady_caret_never_blinks() {
+        let input = TextInput::new().style(InputStyle::default().caret_blink(None));
+        input.focus();
+        for _ in 0..10 {
+            input.update(0.3);
+            assert!(input.caret_visible());
+        }
+
+        let fast = TextInput::new()
+            .style(InputStyle::default().caret_blink(Some(Duration::from_millis(400))));
+        fast.focus();
+        fast.update(0.25);
+        assert!(!fast.caret_visible());
+        fast.update(0.2);
+        assert!(fast.caret_visible());
+    }
+
     #[test]
     fn test_input_builder() {
         let input = TextInputBuilder::new()
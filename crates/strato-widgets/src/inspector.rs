@@ -4,16 +4,17 @@ use std::collections::HashMap;
 
 use glam::Vec2;
 use strato_core::event::{Event, EventResult, KeyCode, KeyboardEvent, Modifiers};
+use strato_core::hit_test::hit_test;
 use strato_core::inspector::{self, ComponentNodeSnapshot, InspectorSnapshot, LayoutBoxSnapshot};
 use strato_core::layout::{Constraints, Layout, Size};
-use strato_core::types::{Color, Rect, Transform};
+use strato_core::types::{BorderRadius, Color, Point, Rect, Transform};
 use strato_renderer::batch::RenderBatch;
 
 use crate::container::Container;
 use crate::layout::Column;
 use crate::scroll_view::ScrollView;
 use crate::text::Text;
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::widget::{clone_id, dispatch_event, generate_id, widgets_at, Widget, WidgetId};
 use slotmap::Key;
 
 const DEFAULT_PANEL_WIDTH: f32 = 340.0;
@@ -29,6 +30,8 @@ pub struct InspectorOverlay {
     cached_child_size: Size,
     panel: Option<Box<dyn Widget>>,
     panel_size: Option<Size>,
+    /// Last pointer position, to outline the widget under it
+    pointer: Option<Point>,
 }
 
 impl InspectorOverlay {
@@ -50,6 +53,7 @@ impl InspectorOverlay {
             cached_child_size: Size::zero(),
             panel: None,
             panel_size: None,
+            pointer: None,
         }
     }
 
@@ -182,6 +186,10 @@ impl Widget for InspectorOverlay {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
@@ -198,14 +206,14 @@ impl Widget for InspectorOverlay {
         // `Box<dyn Widget>` isn't automatically cloneable unless `Widget` has `clone_widget`.
         // We can use the child's `clone_widget` method.
         Box::new(Self {
-            id: generate_id(), // Generate new ID on clone? Or copy? Usually clone implies new ID for widgets or copy?
-            // BaseWidget generates new ID.
+            id: clone_id(self.id),
             child: self.child.clone_widget(),
             shortcut: self.shortcut,
             visible: self.visible,
             cached_child_size: self.cached_child_size,
             panel: self.panel.as_ref().map(|p| p.clone_widget()),
             panel_size: self.panel_size,
+            pointer: None,
         })
     }
 
@@ -263,6 +271,21 @@ impl Widget for InspectorOverlay {
                 );
             }
 
+            // Outline the deepest widget under the pointer
+            let hovered = self
+                .pointer
+                .and_then(|point| widgets_at(self.child.as_ref(), point).first().copied())
+                .and_then(|id| hit_test().bounds(strato_core::widget::WidgetId(id)));
+            if let Some(bounds) = hovered {
+                batch.add_stroke(
+                    bounds,
+                    Color::rgba(1.0, 0.6, 0.1, 0.9),
+                    2.0,
+                    BorderRadius::default(),
+                    Transform::identity(),
+                );
+            }
+
             if let (Some(panel), Some(panel_size)) = (&self.panel, self.panel_size) {
                 let panel_pos = Vec2::new(
                     layout.position.x + layout.size.width - panel_size.width - 12.0,
@@ -285,6 +308,9 @@ impl Widget for InspectorOverlay {
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::MouseMove(mouse) = event {
+            self.pointer = Some(Point::new(mouse.position.x, mouse.position.y));
+        }
         if let Event::KeyDown(key) = event {
             if self.shortcut_pressed(key) {
                 let now_visible = !self.visible;
@@ -294,6 +320,6 @@ impl Widget for InspectorOverlay {
             }
         }
 
-        self.child.handle_event(event)
+        dispatch_event(self.child.as_mut(), event)
     }
 }
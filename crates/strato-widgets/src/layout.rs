@@ -1,7 +1,11 @@
 //! Layout widgets for arranging child widgets
 
 use std::any::Any;
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::reconcile::ChildSlots;
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, register_bounds, Widget, WidgetId,
+};
+use rayon::prelude::*;
 use strato_core::taffy::{
     prelude::*,
     style::{AlignItems, Dimension, FlexDirection, JustifyContent},
@@ -9,10 +13,12 @@ use strato_core::taffy::{
 use strato_core::{
     event::{Event, EventResult},
     layout::{
-        AlignItems as CoreAlignItems, Constraints, FlexContainer, FlexDirection as CoreFlexDirection,
-        FlexItem, JustifyContent as CoreJustifyContent, Layout, Size,
+        layout_config, AlignItems as CoreAlignItems, Constraints, FlexContainer,
+        FlexDirection as CoreFlexDirection, FlexItem, JustifyContent as CoreJustifyContent, Layout,
+        LayoutCache, Size,
     },
     taffy_layout::{TaffyLayoutError, TaffyLayoutResult, TaffyWidget},
+    types::Rect,
 };
 use strato_renderer::batch::RenderBatch;
 
@@ -83,6 +89,82 @@ impl CrossAxisAlignment {
     }
 }
 
+/// Lay out every child with the same constraints
+///
+/// The children of a row or column are measured independently of each
+/// other, so when parallel layout is enabled in the
+/// [`LayoutConfig`](strato_core::layout::LayoutConfig) and there are enough
+/// children they are laid out on the rayon pool. Sizes come back in child
+/// order either way.
+fn layout_children(
+    cache: &LayoutCache,
+    children: &mut [Box<dyn Widget>],
+    constraints: Constraints,
+) -> Vec<Size> {
+    let config = layout_config();
+    if config.parallel && children.len() >= config.parallel_threshold {
+        children
+            .par_iter_mut()
+            .map(|child| layout_child(cache, child.as_mut(), constraints))
+            .collect()
+    } else {
+        children
+            .iter_mut()
+            .map(|child| layout_child(cache, child.as_mut(), constraints))
+            .collect()
+    }
+}
+
+/// Draw every child at its layout, in child order
+///
+/// With parallel layout enabled and enough children, each child is drawn
+/// into a [segment](RenderBatch::segment) on the rayon pool and the segments
+/// are merged in child order, which gives the same batch as drawing them one
+/// after the other. Children of rows and columns don't overlap, so the order
+/// they register their bounds for hit testing in doesn't matter.
+fn render_children(batch: &mut RenderBatch, children: &[Box<dyn Widget>], layouts: &[Layout]) {
+    let config = layout_config();
+    if config.parallel && children.len() >= config.parallel_threshold {
+        let template = &*batch;
+        let segments: Vec<RenderBatch> = children
+            .par_iter()
+            .zip(layouts)
+            .map(|(child, layout)| {
+                let mut segment = template.segment();
+                child.render(&mut segment, *layout);
+                segment
+            })
+            .collect();
+        for segment in segments {
+            batch.merge(segment);
+        }
+    } else {
+        for (child, layout) in children.iter().zip(layouts) {
+            child.render(batch, *layout);
+        }
+    }
+}
+
+/// Constraints for measuring the children of a row or column
+///
+/// Children may be smaller than the container on both axes.
+fn relaxed(constraints: Constraints) -> Constraints {
+    Constraints {
+        min_width: 0.0,
+        max_width: constraints.max_width,
+        min_height: 0.0,
+        max_height: constraints.max_height,
+    }
+}
+
+/// How a child of a row or column grows, from a [`Flex`] wrapper if any
+fn flex_item(child: &dyn Widget) -> FlexItem {
+    match child.as_any().downcast_ref::<Flex>() {
+        Some(flex) => FlexItem::grow(flex.flex),
+        None => FlexItem::default(),
+    }
+}
+
 /// Row widget for horizontal layout
 #[derive(Debug)]
 pub struct Row {
@@ -93,13 +175,13 @@ pub struct Row {
     spacing: f32,
     // Layout cache computed during layout()
     cached_child_sizes: Vec<Size>,
+    layout_cache: LayoutCache,
 }
 
 
 
 
 
-
 impl Row {
     /// Create a new row
     pub fn new() -> Self {
@@ -110,6 +192,7 @@ impl Row {
             cross_axis_alignment: CrossAxisAlignment::Center,
             spacing: 0.0,
             cached_child_sizes: Vec::new(),
+            layout_cache: LayoutCache::new(),
         }
     }
 
@@ -142,50 +225,54 @@ impl Row {
         self.spacing = spacing;
         self
     }
-}
-
-impl Widget for Row {
-    fn id(&self) -> WidgetId {
-        self.id
-    }
 
-    fn layout(&mut self, constraints: Constraints) -> Size {
-        let engine = strato_core::layout::LayoutEngine::new();
-
-        // Relax constraints for children measurement
-        let child_constraints = Constraints {
-            min_width: 0.0,
-            max_width: constraints.max_width,
-            min_height: 0.0,
-            max_height: constraints.max_height,
-        };
-
-        // Calculate child sizes
-        let mut child_data = Vec::new();
-        let mut sizes = Vec::with_capacity(self.children.len());
-        for child in &mut self.children {
-            let child_size = child.layout(child_constraints);
-            sizes.push(child_size);
-
-            let mut flex_item = FlexItem::default();
-            if let Some(flex) = child.as_any().downcast_ref::<Flex>() {
-                flex_item = FlexItem::grow(flex.flex);
+    /// Measure the children, then lay them out at their final sizes
+    fn compute_layout(&mut self, constraints: Constraints) -> Size {
+        // The row owns its children, so it measures them by laying them out
+        // with relaxed constraints, keeping their caches warm for the next pass
+        let sizes = layout_children(&self.layout_cache, &mut self.children, relaxed(constraints));
+        let layouts = self.place_children(&sizes, constraints);
+
+        // Flex children grow and stretched children fill the cross axis, so
+        // they are laid out again at the size they are drawn at
+        for ((child, measured), placed) in self.children.iter_mut().zip(&sizes).zip(&layouts) {
+            if placed.size != *measured {
+                layout_child(
+                    &self.layout_cache,
+                    child.as_mut(),
+                    Constraints::tight_size(placed.size),
+                );
             }
-            child_data.push((flex_item, child_size));
         }
+
         // Cache sizes for use during render()
         self.cached_child_sizes = sizes;
+        Self::extent(&layouts)
+    }
 
-        // Calculate layout
+    /// Place children of the given sizes within `constraints`
+    fn place_children(&self, sizes: &[Size], constraints: Constraints) -> Vec<Layout> {
+        let child_data: Vec<(FlexItem, Size)> = self
+            .children
+            .iter()
+            .zip(sizes)
+            .map(|(child, &size)| (flex_item(child.as_ref()), size))
+            .collect();
         let container = FlexContainer {
             direction: CoreFlexDirection::Row,
             justify_content: self.main_axis_alignment.to_core(),
             align_items: self.cross_axis_alignment.to_core(),
             ..Default::default()
         };
-        let layouts = engine.calculate_flex_layout(&container, &child_data, constraints);
+        strato_core::layout::LayoutEngine::new().calculate_flex_layout(
+            &container,
+            &child_data,
+            constraints,
+        )
+    }
 
-        // Calculate total size
+    /// The size of the row around its placed children
+    fn extent(layouts: &[Layout]) -> Size {
         let width = layouts
             .iter()
             .map(|l| l.position.x + l.size.width)
@@ -199,49 +286,76 @@ impl Widget for Row {
 
         Size::new(width, height)
     }
+}
+
+impl Widget for Row {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
 
-    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
-        let engine = strato_core::layout::LayoutEngine::new();
-
-        // Calculate child layouts using cached sizes measured in layout()
-        let mut child_data = Vec::new();
-        for (i, child) in self.children.iter().enumerate() {
-            let child_size = self
-                .cached_child_sizes
-                .get(i)
-                .copied()
-                .unwrap_or_else(|| Size::new(100.0, 50.0));
-
-            let mut flex_item = FlexItem::default();
-            if let Some(flex) = child.as_any().downcast_ref::<Flex>() {
-                flex_item = FlexItem::grow(flex.flex);
-            }
-            child_data.push((flex_item, child_size));
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || self.compute_layout(constraints))
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        if let Some(size) = self.layout_cache.cached(constraints) {
+            return size;
         }
+        let child_constraints = relaxed(constraints);
+        let sizes: Vec<Size> = self
+            .children
+            .iter()
+            .map(|child| child.measure(child_constraints))
+            .collect();
+        Self::extent(&self.place_children(&sizes, constraints))
+    }
 
-        let container = FlexContainer {
-            direction: CoreFlexDirection::Row,
-            justify_content: self.main_axis_alignment.to_core(),
-            align_items: self.cross_axis_alignment.to_core(),
-            ..Default::default()
-        };
-        let layouts = engine.calculate_flex_layout(
-            &container,
-            &child_data,
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        register_bounds(
+            self.id,
+            Rect::new(
+                layout.position.x,
+                layout.position.y,
+                layout.size.width,
+                layout.size.height,
+            ),
+        );
+
+        // Place children using the sizes measured in layout()
+        let sizes: Vec<Size> = (0..self.children.len())
+            .map(|i| {
+                self.cached_child_sizes
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| Size::new(100.0, 50.0))
+            })
+            .collect();
+        let layouts = self.place_children(
+            &sizes,
             Constraints::loose(layout.size.width, layout.size.height),
         );
 
-        // Render children
-        for (child, child_layout) in self.children.iter().zip(layouts.iter()) {
-            let absolute_layout =
-                Layout::new(layout.position + child_layout.position, child_layout.size);
-            child.render(batch, absolute_layout);
-        }
+        let layouts: Vec<Layout> = layouts
+            .iter()
+            .map(|child_layout| {
+                Layout::new(layout.position + child_layout.position, child_layout.size)
+            })
+            .collect();
+        render_children(batch, &self.children, &layouts);
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         for child in &mut self.children {
-            if child.handle_event(event) == EventResult::Handled {
+            if dispatch_event(child.as_mut(), event) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
@@ -259,6 +373,24 @@ impl Widget for Row {
             .collect()
     }
 
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        let new = new.as_any().downcast_ref::<Self>()?;
+        let changed = self.main_axis_alignment != new.main_axis_alignment
+            || self.cross_axis_alignment != new.cross_axis_alignment
+            || self.spacing != new.spacing;
+        if changed {
+            self.main_axis_alignment = new.main_axis_alignment;
+            self.cross_axis_alignment = new.cross_axis_alignment;
+            self.spacing = new.spacing;
+            self.layout_cache.mark_dirty();
+        }
+        Some(changed)
+    }
+
+    fn child_slots(&mut self) -> Option<ChildSlots<'_>> {
+        Some(ChildSlots::List(&mut self.children))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -269,12 +401,13 @@ impl Widget for Row {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Row {
-            id: generate_id(),
+            id: clone_id(self.id),
             children: self.children.iter().map(|c| c.clone_widget()).collect(),
             main_axis_alignment: self.main_axis_alignment,
             cross_axis_alignment: self.cross_axis_alignment,
             spacing: self.spacing,
             cached_child_sizes: Vec::new(),
+            layout_cache: LayoutCache::new(),
         })
     }
 
@@ -346,6 +479,7 @@ pub struct Column {
     spacing: f32,
     // Layout cache computed during layout()
     cached_child_sizes: Vec<Size>,
+    layout_cache: LayoutCache,
 }
 
 impl Column {
@@ -358,6 +492,7 @@ impl Column {
             cross_axis_alignment: CrossAxisAlignment::Center,
             spacing: 0.0,
             cached_child_sizes: Vec::new(),
+            layout_cache: LayoutCache::new(),
         }
     }
 
@@ -390,50 +525,54 @@ impl Column {
         self.spacing = spacing;
         self
     }
-}
 
-impl Widget for Column {
-    fn id(&self) -> WidgetId {
-        self.id
-    }
-
-    fn layout(&mut self, constraints: Constraints) -> Size {
-        let engine = strato_core::layout::LayoutEngine::new();
-
-        // Relax constraints for children measurement
-        let child_constraints = Constraints {
-            min_width: 0.0,
-            max_width: constraints.max_width,
-            min_height: 0.0,
-            max_height: constraints.max_height,
-        };
-
-        // Calculate child sizes
-        let mut child_data = Vec::new();
-        let mut sizes = Vec::with_capacity(self.children.len());
-        for child in &mut self.children {
-            let child_size = child.layout(child_constraints);
-            sizes.push(child_size);
-
-            let mut flex_item = FlexItem::default();
-            if let Some(flex) = child.as_any().downcast_ref::<Flex>() {
-                flex_item = FlexItem::grow(flex.flex);
+    /// Measure the children, then lay them out at their final sizes
+    fn compute_layout(&mut self, constraints: Constraints) -> Size {
+        // The column owns its children, so it measures them by laying them out
+        // with relaxed constraints, keeping their caches warm for the next pass
+        let sizes = layout_children(&self.layout_cache, &mut self.children, relaxed(constraints));
+        let layouts = self.place_children(&sizes, constraints);
+
+        // Flex children grow and stretched children fill the cross axis, so
+        // they are laid out again at the size they are drawn at
+        for ((child, measured), placed) in self.children.iter_mut().zip(&sizes).zip(&layouts) {
+            if placed.size != *measured {
+                layout_child(
+                    &self.layout_cache,
+                    child.as_mut(),
+                    Constraints::tight_size(placed.size),
+                );
             }
-            child_data.push((flex_item, child_size));
         }
-        // Cache sizes for render()
+
+        // Cache sizes for use during render()
         self.cached_child_sizes = sizes;
+        Self::extent(&layouts)
+    }
 
-        // Calculate layout
+    /// Place children of the given sizes within `constraints`
+    fn place_children(&self, sizes: &[Size], constraints: Constraints) -> Vec<Layout> {
+        let child_data: Vec<(FlexItem, Size)> = self
+            .children
+            .iter()
+            .zip(sizes)
+            .map(|(child, &size)| (flex_item(child.as_ref()), size))
+            .collect();
         let container = FlexContainer {
             direction: CoreFlexDirection::Column,
             justify_content: self.main_axis_alignment.to_core(),
             align_items: self.cross_axis_alignment.to_core(),
             ..Default::default()
         };
-        let layouts = engine.calculate_flex_layout(&container, &child_data, constraints);
+        strato_core::layout::LayoutEngine::new().calculate_flex_layout(
+            &container,
+            &child_data,
+            constraints,
+        )
+    }
 
-        // Calculate total size
+    /// The size of the column around its placed children
+    fn extent(layouts: &[Layout]) -> Size {
         let width = layouts
             .iter()
             .map(|l| l.size.width)
@@ -447,49 +586,76 @@ impl Widget for Column {
 
         Size::new(width, height)
     }
+}
 
-    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
-        let engine = strato_core::layout::LayoutEngine::new();
-
-        // Calculate child layouts using cached sizes computed during layout()
-        let mut child_data = Vec::new();
-        for (i, child) in self.children.iter().enumerate() {
-            let child_size = self
-                .cached_child_sizes
-                .get(i)
-                .copied()
-                .unwrap_or_else(|| Size::new(100.0, 50.0));
-
-            let mut flex_item = FlexItem::default();
-            if let Some(flex) = child.as_any().downcast_ref::<Flex>() {
-                flex_item = FlexItem::grow(flex.flex);
-            }
-            child_data.push((flex_item, child_size));
+impl Widget for Column {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || self.compute_layout(constraints))
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        if let Some(size) = self.layout_cache.cached(constraints) {
+            return size;
         }
+        let child_constraints = relaxed(constraints);
+        let sizes: Vec<Size> = self
+            .children
+            .iter()
+            .map(|child| child.measure(child_constraints))
+            .collect();
+        Self::extent(&self.place_children(&sizes, constraints))
+    }
 
-        let container = FlexContainer {
-            direction: CoreFlexDirection::Column,
-            justify_content: self.main_axis_alignment.to_core(),
-            align_items: self.cross_axis_alignment.to_core(),
-            ..Default::default()
-        };
-        let layouts = engine.calculate_flex_layout(
-            &container,
-            &child_data,
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        register_bounds(
+            self.id,
+            Rect::new(
+                layout.position.x,
+                layout.position.y,
+                layout.size.width,
+                layout.size.height,
+            ),
+        );
+
+        // Place children using the sizes measured in layout()
+        let sizes: Vec<Size> = (0..self.children.len())
+            .map(|i| {
+                self.cached_child_sizes
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| Size::new(100.0, 50.0))
+            })
+            .collect();
+        let layouts = self.place_children(
+            &sizes,
             Constraints::loose(layout.size.width, layout.size.height),
         );
 
-        // Render children
-        for (child, child_layout) in self.children.iter().zip(layouts.iter()) {
-            let absolute_layout =
-                Layout::new(layout.position + child_layout.position, child_layout.size);
-            child.render(batch, absolute_layout);
-        }
+        let layouts: Vec<Layout> = layouts
+            .iter()
+            .map(|child_layout| {
+                Layout::new(layout.position + child_layout.position, child_layout.size)
+            })
+            .collect();
+        render_children(batch, &self.children, &layouts);
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         for child in &mut self.children {
-            if child.handle_event(event) == EventResult::Handled {
+            if dispatch_event(child.as_mut(), event) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
@@ -507,6 +673,24 @@ impl Widget for Column {
             .collect()
     }
 
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        let new = new.as_any().downcast_ref::<Self>()?;
+        let changed = self.main_axis_alignment != new.main_axis_alignment
+            || self.cross_axis_alignment != new.cross_axis_alignment
+            || self.spacing != new.spacing;
+        if changed {
+            self.main_axis_alignment = new.main_axis_alignment;
+            self.cross_axis_alignment = new.cross_axis_alignment;
+            self.spacing = new.spacing;
+            self.layout_cache.mark_dirty();
+        }
+        Some(changed)
+    }
+
+    fn child_slots(&mut self) -> Option<ChildSlots<'_>> {
+        Some(ChildSlots::List(&mut self.children))
+    }
+
     fn as_any(&self) -> &dyn Any {
         self
     }
@@ -517,12 +701,13 @@ impl Widget for Column {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Column {
-            id: generate_id(),
+            id: clone_id(self.id),
             children: self.children.iter().map(|c| c.clone_widget()).collect(),
             main_axis_alignment: self.main_axis_alignment,
             cross_axis_alignment: self.cross_axis_alignment,
             spacing: self.spacing,
             cached_child_sizes: Vec::new(),
+            layout_cache: LayoutCache::new(),
         })
     }
 
@@ -585,10 +770,18 @@ impl TaffyWidget for Column {
 }
 
 /// Stack widget for layered layout
+///
+/// Children are drawn on top of each other in order of their z-index, then
+/// in the order they were added; events reach the topmost child first.
+/// The z-index only orders children within this stack. Anything a child
+/// draws into the batch's overlay layer (`RenderBatch::overlay_commands`) is
+/// still drawn after all regular content, whatever its z-index.
 #[derive(Debug)]
 pub struct Stack {
     id: WidgetId,
     children: Vec<Box<dyn Widget>>,
+    z_indices: Vec<i32>,
+    layout_cache: LayoutCache,
 }
 
 impl Stack {
@@ -597,11 +790,14 @@ impl Stack {
         Self {
             id: generate_id(),
             children: Vec::new(),
+            z_indices: Vec::new(),
+            layout_cache: LayoutCache::new(),
         }
     }
 
     /// Add children widgets
     pub fn children(mut self, children: Vec<Box<dyn Widget>>) -> Self {
+        self.z_indices = vec![0; children.len()];
         self.children = children;
         self
     }
@@ -609,8 +805,38 @@ impl Stack {
     /// Add a single child
     pub fn child(mut self, child: Box<dyn Widget>) -> Self {
         self.children.push(child);
+        self.z_indices.push(0);
+        self
+    }
+
+    /// Add a single child drawn above siblings with a lower z-index
+    pub fn child_with_z_index(mut self, child: Box<dyn Widget>, z_index: i32) -> Self {
+        self.children.push(child);
+        self.z_indices.push(z_index);
         self
     }
+
+    /// Set the z-index of the child at `index`, e.g. to raise a dragged item
+    ///
+    /// Children default to 0; among equal z-indices, later children are on top.
+    pub fn set_z_index(&mut self, index: usize, z_index: i32) {
+        if let Some(z) = self.z_indices.get_mut(index) {
+            *z = z_index;
+        }
+    }
+
+    /// The z-index of the child at `index`
+    pub fn z_index(&self, index: usize) -> Option<i32> {
+        self.z_indices.get(index).copied()
+    }
+
+    /// Child indices from bottom to top
+    fn paint_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.children.len()).collect();
+        // Stable, so children with the same z-index keep their source order
+        order.sort_by_key(|&index| self.z_indices[index]);
+        order
+    }
 }
 
 impl Widget for Stack {
@@ -618,30 +844,41 @@ impl Widget for Stack {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
-        let mut max_width: f32 = 0.0;
-        let mut max_height: f32 = 0.0;
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || {
+            let mut max_width: f32 = 0.0;
+            let mut max_height: f32 = 0.0;
+
+            for child in &mut self.children {
+                let size = layout_child(&cache, child.as_mut(), constraints);
+                max_width = max_width.max(size.width);
+                max_height = max_height.max(size.height);
+            }
 
-        for child in &mut self.children {
-            let size = child.layout(constraints);
-            max_width = max_width.max(size.width);
-            max_height = max_height.max(size.height);
-        }
+            Size::new(max_width, max_height)
+        })
+    }
 
-        Size::new(max_width, max_height)
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
     }
 
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
-        // Render all children at the same position
-        for child in &self.children {
-            child.render(batch, layout);
+        // Render all children at the same position, bottom to top
+        for index in self.paint_order() {
+            self.children[index].render(batch, layout);
         }
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        // Events are handled from top to bottom (reverse order)
-        for child in self.children.iter_mut().rev() {
-            if child.handle_event(event) == EventResult::Handled {
+        // Events are handled from top to bottom (reverse paint order)
+        for index in self.paint_order().into_iter().rev() {
+            if dispatch_event(self.children[index].as_mut(), event) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
@@ -669,8 +906,10 @@ impl Widget for Stack {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Stack {
-            id: generate_id(),
+            id: clone_id(self.id),
             children: self.children.iter().map(|c| c.clone_widget()).collect(),
+            z_indices: self.z_indices.clone(),
+            layout_cache: LayoutCache::new(),
         })
     }
 
@@ -688,16 +927,19 @@ impl Widget for Stack {
         if let Ok(layout) = tree.layout(node) {
             let my_position = parent_offset + strato_core::types::Point::new(layout.location.x, layout.location.y);
             
-            // Render children
+            // Render children bottom to top
             if let Ok(children_nodes) = tree.children(node) {
+                let mut child_nodes = vec![None; self.children.len()];
                 let mut child_node_idx = 0;
-                for child in &self.children {
-                    if child.as_taffy().is_some() {
-                        if child_node_idx < children_nodes.len() {
-                            let child_node = children_nodes[child_node_idx];
-                            child.render_taffy(batch, tree, child_node, my_position);
-                            child_node_idx += 1;
-                        }
+                for (index, child) in self.children.iter().enumerate() {
+                    if child.as_taffy().is_some() && child_node_idx < children_nodes.len() {
+                        child_nodes[index] = Some(children_nodes[child_node_idx]);
+                        child_node_idx += 1;
+                    }
+                }
+                for index in self.paint_order() {
+                    if let Some(child_node) = child_nodes[index] {
+                        self.children[index].render_taffy(batch, tree, child_node, my_position);
                     }
                 }
             }
@@ -763,16 +1005,29 @@ impl Widget for Flex {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         self.child.layout(constraints)
     }
 
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        // Flex only forwards to its child, so it shares the child's cache
+        self.child.layout_cache()
+    }
+
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
         self.child.render(batch, layout);
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
-        self.child.handle_event(event)
+        dispatch_event(self.child.as_mut(), event)
     }
 
     fn children(&self) -> Vec<&(dyn Widget + '_)> {
@@ -793,7 +1048,7 @@ impl Widget for Flex {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Flex {
-            id: generate_id(),
+            id: clone_id(self.id),
             child: self.child.clone_widget(),
             flex: self.flex,
         })
@@ -831,3 +1086,266 @@ impl TaffyWidget for Flex {
 }
 
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::container::Container;
+    use crate::text::Text;
+    use glam::Vec2;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use strato_core::layout::{set_layout_config, LayoutConfig};
+    use strato_core::state::Signal;
+    use strato_core::types::{Color, Point};
+    use strato_renderer::batch::DrawCommand;
+
+    fn layer(color: Color) -> Box<dyn Widget> {
+        Box::new(Container::new().background(color).size(10.0, 10.0))
+    }
+
+    fn draw_order(stack: &Stack) -> Vec<Color> {
+        let mut batch = RenderBatch::new();
+        stack.render(&mut batch, Layout::new(Vec2::ZERO, Size::new(10.0, 10.0)));
+        batch
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Rect { color, .. } => Some(*color),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stack_draws_by_z_index_then_source_order() {
+        let (red, green, blue) = (
+            Color::rgb(1.0, 0.0, 0.0),
+            Color::rgb(0.0, 1.0, 0.0),
+            Color::rgb(0.0, 0.0, 1.0),
+        );
+        let mut stack = Stack::new()
+            .child_with_z_index(layer(red), 1)
+            .child(layer(green))
+            .child(layer(blue));
+        assert_eq!(draw_order(&stack), vec![green, blue, red]);
+
+        // Raise the middle child above everything without reordering
+        stack.set_z_index(1, 2);
+        assert_eq!(stack.z_index(1), Some(2));
+        assert_eq!(draw_order(&stack), vec![blue, red, green]);
+
+        // Equal z-indices fall back to source order
+        stack.set_z_index(1, 0);
+        stack.set_z_index(0, 0);
+        assert_eq!(draw_order(&stack), vec![red, green, blue]);
+    }
+
+    #[test]
+    fn test_hit_testing_follows_paint_order() {
+        let mut stack = Stack::new()
+            .child(layer(Color::WHITE))
+            .child(layer(Color::BLACK));
+        let ids: Vec<WidgetId> = stack.children.iter().map(|child| child.id()).collect();
+        let point = Point::new(5.0, 5.0);
+
+        draw_order(&stack);
+        assert_eq!(crate::widget::widgets_at(&stack, point), vec![ids[1]]);
+
+        // Raising the first child puts it on top for hit testing too
+        stack.set_z_index(0, 1);
+        draw_order(&stack);
+        assert_eq!(crate::widget::widgets_at(&stack, point), vec![ids[0]]);
+        assert!(crate::widget::hit(ids[1], point));
+    }
+
+    fn feed(rows: usize, columns: usize) -> Column {
+        Column::new().children(
+            (0..rows)
+                .map(|row| {
+                    Box::new(
+                        Row::new().children(
+                            (0..columns)
+                                .map(|column| {
+                                    Box::new(Text::new(format!("{row}:{column}"))) as Box<dyn Widget>
+                                })
+                                .collect(),
+                        ),
+                    ) as Box<dyn Widget>
+                })
+                .collect(),
+        )
+    }
+
+    fn lay_out_and_render(column: &mut Column) -> (Size, String) {
+        let size = column.layout(Constraints::loose(1920.0, 1080.0));
+        let mut batch = RenderBatch::new();
+        column.render(&mut batch, Layout::new(Vec2::ZERO, size));
+        (size, format!("{:?}", batch.commands))
+    }
+
+    #[test]
+    fn test_parallel_layout_matches_serial() {
+        set_layout_config(LayoutConfig {
+            parallel: false,
+            ..LayoutConfig::default()
+        });
+        let serial = lay_out_and_render(&mut feed(20, 50));
+
+        set_layout_config(LayoutConfig {
+            parallel: true,
+            parallel_threshold: 1,
+        });
+        let parallel = lay_out_and_render(&mut feed(20, 50));
+        set_layout_config(LayoutConfig::default());
+
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn test_parallel_render_matches_serial_byte_for_byte() {
+        let buttons = || {
+            Column::new().children(
+                (0..40)
+                    .map(|row| {
+                        Box::new(Row::new().children(vec![
+                            Box::new(Button::new(format!("Button {row}"))) as Box<dyn Widget>,
+                            Box::new(Text::new(format!("Label {row}"))),
+                        ])) as Box<dyn Widget>
+                    })
+                    .collect(),
+            )
+        };
+        let render = |parallel: bool| {
+            set_layout_config(LayoutConfig {
+                parallel,
+                parallel_threshold: 1,
+            });
+            let mut column = buttons();
+            let size = column.layout(Constraints::loose(800.0, 2000.0));
+            let mut batch = RenderBatch::new();
+            column.render(&mut batch, Layout::new(Vec2::ZERO, size));
+            (
+                bytemuck::cast_slice::<_, u8>(&batch.vertices).to_vec(),
+                batch.indices.clone(),
+                format!("{:?} {:?}", batch.commands, batch.overlay_commands),
+            )
+        };
+
+        let serial = render(false);
+        let parallel = render(true);
+        set_layout_config(LayoutConfig::default());
+
+        assert!(!serial.0.is_empty());
+        assert_eq!(serial, parallel);
+    }
+
+    /// Fixed-size leaf counting how often it is actually laid out
+    #[derive(Debug)]
+    struct Probe {
+        layouts: Arc<AtomicUsize>,
+        layout_cache: LayoutCache,
+    }
+
+    impl Widget for Probe {
+        fn id(&self) -> WidgetId {
+            0
+        }
+
+        fn layout(&mut self, constraints: Constraints) -> Size {
+            self.layout_cache.get_or_layout(constraints, || {
+                self.layouts.fetch_add(1, Ordering::SeqCst);
+                Size::new(10.0, 10.0)
+            })
+        }
+
+        fn layout_cache(&self) -> Option<&LayoutCache> {
+            Some(&self.layout_cache)
+        }
+
+        fn render(&self, _batch: &mut RenderBatch, _layout: Layout) {}
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn clone_widget(&self) -> Box<dyn Widget> {
+            Box::new(Probe {
+                layouts: self.layouts.clone(),
+                layout_cache: LayoutCache::new(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_static_subtree_is_not_laid_out_on_unrelated_signal_change() {
+        let label = Signal::new(String::from("Inbox"));
+        let layouts = Arc::new(AtomicUsize::new(0));
+        let mut root = Column::new()
+            .child(Box::new(
+                Row::new().child(Box::new(Text::new("").bind(label.clone()))),
+            ))
+            .child(Box::new(Row::new().child(Box::new(
+                Container::new().child(Probe {
+                    layouts: layouts.clone(),
+                    layout_cache: LayoutCache::new(),
+                }),
+            ))));
+        let constraints = Constraints::loose(800.0, 600.0);
+
+        let before = root.layout(constraints);
+        assert_eq!(root.layout(constraints), before);
+        assert_eq!(layouts.load(Ordering::SeqCst), 1);
+
+        // The label's path to the root is laid out again, the probe's row is not
+        label.set(String::from("Inbox (12 unread)"));
+        assert!(root.layout_cache.is_dirty());
+        let after = root.layout(constraints);
+        assert!(after.width > before.width);
+        assert_eq!(layouts.load(Ordering::SeqCst), 1);
+
+        // New constraints reach every widget
+        root.layout(Constraints::loose(400.0, 600.0));
+        assert_eq!(layouts.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_measure_leaves_the_row_unlaid_out() {
+        let mut row = Row::new()
+            .child(Box::new(Text::new("Name")))
+            .child(Box::new(Flex::new(Box::new(
+                Container::new().size(20.0, 10.0),
+            ))));
+        let constraints = Constraints::loose(300.0, 100.0);
+
+        let measured = row.measure(constraints);
+        assert!(row.cached_child_sizes.is_empty());
+        assert!(row.layout_cache.is_dirty());
+
+        assert_eq!(row.layout(constraints), measured);
+        // Answered from the cache once laid out
+        assert_eq!(row.measure(constraints), measured);
+    }
+
+    #[test]
+    fn test_flex_child_is_laid_out_at_its_final_size() {
+        let mut row = Row::new()
+            .child(Box::new(Container::new().size(100.0, 20.0)))
+            .child(Box::new(Flex::new(Box::new(
+                Container::new().size(20.0, 20.0),
+            ))));
+        let size = row.layout(Constraints::loose(300.0, 100.0));
+        assert_eq!(size.width, 300.0);
+
+        // The flex child measured 20 wide and grew into the remaining 200
+        let grown = Constraints::tight(200.0, 20.0);
+        let flex = &row.children[1];
+        assert!(flex.layout_cache().unwrap().cached(grown).is_some());
+        assert_eq!(row.cached_child_sizes[1], Size::new(20.0, 20.0));
+    }
+}
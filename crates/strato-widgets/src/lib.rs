@@ -3,51 +3,88 @@
 //! This crate provides a collection of UI widgets built on top of the StratoUI core framework.
 //! All widgets are designed to be composable, reactive, and performant.
 
+pub mod animated_layout;
 pub mod animation;
 pub mod builder;
 pub mod button;
+pub mod cache_layer;
+pub mod canvas;
+pub mod chart;
 pub mod checkbox;
+pub mod clipboard;
 pub mod container;
 pub mod control;
 pub mod dropdown;
+pub mod focus;
+pub mod gesture;
 pub mod grid;
 pub mod image;
 pub mod input;
 pub mod inspector;
 pub mod layout;
+pub mod log_console;
+pub mod menu_bar;
+pub mod modal;
+pub mod reconcile;
 pub mod registry;
 pub mod scroll_view;
+pub mod shortcut;
+pub mod show;
 pub mod slider;
+pub mod split_pane;
+pub mod state_view;
+pub mod table;
+pub mod test_util;
 pub mod text;
 pub mod theme;
+pub mod toast;
 pub mod top_bar;
 pub mod widget;
+pub mod widget_prelude;
 pub mod wrap;
 
 pub mod prelude;
 use crate::prelude::*;
 
 // Re-export all widget types for easy access
-pub use builder::WidgetBuilder;
-pub use button::{Button, ButtonStyle};
-pub use checkbox::{Checkbox, CheckboxStyle, RadioButton};
-pub use container::{Container, ContainerStyle};
+pub use animated_layout::AnimatedLayout;
+pub use builder::{BuilderExt, IntoChild, ParentWidget, WidgetBuilder};
+pub use button::{
+    Button, ButtonBorder, ButtonStyle, ButtonStyleBuilder, ButtonStyleSpec, PressEffect,
+};
+pub use cache_layer::CacheLayer;
+pub use canvas::Canvas;
+pub use chart::{Chart, ChartStyle, Series, SeriesKind};
+pub use checkbox::{CheckGlyph, Checkbox, CheckboxStyle, LabelClick, RadioButton, RadioStyle};
+pub use container::{Container, ContainerStyle, Overflow, SizeBehavior};
 pub use control::{ControlRole, ControlSemantics, ControlState};
 pub use dropdown::{Dropdown, DropdownOption, DropdownStyle};
-pub use grid::{Grid, GridUnit};
+pub use focus::FocusScope;
+pub use grid::{Alignment, CellAlign, Grid, GridCell, GridUnit};
 pub use image::{
     Image, ImageBuilder, ImageData, ImageFilter, ImageFit, ImageFormat, ImageSource, ImageStyle,
 };
-pub use input::{InputStyle, InputType, TextInput};
+pub use input::{EditAction, InputStyle, InputType, LineIndex, TextInput};
 pub use inspector::InspectorOverlay;
 pub use layout::{Column, Flex, Row, Stack};
+pub use log_console::LogConsole;
+pub use menu_bar::{ContextMenu, ContextMenuResponse, Menu, MenuBar, MenuBarStyle, MenuItem};
+pub use modal::Modal;
+pub use reconcile::{reconcile, ChildSlots, Mutation, Reconciled};
+pub use registry::{IntoWidget, WidgetRegistry};
 pub use scroll_view::ScrollView;
-pub use slider::{ProgressBar, Slider, SliderStyle};
+pub use shortcut::{Shortcut, ShortcutRegistry};
+pub use show::Show;
+pub use slider::{ProgressBar, RangeSlider, Slider, SliderStyle};
+pub use split_pane::{SplitAxis, SplitPane, SplitPaneStyle};
+pub use state_view::{Dispatch, StateView};
+pub use table::{SortDirection, Table, TableColumn, TableStyle};
 pub use strato_macros::view;
-pub use text::{Text, TextStyle};
+pub use text::{Text, TextLink, TextSpan, TextStyle, VerticalAlign};
 pub use theme::Theme;
+pub use toast::{ToastAnchor, ToastHost, ToastLevel, ToastManager};
 pub use top_bar::TopBar;
-pub use widget::{Widget, WidgetContext, WidgetId};
+pub use widget::{IdMode, Widget, WidgetContext, WidgetId};
 
 /// Initialize the widgets module
 pub fn init() -> strato_core::Result<()> {
@@ -0,0 +1,591 @@
+//! In-app log console
+//!
+//! [`LogConsole`] wraps the root widget and, when toggled with its shortcut
+//! (Ctrl+Shift+L by default), shows the records of a [`LogBuffer`] in a panel
+//! docked to the bottom of the window. Rows are colored by level and can be
+//! narrowed down by category and by a search string. Only the rows in view are
+//! drawn, so buffers holding thousands of records stay cheap to render.
+//!
+//! Records logged with plain `tracing` macros, as the renderer and platform
+//! do, reach the buffer through [`LogBufferLayer`](strato_core::logging::LogBufferLayer).
+
+use std::collections::{HashSet, VecDeque};
+
+use glam::Vec2;
+use strato_core::event::{Event, EventResult, KeyCode, KeyboardEvent, Modifiers, MouseButton};
+use strato_core::layout::{Constraints, Layout, Size};
+use strato_core::logging::{LogBuffer, LogCategory, LogLevel, LogRecord};
+use strato_core::state::Signal;
+use strato_core::types::{Color, Point, Rect, Transform};
+use strato_renderer::batch::RenderBatch;
+
+use crate::input::TextInput;
+use crate::text::measure_line_metrics;
+use crate::theme::{ColorPalette, Theme};
+use crate::widget::{clone_id, dispatch_event, generate_id, Widget, WidgetId};
+
+const PANEL_HEIGHT_RATIO: f32 = 0.4;
+const MAX_PANEL_HEIGHT: f32 = 360.0;
+const HEADER_HEIGHT: f32 = 36.0;
+const ROW_HEIGHT: f32 = 18.0;
+const FONT_SIZE: f32 = 12.0;
+const PADDING: f32 = 8.0;
+const CHIP_WIDTH: f32 = 64.0;
+const CHIP_SPACING: f32 = 4.0;
+const SEARCH_WIDTH: f32 = 220.0;
+const LEVEL_STRIP_WIDTH: f32 = 3.0;
+
+fn with_alpha(color: Color, alpha: f32) -> Color {
+    Color {
+        a: color.a * alpha,
+        ..color
+    }
+}
+
+/// Color used for rows of the given level
+pub fn level_color(level: LogLevel, palette: &ColorPalette) -> Color {
+    match level {
+        LogLevel::Trace => palette.text_secondary,
+        LogLevel::Debug => palette.info,
+        LogLevel::Info => palette.text_primary,
+        LogLevel::Warn => palette.warning,
+        LogLevel::Error => palette.error,
+    }
+}
+
+/// Overlay panel listing recent log records
+#[derive(Debug)]
+pub struct LogConsole {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    shortcut: (KeyCode, Modifiers),
+    pub visible: bool,
+    buffer: LogBuffer,
+    palette: ColorPalette,
+    search: TextInput,
+    hidden_categories: HashSet<String>,
+    /// Local copy of the buffer, refreshed during layout
+    records: VecDeque<LogRecord>,
+    last_sequence: u64,
+    /// Indices into `records` passing the filters
+    matching: Vec<usize>,
+    applied_query: String,
+    filters_changed: bool,
+    /// First row of `matching` in view
+    scroll: usize,
+    /// Keep the newest record in view as records arrive
+    follow: bool,
+    rows_in_view: usize,
+    child_size: Size,
+    /// Height available to the console, from the last layout
+    available_height: f32,
+    pointer: Point,
+    panel_rect: Signal<Rect>,
+    chip_rects: Signal<Vec<(LogCategory, Rect)>>,
+}
+
+impl LogConsole {
+    /// Create a console around `child` showing the global [`LogBuffer`]
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self::with_child(Box::new(child))
+    }
+
+    fn with_child(child: Box<dyn Widget>) -> Self {
+        Self {
+            id: generate_id(),
+            child,
+            shortcut: (
+                KeyCode::L,
+                Modifiers {
+                    control: true,
+                    shift: true,
+                    alt: false,
+                    super_key: false,
+                },
+            ),
+            visible: false,
+            buffer: LogBuffer::global(),
+            palette: Theme::dark().colors,
+            search: TextInput::new().placeholder("Search logs"),
+            hidden_categories: HashSet::new(),
+            records: VecDeque::new(),
+            last_sequence: 0,
+            matching: Vec::new(),
+            applied_query: String::new(),
+            filters_changed: false,
+            scroll: 0,
+            follow: true,
+            rows_in_view: 0,
+            child_size: Size::zero(),
+            available_height: 0.0,
+            pointer: Point::new(0.0, 0.0),
+            panel_rect: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            chip_rects: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Show records from a specific buffer instead of the global one
+    pub fn buffer(mut self, buffer: LogBuffer) -> Self {
+        self.buffer = buffer;
+        self.records.clear();
+        self.last_sequence = 0;
+        self.filters_changed = true;
+        self
+    }
+
+    /// Override the keyboard shortcut used to toggle visibility
+    pub fn shortcut(mut self, key: KeyCode, modifiers: Modifiers) -> Self {
+        self.shortcut = (key, modifiers);
+        self
+    }
+
+    /// Take console colors from a theme
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.palette = theme.colors.clone();
+        self
+    }
+
+    /// Show or hide the console
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Show or hide records of a category
+    pub fn set_category_visible(&mut self, category: LogCategory, visible: bool) {
+        let changed = if visible {
+            self.hidden_categories.remove(category.as_str())
+        } else {
+            self.hidden_categories.insert(category.as_str().to_string())
+        };
+        self.filters_changed |= changed;
+    }
+
+    /// Check if records of a category are shown
+    pub fn is_category_visible(&self, category: LogCategory) -> bool {
+        !self.hidden_categories.contains(category.as_str())
+    }
+
+    /// Set the search string; matching is case-insensitive
+    pub fn set_search(&self, query: impl Into<String>) {
+        self.search.set_value(query);
+    }
+
+    /// Records passing the current filters, oldest first
+    pub fn matching_records(&self) -> impl Iterator<Item = &LogRecord> {
+        self.matching.iter().map(|&index| &self.records[index])
+    }
+
+    fn shortcut_pressed(&self, key: &KeyboardEvent) -> bool {
+        key.key_code == self.shortcut.0
+            && key.modifiers.control == self.shortcut.1.control
+            && key.modifiers.shift == self.shortcut.1.shift
+            && key.modifiers.alt == self.shortcut.1.alt
+            && key.modifiers.super_key == self.shortcut.1.super_key
+    }
+
+    fn matches(&self, record: &LogRecord) -> bool {
+        if self.hidden_categories.contains(&record.category) {
+            return false;
+        }
+        let query = &self.applied_query;
+        query.is_empty()
+            || record.message.to_lowercase().contains(query)
+            || record.category.contains(query)
+    }
+
+    /// Pull new records from the buffer and update the filtered rows
+    fn refresh(&mut self) {
+        let new_records = self.buffer.records_since(self.last_sequence);
+        if let Some(last) = new_records.last() {
+            self.last_sequence = last.sequence;
+        }
+        let first_new = self.records.len();
+        self.records.extend(new_records);
+
+        let capacity = self.buffer.capacity();
+        let dropped = self.records.len().saturating_sub(capacity);
+        self.records.drain(..dropped);
+
+        let query = self.search.get_value().to_lowercase();
+        if query != self.applied_query || self.filters_changed {
+            self.applied_query = query;
+            self.filters_changed = false;
+            self.matching = (0..self.records.len())
+                .filter(|&index| self.matches(&self.records[index]))
+                .collect();
+            return;
+        }
+
+        // Only appended: shift the old rows and filter the new ones
+        self.matching.retain(|&index| index >= dropped);
+        for index in &mut self.matching {
+            *index -= dropped;
+        }
+        let first_new = first_new.saturating_sub(dropped);
+        for index in first_new..self.records.len() {
+            if self.matches(&self.records[index]) {
+                self.matching.push(index);
+            }
+        }
+    }
+
+    fn max_scroll(&self) -> usize {
+        self.matching.len().saturating_sub(self.rows_in_view)
+    }
+
+    fn scroll_by(&mut self, rows: isize) {
+        let target = (self.scroll as isize + rows).max(0) as usize;
+        self.scroll = target.min(self.max_scroll());
+        self.follow = self.scroll == self.max_scroll();
+    }
+
+    fn panel_height(&self) -> f32 {
+        (self.available_height * PANEL_HEIGHT_RATIO).min(MAX_PANEL_HEIGHT)
+    }
+
+    fn render_header(&self, batch: &mut RenderBatch, panel: Rect) {
+        let text_y = measure_line_metrics(FONT_SIZE).centered_top(panel.y, HEADER_HEIGHT);
+        batch.add_text(
+            format!("Logs {}/{}", self.matching.len(), self.records.len()),
+            (panel.x + PADDING, text_y),
+            self.palette.text_primary,
+            FONT_SIZE,
+            0.0,
+        );
+
+        let mut chips = Vec::with_capacity(LogCategory::ALL.len());
+        let mut x = panel.x + PADDING + 96.0;
+        for category in LogCategory::ALL {
+            let rect = Rect::new(x, panel.y + 8.0, CHIP_WIDTH, HEADER_HEIGHT - 16.0);
+            let (background, text) = if self.is_category_visible(category) {
+                (
+                    with_alpha(self.palette.primary, 0.35),
+                    self.palette.text_primary,
+                )
+            } else {
+                (self.palette.divider, self.palette.text_disabled)
+            };
+            batch.add_rect(rect, background, Transform::identity());
+            batch.add_text(
+                category.as_str().to_string(),
+                (rect.x + 6.0, text_y),
+                text,
+                FONT_SIZE,
+                0.0,
+            );
+            chips.push((category, rect));
+            x += CHIP_WIDTH + CHIP_SPACING;
+        }
+        self.chip_rects.set(chips);
+
+        let search_size = Size::new(SEARCH_WIDTH, HEADER_HEIGHT - 8.0);
+        let search_pos = Vec2::new(
+            panel.x + panel.width - PADDING - SEARCH_WIDTH,
+            panel.y + 4.0,
+        );
+        Widget::render(&self.search, batch, Layout::new(search_pos, search_size));
+    }
+
+    fn render_rows(&self, batch: &mut RenderBatch, list: Rect) {
+        batch.push_clip(list);
+        let end = (self.scroll + self.rows_in_view + 1).min(self.matching.len());
+        for (row, &index) in self.matching[self.scroll..end].iter().enumerate() {
+            let record = &self.records[index];
+            let y = list.y + row as f32 * ROW_HEIGHT;
+            let color = level_color(record.level, &self.palette);
+
+            batch.add_rect(
+                Rect::new(list.x, y + 2.0, LEVEL_STRIP_WIDTH, ROW_HEIGHT - 4.0),
+                color,
+                Transform::identity(),
+            );
+            batch.add_text(
+                format!(
+                    "{} {:<5} [{}] {}",
+                    record.timestamp.format("%H:%M:%S%.3f"),
+                    record.level.as_str().to_uppercase(),
+                    record.category,
+                    record.message
+                ),
+                (
+                    list.x + LEVEL_STRIP_WIDTH + 6.0,
+                    measure_line_metrics(FONT_SIZE).centered_top(y, ROW_HEIGHT),
+                ),
+                color,
+                FONT_SIZE,
+                0.0,
+            );
+        }
+        batch.pop_clip();
+    }
+}
+
+impl Widget for LogConsole {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.child_size = self.child.layout(constraints);
+        self.available_height = if constraints.max_height.is_finite() {
+            constraints.max_height
+        } else {
+            self.child_size.height
+        };
+
+        if self.visible {
+            self.refresh();
+            Widget::layout(
+                &mut self.search,
+                Constraints::tight(SEARCH_WIDTH, HEADER_HEIGHT - 8.0),
+            );
+
+            let list_height = self.panel_height() - HEADER_HEIGHT - PADDING;
+            self.rows_in_view = (list_height / ROW_HEIGHT).floor().max(0.0) as usize;
+            if self.follow {
+                self.scroll = self.max_scroll();
+            } else {
+                self.scroll = self.scroll.min(self.max_scroll());
+            }
+        }
+
+        self.child_size
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.child.render(batch, layout);
+        if !self.visible {
+            return;
+        }
+
+        let height = self.panel_height();
+        let panel = Rect::new(
+            layout.position.x,
+            layout.position.y + layout.size.height - height,
+            layout.size.width,
+            height,
+        );
+        self.panel_rect.set(panel);
+
+        batch.add_rect(
+            panel,
+            with_alpha(self.palette.surface, 0.96),
+            Transform::identity(),
+        );
+        batch.add_rect(
+            Rect::new(panel.x, panel.y + HEADER_HEIGHT - 1.0, panel.width, 1.0),
+            self.palette.divider,
+            Transform::identity(),
+        );
+        self.render_header(batch, panel);
+
+        let list = Rect::new(
+            panel.x + PADDING,
+            panel.y + HEADER_HEIGHT,
+            panel.width - PADDING * 2.0,
+            height - HEADER_HEIGHT - PADDING,
+        );
+        self.render_rows(batch, list);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::KeyDown(key) = event {
+            if self.shortcut_pressed(key) {
+                self.toggle();
+                return EventResult::Handled;
+            }
+        }
+
+        if !self.visible {
+            return dispatch_event(self.child.as_mut(), event);
+        }
+
+        if let Event::MouseMove(mouse) = event {
+            self.pointer = Point::new(mouse.position.x, mouse.position.y);
+        }
+        let in_panel = self.panel_rect.get().contains(self.pointer);
+
+        let search_result = dispatch_event(&mut self.search, event);
+        let is_pointer_event = matches!(
+            event,
+            Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_)
+        );
+        if search_result == EventResult::Handled && (in_panel || !is_pointer_event) {
+            return EventResult::Handled;
+        }
+
+        match event {
+            Event::MouseWheel { delta, .. } if in_panel => {
+                self.scroll_by((-delta.y / ROW_HEIGHT).round() as isize);
+                EventResult::Handled
+            }
+            Event::MouseDown(mouse) if in_panel => {
+                if mouse.button == Some(MouseButton::Left) {
+                    let point = Point::new(mouse.position.x, mouse.position.y);
+                    let chip = self
+                        .chip_rects
+                        .get()
+                        .into_iter()
+                        .find(|(_, rect)| rect.contains(point));
+                    if let Some((category, _)) = chip {
+                        let visible = self.is_category_visible(category);
+                        self.set_category_visible(category, !visible);
+                    }
+                }
+                EventResult::Handled
+            }
+            Event::MouseUp(_) if in_panel => EventResult::Handled,
+            _ => dispatch_event(self.child.as_mut(), event),
+        }
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let mut console = LogConsole::with_child(self.child.clone_widget())
+            .buffer(self.buffer.clone())
+            .shortcut(self.shortcut.0, self.shortcut.1);
+        console.id = clone_id(self.id);
+        console.visible = self.visible;
+        console.palette = self.palette.clone();
+        console.hidden_categories = self.hidden_categories.clone();
+        console.set_search(self.search.get_value());
+        Box::new(console)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::Text;
+    use strato_renderer::batch::DrawCommand;
+
+    fn console_with(records: &[(LogLevel, LogCategory, &str)]) -> LogConsole {
+        let buffer = LogBuffer::new(100);
+        for (level, category, message) in records {
+            buffer.push(*level, category.as_str(), message);
+        }
+        let mut console = LogConsole::new(Text::new("app")).buffer(buffer);
+        console.visible = true;
+        console
+    }
+
+    fn messages(console: &LogConsole) -> Vec<&str> {
+        console
+            .matching_records()
+            .map(|record| record.message.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_category_and_search_filters() {
+        let mut console = console_with(&[
+            (LogLevel::Info, LogCategory::Renderer, "Surface configured"),
+            (LogLevel::Warn, LogCategory::Renderer, "Device lost"),
+            (LogLevel::Error, LogCategory::Platform, "Window lost focus"),
+        ]);
+        console.layout(Constraints::loose(800.0, 600.0));
+        assert_eq!(messages(&console).len(), 3);
+
+        console.set_search("LOST");
+        console.layout(Constraints::loose(800.0, 600.0));
+        assert_eq!(messages(&console), ["Device lost", "Window lost focus"]);
+
+        console.set_category_visible(LogCategory::Platform, false);
+        console.layout(Constraints::loose(800.0, 600.0));
+        assert_eq!(messages(&console), ["Device lost"]);
+
+        // New records are filtered as they arrive
+        console
+            .buffer
+            .push(LogLevel::Debug, "renderer", "Swapchain lost");
+        console.layout(Constraints::loose(800.0, 600.0));
+        assert_eq!(messages(&console), ["Device lost", "Swapchain lost"]);
+    }
+
+    #[test]
+    fn test_only_rows_in_view_are_drawn() {
+        let mut console = console_with(&[]);
+        for i in 0..5000 {
+            console
+                .buffer
+                .push(LogLevel::Info, "core", &format!("record {}", i));
+        }
+        console.layout(Constraints::loose(800.0, 600.0));
+        let mut batch = RenderBatch::new();
+        console.render(&mut batch, Layout::new(Vec2::ZERO, Size::new(800.0, 600.0)));
+
+        let rows: Vec<&String> = batch
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Text { text, .. } if text.contains("record") => Some(text),
+                _ => None,
+            })
+            .collect();
+        assert!(rows.len() <= console.rows_in_view + 1);
+        // Following the tail, so the newest record is in view
+        assert!(rows.last().unwrap().ends_with("record 4999"));
+    }
+
+    #[test]
+    fn test_shortcut_toggles_console() {
+        let mut console = LogConsole::new(Text::new("app"));
+        let key = KeyboardEvent {
+            key_code: KeyCode::L,
+            modifiers: Modifiers {
+                control: true,
+                shift: true,
+                alt: false,
+                super_key: false,
+            },
+            text: None,
+            is_repeat: false,
+        };
+
+        assert_eq!(
+            console.handle_event(&Event::KeyDown(key.clone())),
+            EventResult::Handled
+        );
+        assert!(console.visible);
+        console.handle_event(&Event::KeyDown(key));
+        assert!(!console.visible);
+    }
+
+    #[test]
+    fn test_tracing_events_show_up_in_the_console() {
+        use strato_core::logging::LogBufferLayer;
+        use tracing_subscriber::layer::SubscriberExt;
+
+        let mut console = console_with(&[]);
+        let subscriber = tracing_subscriber::registry()
+            .with(LogBufferLayer::with_buffer(console.buffer.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::warn!(target: "strato_renderer::gpu", "Surface lost, reconfiguring");
+        });
+        console.layout(Constraints::loose(800.0, 600.0));
+
+        let record = console.matching_records().next().expect("event recorded");
+        assert_eq!(record.level, LogLevel::Warn);
+        assert_eq!(record.category, "renderer");
+        assert_eq!(record.message, "Surface lost, reconfiguring");
+    }
+}
@@ -0,0 +1,1120 @@
+//! Desktop-style menu bar with dropdown menus, submenus and accelerators,
+//! and context menus that share its items and look
+
+use std::any::Any;
+use std::sync::Arc;
+
+use strato_core::event::{Event, EventResult, KeyCode, MouseButton};
+use strato_core::layout::{Constraints, Layout, Size};
+use strato_core::state::Signal;
+use strato_core::text::TextAlign;
+use strato_core::types::{Color, Point, Rect, Transform};
+use strato_renderer::batch::RenderBatch;
+
+use crate::shortcut::{Shortcut, ShortcutHandler, ShortcutRegistry};
+use crate::text::{measure_line_metrics, measure_text_width};
+use crate::widget::{
+    clone_id, generate_id, next_overlay_order, register_bounds, Widget, WidgetContext, WidgetId,
+};
+
+const BAR_HEIGHT: f32 = 28.0;
+const TITLE_PADDING: f32 = 10.0;
+const FONT_SIZE: f32 = 14.0;
+const ITEM_HEIGHT: f32 = 26.0;
+const ITEM_PADDING: f32 = 12.0;
+const SEPARATOR_HEIGHT: f32 = 9.0;
+const PANEL_PADDING: f32 = 4.0;
+const PANEL_MIN_WIDTH: f32 = 180.0;
+const ACCELERATOR_GAP: f32 = 32.0;
+const SUBMENU_ARROW: &str = "▸";
+
+#[derive(Clone)]
+enum MenuItemKind {
+    Action(Option<ShortcutHandler>),
+    Submenu(Vec<MenuItem>),
+    Separator,
+}
+
+/// An entry in a menu: an action, a submenu or a separator
+#[derive(Clone)]
+pub struct MenuItem {
+    label: String,
+    accelerator: Option<Shortcut>,
+    enabled: bool,
+    kind: MenuItemKind,
+}
+
+impl std::fmt::Debug for MenuItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match &self.kind {
+            MenuItemKind::Action(_) => "action",
+            MenuItemKind::Submenu(_) => "submenu",
+            MenuItemKind::Separator => "separator",
+        };
+        f.debug_struct("MenuItem")
+            .field("label", &self.label)
+            .field("kind", &kind)
+            .field("accelerator", &self.accelerator)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
+}
+
+impl MenuItem {
+    /// Create an action item
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+            kind: MenuItemKind::Action(None),
+        }
+    }
+
+    /// Create a separator line
+    pub fn separator() -> Self {
+        Self {
+            label: String::new(),
+            accelerator: None,
+            enabled: false,
+            kind: MenuItemKind::Separator,
+        }
+    }
+
+    /// Create an item that opens a nested menu
+    pub fn submenu(label: impl Into<String>, items: Vec<MenuItem>) -> Self {
+        Self {
+            label: label.into(),
+            accelerator: None,
+            enabled: true,
+            kind: MenuItemKind::Submenu(items),
+        }
+    }
+
+    /// Set the keyboard accelerator shown next to the item
+    pub fn accelerator(mut self, shortcut: Shortcut) -> Self {
+        self.accelerator = Some(shortcut);
+        self
+    }
+
+    /// Enable or disable the item
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Set the handler fired when the item is selected
+    pub fn on_select<F>(mut self, handler: F) -> Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        if let MenuItemKind::Action(slot) = &mut self.kind {
+            *slot = Some(Arc::new(handler));
+        }
+        self
+    }
+
+    /// Get the item label
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Check if the item can be selected
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Check if the item is a separator
+    pub fn is_separator(&self) -> bool {
+        matches!(self.kind, MenuItemKind::Separator)
+    }
+
+    fn height(&self) -> f32 {
+        if self.is_separator() {
+            SEPARATOR_HEIGHT
+        } else {
+            ITEM_HEIGHT
+        }
+    }
+
+    fn children(&self) -> Option<&[MenuItem]> {
+        match &self.kind {
+            MenuItemKind::Submenu(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    fn select(&self) {
+        if let MenuItemKind::Action(Some(handler)) = &self.kind {
+            handler();
+        }
+    }
+}
+
+/// A top-level menu ("File", "Edit", ...)
+#[derive(Debug, Clone)]
+pub struct Menu {
+    pub title: String,
+    pub items: Vec<MenuItem>,
+}
+
+impl Menu {
+    /// Create an empty menu
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            items: Vec::new(),
+        }
+    }
+
+    /// Add an item
+    pub fn item(mut self, item: MenuItem) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Add a separator
+    pub fn separator(mut self) -> Self {
+        self.items.push(MenuItem::separator());
+        self
+    }
+}
+
+/// Visual style of the menu bar and its panels
+#[derive(Debug, Clone)]
+pub struct MenuBarStyle {
+    pub background: Color,
+    pub panel_background: Color,
+    pub highlight: Color,
+    pub text_color: Color,
+    pub disabled_text_color: Color,
+    pub accelerator_color: Color,
+    pub separator_color: Color,
+}
+
+impl Default for MenuBarStyle {
+    fn default() -> Self {
+        Self {
+            background: Color::rgb(0.16, 0.16, 0.16),
+            panel_background: Color::rgba(0.2, 0.2, 0.2, 0.98),
+            highlight: Color::rgb(0.25, 0.45, 0.8),
+            text_color: Color::rgb(0.92, 0.92, 0.92),
+            disabled_text_color: Color::rgb(0.5, 0.5, 0.5),
+            accelerator_color: Color::rgb(0.65, 0.65, 0.65),
+            separator_color: Color::rgba(1.0, 1.0, 1.0, 0.12),
+        }
+    }
+}
+
+/// Geometry of an open menu panel
+struct PanelLayout {
+    rect: Rect,
+    item_rects: Vec<Rect>,
+}
+
+/// Size an item panel whose top-left corner is at `origin`
+fn panel_layout(items: &[MenuItem], origin: Point) -> PanelLayout {
+    let width = items
+        .iter()
+        .map(|item| {
+            let mut width = measure_text_width(&item.label, FONT_SIZE, 0.0);
+            if let Some(shortcut) = item.accelerator {
+                width +=
+                    ACCELERATOR_GAP + measure_text_width(&shortcut.to_string(), FONT_SIZE, 0.0);
+            } else if item.children().is_some() {
+                width += ACCELERATOR_GAP;
+            }
+            width + ITEM_PADDING * 2.0
+        })
+        .fold(PANEL_MIN_WIDTH, f32::max);
+
+    let mut y = origin.y + PANEL_PADDING;
+    let item_rects = items
+        .iter()
+        .map(|item| {
+            let rect = Rect::new(
+                origin.x + PANEL_PADDING,
+                y,
+                width - PANEL_PADDING * 2.0,
+                item.height(),
+            );
+            y += item.height();
+            rect
+        })
+        .collect();
+
+    PanelLayout {
+        rect: Rect::new(origin.x, origin.y, width, y + PANEL_PADDING - origin.y),
+        item_rects,
+    }
+}
+
+/// Draw a panel of items, highlighting the enabled items `highlighted` picks
+fn render_panel(
+    batch: &mut RenderBatch,
+    style: &MenuBarStyle,
+    items: &[MenuItem],
+    panel: &PanelLayout,
+    highlighted: impl Fn(usize) -> bool,
+) {
+    batch.add_rect(panel.rect, style.panel_background, Transform::identity());
+
+    for (index, (item, rect)) in items.iter().zip(&panel.item_rects).enumerate() {
+        if item.is_separator() {
+            let line = Rect::new(rect.x, rect.y + rect.height / 2.0, rect.width, 1.0);
+            batch.add_rect(line, style.separator_color, Transform::identity());
+            continue;
+        }
+
+        if item.enabled && highlighted(index) {
+            batch.add_rect(*rect, style.highlight, Transform::identity());
+        }
+
+        let text_y = measure_line_metrics(FONT_SIZE).centered_top(rect.y, rect.height);
+        let color = if item.enabled {
+            style.text_color
+        } else {
+            style.disabled_text_color
+        };
+        batch.add_text_aligned(
+            item.label.clone(),
+            (rect.x + ITEM_PADDING, text_y),
+            color,
+            FONT_SIZE,
+            0.0,
+            TextAlign::Left,
+        );
+
+        let trailing = match (item.accelerator, item.children()) {
+            (Some(shortcut), _) => Some((shortcut.to_string(), style.accelerator_color)),
+            (None, Some(_)) => Some((SUBMENU_ARROW.to_string(), color)),
+            _ => None,
+        };
+        if let Some((text, color)) = trailing {
+            batch.add_text_aligned(
+                text,
+                (rect.x + rect.width - ITEM_PADDING, text_y),
+                color,
+                FONT_SIZE,
+                0.0,
+                TextAlign::Right,
+            );
+        }
+    }
+}
+
+/// Horizontal bar of menus whose panels are drawn as overlays
+///
+/// Item accelerators are registered with a [`ShortcutRegistry`] (the global
+/// one by default) when the bar is mounted, so they fire even while the menus
+/// are closed, and unregistered when it is unmounted.
+#[derive(Debug)]
+pub struct MenuBar {
+    id: WidgetId,
+    menus: Vec<Menu>,
+    style: MenuBarStyle,
+    registry: ShortcutRegistry,
+    /// Whether the accelerators are in `registry`
+    accelerators_registered: bool,
+    /// Open top-level menu followed by the indices of open submenus
+    open_path: Vec<usize>,
+    /// Overlay order taken when the menus were opened
+    overlay_order: u64,
+    /// Hovered item as (panel level, item index)
+    hovered: Option<(usize, usize)>,
+    bounds: Signal<Rect>,
+}
+
+impl MenuBar {
+    /// Create an empty menu bar
+    pub fn new() -> Self {
+        Self {
+            id: generate_id(),
+            menus: Vec::new(),
+            style: MenuBarStyle::default(),
+            registry: ShortcutRegistry::global(),
+            accelerators_registered: false,
+            open_path: Vec::new(),
+            overlay_order: 0,
+            hovered: None,
+            bounds: Signal::new(Rect::default()),
+        }
+    }
+
+    /// Add a top-level menu
+    pub fn menu(mut self, menu: Menu) -> Self {
+        self.menus.push(menu);
+        self
+    }
+
+    /// Set the style
+    pub fn style(mut self, style: MenuBarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Register accelerators with a specific registry instead of the global one
+    pub fn shortcut_registry(mut self, registry: ShortcutRegistry) -> Self {
+        self.registry = registry;
+        self
+    }
+
+    /// Get the menus
+    pub fn menus(&self) -> &[Menu] {
+        &self.menus
+    }
+
+    /// Index of the open top-level menu, if any
+    pub fn open_menu(&self) -> Option<usize> {
+        self.open_path.first().copied()
+    }
+
+    /// Close all open menus
+    pub fn close(&mut self) {
+        self.open_path.clear();
+        self.hovered = None;
+    }
+
+    /// Register the accelerators of every menu, once
+    fn register_accelerators(&mut self) {
+        if self.accelerators_registered {
+            return;
+        }
+        self.accelerators_registered = true;
+        for menu in &self.menus {
+            Self::visit_accelerators(&menu.items, &mut |shortcut, handler| {
+                self.registry.register_shared(shortcut, handler.clone());
+            });
+        }
+    }
+
+    /// Remove the accelerators added by [`register_accelerators`](Self::register_accelerators)
+    ///
+    /// Bindings another owner has since registered over ours are left alone.
+    fn unregister_accelerators(&mut self) {
+        if !self.accelerators_registered {
+            return;
+        }
+        self.accelerators_registered = false;
+        for menu in &self.menus {
+            Self::visit_accelerators(&menu.items, &mut |shortcut, handler| {
+                self.registry.unregister_shared(shortcut, handler);
+            });
+        }
+    }
+
+    fn visit_accelerators(items: &[MenuItem], visit: &mut dyn FnMut(Shortcut, &ShortcutHandler)) {
+        for item in items {
+            match (&item.kind, item.accelerator) {
+                (MenuItemKind::Action(Some(handler)), Some(shortcut)) if item.enabled => {
+                    visit(shortcut, handler);
+                }
+                (MenuItemKind::Submenu(children), _) if item.enabled => {
+                    Self::visit_accelerators(children, visit);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn title_rects(&self, bounds: Rect) -> Vec<Rect> {
+        let mut x = bounds.x;
+        self.menus
+            .iter()
+            .map(|menu| {
+                let width = measure_text_width(&menu.title, FONT_SIZE, 0.0) + TITLE_PADDING * 2.0;
+                let rect = Rect::new(x, bounds.y, width, bounds.height);
+                x += width;
+                rect
+            })
+            .collect()
+    }
+
+    /// Items shown in each open panel, from the top-level menu down
+    fn open_panels(&self) -> Vec<&[MenuItem]> {
+        let Some(menu) = self.open_path.first().and_then(|&i| self.menus.get(i)) else {
+            return Vec::new();
+        };
+        let mut panels: Vec<&[MenuItem]> = vec![&menu.items];
+        for &index in &self.open_path[1..] {
+            match panels
+                .last()
+                .and_then(|items| items.get(index))
+                .and_then(MenuItem::children)
+            {
+                Some(children) => panels.push(children),
+                None => break,
+            }
+        }
+        panels
+    }
+
+    fn panel_layouts(&self, bounds: Rect) -> Vec<PanelLayout> {
+        let Some(&menu_index) = self.open_path.first() else {
+            return Vec::new();
+        };
+        let Some(title) = self.title_rects(bounds).get(menu_index).copied() else {
+            return Vec::new();
+        };
+
+        let mut layouts: Vec<PanelLayout> = Vec::new();
+        for (level, items) in self.open_panels().into_iter().enumerate() {
+            let origin = match layouts.last() {
+                // Submenus open to the right of the item that owns them.
+                Some(parent) => {
+                    let anchor = parent.item_rects[self.open_path[level]];
+                    Point::new(parent.rect.x + parent.rect.width, anchor.y - PANEL_PADDING)
+                }
+                None => Point::new(title.x, title.y + title.height),
+            };
+            layouts.push(panel_layout(items, origin));
+        }
+        layouts
+    }
+
+    /// Find the (level, item) under a point in the open panels
+    fn item_at(&self, layouts: &[PanelLayout], point: Point) -> Option<(usize, usize)> {
+        // Deeper panels are drawn on top, so check them first.
+        layouts.iter().enumerate().rev().find_map(|(level, panel)| {
+            if !panel.rect.contains(point) {
+                return None;
+            }
+            let index = panel
+                .item_rects
+                .iter()
+                .position(|rect| rect.contains(point));
+            Some((level, index.unwrap_or(usize::MAX)))
+        })
+    }
+
+    fn activate(&mut self, level: usize, index: usize) {
+        let panels = self.open_panels();
+        let Some(item) = panels.get(level).and_then(|items| items.get(index)) else {
+            return;
+        };
+        if !item.enabled {
+            return;
+        }
+        match &item.kind {
+            MenuItemKind::Submenu(_) => {
+                self.open_path.truncate(level + 1);
+                self.open_path.push(index);
+            }
+            MenuItemKind::Action(_) => {
+                let item = item.clone();
+                self.close();
+                item.select();
+            }
+            MenuItemKind::Separator => {}
+        }
+    }
+
+    fn render_panel(
+        &self,
+        batch: &mut RenderBatch,
+        items: &[MenuItem],
+        panel: &PanelLayout,
+        level: usize,
+    ) {
+        render_panel(batch, &self.style, items, panel, |index| {
+            self.hovered == Some((level, index)) || self.open_path.get(level + 1) == Some(&index)
+        });
+    }
+}
+
+impl Default for MenuBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Widget for MenuBar {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn on_mount(&mut self, _ctx: &WidgetContext) {
+        self.register_accelerators();
+    }
+
+    fn on_unmount(&mut self, _ctx: &WidgetContext) {
+        self.unregister_accelerators();
+    }
+
+    fn update(&mut self, _ctx: &WidgetContext) {
+        // Bars driven without mounting still get their accelerators
+        self.register_accelerators();
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let width = if constraints.max_width.is_finite() {
+            constraints.max_width
+        } else {
+            let bounds = Rect::new(0.0, 0.0, 0.0, BAR_HEIGHT);
+            self.title_rects(bounds).iter().map(|r| r.width).sum()
+        };
+        constraints.constrain(Size::new(width, BAR_HEIGHT))
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
+        batch.add_rect(bounds, self.style.background, Transform::identity());
+
+        for (index, (menu, rect)) in self.menus.iter().zip(self.title_rects(bounds)).enumerate() {
+            if self.open_menu() == Some(index) {
+                batch.add_rect(rect, self.style.highlight, Transform::identity());
+            }
+            batch.add_text_aligned(
+                menu.title.clone(),
+                (
+                    rect.x + TITLE_PADDING,
+                    measure_line_metrics(FONT_SIZE).centered_top(rect.y, rect.height),
+                ),
+                self.style.text_color,
+                FONT_SIZE,
+                0.0,
+                TextAlign::Left,
+            );
+        }
+
+        let panels = self.open_panels();
+        if panels.is_empty() {
+            return;
+        }
+        let mut overlay = RenderBatch::new();
+        let layouts = self.panel_layouts(bounds);
+        for (level, (items, panel)) in panels.into_iter().zip(&layouts).enumerate() {
+            self.render_panel(&mut overlay, items, panel, level);
+        }
+        batch.push_overlay(self.overlay_order, overlay);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let bounds = self.bounds.get();
+        match event {
+            Event::KeyDown(key)
+                if key.key_code == KeyCode::Escape && self.open_menu().is_some() =>
+            {
+                // Escape closes the innermost submenu first.
+                self.open_path.pop();
+                self.hovered = None;
+                EventResult::Handled
+            }
+            Event::MouseMove(mouse) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                if self.open_menu().is_none() {
+                    return EventResult::Ignored;
+                }
+
+                // Sliding across titles switches between open menus.
+                if let Some(index) = self
+                    .title_rects(bounds)
+                    .iter()
+                    .position(|rect| rect.contains(point))
+                {
+                    if self.open_menu() != Some(index) {
+                        self.open_path = vec![index];
+                        self.hovered = None;
+                    }
+                    return EventResult::Handled;
+                }
+
+                let layouts = self.panel_layouts(bounds);
+                self.hovered = self
+                    .item_at(&layouts, point)
+                    .filter(|&(_, index)| index != usize::MAX);
+                if let Some((level, index)) = self.hovered {
+                    let opens_submenu = self.open_panels()[level]
+                        .get(index)
+                        .is_some_and(|item| item.enabled && item.children().is_some());
+                    if opens_submenu {
+                        self.activate(level, index);
+                    } else {
+                        self.open_path.truncate(level + 1);
+                    }
+                    return EventResult::Handled;
+                }
+                EventResult::Ignored
+            }
+            Event::MouseDown(mouse) if mouse.button == Some(MouseButton::Left) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+
+                if let Some(index) = self
+                    .title_rects(bounds)
+                    .iter()
+                    .position(|rect| rect.contains(point))
+                {
+                    if self.open_menu() == Some(index) {
+                        self.close();
+                    } else {
+                        if self.open_menu().is_none() {
+                            self.overlay_order = next_overlay_order();
+                        }
+                        self.open_path = vec![index];
+                        self.hovered = None;
+                    }
+                    return EventResult::Handled;
+                }
+
+                if self.open_menu().is_none() {
+                    return EventResult::Ignored;
+                }
+
+                let layouts = self.panel_layouts(bounds);
+                match self.item_at(&layouts, point) {
+                    Some((level, index)) => {
+                        self.activate(level, index);
+                        EventResult::Handled
+                    }
+                    None => {
+                        // Clicking outside closes the menus; the click still
+                        // reaches whatever is underneath.
+                        self.close();
+                        EventResult::Ignored
+                    }
+                }
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.open_menu().map(|_| self.overlay_order)
+    }
+
+    fn hit_test(&self, point: Point, layout: Layout) -> bool {
+        layout.contains(point.to_vec2())
+            || self
+                .panel_layouts(self.bounds.get())
+                .iter()
+                .any(|panel| panel.rect.contains(point))
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(Self {
+            id: clone_id(self.id),
+            menus: self.menus.clone(),
+            style: self.style.clone(),
+            registry: self.registry.clone(),
+            accelerators_registered: false,
+            open_path: Vec::new(),
+            overlay_order: 0,
+            hovered: None,
+            bounds: Signal::new(Rect::default()),
+        })
+    }
+}
+
+/// What a [`ContextMenu`] did with an event
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuResponse {
+    /// The menu is closed or the event was not meant for it
+    Ignored,
+    /// The menu used the event, e.g. to track the hovered item or to close
+    Handled,
+    /// The item at this index was chosen; its handler has run and the menu closed
+    Selected(usize),
+}
+
+/// Panel of menu items opened at a point, e.g. on right-click
+///
+/// A context menu is not a widget of its own: its owner opens it, offers it
+/// events before handling them itself and calls [`ContextMenu::render`]
+/// after drawing, so the panel lands in an overlay above the rest of the
+/// frame. The panel is kept inside the batch viewport, opening upward or to
+/// the left near the window edges.
+#[derive(Debug)]
+pub struct ContextMenu {
+    items: Vec<MenuItem>,
+    style: MenuBarStyle,
+    /// Point the menu was opened at, while open
+    anchor: Option<Point>,
+    /// Top-left corner of the panel as last placed inside the viewport
+    origin: Signal<Point>,
+    hovered: Option<usize>,
+    overlay_order: u64,
+}
+
+impl ContextMenu {
+    /// Create a closed context menu
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            style: MenuBarStyle::default(),
+            anchor: None,
+            origin: Signal::new(Point::zero()),
+            hovered: None,
+            overlay_order: 0,
+        }
+    }
+
+    /// Set the style
+    pub fn style(mut self, style: MenuBarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Open the menu with `items` at `position`
+    ///
+    /// Items are passed on every open so their enabled state can reflect
+    /// the owner's state at that moment.
+    pub fn open(&mut self, items: Vec<MenuItem>, position: Point) {
+        self.items = items;
+        self.anchor = Some(position);
+        self.origin.set(position);
+        self.hovered = None;
+        self.overlay_order = next_overlay_order();
+    }
+
+    /// Close the menu
+    pub fn close(&mut self) {
+        self.anchor = None;
+        self.hovered = None;
+    }
+
+    /// Check if the menu is open
+    pub fn is_open(&self) -> bool {
+        self.anchor.is_some()
+    }
+
+    /// The overlay order taken when the menu opened, while open
+    pub fn overlay_order(&self) -> Option<u64> {
+        self.is_open().then_some(self.overlay_order)
+    }
+
+    /// The items shown by the menu
+    pub fn items(&self) -> &[MenuItem] {
+        &self.items
+    }
+
+    /// Index of the highlighted item, if any
+    pub fn hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Draw the open menu as an overlay
+    pub fn render(&self, batch: &mut RenderBatch) {
+        let Some(anchor) = self.anchor else {
+            return;
+        };
+
+        let size = panel_layout(&self.items, anchor).rect;
+        let mut origin = anchor;
+        if let Some(viewport) = batch.viewport() {
+            if origin.x + size.width > viewport.x + viewport.width {
+                origin.x = (viewport.x + viewport.width - size.width).max(viewport.x);
+            }
+            if origin.y + size.height > viewport.y + viewport.height {
+                origin.y = (anchor.y - size.height).max(viewport.y);
+            }
+        }
+        self.origin.set(origin);
+
+        let mut overlay = RenderBatch::new();
+        let panel = panel_layout(&self.items, origin);
+        render_panel(&mut overlay, &self.style, &self.items, &panel, |index| {
+            self.hovered == Some(index)
+        });
+        batch.push_overlay(self.overlay_order, overlay);
+    }
+
+    /// Offer an event to the menu
+    pub fn handle_event(&mut self, event: &Event) -> ContextMenuResponse {
+        if !self.is_open() {
+            return ContextMenuResponse::Ignored;
+        }
+
+        let panel = panel_layout(&self.items, self.origin.get());
+        match event {
+            Event::KeyDown(key) => match key.key_code {
+                KeyCode::Escape => {
+                    self.close();
+                    ContextMenuResponse::Handled
+                }
+                KeyCode::Up => {
+                    self.move_hover(-1);
+                    ContextMenuResponse::Handled
+                }
+                KeyCode::Down => {
+                    self.move_hover(1);
+                    ContextMenuResponse::Handled
+                }
+                KeyCode::Enter => match self.hovered {
+                    Some(index) => self.select(index),
+                    None => ContextMenuResponse::Handled,
+                },
+                _ => ContextMenuResponse::Ignored,
+            },
+            Event::MouseMove(mouse) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                if !panel.rect.contains(point) {
+                    return ContextMenuResponse::Ignored;
+                }
+                self.hovered = self.selectable_at(&panel, point);
+                ContextMenuResponse::Handled
+            }
+            Event::MouseDown(mouse) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                if !panel.rect.contains(point) {
+                    // Clicking elsewhere dismisses the menu; the click still
+                    // reaches whatever is underneath.
+                    self.close();
+                    return ContextMenuResponse::Ignored;
+                }
+                match self.selectable_at(&panel, point) {
+                    Some(index) => self.select(index),
+                    None => ContextMenuResponse::Handled,
+                }
+            }
+            _ => ContextMenuResponse::Ignored,
+        }
+    }
+
+    fn selectable_at(&self, panel: &PanelLayout, point: Point) -> Option<usize> {
+        panel
+            .item_rects
+            .iter()
+            .position(|rect| rect.contains(point))
+            .filter(|&index| self.is_selectable(index))
+    }
+
+    fn is_selectable(&self, index: usize) -> bool {
+        self.items
+            .get(index)
+            .is_some_and(|item| item.enabled && !item.is_separator())
+    }
+
+    fn move_hover(&mut self, step: isize) {
+        let count = self.items.len() as isize;
+        let mut index = self
+            .hovered
+            .map_or(if step > 0 { -1 } else { count }, |i| i as isize);
+        for _ in 0..count {
+            index = (index + step).rem_euclid(count);
+            if self.is_selectable(index as usize) {
+                self.hovered = Some(index as usize);
+                return;
+            }
+        }
+    }
+
+    fn select(&mut self, index: usize) -> ContextMenuResponse {
+        let item = self.items[index].clone();
+        self.close();
+        item.select();
+        ContextMenuResponse::Selected(index)
+    }
+}
+
+impl Default for ContextMenu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clone for ContextMenu {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+            style: self.style.clone(),
+            anchor: self.anchor,
+            origin: Signal::new(self.origin.get()),
+            hovered: self.hovered,
+            overlay_order: self.overlay_order,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use strato_core::event::{KeyboardEvent, Modifiers, MouseEvent};
+
+    fn click(x: f32, y: f32) -> Event {
+        Event::MouseDown(MouseEvent {
+            position: Vec2::new(x, y),
+            button: Some(MouseButton::Left),
+            modifiers: Modifiers::default(),
+            delta: Vec2::ZERO,
+        })
+    }
+
+    fn key(shortcut: Shortcut) -> Event {
+        Event::KeyDown(KeyboardEvent {
+            key_code: shortcut.key,
+            modifiers: shortcut.modifiers,
+            is_repeat: false,
+            text: None,
+        })
+    }
+
+    fn render(bar: &mut MenuBar) -> RenderBatch {
+        let size = bar.layout(Constraints::loose(800.0, 600.0));
+        let mut batch = RenderBatch::new();
+        bar.render(&mut batch, Layout::new(Vec2::ZERO, size));
+        batch
+    }
+
+    fn file_menu(saves: Arc<AtomicUsize>) -> Menu {
+        Menu::new("File")
+            .item(
+                MenuItem::new("Save")
+                    .accelerator(Shortcut::ctrl(KeyCode::S))
+                    .on_select(move || {
+                        saves.fetch_add(1, Ordering::SeqCst);
+                    }),
+            )
+            .separator()
+            .item(MenuItem::new("Print").enabled(false))
+            .item(MenuItem::submenu(
+                "Recent",
+                vec![MenuItem::new("a.txt"), MenuItem::new("b.txt")],
+            ))
+    }
+
+    #[test]
+    fn test_click_title_opens_and_closes_menu() {
+        let registry = ShortcutRegistry::new();
+        let mut bar = MenuBar::new()
+            .shortcut_registry(registry)
+            .menu(file_menu(Arc::default()));
+        render(&mut bar);
+
+        assert_eq!(bar.handle_event(&click(5.0, 5.0)), EventResult::Handled);
+        assert_eq!(bar.open_menu(), Some(0));
+
+        // Panels are drawn into the overlay layer
+        let batch = render(&mut bar);
+        assert!(!batch.overlay_commands.is_empty());
+
+        bar.handle_event(&click(5.0, 5.0));
+        assert_eq!(bar.open_menu(), None);
+    }
+
+    #[test]
+    fn test_select_item_fires_and_closes() {
+        let saves = Arc::new(AtomicUsize::new(0));
+        let mut bar = MenuBar::new()
+            .shortcut_registry(ShortcutRegistry::new())
+            .menu(file_menu(saves.clone()));
+        render(&mut bar);
+        bar.handle_event(&click(5.0, 5.0));
+        render(&mut bar);
+
+        // First item sits just below the bar
+        bar.handle_event(&click(20.0, BAR_HEIGHT + PANEL_PADDING + 5.0));
+        assert_eq!(saves.load(Ordering::SeqCst), 1);
+        assert_eq!(bar.open_menu(), None);
+    }
+
+    #[test]
+    fn test_escape_and_outside_click_close() {
+        let mut bar = MenuBar::new()
+            .shortcut_registry(ShortcutRegistry::new())
+            .menu(file_menu(Arc::default()));
+        render(&mut bar);
+
+        bar.handle_event(&click(5.0, 5.0));
+        bar.handle_event(&key(Shortcut::key(KeyCode::Escape)));
+        assert_eq!(bar.open_menu(), None);
+
+        bar.handle_event(&click(5.0, 5.0));
+        assert_eq!(bar.handle_event(&click(600.0, 400.0)), EventResult::Ignored);
+        assert_eq!(bar.open_menu(), None);
+    }
+
+    #[test]
+    fn test_disabled_item_does_not_close_menu() {
+        let mut bar = MenuBar::new()
+            .shortcut_registry(ShortcutRegistry::new())
+            .menu(file_menu(Arc::default()));
+        render(&mut bar);
+        bar.handle_event(&click(5.0, 5.0));
+
+        let y = BAR_HEIGHT + PANEL_PADDING + ITEM_HEIGHT + SEPARATOR_HEIGHT + 5.0;
+        bar.handle_event(&click(20.0, y));
+        assert_eq!(bar.open_menu(), Some(0));
+    }
+
+    #[test]
+    fn test_accelerators_fire_through_registry() {
+        let saves = Arc::new(AtomicUsize::new(0));
+        let registry = ShortcutRegistry::new();
+        let mut bar = MenuBar::new()
+            .menu(file_menu(saves.clone()))
+            .shortcut_registry(registry.clone());
+
+        // Nothing is registered until the bar is mounted
+        assert!(!registry.contains(Shortcut::ctrl(KeyCode::S)));
+
+        let theme = crate::theme::Theme::default();
+        let ctx = WidgetContext {
+            theme: &theme,
+            state: crate::widget::WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time: 0.0,
+            input: Default::default(),
+        };
+        let mut mounted = crate::widget::MountedWidgets::new();
+        crate::widget::mount_tree(&mut bar, &ctx, &mut mounted);
+        assert!(registry.contains(Shortcut::ctrl(KeyCode::S)));
+        registry.handle_event(&key(Shortcut::ctrl(KeyCode::S)));
+        assert_eq!(saves.load(Ordering::SeqCst), 1);
+
+        crate::widget::unmount_tree(&mut bar, &ctx, &mut mounted);
+        assert!(!registry.contains(Shortcut::ctrl(KeyCode::S)));
+    }
+
+    #[test]
+    fn test_unmount_keeps_accelerator_taken_over_by_another_owner() {
+        let registry = ShortcutRegistry::new();
+        let mut bar = MenuBar::new()
+            .menu(file_menu(Arc::default()))
+            .shortcut_registry(registry.clone());
+
+        let theme = crate::theme::Theme::default();
+        let ctx = WidgetContext {
+            theme: &theme,
+            state: crate::widget::WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time: 0.0,
+            input: Default::default(),
+        };
+        let mut mounted = crate::widget::MountedWidgets::new();
+        crate::widget::mount_tree(&mut bar, &ctx, &mut mounted);
+
+        let other_saves = Arc::new(AtomicUsize::new(0));
+        let counter = other_saves.clone();
+        registry.register(Shortcut::ctrl(KeyCode::S), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        crate::widget::unmount_tree(&mut bar, &ctx, &mut mounted);
+        assert!(registry.contains(Shortcut::ctrl(KeyCode::S)));
+        registry.handle_event(&key(Shortcut::ctrl(KeyCode::S)));
+        assert_eq!(other_saves.load(Ordering::SeqCst), 1);
+    }
+}
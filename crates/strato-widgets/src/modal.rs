@@ -0,0 +1,385 @@
+//! Modal layers blocking the page behind them
+//!
+//! A [`Modal`] draws its content centered over a backdrop covering the
+//! window while its `open` signal is true. It is an overlay owner (see
+//! [`Widget::overlay_order`]), so it sees pointer events before the widgets
+//! beneath it and consumes every one of them: clicking behind an open modal
+//! doesn't reach the page. Its content sits in a trapping
+//! [`FocusScope`](crate::focus::FocusScope), so Tab can't reach the page
+//! either.
+//!
+//! Place it above the page, e.g. in a [`Stack`](crate::layout::Stack):
+//!
+//! ```
+//! use strato_core::state::Signal;
+//! use strato_widgets::{Button, Column, Modal, Stack, Text};
+//!
+//! let confirm = Signal::new(false);
+//! let page = Stack::new().children(vec![
+//!     Box::new(Button::new("Delete")),
+//!     Box::new(Modal::new(
+//!         confirm.clone(),
+//!         Column::new().children(vec![
+//!             Box::new(Text::new("Delete the file?")),
+//!             Box::new(Button::new("Delete")),
+//!         ]),
+//!     )),
+//! ]);
+//! ```
+
+use crate::focus::FocusScope;
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, next_overlay_order, Widget, WidgetId,
+};
+use strato_core::{
+    event::{Event, EventResult, KeyCode},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    state::Signal,
+    types::{Color, Point, Rect, Transform},
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Content shown over a backdrop while `open` is true, blocking input to the
+/// widgets beneath
+///
+/// The modal fills the space its parent gives it; the backdrop covers the
+/// batch viewport, or that space when the batch has none. A dismissible
+/// modal closes on Escape and on clicks on the backdrop.
+pub struct Modal {
+    id: WidgetId,
+    open: Signal<bool>,
+    content: Box<dyn Widget>,
+    dismissible: bool,
+    backdrop: Color,
+    /// Whether `open` was true at the last layout
+    was_open: bool,
+    overlay_order: u64,
+    content_size: Size,
+    /// Where the content was last drawn
+    content_bounds: Signal<Rect>,
+    layout_cache: LayoutCache,
+}
+
+impl std::fmt::Debug for Modal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Modal")
+            .field("id", &self.id)
+            .field("open", &self.open.get())
+            .field("content", &self.content)
+            .field("dismissible", &self.dismissible)
+            .field("backdrop", &self.backdrop)
+            .finish()
+    }
+}
+
+impl Modal {
+    /// Show `content` while `open` is true
+    pub fn new(open: Signal<bool>, content: impl Widget + 'static) -> Self {
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&open);
+        Self {
+            id: generate_id(),
+            open,
+            content: Box::new(FocusScope::new(content).trap(true)),
+            dismissible: true,
+            backdrop: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            was_open: false,
+            overlay_order: 0,
+            content_size: Size::zero(),
+            content_bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            layout_cache,
+        }
+    }
+
+    /// Close on Escape and on clicks on the backdrop (default true)
+    pub fn dismissible(mut self, dismissible: bool) -> Self {
+        self.dismissible = dismissible;
+        self
+    }
+
+    /// Set the backdrop color
+    pub fn backdrop(mut self, color: Color) -> Self {
+        self.backdrop = color;
+        self
+    }
+
+    /// Show the modal
+    pub fn open(&self) {
+        self.open.set(true);
+    }
+
+    /// Hide the modal
+    pub fn close(&self) {
+        self.open.set(false);
+    }
+
+    /// Whether the modal is shown
+    pub fn is_open(&self) -> bool {
+        self.open.get()
+    }
+
+    /// The content, inside its focus scope
+    pub fn content(&self) -> &dyn Widget {
+        self.content.as_ref()
+    }
+}
+
+impl Widget for Modal {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || {
+            let open = self.open.get();
+            if open && !self.was_open {
+                // Opened last, so above the popups already open
+                self.overlay_order = next_overlay_order();
+            }
+            self.was_open = open;
+            if open {
+                let content = Constraints::loose(constraints.max_width, constraints.max_height);
+                self.content_size = layout_child(&cache, self.content.as_mut(), content);
+            }
+
+            let fill = |max: f32| if max.is_finite() { max } else { 0.0 };
+            constraints.constrain(Size::new(
+                fill(constraints.max_width),
+                fill(constraints.max_height),
+            ))
+        })
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        if !self.is_open() {
+            return;
+        }
+        let area = batch.viewport().unwrap_or(Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        ));
+        let size = self.content_size;
+        let bounds = Rect::new(
+            area.x + (area.width - size.width) / 2.0,
+            area.y + (area.height - size.height) / 2.0,
+            size.width,
+            size.height,
+        );
+        self.content_bounds.set(bounds);
+
+        // Drawn as an overlay so it covers popups opened before it
+        let mut overlay = RenderBatch::new();
+        if let Some(viewport) = batch.viewport() {
+            overlay.set_viewport(viewport);
+        }
+        overlay.add_rect(area, self.backdrop, Transform::identity());
+        self.content.render(
+            &mut overlay,
+            Layout::new(glam::Vec2::new(bounds.x, bounds.y), size),
+        );
+        batch.push_overlay(self.overlay_order, overlay);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.is_open() {
+            return EventResult::Ignored;
+        }
+        let result = dispatch_event(self.content.as_mut(), event);
+        match event {
+            Event::MouseDown(mouse) if result == EventResult::Ignored => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                if self.dismissible && !self.content_bounds.get().contains(point) {
+                    self.close();
+                }
+                EventResult::Handled
+            }
+            // The page beneath gets no pointer input while the modal is open
+            Event::MouseDown(_)
+            | Event::MouseUp(_)
+            | Event::MouseMove(_)
+            | Event::MouseWheel { .. }
+            | Event::Gesture(_) => EventResult::Handled,
+            Event::KeyDown(key)
+                if result == EventResult::Ignored
+                    && self.dismissible
+                    && key.key_code == KeyCode::Escape =>
+            {
+                self.close();
+                EventResult::Handled
+            }
+            _ => result,
+        }
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.is_open().then_some(self.overlay_order)
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        if self.is_open() {
+            vec![self.content.as_ref()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        if self.is_open() {
+            vec![self.content.as_mut()]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn detached_children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        if self.is_open() {
+            Vec::new()
+        } else {
+            vec![self.content.as_mut()]
+        }
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&self.open);
+        Box::new(Modal {
+            id: clone_id(self.id),
+            open: self.open.clone(),
+            content: self.content.clone_widget(),
+            dismissible: self.dismissible,
+            backdrop: self.backdrop,
+            was_open: false,
+            overlay_order: 0,
+            content_size: Size::zero(),
+            content_bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            layout_cache,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::layout::Stack;
+    use crate::test_util::{Probe, WidgetTester};
+    use crate::text::Text;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_clicks_behind_an_open_modal_dont_reach_the_page() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = clicks.clone();
+        let open = Signal::new(true);
+        // Declared first, so a plain Stack would deliver clicks to the
+        // button above it before the modal
+        let stack = Stack::new().children(vec![
+            Box::new(Modal::new(open.clone(), Text::new("Are you sure?"))),
+            Box::new(Button::new("Delete").on_click(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            })),
+        ]);
+        let mut tester = WidgetTester::new(stack, 400.0, 300.0);
+
+        tester.click(5.0, 5.0);
+        assert_eq!(clicks.load(Ordering::SeqCst), 0);
+        // The click landed on the backdrop, which dismisses the modal
+        assert!(!open.get());
+
+        tester.click(5.0, 5.0);
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+    }
+
+    /// Overlay owner that counts the events it sees and handles none
+    #[derive(Debug)]
+    struct Popup {
+        id: WidgetId,
+        seen: Arc<AtomicUsize>,
+    }
+
+    impl Widget for Popup {
+        fn id(&self) -> WidgetId {
+            self.id
+        }
+
+        fn layout(&mut self, constraints: Constraints) -> Size {
+            constraints.constrain(Size::zero())
+        }
+
+        fn render(&self, _batch: &mut RenderBatch, _layout: Layout) {}
+
+        fn handle_event(&mut self, _event: &Event) -> EventResult {
+            self.seen.fetch_add(1, Ordering::SeqCst);
+            EventResult::Ignored
+        }
+
+        fn overlay_order(&self) -> Option<u64> {
+            Some(1)
+        }
+
+        fn clone_widget(&self) -> Box<dyn Widget> {
+            Box::new(Popup {
+                id: self.id,
+                seen: self.seen.clone(),
+            })
+        }
+    }
+
+    #[test]
+    fn test_overlay_owners_see_unhandled_pointer_events_once() {
+        let seen = Arc::new(AtomicUsize::new(0));
+        let popup = Popup {
+            id: crate::widget::generate_id(),
+            seen: seen.clone(),
+        };
+        let stack = Stack::new().children(vec![Box::new(popup)]);
+        let mut tester = WidgetTester::new(stack, 400.0, 300.0);
+
+        tester.move_to(5.0, 5.0);
+        assert_eq!(seen.load(Ordering::SeqCst), 1);
+        // Other events go through the tree as usual
+        tester.key_down(strato_core::event::KeyCode::A);
+        assert_eq!(seen.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_opening_and_closing_mounts_and_unmounts_the_content() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let open = Signal::new(false);
+        let modal = Modal::new(open.clone(), Probe::new("content", &log));
+        let mut tester = WidgetTester::new(modal, 400.0, 300.0);
+        tester.advance(0.0);
+        assert!(log.lock().is_empty(), "closed content isn't mounted");
+
+        tester.root().open();
+        tester.pump();
+        tester.advance(0.0);
+        tester.advance(0.0);
+        assert_eq!(*log.lock(), ["mount content"]);
+
+        tester.root().close();
+        tester.pump();
+        tester.advance(0.0);
+        tester.advance(0.0);
+        assert_eq!(*log.lock(), ["mount content", "unmount content"]);
+
+        open.set(true);
+        tester.advance(0.0);
+        assert_eq!(
+            *log.lock(),
+            ["mount content", "unmount content", "mount content"]
+        );
+    }
+}
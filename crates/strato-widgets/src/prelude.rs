@@ -2,9 +2,27 @@
 //!
 //! This module re-exports the most commonly used types and traits from the widgets crate,
 //! allowing users to import everything they need with a single `use strato_widgets::prelude::*;`
+//!
+//! Besides the widgets it brings the enums their builders take, so typical
+//! UIs need no other import from this crate:
+//!
+//! ```
+//! use strato_widgets::prelude::*;
+//!
+//! let header = Row::new()
+//!     .main_axis_alignment(MainAxisAlignment::SpaceBetween)
+//!     .cross_axis_alignment(CrossAxisAlignment::Center)
+//!     .children(vec![
+//!         Box::new(Text::new("Title").font_weight(FontWeight::Bold)),
+//!         Box::new(Text::new("Subtitle").align(TextAlign::Right)),
+//!     ]);
+//! ```
+//!
+//! The same items stay available at their module paths, e.g.
+//! `strato_widgets::text::FontWeight`, for explicit imports.
 
 // Animation
-pub use crate::animation::{AnimationController, Curve, Tween, Tweenable};
+pub use crate::animation::{AnimationController, AnimationSettings, Curve, Tween, Tweenable};
 pub use crate::control::{ControlRole, ControlSemantics, ControlState};
 
 // Re-export core types that are commonly used with widgets
@@ -15,16 +33,26 @@ pub use strato_macros::view;
 pub use crate::widget::{Widget, WidgetId, WidgetState};
 
 // Layout widgets
-pub use crate::container::Container;
-pub use crate::grid::{Grid, GridUnit};
+pub use crate::builder::{BuilderExt, WidgetBuilder};
+pub use crate::container::{Container, Overflow, SizeBehavior};
+pub use crate::focus::FocusScope;
+pub use crate::grid::{Alignment, Grid, GridCell, GridUnit};
 pub use crate::layout::{Column, CrossAxisAlignment, Flex, MainAxisAlignment, Row, Stack};
 pub use crate::scroll_view::ScrollView;
+pub use crate::show::Show;
+pub use crate::state_view::{Dispatch, StateView};
 pub use crate::wrap::{Wrap, WrapAlignment, WrapCrossAlignment};
 
 // Basic widgets
-pub use crate::button::{Button, ButtonStyle};
+pub use crate::button::{Button, ButtonStyle, ButtonStyleBuilder, ButtonStyleSpec};
+pub use crate::canvas::Canvas;
+pub use crate::chart::{Chart, Series};
 pub use crate::input::TextInput;
-pub use crate::text::Text;
+pub use crate::modal::Modal;
+pub use crate::text::{
+    FontStyle, FontWeight, Text, TextAlign, TextDecoration, TextOverflow, TextStyle, VerticalAlign,
+};
+pub use crate::toast::{ToastHost, ToastLevel, ToastManager};
 
 // Theme system
 pub use crate::theme::{ColorPalette, Theme, Typography};
@@ -0,0 +1,419 @@
+//! Bringing a retained widget tree in line with a freshly built one
+//!
+//! Views are built again whenever their state changes. Swapping the new tree
+//! in would drop every widget along with what it keeps internally: focus,
+//! hover, text being edited, running animations. [`reconcile`] instead walks
+//! the retained tree and the new one side by side and changes the retained
+//! widgets only where the new tree differs:
+//!
+//! - A widget of the same type as the new widget at its place takes the new
+//!   widget's props through [`Widget::apply_props`], keeping its ID and
+//!   state. A widget of another type, or one that can't take props, is
+//!   replaced by the new widget and its subtree.
+//! - Children are matched through [`Widget::child_slots`]. In a list, a new
+//!   child with a [`keyed_id`](crate::widget::keyed_id) matches the old
+//!   child with the same key wherever it was, so keyed rows keep their state
+//!   when reordered; unkeyed children match the unkeyed old ones in order.
+//!   Old children left unmatched are removed and new ones inserted.
+//!
+//! Every change is reported as a [`Mutation`]. Widgets taken out of the tree
+//! are handed back in [`Reconciled::removed`] so the caller can unmount
+//! them; inserted widgets aren't mounted yet, which
+//! [`update_tree`](crate::widget::update_tree) does on its next pass.
+
+use crate::widget::{is_keyed_id, Widget, WidgetId};
+use std::collections::{HashMap, VecDeque};
+
+/// Children a widget owns, as [`Widget::child_slots`] exposes them
+pub enum ChildSlots<'a> {
+    /// Children that can be inserted, removed and reordered
+    List(&'a mut Vec<Box<dyn Widget>>),
+    /// A single child that may be absent
+    Optional(&'a mut Option<Box<dyn Widget>>),
+}
+
+/// A change [`reconcile`] made to the retained tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mutation {
+    /// The widget took new props
+    Props(WidgetId),
+    /// The widget `old` was replaced by `new` and its subtree
+    Replaced { old: WidgetId, new: WidgetId },
+    /// `id` was inserted at `index` among the children of `parent`
+    Inserted {
+        parent: WidgetId,
+        id: WidgetId,
+        index: usize,
+    },
+    /// `id` was removed from the children of `parent`
+    Removed { parent: WidgetId, id: WidgetId },
+    /// The keyed child `id` of `parent` moved before siblings it followed,
+    /// and is now at `index`
+    Moved {
+        parent: WidgetId,
+        id: WidgetId,
+        index: usize,
+    },
+}
+
+/// What [`reconcile`] did
+#[derive(Debug, Default)]
+pub struct Reconciled {
+    /// Changes in the order they were made, parents before their children
+    pub mutations: Vec<Mutation>,
+    /// Widgets taken out of the tree, removed or replaced, with their
+    /// subtrees
+    pub removed: Vec<Box<dyn Widget>>,
+}
+
+impl Reconciled {
+    /// Whether the retained tree is unchanged
+    pub fn is_empty(&self) -> bool {
+        self.mutations.is_empty()
+    }
+}
+
+/// Bring the tree at `retained` in line with `new`, reusing the retained
+/// widgets that match
+///
+/// ```
+/// use strato_widgets::prelude::*;
+/// use strato_widgets::reconcile::{reconcile, Mutation};
+///
+/// let list = |labels: &[&str]| -> Box<dyn Widget> {
+///     Box::new(Column::new().children(
+///         labels.iter().map(|label| Box::new(Text::new(*label).key(label)) as _).collect(),
+///     ))
+/// };
+/// let mut retained = list(&["a", "b"]);
+/// let reconciled = reconcile(&mut retained, list(&["b", "a", "c"]));
+/// assert_eq!(reconciled.mutations.len(), 2);
+/// assert!(matches!(reconciled.mutations[0], Mutation::Moved { index: 1, .. }));
+/// assert!(matches!(reconciled.mutations[1], Mutation::Inserted { index: 2, .. }));
+/// ```
+pub fn reconcile(retained: &mut Box<dyn Widget>, new: Box<dyn Widget>) -> Reconciled {
+    let mut reconciled = Reconciled::default();
+    reconcile_node(retained, new, &mut reconciled);
+    if !reconciled.is_empty() {
+        strato_core::reactive::mark_dirty();
+    }
+    reconciled
+}
+
+fn reconcile_node(retained: &mut Box<dyn Widget>, mut new: Box<dyn Widget>, out: &mut Reconciled) {
+    let same_type = retained.as_any().type_id() == new.as_any().type_id();
+    let changed = match same_type
+        .then(|| retained.apply_props(new.as_ref()))
+        .flatten()
+    {
+        Some(changed) => changed,
+        None => {
+            let old = std::mem::replace(retained, new);
+            out.mutations.push(Mutation::Replaced {
+                old: old.id(),
+                new: retained.id(),
+            });
+            out.removed.push(old);
+            return;
+        }
+    };
+
+    let id = retained.id();
+    if changed {
+        out.mutations.push(Mutation::Props(id));
+    }
+    let before = out.mutations.len();
+    match (retained.child_slots(), new.child_slots()) {
+        (Some(ChildSlots::List(children)), Some(ChildSlots::List(new_children))) => {
+            reconcile_list(id, children, std::mem::take(new_children), out);
+        }
+        (Some(ChildSlots::Optional(child)), Some(ChildSlots::Optional(new_child))) => {
+            match (child.take(), new_child.take()) {
+                (Some(mut old), Some(new_child)) => {
+                    reconcile_node(&mut old, new_child, out);
+                    *child = Some(old);
+                }
+                (None, Some(new_child)) => {
+                    out.mutations.push(Mutation::Inserted {
+                        parent: id,
+                        id: new_child.id(),
+                        index: 0,
+                    });
+                    *child = Some(new_child);
+                }
+                (Some(old), None) => {
+                    out.mutations.push(Mutation::Removed {
+                        parent: id,
+                        id: old.id(),
+                    });
+                    out.removed.push(old);
+                }
+                (None, None) => {}
+            }
+        }
+        _ => {}
+    }
+
+    // Children that changed may change the size they take within this one
+    if out.mutations.len() > before {
+        if let Some(cache) = retained.layout_cache() {
+            cache.mark_dirty();
+        }
+    }
+}
+
+fn reconcile_list(
+    parent: WidgetId,
+    children: &mut Vec<Box<dyn Widget>>,
+    new_children: Vec<Box<dyn Widget>>,
+    out: &mut Reconciled,
+) {
+    let mut old: Vec<Option<Box<dyn Widget>>> = children.drain(..).map(Some).collect();
+    let mut keyed = HashMap::new();
+    let mut unkeyed = VecDeque::new();
+    for (index, child) in old.iter().flatten().enumerate() {
+        if is_keyed_id(child.id()) {
+            keyed.insert(child.id(), index);
+        } else {
+            unkeyed.push_back(index);
+        }
+    }
+
+    // Old position of the last child kept in place; a child found before it
+    // moved, everything else only shifted around insertions and removals
+    let mut last_kept = 0;
+    for (index, new_child) in new_children.into_iter().enumerate() {
+        let matched = if is_keyed_id(new_child.id()) {
+            keyed.remove(&new_child.id())
+        } else {
+            unkeyed.pop_front()
+        };
+        match matched.and_then(|from| Some((from, old[from].take()?))) {
+            Some((from, mut child)) => {
+                if from < last_kept {
+                    out.mutations.push(Mutation::Moved {
+                        parent,
+                        id: child.id(),
+                        index,
+                    });
+                } else {
+                    last_kept = from;
+                }
+                reconcile_node(&mut child, new_child, out);
+                children.push(child);
+            }
+            None => {
+                out.mutations.push(Mutation::Inserted {
+                    parent,
+                    id: new_child.id(),
+                    index,
+                });
+                children.push(new_child);
+            }
+        }
+    }
+
+    for child in old.into_iter().flatten() {
+        out.mutations.push(Mutation::Removed {
+            parent,
+            id: child.id(),
+        });
+        out.removed.push(child);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::builder::BuilderExt;
+    use crate::container::Container;
+    use crate::layout::{Column, Row};
+    use crate::registry::BoxedWidget;
+    use crate::test_util::WidgetTester;
+    use crate::text::Text;
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use strato_core::event::MouseButton;
+    use strato_core::layout::Constraints;
+
+    fn texts(widget: &dyn Widget) -> Vec<String> {
+        let mut found = Vec::new();
+        if let Some(text) = widget.as_any().downcast_ref::<Text>() {
+            found.push(text.content());
+        }
+        for child in widget.children() {
+            found.extend(texts(child));
+        }
+        found
+    }
+
+    fn keyed_list(labels: &[&str]) -> Box<dyn Widget> {
+        Box::new(
+            Column::new().children(
+                labels
+                    .iter()
+                    .map(|label| {
+                        Box::new(Row::new().key(label).child(Box::new(Text::new(*label))))
+                            as Box<dyn Widget>
+                    })
+                    .collect(),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_same_type_takes_new_props_and_keeps_its_id() {
+        let mut retained: Box<dyn Widget> = Box::new(Text::new("before"));
+        let id = retained.id();
+
+        let reconciled = reconcile(&mut retained, Box::new(Text::new("after")));
+        assert_eq!(reconciled.mutations, [Mutation::Props(id)]);
+        assert!(reconciled.removed.is_empty());
+        assert_eq!(retained.id(), id);
+        assert_eq!(texts(retained.as_ref()), ["after"]);
+
+        // Nothing differs the second time
+        assert!(reconcile(&mut retained, Box::new(Text::new("after"))).is_empty());
+    }
+
+    #[test]
+    fn test_other_type_replaces_the_subtree() {
+        let mut retained: Box<dyn Widget> = Box::new(Text::new("label"));
+        let old = retained.id();
+        let new: Box<dyn Widget> = Box::new(Row::new().child(Box::new(Text::new("row"))));
+        let new_id = new.id();
+
+        let reconciled = reconcile(&mut retained, new);
+        assert_eq!(
+            reconciled.mutations,
+            [Mutation::Replaced { old, new: new_id }]
+        );
+        assert_eq!(reconciled.removed[0].id(), old);
+        assert_eq!(texts(retained.as_ref()), ["row"]);
+    }
+
+    #[test]
+    fn test_keyed_children_are_matched_wherever_they_moved() {
+        let mut retained = keyed_list(&["a", "b", "c"]);
+        // Unkeyed descendants keep the retained instances' generated ids
+        let inner: Vec<WidgetId> = retained
+            .children()
+            .iter()
+            .map(|row| row.children()[0].id())
+            .collect();
+
+        let reconciled = reconcile(&mut retained, keyed_list(&["c", "a", "d"]));
+        let parent = retained.id();
+        let (a, b, c, d) = ("a", "b", "c", "d");
+        let key = crate::widget::keyed_id;
+        assert_eq!(
+            reconciled.mutations,
+            [
+                Mutation::Moved {
+                    parent,
+                    id: key(a),
+                    index: 1
+                },
+                Mutation::Inserted {
+                    parent,
+                    id: key(d),
+                    index: 2
+                },
+                Mutation::Removed { parent, id: key(b) },
+            ]
+        );
+        assert_eq!(texts(retained.as_ref()), [c, a, d]);
+        let kept: Vec<WidgetId> = retained.children()[..2]
+            .iter()
+            .map(|row| row.children()[0].id())
+            .collect();
+        assert_eq!(kept, [inner[2], inner[0]]);
+        assert_eq!(reconciled.removed.len(), 1);
+        assert_eq!(texts(reconciled.removed[0].as_ref()), [b]);
+    }
+
+    #[test]
+    fn test_unkeyed_children_are_matched_in_order() {
+        let column = |labels: &[&str]| -> Box<dyn Widget> {
+            Box::new(
+                Column::new().children(
+                    labels
+                        .iter()
+                        .map(|label| Box::new(Text::new(*label)) as Box<dyn Widget>)
+                        .collect(),
+                ),
+            )
+        };
+        let mut retained = column(&["one", "two"]);
+        let first = retained.children()[0].id();
+
+        let reconciled = reconcile(&mut retained, column(&["one", "three", "four"]));
+        let parent = retained.id();
+        let second = retained.children()[1].id();
+        let inserted = retained.children()[2].id();
+        assert_eq!(
+            reconciled.mutations,
+            [
+                Mutation::Props(second),
+                Mutation::Inserted {
+                    parent,
+                    id: inserted,
+                    index: 2
+                },
+            ]
+        );
+        assert_eq!(retained.children()[0].id(), first);
+        assert_eq!(texts(retained.as_ref()), ["one", "three", "four"]);
+    }
+
+    #[test]
+    fn test_optional_child_is_inserted_and_removed() {
+        let mut retained: Box<dyn Widget> = Box::new(Container::new().padding(4.0));
+        let parent = retained.id();
+        retained.layout(Constraints::loose(100.0, 100.0));
+        let new: Box<dyn Widget> = Box::new(Container::new().padding(8.0).child(Text::new("body")));
+
+        let reconciled = reconcile(&mut retained, new);
+        let body = retained.children()[0].id();
+        assert_eq!(
+            reconciled.mutations,
+            [
+                Mutation::Props(parent),
+                Mutation::Inserted {
+                    parent,
+                    id: body,
+                    index: 0
+                },
+            ]
+        );
+        assert!(retained.layout_cache().unwrap().is_dirty());
+
+        let reconciled = reconcile(&mut retained, Box::new(Container::new().padding(8.0)));
+        assert_eq!(
+            reconciled.mutations,
+            [Mutation::Removed { parent, id: body }]
+        );
+        assert!(retained.children().is_empty());
+    }
+
+    #[test]
+    fn test_kept_container_takes_new_gesture_handlers() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let holdable = |name: &'static str| -> Box<dyn Widget> {
+            let log = log.clone();
+            Box::new(
+                Container::new()
+                    .on_long_press(Duration::from_millis(500), move || log.lock().push(name)),
+            )
+        };
+        let mut tester = WidgetTester::new(BoxedWidget(holdable("first")), 100.0, 100.0);
+
+        let reconciled = reconcile(&mut tester.root_mut().0, holdable("second"));
+        assert!(reconciled.removed.is_empty());
+        tester.pump();
+        tester.press(50.0, 50.0, MouseButton::Left);
+        tester.advance_by(0.1, 6);
+        assert_eq!(*log.lock(), ["second"]);
+    }
+}
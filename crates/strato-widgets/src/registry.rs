@@ -1,12 +1,33 @@
+//! Runtime construction of widgets from [`UiNode`] trees
+//!
+//! The registry maps widget names to builders, so trees produced by the
+//! `view!` macro, deserialized from JSON or assembled by tools can be turned
+//! into live widgets with [`IntoWidget::into_widget`].
+//!
+//! Each builder receives the node's props in order and its children. Props the
+//! builder doesn't know, or that have an unexpected type, are ignored. Text
+//! nodes become [`Text`] widgets and fragments a [`Column`]. Built-in widgets
+//! map children as follows:
+//!
+//! - `Column`, `Row`: every child, in order
+//! - `Container`: the first child
+//! - `Text`, `Button`: text children are used as the label when no `text`
+//!   prop is given; other children are ignored
+//!
+//! Unknown widget names build a placeholder [`Text`] naming the widget.
+
 use crate::image::{Image, ImageFit, ImageSource};
 use crate::prelude::*;
+use crate::reconcile::ChildSlots;
 use crate::widget::{Widget, WidgetContext, WidgetId};
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, OnceLock};
 use strato_core::event::{Event, EventResult};
-use strato_core::layout::{Constraints, Layout, Size};
+use strato_core::layout::{Constraints, Layout, LayoutCache, Size};
+use strato_core::taffy_layout::TaffyWidget;
 use strato_core::types::Point;
 use strato_core::ui_node::{PropValue, UiNode, WidgetNode};
+use strato_core::window::CursorIcon;
 use strato_renderer::batch::RenderBatch;
 
 /// A builder function that creates a widget from properties.
@@ -23,15 +44,29 @@ pub struct WidgetRegistry {
 #[derive(Debug)]
 pub struct BoxedWidget(pub Box<dyn Widget>);
 
+// Forwards every method, defaults included, so the wrapped widget behaves
+// as if it were in the tree itself
 impl Widget for BoxedWidget {
     fn id(&self) -> WidgetId {
         self.0.id()
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.0.set_id(id)
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         self.0.layout(constraints)
     }
 
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.0.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        self.0.layout_cache()
+    }
+
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
         self.0.render(batch, layout)
     }
@@ -44,13 +79,62 @@ impl Widget for BoxedWidget {
         self.0.update(ctx)
     }
 
+    fn on_mount(&mut self, ctx: &WidgetContext) {
+        self.0.on_mount(ctx)
+    }
+
+    fn on_unmount(&mut self, ctx: &WidgetContext) {
+        self.0.on_unmount(ctx)
+    }
+
+    fn detached_children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        self.0.detached_children_mut()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.0.is_enabled()
+    }
+
+    fn is_focused(&self) -> bool {
+        self.0.is_focused()
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.0.is_focusable()
+    }
+
+    fn tab_index(&self) -> Option<i32> {
+        self.0.tab_index()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.0.set_focused(focused)
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.0.overlay_order()
+    }
+
     fn children(&self) -> Vec<&(dyn Widget + '_)> {
         self.0.children()
     }
+
     fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
         self.0.children_mut()
     }
 
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        self.0.apply_props(new)
+    }
+
+    fn child_slots(&mut self) -> Option<ChildSlots<'_>> {
+        self.0.child_slots()
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.0.cursor_icon(point)
+    }
+
     fn hit_test(&self, point: Point, layout: Layout) -> bool {
         self.0.hit_test(point, layout)
     }
@@ -58,6 +142,7 @@ impl Widget for BoxedWidget {
     fn as_any(&self) -> &dyn std::any::Any {
         self.0.as_any()
     }
+
     fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
         self.0.as_any_mut()
     }
@@ -65,6 +150,24 @@ impl Widget for BoxedWidget {
     fn clone_widget(&self) -> Box<dyn Widget> {
         self.0.clone_widget()
     }
+
+    fn type_name(&self) -> &'static str {
+        self.0.type_name()
+    }
+
+    fn as_taffy(&self) -> Option<&dyn TaffyWidget> {
+        self.0.as_taffy()
+    }
+
+    fn render_taffy(
+        &self,
+        batch: &mut RenderBatch,
+        tree: &strato_core::taffy::tree::TaffyTree<()>,
+        node: strato_core::taffy::prelude::NodeId,
+        parent_offset: Point,
+    ) {
+        self.0.render_taffy(batch, tree, node, parent_offset)
+    }
 }
 
 impl WidgetRegistry {
@@ -76,6 +179,12 @@ impl WidgetRegistry {
         registry
     }
 
+    /// Access the shared registry with the built-in widgets
+    pub fn global() -> &'static WidgetRegistry {
+        static GLOBAL: OnceLock<WidgetRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(WidgetRegistry::new)
+    }
+
     /// Register a widget builder.
     pub fn register<F>(&mut self, name: &str, builder: F)
     where
@@ -174,8 +283,8 @@ impl WidgetRegistry {
         });
 
         // Text
-        self.register("Text", |props, _children, _registry| {
-            let mut text = String::new();
+        self.register("Text", |props, children, _registry| {
+            let mut text = text_content(&children);
 
             // First pass: find text semantic prop
             for (name, value) in &props {
@@ -201,8 +310,8 @@ impl WidgetRegistry {
         });
 
         // Button
-        self.register("Button", |props, _children, _registry| {
-            let mut label = String::new();
+        self.register("Button", |props, children, _registry| {
+            let mut label = text_content(&children);
             for (name, value) in &props {
                 if name == "text" {
                     if let PropValue::String(s) = value {
@@ -211,18 +320,13 @@ impl WidgetRegistry {
                 }
             }
 
-            let widget = Button::new(label);
-
-            // Button usually doesn't take children in this framework, just text in constructor?
-            // But macro might support `Button { child: Icon }`?
-            // Existing Button::new implementation takes string.
-            // If children present, ignored? or fallback?
+            let mut widget = Button::new(label);
 
+            // Callbacks can't be expressed as props, attach them after building
             for (name, value) in props {
                 match (name.as_str(), value) {
-                    // disabled?
-
-                    // events?
+                    ("enabled", PropValue::Bool(b)) => widget = widget.enabled(b),
+                    ("disabled", PropValue::Bool(b)) => widget = widget.enabled(!b),
                     _ => {}
                 }
             }
@@ -298,7 +402,110 @@ impl WidgetRegistry {
     }
 }
 
+impl Default for WidgetRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // Global registry instance (lazy static approach usually, but here we instantiate it)
 pub fn create_default_registry() -> WidgetRegistry {
     WidgetRegistry::new()
 }
+
+/// Concatenated text of the text children, used as a label fallback
+fn text_content(children: &[UiNode]) -> String {
+    children
+        .iter()
+        .filter_map(|child| match child {
+            UiNode::Text(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Build widgets from [`UiNode`] trees
+pub trait IntoWidget {
+    /// Build using the shared [`WidgetRegistry::global`] registry
+    fn into_widget(self) -> BoxedWidget;
+
+    /// Build using a specific registry, e.g. one with custom widgets
+    fn into_widget_with(self, registry: &WidgetRegistry) -> BoxedWidget;
+}
+
+impl IntoWidget for UiNode {
+    fn into_widget(self) -> BoxedWidget {
+        self.into_widget_with(WidgetRegistry::global())
+    }
+
+    fn into_widget_with(self, registry: &WidgetRegistry) -> BoxedWidget {
+        registry.build(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_into_widget_maps_props_and_children() {
+        let node = UiNode::widget("Column")
+            .prop("spacing", 4.0)
+            .child(UiNode::widget("Text").child(UiNode::text("Hello")))
+            .child(
+                UiNode::widget("Button")
+                    .prop("text", "OK")
+                    .prop("disabled", true),
+            );
+
+        let widget = node.into_widget();
+        let column = widget.as_any().downcast_ref::<Column>().unwrap();
+        let children = Widget::children(column);
+        assert_eq!(children.len(), 2);
+
+        let text = children[0].as_any().downcast_ref::<Text>().unwrap();
+        assert_eq!(text.content(), "Hello");
+        let button = children[1].as_any().downcast_ref::<Button>().unwrap();
+        assert!(!button.is_enabled());
+    }
+
+    #[test]
+    fn test_custom_widgets_and_unknown_names() {
+        let mut registry = WidgetRegistry::new();
+        registry.register("Greeting", |props, _children, _registry| {
+            let name = match props.iter().find(|(key, _)| key == "name") {
+                Some((_, PropValue::String(name))) => name.clone(),
+                _ => "world".to_string(),
+            };
+            Box::new(Text::new(format!("Hello, {}!", name)))
+        });
+
+        let greeting = UiNode::widget("Greeting")
+            .prop("name", "Strato")
+            .into_widget_with(&registry);
+        let text = greeting.as_any().downcast_ref::<Text>().unwrap();
+        assert_eq!(text.content(), "Hello, Strato!");
+
+        let unknown = UiNode::widget("Greeting").into_widget();
+        let text = unknown.as_any().downcast_ref::<Text>().unwrap();
+        assert_eq!(text.content(), "Unknown widget: Greeting");
+    }
+
+    #[test]
+    fn test_tab_focuses_built_button() {
+        use crate::focus::handle_tab;
+        use strato_core::event::{KeyCode, KeyboardEvent};
+
+        let mut button = UiNode::widget("Button").prop("text", "OK").into_widget();
+        assert!(button.is_focusable());
+
+        let tab = Event::KeyDown(KeyboardEvent {
+            key_code: KeyCode::Tab,
+            modifiers: Default::default(),
+            is_repeat: false,
+            text: None,
+        });
+        assert_eq!(handle_tab(&mut button, &tab), EventResult::Handled);
+        assert!(button.is_focused());
+    }
+}
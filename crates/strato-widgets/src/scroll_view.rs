@@ -1,10 +1,61 @@
 use crate::prelude::*;
-use strato_core::event::{Event, EventResult, MouseEvent};
+use glam::Vec2;
+use strato_core::event::{Event, EventResult, GestureEvent, MouseEvent};
 use strato_core::layout::{Constraints, Layout, Size};
 use strato_core::types::{Color, Point, Rect, Transform};
 use strato_renderer::batch::RenderBatch;
 
-use crate::widget::BaseWidget;
+use crate::widget::{clone_id, dispatch_event, BaseWidget, WidgetContext};
+
+// Fling speed below which scrolling stops, in pixels per second
+const MIN_VELOCITY: f32 = 10.0;
+// Spring pulling an overscrolled fling back to the edge, critically damped
+const BOUNCE_STIFFNESS: f32 = 240.0;
+const BOUNCE_DAMPING: f32 = 31.0;
+// How far a fling may overshoot the content edges
+const MAX_OVERSCROLL: f32 = 48.0;
+// Longest step simulated at once, keeps the spring stable through hitches
+const MAX_STEP: f32 = 0.05;
+
+/// Advance one axis of a fling by `dt` seconds
+///
+/// Returns the new offset and velocity; the offset stays within
+/// `0.0..=max` unless `bounce` lets it overshoot and spring back.
+fn coast(offset: f32, velocity: f32, max: f32, dt: f32, friction: f32, bounce: bool) -> (f32, f32) {
+    let dt = dt.min(MAX_STEP);
+    let mut offset = offset + velocity * dt;
+    let mut velocity = velocity * (-friction * dt).exp();
+
+    let overscroll = offset - offset.clamp(0.0, max);
+    if overscroll != 0.0 {
+        if bounce {
+            velocity -= (BOUNCE_STIFFNESS * overscroll + BOUNCE_DAMPING * velocity) * dt;
+            offset = offset.clamp(-MAX_OVERSCROLL, max + MAX_OVERSCROLL);
+        } else {
+            offset -= overscroll;
+            velocity = 0.0;
+        }
+    }
+
+    let overscroll = offset - offset.clamp(0.0, max);
+    if velocity.abs() < MIN_VELOCITY && overscroll.abs() < 0.5 {
+        (offset.clamp(0.0, max), 0.0)
+    } else {
+        (offset, velocity)
+    }
+}
+
+/// Take the unused wheel delta of the outermost scroll views at or below
+/// `widget`, see [`ScrollView::take_unused_scroll`]
+fn take_nested_unused_scroll(widget: &mut dyn Widget) -> Option<Vec2> {
+    if let Some(view) = widget.as_any_mut().downcast_mut::<ScrollView>() {
+        return view.take_unused_scroll();
+    }
+    widget
+        .children_mut()
+        .into_iter()
+        .find_map(take_nested_unused_scroll)
+}
 
 #[derive(Debug)]
 pub struct ScrollView {
@@ -20,6 +71,18 @@ pub struct ScrollView {
     is_dragging: bool,
     drag_start_y: f32,
     offset_start_y: f32,
+    sensitivity: f32,
+
+    // Inertial scrolling
+    inertia: bool,
+    friction: f32,
+    bounce: bool,
+    velocity: Vec2,
+    // Offset change from trackpad input since the last update
+    pending: Vec2,
+    // Wheel delta the last wheel event ran past the edges with, for the
+    // enclosing scroll view
+    unused: Option<Vec2>,
 }
 
 impl ScrollView {
@@ -35,9 +98,119 @@ impl ScrollView {
             is_dragging: false,
             drag_start_y: 0.0,
             offset_start_y: 0.0,
+            sensitivity: 1.0,
+            inertia: false,
+            friction: 3.0,
+            bounce: true,
+            velocity: Vec2::ZERO,
+            pending: Vec2::ZERO,
+            unused: None,
         }
     }
 
+    /// Scale wheel and trackpad deltas by `sensitivity`
+    ///
+    /// Values above 1.0 scroll further per notch, a negative value inverts
+    /// the direction. Deltas handed on by a nested scroll view that reached
+    /// its edge are used as they are. Defaults to 1.0.
+    pub fn sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity = sensitivity;
+        self
+    }
+
+    /// Keep scrolling after a trackpad flick, slowing down until it stops
+    ///
+    /// Precise (trackpad) scrolling sets the speed; wheel notches, a click
+    /// or dragging the scrollbar stop a fling. Off by default.
+    pub fn inertia(mut self, enabled: bool) -> Self {
+        self.inertia = enabled;
+        self
+    }
+
+    /// How quickly a fling slows down, per second
+    ///
+    /// The speed decays by `e^(-friction)` every second, so higher values
+    /// stop sooner. Defaults to 3.0.
+    pub fn friction(mut self, friction: f32) -> Self {
+        self.friction = friction.max(0.0);
+        self
+    }
+
+    /// Whether a fling overshoots the content edges and springs back
+    ///
+    /// Without bounce a fling stops dead at the edge. Defaults to `true`.
+    pub fn bounce(mut self, enabled: bool) -> Self {
+        self.bounce = enabled;
+        self
+    }
+
+    /// Whether a fling is still moving the content
+    pub fn is_flinging(&self) -> bool {
+        self.velocity != Vec2::ZERO
+    }
+
+    /// Take the part of the last wheel delta this view could not use
+    ///
+    /// Set when a wheel event scrolled the view to an edge with delta to
+    /// spare; an enclosing scroll view takes it after passing the event on
+    /// and scrolls by it instead.
+    pub fn take_unused_scroll(&mut self) -> Option<Vec2> {
+        self.unused.take()
+    }
+
+    fn max_offset(&self) -> Vec2 {
+        Vec2::new(
+            (self.content_size.width - self.viewport_size.width).max(0.0),
+            (self.content_size.height - self.viewport_size.height).max(0.0),
+        )
+    }
+
+    /// Advance inertial scrolling by one frame
+    fn advance_inertia(&mut self, dt: f32) {
+        if dt <= 0.0 {
+            return;
+        }
+        if self.pending != Vec2::ZERO {
+            // Still following the fingers, track their speed
+            self.velocity = self.velocity.lerp(self.pending / dt, 0.5);
+            self.pending = Vec2::ZERO;
+            return;
+        }
+        if self.velocity == Vec2::ZERO {
+            return;
+        }
+
+        let max = self.max_offset();
+        let (x, vx) = coast(
+            self.offset.x,
+            self.velocity.x,
+            max.x,
+            dt,
+            self.friction,
+            self.bounce,
+        );
+        let (y, vy) = coast(
+            self.offset.y,
+            self.velocity.y,
+            max.y,
+            dt,
+            self.friction,
+            self.bounce,
+        );
+        self.offset = Point::new(x, y);
+        self.velocity = Vec2::new(vx, vy);
+    }
+
+    fn stop_fling(&mut self) {
+        self.velocity = Vec2::ZERO;
+        self.pending = Vec2::ZERO;
+        let max = self.max_offset();
+        self.offset = Point::new(
+            self.offset.x.clamp(0.0, max.x),
+            self.offset.y.clamp(0.0, max.y),
+        );
+    }
+
     fn update_scrollbar_rect(
         &self,
         content_height: f32,
@@ -77,6 +250,10 @@ impl Widget for ScrollView {
         self.base.id()
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.base.set_id(id);
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         // ScrollView takes all available space or respects max size
         let self_size = Size::new(constraints.max_width, constraints.max_height);
@@ -104,11 +281,11 @@ impl Widget for ScrollView {
         );
         self.bounds.set(bounds);
 
-        // Update scrollbar rect
+        // Update scrollbar rect, pinned to the track while overscrolled
         self.update_scrollbar_rect(
             self.content_size.height,
             layout.size.height,
-            self.offset.y,
+            self.offset.y.clamp(0.0, self.max_offset().y),
             bounds,
         );
 
@@ -143,25 +320,41 @@ impl Widget for ScrollView {
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         match event {
-            Event::MouseWheel { delta, .. } => {
-                let delta_x = delta.x;
-                let delta_y = delta.y;
-
-                let viewport_w = self.viewport_size.width;
-                let viewport_h = self.viewport_size.height;
+            Event::MouseWheel { delta, precise, .. } => {
+                // Content goes first: an open dropdown or a nested scroll
+                // view, which hands back the delta it could not use
+                self.unused = None;
+                let delta = if dispatch_event(self.child.as_mut(), event) == EventResult::Handled {
+                    match take_nested_unused_scroll(self.child.as_mut()) {
+                        Some(unused) => unused,
+                        None => return EventResult::Handled,
+                    }
+                } else {
+                    *delta * self.sensitivity
+                };
 
-                let max_x = (self.content_size.width - viewport_w).max(0.0);
-                let max_y = (self.content_size.height - viewport_h).max(0.0);
+                let max = self.max_offset();
+                let before = self.offset;
+                let target = Vec2::new(before.x - delta.x, before.y - delta.y);
+                self.offset = Point::new(target.x.clamp(0.0, max.x), target.y.clamp(0.0, max.y));
 
-                self.offset.x = (self.offset.x - delta_x).clamp(0.0, max_x);
-                self.offset.y = (self.offset.y - delta_y).clamp(0.0, max_y);
+                // Trackpad movement feeds the fling, wheel notches end it
+                if self.inertia && *precise {
+                    self.pending += Vec2::new(self.offset.x - before.x, self.offset.y - before.y);
+                } else {
+                    self.stop_fling();
+                }
 
-                // Update scrollbar rect immediately for responsiveness if we were running a single loop
-                // but render will handle it.
+                // Whatever ran past the edges scrolls the enclosing view
+                let unused = Vec2::new(self.offset.x - target.x, self.offset.y - target.y);
+                if unused != Vec2::ZERO {
+                    self.unused = Some(unused);
+                }
 
                 EventResult::Handled
             }
             Event::MouseDown(mouse) => {
+                self.stop_fling();
                 let point = Point::new(mouse.position.x, mouse.position.y);
                 let scrollbar = self.scrollbar_rect.get();
 
@@ -172,7 +365,7 @@ impl Widget for ScrollView {
                     return EventResult::Handled;
                 }
 
-                self.child.handle_event(event)
+                dispatch_event(self.child.as_mut(), event)
             }
             Event::MouseMove(mouse) => {
                 if self.is_dragging {
@@ -201,16 +394,33 @@ impl Widget for ScrollView {
 
                     return EventResult::Handled;
                 }
-                self.child.handle_event(event)
+                dispatch_event(self.child.as_mut(), event)
             }
             Event::MouseUp(_) => {
                 if self.is_dragging {
                     self.is_dragging = false;
                     return EventResult::Handled;
                 }
-                self.child.handle_event(event)
+                dispatch_event(self.child.as_mut(), event)
+            }
+            // Two-finger touch pans scroll like a trackpad, fingers and
+            // content moving together
+            Event::Gesture(GestureEvent::Pan { center, delta })
+                if self.bounds.get().contains(Point::new(center.x, center.y)) =>
+            {
+                self.handle_event(&Event::MouseWheel {
+                    delta: *delta,
+                    modifiers: Default::default(),
+                    precise: true,
+                })
             }
-            _ => self.child.handle_event(event),
+            _ => dispatch_event(self.child.as_mut(), event),
+        }
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        if self.inertia {
+            self.advance_inertia(ctx.delta_time);
         }
     }
 
@@ -223,8 +433,10 @@ impl Widget for ScrollView {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
+        let mut base = self.base.clone();
+        base.set_id(clone_id(self.base.id()));
         Box::new(Self {
-            base: self.base.clone(),
+            base,
             child: self.child.clone_widget(),
             offset: self.offset,
             content_size: self.content_size,
@@ -234,6 +446,13 @@ impl Widget for ScrollView {
             is_dragging: false,
             drag_start_y: 0.0,
             offset_start_y: 0.0,
+            sensitivity: self.sensitivity,
+            inertia: self.inertia,
+            friction: self.friction,
+            bounce: self.bounce,
+            velocity: Vec2::ZERO,
+            pending: Vec2::ZERO,
+            unused: None,
         })
     }
 
@@ -245,3 +464,178 @@ impl Widget for ScrollView {
         vec![self.child.as_mut()]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Container;
+    use crate::test_util::WidgetTester;
+
+    const FRAME: f32 = 1.0 / 60.0;
+
+    /// A 100x100 viewport over 1000 pixels of content
+    fn feed(inertia: bool) -> WidgetTester<ScrollView> {
+        let content = Container::new().size(100.0, 1000.0);
+        WidgetTester::new(ScrollView::new(content).inertia(inertia), 100.0, 100.0)
+    }
+
+    /// Flick upwards: a few frames of fast trackpad movement, then release
+    fn flick(tester: &mut WidgetTester<ScrollView>, step: f32) {
+        for _ in 0..4 {
+            tester.scroll_precise(0.0, -step);
+            tester.advance(FRAME);
+        }
+    }
+
+    #[test]
+    fn test_flick_keeps_scrolling_and_slows_down() {
+        let mut tester = feed(true);
+        flick(&mut tester, 20.0);
+        let released = tester.root().offset.y;
+        assert_eq!(released, 80.0);
+
+        tester.advance(FRAME);
+        let first = tester.root().offset.y - released;
+        tester.advance(FRAME);
+        let second = tester.root().offset.y - released - first;
+        assert!(first > 10.0 && second < first, "{first} then {second}");
+
+        for _ in 0..300 {
+            tester.advance(FRAME);
+        }
+        assert!(!tester.root().is_flinging());
+        assert!(tester.root().offset.y > 200.0);
+    }
+
+    #[test]
+    fn test_two_finger_pan_scrolls_with_the_fingers() {
+        let mut tester = feed(false);
+        let pan = |x: f32, dy: f32| {
+            Event::Gesture(GestureEvent::Pan {
+                center: Vec2::new(x, 50.0),
+                delta: Vec2::new(0.0, dy),
+            })
+        };
+        tester.dispatch(pan(50.0, -30.0));
+        assert_eq!(tester.root().offset.y, 30.0);
+        tester.dispatch(pan(50.0, 10.0));
+        assert_eq!(tester.root().offset.y, 20.0);
+
+        // Pans elsewhere are left alone
+        assert_eq!(tester.dispatch(pan(500.0, -30.0)), EventResult::Ignored);
+        assert_eq!(tester.root().offset.y, 20.0);
+    }
+
+    #[test]
+    fn test_wheel_notches_and_disabled_inertia_do_not_fling() {
+        let mut tester = feed(true);
+        tester.scroll(0.0, -40.0);
+        tester.advance(FRAME);
+        tester.advance(FRAME);
+        assert_eq!(tester.root().offset.y, 40.0);
+
+        let mut tester = feed(false);
+        flick(&mut tester, 20.0);
+        tester.advance(FRAME);
+        assert_eq!(tester.root().offset.y, 80.0);
+        assert!(!tester.root().is_flinging());
+    }
+
+    #[test]
+    fn test_sensitivity_scales_and_inverts_the_offset() {
+        let content = || Container::new().size(100.0, 1000.0);
+        let mut tester =
+            WidgetTester::new(ScrollView::new(content()).sensitivity(2.5), 100.0, 100.0);
+        tester.scroll(0.0, -40.0);
+        assert_eq!(tester.root().offset.y, 100.0);
+
+        let mut tester =
+            WidgetTester::new(ScrollView::new(content()).sensitivity(-1.0), 100.0, 100.0);
+        tester.root_mut().offset.y = 500.0;
+        tester.scroll(0.0, -40.0);
+        assert_eq!(tester.root().offset.y, 460.0);
+        tester.scroll(0.0, 40.0);
+        assert_eq!(tester.root().offset.y, 500.0);
+    }
+
+    #[test]
+    fn test_fling_bounces_back_to_the_edge() {
+        let mut tester = feed(true);
+        tester.root_mut().offset.y = 700.0;
+        flick(&mut tester, 30.0);
+
+        let mut furthest: f32 = 0.0;
+        for _ in 0..300 {
+            tester.advance(FRAME);
+            furthest = furthest.max(tester.root().offset.y);
+        }
+        assert!(furthest > 900.0 && furthest <= 900.0 + MAX_OVERSCROLL);
+        assert_eq!(tester.root().offset.y, 900.0);
+        assert!(!tester.root().is_flinging());
+
+        // Without bounce the fling stops at the edge
+        let mut tester = feed(true);
+        tester.root_mut().bounce = false;
+        tester.root_mut().offset.y = 700.0;
+        flick(&mut tester, 30.0);
+        for _ in 0..30 {
+            tester.advance(FRAME);
+            assert!(tester.root().offset.y <= 900.0);
+        }
+        assert_eq!(tester.root().offset.y, 900.0);
+        assert!(!tester.root().is_flinging());
+    }
+
+    /// A 100x100 viewport over a header and a view showing 300 of its 1000
+    /// pixels of content
+    fn nested() -> WidgetTester<ScrollView> {
+        let inner = ScrollView::new(Container::new().size(100.0, 1000.0));
+        let content = crate::layout::Column::new().children(vec![
+            Box::new(Container::new().size(100.0, 100.0)),
+            Box::new(
+                Container::new()
+                    .constraints(Constraints::tight(100.0, 300.0))
+                    .child(inner),
+            ),
+        ]);
+        WidgetTester::new(ScrollView::new(content), 100.0, 100.0)
+    }
+
+    fn inner(tester: &mut WidgetTester<ScrollView>) -> &mut ScrollView {
+        let column = tester.root_mut().child.as_mut();
+        let container = column.children_mut().pop().unwrap();
+        let view = container.children_mut().pop().unwrap();
+        view.as_any_mut().downcast_mut::<ScrollView>().unwrap()
+    }
+
+    #[test]
+    fn test_nested_view_passes_leftover_delta_to_outer() {
+        let mut tester = nested();
+        inner(&mut tester).offset.y = 690.0;
+
+        // The inner view takes the 10 pixels it has left, the outer the rest
+        tester.scroll(0.0, -50.0);
+        assert_eq!(inner(&mut tester).offset.y, 700.0);
+        assert_eq!(tester.root().offset.y, 40.0);
+
+        // Scrolled to its bottom, the inner view passes everything on
+        tester.scroll(0.0, -30.0);
+        assert_eq!(inner(&mut tester).offset.y, 700.0);
+        assert_eq!(tester.root().offset.y, 70.0);
+
+        // Scrolling back goes to the inner view first
+        tester.scroll(0.0, 50.0);
+        assert_eq!(inner(&mut tester).offset.y, 650.0);
+        assert_eq!(tester.root().offset.y, 70.0);
+        assert_eq!(tester.root_mut().take_unused_scroll(), None);
+
+        // What runs past the top of both is left for a view further out
+        tester.scroll(0.0, 1000.0);
+        assert_eq!(inner(&mut tester).offset.y, 0.0);
+        assert_eq!(tester.root().offset.y, 0.0);
+        assert_eq!(
+            tester.root_mut().take_unused_scroll(),
+            Some(Vec2::new(0.0, 280.0))
+        );
+    }
+}
@@ -0,0 +1,243 @@
+//! Keyboard shortcuts and the application-wide shortcut registry
+
+use std::fmt;
+use std::sync::{Arc, OnceLock};
+
+use parking_lot::RwLock;
+use strato_core::event::{Event, EventResult, KeyCode, KeyboardEvent, Modifiers};
+
+/// Callback fired when a shortcut is triggered
+pub type ShortcutHandler = Arc<dyn Fn() + Send + Sync>;
+
+/// A key combined with a set of modifiers, e.g. Ctrl+S
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    pub key: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+impl Shortcut {
+    /// Create a shortcut from a key and modifiers
+    pub fn new(key: KeyCode, modifiers: Modifiers) -> Self {
+        Self { key, modifiers }
+    }
+
+    /// Shortcut without modifiers
+    pub fn key(key: KeyCode) -> Self {
+        Self::new(key, Modifiers::default())
+    }
+
+    /// Ctrl + key
+    pub fn ctrl(key: KeyCode) -> Self {
+        Self::new(
+            key,
+            Modifiers {
+                control: true,
+                ..Modifiers::default()
+            },
+        )
+    }
+
+    /// Ctrl + Shift + key
+    pub fn ctrl_shift(key: KeyCode) -> Self {
+        Self::new(
+            key,
+            Modifiers {
+                control: true,
+                shift: true,
+                ..Modifiers::default()
+            },
+        )
+    }
+
+    /// Check if a keyboard event triggers this shortcut
+    pub fn matches(&self, event: &KeyboardEvent) -> bool {
+        event.key_code == self.key && event.modifiers == self.modifiers
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.modifiers.control {
+            write!(f, "Ctrl+")?;
+        }
+        if self.modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.modifiers.super_key {
+            write!(f, "Super+")?;
+        }
+        write!(f, "{:?}", self.key)
+    }
+}
+
+/// Maps shortcuts to handlers
+///
+/// The registry is a cloneable handle; all clones share the same bindings.
+/// The application dispatches every key press to [`ShortcutRegistry::global`]
+/// before the widget tree sees it.
+#[derive(Clone, Default)]
+pub struct ShortcutRegistry {
+    bindings: Arc<RwLock<Vec<(Shortcut, ShortcutHandler)>>>,
+}
+
+impl fmt::Debug for ShortcutRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shortcuts: Vec<Shortcut> = self.bindings.read().iter().map(|(s, _)| *s).collect();
+        f.debug_struct("ShortcutRegistry")
+            .field("shortcuts", &shortcuts)
+            .finish()
+    }
+}
+
+impl ShortcutRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Access the application-wide registry
+    pub fn global() -> ShortcutRegistry {
+        static GLOBAL: OnceLock<ShortcutRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(ShortcutRegistry::new).clone()
+    }
+
+    /// Bind a handler to a shortcut, replacing any previous binding
+    pub fn register<F>(&self, shortcut: Shortcut, handler: F)
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.register_shared(shortcut, Arc::new(handler));
+    }
+
+    /// Bind an already shared handler to a shortcut
+    pub fn register_shared(&self, shortcut: Shortcut, handler: ShortcutHandler) {
+        let mut bindings = self.bindings.write();
+        bindings.retain(|(existing, _)| *existing != shortcut);
+        bindings.push((shortcut, handler));
+    }
+
+    /// Remove the binding for a shortcut
+    pub fn unregister(&self, shortcut: Shortcut) {
+        self.bindings
+            .write()
+            .retain(|(existing, _)| *existing != shortcut);
+    }
+
+    /// Remove the binding for a shortcut only if it is still bound to `handler`
+    ///
+    /// Owners that share their handlers use this so they never drop a binding
+    /// someone else registered over theirs.
+    pub fn unregister_shared(&self, shortcut: Shortcut, handler: &ShortcutHandler) {
+        self.bindings
+            .write()
+            .retain(|(existing, bound)| *existing != shortcut || !Arc::ptr_eq(bound, handler));
+    }
+
+    /// Check if a shortcut is bound
+    pub fn contains(&self, shortcut: Shortcut) -> bool {
+        self.bindings.read().iter().any(|(s, _)| *s == shortcut)
+    }
+
+    /// Fire the handler bound to a key press, returning whether one matched
+    pub fn dispatch(&self, event: &KeyboardEvent) -> bool {
+        if event.is_repeat {
+            return false;
+        }
+        // Clone the handler out so it may register shortcuts itself.
+        let handler = self
+            .bindings
+            .read()
+            .iter()
+            .find(|(shortcut, _)| shortcut.matches(event))
+            .map(|(_, handler)| handler.clone());
+        match handler {
+            Some(handler) => {
+                handler();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Dispatch a key down event
+    pub fn handle_event(&self, event: &Event) -> EventResult {
+        match event {
+            Event::KeyDown(key) if self.dispatch(key) => EventResult::Handled,
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn key_down(shortcut: Shortcut) -> Event {
+        Event::KeyDown(KeyboardEvent {
+            key_code: shortcut.key,
+            modifiers: shortcut.modifiers,
+            is_repeat: false,
+            text: None,
+        })
+    }
+
+    #[test]
+    fn test_shortcut_label() {
+        assert_eq!(Shortcut::ctrl(KeyCode::S).to_string(), "Ctrl+S");
+        assert_eq!(Shortcut::ctrl_shift(KeyCode::Z).to_string(), "Ctrl+Shift+Z");
+    }
+
+    #[test]
+    fn test_dispatch_fires_matching_handler() {
+        let registry = ShortcutRegistry::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        let counter = count.clone();
+        registry.register(Shortcut::ctrl(KeyCode::S), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        assert_eq!(
+            registry.handle_event(&key_down(Shortcut::ctrl(KeyCode::S))),
+            EventResult::Handled
+        );
+        assert_eq!(
+            registry.handle_event(&key_down(Shortcut::key(KeyCode::S))),
+            EventResult::Ignored
+        );
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_register_replaces_binding() {
+        let registry = ShortcutRegistry::new();
+        let count = Arc::new(AtomicUsize::new(0));
+        registry.register(Shortcut::ctrl(KeyCode::O), || {});
+        let counter = count.clone();
+        registry.register(Shortcut::ctrl(KeyCode::O), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+
+        registry.handle_event(&key_down(Shortcut::ctrl(KeyCode::O)));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_unregister_shared_keeps_replaced_binding() {
+        let registry = ShortcutRegistry::new();
+        let first: ShortcutHandler = Arc::new(|| {});
+        let second: ShortcutHandler = Arc::new(|| {});
+        registry.register_shared(Shortcut::ctrl(KeyCode::O), first.clone());
+        registry.register_shared(Shortcut::ctrl(KeyCode::O), second.clone());
+
+        registry.unregister_shared(Shortcut::ctrl(KeyCode::O), &first);
+        assert!(registry.contains(Shortcut::ctrl(KeyCode::O)));
+
+        registry.unregister_shared(Shortcut::ctrl(KeyCode::O), &second);
+        assert!(!registry.contains(Shortcut::ctrl(KeyCode::O)));
+    }
+}
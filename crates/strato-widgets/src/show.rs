@@ -0,0 +1,350 @@
+//! Showing a widget while a condition holds
+//!
+//! [`Show`] builds its content when a `Signal<bool>` turns true and throws it
+//! away when it turns false, optionally showing a fallback in between. Each
+//! branch is built in a [`Scope`] of its own, so signals derived and effects
+//! started by the builder stop when the branch is hidden instead of lingering
+//! on the signals they follow. A hidden branch is unmounted in the next
+//! update pass, see [`Widget::on_unmount`]:
+//!
+//! ```
+//! use strato_core::state::Signal;
+//! use strato_widgets::show::Show;
+//! use strato_widgets::text::Text;
+//!
+//! let signed_in = Signal::new(false);
+//! let name = Signal::new(String::from("Ada"));
+//! let greeting = Show::new(signed_in.clone(), move || {
+//!     Text::new("").bind(name.map(|name| format!("Hello, {name}")))
+//! })
+//! .fallback(|| Text::new("Please sign in"));
+//! ```
+
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, Widget, WidgetContext, WidgetId,
+};
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    reactive::{current_scope, mark_dirty, Scope},
+    state::Signal,
+};
+use strato_renderer::batch::RenderBatch;
+
+type Build = Arc<dyn Fn() -> Box<dyn Widget> + Send + Sync>;
+
+/// The branch currently built, with the scope owning what it created
+struct Mounted {
+    shown: bool,
+    child: Option<Box<dyn Widget>>,
+    scope: Scope,
+}
+
+/// Shows its content while `condition` is true, and its fallback otherwise
+///
+/// Branches are built on the first layout after the condition changes, the
+/// previous one being dropped and its scope disposed. Scopes of branches are
+/// children of the scope the `Show` was created in, if any, so nested
+/// conditions are torn down with their parent.
+pub struct Show {
+    id: WidgetId,
+    condition: Signal<bool>,
+    content: Build,
+    fallback: Option<Build>,
+    parent_scope: Option<Scope>,
+    mounted: Option<Mounted>,
+    /// Branches hidden since the last update pass, kept until then so they
+    /// can be unmounted
+    retired: Vec<Box<dyn Widget>>,
+    layout_cache: LayoutCache,
+}
+
+impl std::fmt::Debug for Show {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Show")
+            .field("id", &self.id)
+            .field("condition", &self.condition.get())
+            .field("has_fallback", &self.fallback.is_some())
+            .field("child", &self.child())
+            .finish()
+    }
+}
+
+impl Show {
+    /// Show what `content` builds while `condition` is true
+    pub fn new<W, F>(condition: Signal<bool>, content: F) -> Self
+    where
+        W: Widget + 'static,
+        F: Fn() -> W + Send + Sync + 'static,
+    {
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&condition);
+        Self {
+            id: generate_id(),
+            condition,
+            content: Arc::new(move || Box::new(content())),
+            fallback: None,
+            parent_scope: current_scope(),
+            mounted: None,
+            retired: Vec::new(),
+            layout_cache,
+        }
+    }
+
+    /// Show what `fallback` builds while the condition is false
+    pub fn fallback<W, F>(mut self, fallback: F) -> Self
+    where
+        W: Widget + 'static,
+        F: Fn() -> W + Send + Sync + 'static,
+    {
+        self.fallback = Some(Arc::new(move || Box::new(fallback())));
+        self
+    }
+
+    /// The branch built by the last layout, if it has one
+    pub fn child(&self) -> Option<&dyn Widget> {
+        self.mounted
+            .as_ref()
+            .and_then(|mounted| mounted.child.as_deref())
+    }
+
+    /// The scope of the branch built by the last layout
+    pub fn scope(&self) -> Option<&Scope> {
+        self.mounted.as_ref().map(|mounted| &mounted.scope)
+    }
+
+    /// Build the branch for the condition's current value if it isn't already
+    fn mount(&mut self) {
+        let shown = self.condition.get();
+        if self
+            .mounted
+            .as_ref()
+            .is_some_and(|mounted| mounted.shown == shown)
+        {
+            return;
+        }
+        self.unmount();
+
+        let scope = match &self.parent_scope {
+            Some(parent) => parent.child(),
+            None => Scope::new(),
+        };
+        let build = if shown {
+            Some(&self.content)
+        } else {
+            self.fallback.as_ref()
+        };
+        let child = build.map(|build| scope.run(|| build()));
+        self.mounted = Some(Mounted {
+            shown,
+            child,
+            scope,
+        });
+    }
+
+    fn unmount(&mut self) {
+        if let Some(mounted) = self.mounted.take() {
+            mounted.scope.dispose();
+            if let Some(child) = mounted.child {
+                self.retired.push(child);
+                // Make sure an update pass comes to unmount it
+                mark_dirty();
+            }
+        }
+    }
+}
+
+impl Drop for Show {
+    fn drop(&mut self) {
+        if let Some(mounted) = self.mounted.take() {
+            mounted.scope.dispose();
+        }
+    }
+}
+
+impl Widget for Show {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || {
+            self.mount();
+            match self
+                .mounted
+                .as_mut()
+                .and_then(|mounted| mounted.child.as_deref_mut())
+            {
+                Some(child) => layout_child(&cache, child, constraints),
+                None => Size::zero(),
+            }
+        })
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child()
+            .map_or(Size::zero(), |child| child.measure(constraints))
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        if let Some(child) = self.child() {
+            child.render(batch, layout);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match self
+            .mounted
+            .as_mut()
+            .and_then(|mounted| mounted.child.as_deref_mut())
+        {
+            Some(child) => dispatch_event(child, event),
+            None => EventResult::Ignored,
+        }
+    }
+
+    fn update(&mut self, _ctx: &WidgetContext) {
+        // Unmounted by now, see `detached_children_mut`
+        self.retired.clear();
+    }
+
+    fn detached_children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        self.retired
+            .iter_mut()
+            .map(|child| child.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        self.child().into_iter().collect()
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        match self
+            .mounted
+            .as_mut()
+            .and_then(|mounted| mounted.child.as_mut())
+        {
+            Some(child) => vec![child.as_mut()],
+            None => Vec::new(),
+        }
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        // The clone builds its own branch, in a scope of its own
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&self.condition);
+        Box::new(Show {
+            id: clone_id(self.id),
+            condition: self.condition.clone(),
+            content: Arc::clone(&self.content),
+            fallback: self.fallback.clone(),
+            parent_scope: self.parent_scope.clone(),
+            mounted: None,
+            retired: Vec::new(),
+            layout_cache,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::Column;
+    use crate::test_util::{Probe, WidgetTester};
+    use crate::text::Text;
+    use parking_lot::Mutex;
+
+    #[test]
+    fn test_hiding_the_content_unmounts_it() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let probe = {
+            let log = Arc::clone(&log);
+            move |name| Probe::new(name, &log)
+        };
+        let visible = Signal::new(true);
+        let content = probe.clone();
+        let show = Show::new(visible.clone(), move || {
+            Column::new()
+                .child(Box::new(content("first")))
+                .child(Box::new(content("second")))
+        })
+        .fallback(move || probe("fallback"));
+        let mut tester = WidgetTester::new(show, 100.0, 40.0);
+        assert_eq!(*log.lock(), ["mount first", "mount second"]);
+
+        visible.set(false);
+        tester.pump();
+        tester.advance(0.0);
+        assert_eq!(
+            log.lock().drain(..).skip(2).collect::<Vec<_>>(),
+            ["unmount first", "unmount second", "mount fallback"]
+        );
+
+        // Toggling back and forth between update passes mounts only what stays
+        visible.set(true);
+        tester.pump();
+        visible.set(false);
+        tester.pump();
+        visible.set(true);
+        tester.pump();
+        tester.advance(0.0);
+        assert_eq!(
+            *log.lock(),
+            ["unmount fallback", "mount first", "mount second"]
+        );
+    }
+
+    #[test]
+    fn test_hiding_the_content_disposes_its_scope() {
+        let visible = Signal::new(true);
+        let count = Signal::new(1);
+        let source = count.clone();
+        let show = Show::new(visible.clone(), move || {
+            Text::new("").bind(source.map(|n| format!("Count {n}")))
+        })
+        .fallback(|| Text::new("Hidden"));
+        let mut tester = WidgetTester::new(show, 200.0, 40.0);
+        assert_eq!(tester.drawn_text(), vec!["Count 1"]);
+        assert_eq!(count.subscriber_count(), 1);
+        let scope = tester.root().scope().cloned().unwrap();
+
+        visible.set(false);
+        tester.pump();
+        assert!(scope.is_disposed());
+        assert_eq!(count.subscriber_count(), 0);
+        assert_eq!(tester.drawn_text(), vec!["Hidden"]);
+
+        visible.set(true);
+        tester.pump();
+        assert_eq!(count.subscriber_count(), 1);
+        count.set(2);
+        tester.pump();
+        assert_eq!(tester.drawn_text(), vec!["Count 2"]);
+    }
+
+    #[test]
+    fn test_branch_scopes_are_children_of_the_creating_scope() {
+        let visible = Signal::new(true);
+        let (show, outer) = strato_core::reactive::create_scope(|_| {
+            Show::new(visible.clone(), || Text::new("Shown"))
+        });
+        let mut tester = WidgetTester::new(show, 100.0, 40.0);
+        let scope = tester.root().scope().cloned().unwrap();
+
+        outer.dispose();
+        assert!(scope.is_disposed());
+        tester.pump();
+        assert_eq!(tester.drawn_text(), vec!["Shown"]);
+    }
+}
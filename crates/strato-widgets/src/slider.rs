@@ -1,13 +1,18 @@
 //! Slider and Progress widgets implementation for StratoUI
 
 use crate::control::{ControlRole, ControlState};
-use crate::widget::{generate_id, Widget, WidgetContext, WidgetId, WidgetState};
+use crate::theme::ColorPalette;
+use crate::widget::{
+    clone_id, generate_id, register_bounds, Widget, WidgetContext, WidgetId, WidgetState,
+};
 use std::any::Any;
+use std::sync::Arc;
 use strato_core::{
-    event::{Event, EventResult, MouseButton},
+    event::{Event, EventResult, KeyCode, MouseButton},
     layout::{Constraints, Layout, Size},
     state::Signal,
     types::{Color, Point, Rect, Transform},
+    window::CursorIcon,
 };
 use strato_renderer::batch::RenderBatch;
 
@@ -19,13 +24,18 @@ pub struct Slider {
     min: f32,
     max: f32,
     step: f32,
+    keyboard_step: Option<(f32, f32)>,
     width: f32,
     height: f32,
     enabled: bool,
     style: SliderStyle,
     dragging: Signal<bool>,
+    focused: Signal<bool>,
+    tab_index: Option<i32>,
     bounds: Signal<Rect>,
     control: ControlState,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
 }
 
 /// Styling options for slider
@@ -67,6 +77,98 @@ fn blend_color(a: Color, b: Color, t: f32) -> Color {
     Color::rgba(mix(a.r, b.r), mix(a.g, b.g), mix(a.b, b.b), mix(a.a, b.a))
 }
 
+/// `value` snapped to a multiple of `step` above `min`, within `min..=max`
+fn snap(value: f32, min: f32, max: f32, step: f32) -> f32 {
+    let stepped = if step > 0.0 {
+        min + ((value - min) / step).round() * step
+    } else {
+        value
+    };
+    stepped.clamp(min, max)
+}
+
+/// Where `value` sits along a track, from 0.0 at `min` to 1.0 at `max`
+fn ratio_of(value: f32, min: f32, max: f32) -> f32 {
+    if max > min {
+        (value - min) / (max - min)
+    } else {
+        0.0
+    }
+}
+
+impl SliderStyle {
+    /// Start and width of the track in a slider laid out in `bounds`
+    ///
+    /// The track is inset by half a thumb so thumbs at the ends stay inside.
+    fn track(&self, bounds: Rect) -> (f32, f32) {
+        let width = (bounds.width - self.thumb_size).max(0.0);
+        (bounds.x + self.thumb_size * 0.5, width)
+    }
+
+    /// The value under `x` on the track of a slider laid out in `bounds`
+    fn value_at(&self, x: f32, bounds: Rect, min: f32, max: f32, step: f32) -> f32 {
+        let (track_x, track_width) = self.track(bounds);
+        let ratio = ((x - track_x) / track_width).clamp(0.0, 1.0);
+        snap(min + ratio * (max - min), min, max, step)
+    }
+
+    /// Draw the track, filled between the offsets `from` and `to`
+    fn draw_track(
+        &self,
+        batch: &mut RenderBatch,
+        bounds: Rect,
+        from: f32,
+        to: f32,
+        paint: impl Fn(Color) -> Color,
+    ) {
+        let (track_x, track_width) = self.track(bounds);
+        let track_y = bounds.y + (bounds.height - self.track_height) * 0.5;
+        let track_rect = Rect::new(track_x, track_y, track_width, self.track_height);
+        batch.add_rect(
+            track_rect,
+            paint(color_from(self.track_color)),
+            Transform::identity(),
+        );
+
+        let from = from.clamp(0.0, track_width);
+        let to = to.clamp(from, track_width);
+        let fill_rect = Rect::new(track_x + from, track_y, to - from, self.track_height);
+        batch.add_rect(
+            fill_rect,
+            paint(color_from(self.track_fill_color)),
+            Transform::identity(),
+        );
+    }
+
+    /// Draw a thumb at `offset` along the track
+    fn draw_thumb(
+        &self,
+        batch: &mut RenderBatch,
+        bounds: Rect,
+        offset: f32,
+        state: WidgetState,
+        interaction: f32,
+        paint: impl Fn(Color) -> Color,
+    ) {
+        let (track_x, _) = self.track(bounds);
+        let center = (track_x + offset, bounds.y + bounds.height * 0.5);
+
+        let base = color_from(self.thumb_color);
+        let target = match state {
+            WidgetState::Pressed => color_from(self.thumb_active_color),
+            WidgetState::Hovered => color_from(self.thumb_hover_color),
+            _ => base,
+        };
+        batch.add_circle(
+            center,
+            self.thumb_size * 0.5,
+            paint(blend_color(base, target, interaction)),
+            16,
+            Transform::default(),
+        );
+    }
+}
+
 impl Slider {
     /// Create a new slider
     pub fn new(min: f32, max: f32) -> Self {
@@ -78,13 +180,17 @@ impl Slider {
             min,
             max,
             step: 1.0,
+            keyboard_step: None,
             width: 200.0,
             height: 40.0,
             enabled: true,
             style: SliderStyle::default(),
             dragging: Signal::new(false),
+            focused: Signal::new(false),
+            tab_index: None,
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
             control,
+            palette: ColorPalette::default(),
         }
     }
 
@@ -102,6 +208,15 @@ impl Slider {
         self
     }
 
+    /// Set how far the keyboard moves the slider
+    ///
+    /// The arrow keys move it by `small` and Page Up/Down by `large`, both at
+    /// least one [`step`](Self::step). Defaults to one step and ten steps.
+    pub fn keyboard_step(mut self, small: f32, large: f32) -> Self {
+        self.keyboard_step = Some((small, large));
+        self
+    }
+
     /// Set the slider dimensions
     pub fn size(mut self, width: f32, height: f32) -> Self {
         self.width = width;
@@ -116,6 +231,12 @@ impl Slider {
         self
     }
 
+    /// Set the slider's place in the Tab order, see [`focus`](crate::focus)
+    pub fn tab_index(mut self, index: i32) -> Self {
+        self.tab_index = Some(index);
+        self
+    }
+
     /// Set custom style
     pub fn style(mut self, style: SliderStyle) -> Self {
         self.style = style;
@@ -132,38 +253,56 @@ impl Slider {
         self.value.get()
     }
 
-    /// Set the value
+    /// Set the value, snapped to a step and clamped to the range
     pub fn set_value(&mut self, value: f32) {
-        let clamped = value.clamp(self.min, self.max);
-        let stepped = if self.step > 0.0 {
-            (clamped / self.step).round() * self.step
-        } else {
-            clamped
-        };
-        self.value.set(stepped);
-        self.control.set_value(format!("{:.2}", stepped));
+        let value = snap(value, self.min, self.max, self.step);
+        self.value.set(value);
+        self.control.set_value(format!("{:.2}", value));
     }
 
-    /// Calculate value from position
-    fn value_from_position(&self, x: f32, track_width: f32) -> f32 {
-        let ratio = (x / track_width).clamp(0.0, 1.0);
-        let value = self.min + ratio * (self.max - self.min);
+    /// Check if the slider has keyboard focus
+    pub fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
 
-        if self.step > 0.0 {
-            (value / self.step).round() * self.step
-        } else {
-            value
+    /// Give the slider keyboard focus
+    pub fn focus(&self) {
+        if self.enabled {
+            self.focused.set(true);
+            self.control.focus();
         }
     }
 
-    /// Calculate thumb position from value
-    fn thumb_position(&self, track_width: f32) -> f32 {
-        let ratio = if self.max > self.min {
-            (self.value.get() - self.min) / (self.max - self.min)
-        } else {
-            0.0
+    /// Remove keyboard focus
+    pub fn blur(&self) {
+        self.focused.set(false);
+        self.control.blur();
+    }
+
+    /// Handle keyboard events while focused
+    ///
+    /// The arrow keys move by the small keyboard step (right and up
+    /// increase), Page Up/Down by the large one, and Home/End jump to the
+    /// ends of the range.
+    fn handle_key(&mut self, key_code: KeyCode) -> EventResult {
+        if !self.is_focused() {
+            return EventResult::Ignored;
+        }
+
+        let (small, large) = self.keyboard_step.unwrap_or((self.step, self.step * 10.0));
+        let (small, large) = (small.max(self.step), large.max(self.step));
+        let value = self.value.get();
+        let target = match key_code {
+            KeyCode::Right | KeyCode::Up => value + small,
+            KeyCode::Left | KeyCode::Down => value - small,
+            KeyCode::PageUp => value + large,
+            KeyCode::PageDown => value - large,
+            KeyCode::Home => self.min,
+            KeyCode::End => self.max,
+            _ => return EventResult::Ignored,
         };
-        ratio * track_width
+        self.set_value(target);
+        EventResult::Handled
     }
 
     /// Handle mouse events using stored bounds
@@ -173,20 +312,25 @@ impl Slider {
         }
 
         let bounds = self.bounds.get();
-        let track_width = (bounds.width - self.style.thumb_size).max(0.0);
-        let track_start_x = bounds.x + self.style.thumb_size * 0.5;
+        let value_at = |x: f32| {
+            self.style
+                .value_at(x, bounds, self.min, self.max, self.step)
+        };
 
         match event {
             Event::MouseDown(mouse_event) => {
                 let point = Point::new(mouse_event.position.x, mouse_event.position.y);
                 if !bounds.contains(point) {
+                    if self.is_focused() {
+                        self.blur();
+                    }
                     return EventResult::Ignored;
                 }
 
                 if let Some(MouseButton::Left) = mouse_event.button {
+                    self.focused.set(true);
                     self.control.press(point, bounds);
-                    let local_x = mouse_event.position.x - track_start_x;
-                    let new_value = self.value_from_position(local_x, track_width);
+                    let new_value = value_at(point.x);
                     self.set_value(new_value);
                     self.dragging.set(true);
                     EventResult::Handled
@@ -195,8 +339,7 @@ impl Slider {
                 }
             }
             Event::MouseMove(mouse_event) if self.dragging.get() => {
-                let local_x = mouse_event.position.x - track_start_x;
-                let new_value = self.value_from_position(local_x, track_width);
+                let new_value = value_at(mouse_event.position.x);
                 self.set_value(new_value);
                 self.control.set_state(WidgetState::Pressed);
                 EventResult::Handled
@@ -232,6 +375,10 @@ impl Widget for Slider {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         constraints.constrain(Size::new(self.width, self.height))
     }
@@ -244,16 +391,9 @@ impl Widget for Slider {
             layout.size.height,
         );
         self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
-        let track_width = (bounds.width - self.style.thumb_size).max(0.0);
-        let track_x = bounds.x + self.style.thumb_size * 0.5;
-        let track_y = bounds.y + (bounds.height - self.style.track_height) * 0.5;
-
-        let track_rect = Rect::new(track_x, track_y, track_width, self.style.track_height);
-
-        let state = if !self.enabled {
-            WidgetState::Disabled
-        } else if self.dragging.get() {
+        let state = if self.dragging.get() {
             WidgetState::Pressed
         } else {
             self.control.state()
@@ -264,51 +404,59 @@ impl Widget for Slider {
             self.control.interaction_factor()
         };
 
-        let track_color = match state {
-            WidgetState::Disabled => color_from(self.style.disabled_color),
-            _ => color_from(self.style.track_color),
+        // Disabled sliders keep their colors, faded by the theme
+        let paint = |color: Color| {
+            if self.enabled {
+                color
+            } else {
+                self.palette.disabled(color)
+            }
         };
 
-        batch.add_rect(track_rect, track_color, Transform::identity());
+        let (_, track_width) = self.style.track(bounds);
+        let thumb_offset = ratio_of(self.value.get(), self.min, self.max) * track_width;
+        self.style
+            .draw_track(batch, bounds, 0.0, thumb_offset, paint);
+        self.style
+            .draw_thumb(batch, bounds, thumb_offset, state, interaction, paint);
+    }
 
-        let thumb_offset = self.thumb_position(track_width);
-        let fill_rect = Rect::new(
-            track_x,
-            track_y,
-            thumb_offset.clamp(0.0, track_width),
-            self.style.track_height,
-        );
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
 
-        let mut fill_color = color_from(self.style.track_fill_color);
-        if matches!(state, WidgetState::Disabled) {
-            fill_color = blend_color(fill_color, track_color, 0.6);
-        }
+    fn is_focused(&self) -> bool {
+        self.focused.get()
+    }
 
-        batch.add_rect(fill_rect, fill_color, Transform::identity());
+    fn is_focusable(&self) -> bool {
+        self.enabled
+    }
 
-        let thumb_center_x = track_x + thumb_offset;
-        let thumb_center_y = bounds.y + bounds.height * 0.5;
-        let thumb_radius = self.style.thumb_size * 0.5;
+    fn tab_index(&self) -> Option<i32> {
+        self.tab_index
+    }
 
-        let thumb_base = color_from(self.style.thumb_color);
-        let thumb_target = match state {
-            WidgetState::Pressed => color_from(self.style.thumb_active_color),
-            WidgetState::Hovered => color_from(self.style.thumb_hover_color),
-            WidgetState::Disabled => color_from(self.style.disabled_color),
-            _ => thumb_base,
-        };
-        let thumb_color = blend_color(thumb_base, thumb_target, interaction);
+    fn set_focused(&mut self, focused: bool) {
+        if focused {
+            self.focus();
+        } else {
+            self.blur();
+        }
+    }
 
-        batch.add_circle(
-            (thumb_center_x, thumb_center_y),
-            thumb_radius,
-            thumb_color, // Use state-aware color
-            16,
-            strato_core::types::Transform::default(),
-        );
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.bounds
+            .get()
+            .contains(point)
+            .then_some(CursorIcon::Pointer)
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+
         if matches!(
             event,
             Event::MouseDown(_) | Event::MouseUp(_) | Event::MouseMove(_)
@@ -319,6 +467,12 @@ impl Widget for Slider {
             }
         }
 
+        if let Event::KeyDown(key) = event {
+            if let EventResult::Handled = self.handle_key(key.key_code) {
+                return EventResult::Handled;
+            }
+        }
+
         if let EventResult::Handled = self.control.handle_keyboard_activation(event) {
             return EventResult::Handled;
         }
@@ -328,6 +482,7 @@ impl Widget for Slider {
 
     fn update(&mut self, ctx: &WidgetContext) {
         self.control.update(ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -339,12 +494,333 @@ impl Widget for Slider {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(self.clone())
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        widget.dragging = Signal::new(false);
+        widget.focused = Signal::new(false);
+        Box::new(widget)
     }
 
     // Removed state method as it's not part of Widget trait
 }
 
+/// One of the two thumbs of a [`RangeSlider`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Thumb {
+    Low,
+    High,
+}
+
+/// Callback receiving a range slider's `(low, high)` values
+pub type RangeChangeFn = Arc<dyn Fn((f32, f32)) + Send + Sync>;
+
+/// Slider with a low and a high thumb selecting a range
+///
+/// The thumbs can meet but not cross. Pressing the track moves the nearest
+/// thumb there and drags it until the button is released.
+#[derive(Clone)]
+pub struct RangeSlider {
+    id: WidgetId,
+    values: Signal<(f32, f32)>,
+    min: f32,
+    max: f32,
+    step: f32,
+    width: f32,
+    height: f32,
+    enabled: bool,
+    style: SliderStyle,
+    on_change: Option<RangeChangeFn>,
+    dragging: Signal<Option<Thumb>>,
+    bounds: Signal<Rect>,
+    control: ControlState,
+    /// Palette of the theme seen in the last update, for disabled colors
+    palette: ColorPalette,
+}
+
+impl std::fmt::Debug for RangeSlider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeSlider")
+            .field("id", &self.id)
+            .field("values", &self.values)
+            .field("min", &self.min)
+            .field("max", &self.max)
+            .field("step", &self.step)
+            .field("enabled", &self.enabled)
+            .field(
+                "on_change",
+                &self.on_change.as_ref().map(|_| "Fn((f32, f32))"),
+            )
+            .finish()
+    }
+}
+
+impl RangeSlider {
+    /// Create a range slider selecting all of `min..=max`
+    pub fn new(min: f32, max: f32) -> Self {
+        let mut control = ControlState::new(ControlRole::Slider);
+        control.set_value(format!("{:.2} to {:.2}", min, max));
+        Self {
+            id: generate_id(),
+            values: Signal::new((min, max)),
+            min,
+            max,
+            step: 1.0,
+            width: 200.0,
+            height: 40.0,
+            enabled: true,
+            style: SliderStyle::default(),
+            on_change: None,
+            dragging: Signal::new(None),
+            bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            control,
+            palette: ColorPalette::default(),
+        }
+    }
+
+    /// Set the initial range
+    pub fn values(mut self, low: f32, high: f32) -> Self {
+        self.set_values(low, high);
+        self
+    }
+
+    /// Bind the range to a signal, which is updated as the thumbs move
+    pub fn bind(mut self, values: Signal<(f32, f32)>) -> Self {
+        self.values = values;
+        let (low, high) = self.values.get();
+        self.control.set_value(format!("{:.2} to {:.2}", low, high));
+        self
+    }
+
+    /// Set the step size
+    pub fn step(mut self, step: f32) -> Self {
+        self.step = step.max(0.01); // Minimum step
+        self
+    }
+
+    /// Set the slider dimensions
+    pub fn size(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set enabled state
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self.control.set_disabled(!enabled);
+        self
+    }
+
+    /// Set custom style
+    pub fn style(mut self, style: SliderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Call `handler` with `(low, high)` whenever the range changes
+    pub fn on_change<F>(mut self, handler: F) -> Self
+    where
+        F: Fn((f32, f32)) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(handler));
+        self
+    }
+
+    /// Get the range signal
+    pub fn values_signal(&self) -> &Signal<(f32, f32)> {
+        &self.values
+    }
+
+    /// Get the current `(low, high)` range
+    pub fn get_values(&self) -> (f32, f32) {
+        self.values.get()
+    }
+
+    /// Set the range, snapped to a step, clamped and with `low <= high`
+    pub fn set_values(&mut self, low: f32, high: f32) {
+        let low = snap(low, self.min, self.max, self.step);
+        let high = snap(high, self.min, self.max, self.step).max(low);
+        self.update((low, high));
+    }
+
+    /// Move one thumb to `value`, stopping at the other thumb
+    fn move_thumb(&mut self, thumb: Thumb, value: f32) {
+        let (low, high) = self.values.get();
+        let value = snap(value, self.min, self.max, self.step);
+        match thumb {
+            Thumb::Low => self.update((value.min(high), high)),
+            Thumb::High => self.update((low, value.max(low))),
+        }
+    }
+
+    fn update(&mut self, values: (f32, f32)) {
+        if self.values.get() == values {
+            return;
+        }
+        self.values.set(values);
+        self.control
+            .set_value(format!("{:.2} to {:.2}", values.0, values.1));
+        if let Some(handler) = &self.on_change {
+            handler(values);
+        }
+    }
+
+    /// The thumb nearest to `value`
+    ///
+    /// When the thumbs meet, the one on the side of `value` is picked so it
+    /// can move away from the other.
+    fn nearest_thumb(&self, value: f32) -> Thumb {
+        let (low, high) = self.values.get();
+        if value > high || (value - low).abs() > (value - high).abs() {
+            Thumb::High
+        } else if value < low || low < high {
+            Thumb::Low
+        } else {
+            // Thumbs meet at `value`
+            Thumb::High
+        }
+    }
+
+    /// Handle mouse events using stored bounds
+    fn handle_mouse_event(&mut self, event: &Event) -> EventResult {
+        let bounds = self.bounds.get();
+        let value_at = |x: f32| {
+            self.style
+                .value_at(x, bounds, self.min, self.max, self.step)
+        };
+
+        match event {
+            Event::MouseDown(mouse_event) => {
+                let point = Point::new(mouse_event.position.x, mouse_event.position.y);
+                if !bounds.contains(point) || mouse_event.button != Some(MouseButton::Left) {
+                    return EventResult::Ignored;
+                }
+
+                self.control.press(point, bounds);
+                let value = value_at(point.x);
+                let thumb = self.nearest_thumb(value);
+                self.move_thumb(thumb, value);
+                self.dragging.set(Some(thumb));
+                EventResult::Handled
+            }
+            Event::MouseMove(mouse_event) => match self.dragging.get() {
+                Some(thumb) => {
+                    let value = value_at(mouse_event.position.x);
+                    self.move_thumb(thumb, value);
+                    self.control.set_state(WidgetState::Pressed);
+                    EventResult::Handled
+                }
+                None => {
+                    let point = Point::new(mouse_event.position.x, mouse_event.position.y);
+                    self.control.hover(bounds.contains(point));
+                    EventResult::Ignored
+                }
+            },
+            Event::MouseUp(mouse_event) if self.dragging.get().is_some() => {
+                self.dragging.set(None);
+                let point = Point::new(mouse_event.position.x, mouse_event.position.y);
+                self.control.release(point, bounds);
+                EventResult::Handled
+            }
+            _ => EventResult::Ignored,
+        }
+    }
+}
+
+impl Default for RangeSlider {
+    fn default() -> Self {
+        Self::new(0.0, 100.0)
+    }
+}
+
+impl Widget for RangeSlider {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        constraints.constrain(Size::new(self.width, self.height))
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
+
+        let dragging = self.dragging.get();
+        let paint = |color: Color| {
+            if self.enabled {
+                color
+            } else {
+                self.palette.disabled(color)
+            }
+        };
+
+        let (_, track_width) = self.style.track(bounds);
+        let (low, high) = self.values.get();
+        let low_offset = ratio_of(low, self.min, self.max) * track_width;
+        let high_offset = ratio_of(high, self.min, self.max) * track_width;
+        self.style
+            .draw_track(batch, bounds, low_offset, high_offset, paint);
+
+        // The dragged thumb is drawn last, on top where the thumbs meet
+        let mut thumbs = [(Thumb::Low, low_offset), (Thumb::High, high_offset)];
+        if dragging == Some(Thumb::Low) {
+            thumbs.reverse();
+        }
+        for (thumb, offset) in thumbs {
+            let (state, interaction) = if dragging == Some(thumb) {
+                (WidgetState::Pressed, 1.0)
+            } else if dragging.is_some() {
+                (WidgetState::Normal, 0.0)
+            } else {
+                (self.control.state(), self.control.interaction_factor())
+            };
+            self.style
+                .draw_thumb(batch, bounds, offset, state, interaction, paint);
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if !self.enabled {
+            return EventResult::Ignored;
+        }
+        self.handle_mouse_event(event)
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        self.control.update(ctx.delta_time);
+        self.palette = ctx.theme.colors.clone();
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.bounds
+            .get()
+            .contains(point)
+            .then_some(CursorIcon::Pointer)
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        widget.dragging = Signal::new(None);
+        Box::new(widget)
+    }
+}
+
 /// Progress bar widget for showing completion status
 #[derive(Debug, Clone)]
 pub struct ProgressBar {
@@ -456,6 +932,10 @@ impl Widget for ProgressBar {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         constraints.constrain(Size::new(self.width, self.height))
     }
@@ -506,7 +986,9 @@ impl Widget for ProgressBar {
     }
 
     fn clone_widget(&self) -> Box<dyn Widget> {
-        Box::new(self.clone())
+        let mut widget = self.clone();
+        widget.id = clone_id(self.id);
+        Box::new(widget)
     }
 
     // Removed state method as it's not part of Widget trait
@@ -515,6 +997,7 @@ impl Widget for ProgressBar {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::WidgetTester;
 
     #[test]
     fn test_slider_creation() {
@@ -546,6 +1029,111 @@ mod tests {
         assert_eq!(slider.get_value(), 30.0);
     }
 
+    #[test]
+    fn test_keyboard_steps_when_focused() {
+        let mut tester = WidgetTester::new(
+            Slider::new(0.0, 100.0).step(5.0).keyboard_step(5.0, 25.0),
+            200.0,
+            40.0,
+        );
+
+        // Keys do nothing until the slider is clicked
+        assert_eq!(tester.key_down(KeyCode::Right), EventResult::Ignored);
+        tester.click(10.0, 20.0);
+        assert!(tester.root().is_focused());
+        assert_eq!(tester.root().get_value(), 0.0);
+
+        tester.key_down(KeyCode::Right);
+        tester.key_down(KeyCode::Up);
+        assert_eq!(tester.root().get_value(), 10.0);
+        tester.key_down(KeyCode::PageUp);
+        assert_eq!(tester.root().get_value(), 35.0);
+        tester.key_down(KeyCode::PageDown);
+        tester.key_down(KeyCode::PageDown);
+        assert_eq!(tester.root().get_value(), 0.0);
+        tester.key_down(KeyCode::End);
+        assert_eq!(tester.root().get_value(), 100.0);
+        tester.key_down(KeyCode::Right);
+        assert_eq!(tester.root().get_value(), 100.0);
+        tester.key_down(KeyCode::Home);
+        assert_eq!(tester.root().get_value(), 0.0);
+
+        // Clicking elsewhere takes the focus away
+        tester.click(300.0, 20.0);
+        assert!(!tester.root().is_focused());
+        assert_eq!(tester.key_down(KeyCode::End), EventResult::Ignored);
+    }
+
+    #[test]
+    fn test_keyboard_steps_snap_to_the_step() {
+        let mut slider = Slider::new(3.0, 48.0).step(10.0).keyboard_step(1.0, 20.0);
+        slider.focus();
+
+        // A keyboard step below the snapping step still moves a full step
+        slider.handle_event(&key(KeyCode::Right));
+        assert_eq!(slider.get_value(), 13.0);
+        slider.handle_event(&key(KeyCode::PageUp));
+        assert_eq!(slider.get_value(), 33.0);
+        slider.handle_event(&key(KeyCode::PageUp));
+        assert_eq!(slider.get_value(), 48.0);
+    }
+
+    fn key(key_code: KeyCode) -> Event {
+        Event::KeyDown(strato_core::event::KeyboardEvent {
+            key_code,
+            modifiers: Default::default(),
+            text: None,
+            is_repeat: false,
+        })
+    }
+
+    #[test]
+    fn test_range_thumbs_do_not_cross() {
+        let changes = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let recorder = changes.clone();
+        // Track from x = 10 to 210, one pixel per value
+        let slider = RangeSlider::new(0.0, 200.0)
+            .values(50.0, 150.0)
+            .on_change(move |values| recorder.lock().push(values));
+        let mut tester = WidgetTester::new(slider, 220.0, 40.0);
+
+        // Drag the low thumb past the high one: it stops there
+        tester.press(60.0, 20.0, MouseButton::Left);
+        tester.move_to(200.0, 20.0);
+        assert_eq!(tester.root().get_values(), (150.0, 150.0));
+        tester.release(200.0, 20.0, MouseButton::Left);
+
+        // Where the thumbs meet, dragging right moves the high one
+        tester.press(170.0, 20.0, MouseButton::Left);
+        tester.move_to(190.0, 20.0);
+        tester.release(190.0, 20.0, MouseButton::Left);
+        assert_eq!(tester.root().get_values(), (150.0, 180.0));
+
+        assert_eq!(
+            *changes.lock(),
+            vec![(150.0, 150.0), (150.0, 160.0), (150.0, 180.0)]
+        );
+        assert_eq!(tester.root().values_signal().get(), (150.0, 180.0));
+    }
+
+    #[test]
+    fn test_range_track_click_moves_nearest_thumb() {
+        let values = Signal::new((20.0, 80.0));
+        let slider = RangeSlider::new(0.0, 100.0)
+            .step(10.0)
+            .bind(values.clone())
+            .size(120.0, 40.0);
+        let mut tester = WidgetTester::new(slider, 120.0, 40.0);
+
+        // Track from x = 10 to 110, one pixel per value
+        tester.click(43.0, 20.0);
+        assert_eq!(values.get(), (30.0, 80.0));
+        tester.click(71.0, 20.0);
+        assert_eq!(values.get(), (30.0, 60.0));
+        tester.click(5.0, 20.0);
+        assert_eq!(values.get(), (0.0, 60.0));
+    }
+
     #[test]
     fn test_progress_bar_creation() {
         let progress = ProgressBar::new(100.0);
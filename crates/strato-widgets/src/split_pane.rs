@@ -0,0 +1,549 @@
+//! Resizable split pane widget
+//!
+//! [`SplitPane`] shows two children side by side or stacked, separated by a
+//! divider the user drags to share the space between them. The split is kept
+//! in a `Signal<f32>` holding the first pane's fraction of the space, so it
+//! can be saved, restored or driven from elsewhere in the app.
+
+use crate::builder::IntoChild;
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, register_bounds, Widget, WidgetId,
+};
+use std::any::Any;
+use std::time::{Duration, Instant};
+use strato_core::{
+    event::{Event, EventResult, MouseButton},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    state::Signal,
+    types::{Color, Point, Rect},
+    window::CursorIcon,
+    Transform,
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Two presses on the divider within this time reset the split
+const DOUBLE_CLICK_TIME: Duration = Duration::from_millis(400);
+
+/// How a [`SplitPane`] arranges its panes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitAxis {
+    /// First pane on the left, second on the right
+    Horizontal,
+    /// First pane on top, second below
+    Vertical,
+}
+
+/// Styling options for the divider
+#[derive(Debug, Clone)]
+pub struct SplitPaneStyle {
+    pub divider_thickness: f32,
+    /// Grab area around the divider, for dividers too thin to hit
+    pub divider_hit_thickness: f32,
+    pub divider_color: Color,
+    /// Divider color while hovered or dragged
+    pub divider_active_color: Color,
+}
+
+impl Default for SplitPaneStyle {
+    fn default() -> Self {
+        Self {
+            divider_thickness: 4.0,
+            divider_hit_thickness: 8.0,
+            divider_color: Color::rgba(0.5, 0.5, 0.5, 0.4),
+            divider_active_color: Color::rgba(0.2, 0.6, 1.0, 0.8),
+        }
+    }
+}
+
+/// The divider drag in progress
+#[derive(Debug, Clone, Copy)]
+struct Drag {
+    /// Pointer offset from the divider's leading edge when pressed
+    grab_offset: f32,
+}
+
+/// Two panes separated by a draggable divider
+pub struct SplitPane {
+    id: WidgetId,
+    axis: SplitAxis,
+    first: Box<dyn Widget>,
+    second: Box<dyn Widget>,
+    ratio: Signal<f32>,
+    /// Whether `ratio` was bound by the app rather than created here
+    ratio_bound: bool,
+    default_ratio: f32,
+    min_first: f32,
+    min_second: f32,
+    style: SplitPaneStyle,
+    bounds: Signal<Rect>,
+    drag: Option<Drag>,
+    hovered: bool,
+    last_divider_press: Option<Instant>,
+    layout_cache: LayoutCache,
+}
+
+impl std::fmt::Debug for SplitPane {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SplitPane")
+            .field("id", &self.id)
+            .field("axis", &self.axis)
+            .field("first", &self.first)
+            .field("second", &self.second)
+            .field("ratio", &self.ratio.get())
+            .field("default_ratio", &self.default_ratio)
+            .field("min_size", &(self.min_first, self.min_second))
+            .field("style", &self.style)
+            .field("dragging", &self.drag.is_some())
+            .finish()
+    }
+}
+
+impl SplitPane {
+    /// Split between `first` and `second` along `axis`, half and half
+    pub fn new(axis: SplitAxis, first: impl IntoChild, second: impl IntoChild) -> Self {
+        let ratio = Signal::new(0.5);
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&ratio);
+        Self {
+            id: generate_id(),
+            axis,
+            first: first.into_child(),
+            second: second.into_child(),
+            ratio,
+            ratio_bound: false,
+            default_ratio: 0.5,
+            min_first: 0.0,
+            min_second: 0.0,
+            style: SplitPaneStyle::default(),
+            bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
+            drag: None,
+            hovered: false,
+            last_divider_press: None,
+            layout_cache,
+        }
+    }
+
+    /// `left` and `right` side by side with a vertical divider
+    pub fn horizontal(left: impl IntoChild, right: impl IntoChild) -> Self {
+        Self::new(SplitAxis::Horizontal, left, right)
+    }
+
+    /// `top` above `bottom` with a horizontal divider
+    pub fn vertical(top: impl IntoChild, bottom: impl IntoChild) -> Self {
+        Self::new(SplitAxis::Vertical, top, bottom)
+    }
+
+    /// Bind the split to `ratio`, the first pane's fraction of the space
+    ///
+    /// Dragging the divider writes the signal, and setting it moves the
+    /// divider.
+    pub fn ratio(mut self, ratio: Signal<f32>) -> Self {
+        self.layout_cache.watch(&ratio);
+        self.layout_cache.mark_dirty();
+        self.ratio = ratio;
+        self.ratio_bound = true;
+        self
+    }
+
+    /// Set the ratio a double-click on the divider resets to
+    ///
+    /// Also the starting split unless [`ratio`](Self::ratio) binds one.
+    pub fn default_ratio(mut self, ratio: f32) -> Self {
+        let ratio = ratio.clamp(0.0, 1.0);
+        if !self.ratio_bound {
+            self.ratio.set(ratio);
+        }
+        self.default_ratio = ratio;
+        self
+    }
+
+    /// Keep each pane at least `min_size` along the split axis
+    pub fn min_size(self, min_size: f32) -> Self {
+        self.min_sizes(min_size, min_size)
+    }
+
+    /// Keep the first and second pane at least `first` and `second` along
+    /// the split axis
+    pub fn min_sizes(mut self, first: f32, second: f32) -> Self {
+        self.min_first = first.max(0.0);
+        self.min_second = second.max(0.0);
+        self.layout_cache.mark_dirty();
+        self
+    }
+
+    /// Set custom style
+    pub fn style(mut self, style: SplitPaneStyle) -> Self {
+        self.style = style;
+        self.layout_cache.mark_dirty();
+        self
+    }
+
+    /// The signal holding the first pane's fraction of the space
+    pub fn ratio_signal(&self) -> &Signal<f32> {
+        &self.ratio
+    }
+
+    /// Put the divider back at the default ratio
+    pub fn reset(&self) {
+        self.ratio.set(self.default_ratio);
+    }
+
+    /// Whether the divider is being dragged
+    pub fn is_dragging(&self) -> bool {
+        self.drag.is_some()
+    }
+
+    /// Length of `size` along the split axis
+    fn main_extent(&self, size: Size) -> f32 {
+        match self.axis {
+            SplitAxis::Horizontal => size.width,
+            SplitAxis::Vertical => size.height,
+        }
+    }
+
+    /// Coordinate of `point` along the split axis
+    fn main_coordinate(&self, point: Point) -> f32 {
+        match self.axis {
+            SplitAxis::Horizontal => point.x,
+            SplitAxis::Vertical => point.y,
+        }
+    }
+
+    /// Space shared by the panes, i.e. without the divider
+    fn available(&self, bounds: Rect) -> f32 {
+        (self.main_extent(Size::new(bounds.width, bounds.height)) - self.style.divider_thickness)
+            .max(0.0)
+    }
+
+    /// The first pane's extent for `ratio`, honouring the minimum sizes
+    ///
+    /// When both minimums don't fit, the first pane's wins.
+    fn first_extent(&self, ratio: f32, available: f32) -> f32 {
+        let upper = (available - self.min_second).max(self.min_first);
+        (ratio.clamp(0.0, 1.0) * available)
+            .clamp(self.min_first, upper)
+            .min(available)
+    }
+
+    /// The first pane, divider and second pane rects within `bounds`
+    fn split(&self, bounds: Rect) -> (Rect, Rect, Rect) {
+        let available = self.available(bounds);
+        let first = self.first_extent(self.ratio.get(), available);
+        let second = available - first;
+        let divider = self
+            .style
+            .divider_thickness
+            .min(self.main_extent(Size::new(bounds.width, bounds.height)));
+
+        match self.axis {
+            SplitAxis::Horizontal => (
+                Rect::new(bounds.x, bounds.y, first, bounds.height),
+                Rect::new(bounds.x + first, bounds.y, divider, bounds.height),
+                Rect::new(bounds.x + first + divider, bounds.y, second, bounds.height),
+            ),
+            SplitAxis::Vertical => (
+                Rect::new(bounds.x, bounds.y, bounds.width, first),
+                Rect::new(bounds.x, bounds.y + first, bounds.width, divider),
+                Rect::new(bounds.x, bounds.y + first + divider, bounds.width, second),
+            ),
+        }
+    }
+
+    /// The area that grabs the divider, at least the hit thickness across
+    fn divider_hit_rect(&self, bounds: Rect) -> Rect {
+        let (_, divider, _) = self.split(bounds);
+        let grow =
+            ((self.style.divider_hit_thickness - self.style.divider_thickness) * 0.5).max(0.0);
+        match self.axis {
+            SplitAxis::Horizontal => Rect::new(
+                divider.x - grow,
+                divider.y,
+                divider.width + grow * 2.0,
+                divider.height,
+            ),
+            SplitAxis::Vertical => Rect::new(
+                divider.x,
+                divider.y - grow,
+                divider.width,
+                divider.height + grow * 2.0,
+            ),
+        }
+    }
+
+    fn resize_cursor(&self) -> CursorIcon {
+        match self.axis {
+            SplitAxis::Horizontal => CursorIcon::ResizeEW,
+            SplitAxis::Vertical => CursorIcon::ResizeNS,
+        }
+    }
+
+    /// Move the divider so its leading edge sits at `position`
+    fn drag_to(&mut self, position: f32) {
+        let bounds = self.bounds.get();
+        let available = self.available(bounds);
+        if available <= 0.0 {
+            return;
+        }
+        let start = self.main_coordinate(Point::new(bounds.x, bounds.y));
+        let first = self.first_extent((position - start) / available, available);
+        let ratio = first / available;
+        if ratio != self.ratio.get() {
+            self.ratio.set(ratio);
+        }
+    }
+
+    fn dispatch_to_panes(&mut self, event: &Event) -> EventResult {
+        if dispatch_event(self.first.as_mut(), event) == EventResult::Handled {
+            return EventResult::Handled;
+        }
+        dispatch_event(self.second.as_mut(), event)
+    }
+}
+
+impl Widget for SplitPane {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || {
+            // Fills the space it is given, falling back to the minimum
+            // along unbounded axes
+            let width = if constraints.max_width.is_finite() {
+                constraints.max_width
+            } else {
+                constraints.min_width
+            };
+            let height = if constraints.max_height.is_finite() {
+                constraints.max_height
+            } else {
+                constraints.min_height
+            };
+            let size = Size::new(width, height);
+
+            let (first, _, second) = self.split(Rect::new(0.0, 0.0, width, height));
+            layout_child(
+                &cache,
+                self.first.as_mut(),
+                Constraints::tight(first.width, first.height),
+            );
+            layout_child(
+                &cache,
+                self.second.as_mut(),
+                Constraints::tight(second.width, second.height),
+            );
+            size
+        })
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
+
+        let (first, divider, second) = self.split(bounds);
+        self.first.render(
+            batch,
+            Layout::new(
+                glam::Vec2::new(first.x, first.y),
+                Size::new(first.width, first.height),
+            ),
+        );
+        self.second.render(
+            batch,
+            Layout::new(
+                glam::Vec2::new(second.x, second.y),
+                Size::new(second.width, second.height),
+            ),
+        );
+
+        let color = if self.drag.is_some() || self.hovered {
+            self.style.divider_active_color
+        } else {
+            self.style.divider_color
+        };
+        batch.add_rect(divider, color, Transform::identity());
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        match event {
+            Event::MouseDown(mouse) if mouse.button == Some(MouseButton::Left) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                let bounds = self.bounds.get();
+                if !self.divider_hit_rect(bounds).contains(point) {
+                    return self.dispatch_to_panes(event);
+                }
+
+                let now = Instant::now();
+                let double_click = self
+                    .last_divider_press
+                    .is_some_and(|last| now.duration_since(last) <= DOUBLE_CLICK_TIME);
+                if double_click {
+                    self.last_divider_press = None;
+                    self.reset();
+                } else {
+                    self.last_divider_press = Some(now);
+                    let (_, divider, _) = self.split(bounds);
+                    let edge = self.main_coordinate(Point::new(divider.x, divider.y));
+                    self.drag = Some(Drag {
+                        grab_offset: self.main_coordinate(point) - edge,
+                    });
+                }
+                EventResult::Handled
+            }
+            // While dragging the divider captures the pointer: moves and the
+            // release are its own wherever they happen, so a fast drag that
+            // overshoots the divider or leaves the pane keeps tracking
+            Event::MouseMove(mouse) => {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                if let Some(drag) = self.drag {
+                    self.drag_to(self.main_coordinate(point) - drag.grab_offset);
+                    return EventResult::Handled;
+                }
+                self.hovered = self.divider_hit_rect(self.bounds.get()).contains(point);
+                self.dispatch_to_panes(event)
+            }
+            Event::MouseUp(mouse) if mouse.button == Some(MouseButton::Left) => {
+                if self.drag.take().is_some() {
+                    let point = Point::new(mouse.position.x, mouse.position.y);
+                    self.hovered = self.divider_hit_rect(self.bounds.get()).contains(point);
+                    return EventResult::Handled;
+                }
+                self.dispatch_to_panes(event)
+            }
+            _ => self.dispatch_to_panes(event),
+        }
+    }
+
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        (self.drag.is_some() || self.divider_hit_rect(self.bounds.get()).contains(point))
+            .then(|| self.resize_cursor())
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.first.as_ref(), self.second.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.first.as_mut(), self.second.as_mut()]
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        let ratio = Signal::new(self.ratio.get());
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&ratio);
+        Box::new(SplitPane {
+            id: clone_id(self.id),
+            axis: self.axis,
+            first: self.first.clone_widget(),
+            second: self.second.clone_widget(),
+            ratio,
+            ratio_bound: false,
+            default_ratio: self.default_ratio,
+            min_first: self.min_first,
+            min_second: self.min_second,
+            style: self.style.clone(),
+            bounds: Signal::new(self.bounds.get()),
+            drag: None,
+            hovered: false,
+            last_divider_press: None,
+            layout_cache,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::Container;
+    use crate::test_util::WidgetTester;
+
+    fn panes() -> SplitPane {
+        SplitPane::horizontal(Container::new(), Container::new())
+    }
+
+    #[test]
+    fn test_dragging_divider_moves_split() {
+        // 404 wide with a 4px divider leaves 400 to share, 200 each
+        let mut tester = WidgetTester::new(panes(), 404.0, 100.0);
+
+        tester.move_to(202.0, 50.0);
+        tester.press(202.0, 50.0, MouseButton::Left);
+        tester.move_to(102.0, 50.0);
+        tester.release(102.0, 50.0, MouseButton::Left);
+
+        assert!((tester.root().ratio_signal().get() - 0.25).abs() < 1e-6);
+        assert!(!tester.root().is_dragging());
+    }
+
+    #[test]
+    fn test_fast_drag_outside_pane_keeps_tracking_and_clamps() {
+        let pane = panes().min_sizes(50.0, 100.0);
+        let mut tester = WidgetTester::new(pane, 404.0, 100.0);
+
+        tester.press(202.0, 50.0, MouseButton::Left);
+        // Jumps far past the pane in one move, as a fast drag does
+        assert_eq!(tester.move_to(900.0, -300.0), EventResult::Handled);
+        assert!((tester.root().ratio_signal().get() - 0.75).abs() < 1e-6);
+
+        tester.move_to(-500.0, 50.0);
+        assert!((tester.root().ratio_signal().get() - 0.125).abs() < 1e-6);
+        assert_eq!(
+            tester.release(-500.0, 50.0, MouseButton::Left),
+            EventResult::Handled
+        );
+    }
+
+    #[test]
+    fn test_double_click_resets_to_default_ratio() {
+        let ratio = Signal::new(0.3);
+        let pane = SplitPane::vertical(Container::new(), Container::new())
+            .ratio(ratio.clone())
+            .default_ratio(0.6);
+        let mut tester = WidgetTester::new(pane, 100.0, 204.0);
+        assert_eq!(ratio.get(), 0.3);
+
+        // The divider sits at 0.3 * 200 = 60
+        tester.click(50.0, 62.0);
+        tester.click(50.0, 62.0);
+
+        assert_eq!(ratio.get(), 0.6);
+        assert_eq!(
+            tester.root().cursor_icon(Point::new(50.0, 122.0)),
+            Some(CursorIcon::ResizeNS)
+        );
+    }
+
+    #[test]
+    fn test_setting_ratio_relayouts_panes() {
+        let mut tester = WidgetTester::new(panes(), 404.0, 100.0);
+        tester.root().ratio_signal().set(0.1);
+        tester.pump();
+
+        let (first, divider, second) = tester.root().split(tester.root().bounds.get());
+        assert_eq!((first.width, divider.x, second.width), (40.0, 40.0, 360.0));
+        assert_eq!(tester.root().cursor_icon(Point::new(10.0, 50.0)), None);
+    }
+}
@@ -0,0 +1,409 @@
+//! Views of app state updated by messages
+//!
+//! [`StateView`] runs an Elm-like loop for apps that would rather keep their
+//! state in one plain value than share it through `Arc<Mutex<...>>` clones:
+//!
+//! - `view` builds the UI from the state, handing widgets a [`Dispatch`] to
+//!   send messages with,
+//! - `update` applies a message to the state,
+//! - after each event, and on each frame's update pass, queued messages are
+//!   applied in the order they were sent and the UI is built again from the
+//!   new state.
+//!
+//! ```
+//! use strato_widgets::prelude::*;
+//!
+//! #[derive(Clone)]
+//! enum Msg {
+//!     Increment,
+//!     Reset,
+//! }
+//!
+//! let counter = StateView::new(
+//!     0,
+//!     |count: &i32, dispatch: &Dispatch<Msg>| {
+//!         Column::new().children(vec![
+//!             Box::new(Text::new(format!("Count: {count}"))),
+//!             Box::new(Button::new("+").on_click(dispatch.callback(Msg::Increment))),
+//!             Box::new(Button::new("Reset").on_click(dispatch.callback(Msg::Reset))),
+//!         ])
+//!     },
+//!     |count, msg| match msg {
+//!         Msg::Increment => *count += 1,
+//!         Msg::Reset => *count = 0,
+//!     },
+//! );
+//! ```
+//!
+//! It coexists with signals: a view may bind widgets to signals as usual,
+//! and the state may hold some.
+
+use crate::reconcile::reconcile;
+use crate::widget::{
+    clone_id, dispatch_event, generate_id, layout_child, Widget, WidgetContext, WidgetId,
+};
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    reactive::{current_scope, mark_dirty, Scope},
+};
+use strato_renderer::batch::RenderBatch;
+
+type View<S, Msg> = Arc<dyn Fn(&S, &Dispatch<Msg>) -> Box<dyn Widget> + Send + Sync>;
+type Update<S, Msg> = Arc<dyn Fn(&mut S, Msg) + Send + Sync>;
+
+/// Sends messages to a [`StateView`]
+///
+/// Handles are cheap to clone and may be moved to other threads; messages
+/// sent from them are applied on the next frame.
+pub struct Dispatch<Msg> {
+    queue: Arc<Mutex<VecDeque<Msg>>>,
+}
+
+impl<Msg> Clone for Dispatch<Msg> {
+    fn clone(&self) -> Self {
+        Self {
+            queue: Arc::clone(&self.queue),
+        }
+    }
+}
+
+impl<Msg> std::fmt::Debug for Dispatch<Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Dispatch")
+            .field("pending", &self.queue.lock().len())
+            .finish()
+    }
+}
+
+impl<Msg: Send + 'static> Dispatch<Msg> {
+    fn new() -> Self {
+        Self {
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Queue `msg` for the view's `update`
+    pub fn send(&self, msg: Msg) {
+        self.queue.lock().push_back(msg);
+        mark_dirty();
+    }
+
+    /// A handler sending `msg` each time it is called, for callbacks such as
+    /// [`Button::on_click`](crate::button::Button::on_click)
+    pub fn callback(&self, msg: Msg) -> impl Fn() + Send + Sync + 'static
+    where
+        Msg: Clone + Sync,
+    {
+        let dispatch = self.clone();
+        move || dispatch.send(msg.clone())
+    }
+
+    fn take(&self) -> VecDeque<Msg> {
+        std::mem::take(&mut *self.queue.lock())
+    }
+}
+
+/// A UI built from a state value by `view` and changed by messages applied
+/// with `update`
+///
+/// The view is built again after every batch of messages, in a [`Scope`] of
+/// its own that is disposed with the previous build, so signals it derived
+/// stop following their sources. The new build is [reconciled](reconcile)
+/// with the shown view: widgets that match keep what they hold internally,
+/// like focus or hover, while widgets it replaces lose it, so keep what must
+/// survive in the state or in signals. Widgets the new build removes are
+/// unmounted and those it adds mounted in the update pass, see
+/// [`Widget::on_mount`].
+pub struct StateView<S, Msg> {
+    id: WidgetId,
+    state: S,
+    view: View<S, Msg>,
+    update: Update<S, Msg>,
+    dispatch: Dispatch<Msg>,
+    parent_scope: Option<Scope>,
+    child: Box<dyn Widget>,
+    scope: Scope,
+    /// Widgets the view dropped since the last update pass, kept until then
+    /// so they can be unmounted
+    retired: Vec<Box<dyn Widget>>,
+    layout_cache: LayoutCache,
+}
+
+impl<S: std::fmt::Debug, Msg> std::fmt::Debug for StateView<S, Msg> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateView")
+            .field("id", &self.id)
+            .field("state", &self.state)
+            .field("child", &self.child)
+            .finish()
+    }
+}
+
+impl<S, Msg> StateView<S, Msg>
+where
+    S: Send + Sync + 'static,
+    Msg: Send + 'static,
+{
+    /// Show what `view` builds from `state`, applying messages with `update`
+    pub fn new<W, V, U>(state: S, view: V, update: U) -> Self
+    where
+        W: Widget + 'static,
+        V: Fn(&S, &Dispatch<Msg>) -> W + Send + Sync + 'static,
+        U: Fn(&mut S, Msg) + Send + Sync + 'static,
+    {
+        let view: View<S, Msg> = Arc::new(move |state, dispatch| Box::new(view(state, dispatch)));
+        Self::from_parts(state, view, Arc::new(update), current_scope())
+    }
+
+    fn from_parts(
+        state: S,
+        view: View<S, Msg>,
+        update: Update<S, Msg>,
+        parent_scope: Option<Scope>,
+    ) -> Self {
+        let dispatch = Dispatch::new();
+        let scope = match &parent_scope {
+            Some(parent) => parent.child(),
+            None => Scope::new(),
+        };
+        let child = scope.run(|| view(&state, &dispatch));
+        Self {
+            id: generate_id(),
+            state,
+            view,
+            update,
+            dispatch,
+            parent_scope,
+            child,
+            scope,
+            retired: Vec::new(),
+            layout_cache: LayoutCache::new(),
+        }
+    }
+
+    /// The current state
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    /// A handle for sending messages from outside the view, e.g. from a
+    /// background task
+    pub fn dispatch(&self) -> Dispatch<Msg> {
+        self.dispatch.clone()
+    }
+
+    /// The view built from the current state
+    pub fn child(&self) -> &dyn Widget {
+        self.child.as_ref()
+    }
+
+    /// Apply queued messages, rebuilding the view if there were any
+    fn process_messages(&mut self) -> bool {
+        let mut changed = false;
+        // Updates may send more messages; they are applied in the same pass
+        loop {
+            let messages = self.dispatch.take();
+            if messages.is_empty() {
+                break;
+            }
+            for msg in messages {
+                (self.update)(&mut self.state, msg);
+            }
+            changed = true;
+        }
+        if changed {
+            self.rebuild();
+        }
+        changed
+    }
+
+    fn rebuild(&mut self) {
+        self.scope.dispose();
+        self.scope = match &self.parent_scope {
+            Some(parent) => parent.child(),
+            None => Scope::new(),
+        };
+        let (view, state, dispatch) = (&self.view, &self.state, &self.dispatch);
+        let built = self.scope.run(|| view(state, dispatch));
+        let reconciled = reconcile(&mut self.child, built);
+        self.retired.extend(reconciled.removed);
+        self.layout_cache.mark_dirty();
+        mark_dirty();
+    }
+}
+
+impl<S, Msg> Drop for StateView<S, Msg> {
+    fn drop(&mut self) {
+        self.scope.dispose();
+    }
+}
+
+impl<S, Msg> Widget for StateView<S, Msg>
+where
+    S: Clone + std::fmt::Debug + Send + Sync + 'static,
+    Msg: Send + 'static,
+{
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        let cache = self.layout_cache.clone();
+        cache.get_or_layout(constraints, || {
+            layout_child(&cache, self.child.as_mut(), constraints)
+        })
+    }
+
+    fn measure(&self, constraints: Constraints) -> Size {
+        self.child.measure(constraints)
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.child.render(batch, layout);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        let result = dispatch_event(self.child.as_mut(), event);
+        if self.process_messages() {
+            EventResult::Handled
+        } else {
+            result
+        }
+    }
+
+    fn update(&mut self, _ctx: &WidgetContext) {
+        // Unmounted by now, see `detached_children_mut`
+        self.retired.clear();
+        // Messages sent from outside an event, e.g. by a background task
+        self.process_messages();
+    }
+
+    fn detached_children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        self.retired
+            .iter_mut()
+            .map(|view| view.as_mut() as &mut dyn Widget)
+            .collect()
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        // The clone has its own state and message queue from here on
+        let mut clone = StateView::from_parts(
+            self.state.clone(),
+            Arc::clone(&self.view),
+            Arc::clone(&self.update),
+            self.parent_scope.clone(),
+        );
+        clone.id = clone_id(self.id);
+        Box::new(clone)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::layout::Column;
+    use crate::test_util::{Probe, WidgetTester};
+    use crate::text::Text;
+
+    #[derive(Clone)]
+    enum Msg {
+        Add(i32),
+        Double,
+    }
+
+    fn counter() -> StateView<i32, Msg> {
+        StateView::new(
+            1,
+            |count: &i32, dispatch: &Dispatch<Msg>| {
+                Column::new().children(vec![
+                    Box::new(Text::new(format!("Count {count}"))),
+                    Box::new(Button::new("Add").on_click(dispatch.callback(Msg::Add(1)))),
+                ])
+            },
+            |count, msg| match msg {
+                Msg::Add(n) => *count += n,
+                Msg::Double => *count *= 2,
+            },
+        )
+    }
+
+    #[test]
+    fn test_messages_update_state_and_rebuild_the_view() {
+        let mut tester = WidgetTester::new(counter(), 200.0, 200.0);
+        assert_eq!(tester.drawn_text(), vec!["Count 1", "Add"]);
+
+        let layout = tester.layout_tree();
+        let button = layout.find_by_name("Button")[0].bounds.unwrap();
+        tester.click(button.x + 5.0, button.y + 5.0);
+        assert_eq!(*tester.root().state(), 2);
+        assert_eq!(tester.drawn_text(), vec!["Count 2", "Add"]);
+
+        // Messages from outside the view are applied on the next frame, in
+        // the order they were sent
+        let dispatch = tester.root().dispatch();
+        std::thread::spawn(move || {
+            dispatch.send(Msg::Double);
+            dispatch.send(Msg::Add(1));
+        })
+        .join()
+        .unwrap();
+        tester.advance(0.016);
+        assert_eq!(*tester.root().state(), 5);
+        assert_eq!(tester.drawn_text(), vec!["Count 5", "Add"]);
+    }
+
+    #[test]
+    fn test_rebuilt_view_keeps_matching_widgets_mounted() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let probes = log.clone();
+        let view = StateView::new(
+            false,
+            move |expanded: &bool, _: &Dispatch<bool>| {
+                let mut column = Column::new().child(Box::new(Probe::new("header", &probes)));
+                if *expanded {
+                    column = column.child(Box::new(Probe::new("details", &probes)));
+                }
+                column
+            },
+            |expanded, msg| *expanded = msg,
+        );
+        let mut tester = WidgetTester::new(view, 200.0, 200.0);
+        let column = tester.root().child().id();
+        let header = tester.root().child().children()[0].id();
+
+        let dispatch = tester.root().dispatch();
+        dispatch.send(true);
+        tester.advance(0.016);
+        dispatch.send(false);
+        tester.advance(0.016);
+        tester.advance(0.016);
+
+        assert_eq!(tester.root().child().id(), column);
+        assert_eq!(tester.root().child().children()[0].id(), header);
+        assert_eq!(
+            *log.lock(),
+            ["mount header", "mount details", "unmount details"]
+        );
+    }
+}
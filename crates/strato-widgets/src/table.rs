@@ -0,0 +1,535 @@
+//! Table widget for displaying rows of tabular data
+use crate::grid::GridUnit;
+use crate::text::{measure_line_metrics, measure_text_width};
+use crate::widget::{clone_id, generate_id, register_bounds, Widget, WidgetId};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::sync::Arc;
+use strato_core::{
+    event::{Event, EventResult, MouseButton},
+    layout::{Constraints, Layout, Size},
+    state::Signal,
+    text::TextAlign,
+    types::{Color, Point, Rect, Transform},
+};
+use strato_renderer::batch::RenderBatch;
+
+/// Direction a column is sorted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    /// The opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+}
+
+/// Column definition for a [`Table`]
+#[derive(Debug, Clone)]
+pub struct TableColumn {
+    pub header: String,
+    /// Width of the column; `Auto` fits the widest cell
+    pub width: GridUnit,
+    pub align: TextAlign,
+    pub sortable: bool,
+}
+
+impl TableColumn {
+    /// Create an auto-sized, left-aligned, sortable column
+    pub fn new(header: impl Into<String>) -> Self {
+        Self {
+            header: header.into(),
+            width: GridUnit::Auto,
+            align: TextAlign::Left,
+            sortable: true,
+        }
+    }
+
+    /// Set the column width
+    pub fn width(mut self, width: GridUnit) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Set cell alignment
+    pub fn align(mut self, align: TextAlign) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Enable or disable sorting by this column
+    pub fn sortable(mut self, sortable: bool) -> Self {
+        self.sortable = sortable;
+        self
+    }
+}
+
+/// Table styling
+#[derive(Debug, Clone)]
+pub struct TableStyle {
+    pub header_background: Color,
+    pub header_text_color: Color,
+    pub row_background: Color,
+    pub stripe_background: Color,
+    pub text_color: Color,
+    pub divider_color: Color,
+    pub font_size: f32,
+    pub header_height: f32,
+    pub row_height: f32,
+    pub cell_padding: f32,
+}
+
+impl Default for TableStyle {
+    fn default() -> Self {
+        Self {
+            header_background: Color::rgb(0.93, 0.93, 0.95),
+            header_text_color: Color::rgb(0.2, 0.2, 0.2),
+            row_background: Color::WHITE,
+            stripe_background: Color::rgb(0.97, 0.97, 0.98),
+            text_color: Color::rgb(0.13, 0.13, 0.13),
+            divider_color: Color::rgb(0.85, 0.85, 0.87),
+            font_size: 14.0,
+            header_height: 32.0,
+            row_height: 28.0,
+            cell_padding: 8.0,
+        }
+    }
+}
+
+/// Table widget with a header row, sortable columns and zebra striping
+pub struct Table {
+    id: WidgetId,
+    columns: Vec<TableColumn>,
+    rows: Vec<Vec<String>>,
+    style: TableStyle,
+    striped: bool,
+    sort: Option<(usize, SortDirection)>,
+    on_sort: Option<Arc<dyn Fn(usize, SortDirection) + Send + Sync>>,
+    // Column widths resolved during layout()
+    column_widths: Vec<f32>,
+    bounds: Signal<Rect>,
+}
+
+impl std::fmt::Debug for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Table")
+            .field("id", &self.id)
+            .field("columns", &self.columns)
+            .field("rows", &self.rows.len())
+            .field("style", &self.style)
+            .field("striped", &self.striped)
+            .field("sort", &self.sort)
+            .field(
+                "on_sort",
+                &self
+                    .on_sort
+                    .as_ref()
+                    .map(|_| "Fn(usize, SortDirection) + Send + Sync"),
+            )
+            .finish()
+    }
+}
+
+impl Table {
+    /// Create a table with the given columns
+    pub fn new(columns: Vec<TableColumn>) -> Self {
+        Self {
+            id: generate_id(),
+            columns,
+            rows: Vec::new(),
+            style: TableStyle::default(),
+            striped: true,
+            sort: None,
+            on_sort: None,
+            column_widths: Vec::new(),
+            bounds: Signal::new(Rect::default()),
+        }
+    }
+
+    /// Set the row data; each row holds one cell per column
+    pub fn rows(mut self, rows: Vec<Vec<String>>) -> Self {
+        self.rows = rows;
+        self.apply_sort();
+        self
+    }
+
+    /// Add a single row
+    pub fn row<S: Into<String>>(mut self, cells: impl IntoIterator<Item = S>) -> Self {
+        self.rows.push(cells.into_iter().map(Into::into).collect());
+        self.apply_sort();
+        self
+    }
+
+    /// Set the style
+    pub fn style(mut self, style: TableStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Enable or disable zebra striping
+    pub fn striped(mut self, striped: bool) -> Self {
+        self.striped = striped;
+        self
+    }
+
+    /// Set the handler called when a header click changes the sort order
+    pub fn on_sort<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(usize, SortDirection) + Send + Sync + 'static,
+    {
+        self.on_sort = Some(Arc::new(handler));
+        self
+    }
+
+    /// Get the rows in display order
+    pub fn data(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Get the current sort column and direction
+    pub fn sort_state(&self) -> Option<(usize, SortDirection)> {
+        self.sort
+    }
+
+    /// Sort rows by a column
+    pub fn sort_by(&mut self, column: usize, direction: SortDirection) {
+        if column < self.columns.len() {
+            self.sort = Some((column, direction));
+            self.apply_sort();
+        }
+    }
+
+    /// Get the column widths resolved by the last layout
+    pub fn column_widths(&self) -> &[f32] {
+        &self.column_widths
+    }
+
+    fn apply_sort(&mut self) {
+        let Some((column, direction)) = self.sort else {
+            return;
+        };
+        self.rows.sort_by(|a, b| {
+            let ordering = compare_cells(
+                a.get(column).map_or("", String::as_str),
+                b.get(column).map_or("", String::as_str),
+            );
+            match direction {
+                SortDirection::Ascending => ordering,
+                SortDirection::Descending => ordering.reverse(),
+            }
+        });
+    }
+
+    /// Width of the widest header or cell in a column, including padding
+    fn content_width(&self, column: usize) -> f32 {
+        let style = &self.style;
+        // Leave room for the sort indicator in the header.
+        let header = measure_text_width(&self.columns[column].header, style.font_size, 0.0)
+            + style.font_size;
+        let widest_cell = self
+            .rows
+            .iter()
+            .filter_map(|row| row.get(column))
+            .map(|cell| measure_text_width(cell, style.font_size, 0.0))
+            .fold(0.0, f32::max);
+        header.max(widest_cell) + style.cell_padding * 2.0
+    }
+
+    fn resolve_column_widths(&self, available_width: f32) -> Vec<f32> {
+        let mut widths: Vec<f32> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(index, column)| match column.width {
+                GridUnit::Pixel(px) => px,
+                GridUnit::Auto => self.content_width(index),
+                GridUnit::Fraction(_) => 0.0,
+            })
+            .collect();
+
+        let total_fr: f32 = self
+            .columns
+            .iter()
+            .filter_map(|column| match column.width {
+                GridUnit::Fraction(fr) => Some(fr),
+                _ => None,
+            })
+            .sum();
+        if total_fr > 0.0 {
+            let used: f32 = widths.iter().sum();
+            for (index, column) in self.columns.iter().enumerate() {
+                if let GridUnit::Fraction(fr) = column.width {
+                    // Without a width to share, flexible columns fit their content.
+                    widths[index] = if available_width.is_finite() {
+                        (available_width - used).max(0.0) * fr / total_fr
+                    } else {
+                        self.content_width(index)
+                    };
+                }
+            }
+        }
+        widths
+    }
+
+    fn header_rects(&self, bounds: Rect) -> Vec<Rect> {
+        let mut x = bounds.x;
+        self.column_widths
+            .iter()
+            .map(|&width| {
+                let rect = Rect::new(x, bounds.y, width, self.style.header_height);
+                x += width;
+                rect
+            })
+            .collect()
+    }
+
+    fn render_cell(
+        &self,
+        batch: &mut RenderBatch,
+        text: String,
+        rect: Rect,
+        align: TextAlign,
+        color: Color,
+    ) {
+        let padding = self.style.cell_padding;
+        let x = match align {
+            TextAlign::Center => rect.x + rect.width / 2.0,
+            TextAlign::Right | TextAlign::End => rect.x + rect.width - padding,
+            _ => rect.x + padding,
+        };
+        let y = measure_line_metrics(self.style.font_size).centered_top(rect.y, rect.height);
+        batch.add_text_aligned(text, (x, y), color, self.style.font_size, 0.0, align);
+    }
+}
+
+/// Compare two cells numerically when both parse as numbers, otherwise as text
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        _ => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+impl Widget for Table {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.column_widths = self.resolve_column_widths(constraints.max_width);
+        let width: f32 = self.column_widths.iter().sum();
+        let height = self.style.header_height + self.style.row_height * self.rows.len() as f32;
+        constraints.constrain(Size::new(width, height))
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
+        let style = &self.style;
+
+        // Header
+        batch.add_rect(
+            Rect::new(bounds.x, bounds.y, bounds.width, style.header_height),
+            style.header_background,
+            Transform::identity(),
+        );
+        for (index, (column, rect)) in self
+            .columns
+            .iter()
+            .zip(self.header_rects(bounds))
+            .enumerate()
+        {
+            let mut label = column.header.clone();
+            match self.sort {
+                Some((sorted, SortDirection::Ascending)) if sorted == index => label.push_str(" ▲"),
+                Some((sorted, SortDirection::Descending)) if sorted == index => {
+                    label.push_str(" ▼")
+                }
+                _ => {}
+            }
+            self.render_cell(batch, label, rect, column.align, style.header_text_color);
+        }
+
+        // Body
+        let mut y = bounds.y + style.header_height;
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let background = if self.striped && row_index % 2 == 1 {
+                style.stripe_background
+            } else {
+                style.row_background
+            };
+            batch.add_rect(
+                Rect::new(bounds.x, y, bounds.width, style.row_height),
+                background,
+                Transform::identity(),
+            );
+
+            let mut x = bounds.x;
+            for (column_index, (column, &width)) in
+                self.columns.iter().zip(&self.column_widths).enumerate()
+            {
+                if let Some(cell) = row.get(column_index) {
+                    let rect = Rect::new(x, y, width, style.row_height);
+                    self.render_cell(batch, cell.clone(), rect, column.align, style.text_color);
+                }
+                x += width;
+            }
+
+            y += style.row_height;
+            batch.add_rect(
+                Rect::new(bounds.x, y - 1.0, bounds.width, 1.0),
+                style.divider_color,
+                Transform::identity(),
+            );
+        }
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::MouseDown(mouse) = event {
+            if mouse.button != Some(MouseButton::Left) {
+                return EventResult::Ignored;
+            }
+            let point = Point::new(mouse.position.x, mouse.position.y);
+            let clicked = self
+                .header_rects(self.bounds.get())
+                .iter()
+                .position(|rect| rect.contains(point));
+
+            if let Some(column) = clicked {
+                if !self.columns[column].sortable {
+                    return EventResult::Handled;
+                }
+                let direction = match self.sort {
+                    Some((sorted, direction)) if sorted == column => direction.reversed(),
+                    _ => SortDirection::Ascending,
+                };
+                self.sort_by(column, direction);
+                if let Some(handler) = &self.on_sort {
+                    handler(column, direction);
+                }
+                return EventResult::Handled;
+            }
+        }
+        EventResult::Ignored
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(Table {
+            id: clone_id(self.id),
+            columns: self.columns.clone(),
+            rows: self.rows.clone(),
+            style: self.style.clone(),
+            striped: self.striped,
+            sort: self.sort,
+            on_sort: self.on_sort.clone(),
+            column_widths: Vec::new(),
+            bounds: Signal::new(Rect::default()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+    use std::sync::{Arc, Mutex};
+    use strato_core::event::{Modifiers, MouseEvent};
+
+    fn sample() -> Table {
+        Table::new(vec![
+            TableColumn::new("Name").width(GridUnit::Fraction(1.0)),
+            TableColumn::new("Qty")
+                .width(GridUnit::Pixel(80.0))
+                .align(TextAlign::Right),
+            TableColumn::new("Notes").sortable(false),
+        ])
+        .row(["Pears", "12", "ripe"])
+        .row(["apples", "3", ""])
+        .row(["Bananas", "100", "green"])
+    }
+
+    fn click(x: f32, y: f32) -> Event {
+        Event::MouseDown(MouseEvent {
+            position: Vec2::new(x, y),
+            button: Some(MouseButton::Left),
+            modifiers: Modifiers::default(),
+            delta: Vec2::ZERO,
+        })
+    }
+
+    #[test]
+    fn test_column_widths() {
+        let mut table = sample();
+        let size = table.layout(Constraints::loose(600.0, 400.0));
+
+        let widths = table.column_widths().to_vec();
+        assert_eq!(widths[1], 80.0);
+        assert!(widths[2] > 0.0);
+        // The flex column takes up the remaining width
+        assert!((widths.iter().sum::<f32>() - 600.0).abs() < 0.01);
+        assert_eq!(size.height, 32.0 + 28.0 * 3.0);
+    }
+
+    #[test]
+    fn test_sort_numeric_and_text() {
+        let mut table = sample();
+        table.sort_by(1, SortDirection::Ascending);
+        let qty: Vec<&str> = table.data().iter().map(|r| r[1].as_str()).collect();
+        assert_eq!(qty, ["3", "12", "100"]);
+
+        table.sort_by(0, SortDirection::Descending);
+        let names: Vec<&str> = table.data().iter().map(|r| r[0].as_str()).collect();
+        assert_eq!(names, ["Pears", "Bananas", "apples"]);
+    }
+
+    #[test]
+    fn test_header_click_toggles_sort() {
+        let sorts = Arc::new(Mutex::new(Vec::new()));
+        let recorded = sorts.clone();
+        let mut table = sample().on_sort(move |column, direction| {
+            recorded.lock().unwrap().push((column, direction));
+        });
+        let size = table.layout(Constraints::loose(600.0, 400.0));
+        table.render(&mut RenderBatch::new(), Layout::new(Vec2::ZERO, size));
+
+        table.handle_event(&click(10.0, 10.0));
+        table.handle_event(&click(10.0, 10.0));
+        assert_eq!(
+            *sorts.lock().unwrap(),
+            [
+                (0, SortDirection::Ascending),
+                (0, SortDirection::Descending)
+            ]
+        );
+
+        // Clicking a non-sortable header does nothing
+        table.handle_event(&click(595.0, 10.0));
+        assert_eq!(sorts.lock().unwrap().len(), 2);
+        assert_eq!(table.sort_state(), Some((0, SortDirection::Descending)));
+    }
+}
@@ -0,0 +1,485 @@
+//! Harness for testing widgets without a window
+//!
+//! [`WidgetTester`] drives a widget tree the way the application does: it
+//! lays the root out at a fixed size and renders it, which is where widgets
+//! record the bounds they hit test against, then delivers synthesized events
+//! and clock ticks, rendering a new frame after each one.
+//!
+//! ```
+//! use strato_core::state::Signal;
+//! use strato_widgets::{test_util::WidgetTester, Button};
+//!
+//! let count = Signal::new(0);
+//! let counter = count.clone();
+//! let button = Button::new("+").on_click(move || counter.update(|n| *n += 1));
+//!
+//! let mut tester = WidgetTester::new(button, 100.0, 40.0);
+//! tester.click(50.0, 20.0);
+//! assert_eq!(count.get(), 1);
+//! ```
+
+use crate::focus::handle_tab;
+use crate::theme::Theme;
+use crate::widget::{
+    assign_path_ids, dispatch_root_event, id_mode, layout_tree, mount_tree, update_tree, IdMode,
+    MountedWidgets, Widget, WidgetContext, WidgetState,
+};
+use glam::Vec2;
+use strato_core::{
+    event::{Event, EventResult, KeyCode, KeyboardEvent, Modifiers, MouseButton, MouseEvent},
+    hit_test::LayoutTree,
+    input::InputState,
+    layout::{Constraints, Layout, Size},
+    types::Point,
+};
+use strato_renderer::batch::{DrawCommand, RenderBatch};
+
+/// Lays out, renders and feeds events to a widget tree in tests
+pub struct WidgetTester<W: Widget> {
+    root: W,
+    viewport: Size,
+    size: Size,
+    theme: Theme,
+    frame: RenderBatch,
+    pointer: Point,
+    modifiers: Modifiers,
+    input: InputState,
+    elapsed: f32,
+    mounted: MountedWidgets,
+}
+
+impl<W: Widget> WidgetTester<W> {
+    /// Lay `root` out in a `width` x `height` viewport, render a frame and
+    /// mount the tree
+    pub fn new(root: W, width: f32, height: f32) -> Self {
+        let mut tester = Self {
+            root,
+            viewport: Size::new(width, height),
+            size: Size::zero(),
+            theme: Theme::default(),
+            frame: RenderBatch::new(),
+            pointer: Point::new(0.0, 0.0),
+            modifiers: Modifiers::default(),
+            input: InputState::new(),
+            elapsed: 0.0,
+            mounted: MountedWidgets::new(),
+        };
+        tester.pump();
+        let ctx = WidgetContext {
+            theme: &tester.theme,
+            state: WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time: 0.0,
+            input: tester.input,
+        };
+        mount_tree(&mut tester.root, &ctx, &mut tester.mounted);
+        tester
+    }
+
+    /// Use `theme` for later updates
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.theme = theme;
+        self
+    }
+
+    /// The root widget
+    pub fn root(&self) -> &W {
+        &self.root
+    }
+
+    /// The root widget, mutably; call [`pump`](Self::pump) after changing it
+    pub fn root_mut(&mut self) -> &mut W {
+        &mut self.root
+    }
+
+    /// The size the root was last laid out at
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Resize the viewport and render a frame
+    pub fn resize(&mut self, width: f32, height: f32) {
+        self.viewport = Size::new(width, height);
+        self.pump();
+    }
+
+    /// Lay the tree out and render a frame, as the application does each frame
+    pub fn pump(&mut self) {
+        if id_mode() == IdMode::TreePath {
+            assign_path_ids(&mut self.root);
+        }
+        let constraints = Constraints::tight(self.viewport.width, self.viewport.height);
+        self.size = self.root.layout(constraints);
+
+        self.frame = RenderBatch::new();
+        self.root
+            .render(&mut self.frame, Layout::new(Vec2::ZERO, self.size));
+    }
+
+    /// Deliver `event` to the root, then render a frame
+    ///
+    /// Open overlays see pointer events first and a Tab the tree leaves
+    /// unhandled moves focus, as in the application.
+    pub fn dispatch(&mut self, event: Event) -> EventResult {
+        self.input.apply(&event);
+        let result = match dispatch_root_event(&mut self.root, &event) {
+            EventResult::Ignored => handle_tab(&mut self.root, &event),
+            handled => handled,
+        };
+        self.pump();
+        result
+    }
+
+    /// Hold these modifiers for later pointer and key events
+    pub fn set_modifiers(&mut self, modifiers: Modifiers) {
+        self.modifiers = modifiers;
+    }
+
+    /// Move the pointer to (`x`, `y`)
+    pub fn move_to(&mut self, x: f32, y: f32) -> EventResult {
+        let delta = Vec2::new(x - self.pointer.x, y - self.pointer.y);
+        self.pointer = Point::new(x, y);
+        let event = self.mouse_event(None, delta);
+        self.dispatch(Event::MouseMove(event))
+    }
+
+    /// Press `button` at (`x`, `y`)
+    pub fn press(&mut self, x: f32, y: f32, button: MouseButton) -> EventResult {
+        self.pointer = Point::new(x, y);
+        let event = self.mouse_event(Some(button), Vec2::ZERO);
+        self.dispatch(Event::MouseDown(event))
+    }
+
+    /// Release `button` at (`x`, `y`)
+    pub fn release(&mut self, x: f32, y: f32, button: MouseButton) -> EventResult {
+        self.pointer = Point::new(x, y);
+        let event = self.mouse_event(Some(button), Vec2::ZERO);
+        self.dispatch(Event::MouseUp(event))
+    }
+
+    /// Move to (`x`, `y`), then press and release the left button there
+    pub fn click(&mut self, x: f32, y: f32) {
+        self.move_to(x, y);
+        self.press(x, y, MouseButton::Left);
+        self.release(x, y, MouseButton::Left);
+    }
+
+    /// Scroll the wheel by (`dx`, `dy`) logical pixels
+    pub fn scroll(&mut self, dx: f32, dy: f32) -> EventResult {
+        self.dispatch(Event::MouseWheel {
+            delta: Vec2::new(dx, dy),
+            modifiers: self.modifiers,
+            precise: false,
+        })
+    }
+
+    /// Scroll by (`dx`, `dy`) logical pixels on a trackpad
+    pub fn scroll_precise(&mut self, dx: f32, dy: f32) -> EventResult {
+        self.dispatch(Event::MouseWheel {
+            delta: Vec2::new(dx, dy),
+            modifiers: self.modifiers,
+            precise: true,
+        })
+    }
+
+    /// Press `key`
+    pub fn key_down(&mut self, key_code: KeyCode) -> EventResult {
+        let event = self.keyboard_event(key_code);
+        self.dispatch(Event::KeyDown(event))
+    }
+
+    /// Release `key`
+    pub fn key_up(&mut self, key_code: KeyCode) -> EventResult {
+        let event = self.keyboard_event(key_code);
+        self.dispatch(Event::KeyUp(event))
+    }
+
+    /// Press and release `key`
+    pub fn press_key(&mut self, key_code: KeyCode) {
+        self.key_down(key_code);
+        self.key_up(key_code);
+    }
+
+    /// Enter `text` as the platform's text input would
+    pub fn type_text(&mut self, text: &str) -> EventResult {
+        self.dispatch(Event::TextInput(text.to_string()))
+    }
+
+    /// Advance the clock by `delta_time` seconds, updating every widget
+    ///
+    /// Widgets added or removed since the last update, e.g. by a
+    /// [`Show`](crate::show::Show), are mounted or unmounted along the way.
+    pub fn advance(&mut self, delta_time: f32) {
+        self.elapsed += delta_time;
+        let ctx = WidgetContext {
+            theme: &self.theme,
+            state: WidgetState::Normal,
+            is_focused: false,
+            is_hovered: false,
+            delta_time,
+            input: self.input,
+        };
+        update_tree(&mut self.root, &ctx, &mut self.mounted);
+        self.pump();
+    }
+
+    /// Advance the clock in `steps` ticks of `delta_time` seconds each
+    pub fn advance_by(&mut self, delta_time: f32, steps: usize) {
+        for _ in 0..steps {
+            self.advance(delta_time);
+        }
+    }
+
+    /// Seconds the clock has been advanced by
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    /// The draw commands of the last frame
+    pub fn frame(&self) -> &RenderBatch {
+        &self.frame
+    }
+
+    /// Every string drawn in the last frame, in draw order, including the
+    /// content of layers
+    pub fn drawn_text(&self) -> Vec<&str> {
+        fn collect<'a>(commands: &'a [DrawCommand], texts: &mut Vec<&'a str>) {
+            for command in commands {
+                match command {
+                    DrawCommand::Text { text, .. } => texts.push(text),
+                    DrawCommand::Layer { content, .. } => collect(&content.batch().commands, texts),
+                    _ => {}
+                }
+            }
+        }
+        let mut texts = Vec::new();
+        collect(&self.frame.commands, &mut texts);
+        texts
+    }
+
+    /// Where every widget was drawn in the last frame
+    pub fn layout_tree(&self) -> LayoutTree {
+        layout_tree(&self.root)
+    }
+
+    /// The first widget of type `T` in the tree matching `predicate`
+    ///
+    /// Searches depth first, the root included.
+    pub fn find<T: Widget + 'static>(&self, predicate: impl Fn(&T) -> bool) -> Option<&T> {
+        find_in(&self.root, &predicate)
+    }
+
+    fn mouse_event(&self, button: Option<MouseButton>, delta: Vec2) -> MouseEvent {
+        MouseEvent {
+            position: Vec2::new(self.pointer.x, self.pointer.y),
+            button,
+            modifiers: self.modifiers,
+            delta,
+        }
+    }
+
+    fn keyboard_event(&self, key_code: KeyCode) -> KeyboardEvent {
+        KeyboardEvent {
+            key_code,
+            modifiers: self.modifiers,
+            is_repeat: false,
+            text: None,
+        }
+    }
+}
+
+fn find_in<'a, T: Widget + 'static>(
+    widget: &'a (dyn Widget + '_),
+    predicate: &impl Fn(&T) -> bool,
+) -> Option<&'a T> {
+    if let Some(found) = widget.as_any().downcast_ref::<T>() {
+        if predicate(found) {
+            return Some(found);
+        }
+    }
+    widget
+        .children()
+        .into_iter()
+        .find_map(|child| find_in(child, predicate))
+}
+
+/// Leaf logging when it is mounted, unmounted or handed an event, for
+/// lifecycle and dispatch tests
+#[cfg(test)]
+#[derive(Debug, Clone)]
+pub(crate) struct Probe {
+    id: crate::widget::WidgetId,
+    name: &'static str,
+    enabled: bool,
+    log: std::sync::Arc<parking_lot::Mutex<Vec<String>>>,
+}
+
+#[cfg(test)]
+impl Probe {
+    pub(crate) fn new(
+        name: &'static str,
+        log: &std::sync::Arc<parking_lot::Mutex<Vec<String>>>,
+    ) -> Self {
+        Self {
+            id: crate::widget::generate_id(),
+            name,
+            enabled: true,
+            log: log.clone(),
+        }
+    }
+
+    pub(crate) fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+}
+
+#[cfg(test)]
+impl Widget for Probe {
+    fn id(&self) -> crate::widget::WidgetId {
+        self.id
+    }
+
+    fn layout(&mut self, _constraints: Constraints) -> Size {
+        Size::new(10.0, 10.0)
+    }
+
+    fn render(&self, _batch: &mut RenderBatch, _layout: Layout) {}
+
+    fn on_mount(&mut self, _ctx: &WidgetContext) {
+        self.log.lock().push(format!("mount {}", self.name));
+    }
+
+    fn on_unmount(&mut self, _ctx: &WidgetContext) {
+        self.log.lock().push(format!("unmount {}", self.name));
+    }
+
+    fn handle_event(&mut self, _event: &Event) -> EventResult {
+        self.log.lock().push(format!("event {}", self.name));
+        EventResult::Ignored
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        // A probe stands in for one of the same name
+        let new = new.as_any().downcast_ref::<Probe>()?;
+        (new.name == self.name).then_some(false)
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(self.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::input::TextInput;
+    use crate::layout::Row;
+    use crate::text::Text;
+    use strato_core::state::Signal;
+
+    fn counter() -> (Row, Signal<i32>) {
+        let count = Signal::new(0);
+        let (decrement, increment) = (count.clone(), count.clone());
+        let row = Row::new().children(vec![
+            Box::new(
+                Button::new("-")
+                    .size(60.0, 40.0)
+                    .on_click(move || decrement.update(|n| *n -= 1)),
+            ),
+            Box::new(
+                Button::new("+")
+                    .size(60.0, 40.0)
+                    .on_click(move || increment.update(|n| *n += 1)),
+            ),
+            Box::new(Text::new("0").bind(count.map(|n| n.to_string()))),
+        ]);
+        (row, count)
+    }
+
+    #[test]
+    fn test_clicking_plus_increments_the_counter() {
+        let (row, count) = counter();
+        let mut tester = WidgetTester::new(row, 200.0, 40.0);
+
+        tester.click(90.0, 20.0);
+        tester.click(90.0, 20.0);
+        tester.click(30.0, 20.0);
+
+        assert_eq!(count.get(), 1);
+        assert!(tester.drawn_text().contains(&"1"));
+    }
+
+    #[test]
+    fn test_click_outside_buttons_does_nothing() {
+        let (row, count) = counter();
+        let mut tester = WidgetTester::new(row, 200.0, 40.0);
+
+        tester.click(190.0, 5.0);
+
+        assert_eq!(count.get(), 0);
+    }
+
+    #[test]
+    fn test_keyboard_and_text_input_reach_focused_input() {
+        let mut tester = WidgetTester::new(TextInput::new(), 200.0, 32.0);
+
+        tester.click(10.0, 16.0);
+        tester.type_text("hello");
+        tester.press_key(KeyCode::Backspace);
+
+        assert_eq!(tester.root().get_value(), "hell");
+    }
+
+    #[test]
+    fn test_clicked_input_is_the_focused_widget() {
+        let row = Row::new().children(vec![
+            Box::new(Button::new("Send").size(60.0, 32.0)),
+            Box::new(TextInput::new()),
+        ]);
+        let mut tester = WidgetTester::new(row, 200.0, 32.0);
+        let input = tester.find(|_: &TextInput| true).unwrap().id();
+        assert_eq!(crate::widget::focused_widget(tester.root()), None);
+
+        tester.click(100.0, 16.0);
+        assert_eq!(crate::widget::focused_widget(tester.root()), Some(input));
+    }
+
+    #[test]
+    fn test_find_and_advance() {
+        let (row, _) = counter();
+        let mut tester = WidgetTester::new(row, 200.0, 40.0);
+
+        let labelled = |label: &'static str| move |button: &Button| button.text() == label;
+        assert!(tester.find(labelled("+")).is_some());
+        assert!(tester.find(labelled("*")).is_none());
+
+        tester.advance_by(0.5, 4);
+        assert_eq!(tester.elapsed(), 2.0);
+    }
+
+    #[test]
+    fn test_layout_tree_reports_where_widgets_were_drawn() {
+        let (row, _) = counter();
+        let tester = WidgetTester::new(row, 200.0, 40.0);
+        let labelled = |label: &'static str| move |button: &Button| button.text() == label;
+        let minus = tester.find(labelled("-")).unwrap().id();
+        let plus = tester.find(labelled("+")).unwrap().id();
+
+        let layout = tester.layout_tree();
+        assert_eq!(layout.root.name, "Row");
+        assert_eq!(layout.find_by_name("Button").len(), 2);
+        assert_eq!(layout.find_by_name("Text").len(), 1);
+
+        // "+" is to the right of "-"
+        let minus = layout.bounds(strato_core::widget::WidgetId(minus)).unwrap();
+        let plus = layout.bounds(strato_core::widget::WidgetId(plus)).unwrap();
+        assert!(plus.x >= minus.x + minus.width);
+    }
+}
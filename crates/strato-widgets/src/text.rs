@@ -1,18 +1,42 @@
 //! Text widget implementation
 //!
 //! Provides text display components with various styles, formatting, and layout options.
-
-use crate::widget::{generate_id, Widget, WidgetId};
-use std::{any::Any, sync::Arc, sync::OnceLock};
+//!
+//! # Links
+//!
+//! A [`TextSpan`] with a [`link`](TextSpan::link) or
+//! [`on_click`](TextSpan::on_click) is drawn in the link style and reacts to
+//! clicks on the words it covers. URLs are handed to the opener installed with
+//! [`set_url_opener`], which the platform layer points at the system browser.
+//!
+//! # Metrics
+//!
+//! [`measure_line_metrics`] gives the ascent, descent and line gap of the
+//! font text is drawn with, for custom widgets that center labels or line up
+//! baselines. [`install_line_metrics`] makes
+//! [`strato_core::text::line_metrics`] report the same numbers.
+
+use crate::clipboard::{clipboard, Clipboard};
+use crate::shortcut::Shortcut;
+use crate::toast::ToastManager;
+use crate::widget::{clone_id, generate_id, register_bounds, Widget, WidgetId};
+use parking_lot::RwLock;
+use std::time::Duration;
+use std::{any::Any, ops::Range, sync::Arc, sync::OnceLock};
 use strato_core::{
-    event::{Event, EventResult},
-    layout::{Constraints, Layout, Size},
-    state::Signal,
+    event::{Event, EventResult, KeyCode, MouseButton},
+    i18n::{message_args, Localizer, MessageArg},
+    layout::{Constraints, Layout, LayoutCache, Size},
+    state::{Signal, Subscription},
+    text::{set_line_metrics_provider, LineMetrics},
     theme::Theme,
-    types::{Color, Point, Rect},
+    types::{BorderRadius, Color, LinearGradient, Point, Rect, Transform},
+    window::CursorIcon,
 };
 use strato_renderer::{
-    batch::RenderBatch, gpu::texture_mgr::GlyphRasterizer, vertex::VertexBuilder,
+    batch::{RenderBatch, TextFill},
+    gpu::texture_mgr::GlyphRasterizer,
+    vertex::VertexBuilder,
 };
 
 // Helper for text measurement
@@ -49,6 +73,48 @@ fn measure_line_width(line: &str, font_size: f32, letter_spacing: f32) -> f32 {
     width
 }
 
+type UrlOpener = Arc<dyn Fn(&str) + Send + Sync>;
+
+static URL_OPENER: OnceLock<RwLock<Option<UrlOpener>>> = OnceLock::new();
+
+fn url_opener() -> &'static RwLock<Option<UrlOpener>> {
+    URL_OPENER.get_or_init(|| RwLock::new(None))
+}
+
+/// Install how clicked URL links are opened, e.g. in the system browser
+pub fn set_url_opener(opener: impl Fn(&str) + Send + Sync + 'static) {
+    *url_opener().write() = Some(Arc::new(opener));
+}
+
+/// Open `url` with the installed opener
+///
+/// Without one the URL is only logged.
+pub fn open_url(url: &str) {
+    let opener = url_opener().read().clone();
+    match opener {
+        Some(opener) => opener(url),
+        None => tracing::warn!("No URL opener installed, not opening {}", url),
+    }
+}
+
+/// A word of a wrapped line and where it came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct WordPos {
+    /// Byte offset in the line
+    line: usize,
+    /// Byte offset in the content
+    content: usize,
+    len: usize,
+}
+
+/// The words of `content` with their byte offsets
+fn words_with_offsets(content: &str) -> impl Iterator<Item = (usize, &str)> {
+    let base = content.as_ptr() as usize;
+    content
+        .split_whitespace()
+        .map(move |word| (word.as_ptr() as usize - base, word))
+}
+
 /// Text alignment options
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TextAlign {
@@ -58,15 +124,46 @@ pub enum TextAlign {
     Justify,
 }
 
-/// Text vertical alignment options
+/// Where text sits vertically in a layout box taller than its lines
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VerticalAlign {
+    /// First line at the top of the box
     Top,
+    /// Glyphs centered between the box's top and bottom edges
     Middle,
+    /// Descenders of the last line on the bottom edge
     Bottom,
+    /// Baseline of the last line on the bottom edge, descenders below it
+    ///
+    /// Labels of different sizes given the same height share a baseline.
     Baseline,
 }
 
+/// Vertical metrics of the font text is drawn with, at `font_size`
+///
+/// Every font family is drawn with the same embedded font for now, so only
+/// the size matters. [`strato_core::text::line_metrics`] takes a font too and
+/// asks whatever provider is installed, see [`install_line_metrics`].
+pub fn measure_line_metrics(font_size: f32) -> LineMetrics {
+    get_rasterizer()
+        .font
+        .horizontal_line_metrics(font_size)
+        .map(|metrics| LineMetrics {
+            ascent: metrics.ascent,
+            descent: -metrics.descent,
+            line_gap: metrics.line_gap,
+            line_height: metrics.new_line_size,
+        })
+        .unwrap_or_else(|| LineMetrics::approximate(font_size))
+}
+
+/// Make [`strato_core::text::line_metrics`] report [`measure_line_metrics`]
+///
+/// The platform layer does this when an application is created.
+pub fn install_line_metrics() {
+    set_line_metrics_provider(|_, size| Some(measure_line_metrics(size)));
+}
+
 /// Text overflow behavior
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TextOverflow {
@@ -124,13 +221,15 @@ pub enum FontStyle {
 }
 
 /// Text style configuration
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TextStyle {
     pub font_family: String,
     pub font_size: f32,
     pub font_weight: FontWeight,
     pub font_style: FontStyle,
     pub color: Color,
+    /// Gradient painted across the block of text instead of `color`
+    pub gradient: Option<LinearGradient>,
     pub line_height: f32,
     pub letter_spacing: f32,
     pub word_spacing: f32,
@@ -164,6 +263,7 @@ impl Default for TextStyle {
             font_weight: FontWeight::Normal,
             font_style: FontStyle::Normal,
             color: Color::rgba(0.0, 0.0, 0.0, 1.0), // Black text for better readability
+            gradient: None,
             line_height: 1.4,
             letter_spacing: 0.0,
             word_spacing: 0.0,
@@ -240,13 +340,48 @@ impl TextStyle {
     }
 }
 
+/// What clicking a link span does
+#[derive(Clone)]
+pub enum TextLink {
+    /// Open a URL, see [`open_url`]
+    Url(String),
+    /// Run a callback
+    Action(Arc<dyn Fn() + Send + Sync>),
+}
+
+impl TextLink {
+    /// Follow the link
+    pub fn activate(&self) {
+        match self {
+            TextLink::Url(url) => open_url(url),
+            TextLink::Action(action) => action(),
+        }
+    }
+}
+
+impl std::fmt::Debug for TextLink {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextLink::Url(url) => f.debug_tuple("Url").field(url).finish(),
+            TextLink::Action(_) => f.write_str("Action"),
+        }
+    }
+}
+
 /// Text span for rich text formatting
+///
+/// A span restyles the bytes `start..end` of its text widget's content; see
+/// [`Text::rich`] for building the content out of spans. Spans change the
+/// color and decoration of what they cover, the font size stays the widget's.
 #[derive(Debug, Clone)]
 pub struct TextSpan {
     pub text: String,
     pub style: Option<TextStyle>,
     pub start: usize,
     pub end: usize,
+    /// Followed when the span is clicked; drawn in [`TextStyle::link`] unless
+    /// the span has a style
+    pub link: Option<TextLink>,
 }
 
 impl TextSpan {
@@ -258,6 +393,7 @@ impl TextSpan {
             style: None,
             start: 0,
             end: len,
+            link: None,
         }
     }
 
@@ -271,8 +407,36 @@ impl TextSpan {
         self.end = end;
         self
     }
+
+    /// Open `url` when the span is clicked
+    pub fn link(mut self, url: impl Into<String>) -> Self {
+        self.link = Some(TextLink::Url(url.into()));
+        self
+    }
+
+    /// Call `action` when the span is clicked
+    pub fn on_click(mut self, action: impl Fn() + Send + Sync + 'static) -> Self {
+        self.link = Some(TextLink::Action(Arc::new(action)));
+        self
+    }
+
+    /// The style the span is drawn in, given the widget's
+    fn resolve_style<'a>(&'a self, base: &'a TextStyle) -> std::borrow::Cow<'a, TextStyle> {
+        match (&self.style, &self.link) {
+            (Some(style), _) => std::borrow::Cow::Borrowed(style),
+            (None, Some(_)) => std::borrow::Cow::Owned(TextStyle {
+                font_size: base.font_size,
+                letter_spacing: base.letter_spacing,
+                ..TextStyle::link()
+            }),
+            (None, None) => std::borrow::Cow::Borrowed(base),
+        }
+    }
 }
 
+/// How long the toast confirming [`Text::copy`] is shown
+pub const COPIED_TOAST_DURATION: Duration = Duration::from_millis(1500);
+
 /// Text widget
 #[derive(Debug)]
 pub struct Text {
@@ -285,17 +449,36 @@ pub struct Text {
     selectable: Signal<bool>,
     selection_start: Signal<Option<usize>>,
     selection_end: Signal<Option<usize>>,
+    copyable: bool,
+    focused: bool,
+    /// Copied to instead of the global clipboard
+    clipboard: Option<Arc<dyn Clipboard>>,
+    /// Confirms copies instead of the global toast manager
+    toasts: Option<ToastManager>,
     theme: Option<Arc<Theme>>,
     measured_size: Signal<Size>,
     cached_lines: Signal<Vec<String>>,
+    /// The words of each cached line, for mapping spans onto it
+    cached_words: Signal<Vec<Vec<WordPos>>>,
+    /// Where each link span was drawn, with its index
+    link_bounds: Signal<Vec<(Rect, usize)>>,
+    pressed_link: Option<usize>,
+    layout_cache: LayoutCache,
+    // Reformats a localized message when the locale changes, shared by clones
+    localized: Option<Arc<Subscription>>,
+    /// Content follows a signal given to [`bind`](Self::bind)
+    bound: bool,
 }
 
 impl Text {
     /// Create a new text widget
     pub fn new(content: impl Into<String>) -> Self {
+        let content = Signal::new(content.into());
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&content);
         Self {
             id: generate_id(),
-            content: Signal::new(content.into()),
+            content,
             spans: Vec::new(),
             style: TextStyle::default(),
             bounds: Signal::new(Rect::new(0.0, 0.0, 0.0, 0.0)),
@@ -303,15 +486,81 @@ impl Text {
             selectable: Signal::new(false),
             selection_start: Signal::new(None),
             selection_end: Signal::new(None),
+            copyable: false,
+            focused: false,
+            clipboard: None,
+            toasts: None,
             theme: None,
             measured_size: Signal::new(Size::new(0.0, 0.0)),
             cached_lines: Signal::new(Vec::new()),
+            cached_words: Signal::new(Vec::new()),
+            link_bounds: Signal::new(Vec::new()),
+            pressed_link: None,
+            layout_cache,
+            localized: None,
+            bound: false,
+        }
+    }
+
+    /// Create a text from spans drawn one after another
+    ///
+    /// The content is the spans' text joined together, and each span covers
+    /// its own part of it whatever range it was given.
+    ///
+    /// ```
+    /// use strato_widgets::text::{Text, TextSpan};
+    ///
+    /// let about = Text::rich([
+    ///     TextSpan::new("Read the "),
+    ///     TextSpan::new("documentation").link("https://example.com/docs"),
+    ///     TextSpan::new(" to get started."),
+    /// ]);
+    /// assert_eq!(about.content(), "Read the documentation to get started.");
+    /// ```
+    pub fn rich(spans: impl IntoIterator<Item = TextSpan>) -> Self {
+        let mut content = String::new();
+        let mut placed = Vec::new();
+        for span in spans {
+            let start = content.len();
+            content.push_str(&span.text);
+            placed.push(span.with_range(start, content.len()));
         }
+        let mut text = Self::new(content);
+        text.spans = placed;
+        text
+    }
+
+    /// Create a text showing the message `key` of the global [`Localizer`]
+    ///
+    /// The message is formatted with `args` and formatted again whenever the
+    /// locale changes or a message bundle is loaded.
+    pub fn localized<K, V>(key: impl Into<String>, args: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: Into<String>,
+        V: Into<MessageArg>,
+    {
+        let localizer = Localizer::global();
+        let key = key.into();
+        let args = message_args(args);
+        let content = Signal::new(localizer.format(&key, &args));
+
+        let (target, locale) = (content.clone(), localizer.locale_signal().clone());
+        let subscription =
+            locale.subscribe(Box::new(move |_| target.set(localizer.format(&key, &args))));
+
+        let mut text = Self::new("").bind(content);
+        text.localized = Some(Arc::new(subscription));
+        text
     }
 
     /// Bind text content to a signal
+    ///
+    /// The text is laid out again whenever the signal changes.
     pub fn bind(mut self, signal: Signal<String>) -> Self {
+        self.layout_cache.watch(&signal);
+        self.layout_cache.mark_dirty();
         self.content = signal;
+        self.bound = true;
         self
     }
 
@@ -357,6 +606,13 @@ impl Text {
         self
     }
 
+    /// Paint the text with `gradient`, spanning the block of text, instead
+    /// of its color
+    pub fn gradient(mut self, gradient: LinearGradient) -> Self {
+        self.style.gradient = Some(gradient);
+        self
+    }
+
     /// Set font size
     pub fn font_size(mut self, size: f32) -> Self {
         self.style.font_size = size;
@@ -375,6 +631,12 @@ impl Text {
         self
     }
 
+    /// Set where the text sits in a layout box taller than its lines
+    pub fn vertical_align(mut self, align: VerticalAlign) -> Self {
+        self.style.vertical_align = align;
+        self
+    }
+
     /// Set text overflow behavior
     pub fn overflow(mut self, overflow: TextOverflow) -> Self {
         self.style.text_overflow = overflow;
@@ -393,6 +655,32 @@ impl Text {
         self
     }
 
+    /// Let the text be copied whole with Ctrl+C once clicked
+    ///
+    /// Clicking a copyable text or tabbing to it focuses it, and Ctrl+C then
+    /// puts its whole content on the [clipboard](crate::clipboard) and shows
+    /// a "Copied" toast on the global [`ToastManager`]. Handy for error codes, IDs and
+    /// version strings, where selecting part of the text isn't needed.
+    pub fn copyable(mut self, copyable: bool) -> Self {
+        self.copyable = copyable;
+        if !copyable {
+            self.focused = false;
+        }
+        self
+    }
+
+    /// Copy to `clipboard` instead of the global one
+    pub fn clipboard(mut self, clipboard: Arc<dyn Clipboard>) -> Self {
+        self.clipboard = Some(clipboard);
+        self
+    }
+
+    /// Confirm copies with toasts on `manager` instead of the global one
+    pub fn toast_manager(mut self, manager: ToastManager) -> Self {
+        self.toasts = Some(manager);
+        self
+    }
+
     /// Set visible state
     pub fn visible(self, visible: bool) -> Self {
         self.visible.set(visible);
@@ -412,11 +700,22 @@ impl Text {
     }
 
     /// Add a text span for rich formatting
+    ///
+    /// The span restyles the bytes `span.start..span.end` of the content.
     pub fn add_span(mut self, span: TextSpan) -> Self {
         self.spans.push(span);
         self
     }
 
+    /// The link span drawn at `point`, if any
+    fn link_at(&self, point: Point) -> Option<usize> {
+        self.link_bounds
+            .get()
+            .iter()
+            .find(|(rect, _)| rect.contains(point))
+            .map(|&(_, span)| span)
+    }
+
     /// Get text content
     pub fn content(&self) -> String {
         self.content.get()
@@ -438,6 +737,19 @@ impl Text {
         self.selectable.get()
     }
 
+    /// Check if text can be copied with Ctrl+C
+    pub fn is_copyable(&self) -> bool {
+        self.copyable
+    }
+
+    /// Put the whole content on the clipboard and confirm with a toast
+    pub fn copy(&self) {
+        let clipboard = self.clipboard.clone().unwrap_or_else(clipboard);
+        clipboard.set_text(self.content.get());
+        let toasts = self.toasts.clone().unwrap_or_else(ToastManager::global);
+        toasts.success("Copied", COPIED_TOAST_DURATION);
+    }
+
     /// Get current selection
     pub fn get_selection(&self) -> Option<(usize, usize)> {
         match (self.selection_start.get(), self.selection_end.get()) {
@@ -462,6 +774,8 @@ impl Text {
     fn invalidate_layout(&self) {
         self.measured_size.set(Size::new(0.0, 0.0));
         self.cached_lines.set(Vec::new());
+        self.cached_words.set(Vec::new());
+        self.layout_cache.mark_dirty();
     }
 
     /// Measure text size
@@ -470,14 +784,15 @@ impl Text {
         let line_height = self.style.font_size * self.style.line_height;
 
         let mut lines = Vec::new();
+        let mut line_words = Vec::new();
         let mut current_line = String::new();
+        let mut current_words = Vec::new();
         let mut current_line_width = 0.0;
 
         let content = self.content.get();
-        let words: Vec<&str> = content.split_whitespace().collect();
         let space_width = measure_char_width(' ', self.style.font_size) + self.style.letter_spacing;
 
-        for (i, word) in words.iter().enumerate() {
+        for (offset, word) in words_with_offsets(&content) {
             let word_width =
                 measure_line_width(word, self.style.font_size, self.style.letter_spacing);
 
@@ -487,21 +802,25 @@ impl Text {
                 && current_line_width + space_width + word_width > available_width
             {
                 // Push current line and start new one
-                lines.push(current_line);
-                current_line = String::from(*word);
-                current_line_width = word_width;
-            } else {
-                if !current_line.is_empty() {
-                    current_line.push(' ');
-                    current_line_width += space_width;
-                }
-                current_line.push_str(word);
-                current_line_width += word_width;
+                lines.push(std::mem::take(&mut current_line));
+                line_words.push(std::mem::take(&mut current_words));
+                current_line_width = 0.0;
+            } else if !current_line.is_empty() {
+                current_line.push(' ');
+                current_line_width += space_width;
             }
+            current_words.push(WordPos {
+                line: current_line.len(),
+                content: offset,
+                len: word.len(),
+            });
+            current_line.push_str(word);
+            current_line_width += word_width;
         }
 
         if !current_line.is_empty() {
             lines.push(current_line);
+            line_words.push(current_words);
         }
 
         // If no content but we have text, treat as one line (e.g. single word too long or empty)
@@ -513,6 +832,7 @@ impl Text {
         if let Some(max_lines) = self.style.max_lines {
             if lines.len() > max_lines {
                 lines.truncate(max_lines);
+                line_words.truncate(max_lines);
             }
         }
 
@@ -534,6 +854,7 @@ impl Text {
         let size = Size::new(width, height);
         self.measured_size.set(size);
         self.cached_lines.set(lines);
+        self.cached_words.set(line_words);
 
         size
     }
@@ -605,6 +926,38 @@ impl Text {
         false
     }
 
+    /// Split `line` into byte ranges drawn by the same span, `None` for
+    /// the widget's own style
+    fn line_runs(&self, line: &str, words: &[WordPos]) -> Vec<(Range<usize>, Option<usize>)> {
+        let span_over = |range: Range<usize>| {
+            self.spans
+                .iter()
+                .rposition(|span| span.start <= range.start && range.end <= span.end)
+        };
+
+        let mut owners = vec![None; line.len()];
+        for (i, word) in words.iter().enumerate() {
+            for byte in 0..word.len {
+                owners[word.line + byte] = span_over(word.content + byte..word.content + byte + 1);
+            }
+            // The space to the next word belongs to a span covering the gap
+            if let Some(next) = words.get(i + 1) {
+                owners[word.line + word.len] = span_over(word.content + word.len..next.content);
+            }
+        }
+
+        let mut runs: Vec<(Range<usize>, Option<usize>)> = Vec::new();
+        for (byte, owner) in owners.into_iter().enumerate() {
+            match runs.last_mut() {
+                Some((range, last)) if *last == owner || !line.is_char_boundary(byte) => {
+                    range.end = byte + 1;
+                }
+                _ => runs.push((byte..byte + 1, owner)),
+            }
+        }
+        runs
+    }
+
     /// Render the text
     pub fn render(&self, batch: &mut RenderBatch) {
         if !self.is_visible() {
@@ -644,89 +997,142 @@ impl Text {
         }
 
         let lines = self.cached_lines.get();
+        let words = self.cached_words.get();
         let line_height = self.style.font_size * self.style.line_height;
+        let mut link_bounds = Vec::new();
+
+        // Lines are drawn from the top of their glyphs, so the block of text
+        // spans the line advances plus the glyphs of the last line
+        let LineMetrics {
+            ascent, descent, ..
+        } = measure_line_metrics(self.style.font_size);
+        let leading = lines.len().saturating_sub(1) as f32 * line_height;
+        let first_line_y = match self.style.vertical_align {
+            VerticalAlign::Top => bounds.y,
+            VerticalAlign::Middle => {
+                bounds.y + (bounds.height - (leading + ascent + descent)) / 2.0
+            }
+            VerticalAlign::Bottom => bounds.y + bounds.height - (leading + ascent + descent),
+            VerticalAlign::Baseline => bounds.y + bounds.height - (leading + ascent),
+        };
 
-        for (i, line) in lines.iter().enumerate() {
+        let line_x = |line: &str| {
             let line_width =
                 measure_line_width(line, self.style.font_size, self.style.letter_spacing);
-
-            // Calculate text position based on alignment
-            let text_x = match self.style.text_align {
+            let x = match self.style.text_align {
                 TextAlign::Left => bounds.x,
                 TextAlign::Center => bounds.x + (bounds.width - line_width) / 2.0,
                 TextAlign::Right => bounds.x + bounds.width - line_width,
                 TextAlign::Justify => bounds.x, // Simplified
             };
+            (x, line_width)
+        };
+        // Gradients span the glyphs of every line
+        let block = {
+            let (left, right) = lines
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(left, right), line| {
+                    let (x, width) = line_x(line);
+                    (left.min(x), right.max(x + width))
+                });
+            Rect::new(left, first_line_y, right - left, leading + ascent + descent)
+        };
 
-            let text_y = match self.style.vertical_align {
-                VerticalAlign::Top => bounds.y + (i as f32 * line_height),
-                VerticalAlign::Middle => {
-                    bounds.y
-                        + (bounds.height - (lines.len() as f32 * line_height)) / 2.0
-                        + (i as f32 * line_height)
-                }
-                VerticalAlign::Bottom => {
-                    bounds.y + bounds.height - (lines.len() as f32 * line_height)
-                        + (i as f32 * line_height)
-                }
-                VerticalAlign::Baseline => {
-                    bounds.y + (i as f32 * line_height) + self.style.font_size * 0.8
-                }
-            };
+        for (i, line) in lines.iter().enumerate() {
+            // Calculate text position based on alignment
+            let (text_x, _) = line_x(line);
 
-            // Render line
-            batch.add_text(
-                line.clone(),
-                (text_x, text_y),
-                self.style.color,
-                self.style.font_size,
-                self.style.letter_spacing,
-            );
+            let text_y = first_line_y + i as f32 * line_height;
 
-            // Render text decoration if any
-            if self.style.text_decoration != TextDecoration::None {
-                let decoration_y = match self.style.text_decoration {
-                    TextDecoration::Underline => text_y + self.style.font_size + 2.0,
-                    TextDecoration::Overline => text_y - 2.0,
-                    TextDecoration::LineThrough => text_y + self.style.font_size / 2.0,
-                    TextDecoration::None => text_y,
+            // Render line, one run per span it shows
+            let runs = match words.get(i) {
+                Some(words) if !self.spans.is_empty() => self.line_runs(line, words),
+                _ => vec![(0..line.len(), None)],
+            };
+            for (range, span) in runs {
+                let style = match span {
+                    Some(span) => self.spans[span].resolve_style(&self.style),
+                    None => std::borrow::Cow::Borrowed(&self.style),
                 };
+                let run = &line[range.clone()];
+                let run_x = text_x
+                    + measure_line_width(
+                        &line[..range.start],
+                        self.style.font_size,
+                        self.style.letter_spacing,
+                    );
+                let run_width =
+                    measure_line_width(run, self.style.font_size, self.style.letter_spacing);
+
+                match &style.gradient {
+                    Some(gradient) => batch.add_gradient_text(
+                        run.to_string(),
+                        (run_x, text_y),
+                        TextFill::new(gradient.clone(), block),
+                        self.style.font_size,
+                        self.style.letter_spacing,
+                        strato_core::text::TextAlign::Left,
+                    ),
+                    None => batch.add_text(
+                        run.to_string(),
+                        (run_x, text_y),
+                        style.color,
+                        self.style.font_size,
+                        self.style.letter_spacing,
+                    ),
+                }
+                self.render_decoration(batch, &style, run_x, text_y, run_width);
 
-                let (vertices, indices) = VertexBuilder::line(
-                    text_x,
-                    decoration_y,
-                    text_x + line_width,
-                    decoration_y,
-                    1.0,
-                    self.style.decoration_color.to_array(),
-                );
-                batch.add_vertices(&vertices, &indices);
+                if let Some(span) = span.filter(|&span| self.spans[span].link.is_some()) {
+                    let rect = Rect::new(run_x, text_y, run_width, line_height);
+                    link_bounds.push((rect, span));
+                }
             }
         }
 
         if should_clip {
             batch.pop_clip();
         }
-
-        // TODO: Re-implement TextSpan support for multi-line text
-        // This requires mapping lines back to original string indices
-        /*
-        // Render spans if any (rich text)
-        for span in &self.spans {
-            if let Some(span_style) = &span.style {
-                let span_text = &span.text[span.start..span.end.min(span.text.len())];
-                let span_x = text_x + span.start as f32 * self.style.font_size * 0.6;
-
-                batch.add_text(
-                    span_text.to_string(),
-                    (span_x, text_y),
-                    span_style.color,
-                    span_style.font_size,
-                    span_style.letter_spacing,
-                );
-            }
+        self.link_bounds.set(link_bounds);
+
+        // Focus ring, so it's clear what Ctrl+C copies
+        if self.is_focused() {
+            batch.add_stroke(
+                bounds.expand(2.0),
+                Color::rgba(0.0, 0.4, 0.8, 0.8),
+                1.0,
+                BorderRadius::all(2.0),
+                Transform::identity(),
+            );
         }
-        */
+    }
+
+    /// Draw the decoration of `style` under, over or through a run of text
+    fn render_decoration(
+        &self,
+        batch: &mut RenderBatch,
+        style: &TextStyle,
+        x: f32,
+        y: f32,
+        width: f32,
+    ) {
+        let font_size = self.style.font_size;
+        let decoration_y = match style.text_decoration {
+            TextDecoration::None => return,
+            TextDecoration::Underline => y + font_size + 2.0,
+            TextDecoration::Overline => y - 2.0,
+            TextDecoration::LineThrough => y + font_size / 2.0,
+        };
+
+        let (vertices, indices) = VertexBuilder::line(
+            x,
+            decoration_y,
+            x + width,
+            decoration_y,
+            1.0,
+            style.decoration_color.to_array(),
+        );
+        batch.add_vertices(&vertices, &indices);
     }
 
     /// Apply theme to text
@@ -734,6 +1140,7 @@ impl Text {
         self.style.font_family = theme.typography.font_family.clone();
         self.style.font_size = theme.typography.base_size;
         self.style.color = theme.colors.on_surface.to_types_color();
+        self.invalidate_layout();
     }
 }
 
@@ -792,6 +1199,12 @@ impl TextBuilder {
         self
     }
 
+    /// Paint the text with a gradient instead of its color
+    pub fn gradient(mut self, gradient: LinearGradient) -> Self {
+        self.text = self.text.gradient(gradient);
+        self
+    }
+
     /// Set font size
     pub fn font_size(mut self, size: f32) -> Self {
         self.text = self.text.font_size(size);
@@ -810,6 +1223,12 @@ impl TextBuilder {
         self
     }
 
+    /// Set where the text sits in a layout box taller than its lines
+    pub fn vertical_align(mut self, align: VerticalAlign) -> Self {
+        self.text = self.text.vertical_align(align);
+        self
+    }
+
     /// Set text overflow behavior
     pub fn overflow(mut self, overflow: TextOverflow) -> Self {
         self.text = self.text.overflow(overflow);
@@ -828,6 +1247,12 @@ impl TextBuilder {
         self
     }
 
+    /// Let the text be copied whole with Ctrl+C once clicked
+    pub fn copyable(mut self, copyable: bool) -> Self {
+        self.text = self.text.copyable(copyable);
+        self
+    }
+
     /// Set visible state
     pub fn visible(mut self, visible: bool) -> Self {
         self.text = self.text.visible(visible);
@@ -861,6 +1286,10 @@ impl TextBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::WidgetTester;
+    use crate::widget::cursor_icon_at;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use strato_renderer::batch::DrawCommand;
 
     #[test]
     fn test_text_creation() {
@@ -918,6 +1347,269 @@ mod tests {
         assert!(size.width <= available.width);
         assert!(size.height <= available.height);
     }
+
+    #[test]
+    fn test_dropped_bound_texts_unsubscribe() {
+        let content = Signal::new(String::from("Loading"));
+        for i in 0..1_000 {
+            let text = Text::new("").bind(content.clone());
+            let cloned = text.clone_widget();
+            assert_eq!(content.subscriber_count(), 2);
+            drop((text, cloned));
+            content.set(format!("Item {i}"));
+        }
+
+        assert_eq!(content.subscriber_count(), 0);
+
+        let text = Text::new("").bind(content.clone());
+        content.set(String::from("Done"));
+        assert_eq!(text.content(), "Done");
+        assert_eq!(content.subscriber_count(), 1);
+    }
+
+    #[test]
+    fn test_localized_text_follows_locale() {
+        let localizer = Localizer::global();
+        localizer
+            .load(
+                "en",
+                "test.inbox = {count} new messages\ntest.inbox[one] = {count} new message",
+            )
+            .unwrap();
+        localizer
+            .load("de", "test.inbox = {count} neue Nachrichten")
+            .unwrap();
+
+        let text = Text::localized("test.inbox", [("count", 1)]);
+        let subscribers = localizer.locale_signal().subscriber_count();
+        let locale = localizer.locale();
+        localizer.set_locale("de-DE");
+        let german = text.content();
+        localizer.set_locale(locale);
+
+        assert_eq!(german, "1 neue Nachrichten");
+        assert_eq!(text.content(), "1 new message");
+        drop(text);
+        assert_eq!(
+            localizer.locale_signal().subscriber_count(),
+            subscribers - 1
+        );
+    }
+
+    /// Where the run of text `run` was drawn
+    fn drawn_at(tester: &WidgetTester<Text>, run: &str) -> (f32, f32, Color) {
+        tester
+            .frame()
+            .commands
+            .iter()
+            .find_map(|command| match command {
+                DrawCommand::Text {
+                    text,
+                    position,
+                    color,
+                    ..
+                } if text == run => Some((position.0, position.1, *color)),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_line_metrics_of_the_drawn_font() {
+        // Segoe UI: 2048 units per em, ascender 2210, descender -514, no gap
+        let metrics = measure_line_metrics(20.0);
+        assert!((metrics.ascent - 20.0 * 2210.0 / 2048.0).abs() < 0.01);
+        assert!((metrics.descent - 20.0 * 514.0 / 2048.0).abs() < 0.01);
+        assert_eq!(metrics.line_gap, 0.0);
+        assert!((metrics.line_height - metrics.glyph_height()).abs() < 0.01);
+
+        install_line_metrics();
+        let font = strato_core::text::FontDescriptor::default();
+        assert_eq!(strato_core::text::line_metrics(&font, 20.0), metrics);
+    }
+
+    #[test]
+    fn test_middle_aligned_label_is_centered_in_a_taller_box() {
+        let tester = WidgetTester::new(
+            Text::new("Label").vertical_align(VerticalAlign::Middle),
+            200.0,
+            40.0,
+        );
+        assert_eq!(tester.size().height, 40.0);
+
+        let (_, y, _) = drawn_at(&tester, "Label");
+        let metrics = measure_line_metrics(14.0);
+        let above = y;
+        let below = 40.0 - (y + metrics.glyph_height());
+        assert!((above - below).abs() < 0.01, "{above} above, {below} below");
+
+        let tester = WidgetTester::new(
+            Text::new("Label").vertical_align(VerticalAlign::Baseline),
+            200.0,
+            40.0,
+        );
+        let (_, y, _) = drawn_at(&tester, "Label");
+        assert!((metrics.baseline(y) - 40.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_link_span_runs_its_action_when_clicked() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&clicks);
+        let text = Text::rich([
+            TextSpan::new("Read the "),
+            TextSpan::new("release notes").on_click(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+            TextSpan::new(" first"),
+        ]);
+        let mut tester = WidgetTester::new(text, 400.0, 40.0);
+        assert_eq!(
+            tester.drawn_text(),
+            ["Read the ", "release notes", " first"]
+        );
+
+        let (x, y, color) = drawn_at(&tester, "release notes");
+        assert_eq!(color, TextStyle::link().color);
+        let link = Point::new(x + 5.0, y + 5.0);
+        assert_eq!(
+            cursor_icon_at(tester.root(), link),
+            Some(CursorIcon::Pointer)
+        );
+        tester.click(link.x, link.y);
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+
+        // Plain text around the link is not clickable
+        let (x, y, _) = drawn_at(&tester, "Read the ");
+        assert_eq!(
+            cursor_icon_at(tester.root(), Point::new(x + 5.0, y + 5.0)),
+            None
+        );
+        tester.click(x + 5.0, y + 5.0);
+        assert_eq!(clicks.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_url_link_is_handed_to_the_opener() {
+        let opened = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let log = Arc::clone(&opened);
+        set_url_opener(move |url| log.lock().push(url.to_string()));
+
+        let text = Text::new("See the docs or the FAQ").add_span(
+            TextSpan::new("the FAQ")
+                .with_range(16, 23)
+                .link("https://example.com/faq"),
+        );
+        let mut tester = WidgetTester::new(text, 400.0, 40.0);
+        assert_eq!(tester.drawn_text(), ["See the docs or ", "the FAQ"]);
+
+        // Pressed on the link and released elsewhere does nothing
+        let (x, y, _) = drawn_at(&tester, "the FAQ");
+        tester.press(x + 5.0, y + 5.0, MouseButton::Left);
+        tester.release(2.0, y + 5.0, MouseButton::Left);
+        assert!(opened.lock().is_empty());
+
+        tester.click(x + 5.0, y + 5.0);
+        assert_eq!(*opened.lock(), ["https://example.com/faq"]);
+    }
+
+    #[test]
+    fn test_link_wrapped_over_two_lines_is_clickable_on_both() {
+        let clicks = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&clicks);
+        let text = Text::rich([
+            TextSpan::new("Please "),
+            TextSpan::new("contact support").on_click(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }),
+        ]);
+        let width = measure_text_width("Please contact", 14.0, 0.0) + 1.0;
+        let mut tester = WidgetTester::new(text, width, 100.0);
+        assert_eq!(tester.drawn_text(), ["Please ", "contact", "support"]);
+
+        for run in ["contact", "support"] {
+            let (x, y, _) = drawn_at(&tester, run);
+            tester.click(x + 5.0, y + 5.0);
+        }
+        assert_eq!(clicks.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_gradient_spans_every_line_of_the_text() {
+        use strato_core::types::GradientStop;
+        use strato_renderer::batch::DrawCommand;
+
+        let red = Color::rgba(1.0, 0.0, 0.0, 1.0);
+        let blue = Color::rgba(0.0, 0.0, 1.0, 1.0);
+        let text = Text::new("Top line Bottom").gradient(LinearGradient::vertical(vec![
+            GradientStop {
+                color: red,
+                position: 0.0,
+            },
+            GradientStop {
+                color: blue,
+                position: 1.0,
+            },
+        ]));
+        let width = measure_text_width("Top line", 14.0, 0.0) + 1.0;
+        let tester = WidgetTester::new(text, width, 100.0);
+
+        let fills: Vec<_> = tester
+            .frame()
+            .commands
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Text { fill, .. } => fill.clone(),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0], fills[1]);
+        // Red at the top of the first line, blue at the bottom of the last
+        let block = fills[0].bounds;
+        let (_, bottom_y, _) = drawn_at(&tester, "Bottom");
+        assert_eq!(fills[0].color_at([block.x, block.y]), red);
+        assert!(
+            (block.y + block.height - bottom_y - measure_line_metrics(14.0).glyph_height()).abs()
+                < 0.01
+        );
+        assert_eq!(fills[0].color_at([block.x, block.y + block.height]), blue);
+    }
+
+    #[test]
+    fn test_ctrl_c_copies_a_focused_copyable_text() {
+        let clipboard = Arc::new(crate::clipboard::MemoryClipboard::new());
+        let toasts = ToastManager::new();
+        let text = Text::new("ERR-4021")
+            .copyable(true)
+            .clipboard(clipboard.clone())
+            .toast_manager(toasts.clone());
+        let mut tester = WidgetTester::new(text, 200.0, 40.0);
+        assert!(tester.root().is_focusable());
+        let ctrl = strato_core::event::Modifiers {
+            control: true,
+            ..Default::default()
+        };
+
+        // Not focused yet, so the shortcut is left to others
+        tester.set_modifiers(ctrl);
+        assert_eq!(tester.key_down(KeyCode::C), EventResult::Ignored);
+        assert_eq!(clipboard.text(), None);
+
+        tester.set_modifiers(Default::default());
+        tester.click(10.0, 10.0);
+        assert!(tester.root().is_focused());
+        tester.set_modifiers(ctrl);
+        assert_eq!(tester.key_down(KeyCode::C), EventResult::Handled);
+        assert_eq!(clipboard.text().as_deref(), Some("ERR-4021"));
+        let shown: Vec<_> = toasts.visible().into_iter().map(|t| t.message).collect();
+        assert_eq!(shown, ["Copied"]);
+
+        // Clicking elsewhere takes the focus away
+        tester.set_modifiers(Default::default());
+        tester.click(10.0, 100.0);
+        assert!(!tester.root().is_focused());
+    }
 }
 
 // Implement Widget trait for Text
@@ -926,18 +1618,32 @@ impl Widget for Text {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
-        self.measure_text(constraints.max_width)
+        let cache = self.layout_cache.clone();
+        // Fill a box the parent insists on, so vertical alignment has room
+        cache.get_or_layout(constraints, || {
+            constraints.constrain(self.measure_text(constraints.max_width))
+        })
+    }
+
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        Some(&self.layout_cache)
     }
 
     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
         // Update bounds based on layout
-        self.bounds.set(Rect::new(
+        let bounds = Rect::new(
             layout.position.x,
             layout.position.y,
             layout.size.width,
             layout.size.height,
-        ));
+        );
+        self.bounds.set(bounds);
+        register_bounds(self.id, bounds);
 
         // Ensure text is measured/wrapped for these bounds
         // Note: layout() should have been called before render(), but we need to ensure
@@ -949,15 +1655,56 @@ impl Widget for Text {
         self.render(batch);
     }
 
+    fn cursor_icon(&self, point: Point) -> Option<CursorIcon> {
+        self.link_at(point).map(|_| CursorIcon::Pointer)
+    }
+
+    fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    fn is_focusable(&self) -> bool {
+        self.copyable && self.is_visible()
+    }
+
+    fn set_focused(&mut self, focused: bool) {
+        self.focused = focused && self.copyable;
+    }
+
     fn handle_event(&mut self, event: &Event) -> EventResult {
         match event {
-            Event::MouseDown(mouse_event) => {
-                if mouse_event.button == Some(strato_core::event::MouseButton::Left) {
-                    if self.on_mouse_press(mouse_event.position.into()) {
+            Event::MouseDown(mouse_event) if mouse_event.button == Some(MouseButton::Left) => {
+                let point = mouse_event.position.into();
+                self.pressed_link = self.link_at(point);
+                if self.pressed_link.is_some() || self.on_mouse_press(point) {
+                    return EventResult::Handled;
+                }
+                if self.copyable {
+                    let inside = self.is_visible() && self.bounds.get().contains(point);
+                    self.set_focused(inside);
+                    if inside {
                         return EventResult::Handled;
                     }
                 }
             }
+            Event::KeyDown(keyboard_event)
+                if self.is_focused() && Shortcut::ctrl(KeyCode::C).matches(keyboard_event) =>
+            {
+                self.copy();
+                return EventResult::Handled;
+            }
+            Event::MouseUp(mouse_event) if mouse_event.button == Some(MouseButton::Left) => {
+                let pressed = self.pressed_link.take();
+                if let Some(span) = pressed {
+                    // Released over the link it was pressed on
+                    if self.link_at(mouse_event.position.into()) == Some(span) {
+                        if let Some(link) = &self.spans[span].link {
+                            link.activate();
+                        }
+                    }
+                    return EventResult::Handled;
+                }
+            }
             Event::MouseMove(mouse_event) => {
                 if self.on_mouse_drag(mouse_event.position.into()) {
                     return EventResult::Handled;
@@ -976,9 +1723,40 @@ impl Widget for Text {
         self
     }
 
+    fn apply_props(&mut self, new: &dyn Widget) -> Option<bool> {
+        let new = new.as_any().downcast_ref::<Text>()?;
+        // Rich and bound content is replaced whole rather than copied
+        if self.bound || new.bound || !self.spans.is_empty() || !new.spans.is_empty() {
+            return None;
+        }
+        let mut changed = false;
+        let content = new.content.peek();
+        if self.content.peek() != content {
+            self.set_content(content);
+            changed = true;
+        }
+        if self.style != new.style {
+            self.style = new.style.clone();
+            self.invalidate_layout();
+            changed = true;
+        }
+        if self.selectable.peek() != new.selectable.peek() {
+            self.selectable.set(new.selectable.peek());
+            changed = true;
+        }
+        if self.copyable != new.copyable {
+            self.copyable = new.copyable;
+            self.focused &= new.copyable;
+            changed = true;
+        }
+        Some(changed)
+    }
+
     fn clone_widget(&self) -> Box<dyn Widget> {
+        let layout_cache = LayoutCache::new();
+        layout_cache.watch(&self.content);
         Box::new(Text {
-            id: generate_id(),
+            id: clone_id(self.id),
             content: self.content.clone(),
             spans: self.spans.clone(),
             style: self.style.clone(),
@@ -987,9 +1765,19 @@ impl Widget for Text {
             selectable: Signal::new(self.selectable.get()),
             selection_start: Signal::new(self.selection_start.get()),
             selection_end: Signal::new(self.selection_end.get()),
+            copyable: self.copyable,
+            focused: false,
+            clipboard: self.clipboard.clone(),
+            toasts: self.toasts.clone(),
             theme: self.theme.clone(),
             measured_size: Signal::new(self.measured_size.get()),
             cached_lines: Signal::new(self.cached_lines.get()),
+            cached_words: Signal::new(self.cached_words.get()),
+            link_bounds: Signal::new(self.link_bounds.get()),
+            pressed_link: None,
+            layout_cache,
+            localized: self.localized.clone(),
+            bound: self.bound,
         })
     }
 }
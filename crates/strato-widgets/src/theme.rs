@@ -91,6 +91,8 @@ pub struct ColorPalette {
     pub text_secondary: Color,
     pub text_disabled: Color,
     pub divider: Color,
+    /// Opacity multiplier applied to controls that are disabled
+    pub disabled_opacity: f32,
 }
 
 impl ColorPalette {
@@ -109,6 +111,7 @@ impl ColorPalette {
             text_secondary: Color::rgba(0.0, 0.0, 0.0, 0.60),
             text_disabled: Color::rgba(0.0, 0.0, 0.0, 0.38),
             divider: Color::rgba(0.0, 0.0, 0.0, 0.12),
+            disabled_opacity: 0.38,
         }
     }
 
@@ -127,6 +130,7 @@ impl ColorPalette {
             text_secondary: Color::rgba(1.0, 1.0, 1.0, 0.60),
             text_disabled: Color::rgba(1.0, 1.0, 1.0, 0.38),
             divider: Color::rgba(1.0, 1.0, 1.0, 0.12),
+            disabled_opacity: 0.38,
         }
     }
 
@@ -145,8 +149,14 @@ impl ColorPalette {
             text_secondary: Color::WHITE, // No subtle text in HC
             text_disabled: Color::rgb(0.7, 0.7, 0.7),
             divider: Color::WHITE,
+            disabled_opacity: 0.6,
         }
     }
+
+    /// Muted version of `color` for drawing a disabled control
+    pub fn disabled(&self, color: Color) -> Color {
+        Color::rgba(color.r, color.g, color.b, color.a * self.disabled_opacity)
+    }
 }
 
 impl Default for ColorPalette {
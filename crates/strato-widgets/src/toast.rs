@@ -0,0 +1,541 @@
+//! Toast / snackbar notifications
+//!
+//! Toasts are short-lived messages ("Saved", "Connection lost") drawn above the
+//! rest of the UI. Notifications are posted through a [`ToastManager`] and drawn
+//! by a [`ToastHost`] wrapping the application's root widget.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use parking_lot::RwLock;
+use strato_core::event::{Event, EventResult, MouseButton};
+use strato_core::layout::{Constraints, Layout, Size};
+use strato_core::state::Signal;
+use strato_core::text::TextAlign;
+use strato_core::types::{Color, Point, Rect, Transform};
+use strato_renderer::batch::RenderBatch;
+
+use crate::animation::{animation_settings, Curve};
+use crate::text::measure_line_metrics;
+use crate::theme::{ColorPalette, Theme};
+use crate::widget::{clone_id, dispatch_event, generate_id, Widget, WidgetContext, WidgetId};
+
+const TOAST_WIDTH: f32 = 320.0;
+const TOAST_HEIGHT: f32 = 44.0;
+const TOAST_SPACING: f32 = 8.0;
+const TOAST_MARGIN: f32 = 16.0;
+const TOAST_FONT_SIZE: f32 = 14.0;
+const ACCENT_WIDTH: f32 = 4.0;
+const FADE_DURATION: Duration = Duration::from_millis(200);
+
+/// Identifier of a toast posted to a [`ToastManager`]
+pub type ToastId = u64;
+
+/// Severity of a toast, used to pick its accent color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastLevel {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl ToastLevel {
+    /// Accent color for this level in the given palette
+    pub fn color(&self, palette: &ColorPalette) -> Color {
+        match self {
+            ToastLevel::Info => palette.info,
+            ToastLevel::Success => palette.success,
+            ToastLevel::Warning => palette.warning,
+            ToastLevel::Error => palette.error,
+        }
+    }
+}
+
+/// Screen corner the toast stack is anchored to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ToastAnchor {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+/// A toast that is currently on screen
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub id: ToastId,
+    pub level: ToastLevel,
+    pub message: String,
+    pub duration: Duration,
+    shown_for: Duration,
+    dismissed_for: Option<Duration>,
+}
+
+impl Toast {
+    /// Current opacity (0.0 to 1.0) including fade in and fade out
+    pub fn opacity(&self) -> f32 {
+        let fade_in = Curve::EaseOut.transform(fade_progress(self.shown_for));
+        let fade_out = self.dismissed_for.map_or(0.0, |elapsed| {
+            Curve::EaseIn.transform(fade_progress(elapsed))
+        });
+        fade_in * (1.0 - fade_out)
+    }
+
+    /// Check if the toast is fading out
+    pub fn is_dismissing(&self) -> bool {
+        self.dismissed_for.is_some()
+    }
+
+    fn dismiss(&mut self) {
+        if self.dismissed_for.is_none() {
+            self.dismissed_for = Some(Duration::ZERO);
+        }
+    }
+
+    fn is_faded_out(&self) -> bool {
+        self.dismissed_for
+            .is_some_and(|elapsed| fade_progress(elapsed) >= 1.0)
+    }
+}
+
+/// Linear progress (0.0 to 1.0) of a fade that has run for `elapsed`
+fn fade_progress(elapsed: Duration) -> f32 {
+    let duration = animation_settings().scale(FADE_DURATION);
+    if duration.is_zero() {
+        1.0
+    } else {
+        (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0)
+    }
+}
+
+#[derive(Debug)]
+struct PendingToast {
+    id: ToastId,
+    level: ToastLevel,
+    message: String,
+    duration: Duration,
+}
+
+#[derive(Debug)]
+struct ToastQueue {
+    next_id: ToastId,
+    max_visible: usize,
+    active: Vec<Toast>,
+    pending: VecDeque<PendingToast>,
+}
+
+/// Posts and schedules toast notifications
+///
+/// The manager is a cheap, cloneable handle; every clone shares the same queue.
+/// At most `max_visible` toasts are on screen at once, further toasts wait in
+/// the queue until a slot frees up.
+#[derive(Debug, Clone)]
+pub struct ToastManager {
+    inner: Arc<RwLock<ToastQueue>>,
+}
+
+impl ToastManager {
+    /// Create a new, empty manager
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(RwLock::new(ToastQueue {
+                next_id: 1,
+                max_visible: 3,
+                active: Vec::new(),
+                pending: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Access the application-wide manager used by [`ToastHost`] by default
+    pub fn global() -> ToastManager {
+        static GLOBAL: OnceLock<ToastManager> = OnceLock::new();
+        GLOBAL.get_or_init(ToastManager::new).clone()
+    }
+
+    /// Set how many toasts may be on screen at once
+    pub fn with_max_visible(self, max_visible: usize) -> Self {
+        self.inner.write().max_visible = max_visible.max(1);
+        self
+    }
+
+    /// Show a toast for `duration`; it is queued if the stack is full
+    pub fn show(
+        &self,
+        level: ToastLevel,
+        message: impl Into<String>,
+        duration: Duration,
+    ) -> ToastId {
+        let mut queue = self.inner.write();
+        let id = queue.next_id;
+        queue.next_id += 1;
+        queue.pending.push_back(PendingToast {
+            id,
+            level,
+            message: message.into(),
+            duration,
+        });
+        Self::promote(&mut queue);
+        id
+    }
+
+    /// Shorthand for an info toast
+    pub fn info(&self, message: impl Into<String>, duration: Duration) -> ToastId {
+        self.show(ToastLevel::Info, message, duration)
+    }
+
+    /// Shorthand for a success toast
+    pub fn success(&self, message: impl Into<String>, duration: Duration) -> ToastId {
+        self.show(ToastLevel::Success, message, duration)
+    }
+
+    /// Shorthand for a warning toast
+    pub fn warning(&self, message: impl Into<String>, duration: Duration) -> ToastId {
+        self.show(ToastLevel::Warning, message, duration)
+    }
+
+    /// Shorthand for an error toast
+    pub fn error(&self, message: impl Into<String>, duration: Duration) -> ToastId {
+        self.show(ToastLevel::Error, message, duration)
+    }
+
+    /// Dismiss a toast; visible toasts fade out, queued ones are dropped
+    pub fn dismiss(&self, id: ToastId) {
+        let mut queue = self.inner.write();
+        queue.pending.retain(|toast| toast.id != id);
+        if let Some(toast) = queue.active.iter_mut().find(|toast| toast.id == id) {
+            toast.dismiss();
+        }
+    }
+
+    /// Dismiss every visible and queued toast
+    pub fn clear(&self) {
+        let mut queue = self.inner.write();
+        queue.pending.clear();
+        queue.active.iter_mut().for_each(Toast::dismiss);
+    }
+
+    /// Advance timers by `delta_time` seconds: start fading expired toasts,
+    /// drop faded ones and promote queued toasts into free slots.
+    pub fn tick(&self, delta_time: f32) {
+        let delta = Duration::from_secs_f32(delta_time.max(0.0));
+        let mut queue = self.inner.write();
+        for toast in queue.active.iter_mut() {
+            toast.shown_for += delta;
+            if let Some(elapsed) = toast.dismissed_for.as_mut() {
+                *elapsed += delta;
+            }
+            if toast.shown_for >= toast.duration {
+                toast.dismiss();
+            }
+        }
+        queue.active.retain(|toast| !toast.is_faded_out());
+        Self::promote(&mut queue);
+    }
+
+    /// Toasts currently on screen, oldest first
+    pub fn visible(&self) -> Vec<Toast> {
+        self.inner.read().active.clone()
+    }
+
+    /// Number of toasts waiting for a free slot
+    pub fn pending_count(&self) -> usize {
+        self.inner.read().pending.len()
+    }
+
+    fn promote(queue: &mut ToastQueue) {
+        while queue.active.len() < queue.max_visible {
+            let Some(pending) = queue.pending.pop_front() else {
+                break;
+            };
+            queue.active.push(Toast {
+                id: pending.id,
+                level: pending.level,
+                message: pending.message,
+                duration: pending.duration,
+                shown_for: Duration::ZERO,
+                dismissed_for: None,
+            });
+        }
+    }
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Wraps the root widget and draws the toast stack on top of it
+///
+/// Clicking a toast dismisses it.
+#[derive(Debug)]
+pub struct ToastHost {
+    id: WidgetId,
+    child: Box<dyn Widget>,
+    manager: ToastManager,
+    anchor: ToastAnchor,
+    palette: ColorPalette,
+    toast_rects: Signal<Vec<(ToastId, Rect)>>,
+}
+
+impl ToastHost {
+    /// Create a host around `child` using the global [`ToastManager`]
+    pub fn new(child: impl Widget + 'static) -> Self {
+        Self {
+            id: generate_id(),
+            child: Box::new(child),
+            manager: ToastManager::global(),
+            anchor: ToastAnchor::BottomRight,
+            palette: Theme::default().colors,
+            toast_rects: Signal::new(Vec::new()),
+        }
+    }
+
+    /// Use a specific manager instead of the global one
+    pub fn manager(mut self, manager: ToastManager) -> Self {
+        self.manager = manager;
+        self
+    }
+
+    /// Set the corner toasts stack from
+    pub fn anchor(mut self, anchor: ToastAnchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Take toast colors from a theme
+    pub fn theme(mut self, theme: &Theme) -> Self {
+        self.palette = theme.colors.clone();
+        self
+    }
+
+    /// Rects of the visible toasts, stacked away from the anchored corner
+    fn toast_rects(&self, bounds: Rect, toasts: &[Toast]) -> Vec<(ToastId, Rect)> {
+        let width = TOAST_WIDTH.min(bounds.width - TOAST_MARGIN * 2.0).max(0.0);
+        let x = match self.anchor {
+            ToastAnchor::TopLeft | ToastAnchor::BottomLeft => bounds.x + TOAST_MARGIN,
+            ToastAnchor::TopRight | ToastAnchor::BottomRight => {
+                bounds.x + bounds.width - TOAST_MARGIN - width
+            }
+        };
+
+        // Newest toast sits closest to the anchored corner.
+        toasts
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, toast)| {
+                let offset = TOAST_MARGIN + index as f32 * (TOAST_HEIGHT + TOAST_SPACING);
+                let y = match self.anchor {
+                    ToastAnchor::TopLeft | ToastAnchor::TopRight => bounds.y + offset,
+                    ToastAnchor::BottomLeft | ToastAnchor::BottomRight => {
+                        bounds.y + bounds.height - offset - TOAST_HEIGHT
+                    }
+                };
+                (toast.id, Rect::new(x, y, width, TOAST_HEIGHT))
+            })
+            .collect()
+    }
+}
+
+impl Widget for ToastHost {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.child.layout(constraints)
+    }
+
+    fn update(&mut self, ctx: &WidgetContext) {
+        self.manager.tick(ctx.delta_time);
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.child.render(batch, layout);
+
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+        let toasts = self.manager.visible();
+        let rects = self.toast_rects(bounds, &toasts);
+
+        for (toast, (_, rect)) in toasts.iter().rev().zip(&rects) {
+            let opacity = toast.opacity();
+            let mut background = self.palette.surface;
+            background.a *= 0.96 * opacity;
+            let mut accent = toast.level.color(&self.palette);
+            accent.a *= opacity;
+            let mut text_color = self.palette.text_primary;
+            text_color.a *= opacity;
+
+            batch.add_overlay_rect(*rect, background, Transform::identity());
+            batch.add_overlay_rect(
+                Rect::new(rect.x, rect.y, ACCENT_WIDTH, rect.height),
+                accent,
+                Transform::identity(),
+            );
+            batch.add_overlay_text_aligned(
+                toast.message.clone(),
+                (
+                    rect.x + ACCENT_WIDTH + 12.0,
+                    measure_line_metrics(TOAST_FONT_SIZE).centered_top(rect.y, rect.height),
+                ),
+                text_color,
+                TOAST_FONT_SIZE,
+                0.0,
+                TextAlign::Left,
+            );
+        }
+
+        self.toast_rects.set(rects);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        if let Event::MouseDown(mouse) = event {
+            if mouse.button == Some(MouseButton::Left) {
+                let point = Point::new(mouse.position.x, mouse.position.y);
+                let hit = self
+                    .toast_rects
+                    .get()
+                    .into_iter()
+                    .find(|(_, rect)| rect.contains(point));
+                if let Some((id, _)) = hit {
+                    self.manager.dismiss(id);
+                    return EventResult::Handled;
+                }
+            }
+        }
+
+        dispatch_event(self.child.as_mut(), event)
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(Self {
+            id: clone_id(self.id),
+            child: self.child.clone_widget(),
+            manager: self.manager.clone(),
+            anchor: self.anchor,
+            palette: self.palette.clone(),
+            toast_rects: Signal::new(Vec::new()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::Text;
+
+    #[test]
+    fn test_toasts_queue_beyond_max_visible() {
+        let manager = ToastManager::new().with_max_visible(2);
+        for i in 0..4 {
+            manager.show(
+                ToastLevel::Info,
+                format!("toast {i}"),
+                Duration::from_secs(5),
+            );
+        }
+
+        assert_eq!(manager.visible().len(), 2);
+        assert_eq!(manager.pending_count(), 2);
+    }
+
+    #[test]
+    fn test_dismiss_promotes_queued_toast() {
+        let manager = ToastManager::new().with_max_visible(1);
+        let first = manager.success("Saved", Duration::from_secs(5));
+        let second = manager.error("Failed", Duration::from_secs(5));
+
+        manager.dismiss(first);
+        assert!(manager.visible()[0].is_dismissing());
+
+        manager.tick(FADE_DURATION.as_secs_f32() / 2.0);
+        assert_eq!(manager.visible()[0].id, first);
+
+        manager.tick(FADE_DURATION.as_secs_f32());
+
+        let visible = manager.visible();
+        assert_eq!(visible.len(), 1);
+        assert_eq!(visible[0].id, second);
+        assert_eq!(manager.pending_count(), 0);
+    }
+
+    #[test]
+    fn test_expired_toast_starts_fading() {
+        let manager = ToastManager::new();
+        manager.warning("Low disk space", Duration::ZERO);
+        manager.tick(0.0);
+
+        assert!(manager.visible()[0].is_dismissing());
+    }
+
+    #[test]
+    fn test_host_expires_toasts_on_update_not_layout() {
+        let manager = ToastManager::new();
+        manager.warning("Low disk space", Duration::ZERO);
+
+        let host = ToastHost::new(Text::new("content")).manager(manager.clone());
+        let mut tester = crate::test_util::WidgetTester::new(host, 800.0, 600.0);
+        tester.pump();
+        assert!(!manager.visible()[0].is_dismissing());
+
+        tester.advance(0.0);
+        assert!(manager.visible()[0].is_dismissing());
+    }
+
+    #[test]
+    fn test_level_colors_follow_theme() {
+        let palette = Theme::dark().colors;
+        assert_eq!(ToastLevel::Error.color(&palette), palette.error);
+        assert_eq!(ToastLevel::Success.color(&palette), palette.success);
+    }
+
+    #[test]
+    fn test_host_renders_toasts_as_overlay() {
+        let manager = ToastManager::new();
+        manager.info("Hello", Duration::from_secs(5));
+
+        let mut host = ToastHost::new(Text::new("content")).manager(manager);
+        host.layout(Constraints::loose(800.0, 600.0));
+
+        let mut batch = RenderBatch::new();
+        host.render(
+            &mut batch,
+            Layout::new(glam::Vec2::ZERO, Size::new(800.0, 600.0)),
+        );
+
+        // Background, accent bar and message
+        assert_eq!(batch.overlay_commands.len(), 3);
+        let (_, rect) = host.toast_rects.get()[0];
+        assert_eq!(rect.x + rect.width, 800.0 - TOAST_MARGIN);
+        assert_eq!(rect.y + rect.height, 600.0 - TOAST_MARGIN);
+    }
+}
@@ -2,15 +2,26 @@ use crate::{
     container::Container,
     layout::{CrossAxisAlignment, MainAxisAlignment, Row},
     text::{FontWeight, Text},
-    widget::{generate_id, WidgetContext, WidgetId},
+    theme::{ColorPalette, Theme},
+    widget::{clone_id, dispatch_event, generate_id, next_overlay_order, WidgetContext, WidgetId},
     Widget,
 };
 use std::any::Any;
 use strato_core::{
-    event::{Event, EventResult},
+    event::{Event, EventResult, KeyCode, MouseButton},
     layout::{Constraints, Layout, Size},
-    types::{Color, Point},
+    state::Signal,
+    text::TextAlign,
+    types::{Color, Point, Rect, Transform},
 };
+use strato_renderer::batch::RenderBatch;
+
+/// Horizontal padding applied inside the bar (matches the inner container).
+const BAR_PADDING: f32 = 12.0;
+/// Spacing between inline action widgets.
+const ACTION_SPACING: f32 = 8.0;
+/// Inner padding of the overflow menu panel.
+const MENU_PADDING: f32 = 4.0;
 
 /// A standardized top bar / header widget
 #[derive(Debug)]
@@ -24,6 +35,18 @@ pub struct TopBar {
     pub trailing: Option<Box<dyn Widget>>,
     pub height: f32,
     pub background: Color,
+    pub actions: Vec<Box<dyn Widget>>,
+    pub overflow_icon: String,
+    pub overflow_threshold: Option<usize>,
+
+    // Overflow state computed during layout()
+    action_sizes: Vec<Size>,
+    visible_actions: Option<usize>,
+    palette: ColorPalette,
+    menu_open: Signal<bool>,
+    overlay_order: Signal<u64>,
+    overflow_button_rect: Signal<Rect>,
+    menu_rect: Signal<Rect>,
 }
 
 impl TopBar {
@@ -36,6 +59,16 @@ impl TopBar {
             trailing: None,
             height: 48.0,
             background: Color::rgba(0.1, 0.1, 0.1, 0.95), // Default dark nice background
+            actions: Vec::new(),
+            overflow_icon: "⋯".to_string(),
+            overflow_threshold: None,
+            action_sizes: Vec::new(),
+            visible_actions: None,
+            palette: Theme::default().colors,
+            menu_open: Signal::new(false),
+            overlay_order: Signal::new(0),
+            overflow_button_rect: Signal::new(Rect::default()),
+            menu_rect: Signal::new(Rect::default()),
         }
     }
 
@@ -63,6 +96,166 @@ impl TopBar {
         self
     }
 
+    /// Add an action widget to the right side of the bar.
+    ///
+    /// Actions that don't fit the available width are collapsed into an
+    /// overflow menu.
+    pub fn with_action(mut self, widget: impl Widget + 'static) -> Self {
+        self.actions.push(Box::new(widget));
+        self
+    }
+
+    /// Add several action widgets at once
+    pub fn with_actions(mut self, widgets: Vec<Box<dyn Widget>>) -> Self {
+        self.actions.extend(widgets);
+        self
+    }
+
+    /// Set the label shown on the overflow button
+    pub fn overflow_icon(mut self, icon: impl Into<String>) -> Self {
+        self.overflow_icon = icon.into();
+        self
+    }
+
+    /// Limit how many actions are shown inline before the rest overflow,
+    /// regardless of the available width.
+    pub fn overflow_threshold(mut self, max_inline: usize) -> Self {
+        self.overflow_threshold = Some(max_inline);
+        self
+    }
+
+    /// Number of actions currently shown inline in the bar
+    ///
+    /// Until the bar has been laid out every action counts as inline.
+    pub fn visible_action_count(&self) -> usize {
+        self.visible_actions
+            .map_or(self.actions.len(), |count| count.min(self.actions.len()))
+    }
+
+    /// Number of actions currently collapsed into the overflow menu
+    pub fn overflow_count(&self) -> usize {
+        self.actions.len() - self.visible_action_count()
+    }
+
+    /// Check if the overflow menu is open
+    pub fn is_overflow_open(&self) -> bool {
+        self.menu_open.get()
+    }
+
+    /// Open or close the overflow menu
+    pub fn set_overflow_open(&self, open: bool) {
+        let open = open && self.overflow_count() > 0;
+        if open && !self.menu_open.get() {
+            self.overlay_order.set(next_overlay_order());
+        }
+        self.menu_open.set(open);
+    }
+
+    /// Width of the square overflow button
+    fn overflow_button_size(&self) -> f32 {
+        (self.height - BAR_PADDING).max(16.0)
+    }
+
+    /// Intrinsic width of the leading widget, title and trailing widget.
+    fn title_row_width(&mut self) -> f32 {
+        let constraints = intrinsic_constraints(self.height);
+        let mut width = crate::text::measure_text_width(&self.title, 16.0, 0.0);
+        let mut items = 1;
+        for widget in [self.leading.as_mut(), self.trailing.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            width += widget.layout(constraints).width;
+            items += 1;
+        }
+        width + 16.0 * (items - 1) as f32
+    }
+
+    /// Measure the actions and decide how many of them fit inline.
+    fn layout_actions(&mut self, bar_width: f32) {
+        let constraints = intrinsic_constraints(self.height - BAR_PADDING);
+        self.action_sizes = self
+            .actions
+            .iter_mut()
+            .map(|action| action.layout(constraints))
+            .collect();
+
+        // The title row is centered, so actions only own the space to its right.
+        let content_width = (bar_width - BAR_PADDING * 2.0).max(0.0);
+        let available = ((content_width - self.title_row_width()) / 2.0).max(0.0);
+        let limit = self.overflow_threshold.unwrap_or(usize::MAX);
+
+        let total: f32 = self.action_sizes.iter().map(|s| s.width).sum::<f32>()
+            + ACTION_SPACING * self.actions.len().saturating_sub(1) as f32;
+        if self.actions.len() <= limit && total <= available {
+            self.visible_actions = Some(self.actions.len());
+        } else {
+            // Reserve room for the overflow button before filling in actions.
+            let mut used = self.overflow_button_size();
+            let mut count = 0;
+            for size in &self.action_sizes {
+                if count >= limit || used + ACTION_SPACING + size.width > available {
+                    break;
+                }
+                used += ACTION_SPACING + size.width;
+                count += 1;
+            }
+            self.visible_actions = Some(count);
+        }
+
+        if self.overflow_count() == 0 {
+            self.menu_open.set(false);
+        }
+    }
+
+    /// Rects of the inline actions, laid out right-to-left from the bar edge.
+    fn inline_action_rects(&self, bounds: Rect) -> Vec<Rect> {
+        let mut right = bounds.x + bounds.width - BAR_PADDING;
+        if self.overflow_count() > 0 {
+            right -= self.overflow_button_size() + ACTION_SPACING;
+        }
+
+        let visible = self.visible_action_count();
+        let mut rects = vec![Rect::default(); visible];
+        for index in (0..visible).rev() {
+            let size = self.action_sizes[index];
+            let x = right - size.width;
+            let y = bounds.y + (bounds.height - size.height) / 2.0;
+            rects[index] = Rect::new(x, y, size.width, size.height);
+            right = x - ACTION_SPACING;
+        }
+        rects
+    }
+
+    fn render_overflow_menu(&self, batch: &mut RenderBatch, bounds: Rect) {
+        let button = self.overflow_button_rect.get();
+        let visible = self.visible_action_count();
+        let overflowed = &self.action_sizes[visible..];
+
+        let width = overflowed.iter().map(|s| s.width).fold(0.0, f32::max) + MENU_PADDING * 2.0;
+        let height = overflowed.iter().map(|s| s.height).sum::<f32>() + MENU_PADDING * 2.0;
+        let x = (button.x + button.width - width).max(bounds.x);
+        let menu = Rect::new(x, bounds.y + bounds.height, width, height);
+        self.menu_rect.set(menu);
+
+        let mut overlay = RenderBatch::new();
+        let mut background = self.palette.surface;
+        background.a *= 0.98;
+        overlay.add_rect(menu, background, Transform::identity());
+
+        let mut y = menu.y + MENU_PADDING;
+        for (action, size) in self.actions[visible..].iter().zip(overflowed) {
+            let position = glam::Vec2::new(menu.x + MENU_PADDING, y);
+            action.render(
+                &mut overlay,
+                Layout::new(position, Size::new(width - MENU_PADDING * 2.0, size.height)),
+            );
+            y += size.height;
+        }
+
+        batch.push_overlay(self.overlay_order.get(), overlay);
+    }
+
     fn ensure_inner(&mut self) {
         if self.inner.is_some() {
             return;
@@ -100,50 +293,178 @@ impl TopBar {
     }
 }
 
+/// Constraints that let a widget report its natural width.
+fn intrinsic_constraints(max_height: f32) -> Constraints {
+    Constraints {
+        min_width: 0.0,
+        max_width: f32::INFINITY,
+        min_height: 0.0,
+        max_height: max_height.max(0.0),
+    }
+}
+
 impl Widget for TopBar {
     fn id(&self) -> WidgetId {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         self.ensure_inner();
-        self.inner.as_mut().unwrap().layout(constraints)
+        let size = self.inner.as_mut().unwrap().layout(constraints);
+        self.layout_actions(size.width);
+        size
     }
 
-    fn render(&self, batch: &mut strato_renderer::batch::RenderBatch, layout: Layout) {
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
         if let Some(inner) = &self.inner {
             inner.render(batch, layout);
         }
+
+        let bounds = Rect::new(
+            layout.position.x,
+            layout.position.y,
+            layout.size.width,
+            layout.size.height,
+        );
+
+        for (action, rect) in self.actions.iter().zip(self.inline_action_rects(bounds)) {
+            action.render(
+                batch,
+                Layout::new(
+                    glam::Vec2::new(rect.x, rect.y),
+                    Size::new(rect.width, rect.height),
+                ),
+            );
+        }
+
+        if self.overflow_count() == 0 {
+            self.overflow_button_rect.set(Rect::default());
+            return;
+        }
+
+        let button_size = self.overflow_button_size();
+        let button = Rect::new(
+            bounds.x + bounds.width - BAR_PADDING - button_size,
+            bounds.y + (bounds.height - button_size) / 2.0,
+            button_size,
+            button_size,
+        );
+        self.overflow_button_rect.set(button);
+
+        if self.menu_open.get() {
+            batch.add_rect(
+                button,
+                Color::rgba(1.0, 1.0, 1.0, 0.15),
+                Transform::identity(),
+            );
+        }
+        batch.add_text_aligned(
+            self.overflow_icon.clone(),
+            (
+                button.x + button.width / 2.0,
+                button.y + button.height / 2.0 - 9.0,
+            ),
+            Color::WHITE,
+            18.0,
+            0.0,
+            TextAlign::Center,
+        );
+
+        if self.menu_open.get() {
+            self.render_overflow_menu(batch, bounds);
+        }
     }
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         self.ensure_inner();
-        self.inner.as_mut().unwrap().handle_event(event)
+
+        if self.menu_open.get() {
+            match event {
+                Event::KeyDown(key) if key.key_code == KeyCode::Escape => {
+                    self.menu_open.set(false);
+                    return EventResult::Handled;
+                }
+                Event::MouseDown(mouse) | Event::MouseUp(mouse) | Event::MouseMove(mouse) => {
+                    let point = Point::new(mouse.position.x, mouse.position.y);
+                    if self.menu_rect.get().contains(point) {
+                        let visible = self.visible_action_count();
+                        for action in &mut self.actions[visible..] {
+                            if dispatch_event(action.as_mut(), event) == EventResult::Handled {
+                                if matches!(event, Event::MouseUp(_)) {
+                                    self.menu_open.set(false);
+                                }
+                                return EventResult::Handled;
+                            }
+                        }
+                        return EventResult::Handled;
+                    }
+                    if let Event::MouseDown(_) = event {
+                        // Clicking anywhere outside the menu dismisses it
+                        self.menu_open.set(false);
+                        if self.overflow_button_rect.get().contains(point) {
+                            return EventResult::Handled;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if let Event::MouseDown(mouse) = event {
+            let point = Point::new(mouse.position.x, mouse.position.y);
+            if mouse.button == Some(MouseButton::Left)
+                && self.overflow_count() > 0
+                && self.overflow_button_rect.get().contains(point)
+            {
+                self.set_overflow_open(true);
+                return EventResult::Handled;
+            }
+        }
+
+        let visible = self.visible_action_count();
+        for action in &mut self.actions[..visible] {
+            if dispatch_event(action.as_mut(), event) == EventResult::Handled {
+                return EventResult::Handled;
+            }
+        }
+
+        dispatch_event(self.inner.as_mut().unwrap().as_mut(), event)
+    }
+
+    fn overlay_order(&self) -> Option<u64> {
+        self.menu_open.get().then(|| self.overlay_order.get())
     }
 
     fn update(&mut self, ctx: &WidgetContext) {
-        if let Some(inner) = &mut self.inner {
-            inner.update(ctx);
-        }
+        self.palette = ctx.theme.colors.clone();
     }
 
     fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        let mut children: Vec<&(dyn Widget + '_)> = Vec::new();
         if let Some(inner) = &self.inner {
-            vec![inner.as_ref()]
-        } else {
-            vec![]
+            children.push(inner.as_ref());
         }
+        children.extend(self.actions.iter().map(|a| a.as_ref()));
+        children
     }
 
     fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        let mut children: Vec<&mut (dyn Widget + '_)> = Vec::new();
         if let Some(inner) = &mut self.inner {
-            vec![inner.as_mut()]
-        } else {
-            vec![]
+            children.push(inner.as_mut());
+        }
+        for action in &mut self.actions {
+            children.push(action.as_mut());
         }
+        children
     }
 
     fn hit_test(&self, point: Point, layout: Layout) -> bool {
+        if self.menu_open.get() && self.menu_rect.get().contains(point) {
+            return true;
+        }
         if let Some(inner) = &self.inner {
             inner.hit_test(point, layout)
         } else {
@@ -160,13 +481,103 @@ impl Widget for TopBar {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Self {
-            id: generate_id(), // New ID
-            inner: None,       // Reset inner to force rebuild/fresh state
+            id: clone_id(self.id),
+            inner: None, // Reset inner to force rebuild/fresh state
             title: self.title.clone(),
             leading: self.leading.as_ref().map(|w| w.clone_widget()),
             trailing: self.trailing.as_ref().map(|w| w.clone_widget()),
             height: self.height,
             background: self.background,
+            actions: self.actions.iter().map(|a| a.clone_widget()).collect(),
+            overflow_icon: self.overflow_icon.clone(),
+            overflow_threshold: self.overflow_threshold,
+            action_sizes: Vec::new(),
+            visible_actions: None,
+            palette: self.palette.clone(),
+            menu_open: Signal::new(false),
+            overlay_order: Signal::new(0),
+            overflow_button_rect: Signal::new(Rect::default()),
+            menu_rect: Signal::new(Rect::default()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::button::Button;
+    use crate::test_util::{Probe, WidgetTester};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn bar_with_actions(count: usize) -> TopBar {
+        (0..count).fold(TopBar::new("Title".to_string()), |bar, i| {
+            bar.with_action(Button::new(format!("Action {}", i)))
         })
     }
+
+    #[test]
+    fn test_actions_fit_inline() {
+        let mut bar = bar_with_actions(2);
+        bar.layout(Constraints::loose(1200.0, 48.0));
+        assert_eq!(bar.visible_action_count(), 2);
+        assert_eq!(bar.overflow_count(), 0);
+    }
+
+    #[test]
+    fn test_actions_overflow_when_narrow() {
+        let mut bar = bar_with_actions(6);
+        bar.layout(Constraints::loose(400.0, 48.0));
+        assert!(bar.overflow_count() > 0);
+        assert_eq!(bar.visible_action_count() + bar.overflow_count(), 6);
+    }
+
+    #[test]
+    fn test_actions_count_as_inline_before_layout() {
+        let bar = bar_with_actions(3);
+        assert_eq!(bar.visible_action_count(), 3);
+        assert_eq!(bar.overflow_count(), 0);
+    }
+
+    #[test]
+    fn test_overflow_threshold() {
+        let mut bar = bar_with_actions(4).overflow_threshold(1);
+        bar.layout(Constraints::loose(2000.0, 48.0));
+        assert_eq!(bar.visible_action_count(), 1);
+        assert_eq!(bar.overflow_count(), 3);
+    }
+
+    #[test]
+    fn test_update_pass_advances_actions_once() {
+        let long_presses = Arc::new(parking_lot::Mutex::new(0));
+        let counter = long_presses.clone();
+        let bar = TopBar::new("Title".to_string()).with_action(
+            Button::new("Hold")
+                .on_long_press(Duration::from_millis(500), move || *counter.lock() += 1),
+        );
+        let mut tester = WidgetTester::new(bar, 800.0, 48.0);
+        let button = tester.layout_tree().find_by_name("Button")[0]
+            .bounds
+            .unwrap();
+
+        tester.press(button.x + 5.0, button.y + 5.0, MouseButton::Left);
+        tester.advance(0.3);
+        assert_eq!(*long_presses.lock(), 0);
+        tester.advance(0.3);
+        assert_eq!(*long_presses.lock(), 1);
+    }
+
+    #[test]
+    fn test_disabled_actions_receive_no_events() {
+        let log = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let bar = TopBar::new("Title".to_string())
+            .with_action(Probe::new("enabled", &log))
+            .with_action(Probe::new("disabled", &log).enabled(false));
+        let mut tester = WidgetTester::new(bar, 1200.0, 48.0);
+
+        tester.key_down(KeyCode::A);
+
+        assert!(log.lock().contains(&"event enabled".to_string()));
+        assert!(!log.lock().contains(&"event disabled".to_string()));
+    }
 }
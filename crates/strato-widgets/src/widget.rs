@@ -1,11 +1,18 @@
 //! Base widget trait and common functionality
 
+use crate::reconcile::ChildSlots;
+use parking_lot::RwLock;
 use std::any::Any;
+use std::collections::HashSet;
 use std::fmt::Debug;
+use std::sync::OnceLock;
 use strato_core::{
     event::{Event, EventResult},
-    layout::{Constraints, Layout, Size},
-    types::Point, // Removed unused Color and Rect imports
+    hit_test::{hit_test, LayoutTree},
+    input::InputState,
+    layout::{Constraints, Layout, LayoutCache, Size},
+    types::{Point, Rect},
+    window::CursorIcon,
 };
 use strato_core::taffy_layout::TaffyWidget;
 use strato_renderer::batch::RenderBatch;
@@ -30,16 +37,92 @@ pub struct WidgetContext<'a> {
     pub is_focused: bool,
     pub is_hovered: bool,
     pub delta_time: f32,
+    /// Input held at the start of the frame; events remain the
+    /// authoritative record, see [`strato_core::input`]
+    pub input: InputState,
+}
+
+/// Type-erased access to a widget, implemented for every `'static` type
+///
+/// Backs the default [`Widget::as_any`] and [`Widget::as_any_mut`].
+#[doc(hidden)]
+pub trait AsAny {
+    fn any_ref(&self) -> &dyn Any;
+    fn any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: Any> AsAny for T {
+    fn any_ref(&self) -> &dyn Any {
+        self
+    }
+
+    fn any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
 }
 
 /// Base trait for all widgets
-pub trait Widget: Debug + Send + Sync {
+///
+/// # Implementing a widget
+///
+/// Third-party widgets import everything they need from
+/// [`widget_prelude`](crate::widget_prelude) and implement four methods:
+///
+/// - [`id`](Self::id), returning an ID from [`generate_id`] kept for the
+///   widget's lifetime,
+/// - [`layout`](Self::layout), returning the widget's size within the
+///   constraints and laying out its children,
+/// - [`render`](Self::render), drawing the widget into the batch at the
+///   laid out position,
+/// - [`clone_widget`](Self::clone_widget), usually `self.clone()` with its
+///   ID replaced by [`clone_id`]. It has no default since not every widget
+///   is `Clone`.
+///
+/// Everything else has a default: widgets ignore events, have no children
+/// and no cursor preference, and are always enabled. Override
+/// [`handle_event`](Self::handle_event) and [`update`](Self::update) for
+/// interactive or animated widgets, and [`children`](Self::children) and
+/// [`children_mut`](Self::children_mut) for containers so that events,
+/// updates and tree walks reach their children. Hidden methods are for the
+/// Taffy integration and may change.
+pub trait Widget: Debug + Send + Sync + AsAny {
     /// Get the widget's unique ID
     fn id(&self) -> WidgetId;
 
+    /// Replace the widget's ID, e.g. with a [`keyed_id`]
+    ///
+    /// Widgets that don't override this keep the ID they were created with.
+    fn set_id(&mut self, _id: WidgetId) {}
+
     /// Calculate the widget's size given constraints
     fn layout(&mut self, constraints: Constraints) -> Size;
 
+    /// The size the widget would take within `constraints`, without laying
+    /// it out
+    ///
+    /// For parents that size one child by the others', e.g. a flex basis or
+    /// a table column, before assigning final bounds. The default answers
+    /// from the [`layout_cache`](Self::layout_cache) when it holds these
+    /// constraints and lays out a clone otherwise; containers override it to
+    /// measure their children instead of cloning them.
+    fn measure(&self, constraints: Constraints) -> Size {
+        if let Some(size) = self
+            .layout_cache()
+            .and_then(|cache| cache.cached(constraints))
+        {
+            return size;
+        }
+        self.clone_widget().layout(constraints)
+    }
+
+    /// The cache memoizing this widget's layout, if it keeps one
+    ///
+    /// Widgets without one are laid out on every pass, and so are the
+    /// widgets containing them.
+    fn layout_cache(&self) -> Option<&LayoutCache> {
+        None
+    }
+
     /// Render the widget
     fn render(&self, batch: &mut RenderBatch, layout: Layout);
 
@@ -51,6 +134,68 @@ pub trait Widget: Debug + Send + Sync {
     /// Update the widget state
     fn update(&mut self, _ctx: &WidgetContext) {}
 
+    /// Called when the widget enters the tree, before its first update
+    ///
+    /// Start animations, timers and subscriptions here rather than in the
+    /// constructor, so a widget built but never shown doesn't run them. See
+    /// [`update_tree`] for when widgets count as entering and leaving.
+    fn on_mount(&mut self, _ctx: &WidgetContext) {}
+
+    /// Called when the widget leaves the tree, before it is dropped
+    ///
+    /// Stop whatever [`on_mount`](Self::on_mount) started.
+    fn on_unmount(&mut self, _ctx: &WidgetContext) {}
+
+    /// Children the widget still owns but took out of the tree, like the
+    /// content of a closed [`Modal`](crate::modal::Modal)
+    ///
+    /// [`update_tree`] unmounts those it had mounted. A widget dropping
+    /// children it replaced, like [`Show`](crate::show::Show), lists them
+    /// here until its next [`update`](Self::update) so they can be
+    /// unmounted first; widgets whose children all stay keep the default.
+    fn detached_children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        Vec::new()
+    }
+
+    /// Whether the widget accepts input; disabled widgets receive no events
+    fn is_enabled(&self) -> bool {
+        true
+    }
+
+    /// Whether the widget has keyboard focus
+    fn is_focused(&self) -> bool {
+        false
+    }
+
+    /// Whether Tab can move keyboard focus to the widget right now
+    fn is_focusable(&self) -> bool {
+        false
+    }
+
+    /// The widget's place in the Tab order, see [`focus`](crate::focus)
+    ///
+    /// `None` keeps it in tree order.
+    fn tab_index(&self) -> Option<i32> {
+        None
+    }
+
+    /// Give or take keyboard focus, e.g. when Tab moves it
+    ///
+    /// Focusable widgets override this along with
+    /// [`is_focusable`](Self::is_focusable).
+    fn set_focused(&mut self, _focused: bool) {}
+
+    /// The order of the overlay the widget has open, `None` while it has
+    /// none
+    ///
+    /// Popups and modal layers return the order they took from
+    /// [`next_overlay_order`] when they opened, so that
+    /// [`dispatch_root_event`] gives them pointer events before the widgets
+    /// they cover.
+    fn overlay_order(&self) -> Option<u64> {
+        None
+    }
+
     /// Get children widgets
     fn children(&self) -> Vec<&(dyn Widget + '_)> {
         vec![]
@@ -61,27 +206,74 @@ pub trait Widget: Debug + Send + Sync {
         vec![]
     }
 
+    /// Take the props of `new`, a widget of the same type built for this
+    /// one's place in the tree, keeping this widget's ID and state
+    ///
+    /// Returns whether any prop changed, or `None` when the widget can't
+    /// take them and [`reconcile`](crate::reconcile::reconcile) replaces it
+    /// with `new`, the default. Children are left to
+    /// [`child_slots`](Self::child_slots).
+    fn apply_props(&mut self, _new: &dyn Widget) -> Option<bool> {
+        None
+    }
+
+    /// The owned children, for [`reconcile`](crate::reconcile::reconcile)
+    /// to match against those of a new widget
+    ///
+    /// Widgets returning `None`, the default, keep their children.
+    fn child_slots(&mut self) -> Option<ChildSlots<'_>> {
+        None
+    }
+
+    /// The cursor to show while the pointer is at `point`
+    ///
+    /// Return `None` when `point` is outside the widget or it has no
+    /// preference, and the cursor of the widget beneath is used instead.
+    fn cursor_icon(&self, _point: Point) -> Option<CursorIcon> {
+        None
+    }
+
     /// Check if point is inside widget
+    #[doc(hidden)]
     fn hit_test(&self, point: Point, layout: Layout) -> bool {
         layout.contains(point.to_vec2())
     }
 
     /// Get widget as Any for downcasting
-    fn as_any(&self) -> &dyn Any;
+    fn as_any(&self) -> &dyn Any {
+        self.any_ref()
+    }
 
     /// Get mutable widget as Any for downcasting
-    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self.any_mut()
+    }
 
     /// Clone the widget
+    ///
+    /// The clone shares the original's event handlers and validators, which
+    /// are reference counted, and its configuration and bound signals. It
+    /// keeps a keyed ID and gets a fresh one otherwise, see [`clone_id`].
+    /// Transient interaction state (hover, press, focus, an ongoing drag or
+    /// gesture) is not carried over.
     fn clone_widget(&self) -> Box<dyn Widget>;
 
+    /// The widget's type without its module path or generics, e.g.
+    /// `Button`, for debugging tools and layout snapshots
+    fn type_name(&self) -> &'static str {
+        let name = std::any::type_name::<Self>();
+        let name = name.split('<').next().unwrap_or(name);
+        name.rsplit("::").next().unwrap_or(name)
+    }
+
     /// Get the widget as TaffyWidget if supported
-    /// Get the widget as TaffyWidget if supported
+    #[doc(hidden)]
     fn as_taffy(&self) -> Option<&dyn TaffyWidget> {
         None
     }
 
     /// Render using Taffy layout
+    #[doc(hidden)]
     fn render_taffy(
         &self,
         batch: &mut RenderBatch,
@@ -99,13 +291,384 @@ pub trait Widget: Debug + Send + Sync {
     }
 }
 
+/// Route an event to a widget unless it is disabled
+///
+/// Pointer events skip widgets with an open overlay: [`dispatch_root_event`]
+/// gives those the event before the rest of the tree, so they would
+/// otherwise see it twice.
+pub fn dispatch_event(widget: &mut (dyn Widget + '_), event: &Event) -> EventResult {
+    let delivered = is_pointer_event(event) && widget.overlay_order().is_some();
+    if widget.is_enabled() && !delivered {
+        widget.handle_event(event)
+    } else {
+        EventResult::Ignored
+    }
+}
+
+fn is_pointer_event(event: &Event) -> bool {
+    matches!(
+        event,
+        Event::MouseDown(_)
+            | Event::MouseUp(_)
+            | Event::MouseMove(_)
+            | Event::MouseWheel { .. }
+            | Event::Gesture(_)
+    )
+}
+
+/// Deliver an event from the window to the tree at `root`
+///
+/// Pointer events go to the widgets with an open overlay first, the most
+/// recently opened first, see [`Widget::overlay_order`]. An overlay owner
+/// that handles the event stops it there, so a modal layer or a popup can
+/// consume clicks meant for the widgets beneath it. Events no overlay
+/// handled then go through the tree as usual; [`dispatch_event`] skips the
+/// overlay owners, which have all seen it.
+/// Other events are dispatched as by [`dispatch_event`].
+pub fn dispatch_root_event(root: &mut (dyn Widget + '_), event: &Event) -> EventResult {
+    if !is_pointer_event(event) {
+        return dispatch_event(root, event);
+    }
+
+    let mut overlays = Vec::new();
+    collect_overlays(root, &mut overlays);
+    // Stable, so owners of equal orders go in tree order
+    overlays.sort_by_key(|&(order, _)| std::cmp::Reverse(order));
+
+    for &(_, id) in &overlays {
+        if deliver_to(root, id, event) == Some(EventResult::Handled) {
+            return EventResult::Handled;
+        }
+    }
+    // Every open overlay saw the event above, so the tree pass skips them
+    dispatch_event(root, event)
+}
+
+fn collect_overlays(widget: &dyn Widget, overlays: &mut Vec<(u64, WidgetId)>) {
+    if !widget.is_enabled() {
+        return;
+    }
+    if let Some(order) = widget.overlay_order() {
+        overlays.push((order, widget.id()));
+    }
+    for child in widget.children() {
+        collect_overlays(child, overlays);
+    }
+}
+
+fn deliver_to(
+    widget: &mut (dyn Widget + '_),
+    id: WidgetId,
+    event: &Event,
+) -> Option<EventResult> {
+    if widget.id() == id {
+        // Bypasses dispatch_event, which holds pointer events back from
+        // widgets with an open overlay
+        return Some(widget.handle_event(event));
+    }
+    widget
+        .children_mut()
+        .into_iter()
+        .find_map(|child| deliver_to(child, id, event))
+}
+
+/// The widgets of a tree that are mounted, kept from one update pass to the
+/// next
+///
+/// [`update_tree`] compares the children it finds against it: children not
+/// in it entered the tree since the last pass and are mounted, and
+/// [detached](Widget::detached_children_mut) ones still in it left and are
+/// unmounted. Widget ids must be unique within the tree.
+#[derive(Debug, Default)]
+pub struct MountedWidgets {
+    ids: HashSet<WidgetId>,
+}
+
+impl MountedWidgets {
+    /// An empty record, for a tree not mounted yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the widget with `id` is mounted
+    pub fn contains(&self, id: WidgetId) -> bool {
+        self.ids.contains(&id)
+    }
+
+    /// Whether no widget is mounted
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+}
+
+/// Call [`Widget::update`] on `widget` and every widget below it
+///
+/// Along the way, children that entered the tree since the last pass are
+/// mounted before their first update and detached ones that were mounted
+/// are unmounted, see [`MountedWidgets`]. The tree at `widget` itself must
+/// have been mounted with [`mount_tree`].
+pub fn update_tree(
+    widget: &mut (dyn Widget + '_),
+    ctx: &WidgetContext,
+    mounted: &mut MountedWidgets,
+) {
+    // Children replaced since the last pass, before the update drops them
+    unmount_detached(widget, ctx, mounted);
+    widget.update(ctx);
+    // Children replaced in the update leave before their successors, which
+    // may have the same ids, are mounted in the same pass
+    unmount_detached(widget, ctx, mounted);
+    for child in widget.children_mut() {
+        if !mounted.contains(child.id()) {
+            mount_tree(child, ctx, mounted);
+        }
+        update_tree(child, ctx, mounted);
+    }
+}
+
+/// Call [`Widget::on_mount`] on `widget` and every widget below it, parents
+/// first, recording them in `mounted`
+pub fn mount_tree(
+    widget: &mut (dyn Widget + '_),
+    ctx: &WidgetContext,
+    mounted: &mut MountedWidgets,
+) {
+    mounted.ids.insert(widget.id());
+    widget.on_mount(ctx);
+    for child in widget.children_mut() {
+        mount_tree(child, ctx, mounted);
+    }
+}
+
+/// Call [`Widget::on_unmount`] on `widget` and every mounted widget below
+/// it, children first, removing them from `mounted`
+pub fn unmount_tree(
+    widget: &mut (dyn Widget + '_),
+    ctx: &WidgetContext,
+    mounted: &mut MountedWidgets,
+) {
+    for child in widget.children_mut() {
+        if mounted.contains(child.id()) {
+            unmount_tree(child, ctx, mounted);
+        }
+    }
+    // Detached children not unmounted yet are leaving too
+    unmount_detached(widget, ctx, mounted);
+    mounted.ids.remove(&widget.id());
+    widget.on_unmount(ctx);
+}
+
+fn unmount_detached(
+    widget: &mut (dyn Widget + '_),
+    ctx: &WidgetContext,
+    mounted: &mut MountedWidgets,
+) {
+    for detached in widget.detached_children_mut() {
+        if mounted.contains(detached.id()) {
+            unmount_tree(detached, ctx, mounted);
+        }
+    }
+}
+
+/// Lay out the child of a widget whose layout is memoized in `cache`
+///
+/// Links the child's cache to `cache`, so the child being marked dirty marks
+/// the parent too. A child without a cache may change size at any time, so
+/// the parent is left dirty and laid out again on the next pass.
+pub fn layout_child(
+    cache: &LayoutCache,
+    child: &mut (dyn Widget + '_),
+    constraints: Constraints,
+) -> Size {
+    match child.layout_cache() {
+        Some(child_cache) => cache.adopt(child_cache),
+        None => cache.mark_dirty(),
+    }
+    child.layout(constraints)
+}
+
+/// The cursor for `point` in the tree at `root`
+///
+/// The deepest widget with a preference wins, later children before earlier
+/// ones. Disabled subtrees are skipped.
+pub fn cursor_icon_at(root: &dyn Widget, point: Point) -> Option<CursorIcon> {
+    if !root.is_enabled() {
+        return None;
+    }
+    root.children()
+        .into_iter()
+        .rev()
+        .find_map(|child| cursor_icon_at(child, point))
+        .or_else(|| root.cursor_icon(point))
+}
+
+/// The focused widget in the tree at `root`, if any
+///
+/// Only enabled subtrees are searched, in depth-first order.
+pub fn focused_widget(root: &dyn Widget) -> Option<WidgetId> {
+    if !root.is_enabled() {
+        return None;
+    }
+    if root.is_focused() {
+        return Some(root.id());
+    }
+    root.children().into_iter().find_map(focused_widget)
+}
+
+/// Record the bounds a widget was laid out in, for hit testing
+///
+/// Call from `render`, where the widget's final position is known.
+pub fn register_bounds(id: WidgetId, bounds: Rect) {
+    hit_test().register(strato_core::widget::WidgetId(id), bounds);
+}
+
+/// Whether `point` is inside the bounds a widget last registered
+pub fn hit(id: WidgetId, point: Point) -> bool {
+    hit_test().contains(strato_core::widget::WidgetId(id), point)
+}
+
+/// The widgets under `point` in the tree at `root`, deepest first
+pub fn widgets_at(root: &dyn Widget, point: Point) -> Vec<WidgetId> {
+    hit_test()
+        .widgets_at(
+            root,
+            point,
+            |widget| strato_core::widget::WidgetId(widget.id()),
+            |widget| widget.children(),
+        )
+        .into_iter()
+        .map(|id| id.0)
+        .collect()
+}
+
+/// Where every widget in the tree at `root` was drawn in the last frame
+///
+/// Bounds come from [`register_bounds`]; widgets that don't register them
+/// appear without bounds.
+pub fn layout_tree(root: &dyn Widget) -> LayoutTree {
+    hit_test().layout_tree(
+        root,
+        |widget| strato_core::widget::WidgetId(widget.id()),
+        |widget| widget.type_name().to_string(),
+        |widget| widget.children(),
+    )
+}
+
 /// Generate a unique widget ID
+///
+/// IDs count up from zero in creation order, so they differ between runs and
+/// between trees. Use [`keyed_id`] or [`assign_path_ids`] for stable ones.
 pub fn generate_id() -> WidgetId {
     use std::sync::atomic::{AtomicU64, Ordering};
     static COUNTER: AtomicU64 = AtomicU64::new(0);
     COUNTER.fetch_add(1, Ordering::SeqCst)
 }
 
+// Stable IDs are hashes tagged with the top bits, out of the counter's reach
+const STABLE_ID: WidgetId = 1 << 63;
+const KEYED_ID: WidgetId = 1 << 62;
+const ID_TAGS: WidgetId = STABLE_ID | KEYED_ID;
+
+/// FNV-1a, chosen over `DefaultHasher` because its output is fixed
+fn stable_hash(parts: &[&[u8]]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// The stable ID for a widget with `key`, the same in every run
+///
+/// Keys must be unique within a tree, like element IDs in HTML.
+pub fn keyed_id(key: &str) -> WidgetId {
+    (stable_hash(&[key.as_bytes()]) & !ID_TAGS) | STABLE_ID | KEYED_ID
+}
+
+/// Whether `id` came from [`keyed_id`]
+pub fn is_keyed_id(id: WidgetId) -> bool {
+    id & ID_TAGS == ID_TAGS
+}
+
+/// Whether `id` is the same in every run, from [`keyed_id`] or
+/// [`assign_path_ids`]
+pub fn is_stable_id(id: WidgetId) -> bool {
+    id & STABLE_ID != 0
+}
+
+/// The ID for a clone of the widget with `id`
+///
+/// A keyed ID names the widget's role rather than one instance, so clones
+/// keep it; any other ID is replaced with a fresh one so hit testing and
+/// focus can tell the original and the clone apart.
+pub fn clone_id(id: WidgetId) -> WidgetId {
+    if is_keyed_id(id) {
+        id
+    } else {
+        generate_id()
+    }
+}
+
+/// How widget IDs are assigned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum IdMode {
+    /// Keep the creation-order IDs from [`generate_id`]
+    #[default]
+    AutoIncrement,
+    /// Derive IDs from the widget's position in the tree every frame
+    TreePath,
+}
+
+static ID_MODE: OnceLock<RwLock<IdMode>> = OnceLock::new();
+
+fn id_mode_slot() -> &'static RwLock<IdMode> {
+    ID_MODE.get_or_init(|| RwLock::new(IdMode::default()))
+}
+
+/// How the application assigns widget IDs
+pub fn id_mode() -> IdMode {
+    *id_mode_slot().read()
+}
+
+/// Change how the application assigns widget IDs
+pub fn set_id_mode(mode: IdMode) {
+    *id_mode_slot().write() = mode;
+}
+
+/// Give every unkeyed widget in the tree at `root` an ID from its tree path
+///
+/// A widget's ID is derived from its parent's ID and its index among its
+/// siblings, so the same tree gets the same IDs in every run. Keyed widgets
+/// keep their ID, which anchors their subtree wherever they move.
+pub fn assign_path_ids(root: &mut (dyn Widget + '_)) {
+    if !is_keyed_id(root.id()) {
+        root.set_id((stable_hash(&[]) & !ID_TAGS) | STABLE_ID);
+    }
+    assign_child_path_ids(root);
+}
+
+fn assign_child_path_ids(parent: &mut (dyn Widget + '_)) {
+    let parent_id = parent.id();
+    for (index, child) in parent.children_mut().into_iter().enumerate() {
+        if !is_keyed_id(child.id()) {
+            let hash = stable_hash(&[&parent_id.to_le_bytes(), &(index as u64).to_le_bytes()]);
+            child.set_id((hash & !ID_TAGS) | STABLE_ID);
+        }
+        assign_child_path_ids(child);
+    }
+}
+
+/// Take the overlay order for a popup that is opening
+///
+/// Pass the value to [`RenderBatch::push_overlay`] every frame the popup is
+/// drawn, so popups stack in the order they were opened.
+pub fn next_overlay_order() -> u64 {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    static COUNTER: AtomicU64 = AtomicU64::new(1);
+    COUNTER.fetch_add(1, Ordering::SeqCst)
+}
+
 /// Base widget implementation helper
 #[derive(Debug, Clone)]
 pub struct BaseWidget {
@@ -135,6 +698,11 @@ impl BaseWidget {
         self.id
     }
 
+    /// Replace the widget ID
+    pub fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     /// Set minimum size
     pub fn with_min_size(mut self, width: f32, height: f32) -> Self {
         self.min_size = Size::new(width, height);
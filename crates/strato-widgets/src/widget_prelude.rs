@@ -0,0 +1,86 @@
+//! Everything needed to write a widget outside this crate
+//!
+//! This is the supported surface for third-party widgets: the [`Widget`]
+//! trait and its helpers, plus the core and renderer types its methods
+//! take, so a widget crate depends on `strato-widgets` alone rather than on
+//! internal paths of `strato-core` and `strato-renderer`. See [`Widget`] for
+//! which methods to implement.
+//!
+//! ```
+//! use strato_widgets::widget_prelude::*;
+//!
+//! /// A colored square that toggles between two colors when clicked
+//! #[derive(Debug, Clone)]
+//! struct Swatch {
+//!     id: WidgetId,
+//!     colors: [Color; 2],
+//!     selected: usize,
+//! }
+//!
+//! impl Widget for Swatch {
+//!     fn id(&self) -> WidgetId {
+//!         self.id
+//!     }
+//!
+//!     fn layout(&mut self, constraints: Constraints) -> Size {
+//!         constraints.constrain(Size::new(24.0, 24.0))
+//!     }
+//!
+//!     fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+//!         let bounds = Rect::new(
+//!             layout.position.x,
+//!             layout.position.y,
+//!             layout.size.width,
+//!             layout.size.height,
+//!         );
+//!         register_bounds(self.id, bounds);
+//!         batch.add_rect(bounds, self.colors[self.selected], Transform::identity());
+//!     }
+//!
+//!     fn handle_event(&mut self, event: &Event) -> EventResult {
+//!         match event {
+//!             Event::MouseDown(mouse) if hit(self.id, mouse.position.into()) => {
+//!                 self.selected = 1 - self.selected;
+//!                 EventResult::Handled
+//!             }
+//!             _ => EventResult::Ignored,
+//!         }
+//!     }
+//!
+//!     fn clone_widget(&self) -> Box<dyn Widget> {
+//!         let mut clone = self.clone();
+//!         clone.id = clone_id(self.id);
+//!         Box::new(clone)
+//!     }
+//! }
+//!
+//! let swatch = Swatch {
+//!     id: generate_id(),
+//!     colors: [Color::rgb(1.0, 0.0, 0.0), Color::rgb(0.0, 0.0, 1.0)],
+//!     selected: 0,
+//! };
+//! let boxed: Box<dyn Widget> = swatch.clone_widget();
+//! assert!(boxed.as_any().downcast_ref::<Swatch>().is_some());
+//! ```
+
+// The widget trait and the helpers widgets call
+pub use crate::builder::IntoChild;
+pub use crate::reconcile::ChildSlots;
+pub use crate::theme::Theme;
+pub use crate::widget::{
+    clone_id, dispatch_event, generate_id, hit, keyed_id, layout_child, register_bounds, Widget,
+    WidgetContext, WidgetId, WidgetState,
+};
+
+// Types taken and returned by widget methods
+pub use glam::Vec2;
+pub use std::any::Any;
+pub use strato_core::event::{
+    Event, EventResult, KeyCode, KeyboardEvent, Modifiers, MouseButton, MouseEvent,
+};
+pub use strato_core::layout::{Constraints, EdgeInsets, Layout, LayoutCache, Size};
+pub use strato_core::state::Signal;
+pub use strato_core::text::TextAlign;
+pub use strato_core::types::{Color, Point, Rect, Transform};
+pub use strato_core::window::CursorIcon;
+pub use strato_renderer::batch::RenderBatch;
@@ -1,5 +1,5 @@
 //! Wrap widget for flow layout
-use crate::widget::{generate_id, Widget, WidgetId};
+use crate::widget::{clone_id, dispatch_event, generate_id, Widget, WidgetId};
 use std::any::Any;
 use strato_core::{
     event::{Event, EventResult},
@@ -114,6 +114,10 @@ impl Widget for Wrap {
         self.id
     }
 
+    fn set_id(&mut self, id: WidgetId) {
+        self.id = id;
+    }
+
     fn layout(&mut self, constraints: Constraints) -> Size {
         let engine = strato_core::layout::LayoutEngine::new();
 
@@ -260,7 +264,7 @@ impl Widget for Wrap {
 
     fn handle_event(&mut self, event: &Event) -> EventResult {
         for child in &mut self.children {
-            if child.handle_event(event) == EventResult::Handled {
+            if dispatch_event(child.as_mut(), event) == EventResult::Handled {
                 return EventResult::Handled;
             }
         }
@@ -288,7 +292,7 @@ impl Widget for Wrap {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Wrap {
-            id: generate_id(),
+            id: clone_id(self.id),
             children: self.children.iter().map(|c| c.clone_widget()).collect(),
             direction: self.direction,
             alignment: self.alignment,
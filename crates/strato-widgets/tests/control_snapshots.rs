@@ -52,6 +52,7 @@ fn render_button_with_state(state: ButtonState) -> Vec<String> {
         is_focused: matches!(state, ButtonState::Focused),
         is_hovered: matches!(state, ButtonState::Hovered),
         delta_time: 1.0,
+        input: Default::default(),
     };
     button.update(&ctx);
     button.render(&mut batch, layout);
@@ -77,6 +78,7 @@ fn render_slider_variant(disabled: bool) -> Vec<String> {
         is_focused: false,
         is_hovered: false,
         delta_time: 1.0,
+        input: Default::default(),
     };
     slider.update(&ctx);
     slider.render(&mut batch, layout);
@@ -1,13 +1,15 @@
 use anyhow::Result;
 use std::sync::Arc;
 use strato_renderer::{
-    AllocationStrategy, IntegratedRenderer, RenderContext, RendererBuilder, RendererConfig,
+    AllocationStrategy, CompiledShader, HotReloadEvent, IntegratedRenderer, RenderContext,
+    RendererBuilder, RendererConfig,
 };
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use wgpu::*;
 use winit::{
-    event::{Event, WindowEvent},
+    event::{ElementState, Event, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
+    keyboard::Key,
     window::{Window, WindowBuilder},
 };
 
@@ -25,6 +27,7 @@ struct AdvancedRendererExample {
 
     // State
     frame_count: u64,
+    screenshot_requested: bool,
 }
 
 impl AdvancedRendererExample {
@@ -49,6 +52,7 @@ impl AdvancedRendererExample {
             .with_max_memory_pool_size(256 * 1024 * 1024) // 256MB
             .with_preferred_adapter(PowerPreference::HighPerformance)
             .with_validation(cfg!(debug_assertions))
+            .with_clear_color(strato_core::types::Color::rgb(0.1, 0.2, 0.3))
             .build()
             .await?;
 
@@ -59,11 +63,12 @@ impl AdvancedRendererExample {
 
         let size = window.inner_size();
         let surface_config = SurfaceConfiguration {
-            usage: TextureUsages::RENDER_ATTACHMENT,
+            // COPY_SRC allows capturing screenshots of the swapchain
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
             format: surface.get_capabilities(adapter).formats[0],
             width: size.width,
             height: size.height,
-            present_mode: PresentMode::Fifo,
+            present_mode: renderer.present_mode(),
             alpha_mode: CompositeAlphaMode::Auto,
             view_formats: vec![],
             desired_maximum_frame_latency: 2,
@@ -75,6 +80,10 @@ impl AdvancedRendererExample {
         renderer.initialize().await?;
 
         info!("Renderer initialized successfully");
+        info!(
+            "Present modes: {:?} (press V to toggle vsync, S to save a screenshot)",
+            renderer.supported_present_modes(&surface)
+        );
         info!("GPU: {}", renderer.get_device_info().device_name);
         // info!("Backend: {:?}", renderer.get_device_info().backend);
 
@@ -87,6 +96,7 @@ impl AdvancedRendererExample {
             index_buffer: None,
             render_pipeline: None,
             frame_count: 0,
+            screenshot_requested: false,
         };
 
         // Create example resources
@@ -121,15 +131,23 @@ impl AdvancedRendererExample {
         // Load shader
         let path = std::path::PathBuf::from("examples/advanced_renderer/shaders/triangle.wgsl");
         let stage = strato_renderer::shader::ShaderStage::Vertex;
-        let variant = strato_renderer::shader::ShaderVariant {
-            macros: vec![],
-            features: vec![],
-            optimization_level: 0,
-        };
+        let variant = strato_renderer::shader::ShaderVariant::default();
 
         let shader = self.renderer.load_shader(&path, stage, variant)?;
 
-        // Create render pipeline
+        // Editing the shader while the example runs rebuilds the pipeline
+        self.renderer.enable_shader_hot_reload([&path])?;
+
+        self.render_pipeline = Some(self.create_pipeline(&shader)?);
+
+        self.vertex_buffer = Some(vertex_buffer);
+        self.index_buffer = Some(index_buffer);
+
+        info!("Resources created successfully");
+        Ok(())
+    }
+
+    fn create_pipeline(&self, shader: &CompiledShader) -> Result<RenderPipeline> {
         let render_pipeline_desc = RenderPipelineDescriptor {
             label: Some("Triangle Pipeline"),
             layout: None,
@@ -182,18 +200,31 @@ impl AdvancedRendererExample {
             multiview: None,
         };
 
-        let render_pipeline = self
-            .renderer
-            .device()
-            .device
-            .create_render_pipeline(&render_pipeline_desc);
-        self.render_pipeline = Some(render_pipeline);
-
-        self.vertex_buffer = Some(vertex_buffer);
-        self.index_buffer = Some(index_buffer);
+        self.renderer
+            .try_create_render_pipeline(&render_pipeline_desc)
+    }
 
-        info!("Resources created successfully");
-        Ok(())
+    /// Swap in pipelines built from shaders edited on disk
+    ///
+    /// On any error the previous pipeline keeps rendering.
+    fn apply_shader_reloads(&mut self) {
+        for event in self.renderer.take_shader_reloads() {
+            match event {
+                HotReloadEvent::ShaderReloaded(path, shader) => {
+                    match self.create_pipeline(&shader) {
+                        Ok(pipeline) => {
+                            self.render_pipeline = Some(pipeline);
+                            info!("Reloaded {}", path.display());
+                        }
+                        Err(e) => warn!("Keeping previous pipeline for {}: {}", path.display(), e),
+                    }
+                }
+                HotReloadEvent::ReloadFailed(path, e) => {
+                    warn!("Keeping previous pipeline for {}: {}", path.display(), e)
+                }
+                _ => {}
+            }
+        }
     }
 
     fn render(&mut self) -> Result<()> {
@@ -212,6 +243,7 @@ impl AdvancedRendererExample {
 
         // Begin frame
         let mut render_context = self.renderer.begin_frame()?;
+        self.apply_shader_reloads();
 
         // Get surface texture
         let output = self.surface.get_current_texture()?;
@@ -220,18 +252,14 @@ impl AdvancedRendererExample {
             .create_view(&TextureViewDescriptor::default());
 
         // Begin render pass
+        let clear = render_context.clear_value(self.surface_config.format);
         let mut render_pass = render_context.begin_render_pass(&RenderPassDescriptor {
             label: Some("Main Render Pass"),
             color_attachments: &[Some(RenderPassColorAttachment {
                 view: &view,
                 resolve_target: None,
                 ops: Operations {
-                    load: LoadOp::Clear(Color {
-                        r: 0.1,
-                        g: 0.2,
-                        b: 0.3,
-                        a: 1.0,
-                    }),
+                    load: LoadOp::Clear(clear),
                     store: StoreOp::Store,
                 },
             })],
@@ -253,12 +281,24 @@ impl AdvancedRendererExample {
         drop(render_pass);
         render_context.end_render_pass();
 
+        // Capture the frame after it has been drawn
+        if std::mem::take(&mut self.screenshot_requested) {
+            self.renderer
+                .capture_frame(&mut render_context, &output.texture)?;
+        }
+
         // End frame
         self.renderer.end_frame(render_context)?;
 
         // Present
         output.present();
 
+        // Save a finished capture without waiting on the GPU
+        if let Some(image) = self.renderer.take_captured_frame() {
+            image?.save_png("screenshot.png")?;
+            info!("Saved screenshot.png");
+        }
+
         self.frame_count += 1;
 
         // Print stats every 60 frames
@@ -280,6 +320,17 @@ impl AdvancedRendererExample {
         Ok(())
     }
 
+    fn toggle_vsync(&mut self) {
+        let vsync_enabled = !matches!(
+            self.renderer.present_mode(),
+            PresentMode::AutoNoVsync | PresentMode::Immediate | PresentMode::Mailbox
+        );
+        let mode = self
+            .renderer
+            .set_vsync(&self.surface, &mut self.surface_config, !vsync_enabled);
+        info!("Present mode: {:?}", mode);
+    }
+
     fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) -> Result<()> {
         if new_size.width > 0 && new_size.height > 0 {
             self.surface_config.width = new_size.width;
@@ -329,6 +380,18 @@ async fn run() -> Result<()> {
                         error!("Resize error: {}", e);
                     }
                 }
+                WindowEvent::KeyboardInput { event, .. }
+                    if event.state == ElementState::Pressed
+                        && event.logical_key == Key::Character("v".into()) =>
+                {
+                    example.toggle_vsync();
+                }
+                WindowEvent::KeyboardInput { event, .. }
+                    if event.state == ElementState::Pressed
+                        && event.logical_key == Key::Character("s".into()) =>
+                {
+                    example.screenshot_requested = true;
+                }
                 WindowEvent::RedrawRequested => {
                     if let Err(e) = example.render() {
                         error!("Render error: {}", e);
@@ -1,3 +1,4 @@
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use strato_core::inspector::{inspector, InspectorConfig};
 use strato_core::state::Signal;
@@ -6,8 +7,79 @@ use strato_platform::{ApplicationBuilder, WindowBuilder};
 use strato_widgets::animation::{Curve, KeyframeAnimation, Parallel, Timeline, Tween};
 use strato_widgets::prelude::*;
 use strato_widgets::scroll_view::ScrollView;
+use strato_widgets::widget_prelude::{
+    dispatch_event, generate_id, Constraints, Event, EventResult, Layout, RenderBatch, Size,
+    WidgetContext, WidgetId,
+};
 use strato_widgets::InspectorOverlay;
 
+/// Plays a timeline while its child is in the tree
+///
+/// The timeline starts when the player is mounted and pauses when it is
+/// unmounted, so it doesn't keep driving its signals once hidden.
+#[derive(Debug)]
+struct TimelinePlayer {
+    id: WidgetId,
+    timeline: Arc<Mutex<Timeline>>,
+    child: Box<dyn Widget>,
+}
+
+impl TimelinePlayer {
+    fn new(timeline: Timeline, child: impl Widget + 'static) -> Self {
+        Self {
+            id: generate_id(),
+            timeline: Arc::new(Mutex::new(timeline)),
+            child: Box::new(child),
+        }
+    }
+}
+
+impl Widget for TimelinePlayer {
+    fn id(&self) -> WidgetId {
+        self.id
+    }
+
+    fn layout(&mut self, constraints: Constraints) -> Size {
+        self.child.layout(constraints)
+    }
+
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        self.child.render(batch, layout);
+    }
+
+    fn handle_event(&mut self, event: &Event) -> EventResult {
+        dispatch_event(self.child.as_mut(), event)
+    }
+
+    fn on_mount(&mut self, _ctx: &WidgetContext) {
+        self.timeline.lock().unwrap().play();
+    }
+
+    fn update(&mut self, _ctx: &WidgetContext) {
+        self.timeline.lock().unwrap().update();
+    }
+
+    fn on_unmount(&mut self, _ctx: &WidgetContext) {
+        self.timeline.lock().unwrap().pause();
+    }
+
+    fn children(&self) -> Vec<&(dyn Widget + '_)> {
+        vec![self.child.as_ref()]
+    }
+
+    fn children_mut(&mut self) -> Vec<&mut (dyn Widget + '_)> {
+        vec![self.child.as_mut()]
+    }
+
+    fn clone_widget(&self) -> Box<dyn Widget> {
+        Box::new(TimelinePlayer {
+            id: generate_id(),
+            timeline: Arc::clone(&self.timeline),
+            child: self.child.clone_widget(),
+        })
+    }
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
@@ -61,7 +133,6 @@ async fn main() -> anyhow::Result<()> {
     let parallel = Parallel::new(vec![Box::new(color_anim), Box::new(width_anim)]);
 
     timeline.add(parallel);
-    timeline.play();
 
     ApplicationBuilder::new()
         .title("Animated Showcase")
@@ -72,7 +143,10 @@ async fn main() -> anyhow::Result<()> {
                     Container::new()
                         .width(300.0)
                         .background(Color::BLACK)
-                        .child(Text::new("Animation Placeholder")),
+                        .child(TimelinePlayer::new(
+                            timeline,
+                            Text::new("").bind(box_width.map(|width| format!("Width {width:.0}"))),
+                        )),
                 )),
             ),
         ));
@@ -0,0 +1,47 @@
+//! Updating the UI from a background thread
+//!
+//! A worker thread counts the seconds since start and posts each new label
+//! to the UI thread with `EventLoopProxy::send_action`. The action runs on
+//! the UI thread and the window redraws right after it, without any input.
+
+use std::time::Duration;
+use strato_core::{state::Signal, types::Color};
+use strato_platform::{ApplicationBuilder, WindowBuilder};
+use strato_widgets::prelude::*;
+
+fn main() {
+    strato_core::init().expect("Failed to initialize StratoUI core");
+    strato_widgets::init().expect("Failed to initialize StratoUI widgets");
+    strato_platform::init().expect("Failed to initialize StratoUI platform");
+
+    let status = Signal::new(String::from("Waiting for the worker..."));
+
+    let mut app = ApplicationBuilder::new()
+        .title("Background Worker")
+        .window(WindowBuilder::new().with_size(420.0, 200.0))
+        .build();
+
+    let proxy = app.create_proxy();
+    let label = status.clone();
+    std::thread::spawn(move || {
+        for seconds in 1.. {
+            std::thread::sleep(Duration::from_secs(1));
+            let text = format!("Worker running for {} s", seconds);
+            let label = label.clone();
+            if proxy
+                .send_action(Box::new(move || label.set(text)))
+                .is_err()
+            {
+                // The window was closed
+                break;
+            }
+        }
+    });
+
+    app.set_root(Box::new(
+        Container::new()
+            .padding(30.0)
+            .child(Text::new("").bind(status).size(20.0).color(Color::WHITE)),
+    ));
+    app.run();
+}
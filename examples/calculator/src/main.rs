@@ -1,4 +1,3 @@
-use std::any::Any;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use strato_core::event::{Event, EventResult, MouseEvent};
@@ -11,6 +10,9 @@ use strato_platform::{
 };
 use strato_sdk::prelude::*;
 use strato_widgets::animation::{AnimationController, Curve};
+use strato_widgets::widget_prelude::{
+    clone_id, generate_id, hit, register_bounds, Constraints, Layout, RenderBatch, Size,
+};
 use strato_widgets::{text::TextAlign, Flex, InspectorOverlay};
 
 #[derive(Clone, Debug)]
@@ -378,7 +380,6 @@ struct AnimatedButton {
     state: Arc<Mutex<CalculatorState>>,
     anim_controller: AnimationController,
     is_pressed: bool,
-    bounds: Arc<Mutex<Rect>>,
 }
 
 impl AnimatedButton {
@@ -387,13 +388,12 @@ impl AnimatedButton {
             AnimationController::new(Duration::from_millis(100)).with_curve(Curve::EaseOut);
 
         Self {
-            id: strato_widgets::widget::generate_id(),
+            id: generate_id(),
             text: text.to_string(),
             button_type,
             state,
             anim_controller: controller,
             is_pressed: false,
-            bounds: Arc::new(Mutex::new(Rect::new(0.0, 0.0, 0.0, 0.0))),
         }
     }
 }
@@ -403,32 +403,26 @@ impl Widget for AnimatedButton {
         self.id
     }
 
-    fn layout(
-        &mut self,
-        constraints: strato_core::layout::Constraints,
-    ) -> strato_core::layout::Size {
+    fn layout(&mut self, constraints: Constraints) -> Size {
         // Fill available space
-        strato_core::layout::Size::new(
+        Size::new(
             constraints.max_width,
             // 70.0 is roughly the height we want, but let's be flexible
             constraints.max_height.min(70.0).max(50.0),
         )
     }
 
-    fn render(
-        &self,
-        batch: &mut strato_renderer::batch::RenderBatch,
-        layout: strato_core::layout::Layout,
-    ) {
-        // Update bounds for hit testing in event handling
-        if let Ok(mut bounds) = self.bounds.lock() {
-            *bounds = Rect::new(
+    fn render(&self, batch: &mut RenderBatch, layout: Layout) {
+        // Register bounds for hit testing in event handling
+        register_bounds(
+            self.id,
+            Rect::new(
                 layout.position.x,
                 layout.position.y,
                 layout.size.width,
                 layout.size.height,
-            );
-        }
+            ),
+        );
 
         let bg_color = match self.button_type {
             ButtonType::Operation(_) | ButtonType::Equals => Color::rgb(1.0, 0.62, 0.04), // Orange (#FF9F0A)
@@ -462,31 +456,36 @@ impl Widget for AnimatedButton {
         let radius = layout.size.height.min(layout.size.width) / 2.0;
 
         // Apply scale from center
-        let center = layout.size.to_vec2() / 2.0;
-        let transform =
-            Transform::translate(layout.position.x + center.x, layout.position.y + center.y)
-                .combine(&Transform::scale(scale, scale))
-                .combine(&Transform::translate(-center.x, -center.y));
+        let center = layout.center();
+        let half = layout.size / 2.0;
+        let transform = Transform::translate(center.x, center.y)
+            .combine(&Transform::scale(scale, scale))
+            .combine(&Transform::translate(-half.width, -half.height));
 
         if (layout.size.width - layout.size.height).abs() < 1.0 {
             // Perfect circle (Square aspect ratio)
-            let center_pt = (
-                layout.position.x + layout.size.width / 2.0,
-                layout.position.y + layout.size.height / 2.0,
-            );
-            batch.add_circle(center_pt, radius, bg_color, 32, transform);
+            batch.add_circle((center.x, center.y), radius, bg_color, 32, transform);
         } else {
             // Pill shape (Width > Height, e.g., '0' button)
             // Left circle
-            let left_center = (layout.position.x + radius, layout.position.y + radius);
-            batch.add_circle(left_center, radius, bg_color, 32, transform);
+            let left_center = layout.origin() + Point::new(radius, radius);
+            batch.add_circle(
+                (left_center.x, left_center.y),
+                radius,
+                bg_color,
+                32,
+                transform,
+            );
 
             // Right circle
-            let right_center = (
-                layout.position.x + layout.size.width - radius,
-                layout.position.y + radius,
+            let right_center = left_center + Point::new(layout.size.width - 2.0 * radius, 0.0);
+            batch.add_circle(
+                (right_center.x, right_center.y),
+                radius,
+                bg_color,
+                32,
+                transform,
             );
-            batch.add_circle(right_center, radius, bg_color, 32, transform);
 
             // Middle rect
             let rect = Rect::new(
@@ -516,10 +515,9 @@ impl Widget for AnimatedButton {
     fn handle_event(&mut self, event: &Event) -> EventResult {
         match event {
             Event::MouseDown(MouseEvent { position, .. }) => {
-                let bounds = *self.bounds.lock().unwrap();
                 let point = Point::new(position.x, position.y);
 
-                if bounds.contains(point) {
+                if hit(self.id, point) {
                     self.is_pressed = true;
                     return EventResult::Handled;
                 }
@@ -531,10 +529,9 @@ impl Widget for AnimatedButton {
                     self.anim_controller.start();
 
                     // Check if still within bounds to trigger action (standard button behavior)
-                    let bounds = *self.bounds.lock().unwrap();
                     let point = Point::new(position.x, position.y);
 
-                    if bounds.contains(point) {
+                    if hit(self.id, point) {
                         // Perform action
                         handle_button_click(&self.button_type, self.state.clone());
                     }
@@ -546,23 +543,14 @@ impl Widget for AnimatedButton {
         EventResult::Ignored
     }
 
-    fn as_any(&self) -> &dyn Any {
-        self
-    }
-
-    fn as_any_mut(&mut self) -> &mut dyn Any {
-        self
-    }
-
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Self {
-            id: strato_widgets::widget::generate_id(),
+            id: clone_id(self.id),
             text: self.text.clone(),
             button_type: self.button_type.clone(),
             state: self.state.clone(),
             anim_controller: self.anim_controller.clone(),
             is_pressed: self.is_pressed,
-            bounds: self.bounds.clone(),
         })
     }
 }
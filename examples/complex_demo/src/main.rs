@@ -1,11 +1,7 @@
 use strato_core::inspector::{inspector, InspectorConfig};
-use strato_core::types::Color;
 use strato_platform::{application::Application, window::WindowBuilder};
-use strato_widgets::{
-    layout::{CrossAxisAlignment, MainAxisAlignment},
-    text::{FontWeight, TextAlign},
-    Button, ButtonStyle, Column, Container, InspectorOverlay, Row, Text, TextStyle, Widget,
-};
+use strato_widgets::prelude::*;
+use strato_widgets::InspectorOverlay;
 
 fn main() -> anyhow::Result<()> {
     // Create application
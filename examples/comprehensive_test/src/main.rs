@@ -1,12 +1,7 @@
 use strato_core::inspector::{inspector, InspectorConfig};
-use strato_core::types::Color;
 use strato_platform::{application::Application, window::WindowBuilder};
-use strato_widgets::{
-    input::{InputType, TextInput},
-    layout::{CrossAxisAlignment, MainAxisAlignment},
-    text::FontWeight,
-    Button, ButtonStyle, Column, Container, Dropdown, Flex, InspectorOverlay, Row, Text, Widget,
-};
+use strato_widgets::prelude::*;
+use strato_widgets::{input::InputType, Dropdown, InspectorOverlay};
 use tracing::{info, warn};
 use tracing_subscriber::prelude::*;
 
@@ -43,6 +38,7 @@ fn main() -> anyhow::Result<()> {
                 .with_ansi(false),
         )
         .with(tracing_subscriber::fmt::layer().with_writer(std::io::stdout))
+        .with(strato_core::logging::LogBufferLayer::new())
         .init();
 
     info!("Starting Comprehensive Test Example - Revolutionized UI");
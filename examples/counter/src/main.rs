@@ -1,35 +1,79 @@
 //! Counter example demonstrating state management in StratoUI
 
-use parking_lot::RwLock;
+use std::any::Any;
 use std::sync::Arc;
+use strato_core::event::{Event, EventResult};
 use strato_core::inspector::{inspector, InspectorConfig};
+use strato_core::persistence::{PersistentSignal, StateStore};
+use strato_core::plugin::{PluginContext, PluginMetadata};
 use strato_core::{error::Result, types::Color};
-use strato_platform::{ApplicationBuilder, WindowBuilder};
+use strato_platform::{ApplicationBuilder, Plugin, WindowBuilder};
 use strato_widgets::prelude::*;
 use strato_widgets::ButtonStyle;
 use strato_widgets::InspectorOverlay;
 
 struct CounterApp {
-    count: Arc<RwLock<i32>>,
+    // Remembered across runs
+    count: PersistentSignal<i32>,
 }
 
 impl CounterApp {
     fn new() -> Self {
+        let store = StateStore::for_app("strato-counter");
         Self {
-            count: Arc::new(RwLock::new(0)),
+            count: PersistentSignal::new(&store, "count", 0),
         }
     }
 
     fn increment(&self) {
-        *self.count.write() += 1;
+        self.count.update(|count| *count += 1);
     }
 
     fn decrement(&self) {
-        *self.count.write() -= 1;
+        self.count.update(|count| *count -= 1);
     }
 
     fn reset(&self) {
-        *self.count.write() = 0;
+        self.count.set(0);
+    }
+}
+
+/// Logs key presses without the UI having to know about it
+struct KeyLogPlugin {
+    metadata: PluginMetadata,
+}
+
+impl KeyLogPlugin {
+    fn new() -> Self {
+        Self {
+            metadata: strato_core::plugin_metadata! {
+                name: "key-log",
+                version: "0.1.0",
+                description: "Prints every key pressed",
+                author: "StratoUI",
+            },
+        }
+    }
+}
+
+impl Plugin for KeyLogPlugin {
+    fn metadata(&self) -> &PluginMetadata {
+        &self.metadata
+    }
+
+    fn handle_event(&mut self, event: &Event, _context: &mut PluginContext) -> EventResult {
+        if let Event::KeyDown(key) = event {
+            println!("Key pressed: {:?}", key.key_code);
+        }
+        EventResult::Ignored
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
     }
 }
 
@@ -51,6 +95,7 @@ fn main() -> Result<()> {
     // Create and run the application
     ApplicationBuilder::new()
         .title("Counter Example")
+        .with_plugin(Box::new(KeyLogPlugin::new()))
         .window(
             WindowBuilder::new()
                 .with_size(350.0, 250.0)
@@ -86,7 +131,7 @@ fn build_ui(app: Arc<CounterApp>) -> impl Widget {
                             .background(Color::rgba(0.0, 0.0, 0.0, 0.05))
                             .border_radius(8.0)
                             .child(
-                                Text::new(format!("{}", *app.count.read()))
+                                Text::new(format!("{}", app.count.get()))
                                     .size(48.0)
                                     .color(Color::BLACK),
                             ),
@@ -102,7 +147,7 @@ fn build_ui(app: Arc<CounterApp>) -> impl Widget {
                                         .size(50.0, 40.0)
                                         .on_click(move || {
                                             app_dec.decrement();
-                                            println!("Count: {}", *app_dec.count.read());
+                                            println!("Count: {}", app_dec.count.get());
                                         }),
                                 ),
                                 Box::new(Button::new("Reset").style(ButtonStyle::text()).on_click(
@@ -117,7 +162,7 @@ fn build_ui(app: Arc<CounterApp>) -> impl Widget {
                                         .size(50.0, 40.0)
                                         .on_click(move || {
                                             app_inc.increment();
-                                            println!("Count: {}", *app_inc.count.read());
+                                            println!("Count: {}", app_inc.count.get());
                                         }),
                                 ),
                             ]),
@@ -1,8 +1,6 @@
 //! Hello World example for StratoSDK showing state management and modern UI
-use std::sync::{Arc, Mutex};
 use strato_sdk::prelude::*;
 use strato_sdk::strato_core::inspector::{inspector, InspectorConfig};
-use strato_sdk::strato_core::state::Signal;
 use strato_sdk::strato_core::types::Color;
 use strato_sdk::strato_platform::{
     init::{InitBuilder, InitConfig},
@@ -16,30 +14,34 @@ use strato_sdk::strato_widgets::{
 
 #[derive(Clone, Debug)]
 struct HelloWorldState {
-    counter: Signal<i32>,
-    message: Signal<String>,
+    counter: i32,
+    message: String,
 }
 
 impl Default for HelloWorldState {
     fn default() -> Self {
         Self {
-            counter: Signal::new(0),
-            message: Signal::new("Welcome to StratoSDK!".to_string()),
+            counter: 0,
+            message: "Welcome to StratoSDK!".to_string(),
         }
     }
 }
 
-impl HelloWorldState {
-    fn increment(&mut self) {
-        self.counter.update(|c| *c += 1);
-        let count = self.counter.get();
-        if count == 1 {
-            self.message.set("First click! Keep going!".to_string());
-        } else if count == 5 {
-            self.message
-                .set("You're getting the hang of it!".to_string());
-        } else if count == 10 {
-            self.message.set("Double digits! 🚀".to_string());
+#[derive(Clone)]
+enum Msg {
+    Increment,
+}
+
+fn update(state: &mut HelloWorldState, msg: Msg) {
+    match msg {
+        Msg::Increment => {
+            state.counter += 1;
+            match state.counter {
+                1 => state.message = "First click! Keep going!".to_string(),
+                5 => state.message = "You're getting the hang of it!".to_string(),
+                10 => state.message = "Double digits! 🚀".to_string(),
+                _ => {}
+            }
         }
     }
 }
@@ -64,12 +66,14 @@ fn main() -> anyhow::Result<()> {
     ApplicationBuilder::new()
         .title("Hello StratoSDK")
         .window(WindowBuilder::new().with_size(500.0, 400.0).resizable(true))
-        .run(InspectorOverlay::new(build_ui()))
+        .run(InspectorOverlay::new(StateView::new(
+            HelloWorldState::default(),
+            build_ui,
+            update,
+        )))
 }
 
-fn build_ui() -> impl Widget {
-    let state = Arc::new(Mutex::new(HelloWorldState::default()));
-
+fn build_ui(state: &HelloWorldState, dispatch: &Dispatch<Msg>) -> impl Widget {
     // Main Window Background
     Container::new()
         .background(Color::rgb(0.05, 0.05, 0.05)) // Deep dark background
@@ -78,16 +82,11 @@ fn build_ui() -> impl Widget {
             Column::new()
                 .main_axis_alignment(MainAxisAlignment::Center)
                 .cross_axis_alignment(CrossAxisAlignment::Center)
-                .children(vec![Box::new(create_interaction_card(state))]),
+                .children(vec![Box::new(create_interaction_card(state, dispatch))]),
         )
 }
 
-fn create_interaction_card(state: Arc<Mutex<HelloWorldState>>) -> impl Widget {
-    let (counter_signal, message_signal) = {
-        let state = state.lock().unwrap();
-        (state.counter.clone(), state.message.clone())
-    };
-
+fn create_interaction_card(state: &HelloWorldState, dispatch: &Dispatch<Msg>) -> impl Widget {
     // Card Container
     Container::new()
         .background(Color::rgb(0.12, 0.12, 0.12)) // Slightly lighter card
@@ -117,8 +116,7 @@ fn create_interaction_card(state: Arc<Mutex<HelloWorldState>>) -> impl Widget {
                     ),
                     // Dynamic Message
                     Box::new(
-                        Text::new("")
-                            .bind(message_signal)
+                        Text::new(state.message.clone())
                             .size(16.0)
                             .color(Color::rgb(0.7, 0.7, 0.7))
                             .align(TextAlign::Center),
@@ -127,18 +125,15 @@ fn create_interaction_card(state: Arc<Mutex<HelloWorldState>>) -> impl Widget {
                     Box::new(Container::new().height(10.0)),
                     // Counter Display
                     Box::new(
-                        Text::new("")
-                            .bind(counter_signal.map(|c| format!("Clicks: {}", c)))
+                        Text::new(format!("Clicks: {}", state.counter))
                             .size(48.0)
                             .color(Color::rgb(0.25, 0.8, 0.4)) // Green accent
                             .align(TextAlign::Center),
                     ),
                     // Interactive Button
                     Box::new(
-                        Button::new("Increment Counter").on_click(move || {
-                            let mut state = state.lock().unwrap();
-                            state.increment();
-                        }), // .style(...) // if we had style API on button directly
+                        Button::new("Increment Counter")
+                            .on_click(dispatch.callback(Msg::Increment)), // .style(...) // if we had style API on button directly
                     ),
                 ]),
         )
@@ -3,7 +3,7 @@ use strato_core::types::Color;
 use strato_macros::view;
 use strato_platform::{ApplicationBuilder, WindowBuilder};
 use strato_widgets::prelude::*;
-use strato_widgets::InspectorOverlay;
+use strato_widgets::{InspectorOverlay, IntoWidget};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -44,11 +44,8 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    // Create legacy registry
-    let registry = strato_widgets::registry::create_default_registry();
-
-    // Build the widget tree using the registry
-    let root_widget = registry.build(root);
+    // Build the widget tree using the built-in widget registry
+    let root_widget = root.into_widget();
 
     builder.run(InspectorOverlay::new(root_widget));
 }
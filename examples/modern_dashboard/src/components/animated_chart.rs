@@ -107,7 +107,7 @@ impl Widget for AnimatedChart {
 
     fn clone_widget(&self) -> Box<dyn Widget> {
         Box::new(Self {
-            id: strato_widgets::widget::generate_id(),
+            id: strato_widgets::widget::clone_id(self.id),
             data: self.data.clone(),
             bar_color: self.bar_color,
             anim_controller: self.anim_controller.clone(),
@@ -67,6 +67,10 @@ impl Sidebar {
                                         Box::new(Text::new("Pro Plan").font_weight(FontWeight::Bold).color(theme.text_primary)) as Box<dyn Widget>,
                                         Box::new(Text::new("Expires in 12 days").font_size(12.0).color(theme.text_secondary)) as Box<dyn Widget>,
                                         Box::new(Button::new("Upgrade").primary()) as Box<dyn Widget>,
+                                        Box::new(Button::new("Documentation").outline().on_click(|| {
+                                            // Failures are logged by open_url
+                                            let _ = strato_platform::open_url("https://stratosdk.dev");
+                                        })) as Box<dyn Widget>,
                                     ])
                             )
                         ) as Box<dyn Widget>,
@@ -1,5 +1,5 @@
 use strato_widgets::{
-    Widget, Column, Container, Text, Flex,
+    Widget, Column, Container, Text, Flex, Chart, Series,
     text::FontWeight,
 };
 use strato_core::types::Color;
@@ -10,6 +10,7 @@ pub struct StatsCard {
     value: String,
     trend: String,
     is_positive: bool,
+    history: Vec<f32>,
     theme: AppTheme,
 }
 
@@ -20,38 +21,47 @@ impl StatsCard {
             value: value.to_string(),
             trend: trend.to_string(),
             is_positive,
+            history: Vec::new(),
             theme: AppTheme::dark(),
         }
     }
 
+    /// Recent values, drawn as a sparkline under the trend
+    pub fn history(mut self, history: Vec<f32>) -> Self {
+        self.history = history;
+        self
+    }
+
     pub fn build(self) -> impl Widget {
         let theme = self.theme;
         let trend_color = if self.is_positive { theme.success } else { theme.error };
 
+        let mut rows: Vec<Box<dyn Widget>> = vec![
+            Box::new(Text::new(&self.title)
+                .color(theme.text_secondary)
+                .font_size(14.0)) as Box<dyn Widget>,
+
+            Box::new(Text::new(&self.value)
+                .color(theme.text_primary)
+                .font_size(28.0)
+                .font_weight(FontWeight::Bold)) as Box<dyn Widget>,
+
+            Box::new(Text::new(&self.trend)
+                .color(trend_color)
+                .font_size(12.0)) as Box<dyn Widget>,
+        ];
+        if !self.history.is_empty() {
+            let trend = Series::line("", self.history).color(trend_color);
+            rows.push(Box::new(Chart::sparkline(trend).size(160.0, 32.0)));
+        }
+
         // Wrap in Flex(1.0) so it expands in a Row
         Flex::new(
             Box::new(Container::new()
                 .background(theme.bg_secondary)
                 .border_radius(BORDER_RADIUS_MD)
                 .padding(SPACING_MD)
-                .child(
-                    Column::new()
-                        .spacing(SPACING_SM)
-                        .children(vec![
-                            Box::new(Text::new(&self.title)
-                                .color(theme.text_secondary)
-                                .font_size(14.0)) as Box<dyn Widget>,
-
-                            Box::new(Text::new(&self.value)
-                                .color(theme.text_primary)
-                                .font_size(28.0)
-                                .font_weight(FontWeight::Bold)) as Box<dyn Widget>,
-
-                            Box::new(Text::new(&self.trend)
-                                .color(trend_color)
-                                .font_size(12.0)) as Box<dyn Widget>,
-                        ])
-                )
+                .child(Column::new().spacing(SPACING_SM).children(rows))
             )
         ).flex(1.0)
     }
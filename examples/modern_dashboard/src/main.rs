@@ -29,6 +29,8 @@ fn main() {
                 .transparent(true) // Enable glassmorphism support
                 .decorations(true), // Restore native window controls
         )
+        // Keep the clear color from flashing through while resizing
+        .resize_buffering(true)
         .run(root_widget);
 }
 
@@ -210,21 +212,24 @@ fn sidebar_item(label: &str, icon: &str, active: bool) -> Box<dyn Widget> {
     let text_color = if active { Color::WHITE } else { col_subtext() };
     let label_clone = label.to_string();
 
-    Box::new(
-        Container::new()
-            .background(bg_color)
-            .margin(0.0)
-            .padding(10.0)
-            .border_radius(6.0) // Rounded active item
-            .width(240.0) // Slight inset from full width
-            .child(Row::new().spacing(12.0).children(vec![
-                Box::new(Text::new(icon).size(14.0)), // Smaller icons
-                Box::new(Text::new(label).color(text_color).size(14.0)),
-            ]))
-            .on_click(move || {
-                println!("Clicked sidebar item: {}", label_clone);
-            }),
-    )
+    Container::new()
+        .background(bg_color)
+        .margin(0.0)
+        .padding(10.0)
+        .border_radius(6.0) // Rounded active item
+        .width(240.0) // Slight inset from full width
+        .on_click(move || {
+            println!("Clicked sidebar item: {}", label_clone);
+        })
+        .builder()
+        .child(
+            Row::new()
+                .spacing(12.0)
+                .builder()
+                .child(Text::new(icon).size(14.0)) // Smaller icons
+                .child(Text::new(label).color(text_color).size(14.0)),
+        )
+        .boxed()
 }
 
 fn card_item(
@@ -234,58 +239,69 @@ fn card_item(
     stats: &str,
     avatar_url: &str,
 ) -> Box<dyn Widget> {
-    Box::new(
-        Container::new()
-            .background(col_card_bg())
-            .padding(16.0)
-            .border_radius(10.0) // Smooth card rounding
-            .width(600.0) // Fixed card width for specific look
-            .child(Column::new().spacing(12.0).children(vec![
-                // Header Row
-                Box::new(Row::new()
-                    .spacing(12.0)
-                    .children(vec![
-                        // Avatar
-                        Box::new(Container::new()
-                            .width(44.0).height(44.0).border_radius(4.0)
-                            .child(Image::from_url(avatar_url).border_radius(4.0))
-                        ),
-                        // Name & Handle
-                        Box::new(Column::new()
-                            .spacing(2.0)
-                            .children(vec![
-                                Box::new(Text::new(name).color(col_text()).size(15.0)),
-                                Box::new(Text::new(handle).color(col_subtext()).size(13.0))
-                            ])
-                        ),
-                        Box::new(Container::new().width(180.0).child(Text::new(""))), // Flex Spacer
-                        // Button
-                        Box::new(Container::new()
-                            .background(col_accent())
-                            .border_radius(14.0) // Pill shape
-                            .padding(6.0)
-                            .width(60.0)
-                            .child(Container::new()
-                                 // Centering hack (via padding)
-                                 .padding(0.0).margin(0.0)
-                                 .child(Text::new("Open").color(Color::WHITE).size(12.0))
-                            )
-                            .on_click(|| {
-                                println!("Clicked Open button");
-                            })
-                        )
-                    ])
-                ),
+    let header = Row::new()
+        .spacing(12.0)
+        .builder()
+        // Avatar
+        .child(
+            Container::new()
+                .width(44.0)
+                .height(44.0)
+                .border_radius(4.0)
+                .child(Image::from_url(avatar_url).border_radius(4.0)),
+        )
+        // Name & Handle
+        .child(
+            Column::new()
+                .spacing(2.0)
+                .builder()
+                .child(Text::new(name).color(col_text()).size(15.0))
+                .child(Text::new(handle).color(col_subtext()).size(13.0)),
+        )
+        .child(Container::new().width(180.0).child(Text::new(""))) // Flex Spacer
+        // Button
+        .child(
+            Container::new()
+                .background(col_accent())
+                .border_radius(14.0) // Pill shape
+                .padding(6.0)
+                .width(60.0)
+                .child(
+                    Container::new()
+                        // Centering hack (via padding)
+                        .padding(0.0)
+                        .margin(0.0)
+                        .child(Text::new("Open").color(Color::WHITE).size(12.0)),
+                )
+                .on_click(|| {
+                    println!("Clicked Open button");
+                }),
+        );
+
+    Container::new()
+        .background(col_card_bg())
+        .padding(16.0)
+        .border_radius(10.0) // Smooth card rounding
+        .width(600.0) // Fixed card width for specific look
+        .builder()
+        .child(
+            Column::new()
+                .spacing(12.0)
+                .builder()
+                .child(header)
                 // Content Body
-                Box::new(Container::new()
-                     .padding(0.0)
-                     .child(Text::new(content).color(col_subtext()).size(14.0))
-                ),
-                // Footer Stats
-                Box::new(Container::new()
-                    .padding(0.0)
-                    .child(Text::new(stats).color(col_header()).size(12.0))
+                .child(
+                    Container::new()
+                        .padding(0.0)
+                        .child(Text::new(content).color(col_subtext()).size(14.0)),
                 )
-            ])),
-    )
+                // Footer Stats, left out for accounts without any
+                .child_if(
+                    !stats.is_empty(),
+                    Container::new()
+                        .padding(0.0)
+                        .child(Text::new(stats).color(col_header()).size(12.0)),
+                ),
+        )
+        .boxed()
 }
@@ -37,8 +37,12 @@ impl DashboardView {
                     .row_gap(SPACING_MD)
                     .col_gap(SPACING_MD)
                     .children(vec![
-                        Box::new(StatsCard::new("Total Revenue", "$45,231.89", "+20.1%", true).build()),
-                        Box::new(StatsCard::new("Active Users", "2,350", "+15.2%", true).build()),
+                        Box::new(StatsCard::new("Total Revenue", "$45,231.89", "+20.1%", true)
+                            .history(vec![31.0, 34.5, 33.0, 38.2, 41.7, 40.1, 45.2])
+                            .build()),
+                        Box::new(StatsCard::new("Active Users", "2,350", "+15.2%", true)
+                            .history(vec![1840.0, 1910.0, 2050.0, 2010.0, 2180.0, 2290.0, 2350.0])
+                            .build()),
                         Box::new(StatsCard::new("Bounce Rate", "42.3%", "-5.4%", true).build()),
                         Box::new(StatsCard::new("Server Uptime", "99.9%", "+0.1%", true).build()),
                     ])
@@ -0,0 +1,57 @@
+//! Transparent window example checking premultiplied-alpha compositing
+//!
+//! The window has no background. Its left swatch is white at 50% alpha, the
+//! right one an opaque gray of the same brightness (`0.5`, byte 128). Over a
+//! black or dark desktop the two swatches must look identical, with no dark
+//! fringes around the rounded corners; over a light desktop the left swatch
+//! brightens with it while the right one stays put.
+
+use strato_core::{error::Result, types::Color};
+use strato_platform::{ApplicationBuilder, WindowBuilder};
+use strato_widgets::prelude::*;
+
+fn main() -> Result<()> {
+    strato_core::init()?;
+    strato_widgets::init()?;
+    strato_platform::init()
+        .map_err(|e| strato_core::error::StratoError::platform(format!("{:?}", e)))?;
+
+    ApplicationBuilder::new()
+        .title("Transparent Window")
+        .window(
+            WindowBuilder::new()
+                .with_size(420.0, 240.0)
+                .transparent(true)
+                .resizable(false),
+        )
+        .run(build_ui())
+}
+
+fn swatch(label: &str, color: Color) -> Box<dyn Widget> {
+    Box::new(
+        Column::new()
+            .spacing(8.0)
+            .cross_axis_alignment(CrossAxisAlignment::Center)
+            .children(vec![
+                Box::new(
+                    Container::new()
+                        .size(140.0, 140.0)
+                        .border_radius(16.0)
+                        .background(color),
+                ),
+                Box::new(Text::new(label).size(14.0).color(Color::WHITE)),
+            ]),
+    )
+}
+
+fn build_ui() -> impl Widget {
+    Container::new().padding(30.0).child(
+        Row::new()
+            .spacing(40.0)
+            .main_axis_alignment(MainAxisAlignment::Center)
+            .children(vec![
+                swatch("White, 50% alpha", Color::rgba(1.0, 1.0, 1.0, 0.5)),
+                swatch("Gray 0.5, opaque", Color::rgb(0.5, 0.5, 0.5)),
+            ]),
+    )
+}